@@ -50,6 +50,7 @@ variable "database_tags" {
     "q_client_id"                                                                    = "{{ owner_id }}"
     "q_environment_id"                                                               = "{{ environment_id }}"
     "q_project_id"                                                                   = "{{ project_id }}"
+    "q_correlation_id"                                                               = "{{ correlation_id }}"
     {% if resource_expiration_in_seconds > -1 %}
     "ttl"                                                                            = "{{ resource_expiration_in_seconds }}"
     {% endif %}
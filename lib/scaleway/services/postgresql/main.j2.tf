@@ -6,6 +6,7 @@ locals {
     q_client_id = var.q_customer_id
     q_environment_id = var.q_environment_id
     q_project_id = var.q_project_id
+    q_correlation_id = "{{ correlation_id }}"
     database_identifier = var.postgresql_identifier
     {% if resource_expiration_in_seconds > -1 %}ttl = var.resource_expiration_in_seconds{% endif %}
     {% if snapshot is defined and snapshot["snapshot_id"] %}meta_last_restored_from = var.snapshot_identifier{% endif %}
@@ -10,12 +10,15 @@ use sysinfo::{DiskExt, RefreshKind, SystemExt};
 use uuid::Uuid;
 
 use crate::build_platform::dockerfile_utils::extract_dockerfile_args;
-use crate::build_platform::{Build, BuildError, BuildPlatform, Credentials, Kind};
+use crate::build_platform::{
+    is_within_directory, parse_build_args_file, Build, BuildError, BuildPlatform, Credentials, Kind,
+};
 use crate::cmd::command;
 use crate::cmd::command::CommandError::Killed;
 use crate::cmd::command::{CommandKiller, ExecutableCommand, QoveryCommand};
 use crate::cmd::docker::{BuildResult, ContainerImage, DockerError};
-use crate::deployment_report::logger::EnvLogger;
+use crate::deployment_report::logger::{EnvLogger, EnvProgressLogger};
+use crate::errors::redact_values;
 
 use crate::fs::workspace_directory;
 use crate::git;
@@ -173,17 +176,67 @@ impl LocalDocker {
             .map(|(k, v)| (k.as_str(), v.as_str()))
             .collect();
 
-        let exit_status = self.context.docker.build(
-            Path::new(dockerfile_complete_path),
-            Path::new(into_dir_docker_style),
-            &image_to_build,
-            &env_vars,
-            &image_cache,
-            true,
-            &mut |line| logger.send_progress(line),
-            &mut |line| logger.send_progress(line),
-            &CommandKiller::from(build.timeout, is_task_canceled),
-        );
+        // Registries requiring mutual TLS need the client certificate to be presented by the docker daemon
+        // for the whole cache pull / push, it is removed once done
+        let _client_certificate_files = match &build.image.registry_client_certificate {
+            Some(client_certificate) => Some(
+                self.context
+                    .docker
+                    .install_registry_client_certificate(
+                        &build.image.registry_url,
+                        &client_certificate.certificate,
+                        &client_certificate.private_key,
+                    )
+                    .map_err(|err| BuildError::DockerError {
+                        application: build.image.application_id.clone(),
+                        raw_error: err,
+                    })?,
+            ),
+            None => None,
+        };
+        // buildkit pushes from its own builder that doesn't read the daemon certificates,
+        // so the image is loaded into the daemon and pushed from there
+        let push_with_daemon = build.image.registry_client_certificate.is_some();
+
+        // build secrets are only written to disk for the duration of the build, they are deleted once dropped
+        let build_secrets_files = build.write_build_secrets().map_err(|err| BuildError::IoError {
+            application: build.image.application_id.clone(),
+            action_description: "writing build secrets".to_string(),
+            raw_error: err,
+        })?;
+
+        let sensitive_values = build.sensitive_values();
+        let sensitive_values = sensitive_values.iter().map(|value| value.as_str()).collect::<Vec<_>>();
+        let progress_logger = EnvProgressLogger::new(logger);
+        let cmd_killer = CommandKiller::from(build.timeout, is_task_canceled);
+        let exit_status = self
+            .context
+            .docker
+            .build(
+                Path::new(dockerfile_complete_path),
+                Path::new(into_dir_docker_style),
+                &image_to_build,
+                &env_vars,
+                build_secrets_files.files(),
+                &image_cache,
+                !push_with_daemon,
+                &mut |line| progress_logger.info(redact_values(&line, &sensitive_values)),
+                &mut |line| progress_logger.info(redact_values(&line, &sensitive_values)),
+                &cmd_killer,
+            )
+            .and_then(|mut build_result| {
+                if push_with_daemon {
+                    self.context.docker.push(
+                        &image_to_build,
+                        &mut |line| progress_logger.info(redact_values(&line, &sensitive_values)),
+                        &mut |line| progress_logger.info(redact_values(&line, &sensitive_values)),
+                        &cmd_killer,
+                    )?;
+                    build_result.pushed(true);
+                }
+
+                Ok(build_result)
+            });
 
         match exit_status {
             Ok(build_result) => Ok(build_result),
@@ -289,8 +342,8 @@ impl LocalDocker {
             cmd.set_kill_grace_period(Duration::from_secs(0));
             let cmd_killer = CommandKiller::from(build.timeout, is_task_canceled);
             exit_status = cmd.exec_with_abort(
-                &mut |line| logger.send_progress(line),
-                &mut |line| logger.send_progress(line),
+                &mut |line| logger.send_progress(build.redact_sensitive_build_args(&line)),
+                &mut |line| logger.send_progress(build.redact_sensitive_build_args(&line)),
                 &cmd_killer,
             );
 
@@ -402,12 +455,30 @@ impl BuildPlatform for LocalDocker {
         }
 
         // Do the real git clone
-        if let Err(clone_error) = git::clone_at_commit(
-            &build.git_repository.url,
-            &build.git_repository.commit_id,
-            &repository_root_path,
-            &get_credentials,
-        ) {
+        let clone_result = match build.git_repository.clone_depth {
+            Some(depth) => git::shallow_clone_at_commit(
+                &build.git_repository.url,
+                &build.git_repository.commit_id,
+                &repository_root_path,
+                &get_credentials,
+                &git::ShallowCloneOptions {
+                    depth,
+                    single_branch: build.git_repository.single_branch,
+                    basic_auth: build
+                        .git_repository
+                        .credentials
+                        .as_ref()
+                        .map(|creds| (creds.login.as_str(), creds.password.as_str())),
+                },
+            ),
+            None => git::clone_at_commit(
+                &build.git_repository.url,
+                &build.git_repository.commit_id,
+                &repository_root_path,
+                &get_credentials,
+            ),
+        };
+        if let Err(clone_error) = clone_result {
             return Err(BuildError::GitError {
                 application: build.image.application_id.clone(),
                 raw_error: clone_error,
@@ -453,6 +524,37 @@ impl BuildPlatform for LocalDocker {
             });
         }
 
+        // Load build args from file if any, explicitly set env vars take precedence
+        if let Some(build_args_file) = &build.build_args_file {
+            let build_args_file_path = repository_root_path.join(build_args_file);
+
+            // Safety check to ensure we can't read a file outside of the repository
+            if build_args_file_path.exists() && !is_within_directory(&build_args_file_path, &repository_root_path) {
+                return Err(BuildError::InvalidConfig {
+                    application: app_id,
+                    raw_error_message: format!(
+                        "Specified build args file {:?} tries to access a file outside of his git repository",
+                        build_args_file,
+                    ),
+                });
+            }
+
+            let content = fs::read_to_string(&build_args_file_path).map_err(|err| BuildError::IoError {
+                application: app_id.clone(),
+                action_description: format!("reading build args file {:?}", build_args_file),
+                raw_error: err,
+            })?;
+            let build_args = parse_build_args_file(&content).map_err(|err| BuildError::InvalidConfig {
+                application: app_id.clone(),
+                raw_error_message: format!("Cannot parse build args file {:?}: {}", build_args_file, err),
+            })?;
+
+            for (key, value) in build_args {
+                build.environment_variables.entry(key).or_insert(value);
+            }
+            build.compute_image_tag();
+        }
+
         // now we have to decide if we use buildpack or docker to build our application
         // If no Dockerfile specified, we should use BuildPacks
         if let Some(dockerfile_path) = &build.git_repository.dockerfile_path {
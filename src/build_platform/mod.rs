@@ -1,17 +1,20 @@
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::cloud_provider::kubernetes::Kind as KubernetesKind;
 use crate::cmd::command::CommandError;
 use crate::cmd::docker::{BuildResult, DockerError};
 use crate::deployment_report::logger::EnvLogger;
-use crate::errors::EngineError;
+use crate::errors::{redact_values, EngineError};
 use crate::events::EventDetails;
+use crate::io_models::container::ClientCertificate;
 
 use crate::utilities::compute_image_tag;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::hash::Hash;
-use std::path::PathBuf;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use url::Url;
 use uuid::Uuid;
@@ -56,10 +59,15 @@ pub enum BuildError {
     },
 }
 
-pub fn to_engine_error(event_details: EventDetails, err: BuildError, user_message: String) -> EngineError {
+pub fn to_engine_error(
+    event_details: EventDetails,
+    err: BuildError,
+    user_message: String,
+    sensitive_values: &[&str],
+) -> EngineError {
     match err {
         BuildError::Aborted { .. } => EngineError::new_task_cancellation_requested(event_details),
-        _ => EngineError::new_build_error(event_details, err, user_message),
+        _ => EngineError::new_build_error(event_details, err, user_message, sensitive_values),
     }
 }
 
@@ -82,6 +90,43 @@ pub struct Build {
     pub environment_variables: BTreeMap<String, String>,
     pub disable_cache: bool,
     pub timeout: Duration,
+    // optional key=value file holding build args, to avoid command line length limits
+    pub build_args_file: Option<PathBuf>,
+    // build args whose values should never be displayed
+    pub sensitive_build_args: BTreeSet<String>,
+    // secrets mounted by BuildKit, their paths and values should never be displayed
+    pub build_secrets: Vec<BuildSecret>,
+}
+
+/// Secret mounted by BuildKit, its value is only written to `path` for the duration of the docker build.
+pub struct BuildSecret {
+    pub id: String,
+    pub value: Vec<u8>,
+    pub path: PathBuf,
+}
+
+/// Build secrets files written in clear for a docker build, they are deleted once dropped.
+pub struct BuildSecretsFiles {
+    files: Vec<(String, PathBuf)>,
+}
+
+impl BuildSecretsFiles {
+    /// Files as (id, source file), as expected by `docker build --secret`.
+    pub fn files(&self) -> &[(String, PathBuf)] {
+        &self.files
+    }
+}
+
+impl Drop for BuildSecretsFiles {
+    fn drop(&mut self) {
+        for (id, src) in &self.files {
+            match std::fs::remove_file(src) {
+                Ok(_) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => error!("Cannot delete build secret {} source file: {}", id, err),
+            }
+        }
+    }
 }
 
 impl Build {
@@ -93,6 +138,93 @@ impl Build {
             &self.git_repository.commit_id,
         );
     }
+
+    /// Returns values of build args flagged as sensitive.
+    pub fn sensitive_build_args_values(&self) -> Vec<&str> {
+        self.sensitive_build_args
+            .iter()
+            .filter_map(|key| self.environment_variables.get(key))
+            .filter(|value| !value.is_empty())
+            .map(|value| value.as_str())
+            .collect()
+    }
+
+    /// Replaces values of build args flagged as sensitive by a placeholder.
+    pub fn redact_sensitive_build_args(&self, message: &str) -> String {
+        redact_values(message, &self.sensitive_build_args_values())
+    }
+
+    /// Writes the build secrets files, readable by their owner only. Files already written are deleted
+    /// if one of them can't be.
+    pub fn write_build_secrets(&self) -> std::io::Result<BuildSecretsFiles> {
+        let mut build_secrets_files = BuildSecretsFiles {
+            files: Vec::with_capacity(self.build_secrets.len()),
+        };
+        for build_secret in &self.build_secrets {
+            let mut file = std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .mode(0o600)
+                .open(&build_secret.path)?;
+            build_secrets_files
+                .files
+                .push((build_secret.id.clone(), build_secret.path.clone()));
+            file.write_all(&build_secret.value)?;
+        }
+
+        Ok(build_secrets_files)
+    }
+
+    /// Returns every value which should never be displayed: values of build args flagged as sensitive,
+    /// and build secrets source paths, contents and each line of their contents.
+    pub fn sensitive_values(&self) -> Vec<String> {
+        let build_secrets_values = self.build_secrets.iter().flat_map(|build_secret| {
+            let content = String::from_utf8_lossy(&build_secret.value).to_string();
+            let lines = content.lines().map(|line| line.trim().to_string()).collect::<Vec<_>>();
+            std::iter::once(build_secret.path.to_string_lossy().to_string())
+                .chain(std::iter::once(content.trim().to_string()))
+                .chain(lines)
+        });
+
+        self.sensitive_build_args_values()
+            .into_iter()
+            .map(|value| value.to_string())
+            .chain(build_secrets_values)
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+}
+
+/// Returns true if `path`, once `..` and symlinks are resolved, is located under `root`.
+pub fn is_within_directory(path: &Path, root: &Path) -> bool {
+    match (path.canonicalize(), root.canonicalize()) {
+        (Ok(path), Ok(root)) => path.starts_with(root),
+        _ => false,
+    }
+}
+
+/// Parses build args file content, one `KEY=VALUE` per line.
+/// Empty lines and lines starting with `#` are ignored.
+/// Errors only mention line numbers, never line content as it might hold sensitive values.
+pub fn parse_build_args_file(content: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut build_args = BTreeMap::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() && !key.trim().contains(char::is_whitespace) => {
+                build_args.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            _ => return Err(format!("invalid build arg at line {}, expected `KEY=VALUE`", idx + 1)),
+        }
+    }
+
+    Ok(build_args)
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -122,6 +254,10 @@ pub struct GitRepository {
     pub dockerfile_path: Option<PathBuf>,
     pub root_path: PathBuf,
     pub buildpack_language: Option<String>,
+    // None means a full clone, otherwise the number of commits to fetch
+    pub clone_depth: Option<u32>,
+    // only fetch the default branch, used for shallow clones only
+    pub single_branch: bool,
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -136,6 +272,8 @@ pub struct Image {
     pub registry_name: String,
     // registry docker json config: Optional
     pub registry_docker_json_config: Option<String>,
+    // registry client certificate, for registries requiring mutual TLS: Optional
+    pub registry_client_certificate: Option<ClientCertificate>,
     // complete registry URL where the image has been pushed
     pub registry_url: Url,
     pub repository_name: String,
@@ -189,6 +327,7 @@ impl Default for Image {
             commit_id: "".to_string(),
             registry_name: "".to_string(),
             registry_docker_json_config: None,
+            registry_client_certificate: None,
             registry_url: Url::parse("https://default.com").unwrap(),
             repository_name: "".to_string(),
         }
@@ -210,3 +349,258 @@ impl Display for Image {
 pub enum Kind {
     LocalDocker,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::build_platform::{
+        is_within_directory, parse_build_args_file, to_engine_error, Build, BuildError, BuildSecret, GitRepository,
+        Image,
+    };
+    use crate::errors::ErrorMessageVerbosity;
+    use crate::events::{EnvironmentStep, Stage};
+    use crate::test_utils::EventDetailsBuilder;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use tempfile::tempdir;
+    use url::Url;
+
+    #[test]
+    fn test_is_within_directory() {
+        // setup:
+        let root = tempdir().expect("cannot create temp dir");
+        let repository = root.path().join("repository");
+        fs::create_dir_all(repository.join("config")).expect("cannot create dir");
+        fs::write(repository.join("config/build.args"), "A=1").expect("cannot write file");
+        fs::write(root.path().join("secrets.args"), "A=1").expect("cannot write file");
+        std::os::unix::fs::symlink(root.path().join("secrets.args"), repository.join("link.args"))
+            .expect("cannot create symlink");
+
+        let test_cases = vec![
+            ("config/build.args", true),
+            ("./config/../config/build.args", true),
+            ("../secrets.args", false),
+            ("config/../../secrets.args", false),
+            ("link.args", false),
+            ("does_not_exist.args", false),
+        ];
+
+        for (path, expected) in test_cases {
+            // execute & verify:
+            assert_eq!(
+                expected,
+                is_within_directory(&repository.join(path), &repository),
+                "path: {}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_build_args_file() {
+        // setup:
+        let content = r#"
+# database settings
+DB_HOST=my-db.internal
+DB_PASSWORD = my$ecret=pass
+
+EMPTY_VALUE=
+"#;
+
+        // execute:
+        let result = parse_build_args_file(content);
+
+        // verify:
+        assert_eq!(
+            Ok(BTreeMap::from([
+                ("DB_HOST".to_string(), "my-db.internal".to_string()),
+                ("DB_PASSWORD".to_string(), "my$ecret=pass".to_string()),
+                ("EMPTY_VALUE".to_string(), "".to_string()),
+            ])),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_build_args_file_invalid_lines() {
+        // setup:
+        let test_cases = vec![
+            ("VALID=1\nmy-secret-without-key", 2),
+            ("=my-secret-without-key", 1),
+            ("MY KEY=my-secret", 1),
+        ];
+
+        for (content, line) in test_cases {
+            // execute:
+            let result = parse_build_args_file(content);
+
+            // verify:
+            let err = result.expect_err("build args file should be invalid");
+            assert!(err.contains(&format!("line {}", line)), "content: {}", content);
+            assert!(!err.contains("my-secret"), "content: {}", content);
+        }
+    }
+
+    fn test_build() -> Build {
+        Build {
+            git_repository: GitRepository {
+                url: Url::parse("https://my_git_url.com").unwrap(),
+                credentials: None,
+                ssh_keys: vec![],
+                commit_id: "my_commit_id".to_string(),
+                dockerfile_path: Some(PathBuf::from("Dockerfile")),
+                root_path: PathBuf::from("."),
+                buildpack_language: None,
+                clone_depth: None,
+                single_branch: false,
+            },
+            image: Image::default(),
+            environment_variables: BTreeMap::new(),
+            disable_cache: false,
+            timeout: Duration::from_secs(60),
+            build_args_file: None,
+            sensitive_build_args: BTreeSet::new(),
+            build_secrets: vec![],
+        }
+    }
+
+    #[test]
+    fn test_build_error_redacts_sensitive_build_args() {
+        // setup:
+        let build = Build {
+            environment_variables: BTreeMap::from([
+                ("DB_HOST".to_string(), "my-db.internal".to_string()),
+                ("DB_PASSWORD".to_string(), "my-db-password".to_string()),
+            ]),
+            build_args_file: Some(PathBuf::from("build.args")),
+            sensitive_build_args: BTreeSet::from(["DB_PASSWORD".to_string()]),
+            ..test_build()
+        };
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Environment(EnvironmentStep::Build))
+            .build();
+        let raw_error_message = "failed to connect to my-db.internal with password my-db-password".to_string();
+
+        // execute:
+        let err = to_engine_error(
+            event_details,
+            BuildError::InvalidConfig {
+                application: "app".to_string(),
+                raw_error_message: raw_error_message.clone(),
+            },
+            format!("Build failed: {}", raw_error_message),
+            &build.sensitive_build_args_values(),
+        );
+
+        // verify:
+        let full_message = err.message(ErrorMessageVerbosity::FullDetails);
+        assert!(full_message.contains("my-db.internal"));
+        assert!(!full_message.contains("my-db-password"));
+        assert!(!err.user_log_message().contains("my-db-password"));
+        assert_eq!(
+            "connect with password <redacted>",
+            build.redact_sensitive_build_args("connect with password my-db-password")
+        );
+    }
+
+    #[test]
+    fn test_build_error_redacts_build_secrets() {
+        // setup:
+        let secret_path = "/tmp/build-secrets/npm_token";
+        let build = Build {
+            build_secrets: vec![BuildSecret {
+                id: "npm_token".to_string(),
+                value: b"npm_s3cr3t_t0k3n\naws_s3cr3t_k3y\n".to_vec(),
+                path: PathBuf::from(secret_path),
+            }],
+            ..test_build()
+        };
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Environment(EnvironmentStep::Build))
+            .build();
+        let raw_error_message = format!(
+            "docker buildx build --secret id=npm_token,src={} failed: 401 with token npm_s3cr3t_t0k3n and key aws_s3cr3t_k3y",
+            secret_path
+        );
+        let sensitive_values = build.sensitive_values();
+
+        // execute:
+        let err = to_engine_error(
+            event_details,
+            BuildError::InvalidConfig {
+                application: "app".to_string(),
+                raw_error_message: raw_error_message.clone(),
+            },
+            format!("Build failed: {}", raw_error_message),
+            &sensitive_values.iter().map(|value| value.as_str()).collect::<Vec<_>>(),
+        );
+
+        // verify:
+        let full_message = err.message(ErrorMessageVerbosity::FullDetails);
+        assert!(full_message.contains("id=npm_token"));
+        assert!(!full_message.contains("npm_s3cr3t_t0k3n"));
+        assert!(!full_message.contains("aws_s3cr3t_k3y"));
+        assert!(!full_message.contains(secret_path));
+        assert!(!err.user_log_message().contains("npm_s3cr3t_t0k3n"));
+        assert!(!err.user_log_message().contains("aws_s3cr3t_k3y"));
+        assert!(!err.user_log_message().contains(secret_path));
+    }
+
+    #[test]
+    fn test_write_build_secrets_deletes_files_once_dropped() {
+        // setup:
+        let build_secrets_dir = tempdir().expect("cannot create temp dir");
+        let secret_path = build_secrets_dir.path().join("NPM_TOKEN");
+        let build = Build {
+            build_secrets: vec![BuildSecret {
+                id: "NPM_TOKEN".to_string(),
+                value: b"npm_s3cr3t_t0k3n".to_vec(),
+                path: secret_path.clone(),
+            }],
+            ..test_build()
+        };
+
+        // execute:
+        let build_secrets_files = build.write_build_secrets().expect("build secrets should be written");
+
+        // verify:
+        assert_eq!(&[("NPM_TOKEN".to_string(), secret_path.clone())], build_secrets_files.files());
+        assert_eq!("npm_s3cr3t_t0k3n", fs::read_to_string(&secret_path).unwrap());
+        assert_eq!(0o600, fs::metadata(&secret_path).unwrap().permissions().mode() & 0o777);
+
+        drop(build_secrets_files);
+        assert!(!secret_path.exists());
+        assert!(build_secrets_dir.path().exists());
+    }
+
+    #[test]
+    fn test_write_build_secrets_deletes_written_files_on_error() {
+        // setup:
+        let build_secrets_dir = tempdir().expect("cannot create temp dir");
+        let secret_path = build_secrets_dir.path().join("NPM_TOKEN");
+        let build = Build {
+            build_secrets: vec![
+                BuildSecret {
+                    id: "NPM_TOKEN".to_string(),
+                    value: b"npm_s3cr3t_t0k3n".to_vec(),
+                    path: secret_path.clone(),
+                },
+                BuildSecret {
+                    id: "AWS_KEY".to_string(),
+                    value: b"aws_s3cr3t_k3y".to_vec(),
+                    path: build_secrets_dir.path().join("does_not_exist/AWS_KEY"),
+                },
+            ],
+            ..test_build()
+        };
+
+        // execute:
+        let result = build.write_build_secrets();
+
+        // verify:
+        assert!(result.is_err());
+        assert!(!secret_path.exists());
+    }
+}
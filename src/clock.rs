@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Source of time, to be injected where time is read or waited for, so time dependent logic
+/// (deadlines, backoff, expiry) can be tested deterministically with a `MockClock`.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+    fn utc_now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// Clock only moving forward when asked to: sleeping advances it instantly.
+pub struct MockClock {
+    started_at: Instant,
+    utc_started_at: DateTime<Utc>,
+    elapsed: Mutex<Duration>,
+}
+
+impl MockClock {
+    pub fn new(utc_now: DateTime<Utc>) -> Self {
+        MockClock {
+            started_at: Instant::now(),
+            utc_started_at: utc_now,
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.started_at + self.elapsed()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        self.utc_started_at + chrono::Duration::from_std(self.elapsed()).unwrap_or_else(|_| chrono::Duration::zero())
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::{Clock, MockClock};
+    use chrono::Utc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_mock_clock_only_moves_when_advanced() {
+        // setup:
+        let utc_start = Utc::now();
+        let clock = MockClock::new(utc_start);
+        let start = clock.now();
+
+        // execute:
+        clock.sleep(Duration::from_secs(90));
+        clock.advance(Duration::from_secs(30));
+
+        // verify:
+        assert_eq!(Duration::from_secs(120), clock.now() - start);
+        assert_eq!(chrono::Duration::seconds(120), clock.utc_now() - utc_start);
+    }
+}
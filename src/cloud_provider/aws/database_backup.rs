@@ -0,0 +1,141 @@
+use crate::cloud_provider::database_backup::DatabaseBackupClient;
+use crate::cloud_provider::service::DatabaseType;
+use crate::cmd::command::{ExecutableCommand, QoveryCommand};
+use crate::constants::{AWS_ACCESS_KEY_ID, AWS_DEFAULT_REGION, AWS_SECRET_ACCESS_KEY};
+use crate::errors::CommandError;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct DbSnapshot {
+    #[serde(alias = "DBSnapshotArn")]
+    pub db_snapshot_arn: String,
+}
+
+#[derive(Deserialize)]
+struct CreateDbSnapshotResponse {
+    #[serde(alias = "DBSnapshot")]
+    pub db_snapshot: DbSnapshot,
+}
+
+/// Backups of RDS instances, through RDS CreateDBSnapshot.
+pub struct AwsDatabaseBackupClient {
+    access_key_id: String,
+    secret_access_key: String,
+    region: String,
+}
+
+impl AwsDatabaseBackupClient {
+    pub fn new(access_key_id: String, secret_access_key: String, region: String) -> Self {
+        AwsDatabaseBackupClient {
+            access_key_id,
+            secret_access_key,
+            region,
+        }
+    }
+}
+
+impl DatabaseBackupClient for AwsDatabaseBackupClient {
+    fn create_backup(
+        &self,
+        db_type: DatabaseType,
+        database_identifier: &str,
+        backup_identifier: &str,
+    ) -> Result<String, CommandError> {
+        match db_type {
+            DatabaseType::PostgreSQL | DatabaseType::MySQL => {}
+            DatabaseType::MongoDB | DatabaseType::Redis => {
+                return Err(CommandError::new_from_safe_message(format!(
+                    "On-demand backup of managed {} databases is not supported",
+                    db_type.to_string()
+                )))
+            }
+        }
+
+        let mut cmd = QoveryCommand::new(
+            "aws",
+            &[
+                "rds",
+                "create-db-snapshot",
+                "--db-instance-identifier",
+                database_identifier,
+                "--db-snapshot-identifier",
+                backup_identifier,
+            ],
+            &[
+                (AWS_ACCESS_KEY_ID, self.access_key_id.as_str()),
+                (AWS_SECRET_ACCESS_KEY, self.secret_access_key.as_str()),
+                (AWS_DEFAULT_REGION, self.region.as_str()),
+            ],
+        );
+
+        let mut output_stdout: Vec<String> = vec![];
+        let mut output_stderr: Vec<String> = vec![];
+        if let Err(err) =
+            cmd.exec_with_output(&mut |line| output_stdout.push(line), &mut |line| output_stderr.push(line))
+        {
+            return Err(CommandError::new(
+                format!("Cannot create a snapshot of RDS instance `{}`", database_identifier),
+                Some(format!("{}: {}", err, output_stderr.join("\n"))),
+                None,
+            ));
+        }
+
+        snapshot_arn(&output_stdout.join(""))
+    }
+}
+
+fn snapshot_arn(create_db_snapshot_output: &str) -> Result<String, CommandError> {
+    serde_json::from_str::<CreateDbSnapshotResponse>(create_db_snapshot_output)
+        .map(|response| response.db_snapshot.db_snapshot_arn)
+        .map_err(|err| {
+            CommandError::new(
+                "Cannot parse RDS CreateDBSnapshot response".to_string(),
+                Some(format!("{}: {}", err, create_db_snapshot_output)),
+                None,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::aws::database_backup::{snapshot_arn, AwsDatabaseBackupClient};
+    use crate::cloud_provider::database_backup::DatabaseBackupClient;
+    use crate::cloud_provider::service::DatabaseType;
+
+    #[test]
+    fn test_snapshot_arn() {
+        // setup:
+        let output = r#"{
+            "DBSnapshot": {
+                "DBSnapshotIdentifier": "qovery-zabcd1234-20221015120000",
+                "DBInstanceIdentifier": "zabcd1234",
+                "Status": "creating",
+                "DBSnapshotArn": "arn:aws:rds:eu-west-3:123456789012:snapshot:qovery-zabcd1234-20221015120000"
+            }
+        }"#;
+
+        // execute & verify:
+        assert_eq!(
+            "arn:aws:rds:eu-west-3:123456789012:snapshot:qovery-zabcd1234-20221015120000",
+            snapshot_arn(output).expect("snapshot arn should be parsed")
+        );
+        assert!(snapshot_arn("").is_err());
+    }
+
+    #[test]
+    fn test_backup_of_non_rds_databases_is_not_supported() {
+        // setup:
+        let client = AwsDatabaseBackupClient::new("key".to_string(), "secret".to_string(), "eu-west-3".to_string());
+
+        for db_type in [DatabaseType::MongoDB, DatabaseType::Redis] {
+            // execute:
+            let result = client.create_backup(db_type, "zabcd1234", "qovery-zabcd1234-20221015120000");
+
+            // verify:
+            assert!(result
+                .expect_err("only RDS instances can be backed up")
+                .message_safe()
+                .contains("is not supported"));
+        }
+    }
+}
@@ -1,17 +1,19 @@
 use crate::cloud_provider::aws::kubernetes::{Options, VpcQoveryNetworkMode};
 use crate::cloud_provider::helm::{
     get_chart_for_cert_manager_config, get_chart_for_cluster_agent, get_chart_for_shell_agent,
-    get_engine_helm_action_from_location, ChartInfo, ChartSetValue, ClusterAgentContext, CommonChart, HelmAction,
-    HelmChart, HelmChartNamespaces, ShellAgentContext,
+    get_engine_helm_action_from_location, override_charts_timeout, ChartInfo, ChartSetValue, ClusterAgentContext,
+    CommonChart, HelmAction, HelmChart, HelmChartNamespaces, ShellAgentContext,
 };
 use crate::cloud_provider::helm_charts::qovery_storage_class_chart::{QoveryStorageClassChart, QoveryStorageType};
 use crate::cloud_provider::helm_charts::ToCommonHelmChart;
+use crate::cloud_provider::io::ClusterAdvancedSettings;
 use crate::cloud_provider::qovery::{get_qovery_app_version, EngineLocation, QoveryAppName, QoveryEngine};
 use crate::cmd::terraform::TerraformError;
 use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
+use crate::io_models::context::FeatureFlags;
 
-use crate::cloud_provider::helm_charts::coredns_config_chart::CoreDNSConfigChart;
+use crate::cloud_provider::helm_charts::coredns_config_chart::{parse_dns_resolvers, CoreDNSConfigChart};
 use crate::cloud_provider::helm_charts::external_dns_chart::ExternalDNSChart;
 use crate::cloud_provider::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use serde::{Deserialize, Serialize};
@@ -53,8 +55,7 @@ pub struct Ec2ChartsConfigPrerequisites {
     pub aws_secret_access_key: String,
     pub vpc_qovery_network_mode: VpcQoveryNetworkMode,
     pub qovery_engine_location: EngineLocation,
-    pub ff_log_history_enabled: bool,
-    pub ff_metrics_history_enabled: bool,
+    pub feature_flags: FeatureFlags,
     pub managed_dns_name: String,
     pub managed_dns_name_wildcarded: String,
     pub managed_dns_helm_format: String,
@@ -64,9 +65,9 @@ pub struct Ec2ChartsConfigPrerequisites {
     pub dns_email_report: String,
     pub acme_url: String,
     pub dns_provider_config: DnsProviderConfiguration,
-    pub disable_pleco: bool,
     // qovery options form json input
     pub infra_options: Options,
+    pub cluster_advanced_settings: ClusterAdvancedSettings,
 }
 
 pub fn get_aws_ec2_qovery_terraform_config(
@@ -155,6 +156,11 @@ pub fn ec2_aws_helm_charts(
         chart_config_prerequisites
             .managed_dns_resolvers_terraform_format
             .to_string(),
+        parse_dns_resolvers(
+            &chart_config_prerequisites
+                .cluster_advanced_settings
+                .dns_custom_resolvers,
+        )?,
     );
 
     let registry_creds = CommonChart {
@@ -380,7 +386,7 @@ pub fn ec2_aws_helm_charts(
         cluster_long_id: &chart_config_prerequisites.cluster_long_id,
         cluster_jwt_token: &chart_config_prerequisites.infra_options.jwt_token,
         grpc_url: &chart_config_prerequisites.infra_options.qovery_grpc_url,
-        loki_url: if chart_config_prerequisites.ff_log_history_enabled {
+        loki_url: if chart_config_prerequisites.feature_flags.log_history_enabled {
             Some("http://loki.logging.svc.cluster.local:3100")
         } else {
             None
@@ -568,6 +574,14 @@ pub fn ec2_aws_helm_charts(
         Box::new(shell_agent),
     ];
 
+    let mut levels = vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7];
+    override_charts_timeout(
+        &mut levels,
+        &chart_config_prerequisites
+            .cluster_advanced_settings
+            .helm_charts_timeout_override_sec,
+    );
+
     info!("charts configuration preparation finished");
-    Ok(vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7])
+    Ok(levels)
 }
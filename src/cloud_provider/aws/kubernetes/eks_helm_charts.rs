@@ -1,8 +1,9 @@
 use crate::cloud_provider::aws::kubernetes::{Options, VpcQoveryNetworkMode};
 use crate::cloud_provider::helm::{
     get_chart_for_cert_manager_config, get_chart_for_cluster_agent, get_chart_for_shell_agent,
-    get_engine_helm_action_from_location, ChartInfo, ChartSetValue, ChartValuesGenerated, ClusterAgentContext,
-    CommonChart, HelmAction, HelmChart, HelmChartNamespaces, ShellAgentContext,
+    get_engine_helm_action_from_location, override_charts_timeout, ChartInfo, ChartSetValue, ChartValuesGenerated,
+    ClusterAgentContext, CommonChart, FeatureFlaggedCharts, HelmAction, HelmChart, HelmChartNamespaces,
+    ShellAgentContext,
 };
 use crate::cloud_provider::helm_charts::qovery_storage_class_chart::{QoveryStorageClassChart, QoveryStorageType};
 use crate::cloud_provider::helm_charts::ToCommonHelmChart;
@@ -11,6 +12,7 @@ use crate::cloud_provider::qovery::{get_qovery_app_version, EngineLocation, Qove
 
 use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
+use crate::io_models::context::FeatureFlags;
 
 use crate::cloud_provider::aws::kubernetes::helm_charts::aws_iam_eks_user_mapper_chart::AwsIamEksUserMapperChart;
 use crate::cloud_provider::aws::kubernetes::helm_charts::aws_node_term_handler_chart::AwsNodeTermHandlerChart;
@@ -27,9 +29,9 @@ use crate::cloud_provider::aws::kubernetes::helm_charts::aws_vpc_cni_chart::{
     is_cni_old_version_installed, AwsVpcCniChart,
 };
 use crate::cloud_provider::aws::kubernetes::helm_charts::cluster_autoscaler_chart::ClusterAutoscalerChart;
-use crate::cloud_provider::helm_charts::coredns_config_chart::CoreDNSConfigChart;
+use crate::cloud_provider::helm_charts::coredns_config_chart::{parse_dns_resolvers, CoreDNSConfigChart};
 use crate::cloud_provider::helm_charts::external_dns_chart::ExternalDNSChart;
-use crate::cloud_provider::helm_charts::kube_prometheus_stack_chart::KubePrometheusStackChart;
+use crate::cloud_provider::helm_charts::kube_prometheus_stack_chart::{KubePrometheusStackChart, RemoteWriteConfig};
 use crate::cloud_provider::helm_charts::loki_chart::{LokiChart, LokiEncryptionType, LokiS3BucketConfiguration};
 use crate::cloud_provider::helm_charts::prometheus_adapter_chart::PrometheusAdapterChart;
 use crate::cloud_provider::helm_charts::promtail_chart::PromtailChart;
@@ -61,8 +63,7 @@ pub struct EksChartsConfigPrerequisites {
     pub aws_secret_access_key: String,
     pub vpc_qovery_network_mode: VpcQoveryNetworkMode,
     pub qovery_engine_location: EngineLocation,
-    pub ff_log_history_enabled: bool,
-    pub ff_metrics_history_enabled: bool,
+    pub feature_flags: FeatureFlags,
     pub managed_dns_name: String,
     pub managed_dns_helm_format: String,
     pub managed_dns_resolvers_terraform_format: String,
@@ -71,7 +72,6 @@ pub struct EksChartsConfigPrerequisites {
     pub dns_email_report: String,
     pub acme_url: String,
     pub dns_provider_config: DnsProviderConfiguration,
-    pub disable_pleco: bool,
     // qovery options form json input
     pub infra_options: Options,
     pub cluster_advanced_settings: ClusterAdvancedSettings,
@@ -162,7 +162,7 @@ pub fn eks_aws_helm_charts(
         qovery_terraform_config.aws_iam_cluster_autoscaler_key.to_string(),
         qovery_terraform_config.aws_iam_cluster_autoscaler_secret.to_string(),
         prometheus_namespace,
-        chart_config_prerequisites.ff_metrics_history_enabled,
+        chart_config_prerequisites.feature_flags.metrics_history_enabled,
     )
     .to_common_helm_chart();
 
@@ -174,6 +174,11 @@ pub fn eks_aws_helm_charts(
         chart_config_prerequisites
             .managed_dns_resolvers_terraform_format
             .to_string(),
+        parse_dns_resolvers(
+            &chart_config_prerequisites
+                .cluster_advanced_settings
+                .dns_custom_resolvers,
+        )?,
     );
 
     // External DNS
@@ -192,18 +197,20 @@ pub fn eks_aws_helm_charts(
     let promtail = PromtailChart::new(chart_prefix_path, loki_kube_dns_name).to_common_helm_chart();
 
     // Loki
+    let advanced_settings = &chart_config_prerequisites.cluster_advanced_settings;
     let loki = LokiChart::new(
         chart_prefix_path,
-        LokiEncryptionType::ServerSideEncryption,
+        LokiEncryptionType::from_advanced_settings(advanced_settings, true, LokiEncryptionType::ServerSideEncryption)?,
         loki_namespace,
-        chart_config_prerequisites
-            .cluster_advanced_settings
-            .loki_log_retention_in_week,
+        advanced_settings.loki_log_retention_in_week,
         LokiS3BucketConfiguration {
             s3_config: Some(qovery_terraform_config.loki_storage_config_aws_s3),
             region: Some(chart_config_prerequisites.region.to_string()),
             access_key_id: Some(qovery_terraform_config.aws_iam_loki_storage_key),
             secret_access_key: Some(qovery_terraform_config.aws_iam_loki_storage_secret),
+            // S3 uses virtual-hosted style by default
+            use_path_style: advanced_settings.loki_s3_use_path_style.unwrap_or(false),
+            insecure: advanced_settings.loki_s3_insecure,
             ..Default::default()
         },
     )
@@ -226,6 +233,7 @@ pub fn eks_aws_helm_charts(
         prometheus_internal_url.to_string(),
         prometheus_namespace,
         false,
+        RemoteWriteConfig::from_advanced_settings(advanced_settings)?,
     )
     .to_common_helm_chart();
 
@@ -390,7 +398,10 @@ datasources:
                 },
                 ChartSetValue {
                     key: "prometheus.servicemonitor.enabled".to_string(),
-                    value: chart_config_prerequisites.ff_metrics_history_enabled.to_string(),
+                    value: chart_config_prerequisites
+                        .feature_flags
+                        .metrics_history_enabled
+                        .to_string(),
                 },
                 ChartSetValue {
                     key: "prometheus.servicemonitor.prometheusInstance".to_string(),
@@ -550,7 +561,7 @@ datasources:
         cluster_long_id: &chart_config_prerequisites.cluster_long_id,
         cluster_jwt_token: &chart_config_prerequisites.infra_options.jwt_token,
         grpc_url: &chart_config_prerequisites.infra_options.qovery_grpc_url,
-        loki_url: if chart_config_prerequisites.ff_log_history_enabled {
+        loki_url: if chart_config_prerequisites.feature_flags.log_history_enabled {
             Some("http://loki.logging.svc.cluster.local:3100")
         } else {
             None
@@ -606,7 +617,10 @@ datasources:
                 },
                 ChartSetValue {
                     key: "metrics.enabled".to_string(),
-                    value: chart_config_prerequisites.ff_metrics_history_enabled.to_string(),
+                    value: chart_config_prerequisites
+                        .feature_flags
+                        .metrics_history_enabled
+                        .to_string(),
                 },
                 ChartSetValue {
                     key: "volumes.storageClassName".to_string(),
@@ -718,30 +732,34 @@ datasources:
         Box::new(qovery_engine),
     ];
 
-    // observability
-    if chart_config_prerequisites.ff_metrics_history_enabled {
-        level_1.push(Box::new(kube_prometheus_stack));
-        level_2.push(Box::new(prometheus_adapter));
-        level_2.push(Box::new(kube_state_metrics));
-    }
-    if chart_config_prerequisites.ff_log_history_enabled {
-        level_1.push(Box::new(promtail));
-        level_2.push(Box::new(loki));
-    }
-
-    if chart_config_prerequisites.ff_metrics_history_enabled || chart_config_prerequisites.ff_log_history_enabled {
-        level_2.push(Box::new(grafana))
-    };
-
     if let Some(qovery_webhook) = qovery_cert_manager_webhook {
         level_4.push(Box::new(qovery_webhook));
     }
 
-    // pleco
-    if !chart_config_prerequisites.disable_pleco {
-        level_6.push(Box::new(pleco));
+    FeatureFlaggedCharts {
+        kube_prometheus_stack: Box::new(kube_prometheus_stack),
+        prometheus_adapter: Box::new(prometheus_adapter),
+        kube_state_metrics: Box::new(kube_state_metrics),
+        promtail: Box::new(promtail),
+        loki: Box::new(loki),
+        grafana: Box::new(grafana),
+        pleco: Box::new(pleco),
     }
+    .add_to_levels(
+        &chart_config_prerequisites.feature_flags,
+        &mut level_1,
+        &mut level_2,
+        &mut level_6,
+    );
+
+    let mut levels = vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7];
+    override_charts_timeout(
+        &mut levels,
+        &chart_config_prerequisites
+            .cluster_advanced_settings
+            .helm_charts_timeout_override_sec,
+    );
 
     info!("charts configuration preparation finished");
-    Ok(vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7])
+    Ok(levels)
 }
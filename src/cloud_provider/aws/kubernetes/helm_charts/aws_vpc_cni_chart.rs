@@ -105,6 +105,10 @@ impl HelmChart for AwsVpcCniChart {
         &self.chart_info
     }
 
+    fn get_chart_info_mut(&mut self) -> &mut ChartInfo {
+        &mut self.chart_info
+    }
+
     // TODO(benjaminch): This piece of code should be handled via a dedicated struct, no need to override here.
     fn pre_exec(
         &self,
@@ -311,7 +315,7 @@ pub fn is_cni_old_version_installed(
                 e.message(ErrorMessageVerbosity::SafeOnly)
             ),
             e.message_raw(),
-            e.env_vars(),
+            e.into_raw_env_vars(),
         )),
     }
 }
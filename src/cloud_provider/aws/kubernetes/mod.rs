@@ -27,7 +27,7 @@ use crate::cloud_provider::aws::kubernetes::vault::{ClusterSecretsAws, ClusterSe
 use crate::cloud_provider::aws::regions::{AwsRegion, AwsZones};
 use crate::cloud_provider::helm::{deploy_charts_levels, ChartInfo};
 use crate::cloud_provider::kubernetes::{
-    is_kubernetes_upgrade_required, uninstall_cert_manager, Kind, Kubernetes, ProviderOptions,
+    is_kubernetes_upgrade_required, retry_kube, uninstall_cert_manager, Kind, Kubernetes, ProviderOptions, RetryPolicy,
 };
 use crate::cloud_provider::models::{
     KubernetesClusterAction, NodeGroups, NodeGroupsFormat, NodeGroupsWithDesiredState,
@@ -833,10 +833,9 @@ fn create(
                 event_details.clone(),
                 EventMessage::new_from_safe(format!("Role {} is already present, no need to create", role.role_name)),
             )),
-            Err(e) => kubernetes.logger().log(EngineEvent::Error(
-                EngineError::new_cannot_get_or_create_iam_role(event_details.clone(), role.role_name, e),
-                None,
-            )),
+            Err(e) => kubernetes
+                .logger()
+                .log(EngineEvent::Error(e.to_engine_error(event_details.clone()), None)),
         }
     }
 
@@ -898,7 +897,11 @@ fn create(
     ));
 
     // terraform deployment dedicated to cloud resources
-    if let Err(e) = terraform_init_validate_plan_apply(temp_dir.as_str(), kubernetes.context().is_dry_run_deploy()) {
+    if let Err(e) = terraform_init_validate_plan_apply(
+        temp_dir.as_str(),
+        kubernetes.context().is_dry_run_deploy(),
+        kubernetes.advanced_settings().terraform_parallelism,
+    ) {
         return Err(EngineError::new_terraform_error(event_details, e));
     }
 
@@ -1107,8 +1110,7 @@ fn create(
                 aws_secret_access_key: kubernetes.cloud_provider().secret_access_key(),
                 vpc_qovery_network_mode: options.vpc_qovery_network_mode.clone(),
                 qovery_engine_location: options.qovery_engine_location.clone(),
-                ff_log_history_enabled: kubernetes.context().is_feature_enabled(&Features::LogsHistory),
-                ff_metrics_history_enabled: kubernetes.context().is_feature_enabled(&Features::MetricsHistory),
+                feature_flags: kubernetes.context().feature_flags(),
                 managed_dns_name: kubernetes.dns_provider().domain().to_string(),
                 managed_dns_helm_format: kubernetes.dns_provider().domain().to_helm_format_string(),
                 managed_dns_resolvers_terraform_format: managed_dns_resolvers_terraform_format(
@@ -1123,7 +1125,6 @@ fn create(
                 dns_email_report: options.tls_email_report.clone(),
                 acme_url: lets_encrypt_url(kubernetes.context()),
                 dns_provider_config: kubernetes.dns_provider().provider_configuration(),
-                disable_pleco: kubernetes.context().disable_pleco(),
                 cluster_advanced_settings: kubernetes.advanced_settings().clone(),
             };
             eks_aws_helm_charts(
@@ -1150,8 +1151,7 @@ fn create(
                 aws_secret_access_key: kubernetes.cloud_provider().secret_access_key(),
                 vpc_qovery_network_mode: options.vpc_qovery_network_mode.clone(),
                 qovery_engine_location: options.qovery_engine_location.clone(),
-                ff_log_history_enabled: kubernetes.context().is_feature_enabled(&Features::LogsHistory),
-                ff_metrics_history_enabled: kubernetes.context().is_feature_enabled(&Features::MetricsHistory),
+                feature_flags: kubernetes.context().feature_flags(),
                 managed_dns_name: kubernetes.dns_provider().domain().to_string(),
                 managed_dns_name_wildcarded: kubernetes.dns_provider().domain().wildcarded().to_string(),
                 managed_dns_helm_format: kubernetes.dns_provider().domain().to_helm_format_string(),
@@ -1167,7 +1167,7 @@ fn create(
                 dns_email_report: options.tls_email_report.clone(),
                 acme_url: lets_encrypt_url(kubernetes.context()),
                 dns_provider_config: kubernetes.dns_provider().provider_configuration(),
-                disable_pleco: kubernetes.context().disable_pleco(),
+                cluster_advanced_settings: kubernetes.advanced_settings().clone(),
             };
             ec2_aws_helm_charts(
                 format!("{}/qovery-tf-config.json", &temp_dir).as_str(),
@@ -1194,6 +1194,7 @@ fn create(
         &credentials_environment_variables,
         helm_charts_to_deploy,
         kubernetes.context().is_dry_run_deploy(),
+        kubernetes.advanced_settings().helm_charts_max_parallelism as usize,
     )
     .map_err(|e| EngineError::new_helm_charts_deploy_error(event_details.clone(), e))
 }
@@ -1208,7 +1209,14 @@ fn create_error(kubernetes: &dyn Kubernetes) -> Result<(), EngineError> {
         EventMessage::new_from_safe(format!("{}.create_error() called.", kubernetes.kind())),
     ));
 
-    match kubectl_exec_get_events(kubeconfig_path, None, environment_variables) {
+    let events = retry_kube(
+        || {
+            kubectl_exec_get_events(&kubeconfig_path, None, environment_variables.clone())
+                .map_err(|err| EngineError::new_k8s_get_events(event_details.clone(), err))
+        },
+        RetryPolicy::default(),
+    );
+    match events {
         Ok(ok_line) => kubernetes
             .logger()
             .log(EngineEvent::Info(event_details, EventMessage::new(ok_line, None))),
@@ -1386,7 +1394,11 @@ fn pause(
         EventMessage::new_from_safe("Pausing cluster deployment.".to_string()),
     ));
 
-    match terraform_apply_with_tf_workers_resources(temp_dir.as_str(), tf_workers_resources) {
+    match terraform_apply_with_tf_workers_resources(
+        temp_dir.as_str(),
+        tf_workers_resources,
+        kubernetes.advanced_settings().terraform_parallelism,
+    ) {
         Ok(_) => {
             let message = format!("Kubernetes cluster {} successfully paused", kubernetes.name());
             kubernetes
@@ -1521,7 +1533,11 @@ fn delete(
         EventMessage::new_from_safe("Running Terraform apply before running a delete.".to_string()),
     ));
 
-    if let Err(e) = terraform_init_validate_plan_apply(temp_dir.as_str(), false) {
+    if let Err(e) = terraform_init_validate_plan_apply(
+        temp_dir.as_str(),
+        false,
+        kubernetes.advanced_settings().terraform_parallelism,
+    ) {
         // An issue occurred during the apply before destroy of Terraform, it may be expected if you're resuming a destroy
         kubernetes.logger().log(EngineEvent::Warning(
             event_details.clone(),
@@ -1763,7 +1779,11 @@ fn delete(
         EventMessage::new_from_safe("Running Terraform destroy".to_string()),
     ));
 
-    match cmd::terraform::terraform_init_validate_destroy(temp_dir.as_str(), false) {
+    match cmd::terraform::terraform_init_validate_destroy(
+        temp_dir.as_str(),
+        false,
+        kubernetes.advanced_settings().terraform_parallelism,
+    ) {
         Ok(_) => {
             kubernetes.logger().log(EngineEvent::Info(
                 event_details.clone(),
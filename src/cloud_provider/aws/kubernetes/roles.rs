@@ -1,7 +1,11 @@
-use self::rusoto_iam::{CreateServiceLinkedRoleRequest, GetRoleRequest, Iam, IamClient};
-use crate::errors::CommandError;
-use rusoto_core::{Client, HttpClient, Region};
+use self::rusoto_iam::{
+    CreateServiceLinkedRoleError, CreateServiceLinkedRoleRequest, GetRoleError, GetRoleRequest, Iam, IamClient,
+};
+use crate::errors::{CommandError, EngineError, Hint};
+use crate::events::EventDetails;
+use rusoto_core::{Client, HttpClient, Region, RusotoError};
 use rusoto_credential::StaticProvider;
+use std::fmt::{Display, Formatter};
 use tokio::runtime::Runtime;
 
 extern crate rusoto_iam;
@@ -12,6 +16,117 @@ pub struct Role {
     pub description: String,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IamRoleAction {
+    Get,
+    Create,
+}
+
+impl Display for IamRoleAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            IamRoleAction::Get => "getting",
+            IamRoleAction::Create => "creating",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IamRoleErrorCause {
+    /// A role with the same name already exists, but isn't the one expected.
+    NamingConflict,
+    /// Credentials are not allowed to perform the action.
+    PermissionDenied,
+    Other,
+}
+
+#[derive(Clone, Debug)]
+pub struct IamRoleError {
+    pub action: IamRoleAction,
+    pub role_name: String,
+    pub trust_policy: String,
+    pub aws_error_code: Option<String>,
+    pub raw_error: CommandError,
+}
+
+impl IamRoleError {
+    pub fn cause(&self) -> IamRoleErrorCause {
+        match self.aws_error_code.as_deref() {
+            Some("EntityAlreadyExists") => IamRoleErrorCause::NamingConflict,
+            // service linked role names are reserved, AWS answers this when the name is already used
+            Some("InvalidInput")
+                if self
+                    .raw_error
+                    .message_raw()
+                    .unwrap_or_default()
+                    .contains("has been taken") =>
+            {
+                IamRoleErrorCause::NamingConflict
+            }
+            Some("AccessDenied") | Some("AccessDeniedException") | Some("UnauthorizedOperation") => {
+                IamRoleErrorCause::PermissionDenied
+            }
+            _ => IamRoleErrorCause::Other,
+        }
+    }
+
+    fn hint(&self) -> Option<Hint> {
+        match self.cause() {
+            IamRoleErrorCause::NamingConflict => Some(Hint::new(
+                "hint.cannot_get_or_create_iam_role.naming_conflict",
+                format!(
+                    "A role named `{}` already exists in the AWS account with a different trust policy, it has to be renamed or deleted.",
+                    self.role_name
+                ),
+            )),
+            IamRoleErrorCause::PermissionDenied => Some(Hint::new(
+                "hint.cannot_get_or_create_iam_role.permission_denied",
+                format!(
+                    "Access denied: the credentials are not allowed to perform {} of IAM roles, `iam:GetRole` and `iam:CreateServiceLinkedRole` permissions are required.",
+                    self.action
+                ),
+            )),
+            IamRoleErrorCause::Other => None,
+        }
+    }
+
+    pub fn to_engine_error(self, event_details: EventDetails) -> EngineError {
+        let reason = format!(
+            "{} the {} failed with AWS error code `{}`",
+            self.action,
+            self.trust_policy,
+            self.aws_error_code.as_deref().unwrap_or("unknown"),
+        );
+        let hint = self.hint();
+
+        EngineError::new_cannot_get_or_create_iam_role(event_details, self.role_name, reason, self.raw_error, hint)
+    }
+}
+
+/// Only a missing role means it has to be created, any other error prevents knowing whether it exists.
+fn role_exists_from_get_error(err: IamRoleError) -> Result<bool, IamRoleError> {
+    match err.aws_error_code.as_deref() {
+        Some("NoSuchEntity") => Ok(false),
+        _ => Err(err),
+    }
+}
+
+/// Returns the AWS error code of an IAM error, either from the typed error or from the raw response.
+fn aws_error_code<E>(err: &RusotoError<E>, service_error_code: impl Fn(&E) -> &'static str) -> Option<String> {
+    match err {
+        RusotoError::Service(e) => Some(service_error_code(e).to_string()),
+        RusotoError::Unknown(response) => parse_aws_error_code(response.body_as_str()),
+        _ => None,
+    }
+}
+
+fn parse_aws_error_code(body: &str) -> Option<String> {
+    let start = body.find("<Code>")? + "<Code>".len();
+    let end = body[start..].find("</Code>")?;
+
+    Some(body[start..start + end].trim().to_string())
+}
+
 pub fn get_default_roles_to_create() -> Vec<Role> {
     vec![Role::new(
         "AWSServiceRoleForAmazonElasticsearchService".to_string(),
@@ -30,7 +145,26 @@ impl Role {
         }
     }
 
-    pub async fn is_exist(&self, access_key: &str, secret_key: &str) -> Result<bool, CommandError> {
+    pub fn trust_policy_summary(&self) -> String {
+        format!("service linked role assumable by `{}`", self.service_name)
+    }
+
+    fn to_iam_role_error(
+        &self,
+        action: IamRoleAction,
+        aws_error_code: Option<String>,
+        raw_error: CommandError,
+    ) -> IamRoleError {
+        IamRoleError {
+            action,
+            role_name: self.role_name.clone(),
+            trust_policy: self.trust_policy_summary(),
+            aws_error_code,
+            raw_error,
+        }
+    }
+
+    pub async fn is_exist(&self, access_key: &str, secret_key: &str) -> Result<bool, IamRoleError> {
         let credentials = StaticProvider::new(access_key.to_string(), secret_key.to_string(), None, None);
         let client = Client::new_with(credentials, HttpClient::new().unwrap());
         let iam_client = IamClient::new_with_client(client, Region::UsEast1);
@@ -42,15 +176,22 @@ impl Role {
 
         match role {
             Ok(_) => Ok(true),
-            Err(e) => Err(CommandError::new(
-                format!("Unable to know if {} exist on AWS account.", &self.role_name,),
-                Some(e.to_string()),
-                None,
+            Err(e) => role_exists_from_get_error(self.to_iam_role_error(
+                IamRoleAction::Get,
+                aws_error_code(&e, |err| match err {
+                    GetRoleError::NoSuchEntity(_) => "NoSuchEntity",
+                    GetRoleError::ServiceFailure(_) => "ServiceFailure",
+                }),
+                CommandError::new(
+                    format!("Unable to know if {} exist on AWS account.", &self.role_name,),
+                    Some(e.to_string()),
+                    None,
+                ),
             )),
         }
     }
 
-    pub fn create_service_linked_role(&self, access_key: &str, secret_key: &str) -> Result<bool, CommandError> {
+    pub fn create_service_linked_role(&self, access_key: &str, secret_key: &str) -> Result<bool, IamRoleError> {
         let future_is_exist = self.is_exist(access_key, secret_key);
         let exist = Runtime::new()
             .expect("Failed to create Tokio runtime to check if role exist")
@@ -61,7 +202,8 @@ impl Role {
                 // Role already exist, nothing to do
                 Ok(true)
             }
-            _ => {
+            Err(e) => Err(e),
+            Ok(false) => {
                 // Role doesn't exist, let's create it !
                 let credentials = StaticProvider::new(access_key.to_string(), secret_key.to_string(), None, None);
                 let client = Client::new_with(credentials, HttpClient::new().unwrap());
@@ -78,13 +220,122 @@ impl Role {
 
                 match created {
                     Ok(_) => Ok(true),
-                    Err(e) => Err(CommandError::new(
-                        format!("Unable to know if `{}` exist on AWS Account", &self.role_name),
-                        Some(e.to_string()),
-                        None,
+                    Err(e) => Err(self.to_iam_role_error(
+                        IamRoleAction::Create,
+                        aws_error_code(&e, |err| match err {
+                            CreateServiceLinkedRoleError::InvalidInput(_) => "InvalidInput",
+                            CreateServiceLinkedRoleError::LimitExceeded(_) => "LimitExceeded",
+                            CreateServiceLinkedRoleError::NoSuchEntity(_) => "NoSuchEntity",
+                            CreateServiceLinkedRoleError::ServiceFailure(_) => "ServiceFailure",
+                        }),
+                        CommandError::new(
+                            format!("Unable to create `{}` on AWS Account", &self.role_name),
+                            Some(e.to_string()),
+                            None,
+                        ),
                     )),
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::aws::kubernetes::roles::{
+        parse_aws_error_code, role_exists_from_get_error, IamRoleAction, IamRoleError,
+    };
+    use crate::cloud_provider::Kind;
+    use crate::errors::{CommandError, Tag};
+    use crate::events::{InfrastructureStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use uuid::Uuid;
+
+    fn iam_role_error(action: IamRoleAction, code: &str, raw_message: &str) -> IamRoleError {
+        IamRoleError {
+            action,
+            role_name: "AWSServiceRoleForAmazonElasticsearchService".to_string(),
+            trust_policy: "service linked role assumable by `es.amazonaws.com`".to_string(),
+            aws_error_code: Some(code.to_string()),
+            raw_error: CommandError::new("error".to_string(), Some(raw_message.to_string()), None),
+        }
+    }
+
+    #[test]
+    fn test_only_missing_role_is_reported_as_not_existing() {
+        // setup:
+        let test_cases = vec![
+            ("NoSuchEntity", Some(false)),
+            ("AccessDenied", None),
+            ("ServiceFailure", None),
+        ];
+
+        for (code, expected) in test_cases {
+            // execute:
+            let exists = role_exists_from_get_error(iam_role_error(IamRoleAction::Get, code, "error"));
+
+            // verify:
+            assert_eq!(expected, exists.ok(), "AWS error code: {}", code);
+        }
+    }
+
+    #[test]
+    fn test_iam_role_engine_error_distinguishes_naming_conflict_from_access_denied() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let conflict_err = iam_role_error(
+            IamRoleAction::Create,
+            "InvalidInput",
+            "Service role name AWSServiceRoleForAmazonElasticsearchService has been taken in this account",
+        )
+        .to_engine_error(event_details.clone());
+        let denied_err = iam_role_error(
+            IamRoleAction::Get,
+            "AccessDenied",
+            "User is not authorized to perform: iam:GetRole",
+        )
+        .to_engine_error(event_details);
+
+        // verify:
+        assert_eq!(&Tag::CannotGetOrCreateIamRole, conflict_err.tag());
+        assert!(conflict_err
+            .user_log_message()
+            .contains("creating the service linked role"));
+        assert!(conflict_err
+            .user_log_message()
+            .contains("assumable by `es.amazonaws.com`"));
+        assert!(conflict_err.user_log_message().contains("`InvalidInput`"));
+        assert!(conflict_err
+            .hint_message()
+            .as_ref()
+            .map(|hint| hint.contains("different trust policy"))
+            .unwrap_or(false));
+
+        assert!(denied_err
+            .user_log_message()
+            .contains("getting the service linked role"));
+        assert!(denied_err.user_log_message().contains("`AccessDenied`"));
+        assert!(denied_err
+            .hint_message()
+            .as_ref()
+            .map(|hint| hint.contains("Access denied") && !hint.contains("trust policy"))
+            .unwrap_or(false));
+    }
+
+    #[test]
+    fn test_parse_aws_error_code() {
+        assert_eq!(
+            Some("AccessDenied".to_string()),
+            parse_aws_error_code(
+                "<ErrorResponse><Error><Type>Sender</Type><Code>AccessDenied</Code><Message>User is not authorized</Message></Error></ErrorResponse>"
+            )
+        );
+        assert_eq!(None, parse_aws_error_code("Service Unavailable"));
+    }
+}
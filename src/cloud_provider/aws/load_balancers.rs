@@ -18,6 +18,26 @@ pub fn clean_up_deleted_k8s_nlb(event_details: EventDetails, target: &Deployment
         Some(x) => x,
         None => return Ok(()),
     };
+
+    // get load balancers from k8s
+    let kube_svcs = block_on(kube_list_services(&target.kube, None, None))
+        .map_err(|e| EngineError::new_k8s_service_issue(event_details.clone(), e))?;
+    let k8s_lbs = filter_svc_loadbalancers(kube_svcs);
+
+    if target.is_dry_run_deploy {
+        target.log_dry_run(&event_details, "deletion of orphaned load balancers".to_string());
+        return Ok(());
+    }
+
+    delete_orphaned_load_balancers(&event_details, &conn, &target.kubernetes.cluster_name(), k8s_lbs)
+}
+
+/// List AWS load balancers (with their tags) owned by the given cluster.
+pub fn list_load_balancers(
+    event_details: &EventDetails,
+    conn: &impl QoveryAwsSdkConfig,
+    cluster_name: &str,
+) -> Result<Vec<TagDescription>, EngineError> {
     let load_balancers = block_on(conn.list_all_aws_load_balancers()).map_err(|e| {
         EngineError::new_cloud_provider_error_getting_load_balancers(
             event_details.clone(),
@@ -34,18 +54,24 @@ pub fn clean_up_deleted_k8s_nlb(event_details: EventDetails, target: &Deployment
             CommandError::new_from_safe_message(e.to_string()),
         )
     })?;
+
     // get only ones matching the current cluster
-    let load_balancers_matching_current_cluster = filter_load_balancers_by_tag(
-        Some(format!("kubernetes.io/cluster/{}", target.kubernetes.cluster_name()).as_str()),
+    Ok(filter_load_balancers_by_tag(
+        Some(format!("kubernetes.io/cluster/{}", cluster_name).as_str()),
         Some("owned"),
         load_balancers_tags,
         true,
-    );
+    ))
+}
 
-    // get load balancers from k8s
-    let kube_svcs = block_on(kube_list_services(&target.kube, None, None))
-        .map_err(|e| EngineError::new_k8s_service_issue(event_details.clone(), e))?;
-    let k8s_lbs = filter_svc_loadbalancers(kube_svcs);
+/// Delete cluster's AWS load balancers which are not backed anymore by a live Kubernetes service.
+fn delete_orphaned_load_balancers(
+    event_details: &EventDetails,
+    conn: &impl QoveryAwsSdkConfig,
+    cluster_name: &str,
+    k8s_lbs: Vec<Service>,
+) -> Result<(), EngineError> {
+    let load_balancers_matching_current_cluster = list_load_balancers(event_details, conn, cluster_name)?;
 
     // find the ones to delete
     let nlb_to_delete = find_aws_nlb_to_delete(load_balancers_matching_current_cluster, k8s_lbs);
@@ -231,12 +257,81 @@ pub fn filter_load_balancers_by_tag(
 
 #[cfg(test)]
 mod tests {
-    use super::{filter_load_balancers_by_tag, find_aws_nlb_to_delete};
-    use aws_sdk_elasticloadbalancingv2::model::{Tag, TagDescription};
+    use super::{delete_orphaned_load_balancers, filter_load_balancers_by_tag, find_aws_nlb_to_delete};
+    use crate::cloud_provider::aws::models::QoveryAwsSdkConfig;
+    use crate::events::{EnvironmentStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use async_trait::async_trait;
+    use aws_sdk_elasticloadbalancingv2::error::{
+        DeleteLoadBalancerError, DescribeLoadBalancersError, DescribeTagsError,
+    };
+    use aws_sdk_elasticloadbalancingv2::model::{LoadBalancer, Tag, TagDescription};
+    use aws_sdk_elasticloadbalancingv2::output::DescribeLoadBalancersOutput;
+    use aws_smithy_client::SdkError;
     use k8s_openapi::api::core::v1::Service;
     use kube::core::ObjectMeta;
+    use std::sync::Mutex;
     use uuid::Uuid;
 
+    struct FakeAwsSdkConfig {
+        load_balancers_tags: Vec<TagDescription>,
+        deleted_load_balancers: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl QoveryAwsSdkConfig for FakeAwsSdkConfig {
+        async fn list_all_aws_load_balancers(
+            &self,
+        ) -> Result<DescribeLoadBalancersOutput, SdkError<DescribeLoadBalancersError>> {
+            Ok(DescribeLoadBalancersOutput::builder()
+                .set_load_balancers(Some(
+                    self.load_balancers_tags
+                        .iter()
+                        .map(|lb| {
+                            LoadBalancer::builder()
+                                .set_load_balancer_arn(lb.resource_arn().map(|arn| arn.to_string()))
+                                .build()
+                        })
+                        .collect(),
+                ))
+                .build())
+        }
+
+        async fn get_aws_load_balancers_tags(
+            &self,
+            _load_balancers: Vec<LoadBalancer>,
+        ) -> Result<Vec<TagDescription>, SdkError<DescribeTagsError>> {
+            Ok(self.load_balancers_tags.clone())
+        }
+
+        async fn delete_aws_load_balancer(
+            &self,
+            load_balancer_arn: String,
+        ) -> Result<(), SdkError<DeleteLoadBalancerError>> {
+            self.deleted_load_balancers
+                .lock()
+                .expect("cannot lock deleted load balancers")
+                .push(load_balancer_arn);
+            Ok(())
+        }
+    }
+
+    fn cluster_load_balancer(arn: &str, cluster_name: &str, service: &str) -> TagDescription {
+        TagDescription::builder()
+            .set_resource_arn(Some(arn.to_string()))
+            .set_tags(Some(vec![
+                Tag::builder()
+                    .set_key(Some(format!("kubernetes.io/cluster/{}", cluster_name)))
+                    .set_value(Some("owned".to_string()))
+                    .build(),
+                Tag::builder()
+                    .set_key(Some("kubernetes.io/service-name".to_string()))
+                    .set_value(Some(service.to_string()))
+                    .build(),
+            ]))
+            .build()
+    }
+
     #[test]
     fn test_load_balancers_filter() {
         let arn_id = Uuid::new_v4().to_string();
@@ -404,4 +499,46 @@ mod tests {
         assert_eq!(x.len(), 1);
         assert_eq!(x[0].resource_arn().unwrap(), "arn-ok-1".to_string());
     }
+
+    #[test]
+    fn test_delete_orphaned_load_balancers() {
+        // setup:
+        let cluster_name = "qovery-z00000000";
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Environment(EnvironmentStep::Delete))
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let conn = FakeAwsSdkConfig {
+            load_balancers_tags: vec![
+                // backed by a live service
+                cluster_load_balancer("arn-in-use", cluster_name, "namespace0/service-name0"),
+                // service has been deleted, should be deleted
+                cluster_load_balancer("arn-orphan", cluster_name, "namespace1/router-name1"),
+                // belongs to another cluster, should be left untouched
+                cluster_load_balancer("arn-other-cluster", "qovery-z11111111", "namespace2/service-name2"),
+            ],
+            deleted_load_balancers: Mutex::new(vec![]),
+        };
+        let k8s_lbs = vec![Service {
+            metadata: ObjectMeta {
+                name: Some("service-name0".to_string()),
+                namespace: Some("namespace0".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }];
+
+        // execute:
+        let result = delete_orphaned_load_balancers(&event_details, &conn, cluster_name, k8s_lbs);
+
+        // verify:
+        assert!(result.is_ok());
+        assert_eq!(
+            vec!["arn-orphan".to_string()],
+            *conn
+                .deleted_load_balancers
+                .lock()
+                .expect("cannot lock deleted load balancers")
+        );
+    }
 }
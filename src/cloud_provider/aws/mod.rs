@@ -1,16 +1,25 @@
 use std::any::Any;
+use std::sync::Mutex;
 
 use aws_config::provider_config::ProviderConfig;
 use aws_config::SdkConfig;
 use aws_smithy_client::erase::DynConnector;
 use aws_smithy_client::never::NeverConnector;
 use aws_types::os_shim_internal::Env;
+use lazy_static::lazy_static;
 use rusoto_core::{Client, HttpClient, Region};
 use rusoto_credential::StaticProvider;
 use rusoto_sts::{GetCallerIdentityRequest, Sts, StsClient};
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 
-use crate::cloud_provider::{kubernetes::Kind as KubernetesKind, CloudProvider, Kind, TerraformStateCredentials};
+use crate::cloud_provider::aws::database_backup::AwsDatabaseBackupClient;
+use crate::cloud_provider::aws::regions::AwsRegion;
+use crate::cloud_provider::database_backup::DatabaseBackupClient;
+use crate::cloud_provider::{
+    kubernetes::Kind as KubernetesKind, CloudProvider, Kind, RequiredPermission, SupportedRegion,
+    TerraformStateCredentials,
+};
 use crate::constants::{AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY};
 use crate::errors::EngineError;
 use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
@@ -19,11 +28,20 @@ use crate::io_models::QoveryIdentifier;
 use crate::runtime::block_on;
 use crate::utilities::to_short_id;
 
+pub mod database_backup;
 pub mod kubernetes;
 pub mod load_balancers;
 pub mod models;
+pub mod network;
+pub mod permissions;
 pub mod regions;
 
+lazy_static! {
+    static ref AWS_REGIONS: Vec<SupportedRegion> = AwsRegion::iter()
+        .map(|region| SupportedRegion::new(region.human_name(), region.to_aws_format(), region.get_zones_to_string()))
+        .collect();
+}
+
 pub struct AWS {
     context: Context,
     id: String,
@@ -35,6 +53,7 @@ pub struct AWS {
     pub zones: Vec<String>,
     kubernetes_kind: KubernetesKind,
     terraform_state_credentials: TerraformStateCredentials,
+    account_id: Mutex<Option<String>>,
 }
 
 impl AWS {
@@ -60,6 +79,31 @@ impl AWS {
             zones,
             kubernetes_kind,
             terraform_state_credentials,
+            account_id: Mutex::new(None),
+        }
+    }
+
+    pub fn with_account_id(self, account_id: &str) -> Self {
+        *self.account_id.lock().unwrap() = Some(account_id.to_string());
+        self
+    }
+
+    /// AWS account id owning the credentials, retrieved from STS on first use then cached
+    pub fn account_id(&self) -> Result<String, EngineError> {
+        let mut account_id = self.account_id.lock().unwrap();
+        if let Some(account_id) = account_id.as_ref() {
+            return Ok(account_id.to_string());
+        }
+
+        let client = StsClient::new_with_client(self.client(), Region::default());
+        match block_on(client.get_caller_identity(GetCallerIdentityRequest::default()))
+            .ok()
+            .and_then(|identity| identity.account)
+        {
+            Some(id) => Ok(account_id.insert(id).to_string()),
+            None => Err(EngineError::new_client_invalid_cloud_provider_credentials(
+                self.get_event_details(Stage::Infrastructure(InfrastructureStep::RetrieveClusterConfig)),
+            )),
         }
     }
 
@@ -113,6 +157,14 @@ impl CloudProvider for AWS {
         self.region.to_string()
     }
 
+    fn database_backup_client(&self) -> Option<Box<dyn DatabaseBackupClient>> {
+        Some(Box::new(AwsDatabaseBackupClient::new(
+            self.access_key_id(),
+            self.secret_access_key(),
+            self.region(),
+        )))
+    }
+
     fn aws_sdk_client(&self) -> Option<SdkConfig> {
         let env = Env::from_slice(&[
             ("AWS_MAX_ATTEMPTS", "10"),
@@ -147,10 +199,24 @@ impl CloudProvider for AWS {
         }
     }
 
+    fn validate_permissions(&self, required: &[RequiredPermission]) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Infrastructure(InfrastructureStep::RetrieveClusterConfig));
+        permissions::validate_permissions(self.client(), required, event_details)
+    }
+
     fn zones(&self) -> &Vec<String> {
         &self.zones
     }
 
+    fn supported_regions(&self) -> &'static [SupportedRegion] {
+        &AWS_REGIONS
+    }
+
+    fn container_registry_host(&self) -> Result<String, EngineError> {
+        // ECR hosts are prefixed by the AWS account id (<account_id>.dkr.ecr.<region>.amazonaws.com)
+        Ok(format!("{}.dkr.ecr.{}.amazonaws.com", self.account_id()?, self.region))
+    }
+
     fn credentials_environment_variables(&self) -> Vec<(&str, &str)> {
         vec![
             (AWS_ACCESS_KEY_ID, self.access_key_id.as_str()),
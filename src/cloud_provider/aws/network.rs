@@ -0,0 +1,164 @@
+use crate::errors::EngineError;
+use crate::events::EventDetails;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+// AWS accepts VPC and subnets CIDR blocks between /16 and /28
+const AWS_MIN_CIDR_PREFIX_LENGTH: u32 = 16;
+const AWS_MAX_CIDR_PREFIX_LENGTH: u32 = 28;
+
+/// Public and private subnets of an availability zone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubnetPlan {
+    /// zone_index: index of the availability zone, in the order the zones are given to the cluster.
+    pub zone_index: usize,
+    pub public_cidr_block: String,
+    pub private_cidr_block: String,
+}
+
+/// Splits a VPC CIDR block into equally sized subnets, one public and one private per availability zone.
+/// Public subnets take the first blocks of the VPC, private ones the following blocks.
+/// Subnets being power of two sized, the end of the VPC range may be left unused.
+pub fn generate_balanced_subnets(
+    event_details: EventDetails,
+    vpc_cidr_block: &str,
+    az_count: usize,
+) -> Result<Vec<SubnetPlan>, EngineError> {
+    let invalid_cidr_block =
+        |reason: String| EngineError::new_invalid_cidr_block(event_details.clone(), vpc_cidr_block.to_string(), reason);
+
+    let (network, prefix_length) = parse_ipv4_cidr_block(vpc_cidr_block)
+        .ok_or_else(|| invalid_cidr_block("expected an IPv4 network such as `10.0.0.0/16`".to_string()))?;
+    if !(AWS_MIN_CIDR_PREFIX_LENGTH..=AWS_MAX_CIDR_PREFIX_LENGTH).contains(&prefix_length) {
+        return Err(invalid_cidr_block(format!(
+            "VPC prefix length should be between /{} and /{}",
+            AWS_MIN_CIDR_PREFIX_LENGTH, AWS_MAX_CIDR_PREFIX_LENGTH
+        )));
+    }
+    if az_count == 0 {
+        return Err(invalid_cidr_block("cannot be split across 0 availability zones".to_string()));
+    }
+
+    let subnets_count = 2 * az_count;
+    let subnet_prefix_length = prefix_length + subnets_count.next_power_of_two().trailing_zeros();
+    if subnet_prefix_length > AWS_MAX_CIDR_PREFIX_LENGTH {
+        return Err(invalid_cidr_block(format!(
+            "too small to be split into {} subnets of at least /{}",
+            subnets_count, AWS_MAX_CIDR_PREFIX_LENGTH
+        )));
+    }
+
+    let subnet_size = 1u32 << (32 - subnet_prefix_length);
+    let subnet = |index: usize| {
+        format!(
+            "{}/{}",
+            Ipv4Addr::from(network + index as u32 * subnet_size),
+            subnet_prefix_length
+        )
+    };
+
+    Ok((0..az_count)
+        .map(|zone_index| SubnetPlan {
+            zone_index,
+            public_cidr_block: subnet(zone_index),
+            private_cidr_block: subnet(az_count + zone_index),
+        })
+        .collect())
+}
+
+/// Returns the network address and prefix length of an IPv4 CIDR block, None if it is malformed or has host bits set.
+fn parse_ipv4_cidr_block(cidr_block: &str) -> Option<(u32, u32)> {
+    let (address, prefix_length) = cidr_block.trim().split_once('/')?;
+    let address = u32::from(Ipv4Addr::from_str(address).ok()?);
+    let prefix_length = u32::from_str(prefix_length).ok().filter(|length| *length <= 32)?;
+
+    let host_mask = u32::MAX.checked_shr(prefix_length).unwrap_or(0);
+    match address & host_mask {
+        0 => Some((address, prefix_length)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::aws::network::{generate_balanced_subnets, SubnetPlan};
+    use crate::cloud_provider::Kind;
+    use crate::errors::Tag;
+    use crate::events::{InfrastructureStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+
+    use uuid::Uuid;
+
+    #[test]
+    fn test_generate_balanced_subnets_across_3_zones() {
+        // execute:
+        let subnets = generate_balanced_subnets(
+            EventDetailsBuilder::new()
+                .provider_kind(Kind::Aws)
+                .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                .build(),
+            "10.0.0.0/16",
+            3,
+        )
+        .expect("subnets should be generated");
+
+        // verify:
+        assert_eq!(
+            vec![
+                SubnetPlan {
+                    zone_index: 0,
+                    public_cidr_block: "10.0.0.0/19".to_string(),
+                    private_cidr_block: "10.0.96.0/19".to_string(),
+                },
+                SubnetPlan {
+                    zone_index: 1,
+                    public_cidr_block: "10.0.32.0/19".to_string(),
+                    private_cidr_block: "10.0.128.0/19".to_string(),
+                },
+                SubnetPlan {
+                    zone_index: 2,
+                    public_cidr_block: "10.0.64.0/19".to_string(),
+                    private_cidr_block: "10.0.160.0/19".to_string(),
+                },
+            ],
+            subnets
+        );
+    }
+
+    #[test]
+    fn test_generate_balanced_subnets_invalid_cidr_block() {
+        // setup:
+        let test_cases = vec![
+            // too small to get 6 subnets of at least /28
+            ("10.0.0.0/27", 3),
+            ("10.0.0.0/8", 3),
+            ("10.0.0.1/16", 3),
+            ("10.0.0.0", 3),
+            ("not-a-cidr/16", 3),
+            ("10.0.0.0/16", 0),
+        ];
+
+        for (vpc_cidr_block, az_count) in test_cases {
+            // execute:
+            let result = generate_balanced_subnets(
+                EventDetailsBuilder::new()
+                    .provider_kind(Kind::Aws)
+                    .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                    .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                    .build(),
+                vpc_cidr_block,
+                az_count,
+            );
+
+            // verify:
+            assert_eq!(
+                Some(&Tag::InvalidCIDRBlock),
+                result.as_ref().err().map(|err| err.tag()),
+                "{} across {} zones",
+                vpc_cidr_block,
+                az_count
+            );
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::cloud_provider::RequiredPermission;
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::runtime::block_on;
+use rusoto_core::{Client, Region};
+use rusoto_iam::{Iam, IamClient, SimulatePolicyResponse, SimulatePrincipalPolicyRequest};
+use rusoto_sts::{GetCallerIdentityRequest, Sts, StsClient};
+use std::collections::BTreeMap;
+
+// IAM simulation decisions are `allowed`, `explicitDeny` or `implicitDeny`
+const ALLOWED_DECISION: &str = "allowed";
+
+/// Checks, through IAM policy simulation, the credentials are allowed to perform every required action.
+/// Nothing is created nor modified on the account.
+pub fn validate_permissions(
+    client: Client,
+    required: &[RequiredPermission],
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    if required.is_empty() {
+        return Ok(());
+    }
+
+    let sts = StsClient::new_with_client(client.clone(), Region::default());
+    let caller_arn = block_on(sts.get_caller_identity(GetCallerIdentityRequest::default()))
+        .ok()
+        .and_then(|identity| identity.arn)
+        .ok_or_else(|| EngineError::new_client_invalid_cloud_provider_credentials(event_details.clone()))?;
+    let policy_source_arn = policy_source_arn(&caller_arn);
+
+    // resources apply to every simulated action, so actions are simulated per resource
+    let mut actions_by_resource: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for permission in required {
+        actions_by_resource
+            .entry(permission.resource.as_str())
+            .or_default()
+            .push(permission.action.clone());
+    }
+
+    let iam = IamClient::new_with_client(client, Region::default());
+    let mut simulations: Vec<SimulatePolicyResponse> = vec![];
+    for (resource, actions) in actions_by_resource {
+        let mut marker: Option<String> = None;
+        loop {
+            let simulation = block_on(iam.simulate_principal_policy(SimulatePrincipalPolicyRequest {
+                action_names: actions.clone(),
+                policy_source_arn: policy_source_arn.clone(),
+                resource_arns: Some(vec![resource.to_string()]),
+                marker: marker.clone(),
+                ..Default::default()
+            }))
+            .map_err(|err| {
+                EngineError::new_error_on_cloud_provider_information(
+                    event_details.clone(),
+                    CommandError::new(
+                        format!("Cannot simulate IAM permissions of `{}`.", policy_source_arn),
+                        Some(err.to_string()),
+                        None,
+                    ),
+                )
+            })?;
+
+            marker = match simulation.is_truncated {
+                Some(true) => simulation.marker.clone(),
+                _ => None,
+            };
+            simulations.push(simulation);
+            if marker.is_none() {
+                break;
+            }
+        }
+    }
+
+    check_simulations(&simulations, &policy_source_arn, event_details)
+}
+
+/// IAM can only simulate the policies of users and roles, an assumed role session is checked against its role.
+/// i.e: `arn:aws:sts::123456789012:assumed-role/qovery/session` => `arn:aws:iam::123456789012:role/qovery`
+fn policy_source_arn(caller_arn: &str) -> String {
+    let parts: Vec<&str> = caller_arn.splitn(6, ':').collect();
+    match parts.as_slice() {
+        ["arn", partition, "sts", _, account_id, resource] => match resource.split('/').collect::<Vec<&str>>()[..] {
+            ["assumed-role", role_name, ..] => format!("arn:{}:iam::{}:role/{}", partition, account_id, role_name),
+            _ => caller_arn.to_string(),
+        },
+        _ => caller_arn.to_string(),
+    }
+}
+
+/// Returns the actions the simulation didn't allow, once each, in the order they were simulated.
+fn denied_actions(simulations: &[SimulatePolicyResponse]) -> Vec<String> {
+    let mut denied: Vec<String> = vec![];
+    for result in simulations
+        .iter()
+        .flat_map(|simulation| simulation.evaluation_results.iter().flatten())
+    {
+        if result.eval_decision != ALLOWED_DECISION && !denied.contains(&result.eval_action_name) {
+            denied.push(result.eval_action_name.clone());
+        }
+    }
+
+    denied
+}
+
+fn check_simulations(
+    simulations: &[SimulatePolicyResponse],
+    policy_source_arn: &str,
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    match denied_actions(simulations) {
+        denied if denied.is_empty() => Ok(()),
+        denied => Err(EngineError::new_cloud_provider_missing_permissions(
+            event_details,
+            policy_source_arn.to_string(),
+            denied,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::aws::permissions::{check_simulations, denied_actions, policy_source_arn};
+    use crate::cloud_provider::Kind;
+    use crate::errors::Tag;
+    use crate::events::{InfrastructureStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use rusoto_iam::{EvaluationResult, SimulatePolicyResponse};
+    use uuid::Uuid;
+
+    fn simulation(decisions: &[(&str, &str)]) -> SimulatePolicyResponse {
+        SimulatePolicyResponse {
+            evaluation_results: Some(
+                decisions
+                    .iter()
+                    .map(|(action, decision)| EvaluationResult {
+                        eval_action_name: action.to_string(),
+                        eval_decision: decision.to_string(),
+                        eval_resource_name: Some("*".to_string()),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            is_truncated: Some(false),
+            marker: None,
+        }
+    }
+
+    #[test]
+    fn test_policy_source_arn() {
+        assert_eq!(
+            "arn:aws:iam::123456789012:role/qovery-admin",
+            policy_source_arn("arn:aws:sts::123456789012:assumed-role/qovery-admin/engine-session")
+        );
+        assert_eq!(
+            "arn:aws:iam::123456789012:user/qovery",
+            policy_source_arn("arn:aws:iam::123456789012:user/qovery")
+        );
+    }
+
+    #[test]
+    fn test_check_simulations_with_denied_action() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::RetrieveClusterConfig))
+            .transmitter(Transmitter::CloudProvider(Uuid::new_v4(), "aws".to_string()))
+            .build();
+        let simulations = vec![
+            simulation(&[("eks:CreateCluster", "allowed"), ("iam:CreateRole", "implicitDeny")]),
+            simulation(&[("ec2:CreateVpc", "allowed"), ("iam:CreateRole", "explicitDeny")]),
+        ];
+
+        // execute:
+        let result = check_simulations(&simulations, "arn:aws:iam::123456789012:user/qovery", event_details);
+
+        // verify:
+        assert_eq!(vec!["iam:CreateRole".to_string()], denied_actions(&simulations));
+        let err = result.expect_err("denied action should be reported");
+        assert_eq!(&Tag::TerraformNotEnoughPermissions, err.tag());
+        assert!(err.user_log_message().contains("iam:CreateRole"));
+        assert!(!err.user_log_message().contains("eks:CreateCluster"));
+        assert!(err.user_log_message().contains("arn:aws:iam::123456789012:user/qovery"));
+    }
+
+    #[test]
+    fn test_check_simulations_all_allowed() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::RetrieveClusterConfig))
+            .transmitter(Transmitter::CloudProvider(Uuid::new_v4(), "aws".to_string()))
+            .build();
+
+        // execute:
+        let result = check_simulations(
+            &[simulation(&[("eks:CreateCluster", "allowed")])],
+            "arn:aws:iam::123456789012:user/qovery",
+            event_details,
+        );
+
+        // verify:
+        assert!(result.is_ok());
+    }
+}
@@ -198,6 +198,33 @@ impl AwsRegion {
         }
     }
 
+    pub fn human_name(&self) -> &'static str {
+        match self {
+            AwsRegion::UsEast1 => "North Virginia",
+            AwsRegion::UsEast2 => "Ohio",
+            AwsRegion::UsWest2 => "Oregon",
+            AwsRegion::AfSouth1 => "Cape Town",
+            AwsRegion::ApEast1 => "Hong Kong",
+            AwsRegion::ApSouth1 => "Mumbai",
+            AwsRegion::ApNortheast1 => "Tokyo",
+            AwsRegion::ApNortheast2 => "Seoul",
+            AwsRegion::ApNortheast3 => "Osaka",
+            AwsRegion::ApSoutheast1 => "Singapore",
+            AwsRegion::ApSoutheast2 => "Sydney",
+            AwsRegion::CaCentral1 => "Toronto",
+            AwsRegion::CnNorth1 => "Beijing",
+            AwsRegion::CnNorthwest1 => "Ningxia",
+            AwsRegion::EuCentral1 => "Frankfurt",
+            AwsRegion::EuWest1 => "Ireland",
+            AwsRegion::EuWest2 => "London",
+            AwsRegion::EuWest3 => "Paris",
+            AwsRegion::EuNorth1 => "Stockholm",
+            AwsRegion::EuSouth1 => "Milan",
+            AwsRegion::MeSouth1 => "Bahrain",
+            AwsRegion::SaEast1 => "Sao Paulo",
+        }
+    }
+
     pub fn get_zones_to_string(&self) -> Vec<String> {
         let zones = self.get_zones();
         let zones_to_string: Vec<String> = zones.into_iter().map(|x| x.to_string()).collect();
@@ -0,0 +1,175 @@
+use crate::cloud_provider::service::DatabaseType;
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Handle on an on-demand backup of a managed database, to poll its status from the cloud provider.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackupHandle {
+    pub database_id: Uuid,
+    /// backup_id: backup identifier at the cloud provider.
+    pub backup_id: String,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// Client of a cloud provider managed databases backup API.
+pub trait DatabaseBackupClient: Send + Sync {
+    /// Starts a backup of the given managed database instance named after `backup_identifier`,
+    /// returns the backup identifier at the cloud provider.
+    fn create_backup(
+        &self,
+        db_type: DatabaseType,
+        database_identifier: &str,
+        backup_identifier: &str,
+    ) -> Result<String, CommandError>;
+}
+
+pub fn trigger_database_backup(
+    is_managed: bool,
+    db_type: DatabaseType,
+    database_id: Uuid,
+    database_identifier: &str,
+    client: Option<&dyn DatabaseBackupClient>,
+    event_details: EventDetails,
+) -> Result<BackupHandle, EngineError> {
+    // self-hosted databases have no backup API to call
+    if !is_managed {
+        return Err(EngineError::new_not_implemented_error(event_details));
+    }
+
+    let client = client.ok_or_else(|| {
+        EngineError::new_cannot_trigger_database_backup(
+            event_details.clone(),
+            database_identifier.to_string(),
+            CommandError::new_from_safe_message("Cloud provider has no managed databases backup API".to_string()),
+        )
+    })?;
+
+    let requested_at = Utc::now();
+    let backup_identifier = format!("qovery-{}-{}", database_identifier, requested_at.format("%Y%m%d%H%M%S"));
+    let backup_id = client
+        .create_backup(db_type, database_identifier, &backup_identifier)
+        .map_err(|err| {
+            EngineError::new_cannot_trigger_database_backup(event_details, database_identifier.to_string(), err)
+        })?;
+
+    Ok(BackupHandle {
+        database_id,
+        backup_id,
+        requested_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::database_backup::{trigger_database_backup, DatabaseBackupClient};
+    use crate::cloud_provider::service::DatabaseType;
+    use crate::errors::{CommandError, Tag};
+    use crate::events::Transmitter;
+    use crate::test_utils::EventDetailsBuilder;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    struct MockBackupClient {
+        result: Result<String, CommandError>,
+        calls: Mutex<Vec<(String, String)>>,
+    }
+
+    impl DatabaseBackupClient for MockBackupClient {
+        fn create_backup(
+            &self,
+            _db_type: DatabaseType,
+            database_identifier: &str,
+            backup_identifier: &str,
+        ) -> Result<String, CommandError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((database_identifier.to_string(), backup_identifier.to_string()));
+            self.result.clone()
+        }
+    }
+
+    #[test]
+    fn test_trigger_managed_database_backup() {
+        // setup:
+        let database_id = Uuid::new_v4();
+        let client = MockBackupClient {
+            result: Ok("rds:zabcd1234-backup".to_string()),
+            calls: Mutex::new(vec![]),
+        };
+
+        // execute:
+        let handle = trigger_database_backup(
+            true,
+            DatabaseType::PostgreSQL,
+            database_id,
+            "zabcd1234",
+            Some(&client),
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(database_id, "db".to_string()))
+                .build(),
+        )
+        .expect("backup should be triggered");
+
+        // verify:
+        assert_eq!(database_id, handle.database_id);
+        assert_eq!("rds:zabcd1234-backup", handle.backup_id);
+        let calls = client.calls.lock().unwrap();
+        assert_eq!(1, calls.len());
+        assert_eq!("zabcd1234", calls[0].0);
+        assert!(calls[0].1.starts_with("qovery-zabcd1234-"));
+    }
+
+    #[test]
+    fn test_trigger_managed_database_backup_failure() {
+        // setup:
+        let database_id = Uuid::new_v4();
+        let client = MockBackupClient {
+            result: Err(CommandError::new_from_safe_message("InvalidDBInstanceState".to_string())),
+            calls: Mutex::new(vec![]),
+        };
+
+        // execute:
+        let result = trigger_database_backup(
+            true,
+            DatabaseType::PostgreSQL,
+            database_id,
+            "zabcd1234",
+            Some(&client),
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(database_id, "db".to_string()))
+                .build(),
+        );
+
+        // verify:
+        assert_eq!(&Tag::CannotTriggerDatabaseBackup, result.unwrap_err().tag());
+    }
+
+    #[test]
+    fn test_trigger_self_hosted_database_backup_is_not_implemented() {
+        // setup:
+        let database_id = Uuid::new_v4();
+        let client = MockBackupClient {
+            result: Ok("backup".to_string()),
+            calls: Mutex::new(vec![]),
+        };
+
+        // execute:
+        let result = trigger_database_backup(
+            false,
+            DatabaseType::PostgreSQL,
+            database_id,
+            "postgresql-zabcd1234",
+            Some(&client),
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(database_id, "db".to_string()))
+                .build(),
+        );
+
+        // verify:
+        assert_eq!(&Tag::NotImplementedError, result.unwrap_err().tag());
+        assert!(client.calls.lock().unwrap().is_empty());
+    }
+}
@@ -2,20 +2,23 @@ use crate::cloud_provider::digitalocean::kubernetes::DoksOptions;
 use crate::cloud_provider::helm::HelmAction::Destroy;
 use crate::cloud_provider::helm::{
     get_chart_for_cert_manager_config, get_chart_for_cluster_agent, get_chart_for_shell_agent,
-    get_engine_helm_action_from_location, ChartInfo, ChartSetValue, ChartValuesGenerated, ClusterAgentContext,
-    CommonChart, HelmChart, HelmChartNamespaces, ShellAgentContext,
+    get_engine_helm_action_from_location, override_charts_timeout, resolve_chart_levels, ChartInfo, ChartSetValue,
+    ChartValuesGenerated, ClusterAgentContext, CommonChart, FeatureFlaggedCharts, HelmChart, HelmChartNamespaces,
+    ShellAgentContext,
 };
 use crate::cloud_provider::helm_charts::qovery_storage_class_chart::{QoveryStorageClassChart, QoveryStorageType};
 use crate::cloud_provider::helm_charts::ToCommonHelmChart;
 use crate::cloud_provider::io::ClusterAdvancedSettings;
 use crate::cloud_provider::qovery::{get_qovery_app_version, EngineLocation, QoveryAppName, QoveryEngine};
 
+use crate::container_registry::{docker_json_config, RegistryCredential};
 use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
+use crate::io_models::context::FeatureFlags;
 
-use crate::cloud_provider::helm_charts::coredns_config_chart::CoreDNSConfigChart;
+use crate::cloud_provider::helm_charts::coredns_config_chart::{parse_dns_resolvers, CoreDNSConfigChart};
 use crate::cloud_provider::helm_charts::external_dns_chart::ExternalDNSChart;
-use crate::cloud_provider::helm_charts::kube_prometheus_stack_chart::KubePrometheusStackChart;
+use crate::cloud_provider::helm_charts::kube_prometheus_stack_chart::{KubePrometheusStackChart, RemoteWriteConfig};
 use crate::cloud_provider::helm_charts::loki_chart::{LokiChart, LokiEncryptionType, LokiS3BucketConfiguration};
 use crate::cloud_provider::helm_charts::prometheus_adapter_chart::PrometheusAdapterChart;
 use crate::cloud_provider::helm_charts::promtail_chart::PromtailChart;
@@ -27,6 +30,13 @@ use std::io::BufReader;
 use std::iter::FromIterator;
 use std::path::Path;
 
+/// Loki log retention bounds, in weeks (up to 10 years).
+const LOKI_LOG_RETENTION_MIN_WEEKS: u32 = 1;
+const LOKI_LOG_RETENTION_MAX_WEEKS: u32 = 520;
+/// With a typical ingest of ~1GiB of compressed logs a day, a Spaces bucket and its 250GiB of included storage
+/// is full after ~35 weeks, longer retentions work but get billed for the extra storage.
+const LOKI_LOG_RETENTION_SPACES_CAPACITY_WEEKS: u32 = 35;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DigitalOceanQoveryTerraformConfig {
     pub loki_storage_config_do_space_access_id: String,
@@ -47,13 +57,13 @@ pub struct ChartsConfigPrerequisites {
     pub cloud_provider: String,
     pub test_cluster: bool,
     pub do_token: String,
+    pub do_container_registry_host: String,
     pub do_space_access_id: String,
     pub do_space_secret_key: String,
     pub do_space_bucket_kubeconfig: String,
     pub do_space_kubeconfig_filename: String,
     pub qovery_engine_location: EngineLocation,
-    pub ff_log_history_enabled: bool,
-    pub ff_metrics_history_enabled: bool,
+    pub feature_flags: FeatureFlags,
     pub managed_dns_name: String,
     pub managed_dns_helm_format: String,
     pub managed_dns_resolvers_terraform_format: String,
@@ -62,7 +72,6 @@ pub struct ChartsConfigPrerequisites {
     pub dns_email_report: String,
     pub acme_url: String,
     pub dns_provider_config: DnsProviderConfiguration,
-    pub disable_pleco: bool,
     // qovery options form json input
     pub infra_options: DoksOptions,
     pub cluster_advanced_settings: ClusterAdvancedSettings,
@@ -80,13 +89,13 @@ impl ChartsConfigPrerequisites {
         cloud_provider: String,
         test_cluster: bool,
         do_token: String,
+        do_container_registry_host: String,
         do_space_access_id: String,
         do_space_secret_key: String,
         do_space_bucket_kubeconfig: String,
         do_space_kubeconfig_filename: String,
         qovery_engine_location: EngineLocation,
-        ff_log_history_enabled: bool,
-        ff_metrics_history_enabled: bool,
+        feature_flags: FeatureFlags,
         managed_dns_name: String,
         managed_dns_helm_format: String,
         managed_dns_resolvers_terraform_format: String,
@@ -95,7 +104,6 @@ impl ChartsConfigPrerequisites {
         dns_email_report: String,
         acme_url: String,
         dns_provider_config: DnsProviderConfiguration,
-        disable_pleco: bool,
         infra_options: DoksOptions,
         cluster_advanced_settings: ClusterAdvancedSettings,
     ) -> Self {
@@ -110,13 +118,13 @@ impl ChartsConfigPrerequisites {
             cloud_provider,
             test_cluster,
             do_token,
+            do_container_registry_host,
             do_space_access_id,
             do_space_secret_key,
             do_space_bucket_kubeconfig,
             do_space_kubeconfig_filename,
             qovery_engine_location,
-            ff_log_history_enabled,
-            ff_metrics_history_enabled,
+            feature_flags,
             managed_dns_name,
             managed_dns_helm_format,
             managed_dns_resolvers_terraform_format,
@@ -125,11 +133,24 @@ impl ChartsConfigPrerequisites {
             dns_email_report,
             acme_url,
             dns_provider_config,
-            disable_pleco,
             infra_options,
             cluster_advanced_settings,
         }
     }
+
+    /// Credentials of the DO registry, then of the additional registries apps can pull images from.
+    pub fn registry_credentials(&self) -> Vec<RegistryCredential> {
+        // https://docs.digitalocean.com/products/container-registry/how-to/use-registry-docker-kubernetes/
+        let do_registry = RegistryCredential {
+            host: self.do_container_registry_host.clone(),
+            username: self.do_token.clone(),
+            password: self.do_token.clone(),
+        };
+
+        std::iter::once(do_registry)
+            .chain(self.infra_options.additional_registry_credentials.iter().cloned())
+            .collect()
+    }
 }
 
 pub fn do_helm_charts(
@@ -181,10 +202,15 @@ pub fn do_helm_charts(
         chart_config_prerequisites
             .managed_dns_resolvers_terraform_format
             .to_string(),
+        parse_dns_resolvers(
+            &chart_config_prerequisites
+                .cluster_advanced_settings
+                .dns_custom_resolvers,
+        )?,
     );
 
     // External DNS
-    let external_dns = ExternalDNSChart::new(
+    let mut external_dns = ExternalDNSChart::new(
         chart_prefix_path,
         chart_config_prerequisites.dns_provider_config.clone(),
         chart_config_prerequisites
@@ -199,16 +225,21 @@ pub fn do_helm_charts(
     let promtail = PromtailChart::new(chart_prefix_path, loki_kube_dns_name).to_common_helm_chart();
 
     // Loki
-    let loki = LokiChart::new(
-        chart_prefix_path,
-        LokiEncryptionType::None, // DigitalOcean does not support encryption yet: https://docs.digitalocean.com/reference/api/spaces-api/
-        loki_namespace,
+    let loki_log_retention_in_week = check_loki_log_retention(
         chart_config_prerequisites
             .cluster_advanced_settings
             .loki_log_retention_in_week,
+    )?;
+    let advanced_settings = &chart_config_prerequisites.cluster_advanced_settings;
+    let mut loki = LokiChart::new(
+        chart_prefix_path,
+        // DigitalOcean does not support encryption yet: https://docs.digitalocean.com/reference/api/spaces-api/
+        LokiEncryptionType::from_advanced_settings(advanced_settings, false, LokiEncryptionType::None)?,
+        loki_namespace,
+        loki_log_retention_in_week,
         LokiS3BucketConfiguration {
-            use_path_style: true,
-            insecure: false,
+            use_path_style: advanced_settings.loki_s3_use_path_style.unwrap_or(true),
+            insecure: advanced_settings.loki_s3_insecure,
             bucketname: Some(qovery_terraform_config.loki_storage_config_do_space_bucket_name),
             endpoint: Some(qovery_terraform_config.loki_storage_config_do_space_host),
             region: Some(qovery_terraform_config.loki_storage_config_do_space_region),
@@ -236,17 +267,19 @@ pub fn do_helm_charts(
         prometheus_internal_url.to_string(),
         prometheus_namespace,
         true,
+        RemoteWriteConfig::from_advanced_settings(advanced_settings)?,
     )
     .to_common_helm_chart();
 
     // Prometheus adapter
-    let prometheus_adapter =
+    let mut prometheus_adapter =
         PrometheusAdapterChart::new(chart_prefix_path, prometheus_internal_url.clone(), prometheus_namespace)
             .to_common_helm_chart();
 
     let metrics_server = CommonChart {
         chart_info: ChartInfo {
             name: "metrics-server".to_string(),
+            depends_on: vec!["cert-manager"],
             path: chart_path("common/charts/metrics-server"),
             values_files: vec![chart_path("chart_values/metrics-server.yaml")],
             values: vec![
@@ -275,6 +308,7 @@ pub fn do_helm_charts(
     let kube_state_metrics = CommonChart {
         chart_info: ChartInfo {
             name: "kube-state-metrics".to_string(),
+            depends_on: vec!["kube-prometheus-stack"],
             namespace: HelmChartNamespaces::Prometheus,
             last_breaking_version_requiring_restart: Some(Version::new(4, 6, 0)),
             path: chart_path("common/charts/kube-state-metrics"),
@@ -331,6 +365,7 @@ datasources:
     let grafana = CommonChart {
         chart_info: ChartInfo {
             name: "grafana".to_string(),
+            depends_on: vec!["kube-prometheus-stack", "loki"],
             path: chart_path("common/charts/grafana"),
             namespace: prometheus_namespace,
             values_files: vec![chart_path("chart_values/grafana.yaml")],
@@ -346,6 +381,7 @@ datasources:
     let cert_manager = CommonChart {
         chart_info: ChartInfo {
             name: "cert-manager".to_string(),
+            depends_on: vec!["container-registry-secret"],
             path: chart_path("common/charts/cert-manager"),
             namespace: HelmChartNamespaces::CertManager,
             values: vec![
@@ -377,7 +413,10 @@ datasources:
                 },
                 ChartSetValue {
                     key: "prometheus.servicemonitor.enabled".to_string(),
-                    value: chart_config_prerequisites.ff_metrics_history_enabled.to_string(),
+                    value: chart_config_prerequisites
+                        .feature_flags
+                        .metrics_history_enabled
+                        .to_string(),
                 },
                 ChartSetValue {
                     key: "prometheus.servicemonitor.prometheusInstance".to_string(),
@@ -440,7 +479,7 @@ datasources:
         ..Default::default()
     };
 
-    let cert_manager_config = get_chart_for_cert_manager_config(
+    let mut cert_manager_config = get_chart_for_cert_manager_config(
         &chart_config_prerequisites.dns_provider_config,
         chart_path("common/charts/cert-manager-configs"),
         chart_config_prerequisites.dns_email_report.clone(),
@@ -451,6 +490,7 @@ datasources:
     let nginx_ingress = CommonChart {
         chart_info: ChartInfo {
             name: "nginx-ingress".to_string(),
+            depends_on: vec!["metrics-server", "externaldns"],
             path: chart_path("common/charts/ingress-nginx"),
             namespace: HelmChartNamespaces::NginxIngress,
             // Because of NLB, svc can take some time to start
@@ -504,6 +544,7 @@ datasources:
     let digital_mobius = CommonChart {
         chart_info: ChartInfo {
             name: "digital-mobius".to_string(),
+            depends_on: vec!["nginx-ingress"],
             path: chart_path("charts/digital-mobius"),
             values: vec![
                 ChartSetValue {
@@ -535,6 +576,7 @@ datasources:
     let pleco = CommonChart {
         chart_info: ChartInfo {
             name: "pleco".to_string(),
+            depends_on: vec!["metrics-server", "externaldns"],
             path: chart_path("common/charts/pleco"),
             values_files: vec![chart_path("chart_values/pleco-do.yaml")],
             values: vec![
@@ -576,13 +618,13 @@ datasources:
         cluster_long_id: &chart_config_prerequisites.cluster_long_id,
         cluster_jwt_token: &chart_config_prerequisites.infra_options.jwt_token,
         grpc_url: &chart_config_prerequisites.infra_options.qovery_grpc_url,
-        loki_url: if chart_config_prerequisites.ff_log_history_enabled {
+        loki_url: if chart_config_prerequisites.feature_flags.log_history_enabled {
             Some("http://loki.logging.svc.cluster.local:3100")
         } else {
             None
         },
     };
-    let cluster_agent = get_chart_for_cluster_agent(cluster_agent_context, chart_path, None)?;
+    let mut cluster_agent = get_chart_for_cluster_agent(cluster_agent_context, chart_path, None)?;
 
     let shell_context = ShellAgentContext {
         api_url: &chart_config_prerequisites.infra_options.qovery_api_url,
@@ -593,11 +635,12 @@ datasources:
         cluster_jwt_token: &chart_config_prerequisites.infra_options.jwt_token,
         grpc_url: &chart_config_prerequisites.infra_options.qovery_grpc_url,
     };
-    let shell_agent = get_chart_for_shell_agent(shell_context, chart_path, None)?;
+    let mut shell_agent = get_chart_for_shell_agent(shell_context, chart_path, None)?;
 
     let qovery_agent = CommonChart {
         chart_info: ChartInfo {
             name: "qovery-agent".to_string(),
+            depends_on: vec!["nginx-ingress"],
             path: chart_path("common/charts/qovery/qovery-agent"),
             namespace: HelmChartNamespaces::Qovery,
             action: Destroy,
@@ -616,6 +659,7 @@ datasources:
     let qovery_engine = CommonChart {
         chart_info: ChartInfo {
             name: "qovery-engine".to_string(),
+            depends_on: vec!["nginx-ingress"],
             action: get_engine_helm_action_from_location(&chart_config_prerequisites.qovery_engine_location),
             path: chart_path("common/charts/qovery-engine"),
             namespace: HelmChartNamespaces::Qovery,
@@ -631,7 +675,10 @@ datasources:
                 },
                 ChartSetValue {
                     key: "metrics.enabled".to_string(),
-                    value: chart_config_prerequisites.ff_metrics_history_enabled.to_string(),
+                    value: chart_config_prerequisites
+                        .feature_flags
+                        .metrics_history_enabled
+                        .to_string(),
                 },
                 ChartSetValue {
                     key: "volumes.storageClassName".to_string(),
@@ -715,27 +762,14 @@ datasources:
     let container_registry_secret = CommonChart {
         chart_info: ChartInfo {
             name: "container-registry-secret".to_string(),
+            depends_on: vec!["q-storageclass", "coredns"],
             path: chart_path("charts/container-registry-secret"),
             namespace: HelmChartNamespaces::KubeSystem,
             values_files: vec![chart_path("chart_values/container-registry-secret.yaml")],
             values: vec![
                 ChartSetValue {
                     key: "do_container_registry_docker_json_config".to_string(),
-                    // https://docs.digitalocean.com/products/container-registry/how-to/use-registry-docker-kubernetes/
-                    value: base64::encode(
-                        format!(
-                            r#"{{"auths":{{"registry.digitalocean.com":{{"auth":"{}"}}}}}}"#,
-                            base64::encode(
-                                format!(
-                                    "{}:{}",
-                                    chart_config_prerequisites.do_token.clone(),
-                                    chart_config_prerequisites.do_token.clone()
-                                )
-                                .as_bytes()
-                            )
-                        )
-                        .as_bytes(),
-                    ),
+                    value: docker_json_config(&chart_config_prerequisites.registry_credentials()),
                 },
                 ChartSetValue {
                     key: "do_container_registry_secret_identifier".to_string(),
@@ -755,46 +789,169 @@ datasources:
         ..Default::default()
     };
 
-    // chart deployment order matters!!!
-    let mut level_1: Vec<Box<dyn HelmChart>> = vec![Box::new(q_storage_class), Box::new(coredns_config)];
+    // chart deployment order matters!!! charts built by shared helpers get their dependencies here
+    loki.chart_info.depends_on = vec!["q-storageclass"];
+    prometheus_adapter.chart_info.depends_on = vec!["kube-prometheus-stack"];
+    external_dns.chart_info.depends_on = vec!["cert-manager"];
+    cert_manager_config.chart_info.depends_on = vec!["cert-manager", "nginx-ingress"];
+    cluster_agent.chart_info.depends_on = vec!["nginx-ingress"];
+    shell_agent.chart_info.depends_on = vec!["nginx-ingress"];
+
+    let mut charts: Vec<Box<dyn HelmChart>> = vec![
+        Box::new(q_storage_class),
+        Box::new(coredns_config),
+        Box::new(container_registry_secret),
+        Box::new(cert_manager),
+        Box::new(metrics_server),
+        Box::new(external_dns),
+        Box::new(nginx_ingress),
+    ];
 
-    let mut level_2: Vec<Box<dyn HelmChart>> = vec![Box::new(container_registry_secret)];
+    charts.extend(
+        FeatureFlaggedCharts {
+            kube_prometheus_stack: Box::new(kube_prometheus_stack),
+            prometheus_adapter: Box::new(prometheus_adapter),
+            kube_state_metrics: Box::new(kube_state_metrics),
+            promtail: Box::new(promtail),
+            loki: Box::new(loki),
+            grafana: Box::new(grafana),
+            pleco: Box::new(pleco),
+        }
+        .enabled_charts(&chart_config_prerequisites.feature_flags),
+    );
+
+    charts.extend(with_qovery_engine_chart(
+        &chart_config_prerequisites.qovery_engine_location,
+        vec![
+            Box::new(cert_manager_config),
+            Box::new(qovery_agent),
+            Box::new(cluster_agent),
+            Box::new(shell_agent),
+            Box::new(digital_mobius),
+        ],
+        Box::new(qovery_engine),
+    ));
 
-    let level_3: Vec<Box<dyn HelmChart>> = vec![Box::new(cert_manager)];
+    let mut levels = resolve_chart_levels(charts)?;
+    override_charts_timeout(
+        &mut levels,
+        &chart_config_prerequisites
+            .cluster_advanced_settings
+            .helm_charts_timeout_override_sec,
+    );
 
-    let level_4: Vec<Box<dyn HelmChart>> = vec![Box::new(metrics_server), Box::new(external_dns)];
+    info!("charts configuration preparation finished");
+    Ok(levels)
+}
 
-    let mut level_5: Vec<Box<dyn HelmChart>> = vec![Box::new(nginx_ingress)];
+/// Engine chart is only part of the cluster charts when the engine runs in it,
+/// when it runs elsewhere (agent only cluster) the chart is fully omitted.
+fn with_qovery_engine_chart(
+    qovery_engine_location: &EngineLocation,
+    mut charts: Vec<Box<dyn HelmChart>>,
+    qovery_engine: Box<dyn HelmChart>,
+) -> Vec<Box<dyn HelmChart>> {
+    if *qovery_engine_location == EngineLocation::ClientSide {
+        charts.push(qovery_engine);
+    }
 
-    let level_6: Vec<Box<dyn HelmChart>> = vec![
-        Box::new(cert_manager_config),
-        Box::new(qovery_agent),
-        Box::new(cluster_agent),
-        Box::new(shell_agent),
-        Box::new(qovery_engine),
-        Box::new(digital_mobius),
-    ];
+    charts
+}
 
-    // observability
-    if chart_config_prerequisites.ff_metrics_history_enabled {
-        level_1.push(Box::new(kube_prometheus_stack));
-        level_2.push(Box::new(prometheus_adapter));
-        level_2.push(Box::new(kube_state_metrics));
+/// Zero retention makes Loki drop every log while huge ones overflow its retention period computation,
+/// so retention has to be within `LOKI_LOG_RETENTION_MIN_WEEKS..=LOKI_LOG_RETENTION_MAX_WEEKS`.
+fn check_loki_log_retention(loki_log_retention_in_week: u32) -> Result<u32, CommandError> {
+    if !(LOKI_LOG_RETENTION_MIN_WEEKS..=LOKI_LOG_RETENTION_MAX_WEEKS).contains(&loki_log_retention_in_week) {
+        return Err(CommandError::new_from_safe_message(format!(
+            "Loki log retention of {} weeks is invalid, it should be between {} and {} weeks. Please update the `loki.log_retention_in_week` cluster advanced setting.",
+            loki_log_retention_in_week, LOKI_LOG_RETENTION_MIN_WEEKS, LOKI_LOG_RETENTION_MAX_WEEKS
+        )));
     }
-    if chart_config_prerequisites.ff_log_history_enabled {
-        level_1.push(Box::new(promtail));
-        level_2.push(Box::new(loki));
+
+    Ok(loki_log_retention_in_week)
+}
+
+/// Warning to show to the user when the retention is valid but exceeds what a Spaces bucket holds with a typical logs ingest.
+pub fn loki_log_retention_warning(loki_log_retention_in_week: u32) -> Option<String> {
+    if loki_log_retention_in_week <= LOKI_LOG_RETENTION_SPACES_CAPACITY_WEEKS {
+        return None;
     }
 
-    if chart_config_prerequisites.ff_metrics_history_enabled || chart_config_prerequisites.ff_log_history_enabled {
-        level_2.push(Box::new(grafana))
+    Some(format!(
+        "Loki log retention of {} weeks exceeds what a Spaces bucket holds with a typical logs ingest ({} weeks), extra storage will be billed",
+        loki_log_retention_in_week, LOKI_LOG_RETENTION_SPACES_CAPACITY_WEEKS
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::digitalocean::kubernetes::helm_charts::{
+        check_loki_log_retention, loki_log_retention_warning, with_qovery_engine_chart,
     };
+    use crate::cloud_provider::helm::{ChartInfo, CommonChart, HelmChart};
+    use crate::cloud_provider::qovery::EngineLocation;
+
+    fn chart(name: &str) -> Box<dyn HelmChart> {
+        Box::new(CommonChart {
+            chart_info: ChartInfo {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
 
-    // pleco
-    if !chart_config_prerequisites.disable_pleco {
-        level_5.push(Box::new(pleco));
+    #[test]
+    fn test_qovery_engine_chart_is_omitted_when_engine_is_not_in_cluster() {
+        for (location, expected_charts) in [
+            (
+                EngineLocation::ClientSide,
+                vec!["qovery-agent", "digital-mobius", "qovery-engine"],
+            ),
+            (EngineLocation::QoverySide, vec!["qovery-agent", "digital-mobius"]),
+        ] {
+            // execute:
+            let charts = with_qovery_engine_chart(
+                &location,
+                vec![chart("qovery-agent"), chart("digital-mobius")],
+                chart("qovery-engine"),
+            );
+
+            // verify:
+            assert_eq!(
+                expected_charts,
+                charts
+                    .iter()
+                    .map(|chart| chart.get_chart_info().name.as_str())
+                    .collect::<Vec<_>>(),
+                "unexpected charts for {:?}",
+                location
+            );
+        }
     }
 
-    info!("charts configuration preparation finished");
-    Ok(vec![level_1, level_2, level_3, level_4, level_5, level_6])
+    #[test]
+    fn test_check_loki_log_retention() {
+        for (retention_in_week, is_valid, has_warning) in [
+            (0, false, false),
+            (1, true, false),
+            (12, true, false),
+            (35, true, false),
+            (36, true, true),
+            (520, true, true),
+            (521, false, true),
+            (u32::MAX, false, true),
+        ] {
+            // execute:
+            let result = check_loki_log_retention(retention_in_week);
+            let warning = loki_log_retention_warning(retention_in_week);
+
+            // verify:
+            assert_eq!(is_valid, result.is_ok(), "retention: {} weeks", retention_in_week);
+            if is_valid {
+                assert_eq!(retention_in_week, result.unwrap());
+            }
+            assert_eq!(has_warning, warning.is_some(), "retention: {} weeks", retention_in_week);
+        }
+    }
 }
@@ -10,7 +10,9 @@ use crate::cloud_provider::digitalocean::do_api_common::{do_get_from_api, DoApiT
 use crate::cloud_provider::digitalocean::kubernetes::doks_api::{
     get_do_kubeconfig_by_cluster_name, get_do_latest_doks_slug_from_api, get_doks_info_from_name,
 };
-use crate::cloud_provider::digitalocean::kubernetes::helm_charts::{do_helm_charts, ChartsConfigPrerequisites};
+use crate::cloud_provider::digitalocean::kubernetes::helm_charts::{
+    do_helm_charts, loki_log_retention_warning, ChartsConfigPrerequisites,
+};
 use crate::cloud_provider::digitalocean::kubernetes::node::DoInstancesType;
 use crate::cloud_provider::digitalocean::models::doks::KubernetesCluster;
 use crate::cloud_provider::digitalocean::network::load_balancer::do_get_load_balancer_ip;
@@ -37,6 +39,7 @@ use crate::cmd::kubectl_utils::kubectl_are_qovery_infra_pods_executed;
 use crate::cmd::terraform::{
     terraform_init_validate_plan_apply, terraform_init_validate_state_list, terraform_state_rm_entry,
 };
+use crate::container_registry::RegistryCredential;
 use crate::deletion_utilities::{get_firsts_namespaces_to_delete, get_qovery_managed_namespaces};
 use crate::dns_provider::DnsProvider;
 use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
@@ -88,6 +91,9 @@ pub struct DoksOptions {
     pub qovery_ssh_key: String,
     #[serde(default)]
     pub user_ssh_keys: Vec<String>,
+    // registries, i.e: a private mirror, apps can pull images from in addition to the DO one
+    #[serde(default)]
+    pub additional_registry_credentials: Vec<RegistryCredential>,
     // Others
     pub tls_email_report: String,
 }
@@ -561,7 +567,11 @@ impl DOKS {
         }
 
         // terraform deployment dedicated to cloud resources
-        if let Err(e) = terraform_init_validate_plan_apply(temp_dir.as_str(), self.context.is_dry_run_deploy()) {
+        if let Err(e) = terraform_init_validate_plan_apply(
+            temp_dir.as_str(),
+            self.context.is_dry_run_deploy(),
+            self.advanced_settings().terraform_parallelism,
+        ) {
             return Err(EngineError::new_terraform_error(event_details, e));
         }
 
@@ -603,13 +613,13 @@ impl DOKS {
             cloud_provider: "digitalocean".to_string(),
             test_cluster: self.context.is_test_cluster(),
             do_token: self.cloud_provider.token().to_string(),
+            do_container_registry_host: self.cloud_provider.container_registry_host()?,
             do_space_access_id: self.cloud_provider.access_key_id(),
             do_space_secret_key: self.cloud_provider.secret_access_key(),
             do_space_bucket_kubeconfig: self.kubeconfig_bucket_name(),
             do_space_kubeconfig_filename: self.kubeconfig_file_name(),
             qovery_engine_location: self.options.qovery_engine_location.clone(),
-            ff_log_history_enabled: self.context.is_feature_enabled(&Features::LogsHistory),
-            ff_metrics_history_enabled: self.context.is_feature_enabled(&Features::MetricsHistory),
+            feature_flags: self.context.feature_flags(),
             managed_dns_name: self.dns_provider.domain().to_string(),
             managed_dns_helm_format: self.dns_provider.domain().to_helm_format_string(),
             managed_dns_resolvers_terraform_format: self.managed_dns_resolvers_terraform_format(),
@@ -618,7 +628,6 @@ impl DOKS {
             dns_email_report: self.options.tls_email_report.clone(),
             acme_url: self.lets_encrypt_url(),
             dns_provider_config: self.dns_provider().provider_configuration(),
-            disable_pleco: self.context.disable_pleco(),
             cluster_advanced_settings: self.advanced_settings.clone(),
         };
 
@@ -636,6 +645,13 @@ impl DOKS {
             EventMessage::new_from_safe("Preparing chart configuration to be deployed".to_string()),
         ));
 
+        if let Some(warning) = loki_log_retention_warning(self.advanced_settings().loki_log_retention_in_week) {
+            self.logger().log(EngineEvent::Warning(
+                event_details.clone(),
+                EventMessage::new_from_safe(warning),
+            ));
+        }
+
         let helm_charts_to_deploy = do_helm_charts(
             format!("{}/qovery-tf-config.json", &temp_dir).as_str(),
             &charts_prerequisites,
@@ -651,6 +667,7 @@ impl DOKS {
             &credentials_environment_variables,
             helm_charts_to_deploy,
             self.context.is_dry_run_deploy(),
+            self.advanced_settings().helm_charts_max_parallelism as usize,
         )
         .map_err(|e| EngineError::new_helm_charts_deploy_error(event_details.clone(), e))?;
 
@@ -683,7 +700,7 @@ impl DOKS {
                     CommandError::new(
                         format!("Load balancer IP wasn't able to be retrieved from UUID on DigitalOcean API and it's required for TLS setup, {}", e.message_safe()),
                     e.message_raw(),
-                        e.env_vars(),
+                        e.into_raw_env_vars(),
                 )));
             }
         };
@@ -825,7 +842,9 @@ impl DOKS {
             event_details.clone(),
             EventMessage::new_from_safe("Running Terraform apply before running a delete.".to_string()),
         ));
-        if let Err(e) = terraform_init_validate_plan_apply(temp_dir.as_str(), false) {
+        if let Err(e) =
+            terraform_init_validate_plan_apply(temp_dir.as_str(), false, self.advanced_settings().terraform_parallelism)
+        {
             // An issue occurred during the apply before destroy of Terraform, it may be expected if you're resuming a destroy
             self.logger().log(EngineEvent::Error(
                 EngineError::new_terraform_error(event_details.clone(), e),
@@ -1029,7 +1048,11 @@ impl DOKS {
             EventMessage::new_from_safe("Running Terraform destroy".to_string()),
         ));
 
-        match cmd::terraform::terraform_init_validate_destroy(temp_dir.as_str(), false) {
+        match cmd::terraform::terraform_init_validate_destroy(
+            temp_dir.as_str(),
+            false,
+            self.advanced_settings().terraform_parallelism,
+        ) {
             Ok(_) => {
                 self.logger().log(EngineEvent::Info(
                     event_details,
@@ -1372,7 +1395,11 @@ impl Kubernetes for DOKS {
             return Err(e);
         }
 
-        match terraform_init_validate_plan_apply(temp_dir.as_str(), self.context.is_dry_run_deploy()) {
+        match terraform_init_validate_plan_apply(
+            temp_dir.as_str(),
+            self.context.is_dry_run_deploy(),
+            self.advanced_settings().terraform_parallelism,
+        ) {
             Ok(_) => match self.check_workers_on_upgrade(kubernetes_upgrade_status.requested_version.to_string()) {
                 Ok(_) => {
                     self.logger().log(EngineEvent::Info(
@@ -1,16 +1,31 @@
 extern crate digitalocean;
 
 use std::any::Any;
+use std::str::FromStr;
 
+use aws_config::provider_config::ProviderConfig;
+use aws_config::SdkConfig;
+use aws_smithy_client::erase::DynConnector;
+use aws_smithy_client::never::NeverConnector;
+use aws_smithy_http::endpoint::Endpoint;
+use aws_types::os_shim_internal::Env;
 use digitalocean::DigitalOcean;
+use http::Uri;
+use lazy_static::lazy_static;
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 
-use crate::cloud_provider::{kubernetes::Kind as KubernetesKind, CloudProvider, Kind, TerraformStateCredentials};
+use crate::cloud_provider::{
+    kubernetes::Kind as KubernetesKind, CloudProvider, Kind, SupportedRegion, TerraformStateCredentials,
+};
 use crate::constants::DIGITAL_OCEAN_TOKEN;
-use crate::errors::EngineError;
+use crate::container_registry::docr::CR_REGISTRY_DOMAIN;
+use crate::errors::{CommandError, EngineError};
 use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
 use crate::io_models::context::Context;
 use crate::io_models::QoveryIdentifier;
+use crate::models::digital_ocean::DoRegion;
+use crate::runtime::block_on;
 use crate::utilities::to_short_id;
 
 pub mod do_api_common;
@@ -18,6 +33,23 @@ pub mod kubernetes;
 pub mod models;
 pub mod network;
 
+// personal access (`dop_v1_`) and OAuth (`doo_v1_`) tokens are a prefix followed by 64 hex chars,
+// legacy tokens created before the prefixes were introduced are the 64 hex chars alone
+const DO_TOKEN_PREFIXES: [&str; 3] = ["dop_v1_", "doo_v1_", ""];
+const DO_TOKEN_SECRET_LENGTH: usize = 64;
+
+/// Spaces (object storage) S3 compatible endpoint of a region.
+pub fn spaces_endpoint(region: &str) -> String {
+    format!("https://{}.digitaloceanspaces.com", region)
+}
+
+lazy_static! {
+    // DigitalOcean regions are single datacenters, so each region is its own zone
+    static ref DO_REGIONS: Vec<SupportedRegion> = DoRegion::iter()
+        .map(|region| SupportedRegion::new(region.human_name(), region.as_str(), vec![region.to_string()]))
+        .collect();
+}
+
 pub struct DO {
     context: Context,
     id: String,
@@ -27,6 +59,7 @@ pub struct DO {
     spaces_access_id: String,
     spaces_secret_key: String,
     region: String,
+    zones: Vec<String>,
     terraform_state_credentials: TerraformStateCredentials,
 }
 
@@ -40,8 +73,20 @@ impl DO {
         region: &str,
         name: &str,
         terraform_state_credentials: TerraformStateCredentials,
-    ) -> Self {
-        DO {
+    ) -> Result<Self, EngineError> {
+        if let Err(err) = DoRegion::from_str(region) {
+            let event_details = EventDetails::new(
+                None,
+                QoveryIdentifier::new(*context.organization_long_id()),
+                QoveryIdentifier::new(*context.cluster_long_id()),
+                context.execution_id().to_string(),
+                Stage::Infrastructure(InfrastructureStep::LoadConfiguration),
+                Transmitter::CloudProvider(long_id, name.to_string()),
+            );
+            return Err(EngineError::new_unsupported_region(event_details, region.to_string(), err));
+        }
+
+        Ok(DO {
             context,
             id: to_short_id(&long_id),
             long_id,
@@ -50,8 +95,9 @@ impl DO {
             spaces_access_id: spaces_access_id.to_string(),
             spaces_secret_key: spaces_secret_key.to_string(),
             region: region.to_string(),
+            zones: DoRegion::iter().map(|region| region.to_string()).collect(),
             terraform_state_credentials,
-        }
+        })
     }
 
     pub fn client(&self) -> DigitalOcean {
@@ -100,8 +146,26 @@ impl CloudProvider for DO {
         self.region.to_string()
     }
 
-    fn aws_sdk_client(&self) -> Option<aws_config::SdkConfig> {
-        None
+    /// Spaces is S3 compatible, AWS SDK clients built from this config talk to the region Spaces endpoint.
+    fn aws_sdk_client(&self) -> Option<SdkConfig> {
+        let endpoint = Uri::from_str(&spaces_endpoint(&self.region)).ok()?;
+        let env = Env::from_slice(&[
+            ("AWS_MAX_ATTEMPTS", "10"),
+            ("AWS_REGION", self.region.as_str()),
+            ("AWS_ACCESS_KEY_ID", self.spaces_access_id.as_str()),
+            ("AWS_SECRET_ACCESS_KEY", self.spaces_secret_key.as_str()),
+        ]);
+
+        Some(block_on(
+            aws_config::from_env()
+                .configure(
+                    ProviderConfig::empty()
+                        .with_env(env)
+                        .with_http_connector(DynConnector::new(NeverConnector::new())),
+                )
+                .endpoint_resolver(Endpoint::immutable(endpoint))
+                .load(),
+        ))
     }
 
     fn token(&self) -> &str {
@@ -110,6 +174,9 @@ impl CloudProvider for DO {
 
     fn is_valid(&self) -> Result<(), EngineError> {
         let event_details = self.get_event_details(Stage::Infrastructure(InfrastructureStep::RetrieveClusterConfig));
+        check_token_format(&self.token)
+            .map_err(|err| EngineError::new_error_on_cloud_provider_information(event_details.clone(), err))?;
+
         let client = DigitalOcean::new(&self.token);
         match client {
             Ok(_x) => Ok(()),
@@ -117,8 +184,17 @@ impl CloudProvider for DO {
         }
     }
 
+    /// DigitalOcean has no zones within a region, so every supported region is returned.
     fn zones(&self) -> &Vec<String> {
-        todo!()
+        &self.zones
+    }
+
+    fn supported_regions(&self) -> &'static [SupportedRegion] {
+        &DO_REGIONS
+    }
+
+    fn container_registry_host(&self) -> Result<String, EngineError> {
+        Ok(CR_REGISTRY_DOMAIN.to_string())
     }
 
     fn credentials_environment_variables(&self) -> Vec<(&str, &str)> {
@@ -153,3 +229,79 @@ impl CloudProvider for DO {
         Transmitter::CloudProvider(self.long_id, self.name.to_string())
     }
 }
+
+/// Catches obviously wrong tokens before any API call, which would otherwise only fail with a 401.
+fn check_token_format(token: &str) -> Result<(), CommandError> {
+    let hint = "DigitalOcean token should be a personal access token, looking like `dop_v1_<64 hex chars>`, or a legacy `<64 hex chars>` token";
+
+    if token.trim().is_empty() {
+        return Err(CommandError::new_from_safe_message(format!(
+            "DigitalOcean token is empty. {}",
+            hint
+        )));
+    }
+
+    if token.trim() != token {
+        return Err(CommandError::new_from_safe_message(format!(
+            "DigitalOcean token contains leading or trailing whitespaces. {}",
+            hint
+        )));
+    }
+
+    let is_valid_secret =
+        |secret: &str| secret.len() == DO_TOKEN_SECRET_LENGTH && secret.chars().all(|c| c.is_ascii_hexdigit());
+
+    match DO_TOKEN_PREFIXES
+        .iter()
+        .filter_map(|prefix| token.strip_prefix(prefix))
+        .any(is_valid_secret)
+    {
+        true => Ok(()),
+        false => Err(CommandError::new_from_safe_message(format!(
+            "DigitalOcean token format is invalid. {}",
+            hint
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::digitalocean::check_token_format;
+
+    #[test]
+    fn test_check_token_format() {
+        // setup:
+        let valid_token = format!("dop_v1_{}", "0a1b2c3d4e5f6789".repeat(4));
+
+        // execute & verify:
+        for token in [
+            valid_token.clone(),
+            format!("doo_v1_{}", "0a1b2c3d4e5f6789".repeat(4)),
+            "0a1b2c3d4e5f6789".repeat(4),
+        ] {
+            assert!(check_token_format(&token).is_ok(), "`{}` should be a valid token", token);
+        }
+        assert!(check_token_format("")
+            .expect_err("empty token must be rejected")
+            .message_safe()
+            .contains("token is empty"));
+
+        for invalid_token in [
+            "".to_string(),
+            "   ".to_string(),
+            format!(" {} ", valid_token),
+            "dop_v1_".to_string(),
+            "dop_v1_not-an-hexadecimal-secret".to_string(),
+            format!("dop_v1_{}", "z".repeat(64)),
+            format!("dop_v1_{}", "a".repeat(63)),
+            format!("dox_v1_{}", "a".repeat(64)),
+            "a".repeat(63),
+        ] {
+            assert!(
+                check_token_format(&invalid_token).is_err(),
+                "`{}` should be an invalid token",
+                invalid_token
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::cloud_provider::service::Action;
+use crate::cloud_provider::service::{Action, Service};
 
 use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
 use crate::io_models::context::Context;
@@ -9,8 +9,27 @@ use crate::models::database::DatabaseService;
 use crate::models::job::JobService;
 use crate::models::router::RouterService;
 use crate::utilities::to_short_id;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+/// Pods requested by a service to be created, at its minimum scale.
+pub struct RequestedPods {
+    pub service_name: String,
+    /// Kubernetes labels selecting the service pods, if it has any
+    pub selector: Option<String>,
+    pub pods: u32,
+}
+
+impl RequestedPods {
+    fn new(service: &dyn Service, pods: u32) -> RequestedPods {
+        RequestedPods {
+            service_name: service.name().to_string(),
+            selector: service.selector(),
+            pods,
+        }
+    }
+}
+
 pub struct Environment {
     namespace: String,
     event_details: EventDetails,
@@ -80,4 +99,137 @@ impl Environment {
     pub fn event_details(&self) -> &EventDetails {
         &self.event_details
     }
+
+    /// Returns the number of pods each service to be created requests, at its minimum scale.
+    /// Routers and managed databases don't run any pod in the cluster.
+    pub fn requested_pods(&self) -> Vec<RequestedPods> {
+        let to_create = |action: &Action| *action == Action::Create;
+
+        std::iter::empty()
+            .chain(
+                self.databases
+                    .iter()
+                    .filter(|db| to_create(db.action()) && !db.is_managed_service())
+                    .map(|db| RequestedPods::new(db.as_service(), 1)),
+            )
+            .chain(
+                self.jobs
+                    .iter()
+                    .filter(|job| to_create(job.action()))
+                    .map(|job| RequestedPods::new(job.as_service(), 1)),
+            )
+            .chain(
+                self.containers
+                    .iter()
+                    .filter(|container| to_create(container.action()))
+                    .map(|container| RequestedPods::new(container.as_service(), container.min_instances())),
+            )
+            .chain(
+                self.applications
+                    .iter()
+                    .filter(|app| to_create(app.action()))
+                    .map(|app| RequestedPods::new(app.as_service(), app.min_instances())),
+            )
+            .collect()
+    }
+
+    /// Returns services names whose sanitized names collide, grouped by sanitized name.
+    /// Jobs are not checked as their sanitized name is deprecated.
+    pub fn sanitized_name_collisions(&self) -> Vec<(String, Vec<String>)> {
+        let names = std::iter::empty()
+            .chain(
+                self.applications
+                    .iter()
+                    .map(|s| (s.name().to_string(), s.sanitized_name())),
+            )
+            .chain(
+                self.containers
+                    .iter()
+                    .map(|s| (s.name().to_string(), s.sanitized_name())),
+            )
+            .chain(self.routers.iter().map(|s| (s.name().to_string(), s.sanitized_name())))
+            .chain(
+                self.databases
+                    .iter()
+                    .map(|s| (s.name().to_string(), s.sanitized_name())),
+            );
+
+        find_sanitized_name_collisions(names)
+    }
+}
+
+fn find_sanitized_name_collisions(names: impl Iterator<Item = (String, String)>) -> Vec<(String, Vec<String>)> {
+    let mut services_by_sanitized_name: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (name, sanitized_name) in names {
+        services_by_sanitized_name.entry(sanitized_name).or_default().push(name);
+    }
+
+    services_by_sanitized_name
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::environment::find_sanitized_name_collisions;
+    use crate::cloud_provider::utilities::{managed_db_name_sanitizer, sanitize_name};
+
+    #[test]
+    fn test_find_sanitized_name_collisions() {
+        // setup: applications and routers only swap `_` for `-`, aws databases drop them and are truncated
+        let names = vec![
+            ("my-app".to_string(), sanitize_name("app", "z1234_abcd")),
+            ("my-other-app".to_string(), sanitize_name("app", "z1234-abcd")),
+            ("my-router".to_string(), sanitize_name("router", "z1234_abcd")),
+            ("my-db".to_string(), managed_db_name_sanitizer(60, "postgresql", "zabcd_1234")),
+            (
+                "my-other-db".to_string(),
+                managed_db_name_sanitizer(60, "postgresql", "zabcd-1234"),
+            ),
+            (
+                "my-long-db".to_string(),
+                managed_db_name_sanitizer(30, "mysql", "z12345678901234567890a"),
+            ),
+            (
+                "my-other-long-db".to_string(),
+                managed_db_name_sanitizer(30, "mysql", "z12345678901234567890b"),
+            ),
+        ];
+
+        // execute:
+        let collisions = find_sanitized_name_collisions(names.into_iter());
+
+        // verify:
+        assert_eq!(
+            vec![
+                (
+                    "app-z1234-abcd".to_string(),
+                    vec!["my-app".to_string(), "my-other-app".to_string()]
+                ),
+                (
+                    "mysqlz1234567890123456789".to_string(),
+                    vec!["my-long-db".to_string(), "my-other-long-db".to_string()]
+                ),
+                (
+                    "postgresqlzabcd1234".to_string(),
+                    vec!["my-db".to_string(), "my-other-db".to_string()]
+                ),
+            ],
+            collisions
+        );
+    }
+
+    #[test]
+    fn test_find_sanitized_name_collisions_none() {
+        // setup:
+        let names = vec![
+            ("my-app".to_string(), sanitize_name("app", "z1234-abcd")),
+            ("my-router".to_string(), sanitize_name("router", "z1234-abcd")),
+            ("my-db".to_string(), managed_db_name_sanitizer(60, "postgresql", "z1234-abcd")),
+        ];
+
+        // execute & verify:
+        assert!(find_sanitized_name_collisions(names.into_iter()).is_empty());
+    }
 }
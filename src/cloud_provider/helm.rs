@@ -10,13 +10,16 @@ use crate::cmd::kubectl::{kubectl_delete_crash_looping_pods, kubectl_exec_delete
 use crate::cmd::structs::HelmHistoryRow;
 use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::{CommandError, ErrorMessageVerbosity};
+use crate::io_models::context::FeatureFlags;
 
 use semver::Version;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 
 use crate::cmd::command::CommandKiller;
+use std::cmp::{max, min};
+use std::sync::Mutex;
 use std::{fs, thread};
 use uuid::Uuid;
 
@@ -25,6 +28,8 @@ pub enum HelmAction {
     Deploy,
     Destroy,
     Skip,
+    /// Upgrades the chart only if it is already installed, it is never installed by this action.
+    Upgrade,
 }
 
 #[derive(Copy, Clone)]
@@ -60,12 +65,60 @@ pub struct ChartSetValue {
     pub value: String,
 }
 
+/// Value passed with `--set-json`, for nested objects or arrays which can't be expressed with a flat key=value.
+#[derive(Clone)]
+pub struct ChartSetJsonValue {
+    pub key: String,
+    pub json: String,
+}
+
 #[derive(Clone)]
 pub struct ChartValuesGenerated {
     pub filename: String,
     pub yaml_content: String,
 }
 
+/// Remote repository a chart is pulled from, instead of the vendored chart directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChartRepository {
+    /// Classic helm repository served over HTTP(S), registered under `name` with `helm repo add`.
+    Http {
+        name: String,
+        url: String,
+        chart: String,
+        version: Option<String>,
+    },
+    /// OCI registry holding the chart, i.e: `oci://registry-1.docker.io/bitnamicharts`.
+    Oci {
+        url: String,
+        chart: String,
+        version: Option<String>,
+    },
+}
+
+impl ChartRepository {
+    /// Chart reference given to helm, i.e: `bitnami/redis` or `oci://registry-1.docker.io/bitnamicharts/redis`.
+    pub fn chart_reference(&self) -> String {
+        match self {
+            ChartRepository::Http { name, chart, .. } => format!("{}/{}", name, chart),
+            ChartRepository::Oci { url, chart, .. } => {
+                let url = url.trim_end_matches('/');
+                match url.starts_with("oci://") {
+                    true => format!("{}/{}", url, chart),
+                    false => format!("oci://{}/{}", url, chart),
+                }
+            }
+        }
+    }
+
+    /// Chart version to pull, the latest one if not set.
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            ChartRepository::Http { version, .. } | ChartRepository::Oci { version, .. } => version.as_deref(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ChartInfo {
     pub name: String,
@@ -83,12 +136,19 @@ pub struct ChartInfo {
     /// Values used to override values set inside values files.
     pub values: Vec<ChartSetValue>,
     pub values_string: Vec<ChartSetValue>,
+    pub values_json: Vec<ChartSetJsonValue>,
     pub values_files: Vec<String>,
     pub yaml_files_content: Vec<ChartValuesGenerated>,
     pub parse_stderr_for_error: bool,
     pub k8s_selector: Option<String>,
     pub backup_resources: Option<Vec<String>>,
     pub crds_update: Option<CRDSUpdate>,
+    /// Executable passed to helm `--post-renderer` to patch rendered manifests (i.e: kustomize).
+    pub post_renderer: Option<PathBuf>,
+    /// Names of the charts which have to be deployed before this one, see `resolve_chart_levels`.
+    pub depends_on: Vec<&'static str>,
+    /// Remote repository to pull the chart from, `path` is then only used to write generated values files.
+    pub repository: Option<ChartRepository>,
 }
 
 impl ChartInfo {
@@ -136,6 +196,24 @@ impl ChartInfo {
             _ => self.namespace.to_string(),
         }
     }
+
+    /// Overrides `values` in place: an override replaces the existing value with the same key,
+    /// keeping its position, while overrides for new keys are appended in the given order.
+    pub fn override_values(&mut self, overrides: Vec<ChartSetValue>) {
+        for value_override in overrides {
+            match self.values.iter_mut().find(|value| value.key == value_override.key) {
+                Some(value) => value.value = value_override.value,
+                None => self.values.push(value_override),
+            }
+        }
+    }
+
+    /// Overrides `timeout_in_seconds` if `overrides` has one for this chart name, the chart default is kept otherwise.
+    pub fn override_timeout(&mut self, overrides: &HashMap<String, u32>) {
+        if let Some(timeout_in_seconds) = overrides.get(&self.name) {
+            self.timeout_in_seconds = *timeout_in_seconds as i64;
+        }
+    }
 }
 
 impl Default for ChartInfo {
@@ -155,12 +233,16 @@ impl Default for ChartInfo {
             wait: true,
             values: vec![],
             values_string: vec![], // values to force string usage
+            values_json: vec![],
             values_files: vec![],
             yaml_files_content: vec![],
             parse_stderr_for_error: true,
             k8s_selector: None,
             backup_resources: None,
             crds_update: None,
+            post_renderer: None,
+            depends_on: vec![],
+            repository: None,
         }
     }
 }
@@ -186,6 +268,12 @@ pub trait HelmChart: Send {
 
     fn get_chart_info(&self) -> &ChartInfo;
 
+    fn get_chart_info_mut(&mut self) -> &mut ChartInfo;
+
+    fn depends_on(&self) -> Vec<&'static str> {
+        self.get_chart_info().depends_on.clone()
+    }
+
     fn namespace(&self) -> String {
         self.get_chart_info().get_namespace_string()
     }
@@ -329,6 +417,10 @@ pub trait HelmChart: Send {
                 }
                 helm.uninstall(chart_info, &[]).map_err(to_command_error)?;
             }
+            HelmAction::Upgrade => {
+                helm.upgrade_if_exists(chart_info, &[], &CommandKiller::never())
+                    .map_err(to_command_error)?;
+            }
             HelmAction::Skip => {}
         }
         Ok(payload)
@@ -367,36 +459,48 @@ pub trait HelmChart: Send {
     }
 }
 
-fn deploy_parallel_charts(
-    kube_client: &kube::Client,
-    kubernetes_config: &Path,
-    envs: &[(String, String)],
+/// Runs the charts of a level on at most `max_parallelism` threads, each one picking the next chart to run as soon
+/// as it is done with the previous one. Every chart is run even if one fails, the error of the first failing chart
+/// (in level order) is returned.
+fn run_charts_in_parallel<F>(
     charts: Vec<Box<dyn HelmChart>>,
-) -> Result<(), CommandError> {
-    thread::scope(|s| {
-        let mut handles = vec![];
-
-        for chart in charts.into_iter() {
-            let environment_variables = envs.to_owned();
-            let path = kubernetes_config.to_path_buf();
-            let current_span = tracing::Span::current();
-            let handle = s.spawn(move || {
-                // making sure to pass the current span to the new thread not to lose any tracing info
-                let _ = current_span.enter();
-                chart.run(kube_client, path.as_path(), &environment_variables)
-            });
-
-            handles.push(handle);
-        }
-
-        let mut errors: Vec<Result<(), CommandError>> = vec![];
-        for handle in handles {
-            match handle.join() {
-                Ok(helm_run_ret) => {
-                    if let Err(e) = helm_run_ret {
-                        errors.push(Err(e));
+    max_parallelism: usize,
+    run: &F,
+) -> Result<(), CommandError>
+where
+    F: Fn(&dyn HelmChart) -> Result<(), CommandError> + Sync,
+{
+    let workers_count = min(max(max_parallelism, 1), charts.len());
+    let queue = Mutex::new(charts.into_iter().enumerate());
+
+    let mut errors: Vec<(usize, CommandError)> = thread::scope(|s| {
+        let queue = &queue;
+        let handles: Vec<_> = (0..workers_count)
+            .map(|_| {
+                let current_span = tracing::Span::current();
+                s.spawn(move || {
+                    // making sure to pass the current span to the new thread not to lose any tracing info
+                    let _enter = current_span.enter();
+                    let mut errors = vec![];
+                    loop {
+                        let next_chart = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                        match next_chart {
+                            Some((index, chart)) => {
+                                if let Err(e) = run(chart.as_ref()) {
+                                    errors.push((index, e));
+                                }
+                            }
+                            None => return errors,
+                        }
                     }
-                }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(errors) => errors,
                 Err(e) => {
                     let err = match e.downcast_ref::<&'static str>() {
                         None => match e.downcast_ref::<String>() {
@@ -405,55 +509,73 @@ fn deploy_parallel_charts(
                         },
                         Some(s) => *s,
                     };
-                    let error = Err(CommandError::new(
-                        "Thread panicked during parallel charts deployments.".to_string(),
-                        Some(err.to_string()),
-                        None,
-                    ));
-                    errors.push(error);
+                    vec![(
+                        usize::MAX,
+                        CommandError::new(
+                            "Thread panicked during parallel charts deployments.".to_string(),
+                            Some(err.to_string()),
+                            None,
+                        ),
+                    )]
                 }
-            }
-        }
+            })
+            .collect()
+    });
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.sort_by_key(|(index, _)| *index);
+        error!("Deployments of charts failed with: {:?}", errors);
+        Err(errors.remove(0).1)
+    }
+}
 
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            error!("Deployments of charts failed with: {:?}", errors);
-            errors.remove(0)
-        }
-    })
+/// Runs levels one after the other, charts of a same level being run in parallel.
+fn run_charts_levels<F>(
+    levels: Vec<Vec<Box<dyn HelmChart>>>,
+    max_parallelism: usize,
+    run: &F,
+) -> Result<(), CommandError>
+where
+    F: Fn(&dyn HelmChart) -> Result<(), CommandError> + Sync,
+{
+    for level in levels {
+        run_charts_in_parallel(level, max_parallelism, run)?;
+    }
+
+    Ok(())
 }
 
+/// Deploys levels one after the other, at most `max_parallelism` charts of a same level being deployed at once.
 pub fn deploy_charts_levels(
     kube_client: &kube::Client,
     kubernetes_config: &Path,
     envs: &[(String, String)],
     charts: Vec<Vec<Box<dyn HelmChart>>>,
     dry_run: bool,
+    max_parallelism: usize,
 ) -> Result<(), CommandError> {
-    // first show diff
     let envs_ref: Vec<(&str, &str)> = envs.iter().map(|(x, y)| (x.as_str(), y.as_str())).collect();
     let helm = Helm::new(&kubernetes_config, &envs_ref).map_err(to_command_error)?;
-
-    for level in charts {
-        // Show diff for all chart in this state
-        for chart in &level {
-            let chart_info = chart.get_chart_info();
-            // don't do diff on destroy or skip
-            if chart_info.action == Deploy {
-                let _ = helm.upgrade_diff(chart_info, &[]);
-            }
-        }
-
-        // Skip actual deployment if dry run
-        if dry_run {
-            continue;
+    let show_diff = |chart: &dyn HelmChart| {
+        let chart_info = chart.get_chart_info();
+        // don't do diff on destroy or skip
+        if chart_info.action == Deploy {
+            let _ = helm.upgrade_diff(chart_info, &[]);
         }
+    };
 
-        deploy_parallel_charts(kube_client, kubernetes_config, envs, level)?
+    // Skip actual deployment if dry run
+    if dry_run {
+        charts.iter().flatten().for_each(|chart| show_diff(chart.as_ref()));
+        return Ok(());
     }
 
-    Ok(())
+    run_charts_levels(charts, max_parallelism, &|chart: &dyn HelmChart| {
+        show_diff(chart);
+        chart.run(kube_client, kubernetes_config, envs).map(|_| ())
+    })
 }
 
 //
@@ -491,6 +613,10 @@ impl HelmChart for CommonChart {
         &self.chart_info
     }
 
+    fn get_chart_info_mut(&mut self) -> &mut ChartInfo {
+        &mut self.chart_info
+    }
+
     fn post_exec(
         &self,
         kube_client: &kube::Client,
@@ -509,6 +635,103 @@ impl HelmChart for CommonChart {
     }
 }
 
+/// Charts only deployed when enabled by feature flags, the same for every cloud provider
+pub struct FeatureFlaggedCharts {
+    pub kube_prometheus_stack: Box<dyn HelmChart>,
+    pub prometheus_adapter: Box<dyn HelmChart>,
+    pub kube_state_metrics: Box<dyn HelmChart>,
+    pub promtail: Box<dyn HelmChart>,
+    pub loki: Box<dyn HelmChart>,
+    pub grafana: Box<dyn HelmChart>,
+    pub pleco: Box<dyn HelmChart>,
+}
+
+impl FeatureFlaggedCharts {
+    /// Adds enabled charts to their deployment level: observability charts go to the first two levels,
+    /// pleco goes to `pleco_level` (alongside nginx ingress).
+    pub fn add_to_levels(
+        self,
+        feature_flags: &FeatureFlags,
+        level_1: &mut Vec<Box<dyn HelmChart>>,
+        level_2: &mut Vec<Box<dyn HelmChart>>,
+        pleco_level: &mut Vec<Box<dyn HelmChart>>,
+    ) {
+        // observability
+        if feature_flags.metrics_history_enabled {
+            level_1.push(self.kube_prometheus_stack);
+            level_2.push(self.prometheus_adapter);
+            level_2.push(self.kube_state_metrics);
+        }
+        if feature_flags.log_history_enabled {
+            level_1.push(self.promtail);
+            level_2.push(self.loki);
+        }
+        if feature_flags.observability_enabled() {
+            level_2.push(self.grafana);
+        }
+
+        // pleco
+        if !feature_flags.disable_pleco {
+            pleco_level.push(self.pleco);
+        }
+    }
+
+    /// Returns enabled charts as a flat list, ordering is left to the charts declared dependencies.
+    pub fn enabled_charts(self, feature_flags: &FeatureFlags) -> Vec<Box<dyn HelmChart>> {
+        let mut level_1 = vec![];
+        let mut level_2 = vec![];
+        let mut pleco_level = vec![];
+        self.add_to_levels(feature_flags, &mut level_1, &mut level_2, &mut pleco_level);
+
+        level_1.into_iter().chain(level_2).chain(pleco_level).collect()
+    }
+}
+
+/// Overrides charts timeout from cluster advanced settings, by chart name.
+pub fn override_charts_timeout(charts: &mut [Vec<Box<dyn HelmChart>>], overrides: &HashMap<String, u32>) {
+    charts
+        .iter_mut()
+        .flatten()
+        .for_each(|chart| chart.get_chart_info_mut().override_timeout(overrides));
+}
+
+/// Groups charts into deployment levels from their declared dependencies: a chart goes to the level following
+/// the one of its deepest dependency, charts without dependencies go to the first level.
+/// Charts keep their relative order within a level. Dependencies on charts absent from the list
+/// (i.e: disabled by a feature flag) are ignored.
+pub fn resolve_chart_levels(charts: Vec<Box<dyn HelmChart>>) -> Result<Vec<Vec<Box<dyn HelmChart>>>, CommandError> {
+    let available_charts: HashSet<String> = charts.iter().map(|c| c.get_chart_info().name.clone()).collect();
+    let mut deployed_charts: HashSet<String> = HashSet::new();
+    let mut remaining_charts = charts;
+    let mut levels = vec![];
+
+    while !remaining_charts.is_empty() {
+        let (level, blocked_charts): (Vec<_>, Vec<_>) = remaining_charts.into_iter().partition(|chart| {
+            chart
+                .depends_on()
+                .iter()
+                .all(|dependency| deployed_charts.contains(*dependency) || !available_charts.contains(*dependency))
+        });
+
+        if level.is_empty() {
+            return Err(CommandError::new_from_safe_message(format!(
+                "Cannot resolve helm charts deployment order, dependency cycle between charts: {}",
+                blocked_charts
+                    .iter()
+                    .map(|c| c.get_chart_info().name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        deployed_charts.extend(level.iter().map(|c| c.get_chart_info().name.clone()));
+        levels.push(level);
+        remaining_charts = blocked_charts;
+    }
+
+    Ok(levels)
+}
+
 pub fn get_latest_successful_deployment(helm_history_list: &[HelmHistoryRow]) -> Result<HelmHistoryRow, CommandError> {
     let mut helm_history_reversed = helm_history_list.to_owned();
     helm_history_reversed.reverse();
@@ -811,8 +1034,200 @@ pub fn get_chart_for_cert_manager_config(
 
 #[cfg(test)]
 mod tests {
-    use crate::cloud_provider::helm::get_latest_successful_deployment;
+    use crate::cloud_provider::helm::{
+        get_latest_successful_deployment, override_charts_timeout, resolve_chart_levels, run_charts_levels, ChartInfo,
+        ChartSetValue, CommonChart, FeatureFlaggedCharts, HelmChart,
+    };
     use crate::cmd::structs::HelmHistoryRow;
+    use crate::errors::CommandError;
+    use crate::io_models::context::FeatureFlags;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    fn chart_names(charts: &[Box<dyn HelmChart>]) -> Vec<String> {
+        charts.iter().map(|c| c.get_chart_info().name.clone()).collect()
+    }
+
+    fn feature_flagged_charts() -> FeatureFlaggedCharts {
+        let chart = |name: &str| -> Box<dyn HelmChart> {
+            Box::new(CommonChart {
+                chart_info: ChartInfo {
+                    name: name.to_string(),
+                    ..Default::default()
+                },
+                chart_installation_checker: None,
+            })
+        };
+
+        FeatureFlaggedCharts {
+            kube_prometheus_stack: chart("kube-prometheus-stack"),
+            prometheus_adapter: chart("prometheus-adapter"),
+            kube_state_metrics: chart("kube-state-metrics"),
+            promtail: chart("promtail"),
+            loki: chart("loki"),
+            grafana: chart("grafana"),
+            pleco: chart("pleco"),
+        }
+    }
+
+    #[test]
+    fn test_feature_flagged_charts_add_to_levels() {
+        // setup:
+        struct TestCase {
+            feature_flags: FeatureFlags,
+            expected_level_1: Vec<&'static str>,
+            expected_level_2: Vec<&'static str>,
+            expected_pleco_level: Vec<&'static str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                feature_flags: FeatureFlags::default(),
+                expected_level_1: vec![],
+                expected_level_2: vec![],
+                expected_pleco_level: vec!["pleco"],
+            },
+            TestCase {
+                feature_flags: FeatureFlags {
+                    log_history_enabled: false,
+                    metrics_history_enabled: true,
+                    disable_pleco: false,
+                },
+                expected_level_1: vec!["kube-prometheus-stack"],
+                expected_level_2: vec!["prometheus-adapter", "kube-state-metrics", "grafana"],
+                expected_pleco_level: vec!["pleco"],
+            },
+            TestCase {
+                feature_flags: FeatureFlags {
+                    log_history_enabled: true,
+                    metrics_history_enabled: true,
+                    disable_pleco: true,
+                },
+                expected_level_1: vec!["kube-prometheus-stack", "promtail"],
+                expected_level_2: vec!["prometheus-adapter", "kube-state-metrics", "loki", "grafana"],
+                expected_pleco_level: vec![],
+            },
+        ];
+
+        for tc in test_cases {
+            let mut level_1: Vec<Box<dyn HelmChart>> = vec![];
+            let mut level_2: Vec<Box<dyn HelmChart>> = vec![];
+            let mut pleco_level: Vec<Box<dyn HelmChart>> = vec![];
+
+            // execute:
+            feature_flagged_charts().add_to_levels(&tc.feature_flags, &mut level_1, &mut level_2, &mut pleco_level);
+
+            // verify:
+            assert_eq!(tc.expected_level_1, chart_names(&level_1), "{:?}", tc.feature_flags);
+            assert_eq!(tc.expected_level_2, chart_names(&level_2), "{:?}", tc.feature_flags);
+            assert_eq!(tc.expected_pleco_level, chart_names(&pleco_level), "{:?}", tc.feature_flags);
+        }
+    }
+
+    #[test]
+    fn test_feature_flagged_charts_enabled_charts() {
+        for (feature_flags, expected_charts) in [
+            (
+                FeatureFlags {
+                    log_history_enabled: true,
+                    metrics_history_enabled: true,
+                    disable_pleco: false,
+                },
+                vec![
+                    "kube-prometheus-stack",
+                    "promtail",
+                    "prometheus-adapter",
+                    "kube-state-metrics",
+                    "loki",
+                    "grafana",
+                    "pleco",
+                ],
+            ),
+            (
+                FeatureFlags {
+                    log_history_enabled: false,
+                    metrics_history_enabled: false,
+                    disable_pleco: true,
+                },
+                vec![],
+            ),
+            (
+                FeatureFlags {
+                    log_history_enabled: true,
+                    metrics_history_enabled: false,
+                    disable_pleco: true,
+                },
+                vec!["promtail", "loki", "grafana"],
+            ),
+        ] {
+            // execute:
+            let charts = feature_flagged_charts().enabled_charts(&feature_flags);
+
+            // verify:
+            assert_eq!(expected_charts, chart_names(&charts), "{:?}", feature_flags);
+        }
+    }
+
+    fn chart_with_dependencies(name: &str, depends_on: Vec<&'static str>) -> Box<dyn HelmChart> {
+        Box::new(CommonChart {
+            chart_info: ChartInfo {
+                name: name.to_string(),
+                depends_on,
+                ..Default::default()
+            },
+            chart_installation_checker: None,
+        })
+    }
+
+    #[test]
+    fn test_resolve_chart_levels() {
+        // setup:
+        let charts = vec![
+            chart_with_dependencies("ingress", vec!["cert-manager", "dns"]),
+            chart_with_dependencies("storage-class", vec![]),
+            chart_with_dependencies("cert-manager", vec!["dns"]),
+            chart_with_dependencies("dns", vec![]),
+            chart_with_dependencies("agent", vec!["ingress", "disabled-by-feature-flag"]),
+            chart_with_dependencies("metrics", vec!["storage-class"]),
+        ];
+
+        // execute:
+        let levels = resolve_chart_levels(charts).expect("charts levels should be resolved");
+
+        // verify:
+        assert_eq!(
+            vec![
+                vec!["storage-class", "dns"],
+                vec!["cert-manager", "metrics"],
+                vec!["ingress"],
+                vec!["agent"],
+            ],
+            levels.iter().map(|level| chart_names(level)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_resolve_chart_levels_detects_cycles() {
+        // setup:
+        let charts = vec![
+            chart_with_dependencies("dns", vec![]),
+            chart_with_dependencies("cert-manager", vec!["dns", "ingress"]),
+            chart_with_dependencies("ingress", vec!["cert-manager"]),
+        ];
+
+        // execute:
+        let result = resolve_chart_levels(charts);
+
+        // verify:
+        let error = result.err().expect("a dependency cycle should be detected");
+        assert_eq!(
+            "Cannot resolve helm charts deployment order, dependency cycle between charts: cert-manager, ingress",
+            error.message_safe()
+        );
+    }
 
     #[test]
     fn test_last_succeeded_deployment() {
@@ -849,4 +1264,156 @@ mod tests {
         let final_succeed = get_latest_successful_deployment(&results).unwrap();
         assert_eq!(results[1].updated, final_succeed.updated);
     }
+
+    #[test]
+    fn test_chart_info_override_values() {
+        // setup:
+        let value = |key: &str, value: &str| ChartSetValue {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        let mut chart_info = ChartInfo {
+            values: vec![
+                value("resources.limits.cpu", "100m"),
+                value("resources.limits.memory", "128Mi"),
+                value("replicaCount", "1"),
+            ],
+            ..Default::default()
+        };
+
+        // execute:
+        chart_info.override_values(vec![
+            value("resources.limits.cpu", "500m"),
+            value("resources.requests.cpu", "250m"),
+            value("replicaCount", "2"),
+        ]);
+
+        // verify:
+        assert_eq!(
+            vec![
+                ("resources.limits.cpu", "500m"),
+                ("resources.limits.memory", "128Mi"),
+                ("replicaCount", "2"),
+                ("resources.requests.cpu", "250m"),
+            ],
+            chart_info
+                .values
+                .iter()
+                .map(|v| (v.key.as_str(), v.value.as_str()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_override_charts_timeout() {
+        // setup:
+        let chart = |name: &str, timeout_in_seconds: i64| -> Box<dyn HelmChart> {
+            Box::new(CommonChart {
+                chart_info: ChartInfo {
+                    name: name.to_string(),
+                    timeout_in_seconds,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+        let mut charts = vec![
+            vec![chart("nginx-ingress", 800), chart("cert-manager", 600)],
+            vec![chart("loki", 900)],
+        ];
+        let overrides = HashMap::from([("nginx-ingress".to_string(), 1800), ("unknown".to_string(), 10)]);
+
+        // execute:
+        override_charts_timeout(&mut charts, &overrides);
+
+        // verify:
+        assert_eq!(
+            vec![("nginx-ingress", 1800), ("cert-manager", 600), ("loki", 900)],
+            charts
+                .iter()
+                .flatten()
+                .map(|c| (c.get_chart_info().name.as_str(), c.get_chart_info().timeout_in_seconds))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    fn named_chart(name: &str) -> Box<dyn HelmChart> {
+        Box::new(CommonChart {
+            chart_info: ChartInfo {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            chart_installation_checker: None,
+        })
+    }
+
+    #[test]
+    fn test_run_charts_levels_is_parallel_within_levels_and_sequential_across_levels() {
+        // setup:
+        let levels = vec![
+            ["a", "b", "c", "d", "e"].iter().map(|name| named_chart(name)).collect(),
+            ["f", "g"].iter().map(|name| named_chart(name)).collect(),
+        ];
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+        let events = Mutex::new(vec![]);
+        let run = |chart: &dyn HelmChart| -> Result<(), CommandError> {
+            let name = chart.get_chart_info().name.clone();
+            events.lock().unwrap().push(format!("start {}", name));
+            let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(now_running, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(50));
+            running.fetch_sub(1, Ordering::SeqCst);
+            events.lock().unwrap().push(format!("end {}", name));
+            Ok(())
+        };
+
+        // execute:
+        let ret = run_charts_levels(levels, 3, &run);
+
+        // verify:
+        assert!(ret.is_ok());
+        assert_eq!(3, max_running.load(Ordering::SeqCst));
+        let events = events.into_inner().unwrap();
+        assert_eq!(14, events.len());
+        let position = |event: &str| events.iter().position(|e| e == event).unwrap();
+        let last_end_of_first_level = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| position(&format!("end {}", name)))
+            .max()
+            .unwrap();
+        let first_start_of_second_level = ["f", "g"]
+            .iter()
+            .map(|name| position(&format!("start {}", name)))
+            .min()
+            .unwrap();
+        assert!(last_end_of_first_level < first_start_of_second_level);
+    }
+
+    #[test]
+    fn test_run_charts_levels_stops_after_failing_level() {
+        // setup:
+        let levels = vec![
+            vec![named_chart("a"), named_chart("b"), named_chart("c")],
+            vec![named_chart("d")],
+        ];
+        let ran = Mutex::new(vec![]);
+        let run = |chart: &dyn HelmChart| -> Result<(), CommandError> {
+            let name = chart.get_chart_info().name.clone();
+            ran.lock().unwrap().push(name.clone());
+            match name.as_str() {
+                "b" | "c" => Err(CommandError::new_from_safe_message(format!("{} failed", name))),
+                _ => Ok(()),
+            }
+        };
+
+        // execute:
+        let ret = run_charts_levels(levels, 2, &run);
+
+        // verify:
+        assert_eq!("b failed", ret.expect_err("level should fail").message_safe());
+        let mut ran = ran.into_inner().unwrap();
+        ran.sort();
+        assert_eq!(vec!["a", "b", "c"], ran);
+    }
 }
@@ -10,7 +10,25 @@ use crate::errors::{CommandError, ErrorMessageVerbosity};
 use crate::utilities::calculate_hash;
 use kube::Client;
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::path::Path;
+use std::str::FromStr;
+
+/// Parses custom upstream DNS resolvers, keeping their order.
+pub fn parse_dns_resolvers(resolvers: &[String]) -> Result<Vec<IpAddr>, CommandError> {
+    resolvers
+        .iter()
+        .map(|resolver| {
+            IpAddr::from_str(resolver.trim()).map_err(|e| {
+                CommandError::new(
+                    format!("Custom DNS resolver `{}` is not a valid IP address.", resolver),
+                    Some(e.to_string()),
+                    None,
+                )
+            })
+        })
+        .collect()
+}
 
 // TODO(benjaminch): refactor this chart to have only one in common (issue with labels)
 pub struct CoreDNSConfigChart {
@@ -26,6 +44,7 @@ impl CoreDNSConfigChart {
         declare_hosts: bool,
         managed_dns_helm_format: String,
         managed_dns_resolvers_terraform_format: String,
+        custom_dns_resolvers: Vec<IpAddr>,
     ) -> CoreDNSConfigChart {
         let chart_path = HelmChartPath::new(
             chart_prefix_path,
@@ -69,6 +88,16 @@ impl CoreDNSConfigChart {
                         value: managed_dns_resolvers_terraform_format,
                     },
                 ],
+                // custom resolvers, if any, replace the node ones in the cluster domain (.:53) forward block,
+                // managed DNS zones keep being forwarded to the managed DNS provider resolvers
+                values_string: vec![ChartSetValue {
+                    key: "custom_dns_resolvers".to_string(),
+                    value: custom_dns_resolvers
+                        .iter()
+                        .map(|ip| ip.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                }],
                 ..Default::default()
             },
             chart_installation_checker: CoreDNSConfigChartChecker::new(),
@@ -85,6 +114,10 @@ impl HelmChart for CoreDNSConfigChart {
         &self.chart_info
     }
 
+    fn get_chart_info_mut(&mut self) -> &mut ChartInfo {
+        &mut self.chart_info
+    }
+
     fn pre_exec(
         &self,
         kubernetes_config: &Path,
@@ -291,8 +324,8 @@ impl ChartInstallationChecker for CoreDNSConfigChartChecker {
 
 #[cfg(test)]
 mod tests {
-    use crate::cloud_provider::helm::CommonChart;
-    use crate::cloud_provider::helm_charts::coredns_config_chart::CoreDNSConfigChart;
+    use crate::cloud_provider::helm::{ChartSetValue, CommonChart};
+    use crate::cloud_provider::helm_charts::coredns_config_chart::{parse_dns_resolvers, CoreDNSConfigChart};
     use crate::cloud_provider::helm_charts::{
         get_helm_path_kubernetes_provider_sub_folder_name, get_helm_values_set_in_code_but_absent_in_values_file,
         HelmChartType,
@@ -304,7 +337,7 @@ mod tests {
     #[test]
     fn coredns_config_chart_directory_exists_test() {
         // setup:
-        let chart = CoreDNSConfigChart::new(None, false, "whatever".to_string(), "whatever".to_string());
+        let chart = CoreDNSConfigChart::new(None, false, "whatever".to_string(), "whatever".to_string(), vec![]);
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
         let chart_path = format!(
@@ -330,7 +363,7 @@ mod tests {
     #[test]
     fn coredns_config_chart_values_file_exists_test() {
         // setup:
-        let chart = CoreDNSConfigChart::new(None, false, "whatever".to_string(), "whatever".to_string());
+        let chart = CoreDNSConfigChart::new(None, false, "whatever".to_string(), "whatever".to_string(), vec![]);
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
         let chart_values_path = format!(
@@ -357,7 +390,7 @@ mod tests {
     #[test]
     fn coredns_config_chart_rust_overridden_values_exists_in_values_yaml_test() {
         // setup:
-        let chart = CoreDNSConfigChart::new(None, false, "whatever".to_string(), "whatever".to_string());
+        let chart = CoreDNSConfigChart::new(None, false, "whatever".to_string(), "whatever".to_string(), vec![]);
         let chart_values_file_path = chart._chart_values_path.helm_path().clone();
 
         // execute:
@@ -380,4 +413,44 @@ mod tests {
         // verify:
         assert!(missing_fields.is_none(), "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}", missing_fields.unwrap_or_default().join(","));
     }
+
+    #[test]
+    fn coredns_config_chart_custom_dns_resolvers_test() {
+        // setup:
+        let resolvers = parse_dns_resolvers(&["10.0.0.2".to_string(), "10.0.0.1".to_string()])
+            .expect("resolvers should be valid IPs");
+
+        // execute:
+        let chart = CoreDNSConfigChart::new(None, false, "whatever".to_string(), "{1.1.1.1}".to_string(), resolvers);
+
+        // verify:
+        let value = |values: &[ChartSetValue], key: &str| {
+            values
+                .iter()
+                .find(|value| value.key == key)
+                .map(|value| value.value.clone())
+        };
+        // managed DNS zones are still forwarded to the managed DNS provider resolvers
+        assert_eq!(
+            Some("{1.1.1.1}".to_string()),
+            value(&chart.chart_info.values, "managed_dns_resolvers")
+        );
+        // rendered by the chart as `forward . 10.0.0.2 10.0.0.1` in the cluster domain block
+        assert_eq!(
+            Some("10.0.0.2 10.0.0.1".to_string()),
+            value(&chart.chart_info.values_string, "custom_dns_resolvers")
+        );
+    }
+
+    #[test]
+    fn coredns_config_chart_invalid_custom_dns_resolver_test() {
+        // execute:
+        let result = parse_dns_resolvers(&["10.0.0.1".to_string(), "dns.example.com".to_string()]);
+
+        // verify:
+        assert!(result
+            .expect_err("hostname should be rejected")
+            .message_safe()
+            .contains("`dns.example.com` is not a valid IP address"));
+    }
 }
@@ -4,12 +4,54 @@ use crate::cloud_provider::helm::{
 use crate::cloud_provider::helm_charts::{
     HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
 };
+use crate::cloud_provider::io::ClusterAdvancedSettings;
 use crate::errors::CommandError;
 use kube::Client;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LokiEncryptionType {
     None,
     ServerSideEncryption,
+    KmsServerSideEncryption { kms_key_id: String },
+}
+
+impl LokiEncryptionType {
+    /// Encryption requested through the `loki.s3.sse.*` cluster advanced settings, `default` if none is requested.
+    /// Fails if the object storage doesn't support server side encryption or if the settings are inconsistent.
+    pub fn from_advanced_settings(
+        advanced_settings: &ClusterAdvancedSettings,
+        sse_supported: bool,
+        default: LokiEncryptionType,
+    ) -> Result<LokiEncryptionType, CommandError> {
+        let sse_type = advanced_settings.loki_s3_sse_type.as_deref();
+        let kms_key_id = advanced_settings
+            .loki_s3_sse_kms_key_id
+            .as_deref()
+            .filter(|key_id| !key_id.is_empty());
+        let invalid_settings = |reason: &str| {
+            CommandError::new_from_safe_message(format!(
+                "Invalid Loki server side encryption: {}. Please update the `loki.s3.sse` cluster advanced settings.",
+                reason
+            ))
+        };
+
+        match (sse_type, kms_key_id) {
+            (None, None) => Ok(default),
+            _ if !sse_supported => Err(invalid_settings("the object storage doesn't support it")),
+            (Some("SSE-S3"), None) => Ok(LokiEncryptionType::ServerSideEncryption),
+            (Some("SSE-KMS"), Some(kms_key_id)) => Ok(LokiEncryptionType::KmsServerSideEncryption {
+                kms_key_id: kms_key_id.to_string(),
+            }),
+            (Some("SSE-KMS"), None) => Err(invalid_settings("a KMS key id is required with `SSE-KMS`")),
+            (Some("SSE-S3"), Some(_)) | (None, Some(_)) => {
+                Err(invalid_settings("a KMS key id can only be set with `SSE-KMS`"))
+            }
+            (Some(sse_type), _) => Err(invalid_settings(&format!(
+                "unknown type `{}`, it should be `SSE-S3` or `SSE-KMS`",
+                sse_type
+            ))),
+        }
+    }
 }
 
 #[derive(Default)]
@@ -66,7 +108,7 @@ impl LokiChart {
 
 impl ToCommonHelmChart for LokiChart {
     fn to_common_helm_chart(&self) -> CommonChart {
-        CommonChart {
+        let mut chart = CommonChart {
             chart_info: ChartInfo {
                 name: LokiChart::chart_name(),
                 path: self.chart_path.to_string(),
@@ -118,7 +160,7 @@ impl ToCommonHelmChart for LokiChart {
                     ChartSetValue {
                         key: "config.storage_config.aws.sse_encryption".to_string(),
                         value: match self.encryption_type {
-                            LokiEncryptionType::None => "false",
+                            LokiEncryptionType::None | LokiEncryptionType::KmsServerSideEncryption { .. } => "false",
                             LokiEncryptionType::ServerSideEncryption => "true",
                         }
                         .to_string(), // Qovery settings
@@ -159,7 +201,23 @@ impl ToCommonHelmChart for LokiChart {
                 ..Default::default()
             },
             chart_installation_checker: Some(Box::new(LokiChartChecker::new())),
+        };
+
+        // only set when requested, as SSE-S3 is configured through `sse_encryption`
+        if let LokiEncryptionType::KmsServerSideEncryption { kms_key_id } = &self.encryption_type {
+            chart.chart_info.values.extend(vec![
+                ChartSetValue {
+                    key: "config.storage_config.aws.sse.type".to_string(),
+                    value: "SSE-KMS".to_string(),
+                },
+                ChartSetValue {
+                    key: "config.storage_config.aws.sse.kms_key_id".to_string(),
+                    value: kms_key_id.to_string(),
+                },
+            ]);
         }
+
+        chart
     }
 }
 
@@ -192,6 +250,7 @@ mod tests {
         get_helm_path_kubernetes_provider_sub_folder_name, get_helm_values_set_in_code_but_absent_in_values_file,
         HelmChartType, ToCommonHelmChart,
     };
+    use crate::cloud_provider::io::ClusterAdvancedSettings;
     use std::env;
 
     /// Makes sure chart directory containing all YAML files exists.
@@ -285,4 +344,152 @@ mod tests {
         // verify:
         assert!(missing_fields.is_none(), "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}", missing_fields.unwrap_or_default().join(","));
     }
+
+    fn chart_value(chart: &LokiChart, key: &str) -> Option<String> {
+        chart
+            .to_common_helm_chart()
+            .chart_info
+            .values
+            .into_iter()
+            .find(|value| value.key == key)
+            .map(|value| value.value)
+    }
+
+    #[test]
+    fn loki_chart_do_spaces_path_style_config_test() {
+        // setup:
+        let advanced_settings = ClusterAdvancedSettings::default();
+        let encryption_type =
+            LokiEncryptionType::from_advanced_settings(&advanced_settings, false, LokiEncryptionType::None)
+                .expect("no encryption should be valid on Spaces");
+
+        // execute:
+        let chart = LokiChart::new(
+            None,
+            encryption_type,
+            HelmChartNamespaces::Logging,
+            12,
+            LokiS3BucketConfiguration {
+                use_path_style: advanced_settings.loki_s3_use_path_style.unwrap_or(true),
+                insecure: advanced_settings.loki_s3_insecure,
+                bucketname: Some("qovery-logs-z1234".to_string()),
+                endpoint: Some("fra1.digitaloceanspaces.com".to_string()),
+                region: Some("fra1".to_string()),
+                ..Default::default()
+            },
+        );
+
+        // verify:
+        assert_eq!(
+            Some("true".to_string()),
+            chart_value(&chart, "config.storage_config.aws.s3forcepathstyle")
+        );
+        assert_eq!(
+            Some("false".to_string()),
+            chart_value(&chart, "config.storage_config.aws.insecure")
+        );
+        assert_eq!(
+            Some("false".to_string()),
+            chart_value(&chart, "config.storage_config.aws.sse_encryption")
+        );
+        assert_eq!(None, chart_value(&chart, "config.storage_config.aws.sse.type"));
+    }
+
+    #[test]
+    fn loki_chart_aws_sse_kms_config_test() {
+        // setup:
+        let advanced_settings = ClusterAdvancedSettings {
+            loki_s3_sse_type: Some("SSE-KMS".to_string()),
+            loki_s3_sse_kms_key_id: Some("arn:aws:kms:eu-west-3:123456789012:key/1234abcd".to_string()),
+            ..Default::default()
+        };
+        let encryption_type = LokiEncryptionType::from_advanced_settings(
+            &advanced_settings,
+            true,
+            LokiEncryptionType::ServerSideEncryption,
+        )
+        .expect("SSE-KMS should be valid on S3");
+
+        // execute:
+        let chart = LokiChart::new(
+            None,
+            encryption_type,
+            HelmChartNamespaces::Logging,
+            12,
+            LokiS3BucketConfiguration {
+                use_path_style: advanced_settings.loki_s3_use_path_style.unwrap_or(false),
+                insecure: advanced_settings.loki_s3_insecure,
+                ..Default::default()
+            },
+        );
+
+        // verify:
+        assert_eq!(
+            Some("false".to_string()),
+            chart_value(&chart, "config.storage_config.aws.s3forcepathstyle")
+        );
+        assert_eq!(
+            Some("false".to_string()),
+            chart_value(&chart, "config.storage_config.aws.sse_encryption")
+        );
+        assert_eq!(
+            Some("SSE-KMS".to_string()),
+            chart_value(&chart, "config.storage_config.aws.sse.type")
+        );
+        assert_eq!(
+            Some("arn:aws:kms:eu-west-3:123456789012:key/1234abcd".to_string()),
+            chart_value(&chart, "config.storage_config.aws.sse.kms_key_id")
+        );
+    }
+
+    #[test]
+    fn loki_encryption_type_from_advanced_settings_test() {
+        // setup:
+        let settings = |sse_type: Option<&str>, kms_key_id: Option<&str>| ClusterAdvancedSettings {
+            loki_s3_sse_type: sse_type.map(|s| s.to_string()),
+            loki_s3_sse_kms_key_id: kms_key_id.map(|s| s.to_string()),
+            ..Default::default()
+        };
+        let test_cases = vec![
+            (settings(None, None), true, Ok(LokiEncryptionType::ServerSideEncryption)),
+            (
+                settings(Some("SSE-S3"), None),
+                true,
+                Ok(LokiEncryptionType::ServerSideEncryption),
+            ),
+            (
+                settings(Some("SSE-KMS"), Some("key-id")),
+                true,
+                Ok(LokiEncryptionType::KmsServerSideEncryption {
+                    kms_key_id: "key-id".to_string(),
+                }),
+            ),
+            (settings(Some("SSE-KMS"), None), true, Err("a KMS key id is required")),
+            (
+                settings(Some("SSE-S3"), Some("key-id")),
+                true,
+                Err("can only be set with `SSE-KMS`"),
+            ),
+            (settings(Some("SSE-C"), None), true, Err("unknown type `SSE-C`")),
+            // Spaces and Scaleway object storage
+            (settings(Some("SSE-S3"), None), false, Err("doesn't support it")),
+            (settings(None, Some("key-id")), false, Err("doesn't support it")),
+        ];
+
+        for (advanced_settings, sse_supported, expected) in test_cases {
+            // execute:
+            let result = LokiEncryptionType::from_advanced_settings(
+                &advanced_settings,
+                sse_supported,
+                LokiEncryptionType::ServerSideEncryption,
+            );
+
+            // verify:
+            match (expected, result) {
+                (Ok(expected), Ok(encryption_type)) => assert_eq!(expected, encryption_type),
+                (Err(expected), Err(err)) => assert!(err.message_safe().contains(expected), "{}", err.message_safe()),
+                (expected, result) => panic!("expected {:?}, got {:?}", expected, result),
+            }
+        }
+    }
 }
@@ -147,16 +147,18 @@ pub fn get_helm_values_set_in_code_but_absent_in_values_file(
 
     let mut missing_fields = vec![];
 
-    for value in chart_common_chart.chart_info.values {
+    let chart_info = chart_common_chart.chart_info;
+    let keys = chart_info
+        .values
+        .iter()
+        .map(|v| v.key.to_string())
+        .chain(chart_info.values_json.iter().map(|v| v.key.to_string()));
+
+    for key in keys {
         // Check that value declared in rust code exists in the YAML values file
         if let serde_yaml::Value::Mapping(ref m) = data {
             // Black magic allowing to keep only fields before array indexes
-            let fields_raw: String = value
-                .key
-                .to_string()
-                .chars()
-                .take_while(|&ch| ch != '[')
-                .collect::<String>();
+            let fields_raw: String = key.chars().take_while(|&ch| ch != '[').collect::<String>();
             let fields = fields_raw.split('.').collect::<Vec<&str>>();
             let fields_len = fields.len();
 
@@ -164,7 +166,7 @@ pub fn get_helm_values_set_in_code_but_absent_in_values_file(
 
             for (i, f) in fields.iter().enumerate() {
                 if !current_value.contains_key(f) {
-                    missing_fields.push(value.key.to_string());
+                    missing_fields.push(key.to_string());
                 }
 
                 if i < fields_len - 1 {
@@ -31,10 +31,44 @@ pub struct ClusterAdvancedSettings {
     pub pleco_resources_ttl: i32,
     #[serde(alias = "loki.log_retention_in_week")]
     pub loki_log_retention_in_week: u32,
+    /// loki_s3_use_path_style: None to use the object storage default, path style for Spaces and Scaleway, virtual-hosted style for S3.
+    #[serde(alias = "loki.s3.use_path_style")]
+    pub loki_s3_use_path_style: Option<bool>,
+    #[serde(alias = "loki.s3.insecure")]
+    pub loki_s3_insecure: bool,
+    /// loki_s3_sse_type: `SSE-S3` or `SSE-KMS`, None to use the object storage default.
+    #[serde(alias = "loki.s3.sse.type")]
+    pub loki_s3_sse_type: Option<String>,
+    #[serde(alias = "loki.s3.sse.kms_key_id")]
+    pub loki_s3_sse_kms_key_id: Option<String>,
+    #[serde(alias = "prometheus.remote_write.url")]
+    pub prometheus_remote_write_url: Option<String>,
+    /// prometheus_remote_write_auth_type: `basic` or `bearer`, None if the endpoint doesn't require authentication.
+    #[serde(alias = "prometheus.remote_write.auth.type")]
+    pub prometheus_remote_write_auth_type: Option<String>,
+    /// prometheus_remote_write_auth_secret_name: secret of the Prometheus namespace holding the `username` and `password` keys for `basic`, the `token` key for `bearer`.
+    #[serde(alias = "prometheus.remote_write.auth.secret_name")]
+    pub prometheus_remote_write_auth_secret_name: Option<String>,
     #[serde(alias = "aws.iam.admin_group")]
     pub aws_iam_user_mapper_group_name: String,
     #[serde(alias = "cloud_provider.container_registry.tags")]
     pub cloud_provider_container_registry_tags: HashMap<String, String>,
+    #[serde(alias = "terraform.parallelism")]
+    pub terraform_parallelism: Option<u32>,
+    #[serde(alias = "kubernetes.kubeconfig_max_size")]
+    pub kubeconfig_max_size_bytes: u64,
+    #[serde(alias = "dns.custom_resolvers")]
+    pub dns_custom_resolvers: Vec<String>,
+    #[serde(alias = "helm.charts_timeout_override")]
+    pub helm_charts_timeout_override_sec: HashMap<String, u32>,
+    #[serde(alias = "helm.charts_max_parallelism")]
+    pub helm_charts_max_parallelism: u32,
+    #[serde(alias = "deployment.max_parallelism")]
+    pub deployment_max_parallelism: u32,
+    #[serde(alias = "environment.namespace_labels")]
+    pub environment_namespace_labels: HashMap<String, String>,
+    #[serde(alias = "environment.namespace_annotations")]
+    pub environment_namespace_annotations: HashMap<String, String>,
 }
 
 impl Default for ClusterAdvancedSettings {
@@ -44,8 +78,23 @@ impl Default for ClusterAdvancedSettings {
             registry_image_retention_time_sec: 31536000,
             pleco_resources_ttl: -1,
             loki_log_retention_in_week: 12,
+            loki_s3_use_path_style: None,
+            loki_s3_insecure: false,
+            loki_s3_sse_type: None,
+            loki_s3_sse_kms_key_id: None,
+            prometheus_remote_write_url: None,
+            prometheus_remote_write_auth_type: None,
+            prometheus_remote_write_auth_secret_name: None,
             aws_iam_user_mapper_group_name: "Admins".to_string(),
             cloud_provider_container_registry_tags: HashMap::new(),
+            terraform_parallelism: None,
+            kubeconfig_max_size_bytes: 16 * 1024,
+            dns_custom_resolvers: vec![],
+            helm_charts_timeout_override_sec: HashMap::new(),
+            helm_charts_max_parallelism: 3,
+            deployment_max_parallelism: 1,
+            environment_namespace_labels: HashMap::new(),
+            environment_namespace_annotations: HashMap::new(),
         }
     }
 }
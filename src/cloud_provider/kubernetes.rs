@@ -1,12 +1,15 @@
-use k8s_openapi::api::core::v1::{Namespace, Secret, Service};
-use kube::api::{ListParams, ObjectMeta, PostParams};
+use futures::{AsyncBufReadExt, StreamExt, TryStreamExt};
+use k8s_openapi::api::core::v1::{Namespace, Node, Pod, Secret, Service};
+use kube::api::{EvictParams, ListParams, LogParams, ObjectMeta, Patch, PatchParams, PostParams};
 use kube::core::ObjectList;
 use kube::{Api, Error};
+use rand::Rng;
 use retry::delay::{Fibonacci, Fixed};
 use retry::Error::Operation;
 use retry::OperationResult;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::os::unix::fs::PermissionsExt;
@@ -18,6 +21,7 @@ use std::thread;
 use std::time::Duration;
 use uuid::Uuid;
 
+use crate::clock::{Clock, SystemClock};
 use crate::cloud_provider::aws::regions::AwsZones;
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::io::ClusterAdvancedSettings;
@@ -31,7 +35,7 @@ use crate::cmd::kubectl::{
 };
 use crate::cmd::structs::KubernetesNodeCondition;
 use crate::dns_provider::DnsProvider;
-use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity, Tag};
 use crate::events::Stage::Infrastructure;
 use crate::events::{EngineEvent, EventDetails, EventMessage, InfrastructureStep, Stage, Transmitter};
 use crate::fs::{delete_file_if_exists, workspace_directory};
@@ -41,6 +45,7 @@ use crate::io_models::{Action, QoveryIdentifier};
 use crate::logger::Logger;
 use crate::models::types::VersionsNumber;
 use crate::object_storage::ObjectStorage;
+use crate::poller::{poll_until_with_clock, ExponentialBackoff, PollError, PollState};
 use crate::runtime::block_on;
 use crate::unit_conversion::{any_to_mi, cpu_string_to_float};
 use crate::utilities::create_kube_client;
@@ -49,7 +54,7 @@ use super::models::NodeGroupsWithDesiredState;
 
 pub trait ProviderOptions {}
 
-pub trait Kubernetes {
+pub trait Kubernetes: Send + Sync {
     fn context(&self) -> &Context;
     fn kind(&self) -> Kind;
     fn id(&self) -> &str;
@@ -242,15 +247,12 @@ pub trait Kubernetes {
             }
         };
 
-        // security: ensure size match with a kubeconfig file (< 16k)
-        let max_size = 16 * 1024;
-        if metadata.len() > max_size {
-            return Err(EngineError::new_kubeconfig_size_security_check_error(
-                event_details,
-                metadata.len(),
-                max_size,
-            ));
-        };
+        // security: ensure size match with a kubeconfig file
+        validate_kubeconfig_size(
+            event_details,
+            string_path.as_str(),
+            self.advanced_settings().kubeconfig_max_size_bytes,
+        )?;
 
         let mut permissions = metadata.permissions();
         permissions.set_mode(0o400);
@@ -310,27 +312,7 @@ pub trait Kubernetes {
             }
         };
 
-        let mut resources = Resources {
-            free_cpu: 0.0,
-            max_cpu: 0.0,
-            free_ram_in_mib: 0,
-            max_ram_in_mib: 0,
-            free_pods: 0,
-            max_pods: 0,
-            running_nodes: 0,
-        };
-
-        for node in nodes.items {
-            resources.free_cpu += cpu_string_to_float(node.status.allocatable.cpu);
-            resources.max_cpu += cpu_string_to_float(node.status.capacity.cpu);
-            resources.free_ram_in_mib += any_to_mi(node.status.allocatable.memory);
-            resources.max_ram_in_mib += any_to_mi(node.status.capacity.memory);
-            resources.free_pods = node.status.allocatable.pods.parse::<u32>().unwrap_or(0);
-            resources.max_pods = node.status.capacity.pods.parse::<u32>().unwrap_or(0);
-            resources.running_nodes += 1;
-        }
-
-        Ok(resources)
+        Ok(cluster_resources(nodes.items))
     }
 
     fn on_create(&self) -> Result<(), EngineError>;
@@ -538,6 +520,32 @@ pub struct Resources {
     pub running_nodes: u32,
 }
 
+/// Resources of the whole cluster, each node schedules its own pods so pods add up like cpu and memory.
+/// Free pods are compared to every pod running in the cluster when checking an environment fits in it.
+fn cluster_resources(nodes: Vec<crate::cmd::structs::KubernetesNode>) -> Resources {
+    let mut resources = Resources {
+        free_cpu: 0.0,
+        max_cpu: 0.0,
+        free_ram_in_mib: 0,
+        max_ram_in_mib: 0,
+        free_pods: 0,
+        max_pods: 0,
+        running_nodes: 0,
+    };
+
+    for node in nodes {
+        resources.free_cpu += cpu_string_to_float(node.status.allocatable.cpu);
+        resources.max_cpu += cpu_string_to_float(node.status.capacity.cpu);
+        resources.free_ram_in_mib += any_to_mi(node.status.allocatable.memory);
+        resources.max_ram_in_mib += any_to_mi(node.status.capacity.memory);
+        resources.free_pods += node.status.allocatable.pods.parse::<u32>().unwrap_or(0);
+        resources.max_pods += node.status.capacity.pods.parse::<u32>().unwrap_or(0);
+        resources.running_nodes += 1;
+    }
+
+    resources
+}
+
 pub fn uninstall_cert_manager<P>(
     kubernetes_config: P,
     envs: Vec<(&str, &str)>,
@@ -750,6 +758,177 @@ where
     }
 }
 
+/// How `retry_kube` retries a kube operation: how many times, how long to wait between attempts
+/// and which errors are transient enough to be retried.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: ExponentialBackoff,
+    pub retryable_tags: Vec<Tag>,
+}
+
+impl RetryPolicy {
+    fn is_retryable(&self, error: &EngineError) -> bool {
+        self.retryable_tags.contains(error.tag())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            backoff: ExponentialBackoff::new(Duration::from_secs(1), Duration::from_secs(30)),
+            retryable_tags: vec![
+                Tag::K8sCannotGetPods,
+                Tag::K8sGetEvents,
+                Tag::K8sCannotReachToApi,
+                Tag::CannotConnectK8sCluster,
+            ],
+        }
+    }
+}
+
+/// Calls `op` until it succeeds, fails with an error the policy doesn't consider transient, or the policy
+/// attempts are exhausted, in which case the last error is returned. Waits a jittered backoff between attempts.
+pub fn retry_kube<F, T>(op: F, policy: RetryPolicy) -> Result<T, EngineError>
+where
+    F: FnMut() -> Result<T, EngineError>,
+{
+    retry_kube_with_clock(op, policy, &SystemClock)
+}
+
+/// Same as `retry_kube`, waiting between attempts through the given clock.
+pub fn retry_kube_with_clock<F, T>(mut op: F, policy: RetryPolicy, clock: &dyn Clock) -> Result<T, EngineError>
+where
+    F: FnMut() -> Result<T, EngineError>,
+{
+    let mut delays = policy.backoff.delays();
+    let mut attempts: u32 = 0;
+    loop {
+        attempts += 1;
+        let err = match op() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        if attempts >= policy.max_attempts || !policy.is_retryable(&err) {
+            return Err(err);
+        }
+
+        let delay = jittered(delays.next().unwrap_or(policy.backoff.max_delay));
+        info!(
+            "kube operation failed with a transient error {:?}, retrying in {:?} ({}/{})",
+            err.tag(),
+            delay,
+            attempts,
+            policy.max_attempts
+        );
+        clock.sleep(delay);
+    }
+}
+
+// Keep half of the delay and pick the other half randomly, for clients failing together not to retry all at once
+fn jittered(delay: Duration) -> Duration {
+    let half_delay = delay / 2;
+    half_delay + half_delay.mul_f64(rand::thread_rng().gen::<f64>())
+}
+
+// DaemonSet pods are re-created on the node whatever happens and mirror pods are managed by the kubelet,
+// so as kubectl drain, we don't try to evict them. Completed pods don't need to be evicted either.
+fn is_pod_to_evict(pod: &Pod) -> bool {
+    let is_daemonset_pod = pod
+        .metadata
+        .owner_references
+        .as_ref()
+        .map(|owners| owners.iter().any(|owner| owner.kind == "DaemonSet"))
+        .unwrap_or(false);
+    let is_mirror_pod = pod
+        .metadata
+        .annotations
+        .as_ref()
+        .map(|annotations| annotations.contains_key("kubernetes.io/config.mirror"))
+        .unwrap_or(false);
+    let is_completed = matches!(
+        pod.status.as_ref().and_then(|status| status.phase.as_deref()),
+        Some("Succeeded") | Some("Failed")
+    );
+
+    !is_daemonset_pod && !is_mirror_pod && !is_completed
+}
+
+/// Cordon the node and evict its pods through the eviction API, so pod disruption budgets are honored.
+/// Evictions refused because of a PDB are retried until the node is drained, or the timeout is reached.
+pub async fn drain_node_respecting_pdb(
+    kube: &kube::Client,
+    node_name: &str,
+    timeout: Duration,
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    let nodes: Api<Node> = Api::all(kube.clone());
+    if let Err(err) = nodes.cordon(node_name).await {
+        return Err(EngineError::new_k8s_cannot_cordon_node(
+            event_details,
+            node_name.to_string(),
+            CommandError::new(format!("Cannot cordon node `{}`.", node_name), Some(err.to_string()), None),
+        ));
+    }
+
+    let pods: Api<Pod> = Api::all(kube.clone());
+    let list_params = ListParams::default().fields(&format!("spec.nodeName={}", node_name));
+    let started_at = std::time::Instant::now();
+    loop {
+        let pods_to_evict: Vec<Pod> = match pods.list(&list_params).await {
+            Ok(pods) => pods.items.into_iter().filter(is_pod_to_evict).collect(),
+            Err(err) => {
+                return Err(EngineError::new_k8s_cannot_get_pods(
+                    event_details,
+                    CommandError::new(
+                        format!("Cannot list pods of node `{}`.", node_name),
+                        Some(err.to_string()),
+                        None,
+                    ),
+                ))
+            }
+        };
+
+        if pods_to_evict.is_empty() {
+            return Ok(());
+        }
+
+        let mut pod_blocked_by_pdb = None;
+        for pod in pods_to_evict {
+            let pod_name = pod.metadata.name.unwrap_or_default();
+            let namespace = pod.metadata.namespace.unwrap_or_default();
+            let namespaced_pods: Api<Pod> = Api::namespaced(kube.clone(), &namespace);
+            match namespaced_pods.evict(&pod_name, &EvictParams::default()).await {
+                Ok(_) => {}
+                // pod already gone
+                Err(Error::Api(api_err)) if api_err.code == 404 => {}
+                // eviction would violate a pod disruption budget
+                Err(Error::Api(api_err)) if api_err.code == 429 => pod_blocked_by_pdb = Some(pod_name),
+                Err(err) => {
+                    return Err(EngineError::new_k8s_cannot_delete_pod(
+                        event_details,
+                        pod_name,
+                        CommandError::new("Cannot evict pod.".to_string(), Some(err.to_string()), None),
+                    ))
+                }
+            }
+        }
+
+        let elapsed = started_at.elapsed();
+        if elapsed >= timeout {
+            return Err(EngineError::new_k8s_pod_disruption_budget_invalid_state(
+                event_details,
+                pod_blocked_by_pdb.unwrap_or_else(|| node_name.to_string()),
+            ));
+        }
+
+        tokio::time::sleep(POLL_INTERVAL.min(timeout - elapsed)).await;
+    }
+}
+
 pub fn check_workers_upgrade_status<P>(
     kubernetes_config: P,
     envs: Vec<(&str, &str)>,
@@ -916,6 +1095,24 @@ fn check_kubernetes_upgrade_status(
         }
     };
 
+    // masters can only be upgraded one minor version at a time
+    if required_upgrade_on == Some(KubernetesNodesType::Masters)
+        && !wished_version.is_valid_upgrade_from(&deployed_masters_version)
+    {
+        let raw_error = CommandError::new_from_safe_message(format!(
+            "Kubernetes can only be upgraded one minor version at a time, cannot upgrade from {} to {}",
+            deployed_masters_version, wished_version
+        ));
+        return Err(
+            EngineError::new_k8s_version_upgrade_deployed_vs_requested_versions_inconsistency(
+                event_details,
+                deployed_masters_version,
+                wished_version,
+                raw_error,
+            ),
+        );
+    }
+
     // check workers versions
     if deployed_workers_version.is_empty() {
         logger.log(EngineEvent::Info(
@@ -1130,7 +1327,7 @@ where
     F: Fn() -> R,
 {
     let waiting_message = match action {
-        Action::Create => Some(format!(
+        Action::Create | Action::Restart => Some(format!(
             "Infrastructure '{}' deployment is in progress...",
             kubernetes.name_with_id()
         )),
@@ -1177,7 +1374,7 @@ where
             let event_message = EventMessage::new_from_safe(waiting_message.to_string());
 
             match action {
-                Action::Create => {
+                Action::Create | Action::Restart => {
                     logger.log(EngineEvent::Info(
                         EventDetails::clone_changing_stage(event_details, Infrastructure(InfrastructureStep::Create)),
                         event_message,
@@ -1214,6 +1411,32 @@ where
     blocking_task_result
 }
 
+/// Ensures the kubeconfig file size doesn't exceed `max_size_bytes`, a bigger file is most likely not a kubeconfig.
+pub fn validate_kubeconfig_size<P>(event_details: EventDetails, path: P, max_size_bytes: u64) -> Result<(), EngineError>
+where
+    P: AsRef<Path>,
+{
+    let size = match std::fs::metadata(path.as_ref()) {
+        Ok(metadata) => metadata.len(),
+        Err(err) => {
+            return Err(EngineError::new_cannot_retrieve_cluster_config_file(
+                event_details,
+                CommandError::new("Error getting file metadata.".to_string(), Some(err.to_string()), None),
+            ))
+        }
+    };
+
+    if size > max_size_bytes {
+        return Err(EngineError::new_kubeconfig_size_security_check_error(
+            event_details,
+            size,
+            max_size_bytes,
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn validate_k8s_required_cpu_and_burstable(
     total_cpu: String,
     cpu_burst: String,
@@ -1312,34 +1535,193 @@ pub fn filter_svc_loadbalancers(load_balancers: ObjectList<Service>) -> Vec<Serv
     filtered_load_balancers
 }
 
-pub async fn kube_create_namespace_if_not_exists(
+/// Creates the namespace with the given labels and annotations, or adds/updates them on the namespace if it already exists.
+/// Labels and annotations already set on an existing namespace and not given here are left untouched.
+pub async fn kube_create_or_update_namespace(
     kube: &kube::Client,
     namespace_name: &str,
-    labels: Option<std::collections::BTreeMap<String, String>>,
+    labels: BTreeMap<String, String>,
+    annotations: BTreeMap<String, String>,
 ) -> Result<(), Error> {
-    let namespace = Api::all(kube.clone());
-    let namespace_labels = Namespace {
+    let namespaces: Api<Namespace> = Api::all(kube.clone());
+    let namespace = Namespace {
         metadata: ObjectMeta {
             name: Some(namespace_name.to_string()),
-            labels,
+            labels: (!labels.is_empty()).then(|| labels.clone()),
+            annotations: (!annotations.is_empty()).then(|| annotations.clone()),
             ..Default::default()
         },
         spec: None,
         status: None,
     };
 
-    // create namespace
-    if let Err(e) = namespace.create(&PostParams::default(), &namespace_labels).await {
-        match e {
-            // namespace already exists
-            Error::Api(api_err) if api_err.code == 409 => {}
-            _ => return Err(e),
+    match namespaces.create(&PostParams::default(), &namespace).await {
+        Ok(_) => return Ok(()),
+        // namespace already exists, its labels and annotations are reconciled below
+        Err(Error::Api(api_err)) if api_err.code == 409 => {}
+        Err(e) => return Err(e),
+    }
+
+    if labels.is_empty() && annotations.is_empty() {
+        return Ok(());
+    }
+
+    // a merge patch only adds or updates the given keys
+    let patch = serde_json::json!({
+        "metadata": {
+            "labels": labels,
+            "annotations": annotations,
         }
-    };
+    });
+    namespaces
+        .patch(namespace_name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
 
     Ok(())
 }
 
+/// Waits for the pods matching the selector to be gone, checking every 10 seconds until `timeout` is reached.
+pub fn kube_wait_for_pods_deletion(
+    kube: &kube::Client,
+    namespace: &str,
+    selector: &str,
+    timeout: Duration,
+) -> Result<(), PollError<Error>> {
+    kube_wait_for_pods_deletion_with_clock(kube, namespace, selector, timeout, &SystemClock)
+}
+
+/// Same as `kube_wait_for_pods_deletion`, reading and waiting for time through the given clock.
+pub fn kube_wait_for_pods_deletion_with_clock(
+    kube: &kube::Client,
+    namespace: &str,
+    selector: &str,
+    timeout: Duration,
+    clock: &dyn Clock,
+) -> Result<(), PollError<Error>> {
+    const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+    let pods: Api<Pod> = Api::namespaced(kube.clone(), namespace);
+    let list_params = ListParams::default().labels(selector);
+    poll_until_with_clock(
+        || match block_on(pods.list(&list_params))?.items.is_empty() {
+            true => Ok(PollState::Ready(())),
+            false => Ok(PollState::Pending),
+        },
+        ExponentialBackoff::new(POLL_INTERVAL, POLL_INTERVAL),
+        timeout,
+        clock,
+    )
+}
+
+/// Returns the log lines of the pods matching the selector, optionally only the ones written during the last `since`.
+/// With `follow`, lines keep coming as pods write them, until pods stop or the iterator is dropped.
+/// Lines of the different pods are interleaved as they are received. A line that can't be read ends the
+/// iterator with an error, the logs being truncated.
+pub fn tail_pod_logs(
+    kube: &kube::Client,
+    selector: &str,
+    namespace: &str,
+    since: Option<Duration>,
+    follow: bool,
+    event_details: EventDetails,
+) -> Result<impl Iterator<Item = Result<String, EngineError>>, EngineError> {
+    let pods: Api<Pod> = Api::namespaced(kube.clone(), namespace);
+    let log_params = LogParams {
+        follow,
+        since_seconds: since.map(|since| since.as_secs() as i64),
+        ..Default::default()
+    };
+    let to_error = {
+        let (selector, namespace) = (selector.to_string(), namespace.to_string());
+        move |err: String| {
+            EngineError::new_k8s_get_logs_error(
+                event_details.clone(),
+                selector.clone(),
+                namespace.clone(),
+                CommandError::new_from_safe_message(err),
+            )
+        }
+    };
+
+    // streams are opened upfront, so pods logs that can't be retrieved are reported right away
+    let streams = block_on(async {
+        let mut streams = vec![];
+        for pod in pods.list(&ListParams::default().labels(selector)).await?.items {
+            let pod_name = pod.metadata.name.unwrap_or_default();
+            let lines = pods
+                .log_stream(&pod_name, &log_params)
+                .await?
+                .map_err(std::io::Error::other)
+                .into_async_read()
+                .lines();
+            streams.push(Box::pin(lines));
+        }
+        Ok::<_, Error>(streams)
+    })
+    .map_err(|err| to_error(err.to_string()))?;
+
+    let (tx, rx) = mpsc::channel();
+    let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
+    thread::spawn(move || {
+        block_on(forward_pod_logs(
+            futures::stream::select_all(streams),
+            tx,
+            stop_rx,
+            move |err| to_error(format!("Cannot read pod logs: {}", err)),
+        ))
+    });
+
+    Ok(PodLogs {
+        lines: rx.into_iter(),
+        _stop: stop_tx,
+    })
+}
+
+struct PodLogs {
+    lines: mpsc::IntoIter<Result<String, EngineError>>,
+    // dropped along with the iterator, to stop reading the logs even if pods don't write anymore
+    _stop: tokio::sync::oneshot::Sender<()>,
+}
+
+impl Iterator for PodLogs {
+    type Item = Result<String, EngineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.next()
+    }
+}
+
+// Sends the log lines until the logs end, a line can't be read, or the reader stops
+async fn forward_pod_logs<S, F>(
+    mut lines: S,
+    tx: mpsc::Sender<Result<String, EngineError>>,
+    mut stop: tokio::sync::oneshot::Receiver<()>,
+    to_error: F,
+) where
+    S: futures::Stream<Item = std::io::Result<String>> + Unpin,
+    F: Fn(std::io::Error) -> EngineError,
+{
+    loop {
+        let line = tokio::select! {
+            line = lines.next() => line,
+            _ = &mut stop => return,
+        };
+
+        let sent = match line {
+            None => return,
+            Some(Ok(line)) => tx.send(Ok(line)),
+            Some(Err(err)) => {
+                let _ = tx.send(Err(to_error(err)));
+                return;
+            }
+        };
+        // nobody is reading anymore
+        if sent.is_err() {
+            return;
+        }
+    }
+}
+
 pub async fn kube_copy_secret_to_another_namespace(
     kube: &kube::Client,
     name: &str,
@@ -1371,21 +1753,36 @@ mod tests {
     use k8s_openapi::api::core::v1::{Service, ServiceSpec};
     use kube::core::{ListMeta, ObjectList, ObjectMeta};
 
+    use crate::clock::MockClock;
     use crate::cloud_provider::kubernetes::{
-        check_kubernetes_upgrade_status, compare_kubernetes_cluster_versions_for_upgrade, convert_k8s_cpu_value_to_f32,
-        filter_svc_loadbalancers, kube_create_namespace_if_not_exists, kube_does_secret_exists, kube_list_services,
-        validate_k8s_required_cpu_and_burstable, KubernetesNodesType,
+        check_kubernetes_upgrade_status, cluster_resources, compare_kubernetes_cluster_versions_for_upgrade,
+        convert_k8s_cpu_value_to_f32, drain_node_respecting_pdb, filter_svc_loadbalancers, forward_pod_logs,
+        kube_create_or_update_namespace, kube_does_secret_exists, kube_list_services,
+        kube_wait_for_pods_deletion_with_clock, retry_kube_with_clock, tail_pod_logs,
+        validate_k8s_required_cpu_and_burstable, validate_kubeconfig_size, KubernetesNodesType, RetryPolicy,
     };
     use crate::cloud_provider::models::CpuLimits;
     use crate::cmd::structs::{KubernetesList, KubernetesNode, KubernetesVersion};
+    use crate::errors::{CommandError, EngineError, Tag};
     use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
     use crate::io_models::QoveryIdentifier;
     use crate::logger::StdIoLogger;
     use crate::models::types::VersionsNumber;
+    use crate::poller::{ExponentialBackoff, PollError};
     use crate::runtime::block_on;
+    use crate::test_utils::{start_fake_kube_api_server, EventDetailsBuilder};
     use crate::utilities::create_kube_client;
+    use chrono::Utc;
+    use futures::StreamExt;
+    use std::collections::BTreeMap;
     use std::env;
+    use std::io::Write;
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::NamedTempFile;
     use uuid::Uuid;
 
     use super::kube_copy_secret_to_another_namespace;
@@ -1444,11 +1841,43 @@ mod tests {
         assert_eq!(filtered_lbs[0].clone().metadata.namespace.unwrap(), "ns0");
     }
 
+    #[test]
+    fn test_cluster_resources_add_up_nodes_pods() {
+        // setup:
+        let node = |allocatable_pods: &str, capacity_pods: &str| -> KubernetesNode {
+            serde_json::from_str(&format!(
+                r#"{{"status": {{
+                    "allocatable": {{"cpu": "1930m", "memory": "7291264Ki", "pods": "{}"}},
+                    "capacity": {{"cpu": "2", "memory": "7961984Ki", "pods": "{}"}},
+                    "nodeInfo": {{"kubeProxyVersion": "v1.22.1", "kubeletVersion": "v1.22.1"}},
+                    "conditions": []
+                }}}}"#,
+                allocatable_pods, capacity_pods
+            ))
+            .expect("invalid node")
+        };
+
+        // execute:
+        let resources = cluster_resources(vec![node("29", "29"), node("17", "29"), node("110", "110")]);
+
+        // verify: the last node must not be the only one accounted
+        assert_eq!(3, resources.running_nodes);
+        assert_eq!(156, resources.free_pods);
+        assert_eq!(168, resources.max_pods);
+        assert_eq!(0, cluster_resources(vec![]).free_pods);
+    }
+
     #[test]
     #[cfg(feature = "test-local-kube")]
     pub fn k8s_create_namespace() {
         let kube_client = block_on(create_kube_client(kubeconfig_path(), &[])).unwrap();
-        assert!(block_on(kube_create_namespace_if_not_exists(&kube_client, "qovery-test-ns", None)).is_ok(),);
+        assert!(block_on(kube_create_or_update_namespace(
+            &kube_client,
+            "qovery-test-ns",
+            BTreeMap::new(),
+            BTreeMap::new()
+        ))
+        .is_ok(),);
     }
 
     #[test]
@@ -1514,6 +1943,19 @@ mod tests {
         assert!(!result.older_masters_version_detected);
         assert!(!result.older_workers_version_detected);
 
+        // skipping a minor version is not allowed
+        let result = check_kubernetes_upgrade_status(
+            "1.18",
+            version_1_16.clone(),
+            vec![version_1_16.clone()],
+            event_details.clone(),
+            &logger,
+        );
+        assert_eq!(
+            result.unwrap_err().tag(),
+            &Tag::K8sUpgradeDeployedVsRequestedVersionsInconsistency
+        );
+
         // everything is up to date, no upgrade required
         let result = check_kubernetes_upgrade_status(
             "1.17",
@@ -2065,4 +2507,460 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    pub fn test_validate_kubeconfig_size() {
+        // setup:
+        let max_size = 16 * 1024;
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Infrastructure(InfrastructureStep::LoadConfiguration))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "test".to_string()))
+            .build();
+        let test_cases = vec![(max_size - 1, true), (max_size, true), (max_size + 1, false)];
+
+        for (size, expected_ok) in test_cases {
+            let mut kubeconfig = NamedTempFile::new().expect("cannot create temp file");
+            kubeconfig
+                .write_all(&vec![b'a'; size as usize])
+                .expect("cannot write temp file");
+
+            // execute:
+            let result = validate_kubeconfig_size(event_details.clone(), kubeconfig.path(), max_size);
+
+            // verify:
+            assert_eq!(expected_ok, result.is_ok(), "size: {}", size);
+            if let Err(err) = result {
+                assert_eq!(&Tag::KubeconfigSecurityCheckError, err.tag());
+                assert!(err.user_log_message().contains("(16.0 KiB)"));
+                assert!(err.user_log_message().contains("(<= 16.0 KiB)"));
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_drain_node_respecting_pdb_times_out_when_pdb_blocks_eviction() {
+        // setup:
+        let eviction_attempts = Arc::new(AtomicU32::new(0));
+        let attempts = eviction_attempts.clone();
+        let kube_client = start_fake_kube_api_server(move |method, path, _| {
+            match (method, path) {
+            ("PATCH", "/api/v1/nodes/node-1?") => (
+                200,
+                r#"{"apiVersion":"v1","kind":"Node","metadata":{"name":"node-1"},"spec":{"unschedulable":true}}"#
+                    .to_string(),
+            ),
+            ("GET", path) if path.starts_with("/api/v1/pods?") => (
+                200,
+                r#"{"apiVersion":"v1","kind":"PodList","metadata":{},"items":[
+                    {"metadata":{"name":"app-1","namespace":"app-ns"},"status":{"phase":"Running"}},
+                    {"metadata":{"name":"agent-1","namespace":"kube-system","ownerReferences":[{"apiVersion":"apps/v1","kind":"DaemonSet","name":"agent","uid":"1"}]},"status":{"phase":"Running"}}
+                ]}"#
+                    .to_string(),
+            ),
+            ("POST", "/api/v1/namespaces/app-ns/pods/app-1/eviction?") => {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                (
+                    429,
+                    r#"{"apiVersion":"v1","kind":"Status","metadata":{},"status":"Failure","message":"Cannot evict pod as it would violate the pod's disruption budget.","reason":"TooManyRequests","code":429}"#
+                        .to_string(),
+                )
+            }
+            _ => (
+                404,
+                r#"{"apiVersion":"v1","kind":"Status","metadata":{},"status":"Failure","message":"not found","reason":"NotFound","code":404}"#
+                    .to_string(),
+            ),
+        }
+        });
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Infrastructure(InfrastructureStep::Upgrade))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let result = block_on(drain_node_respecting_pdb(
+            &kube_client,
+            "node-1",
+            Duration::from_secs(1),
+            event_details,
+        ));
+
+        // verify:
+        let err = result.expect_err("drain should time out");
+        assert_eq!(&Tag::K8sPodDisruptionBudgetInInvalidState, err.tag());
+        assert!(err.user_log_message().contains("app-1"));
+        assert!(eviction_attempts.load(Ordering::SeqCst) >= 2);
+    }
+
+    #[test]
+    pub fn test_drain_node_respecting_pdb_fails_when_node_cannot_be_cordoned() {
+        // setup:
+        let kube_client = start_fake_kube_api_server(|_, _, _| {
+            (
+                403,
+                r#"{"apiVersion":"v1","kind":"Status","metadata":{},"status":"Failure","message":"forbidden","reason":"Forbidden","code":403}"#
+                    .to_string(),
+            )
+        });
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Infrastructure(InfrastructureStep::Upgrade))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let result = block_on(drain_node_respecting_pdb(
+            &kube_client,
+            "node-1",
+            Duration::from_secs(1),
+            event_details,
+        ));
+
+        // verify:
+        let err = result.expect_err("drain should fail");
+        assert_eq!(&Tag::K8sCannotCordonNode, err.tag());
+        assert!(err.user_log_message().contains("node-1"));
+    }
+
+    #[test]
+    pub fn test_kube_create_or_update_namespace_reconciles_existing_namespace_labels() {
+        // setup:
+        let existing_namespace = Arc::new(Mutex::new(serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "Namespace",
+            "metadata": {"name": "my-env", "labels": {"ttl": "3600"}, "annotations": {"owner": "team-a"}},
+        })));
+        let namespace = existing_namespace.clone();
+        let kube_client = start_fake_kube_api_server(move |method, path, body| {
+            match (method, path) {
+            ("POST", "/api/v1/namespaces?") => (
+                409,
+                r#"{"apiVersion":"v1","kind":"Status","metadata":{},"status":"Failure","message":"namespaces \"my-env\" already exists","reason":"AlreadyExists","code":409}"#
+                    .to_string(),
+            ),
+            ("PATCH", "/api/v1/namespaces/my-env?") => {
+                let patch: serde_json::Value = serde_json::from_str(body).unwrap();
+                let mut namespace = namespace.lock().unwrap();
+                for field in ["labels", "annotations"] {
+                    for (key, value) in patch["metadata"][field].as_object().unwrap() {
+                        namespace["metadata"][field][key] = value.clone();
+                    }
+                }
+                (200, namespace.to_string())
+            }
+            _ => (
+                404,
+                r#"{"apiVersion":"v1","kind":"Status","metadata":{},"status":"Failure","message":"not found","reason":"NotFound","code":404}"#
+                    .to_string(),
+            ),
+        }
+        });
+
+        // execute:
+        let result = block_on(kube_create_or_update_namespace(
+            &kube_client,
+            "my-env",
+            BTreeMap::from([
+                ("ttl".to_string(), "7200".to_string()),
+                ("cost-center".to_string(), "cc-42".to_string()),
+            ]),
+            BTreeMap::from([("contact".to_string(), "ops@mycompany.com".to_string())]),
+        ));
+
+        // verify:
+        assert!(result.is_ok(), "{:?}", result);
+        let namespace = existing_namespace.lock().unwrap();
+        assert_eq!(
+            serde_json::json!({"ttl": "7200", "cost-center": "cc-42"}),
+            namespace["metadata"]["labels"]
+        );
+        assert_eq!(
+            serde_json::json!({"owner": "team-a", "contact": "ops@mycompany.com"}),
+            namespace["metadata"]["annotations"]
+        );
+    }
+
+    #[test]
+    pub fn test_tail_pod_logs() {
+        // setup:
+        let requested_paths = Arc::new(Mutex::new(Vec::<String>::new()));
+        let paths = requested_paths.clone();
+        let kube_client = start_fake_kube_api_server(move |method, path, _| {
+            paths.lock().unwrap().push(path.to_string());
+            match (method, path) {
+                ("GET", path) if path.starts_with("/api/v1/namespaces/my-env/pods?") => (
+                    200,
+                    r#"{"apiVersion":"v1","kind":"PodList","metadata":{},"items":[
+                        {"metadata":{"name":"app-1","namespace":"my-env"},"status":{"phase":"Running"}}
+                    ]}"#
+                        .to_string(),
+                ),
+                ("GET", path) if path.starts_with("/api/v1/namespaces/my-env/pods/app-1/log?") => (
+                    200,
+                    "starting server\nlistening on port 8080\nready\n".to_string(),
+                ),
+                _ => (
+                    404,
+                    r#"{"apiVersion":"v1","kind":"Status","metadata":{},"status":"Failure","message":"not found","reason":"NotFound","code":404}"#
+                        .to_string(),
+                ),
+            }
+        });
+
+        // execute:
+        let lines = tail_pod_logs(
+            &kube_client,
+            "app=my-app",
+            "my-env",
+            Some(Duration::from_secs(300)),
+            false,
+            EventDetailsBuilder::new()
+                .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                .build(),
+        )
+        .expect("logs should be retrieved")
+        .collect::<Result<Vec<String>, _>>()
+        .expect("logs should be read");
+
+        // verify:
+        assert_eq!(vec!["starting server", "listening on port 8080", "ready"], lines);
+        let paths = requested_paths.lock().unwrap();
+        assert!(paths[0].contains("labelSelector=app%3Dmy-app"), "{:?}", paths);
+        assert!(paths[1].contains("sinceSeconds=300"), "{:?}", paths);
+        assert!(!paths[1].contains("follow=true"), "{:?}", paths);
+    }
+
+    #[test]
+    pub fn test_tail_pod_logs_error() {
+        // setup:
+        let kube_client = start_fake_kube_api_server(|_, _, _| {
+            (
+                403,
+                r#"{"apiVersion":"v1","kind":"Status","metadata":{},"status":"Failure","message":"forbidden","reason":"Forbidden","code":403}"#
+                    .to_string(),
+            )
+        });
+
+        // execute:
+        let result = tail_pod_logs(
+            &kube_client,
+            "app=my-app",
+            "my-env",
+            None,
+            true,
+            EventDetailsBuilder::new()
+                .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                .build(),
+        );
+
+        // verify:
+        let err = result.err().expect("logs should not be retrieved");
+        assert_eq!(&Tag::K8sGetLogs, err.tag());
+        assert!(err.user_log_message().contains("app=my-app"));
+    }
+
+    #[test]
+    pub fn test_forward_pod_logs_until_read_error_or_stop() {
+        // setup:
+        let to_error = |err: std::io::Error| {
+            EngineError::new_k8s_get_logs_error(
+                EventDetailsBuilder::new()
+                    .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                    .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                    .build(),
+                "app=my-app".to_string(),
+                "my-env".to_string(),
+                CommandError::new_from_safe_message(err.to_string()),
+            )
+        };
+        let lines = futures::stream::iter(vec![
+            Ok("starting server".to_string()),
+            Err(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset")),
+            Ok("never read".to_string()),
+        ]);
+        let (tx, rx) = mpsc::channel();
+        let (_stop_tx, stop_rx) = tokio::sync::oneshot::channel();
+
+        // execute:
+        block_on(forward_pod_logs(lines, tx, stop_rx, to_error));
+
+        // verify: the read error is reported, the logs being truncated
+        let logs: Vec<Result<String, EngineError>> = rx.into_iter().collect();
+        assert_eq!(2, logs.len());
+        assert_eq!("starting server", logs[0].as_ref().expect("line should be read"));
+        assert_eq!(&Tag::K8sGetLogs, logs[1].as_ref().expect_err("line should fail").tag());
+
+        // setup: a followed pod which doesn't write anymore
+        let lines = futures::stream::iter(vec![Ok("starting server".to_string())]).chain(futures::stream::pending());
+        let (tx, rx) = mpsc::channel();
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
+        let forwarder = thread::spawn(move || block_on(forward_pod_logs(lines, tx, stop_rx, to_error)));
+        assert_eq!(
+            "starting server",
+            rx.recv().expect("line should be sent").expect("line should be read")
+        );
+
+        // execute:
+        drop(stop_tx);
+
+        // verify: the forwarder stops without waiting for another line
+        forwarder.join().expect("forwarder should stop");
+        assert!(rx.recv().is_err());
+    }
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            backoff: ExponentialBackoff::new(Duration::from_secs(2), Duration::from_secs(30)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_retry_kube_retries_transient_errors() {
+        // setup:
+        let clock = MockClock::new(Utc::now());
+        let mut attempts = 0;
+
+        // execute:
+        let result = retry_kube_with_clock(
+            || {
+                attempts += 1;
+                if attempts <= 2 {
+                    return Err(EngineError::new_k8s_cannot_get_pods(
+                        EventDetailsBuilder::new()
+                            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                            .build(),
+                        CommandError::new_from_safe_message("connection reset".to_string()),
+                    ));
+                }
+                Ok("pods")
+            },
+            retry_policy(),
+            &clock,
+        );
+
+        // verify:
+        assert_eq!(Ok("pods"), result.map_err(|e| e.tag().clone()));
+        assert_eq!(3, attempts);
+        // waited 2s then 4s, each jittered down to half of it at most
+        assert!(clock.elapsed() >= Duration::from_secs(3), "elapsed: {:?}", clock.elapsed());
+        assert!(clock.elapsed() <= Duration::from_secs(6), "elapsed: {:?}", clock.elapsed());
+    }
+
+    #[test]
+    pub fn test_retry_kube_returns_last_error() {
+        // setup:
+        let test_cases: Vec<(fn(CommandError) -> EngineError, u32)> = vec![
+            (
+                |e| {
+                    EngineError::new_k8s_cannot_get_pods(
+                        EventDetailsBuilder::new()
+                            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                            .build(),
+                        e,
+                    )
+                },
+                5,
+            ),
+            (
+                |e| {
+                    EngineError::new_k8s_get_events(
+                        EventDetailsBuilder::new()
+                            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                            .build(),
+                        e,
+                    )
+                },
+                5,
+            ),
+            (
+                |e| {
+                    EngineError::new_k8s_cannot_delete_pod(
+                        EventDetailsBuilder::new()
+                            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                            .build(),
+                        "pod".to_string(),
+                        e,
+                    )
+                },
+                1,
+            ),
+        ];
+
+        for (new_error, expected_attempts) in test_cases {
+            let clock = MockClock::new(Utc::now());
+            let mut attempts = 0;
+
+            // execute:
+            let result: Result<(), EngineError> = retry_kube_with_clock(
+                || {
+                    attempts += 1;
+                    Err(new_error(CommandError::new_from_safe_message(format!("attempt {}", attempts))))
+                },
+                retry_policy(),
+                &clock,
+            );
+
+            // verify:
+            let err = result.expect_err("should fail");
+            assert_eq!(expected_attempts, attempts, "tag: {:?}", err.tag());
+            assert_eq!(
+                format!("attempt {}", expected_attempts),
+                err.underlying_error()
+                    .expect("should have an underlying error")
+                    .message_safe()
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_kube_wait_for_pods_deletion_deterministic_timeout() {
+        // setup:
+        let pods_left = Arc::new(AtomicU32::new(1));
+        let pods_left_server = pods_left.clone();
+        let kube_client = start_fake_kube_api_server(move |method, path, _| match (method, path) {
+            ("GET", path) if path.starts_with("/api/v1/namespaces/app-ns/pods?") => {
+                let items = match pods_left_server.load(Ordering::SeqCst) {
+                    0 => "",
+                    _ => r#"{"metadata":{"name":"app-0","namespace":"app-ns"}}"#,
+                };
+                (
+                    200,
+                    format!(r#"{{"apiVersion":"v1","kind":"PodList","metadata":{{}},"items":[{}]}}"#, items),
+                )
+            }
+            _ => (404, r#"{"kind":"Status","status":"Failure","code":404}"#.to_string()),
+        });
+        let clock = MockClock::new(Utc::now());
+
+        // execute:
+        let result =
+            kube_wait_for_pods_deletion_with_clock(&kube_client, "app-ns", "app=app", Duration::from_secs(35), &clock);
+
+        // verify: pods are checked every 10 seconds, the last wait being cut at the deadline
+        match result {
+            Err(PollError::Timeout { elapsed, attempts }) => {
+                assert_eq!(Duration::from_secs(35), elapsed);
+                assert_eq!(5, attempts);
+            }
+            other => panic!("expected a timeout, got {:?}", other),
+        }
+        assert_eq!(Duration::from_secs(35), clock.elapsed());
+
+        // execute:
+        pods_left.store(0, Ordering::SeqCst);
+        let clock = MockClock::new(Utc::now());
+        let result =
+            kube_wait_for_pods_deletion_with_clock(&kube_client, "app-ns", "app=app", Duration::from_secs(35), &clock);
+
+        // verify:
+        assert!(result.is_ok());
+        assert_eq!(Duration::ZERO, clock.elapsed());
+    }
 }
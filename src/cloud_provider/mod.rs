@@ -6,6 +6,7 @@ use std::sync::Arc;
 use aws_config::SdkConfig;
 use serde::{Deserialize, Serialize};
 
+use crate::cloud_provider::database_backup::DatabaseBackupClient;
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::kubernetes::Kubernetes;
 use crate::cloud_provider::service::Service;
@@ -13,16 +14,18 @@ use crate::cmd::docker::Docker;
 use crate::cmd::helm::{to_engine_error, Helm};
 use crate::container_registry::ContainerRegistry;
 use crate::deployment_report::logger::EnvLogger;
+use crate::deployment_report::CancellationToken;
 use crate::dns_provider::DnsProvider;
 use crate::engine::InfrastructureContext;
-use crate::errors::EngineError;
-use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
+use crate::errors::{CommandError, EngineError};
+use crate::events::{CorrelationId, EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage, Transmitter};
 use crate::io_models::context::Context;
 use crate::logger::Logger;
 use crate::runtime::block_on;
 use crate::utilities::create_kube_client;
 
 pub mod aws;
+pub mod database_backup;
 pub mod digitalocean;
 pub mod environment;
 pub mod helm;
@@ -36,7 +39,7 @@ pub mod scaleway;
 pub mod service;
 pub mod utilities;
 
-pub trait CloudProvider {
+pub trait CloudProvider: Send + Sync {
     fn context(&self) -> &Context;
     fn kind(&self) -> Kind;
     fn kubernetes_kind(&self) -> kubernetes::Kind;
@@ -51,9 +54,58 @@ pub trait CloudProvider {
     fn secret_access_key(&self) -> String;
     fn region(&self) -> String;
     fn aws_sdk_client(&self) -> Option<SdkConfig>;
+    /// client of the provider managed databases backup API, None if the provider has none
+    fn database_backup_client(&self) -> Option<Box<dyn DatabaseBackupClient>> {
+        None
+    }
     fn token(&self) -> &str;
     fn is_valid(&self) -> Result<(), EngineError>;
+    /// Checks the credentials are allowed to perform the required operations, without performing them.
+    /// Providers without any way to check permissions beforehand don't check anything.
+    fn validate_permissions(&self, _required: &[RequiredPermission]) -> Result<(), EngineError> {
+        Ok(())
+    }
     fn zones(&self) -> &Vec<String>;
+    /// every region, and its zones, the provider can deploy clusters in
+    fn supported_regions(&self) -> &'static [SupportedRegion];
+    fn supported_region(&self, code: &str) -> Option<&'static SupportedRegion> {
+        self.supported_regions().iter().find(|region| region.code == code)
+    }
+    /// Checks the region, and the requested zones within it, are supported by the provider.
+    fn check_region_and_zones(
+        &self,
+        region: &str,
+        zones: &[String],
+        event_details: EventDetails,
+    ) -> Result<&'static SupportedRegion, EngineError> {
+        let supported_region = self.supported_region(region).ok_or_else(|| {
+            EngineError::new_unsupported_region(
+                event_details.clone(),
+                region.to_string(),
+                CommandError::new_from_safe_message(format!(
+                    "`{}` is not part of {} supported regions",
+                    region,
+                    self.kind()
+                )),
+            )
+        })?;
+
+        match zones.iter().find(|zone| !supported_region.has_zone(zone)) {
+            Some(zone) => Err(EngineError::new_unsupported_zone(
+                event_details,
+                region.to_string(),
+                zone.to_string(),
+                CommandError::new_from_safe_message(format!(
+                    "`{}` supported zones are: {}",
+                    region,
+                    supported_region.zones.join(", ")
+                )),
+            )),
+            None => Ok(supported_region),
+        }
+    }
+    /// host of the provider managed container registry (used in registry secrets / docker auth configs)
+    fn container_registry_host(&self) -> Result<String, EngineError>;
     /// environment variables containing credentials
     fn credentials_environment_variables(&self) -> Vec<(&str, &str)>;
     /// environment variables to inject to generate Terraform files from templates
@@ -97,6 +149,47 @@ impl Display for Kind {
 
 pub trait CloudProviderZones {}
 
+/// Cloud provider operation the engine needs to be allowed to perform, i.e: `eks:CreateCluster` on `*`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequiredPermission {
+    /// action: provider API action, i.e: `iam:CreateRole`
+    pub action: String,
+    /// resource: provider resource identifier the action is performed on, `*` for any
+    pub resource: String,
+}
+
+impl RequiredPermission {
+    pub fn new(action: &str, resource: &str) -> Self {
+        RequiredPermission {
+            action: action.to_string(),
+            resource: resource.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SupportedRegion {
+    /// human readable name, i.e: `Paris`
+    pub name: &'static str,
+    /// region code as expected by the provider API, i.e: `eu-west-3`
+    pub code: String,
+    pub zones: Vec<String>,
+}
+
+impl SupportedRegion {
+    pub fn new(name: &'static str, code: &str, zones: Vec<String>) -> Self {
+        SupportedRegion {
+            name,
+            code: code.to_string(),
+            zones,
+        }
+    }
+
+    pub fn has_zone(&self, zone: &str) -> bool {
+        self.zones.iter().any(|z| z == zone)
+    }
+}
+
 pub struct TerraformStateCredentials {
     pub access_key_id: String,
     pub secret_access_key: String,
@@ -122,17 +215,21 @@ pub struct DeploymentTarget<'a> {
     pub docker: &'a Docker,
     pub kube: kube::Client,
     pub helm: Helm,
-    pub should_abort: &'a dyn Fn() -> bool,
+    pub should_abort: &'a (dyn Fn() -> bool + Sync),
     logger: Arc<Box<dyn Logger>>,
     pub is_dry_run_deploy: bool,
     pub is_test_cluster: bool,
+    // Injected in logs, cloud resources tags and kubernetes labels to correlate them
+    pub correlation_id: CorrelationId,
+    // Shared with long running deployment tasks, to ask them to stop early
+    pub cancellation_token: CancellationToken,
 }
 
 impl<'a> DeploymentTarget<'a> {
     pub fn new(
         infra_ctx: &'a InfrastructureContext,
         environment: &'a Environment,
-        should_abort: &'a dyn Fn() -> bool,
+        should_abort: &'a (dyn Fn() -> bool + Sync),
     ) -> Result<DeploymentTarget<'a>, EngineError> {
         let event_details = environment.event_details();
         let kubernetes = infra_ctx.kubernetes();
@@ -166,17 +263,62 @@ impl<'a> DeploymentTarget<'a> {
             logger: Arc::new(infra_ctx.kubernetes().logger().clone_dyn()),
             is_dry_run_deploy: kubernetes.context().is_dry_run_deploy(),
             is_test_cluster: kubernetes.context().is_test_cluster(),
+            correlation_id: event_details.correlation_id(),
+            cancellation_token: CancellationToken::new(),
         })
     }
 
     pub fn env_logger(&self, service: &impl Service, step: EnvironmentStep) -> EnvLogger {
         EnvLogger::new(service, step, self.logger.clone())
     }
+
+    /// Logs a mutation skipped because of a dry run, so the user can see what would have been done.
+    pub fn log_dry_run(&self, event_details: &EventDetails, skipped_action: String) {
+        self.logger.log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe(format!("🔍 Dry run, skipping: {}", skipped_action)),
+        ));
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::cloud_provider::Kind;
+    use crate::cloud_provider::aws::AWS;
+    use crate::cloud_provider::digitalocean::DO;
+    use crate::cloud_provider::scaleway::Scaleway;
+    use crate::cloud_provider::{kubernetes, CloudProvider, Kind, TerraformStateCredentials};
+    use crate::cmd::docker::Docker;
+    use crate::errors::Tag;
+    use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
+    use crate::io_models::context::Context;
+    use crate::io_models::QoveryIdentifier;
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    fn test_context() -> Context {
+        let organization_id = Uuid::new_v4();
+        let cluster_id = Uuid::new_v4();
+        Context::new(
+            organization_id,
+            cluster_id,
+            "execution_id".to_string(),
+            "/tmp".to_string(),
+            "/tmp".to_string(),
+            true,
+            None,
+            vec![],
+            None,
+            Docker::new_with_options(false, None).expect("Can't init docker"),
+            EventDetails::new(
+                None,
+                QoveryIdentifier::new(organization_id),
+                QoveryIdentifier::new(cluster_id),
+                "execution_id".to_string(),
+                Stage::Infrastructure(InfrastructureStep::LoadConfiguration),
+                Transmitter::Kubernetes(cluster_id, "cluster".to_string()),
+            ),
+        )
+    }
 
     #[test]
     fn test_provider_kind_from_str() {
@@ -215,4 +357,248 @@ mod tests {
             assert_eq!(tc.1, result);
         }
     }
+
+    #[test]
+    fn test_provider_container_registry_host() {
+        // setup:
+        let credentials = || TerraformStateCredentials::new("access", "secret", "region");
+        let test_cases: Vec<(Box<dyn CloudProvider>, &str)> = vec![
+            (
+                Box::new(
+                    AWS::new(
+                        test_context(),
+                        Uuid::new_v4(),
+                        "aws",
+                        "access",
+                        "secret",
+                        "eu-west-3",
+                        vec![],
+                        kubernetes::Kind::Eks,
+                        credentials(),
+                    )
+                    .with_account_id("123456789012"),
+                ),
+                "123456789012.dkr.ecr.eu-west-3.amazonaws.com",
+            ),
+            (
+                Box::new(
+                    DO::new(
+                        test_context(),
+                        Uuid::new_v4(),
+                        "token",
+                        "access",
+                        "secret",
+                        "fra1",
+                        "do",
+                        credentials(),
+                    )
+                    .expect("fra1 is a valid DigitalOcean region"),
+                ),
+                "registry.digitalocean.com",
+            ),
+            (
+                Box::new(Scaleway::new(
+                    test_context(),
+                    Uuid::new_v4(),
+                    "scw",
+                    "access",
+                    "secret",
+                    "project",
+                    "fr-par",
+                    credentials(),
+                )),
+                "rg.fr-par.scw.cloud",
+            ),
+        ];
+
+        for (cloud_provider, expected_host) in test_cases {
+            // execute & verify:
+            assert_eq!(
+                Ok(expected_host.to_string()),
+                cloud_provider.container_registry_host(),
+                "wrong registry host for {}",
+                cloud_provider.kind()
+            );
+        }
+    }
+
+    fn test_cloud_providers() -> Vec<Box<dyn CloudProvider>> {
+        let credentials = || TerraformStateCredentials::new("access", "secret", "region");
+        vec![
+            Box::new(AWS::new(
+                test_context(),
+                Uuid::new_v4(),
+                "aws",
+                "access",
+                "secret",
+                "eu-west-3",
+                vec![],
+                kubernetes::Kind::Eks,
+                credentials(),
+            )),
+            Box::new(
+                DO::new(
+                    test_context(),
+                    Uuid::new_v4(),
+                    "token",
+                    "access",
+                    "secret",
+                    "fra1",
+                    "do",
+                    credentials(),
+                )
+                .expect("fra1 is a valid DigitalOcean region"),
+            ),
+            Box::new(Scaleway::new(
+                test_context(),
+                Uuid::new_v4(),
+                "scw",
+                "access",
+                "secret",
+                "project",
+                "fr-par",
+                credentials(),
+            )),
+        ]
+    }
+
+    #[test]
+    fn test_supported_regions_are_deduplicated() {
+        for cloud_provider in test_cloud_providers() {
+            // execute:
+            let regions = cloud_provider.supported_regions();
+
+            // verify:
+            assert!(!regions.is_empty(), "no regions for {}", cloud_provider.kind());
+            let codes: HashSet<&str> = regions.iter().map(|region| region.code.as_str()).collect();
+            assert_eq!(regions.len(), codes.len(), "duplicated regions for {}", cloud_provider.kind());
+            assert!(cloud_provider.supported_region(&cloud_provider.region()).is_some());
+
+            for region in regions {
+                assert!(!region.name.is_empty());
+                assert!(!region.zones.is_empty(), "no zones for {}", region.code);
+                let zones: HashSet<&String> = region.zones.iter().collect();
+                assert_eq!(region.zones.len(), zones.len(), "duplicated zones for {}", region.code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_supported_region_zones_lookup() {
+        // setup:
+        let cloud_providers = test_cloud_providers();
+        let test_cases = vec![
+            (&cloud_providers[0], "eu-west-3", vec!["eu-west-3a", "eu-west-3b", "eu-west-3c"]),
+            (&cloud_providers[1], "fra1", vec!["fra1"]),
+            (&cloud_providers[2], "fr-par", vec!["fr-par-1", "fr-par-2", "fr-par-3"]),
+            (&cloud_providers[2], "pl-waw", vec!["pl-waw-1"]),
+        ];
+
+        for (cloud_provider, region, expected_zones) in test_cases {
+            // execute:
+            let supported_region = cloud_provider.supported_region(region);
+
+            // verify:
+            assert_eq!(
+                expected_zones,
+                supported_region.expect("region should be supported").zones,
+                "wrong zones for {}",
+                region
+            );
+        }
+        assert_eq!(
+            &vec!["fr-par-1".to_string(), "fr-par-2".to_string(), "fr-par-3".to_string()],
+            cloud_providers[2].zones()
+        );
+    }
+
+    #[test]
+    fn test_check_region_and_zones() {
+        // setup:
+        let cloud_provider = &test_cloud_providers()[0];
+        let event_details = || cloud_provider.get_event_details(Stage::Infrastructure(InfrastructureStep::Create));
+
+        // execute:
+        let valid = cloud_provider.check_region_and_zones(
+            "eu-west-3",
+            &["eu-west-3a".to_string(), "eu-west-3c".to_string()],
+            event_details(),
+        );
+        let unsupported_region = cloud_provider.check_region_and_zones("fr-par", &[], event_details());
+        let unsupported_zone =
+            cloud_provider.check_region_and_zones("eu-west-3", &["eu-west-1a".to_string()], event_details());
+
+        // verify:
+        assert_eq!("Paris", valid.expect("region and zones are supported").name);
+        assert_eq!(
+            &Tag::UnsupportedRegion,
+            unsupported_region.expect_err("fr-par is not an AWS region").tag()
+        );
+        let err = unsupported_zone.expect_err("eu-west-1a is not in eu-west-3");
+        assert_eq!(&Tag::UnsupportedZone, err.tag());
+        assert!(err
+            .user_log_message()
+            .contains("Zone `eu-west-1a` is not supported in region `eu-west-3`"));
+    }
+
+    #[test]
+    fn test_do_region_is_validated() {
+        // setup:
+        let new_do = |region: &str| {
+            DO::new(
+                test_context(),
+                Uuid::new_v4(),
+                "token",
+                "access",
+                "secret",
+                region,
+                "do",
+                TerraformStateCredentials::new("access", "secret", "region"),
+            )
+        };
+
+        // execute:
+        let valid = new_do("nyc3");
+        let invalid = new_do("eu-west-3");
+
+        // verify:
+        let cloud_provider = valid.expect("nyc3 is a valid DigitalOcean region");
+        assert!(cloud_provider.zones().contains(&"nyc3".to_string()));
+        assert!(cloud_provider.zones().contains(&"fra1".to_string()));
+
+        let err = invalid.err().expect("eu-west-3 is not a DigitalOcean region");
+        assert_eq!(&Tag::UnsupportedRegion, err.tag());
+        assert!(err.user_log_message().contains("`eu-west-3` region is not supported"));
+    }
+
+    #[test]
+    fn test_do_aws_sdk_client_targets_spaces() {
+        // setup:
+        let cloud_provider = DO::new(
+            test_context(),
+            Uuid::new_v4(),
+            "token",
+            "spaces_access_id",
+            "spaces_secret_key",
+            "fra1",
+            "do",
+            TerraformStateCredentials::new("access", "secret", "region"),
+        )
+        .expect("fra1 is a valid DigitalOcean region");
+
+        // execute:
+        let config = cloud_provider.aws_sdk_client().expect("Spaces is S3 compatible");
+
+        // verify:
+        let region = config.region().expect("region should be set").clone();
+        assert_eq!("fra1", region.as_ref());
+        let endpoint = config
+            .endpoint_resolver()
+            .expect("endpoint should be set")
+            .resolve_endpoint(&region)
+            .expect("endpoint should be resolved");
+        let mut uri = http::Uri::from_static("/bucket");
+        endpoint.endpoint().set_endpoint(&mut uri, None);
+        assert_eq!("https://fra1.digitaloceanspaces.com/bucket", uri.to_string());
+    }
 }
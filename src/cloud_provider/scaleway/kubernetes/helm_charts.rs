@@ -1,7 +1,8 @@
 use crate::cloud_provider::helm::{
     get_chart_for_cert_manager_config, get_chart_for_cluster_agent, get_chart_for_shell_agent,
-    get_engine_helm_action_from_location, ChartInfo, ChartSetValue, ChartValuesGenerated, ClusterAgentContext,
-    CommonChart, HelmAction, HelmChart, HelmChartNamespaces, ShellAgentContext,
+    get_engine_helm_action_from_location, override_charts_timeout, ChartInfo, ChartSetValue, ChartValuesGenerated,
+    ClusterAgentContext, CommonChart, FeatureFlaggedCharts, HelmAction, HelmChart, HelmChartNamespaces,
+    ShellAgentContext,
 };
 use crate::cloud_provider::helm_charts::qovery_storage_class_chart::{QoveryStorageClassChart, QoveryStorageType};
 use crate::cloud_provider::helm_charts::ToCommonHelmChart;
@@ -11,11 +12,12 @@ use crate::cloud_provider::scaleway::kubernetes::KapsuleOptions;
 
 use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
+use crate::io_models::context::FeatureFlags;
 use crate::models::scaleway::{ScwRegion, ScwZone};
 
-use crate::cloud_provider::helm_charts::coredns_config_chart::CoreDNSConfigChart;
+use crate::cloud_provider::helm_charts::coredns_config_chart::{parse_dns_resolvers, CoreDNSConfigChart};
 use crate::cloud_provider::helm_charts::external_dns_chart::ExternalDNSChart;
-use crate::cloud_provider::helm_charts::kube_prometheus_stack_chart::KubePrometheusStackChart;
+use crate::cloud_provider::helm_charts::kube_prometheus_stack_chart::{KubePrometheusStackChart, RemoteWriteConfig};
 use crate::cloud_provider::helm_charts::loki_chart::{LokiChart, LokiEncryptionType, LokiS3BucketConfiguration};
 use crate::cloud_provider::helm_charts::prometheus_adapter_chart::PrometheusAdapterChart;
 use crate::cloud_provider::helm_charts::promtail_chart::PromtailChart;
@@ -47,8 +49,7 @@ pub struct ChartsConfigPrerequisites {
     pub scw_secret_key: String,
     pub scw_project_id: String,
     pub qovery_engine_location: EngineLocation,
-    pub ff_log_history_enabled: bool,
-    pub ff_metrics_history_enabled: bool,
+    pub feature_flags: FeatureFlags,
     pub managed_dns_name: String,
     pub managed_dns_helm_format: String,
     pub managed_dns_resolvers_terraform_format: String,
@@ -57,7 +58,6 @@ pub struct ChartsConfigPrerequisites {
     pub dns_email_report: String,
     pub acme_url: String,
     pub dns_provider_config: DnsProviderConfiguration,
-    pub disable_pleco: bool,
     // qovery options form json input
     pub infra_options: KapsuleOptions,
     pub cluster_advanced_settings: ClusterAdvancedSettings,
@@ -77,8 +77,7 @@ impl ChartsConfigPrerequisites {
         scw_secret_key: String,
         scw_project_id: String,
         qovery_engine_location: EngineLocation,
-        ff_log_history_enabled: bool,
-        ff_metrics_history_enabled: bool,
+        feature_flags: FeatureFlags,
         managed_dns_name: String,
         managed_dns_helm_format: String,
         managed_dns_resolvers_terraform_format: String,
@@ -87,7 +86,6 @@ impl ChartsConfigPrerequisites {
         dns_email_report: String,
         acme_url: String,
         dns_provider_config: DnsProviderConfiguration,
-        disable_pleco: bool,
         infra_options: KapsuleOptions,
         cluster_advanced_settings: ClusterAdvancedSettings,
     ) -> Self {
@@ -105,8 +103,7 @@ impl ChartsConfigPrerequisites {
             scw_secret_key,
             scw_project_id,
             qovery_engine_location,
-            ff_log_history_enabled,
-            ff_metrics_history_enabled,
+            feature_flags,
             managed_dns_name,
             managed_dns_helm_format,
             managed_dns_resolvers_terraform_format,
@@ -115,7 +112,6 @@ impl ChartsConfigPrerequisites {
             dns_email_report,
             acme_url,
             dns_provider_config,
-            disable_pleco,
             infra_options,
             cluster_advanced_settings,
         }
@@ -173,6 +169,11 @@ pub fn scw_helm_charts(
         chart_config_prerequisites
             .managed_dns_resolvers_terraform_format
             .to_string(),
+        parse_dns_resolvers(
+            &chart_config_prerequisites
+                .cluster_advanced_settings
+                .dns_custom_resolvers,
+        )?,
     );
 
     // External DNS
@@ -191,16 +192,17 @@ pub fn scw_helm_charts(
     let promtail = PromtailChart::new(chart_prefix_path, loki_kube_dns_name).to_common_helm_chart();
 
     // Loki
+    let advanced_settings = &chart_config_prerequisites.cluster_advanced_settings;
     let loki = LokiChart::new(
         chart_prefix_path,
-        LokiEncryptionType::None, // Scaleway does not support encryption yet.
+        // Scaleway does not support encryption yet.
+        LokiEncryptionType::from_advanced_settings(advanced_settings, false, LokiEncryptionType::None)?,
         loki_namespace,
-        chart_config_prerequisites
-            .cluster_advanced_settings
-            .loki_log_retention_in_week,
+        advanced_settings.loki_log_retention_in_week,
         LokiS3BucketConfiguration {
             s3_config: Some(qovery_terraform_config.loki_storage_config_scaleway_s3),
-            use_path_style: true,
+            use_path_style: advanced_settings.loki_s3_use_path_style.unwrap_or(true),
+            insecure: advanced_settings.loki_s3_insecure,
             region: Some(chart_config_prerequisites.zone.region().to_string()),
             ..Default::default()
         },
@@ -224,6 +226,7 @@ pub fn scw_helm_charts(
         prometheus_internal_url.to_string(),
         prometheus_namespace,
         true,
+        RemoteWriteConfig::from_advanced_settings(advanced_settings)?,
     )
     .to_common_helm_chart();
 
@@ -340,7 +343,10 @@ datasources:
                 },
                 ChartSetValue {
                     key: "prometheus.servicemonitor.enabled".to_string(),
-                    value: chart_config_prerequisites.ff_metrics_history_enabled.to_string(),
+                    value: chart_config_prerequisites
+                        .feature_flags
+                        .metrics_history_enabled
+                        .to_string(),
                 },
                 ChartSetValue {
                     key: "prometheus.servicemonitor.prometheusInstance".to_string(),
@@ -507,7 +513,7 @@ datasources:
         cluster_long_id: &chart_config_prerequisites.cluster_long_id,
         cluster_jwt_token: &chart_config_prerequisites.infra_options.jwt_token,
         grpc_url: &chart_config_prerequisites.infra_options.qovery_grpc_url,
-        loki_url: if chart_config_prerequisites.ff_log_history_enabled {
+        loki_url: if chart_config_prerequisites.feature_flags.log_history_enabled {
             Some("http://loki.logging.svc.cluster.local:3100")
         } else {
             None
@@ -562,7 +568,10 @@ datasources:
                 },
                 ChartSetValue {
                     key: "metrics.enabled".to_string(),
-                    value: chart_config_prerequisites.ff_metrics_history_enabled.to_string(),
+                    value: chart_config_prerequisites
+                        .feature_flags
+                        .metrics_history_enabled
+                        .to_string(),
                 },
                 ChartSetValue {
                     key: "volumes.storageClassName".to_string(),
@@ -668,26 +677,30 @@ datasources:
         Box::new(qovery_engine),
     ];
 
-    // // observability
-    if chart_config_prerequisites.ff_metrics_history_enabled {
-        level_1.push(Box::new(kube_prometheus_stack));
-        level_2.push(Box::new(prometheus_adapter));
-        level_2.push(Box::new(kube_state_metrics));
-    }
-    if chart_config_prerequisites.ff_log_history_enabled {
-        level_1.push(Box::new(promtail));
-        level_2.push(Box::new(loki));
+    FeatureFlaggedCharts {
+        kube_prometheus_stack: Box::new(kube_prometheus_stack),
+        prometheus_adapter: Box::new(prometheus_adapter),
+        kube_state_metrics: Box::new(kube_state_metrics),
+        promtail: Box::new(promtail),
+        loki: Box::new(loki),
+        grafana: Box::new(grafana),
+        pleco: Box::new(pleco),
     }
+    .add_to_levels(
+        &chart_config_prerequisites.feature_flags,
+        &mut level_1,
+        &mut level_2,
+        &mut level_6,
+    );
 
-    if chart_config_prerequisites.ff_metrics_history_enabled || chart_config_prerequisites.ff_log_history_enabled {
-        level_2.push(Box::new(grafana))
-    };
-
-    // pleco
-    if !chart_config_prerequisites.disable_pleco {
-        level_6.push(Box::new(pleco));
-    }
+    let mut levels = vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7];
+    override_charts_timeout(
+        &mut levels,
+        &chart_config_prerequisites
+            .cluster_advanced_settings
+            .helm_charts_timeout_override_sec,
+    );
 
     info!("charts configuration preparation finished");
-    Ok(vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7])
+    Ok(levels)
 }
@@ -708,7 +708,11 @@ impl Kapsule {
         }
 
         // terraform deployment dedicated to cloud resources
-        if let Err(e) = terraform_init_validate_plan_apply(temp_dir.as_str(), self.context.is_dry_run_deploy()) {
+        if let Err(e) = terraform_init_validate_plan_apply(
+            temp_dir.as_str(),
+            self.context.is_dry_run_deploy(),
+            self.advanced_settings().terraform_parallelism,
+        ) {
             return Err(EngineError::new_terraform_error(event_details, e));
         }
 
@@ -933,8 +937,7 @@ impl Kapsule {
             self.cloud_provider.secret_access_key(),
             self.options.scaleway_project_id.to_string(),
             self.options.qovery_engine_location.clone(),
-            self.context.is_feature_enabled(&Features::LogsHistory),
-            self.context.is_feature_enabled(&Features::MetricsHistory),
+            self.context.feature_flags(),
             self.dns_provider.domain().root_domain().to_string(),
             self.dns_provider.domain().to_helm_format_string(),
             self.managed_dns_resolvers_terraform_format(),
@@ -943,7 +946,6 @@ impl Kapsule {
             self.options.tls_email_report.clone(),
             self.lets_encrypt_url(),
             self.dns_provider().provider_configuration(),
-            self.context.disable_pleco(),
             self.options.clone(),
             self.advanced_settings().clone(),
         );
@@ -967,6 +969,7 @@ impl Kapsule {
             &credentials_environment_variables,
             helm_charts_to_deploy,
             self.context.is_dry_run_deploy(),
+            self.advanced_settings().helm_charts_max_parallelism as usize,
         )
         .map_err(|e| EngineError::new_helm_charts_deploy_error(event_details.clone(), e))
     }
@@ -1140,7 +1143,11 @@ impl Kapsule {
             EventMessage::new_from_safe("Pausing cluster deployment.".to_string()),
         ));
 
-        if let Err(e) = terraform_apply_with_tf_workers_resources(temp_dir.as_str(), tf_workers_resources) {
+        if let Err(e) = terraform_apply_with_tf_workers_resources(
+            temp_dir.as_str(),
+            tf_workers_resources,
+            self.advanced_settings().terraform_parallelism,
+        ) {
             return Err(EngineError::new_terraform_error(event_details, e));
         }
 
@@ -1219,7 +1226,9 @@ impl Kapsule {
             EventMessage::new_from_safe("Running Terraform apply before running a delete.".to_string()),
         ));
 
-        if let Err(e) = terraform_init_validate_plan_apply(temp_dir.as_str(), false) {
+        if let Err(e) =
+            terraform_init_validate_plan_apply(temp_dir.as_str(), false, self.advanced_settings().terraform_parallelism)
+        {
             // An issue occurred during the apply before destroy of Terraform, it may be expected if you're resuming a destroy
             self.logger().log(EngineEvent::Error(
                 EngineError::new_terraform_error(event_details.clone(), e),
@@ -1434,7 +1443,11 @@ impl Kapsule {
             EventMessage::new_from_safe("Running Terraform destroy".to_string()),
         ));
 
-        match cmd::terraform::terraform_init_validate_destroy(temp_dir.as_str(), false) {
+        match cmd::terraform::terraform_init_validate_destroy(
+            temp_dir.as_str(),
+            false,
+            self.advanced_settings().terraform_parallelism,
+        ) {
             Ok(_) => {
                 self.logger().log(EngineEvent::Info(
                     event_details,
@@ -1628,7 +1641,11 @@ impl Kubernetes for Kapsule {
             return Err(e);
         }
 
-        match terraform_init_validate_plan_apply(temp_dir.as_str(), self.context.is_dry_run_deploy()) {
+        match terraform_init_validate_plan_apply(
+            temp_dir.as_str(),
+            self.context.is_dry_run_deploy(),
+            self.advanced_settings().terraform_parallelism,
+        ) {
             Ok(_) => match self.check_workers_on_upgrade(kubernetes_upgrade_status.requested_version.to_string()) {
                 Ok(_) => {
                     self.logger().log(EngineEvent::Info(
@@ -1,17 +1,32 @@
+use lazy_static::lazy_static;
 use std::any::Any;
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 
 use crate::cloud_provider::{
-    kubernetes::Kind as KubernetesKind, CloudProvider, EngineError, Kind, TerraformStateCredentials,
+    kubernetes::Kind as KubernetesKind, CloudProvider, EngineError, Kind, SupportedRegion, TerraformStateCredentials,
 };
 use crate::constants::{SCALEWAY_ACCESS_KEY, SCALEWAY_DEFAULT_PROJECT_ID, SCALEWAY_SECRET_KEY};
 use crate::events::{EventDetails, Stage, Transmitter};
 use crate::io_models::context::Context;
 use crate::io_models::QoveryIdentifier;
+use crate::models::scaleway::{ScwRegion, ScwZone};
 use crate::utilities::to_short_id;
 
 pub mod kubernetes;
 
+lazy_static! {
+    static ref SCW_REGIONS: Vec<SupportedRegion> = ScwRegion::iter()
+        .map(|region| {
+            let zones = ScwZone::iter()
+                .filter(|zone| zone.region() == region)
+                .map(|zone| zone.to_string())
+                .collect();
+            SupportedRegion::new(region.human_name(), region.as_str(), zones)
+        })
+        .collect();
+}
+
 pub struct Scaleway {
     context: Context,
     id: String,
@@ -21,6 +36,7 @@ pub struct Scaleway {
     secret_key: String,
     project_id: String,
     region: String,
+    zones: Vec<String>,
     terraform_state_credentials: TerraformStateCredentials,
 }
 
@@ -44,6 +60,11 @@ impl Scaleway {
             secret_key: secret_key.to_string(),
             project_id: project_id.to_string(),
             region: region.to_string(),
+            zones: SCW_REGIONS
+                .iter()
+                .find(|supported_region| supported_region.code == region)
+                .map(|supported_region| supported_region.zones.clone())
+                .unwrap_or_default(),
             terraform_state_credentials,
         }
     }
@@ -100,7 +121,15 @@ impl CloudProvider for Scaleway {
     }
 
     fn zones(&self) -> &Vec<String> {
-        todo!()
+        &self.zones
+    }
+
+    fn supported_regions(&self) -> &'static [SupportedRegion] {
+        &SCW_REGIONS
+    }
+
+    fn container_registry_host(&self) -> Result<String, EngineError> {
+        Ok(format!("rg.{}.scw.cloud", self.region))
     }
 
     fn credentials_environment_variables(&self) -> Vec<(&str, &str)> {
@@ -1,19 +1,23 @@
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::BufReader;
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 use tera::Context as TeraContext;
 use uuid::Uuid;
 
 use crate::cloud_provider::environment::Environment;
-use crate::cloud_provider::kubernetes::Kubernetes;
+use crate::cloud_provider::kubernetes::{retry_kube, Kubernetes, RetryPolicy};
 use crate::cmd::kubectl::{kubectl_exec_delete_pod, kubectl_exec_get_pods};
 use crate::cmd::structs::KubernetesPodStatusPhase;
 use crate::cmd::terraform::TerraformError;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
+use crate::io_models::application::AdvancedSettingsProbeType;
+use crate::io_models::context::Context;
 use crate::models;
 use crate::models::database::{Database, DatabaseMode};
 
@@ -29,14 +33,90 @@ pub trait Service {
     fn action(&self) -> &Action;
     // used to retrieve logs by using Kubernetes labels (selector)
     fn selector(&self) -> Option<String>;
+    // endpoint telling if the service is ready, if None only the kubernetes readiness of its pods can be checked
+    fn health_check(&self) -> Option<HealthCheck> {
+        None
+    }
     fn as_service(&self) -> &dyn Service;
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HealthCheckProtocol {
+    Http,
+    Https,
+}
+
+/// Statuses considered as a success by Kubernetes HTTP probes.
+pub const HTTP_PROBE_SUCCESS_STATUSES: RangeInclusive<u16> = 200..=399;
+
+/// HTTP endpoint answering one of `expected_status` once the service is ready to receive traffic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthCheck {
+    pub protocol: HealthCheckProtocol,
+    pub path: String,
+    pub port: u16,
+    pub expected_status: RangeInclusive<u16>,
+}
+
+impl HealthCheck {
+    /// Only an HTTP readiness probe describes a health check endpoint, it succeeds on the same statuses as the probe.
+    pub fn from_readiness_probe(
+        probe_type: &AdvancedSettingsProbeType,
+        port: Option<u16>,
+        http_path: &str,
+    ) -> Option<HealthCheck> {
+        match (probe_type, port) {
+            (AdvancedSettingsProbeType::Http, Some(port)) => Some(HealthCheck {
+                protocol: HealthCheckProtocol::Http,
+                path: http_path.to_string(),
+                port,
+                expected_status: HTTP_PROBE_SUCCESS_STATUSES,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Path of the health check endpoint of the pod through the API server pod proxy
+    pub fn pod_proxy_path(&self, namespace: &str, pod_name: &str) -> String {
+        let scheme = match self.protocol {
+            HealthCheckProtocol::Http => "http",
+            HealthCheckProtocol::Https => "https",
+        };
+        format!(
+            "/api/v1/namespaces/{}/pods/{}:{}:{}/proxy{}",
+            namespace, scheme, pod_name, self.port, self.path
+        )
+    }
+
+    /// The engine may run outside of the cluster network, so the pod is reached through the API server pod proxy.
+    /// Like probes, redirections are not followed: a 3xx is an answer from the service
+    pub async fn run(&self, kube: &kube::Client, namespace: &str, pod_name: &str) -> Result<(), String> {
+        let path = self.pod_proxy_path(namespace, pod_name);
+        let request = http::Request::get(&path)
+            .body(vec![])
+            .map_err(|err| format!("HTTP check on {} failed: {}", path, err))?;
+
+        match tokio::time::timeout(Duration::from_secs(5), kube.request_text(request)).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(kube::Error::Api(err))) if !self.expected_status.contains(&err.code) => Err(format!(
+                "HTTP check on {} returned {}, expected {} to {}",
+                path,
+                err.code,
+                self.expected_status.start(),
+                self.expected_status.end()
+            )),
+            Ok(Err(err)) => Err(format!("HTTP check on {} failed: {}", path, err)),
+            Err(_) => Err(format!("HTTP check on {} timed out", path)),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub enum Action {
     Create,
     Pause,
     Delete,
+    Restart,
 }
 
 impl Action {
@@ -45,6 +125,8 @@ impl Action {
             Action::Create => EnvironmentStep::Deploy,
             Action::Pause => EnvironmentStep::Pause,
             Action::Delete => EnvironmentStep::Delete,
+            // a restart redeploys the service in place
+            Action::Restart => EnvironmentStep::Deploy,
         }
     }
 }
@@ -68,6 +150,18 @@ impl ToString for DatabaseType {
     }
 }
 
+impl DatabaseType {
+    /// Port the database listens on inside its container, as set by the charts in `lib/common/services`.
+    pub fn container_port(&self) -> u16 {
+        match self {
+            DatabaseType::PostgreSQL => 5432,
+            DatabaseType::MongoDB => 27017,
+            DatabaseType::MySQL => 3306,
+            DatabaseType::Redis => 6379,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum ServiceType {
     Application,
@@ -87,6 +181,17 @@ impl ServiceType {
             ServiceType::Job => "Job".to_string(),
         }
     }
+
+    /// Directory, relative to the execution workspace, holding the workspaces of services of this type.
+    pub fn workspace_root_subdir(&self) -> &'static str {
+        match self {
+            ServiceType::Application => "applications",
+            ServiceType::Database(_) => "databases",
+            ServiceType::Router => "routers",
+            ServiceType::Container => "containers",
+            ServiceType::Job => "jobs",
+        }
+    }
 }
 
 impl ToString for ServiceType {
@@ -95,6 +200,37 @@ impl ToString for ServiceType {
     }
 }
 
+/// Creates, if needed, the workspace directory of a service and returns its path.
+///
+/// Arguments:
+///
+/// * `context`: Execution context holding the workspace root directory.
+/// * `service_type`: Type of the service, selecting the workspace sub directory.
+/// * `long_id`: Service identifier.
+/// * `event_details`: Event details of the returned error.
+pub fn workspace_directory(
+    context: &Context,
+    service_type: &ServiceType,
+    long_id: &Uuid,
+    event_details: EventDetails,
+) -> Result<String, EngineError> {
+    crate::fs::workspace_directory(
+        context.workspace_root_dir(),
+        context.execution_id(),
+        format!("{}/{}", service_type.workspace_root_subdir(), long_id),
+    )
+    .map_err(|err| {
+        EngineError::new_cannot_get_workspace_directory(
+            event_details,
+            CommandError::new(
+                format!("Cannot create {} workspace directory", service_type.name()),
+                Some(err.to_string()),
+                None,
+            ),
+        )
+    })
+}
+
 pub fn default_tera_context(
     service: &dyn Service,
     kubernetes: &dyn Kubernetes,
@@ -251,23 +387,173 @@ pub fn delete_pending_service<P>(
 where
     P: AsRef<Path>,
 {
-    match kubectl_exec_get_pods(&kubernetes_config, Some(namespace), Some(selector), envs.clone()) {
-        Ok(pods) => {
-            for pod in pods.items {
-                if pod.status.phase == KubernetesPodStatusPhase::Pending {
-                    if let Err(e) = kubectl_exec_delete_pod(
-                        &kubernetes_config,
-                        pod.metadata.namespace.as_str(),
-                        pod.metadata.name.as_str(),
-                        envs.clone(),
-                    ) {
-                        return Err(EngineError::new_k8s_service_issue(event_details, e));
-                    }
-                }
+    let pods = retry_kube(
+        || {
+            kubectl_exec_get_pods(&kubernetes_config, Some(namespace), Some(selector), envs.clone())
+                .map_err(|e| EngineError::new_k8s_cannot_get_pods(event_details.clone(), e))
+        },
+        RetryPolicy::default(),
+    )?;
+
+    for pod in pods.items {
+        if pod.status.phase == KubernetesPodStatusPhase::Pending {
+            if let Err(e) = kubectl_exec_delete_pod(
+                &kubernetes_config,
+                pod.metadata.namespace.as_str(),
+                pod.metadata.name.as_str(),
+                envs.clone(),
+            ) {
+                return Err(EngineError::new_k8s_service_issue(event_details, e));
             }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::service::{
+        workspace_directory, DatabaseType, HealthCheck, HealthCheckProtocol, ServiceType, HTTP_PROBE_SUCCESS_STATUSES,
+    };
+    use crate::cmd::docker::Docker;
+    use crate::errors::Tag;
+    use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
+    use crate::io_models::application::AdvancedSettingsProbeType;
+    use crate::io_models::context::Context;
+    use crate::io_models::QoveryIdentifier;
+    use crate::runtime::block_on;
+    use crate::test_utils::start_fake_kube_api_server;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    fn test_context(workspace_root_dir: &str) -> Context {
+        let organization_id = Uuid::new_v4();
+        let cluster_id = Uuid::new_v4();
+        Context::new(
+            organization_id,
+            cluster_id,
+            "execution_id".to_string(),
+            workspace_root_dir.to_string(),
+            "/tmp".to_string(),
+            true,
+            None,
+            vec![],
+            None,
+            Docker::new_with_options(false, None).expect("Can't init docker"),
+            EventDetails::new(
+                None,
+                QoveryIdentifier::new(organization_id),
+                QoveryIdentifier::new(cluster_id),
+                "execution_id".to_string(),
+                Stage::Environment(EnvironmentStep::Deploy),
+                Transmitter::Environment(Uuid::new_v4(), "environment".to_string()),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_workspace_directory() {
+        // setup:
+        let workspace_root_dir = tempfile::tempdir().expect("cannot create temp dir");
+        let context = test_context(workspace_root_dir.path().to_str().unwrap());
+        let long_id = Uuid::new_v4();
 
-            Ok(())
+        for (service_type, subdir) in [
+            (ServiceType::Application, "applications"),
+            (ServiceType::Database(DatabaseType::PostgreSQL), "databases"),
+            (ServiceType::Router, "routers"),
+            (ServiceType::Container, "containers"),
+            (ServiceType::Job, "jobs"),
+        ] {
+            // execute:
+            let directory = workspace_directory(
+                &context,
+                &service_type,
+                &long_id,
+                context.get_event_details(Transmitter::Application(long_id, "app".to_string())),
+            )
+            .expect("workspace directory should be created");
+
+            // verify:
+            assert!(directory.ends_with(&format!("/execution_id/{}/{}", subdir, long_id)));
+            assert!(std::path::Path::new(&directory).is_dir());
+        }
+    }
+
+    #[test]
+    fn test_workspace_directory_creation_failure() {
+        // setup:
+        // a file standing where the workspace root directory is expected prevents its creation
+        let workspace_root_file = tempfile::NamedTempFile::new().expect("cannot create temp file");
+        let context = test_context(workspace_root_file.path().to_str().unwrap());
+        let long_id = Uuid::new_v4();
+
+        // execute:
+        let result = workspace_directory(
+            &context,
+            &ServiceType::Application,
+            &long_id,
+            context.get_event_details(Transmitter::Application(long_id, "app".to_string())),
+        );
+
+        // verify:
+        let error = result.expect_err("workspace directory creation should fail");
+        assert_eq!(&Tag::CannotGetWorkspaceDirectory, error.tag());
+    }
+
+    #[test]
+    fn test_health_check_from_readiness_probe() {
+        assert_eq!(
+            Some(HealthCheck {
+                protocol: HealthCheckProtocol::Http,
+                path: "/health".to_string(),
+                port: 8080,
+                expected_status: 200..=399,
+            }),
+            HealthCheck::from_readiness_probe(&AdvancedSettingsProbeType::Http, Some(8080), "/health")
+        );
+        assert_eq!(
+            None,
+            HealthCheck::from_readiness_probe(&AdvancedSettingsProbeType::Tcp, Some(8080), "/")
+        );
+        assert_eq!(
+            None,
+            HealthCheck::from_readiness_probe(&AdvancedSettingsProbeType::Http, None, "/")
+        );
+    }
+
+    #[test]
+    fn test_health_check_run() {
+        for (status, is_healthy) in [(200, true), (204, true), (302, true), (404, false), (503, false)] {
+            // setup:
+            let requested_paths = Arc::new(Mutex::new(vec![]));
+            let kube = {
+                let requested_paths = requested_paths.clone();
+                start_fake_kube_api_server(move |_, path, _| {
+                    requested_paths.lock().unwrap().push(path.to_string());
+                    (status, "".to_string())
+                })
+            };
+            let health_check = HealthCheck {
+                protocol: HealthCheckProtocol::Http,
+                path: "/health".to_string(),
+                port: 8080,
+                expected_status: HTTP_PROBE_SUCCESS_STATUSES,
+            };
+
+            // execute:
+            let result = block_on(health_check.run(&kube, "my-namespace", "app-green-1"));
+
+            // verify:
+            assert_eq!(is_healthy, result.is_ok(), "status: {}, result: {:?}", status, result);
+            if let Err(err) = result {
+                assert!(err.contains(&format!("returned {}, expected 200 to 399", status)));
+            }
+            assert_eq!(
+                vec!["/api/v1/namespaces/my-namespace/pods/http:app-green-1:8080/proxy/health".to_string()],
+                *requested_paths.lock().unwrap()
+            );
         }
-        Err(e) => Err(EngineError::new_k8s_service_issue(event_details, e)),
     }
 }
@@ -9,7 +9,7 @@ use core::result::Result::{Err, Ok};
 use retry::delay::Fixed;
 use retry::{Error, OperationResult};
 use std::net::ToSocketAddrs;
-use std::net::{SocketAddr, TcpStream as NetTcpStream};
+use std::net::{IpAddr, SocketAddr, TcpStream as NetTcpStream};
 use std::time::Duration;
 use std::{fmt, thread};
 use trust_dns_resolver::config::*;
@@ -143,21 +143,28 @@ impl fmt::Display for TcpCheckSource<'_> {
     }
 }
 
+/// Connection timeout of a TCP check, a silently dropped SYN would otherwise hang until the OS timeout.
+pub const TCP_CHECK_DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub fn check_tcp_port_is_open(address: &TcpCheckSource, port: u16) -> Result<(), TcpCheckErrors> {
-    let timeout = core::time::Duration::from_secs(1);
+    check_tcp_port_is_open_with_timeout(address, port, TCP_CHECK_DEFAULT_TIMEOUT)
+}
 
+pub fn check_tcp_port_is_open_with_timeout(
+    address: &TcpCheckSource,
+    port: u16,
+    timeout: Duration,
+) -> Result<(), TcpCheckErrors> {
     let ip = match address {
         TcpCheckSource::SocketAddr(x) => *x,
-        TcpCheckSource::DnsName(x) => {
-            let address = format!("{}:{}", x, port);
-            match address.to_socket_addrs() {
-                Ok(x) => {
-                    let ips: Vec<SocketAddr> = x.collect();
-                    ips[0]
-                }
-                Err(_) => return Err(TcpCheckErrors::DomainNotResolvable),
-            }
-        }
+        // IP literals, IPv6 ones included, are not resolved but used as is
+        TcpCheckSource::DnsName(x) => match x.parse::<IpAddr>() {
+            Ok(ip) => SocketAddr::new(ip, port),
+            Err(_) => match (*x, port).to_socket_addrs().map(|mut ips| ips.next()) {
+                Ok(Some(ip)) => ip,
+                Ok(None) | Err(_) => return Err(TcpCheckErrors::DomainNotResolvable),
+            },
+        },
     };
 
     match NetTcpStream::connect_timeout(&ip, timeout) {
@@ -212,7 +219,8 @@ pub fn print_action(
 #[cfg(test)]
 mod tests {
     use crate::cloud_provider::utilities::{
-        await_domain_resolve_cname, check_tcp_port_is_open, TcpCheckErrors, TcpCheckSource,
+        await_domain_resolve_cname, check_tcp_port_is_open, check_tcp_port_is_open_with_timeout, TcpCheckErrors,
+        TcpCheckSource,
     };
     use crate::cmd::command::CommandKiller;
     use crate::errors::CommandError;
@@ -238,6 +246,47 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_local_port_open() {
+        // setup:
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("cannot bind local port");
+        let listening_port = listener.local_addr().expect("cannot get local address").port();
+        // bound then dropped, nothing listens on it anymore
+        let closed_port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .expect("cannot bind local port")
+            .port();
+        let timeout = Duration::from_secs(2);
+        // the IP literal is used as is, `localhost` may resolve to `::1` first where nothing listens
+        let address = TcpCheckSource::DnsName("127.0.0.1");
+
+        // execute & verify:
+        assert!(check_tcp_port_is_open_with_timeout(&address, listening_port, timeout).is_ok());
+
+        let start = std::time::Instant::now();
+        assert_eq!(
+            Err(TcpCheckErrors::PortNotOpen),
+            check_tcp_port_is_open_with_timeout(&address, closed_port, timeout)
+        );
+        assert!(start.elapsed() < timeout);
+    }
+
+    #[test]
+    pub fn test_local_port_open_ipv6_literal() {
+        // setup:
+        let listener = match std::net::TcpListener::bind("[::1]:0") {
+            Ok(listener) => listener,
+            // no IPv6 loopback available on this host
+            Err(_) => return,
+        };
+        let port = listener.local_addr().expect("cannot get local address").port();
+
+        // execute & verify:
+        assert!(
+            check_tcp_port_is_open_with_timeout(&TcpCheckSource::DnsName("::1"), port, Duration::from_secs(2)).is_ok()
+        );
+    }
+
     #[test]
     pub fn test_cname_resolution() {
         let cname = await_domain_resolve_cname(
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use tracing::{error, info};
 
-use crate::cloud_provider::helm::ChartInfo;
+use crate::cloud_provider::helm::{ChartInfo, ChartRepository};
 use crate::cmd::command::{CommandError, CommandKiller, ExecutableCommand, QoveryCommand};
 use crate::cmd::helm::HelmCommand::{LIST, ROLLBACK, STATUS, UNINSTALL, UPGRADE};
 use crate::cmd::helm::HelmError::{CannotRollback, CmdError, InvalidKubeConfig, ReleaseDoesNotExist};
@@ -13,11 +13,17 @@ use crate::errors::EngineError;
 use crate::events::EventDetails;
 use semver::Version;
 use serde_derive::Deserialize;
+use std::collections::HashSet;
+use std::fs;
 use std::fs::File;
+use std::os::unix::fs::PermissionsExt;
 use std::str::FromStr;
+use std::sync::Mutex;
 
 const HELM_DEFAULT_TIMEOUT_IN_SECONDS: u32 = 600;
 const HELM_MAX_HISTORY: &str = "50";
+// helm --wait debug output, i.e: `ready.go:277: [debug] Deployment is not ready: ns/app. 0 out of 1 expected pods are ready`
+const NOT_READY_MARKER: &str = " is not ready: ";
 
 pub enum Timeout<T> {
     Default,
@@ -50,20 +56,29 @@ pub enum HelmError {
     #[error("Helm release `{0}` cannot be rollbacked due to be at revision 1")]
     CannotRollback(String),
 
+    /// Resources helm was still waiting for when timing out are kept in the last field, i.e: `Deployment ns/app`
     #[error("Helm timed out for release `{0}` during helm {1:?}: {2}")]
-    Timeout(String, HelmCommand, String),
+    Timeout(String, HelmCommand, String, Vec<String>),
 
     #[error("Command killed by user request: {0}")]
     Killed(String, HelmCommand),
 
     #[error("Helm command `{1:?}` for release {0} terminated with an error: {2:?}")]
     CmdError(String, HelmCommand, errors::CommandError),
+
+    #[error("Helm post-renderer `{1}` for release `{0}` doesn't exist or is not executable")]
+    InvalidPostRenderer(String, String),
+
+    #[error("Helm value `{1}` for release `{0}` is not valid JSON: {2}")]
+    InvalidJsonValue(String, String, String),
 }
 
 #[derive(Debug)]
 pub struct Helm {
     kubernetes_config: PathBuf,
     common_envs: Vec<(String, String)>,
+    // helm repositories already added, to add them only once for all the charts deployed with this helm
+    added_repositories: Mutex<HashSet<(String, String)>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -75,6 +90,7 @@ pub enum HelmCommand {
     LIST,
     DIFF,
     TEMPLATE,
+    REPO,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -122,6 +138,7 @@ impl Helm {
                 .iter()
                 .map(|(k, v)| (k.to_string(), v.to_string()))
                 .collect(),
+            added_repositories: Mutex::new(HashSet::new()),
         })
     }
 
@@ -351,6 +368,46 @@ impl Helm {
         Ok(None)
     }
 
+    /// Returns the arguments referencing the chart to helm: its vendored path, or its remote reference and version.
+    /// A classic helm repository is added, or updated, beforehand the first time it is referenced.
+    fn chart_reference_args(
+        &self,
+        chart: &ChartInfo,
+        envs: &[(&str, &str)],
+        cmd_killer: &CommandKiller,
+    ) -> Result<Vec<String>, HelmError> {
+        let repository = match &chart.repository {
+            Some(repository) => repository,
+            None => return Ok(vec![chart.path.clone()]),
+        };
+
+        if let ChartRepository::Http { name, url, .. } = repository {
+            let repository_key = (name.clone(), url.clone());
+            if !self.added_repositories.lock().unwrap().contains(&repository_key) {
+                helm_exec_with_output(
+                    &["repo", "add", "--force-update", name, url],
+                    &self.get_all_envs(envs),
+                    &mut |line| info!("{}", line),
+                    &mut |line| warn!("chart {}: {}", chart.name, line),
+                    cmd_killer,
+                )
+                .map_err(|err| match err {
+                    CommandError::Killed(_) => HelmError::Killed(chart.name.clone(), HelmCommand::REPO),
+                    err => CmdError(chart.name.clone(), HelmCommand::REPO, err.into()),
+                })?;
+                self.added_repositories.lock().unwrap().insert(repository_key);
+            }
+        }
+
+        let mut args = vec![repository.chart_reference()];
+        if let Some(version) = repository.version() {
+            args.push("--version".to_string());
+            args.push(version.to_string());
+        }
+
+        Ok(args)
+    }
+
     pub fn upgrade_diff(&self, chart: &ChartInfo, envs: &[(&str, &str)]) -> Result<(), HelmError> {
         let mut args_string: Vec<String> = vec![
             "diff".to_string(),
@@ -361,11 +418,13 @@ impl Helm {
             "--namespace".to_string(),
             chart.get_namespace_string(),
         ];
+        args_string.append(&mut post_renderer_args(chart)?);
 
         for value in &chart.values {
             args_string.push("--set".to_string());
             args_string.push(format!("{}={}", value.key, value.value));
         }
+        args_string.append(&mut set_json_args(chart)?);
 
         for value_file in &chart.values_files {
             args_string.push("-f".to_string());
@@ -400,7 +459,7 @@ impl Helm {
 
         // add last elements
         args_string.push(chart.name.clone());
-        args_string.push(chart.path.clone());
+        args_string.append(&mut self.chart_reference_args(chart, envs, &CommandKiller::never())?);
 
         let mut stderr_msg = String::new();
         let helm_ret = helm_exec_with_output(
@@ -431,6 +490,33 @@ impl Helm {
         chart: &ChartInfo,
         envs: &[(&str, &str)],
         cmd_killer: &CommandKiller,
+    ) -> Result<(), HelmError> {
+        self.upgrade_release(chart, envs, cmd_killer, true)
+    }
+
+    /// Upgrades the release only if it is already installed, a missing release is not an error and is left as is.
+    pub fn upgrade_if_exists(
+        &self,
+        chart: &ChartInfo,
+        envs: &[(&str, &str)],
+        cmd_killer: &CommandKiller,
+    ) -> Result<(), HelmError> {
+        match self.check_release_exist(chart, envs) {
+            Ok(_) => self.upgrade_release(chart, envs, cmd_killer, false),
+            Err(ReleaseDoesNotExist(_)) => {
+                info!("Helm release {} is not installed, skipping its upgrade", chart.name);
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn upgrade_release(
+        &self,
+        chart: &ChartInfo,
+        envs: &[(&str, &str)],
+        cmd_killer: &CommandKiller,
+        install: bool,
     ) -> Result<(), HelmError> {
         // Due to crash or error it is possible that the release is under an helm lock
         // Try to un-stuck the situation first if needed
@@ -446,8 +532,12 @@ impl Helm {
             "upgrade".to_string(),
             "--kubeconfig".to_string(),
             self.kubernetes_config.to_str().unwrap_or_default().to_string(),
-            "--create-namespace".to_string(),
-            "--install".to_string(),
+        ];
+        if install {
+            args_string.push("--create-namespace".to_string());
+            args_string.push("--install".to_string());
+        }
+        args_string.extend([
             "--debug".to_string(),
             "--timeout".to_string(),
             timeout_string.as_str().to_string(),
@@ -455,7 +545,7 @@ impl Helm {
             HELM_MAX_HISTORY.to_string(),
             "--namespace".to_string(),
             chart.get_namespace_string(),
-        ];
+        ]);
 
         if debug {
             args_string.push("-o".to_string());
@@ -478,6 +568,7 @@ impl Helm {
         if chart.wait {
             args_string.push("--wait".to_string())
         }
+        args_string.append(&mut post_renderer_args(chart)?);
 
         // overrides and files overrides
         for value in &chart.values {
@@ -488,6 +579,7 @@ impl Helm {
             args_string.push("--set-string".to_string());
             args_string.push(format!("{}={}", value.key, value.value));
         }
+        args_string.append(&mut set_json_args(chart)?);
 
         for value_file in &chart.values_files {
             args_string.push("-f".to_string());
@@ -521,9 +613,10 @@ impl Helm {
 
         // add last elements
         args_string.push(chart.name.clone());
-        args_string.push(chart.path.clone());
+        args_string.append(&mut self.chart_reference_args(chart, envs, cmd_killer)?);
 
         let mut error_message: Vec<String> = vec![];
+        let mut wait_message: Vec<String> = vec![];
 
         let helm_ret = helm_exec_with_output(
             &args_string.iter().map(|x| x.as_str()).collect::<Vec<&str>>(),
@@ -535,6 +628,10 @@ impl Helm {
                 warn!("chart {}: {}", chart.name, line);
                 // we don't want to flood user with debug log
                 if line.contains(" [debug] ") {
+                    // but keep what helm is waiting for, to explain a timeout
+                    if line.contains(NOT_READY_MARKER) {
+                        wait_message.push(line);
+                    }
                     return;
                 }
                 error_message.push(line);
@@ -552,7 +649,12 @@ impl Helm {
             // If the helm command has been canceled by the user, propagate correctly the killed error
             match err {
                 CommandError::TimeoutError(_) => {
-                    return Err(HelmError::Timeout(chart.name.clone(), UPGRADE, stderr_msg));
+                    return Err(HelmError::Timeout(
+                        chart.name.clone(),
+                        UPGRADE,
+                        stderr_msg,
+                        not_ready_resources(&wait_message),
+                    ));
                 }
                 CommandError::Killed(_) => {
                     return Err(HelmError::Killed(chart.name.clone(), UPGRADE));
@@ -564,8 +666,8 @@ impl Helm {
                 HelmError::ReleaseLocked(chart.name.clone())
             } else if stderr_msg.contains("has been rolled back") {
                 HelmError::Rollbacked(chart.name.clone(), UPGRADE)
-            } else if stderr_msg.contains("timed out waiting") {
-                HelmError::Timeout(chart.name.clone(), UPGRADE, stderr_msg)
+            } else if stderr_msg.contains("timed out waiting") || stderr_msg.contains("context deadline exceeded") {
+                HelmError::Timeout(chart.name.clone(), UPGRADE, stderr_msg, not_ready_resources(&wait_message))
             } else {
                 CmdError(
                     chart.name.clone(),
@@ -631,6 +733,7 @@ impl Helm {
             args_string.push("--output-dir".to_string());
             args_string.push(output_dir.to_string());
         }
+        args_string.append(&mut post_renderer_args(chart)?);
 
         for value in &chart.values {
             args_string.push("--set".to_string());
@@ -640,6 +743,7 @@ impl Helm {
             args_string.push("--set-string".to_string());
             args_string.push(format!("{}={}", value.key, value.value));
         }
+        args_string.append(&mut set_json_args(chart)?);
 
         for value_file in &chart.values_files {
             args_string.push("-f".to_string());
@@ -674,7 +778,7 @@ impl Helm {
 
         // add last elements
         args_string.push(chart.name.clone());
-        args_string.push(chart.path.clone());
+        args_string.append(&mut self.chart_reference_args(chart, envs, &CommandKiller::never())?);
 
         let mut stderr_msg = String::new();
         let helm_ret = helm_exec_with_output(
@@ -701,6 +805,48 @@ impl Helm {
     }
 }
 
+fn post_renderer_args(chart: &ChartInfo) -> Result<Vec<String>, HelmError> {
+    let post_renderer = match &chart.post_renderer {
+        Some(post_renderer) => post_renderer,
+        None => return Ok(vec![]),
+    };
+
+    // helm only fails at render time with a cryptic message if the post-renderer is not usable
+    let is_executable = fs::metadata(post_renderer)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false);
+    if !is_executable {
+        return Err(HelmError::InvalidPostRenderer(
+            chart.name.clone(),
+            post_renderer.to_string_lossy().to_string(),
+        ));
+    }
+
+    Ok(vec![
+        "--post-renderer".to_string(),
+        post_renderer.to_string_lossy().to_string(),
+    ])
+}
+
+fn set_json_args(chart: &ChartInfo) -> Result<Vec<String>, HelmError> {
+    let mut args = Vec::with_capacity(chart.values_json.len() * 2);
+    for value in &chart.values_json {
+        // helm error on invalid json doesn't tell which value is wrong
+        if let Err(err) = serde_json::from_str::<serde_json::Value>(&value.json) {
+            return Err(HelmError::InvalidJsonValue(
+                chart.name.clone(),
+                value.key.clone(),
+                err.to_string(),
+            ));
+        }
+
+        args.push("--set-json".to_string());
+        args.push(format!("{}={}", value.key, value.json));
+    }
+
+    Ok(args)
+}
+
 fn helm_exec_with_output<STDOUT, STDERR>(
     args: &[&str],
     envs: &[(&str, &str)],
@@ -722,8 +868,38 @@ where
     }
 }
 
+/// Extracts the resources helm is still waiting for from its --wait debug output, as `<kind> <namespace>/<name>`.
+/// Resources are returned once, in the order helm reported them.
+pub fn not_ready_resources(output: &[String]) -> Vec<String> {
+    let mut resources: Vec<String> = vec![];
+    for line in output {
+        let (before, after) = match line.split_once(NOT_READY_MARKER) {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let kind = before.split_whitespace().last().unwrap_or_default();
+        let name = after
+            .split(". ")
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_end_matches('.');
+        if kind.is_empty() || name.is_empty() {
+            continue;
+        }
+
+        let resource = format!("{} {}", kind, name);
+        if !resources.contains(&resource) {
+            resources.push(resource);
+        }
+    }
+
+    resources
+}
+
 pub fn to_command_error(error: HelmError) -> errors::CommandError {
     errors::CommandError::new("Error while executing Helm command.".to_string(), Some(error.to_string()), None)
+        .with_tool(errors::Tool::Helm)
 }
 
 pub fn to_engine_error(event_details: &EventDetails, error: HelmError) -> EngineError {
@@ -904,7 +1080,7 @@ mod tests {
 
         // install it
         let ret = helm.upgrade(&charts[0], &[], &CommandKiller::never());
-        assert!(matches!(ret, Err(HelmError::Timeout(_, _, _))));
+        assert!(matches!(ret, Err(HelmError::Timeout(_, _, _, _))));
 
         // Release should not exist if it fails
         let ret = helm.check_release_exist(&charts[0], &[]);
@@ -1048,3 +1224,325 @@ mod tests {
         assert_eq!(releases[0].clone().chart_version.unwrap(), Version::new(0, 1, 0))
     }
 }
+
+#[cfg(test)]
+mod tests_set_json {
+    use crate::cloud_provider::helm::{ChartInfo, ChartSetJsonValue};
+    use crate::cmd::helm::{set_json_args, HelmError};
+
+    #[test]
+    fn test_set_json_args() {
+        // setup:
+        let chart = ChartInfo {
+            values_json: vec![ChartSetJsonValue {
+                key: "config.clients".to_string(),
+                json: r#"[{"url":"http://loki.logging.svc:3100/loki/api/v1/push"}]"#.to_string(),
+            }],
+            ..Default::default()
+        };
+
+        // execute:
+        let args = set_json_args(&chart).expect("json value should be valid");
+
+        // verify:
+        assert_eq!(
+            vec![
+                "--set-json".to_string(),
+                r#"config.clients=[{"url":"http://loki.logging.svc:3100/loki/api/v1/push"}]"#.to_string(),
+            ],
+            args
+        );
+        assert!(set_json_args(&ChartInfo::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_json_args_invalid_json() {
+        // setup:
+        let chart = ChartInfo {
+            values_json: vec![ChartSetJsonValue {
+                key: "tolerations".to_string(),
+                json: r#"[{"key": "node-role"]"#.to_string(),
+            }],
+            ..Default::default()
+        };
+
+        // execute:
+        let err = set_json_args(&chart).expect_err("json value should be invalid");
+
+        // verify:
+        assert!(matches!(err, HelmError::InvalidJsonValue(_, key, _) if key == "tolerations"));
+    }
+}
+
+#[cfg(test)]
+mod tests_post_renderer {
+    use crate::cloud_provider::helm::ChartInfo;
+    use crate::cmd::helm::{post_renderer_args, to_engine_error, HelmError};
+    use crate::errors::Tag;
+    use crate::events::{InfrastructureStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_post_renderer_args() {
+        // setup:
+        let post_renderer = tempfile::NamedTempFile::new().expect("cannot create post renderer file");
+        fs::set_permissions(post_renderer.path(), fs::Permissions::from_mode(0o755))
+            .expect("cannot set post renderer permissions");
+        let chart = ChartInfo {
+            post_renderer: Some(post_renderer.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        // execute:
+        let args = post_renderer_args(&chart).expect("post renderer should be valid");
+
+        // verify:
+        assert_eq!(
+            vec![
+                "--post-renderer".to_string(),
+                post_renderer.path().to_string_lossy().to_string()
+            ],
+            args
+        );
+        assert!(post_renderer_args(&ChartInfo::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_post_renderer_invalid_executable() {
+        // setup:
+        let not_executable = tempfile::NamedTempFile::new().expect("cannot create post renderer file");
+        fs::set_permissions(not_executable.path(), fs::Permissions::from_mode(0o644))
+            .expect("cannot set post renderer permissions");
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        for post_renderer in [
+            PathBuf::from("/this/post/renderer/does/not/exist"),
+            not_executable.path().to_path_buf(),
+        ] {
+            let chart = ChartInfo {
+                post_renderer: Some(post_renderer),
+                ..Default::default()
+            };
+
+            // execute:
+            let err = post_renderer_args(&chart).expect_err("post renderer should be invalid");
+
+            // verify:
+            assert!(matches!(err, HelmError::InvalidPostRenderer(_, _)));
+            assert_eq!(&Tag::HelmChartsSetupError, to_engine_error(&event_details, err).tag());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_not_ready_resources {
+    use crate::cmd::helm::{not_ready_resources, HelmCommand, HelmError};
+    use crate::errors::{EngineError, Tag};
+    use crate::events::{InfrastructureStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use uuid::Uuid;
+
+    const TIMEOUT_OUTPUT: &str = r#"upgrade.go:142: [debug] preparing upgrade for app-z1234
+upgrade.go:434: [debug] creating upgraded release for app-z1234
+wait.go:48: [debug] beginning wait for 4 resources with timeout of 5m0s
+ready.go:277: [debug] Deployment is not ready: z1234-zabcd/app-z1234. 0 out of 2 expected pods are ready
+ready.go:277: [debug] Deployment is not ready: z1234-zabcd/app-z1234. 1 out of 2 expected pods are ready
+ready.go:258: [debug] StatefulSet is not ready: z1234-zabcd/worker-z1234. 0 out of 1 expected pods are ready
+ready.go:212: [debug] Pod is not ready: z1234-zabcd/worker-z1234-0
+Error: UPGRADE FAILED: context deadline exceeded"#;
+
+    #[test]
+    fn test_not_ready_resources() {
+        // setup:
+        let output: Vec<String> = TIMEOUT_OUTPUT.lines().map(|line| line.to_string()).collect();
+
+        // execute:
+        let resources = not_ready_resources(&output);
+
+        // verify:
+        assert_eq!(
+            vec![
+                "Deployment z1234-zabcd/app-z1234".to_string(),
+                "StatefulSet z1234-zabcd/worker-z1234".to_string(),
+                "Pod z1234-zabcd/worker-z1234-0".to_string(),
+            ],
+            resources
+        );
+        assert!(not_ready_resources(&["Error: UPGRADE FAILED: context deadline exceeded".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_timeout_error_lists_not_ready_resources() {
+        // setup:
+        let output: Vec<String> = TIMEOUT_OUTPUT.lines().map(|line| line.to_string()).collect();
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::TaskManager(Uuid::new_v4(), "engine".to_string()))
+            .build();
+        let helm_error = HelmError::Timeout(
+            "app-z1234".to_string(),
+            HelmCommand::UPGRADE,
+            "Error: UPGRADE FAILED: context deadline exceeded".to_string(),
+            not_ready_resources(&output),
+        );
+
+        // execute:
+        let err = EngineError::new_helm_error(event_details, helm_error);
+
+        // verify:
+        assert_eq!(&Tag::HelmDeployTimeout, err.tag());
+        for resource in [
+            "Deployment z1234-zabcd/app-z1234",
+            "StatefulSet z1234-zabcd/worker-z1234",
+        ] {
+            assert!(err.user_log_message().contains(resource), "{}", err.user_log_message());
+            assert!(err.hint_message().clone().unwrap_or_default().contains(resource));
+        }
+        assert_eq!(Some("hint.helm_deploy_timeout"), err.hint_key());
+    }
+}
+
+#[cfg(test)]
+mod tests_with_fake_helm {
+    use crate::cloud_provider::helm::{ChartInfo, ChartRepository};
+    use crate::cmd::command::CommandKiller;
+    use crate::cmd::helm::Helm;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// Fake helm binary recording its arguments, `helm status` output depends on whether the release exists.
+    fn fake_helm(release_exists: bool) -> TempDir {
+        let bin_dir = tempfile::tempdir().expect("cannot create fake helm directory");
+        let status = match release_exists {
+            true => r#"echo '{"version": 2, "info": {"status": "deployed"}}'"#,
+            false => "echo 'Error: release: not found' >&2; exit 1",
+        };
+        let script = format!(
+            "#!/bin/sh\necho \"$@\" >> {}/calls\nif [ \"$1\" = \"status\" ]; then {}; fi\nexit 0\n",
+            bin_dir.path().display(),
+            status
+        );
+        let helm = bin_dir.path().join("helm");
+        fs::write(&helm, script).expect("cannot write fake helm");
+        fs::set_permissions(&helm, fs::Permissions::from_mode(0o755)).expect("cannot set fake helm permissions");
+
+        bin_dir
+    }
+
+    fn helm_calls(bin_dir: &TempDir) -> Vec<String> {
+        fs::read_to_string(bin_dir.path().join("calls"))
+            .unwrap_or_default()
+            .lines()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_upgrade_if_exists() {
+        for release_exists in [false, true] {
+            // setup:
+            let bin_dir = fake_helm(release_exists);
+            let kubeconfig = tempfile::NamedTempFile::new().expect("cannot create kubeconfig");
+            let path = format!("{}:{}", bin_dir.path().display(), std::env::var("PATH").unwrap_or_default());
+            let helm = Helm::new(kubeconfig.path(), &[("PATH", path.as_str())]).expect("cannot create helm");
+            let chart = ChartInfo {
+                name: "grafana".to_string(),
+                path: "/charts/grafana".to_string(),
+                ..Default::default()
+            };
+
+            // execute:
+            let result = helm.upgrade_if_exists(&chart, &[], &CommandKiller::never());
+
+            // verify:
+            assert!(result.is_ok(), "release exists: {}, result: {:?}", release_exists, result);
+            let upgrades: Vec<String> = helm_calls(&bin_dir)
+                .into_iter()
+                .filter(|call| call.starts_with("upgrade"))
+                .collect();
+            match release_exists {
+                false => assert!(upgrades.is_empty(), "missing release must not be installed: {:?}", upgrades),
+                true => {
+                    assert_eq!(1, upgrades.len(), "{:?}", upgrades);
+                    assert!(!upgrades[0].contains("--install"), "{}", upgrades[0]);
+                    assert!(upgrades[0].ends_with("grafana /charts/grafana"), "{}", upgrades[0]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_upgrade_remote_chart() {
+        // setup:
+        let test_cases = vec![
+            (
+                ChartRepository::Oci {
+                    url: "oci://registry-1.docker.io/bitnamicharts".to_string(),
+                    chart: "redis".to_string(),
+                    version: Some("17.3.0".to_string()),
+                },
+                None,
+                "redis oci://registry-1.docker.io/bitnamicharts/redis --version 17.3.0",
+            ),
+            (
+                ChartRepository::Http {
+                    name: "bitnami".to_string(),
+                    url: "https://charts.bitnami.com/bitnami".to_string(),
+                    chart: "redis".to_string(),
+                    version: None,
+                },
+                Some("repo add --force-update bitnami https://charts.bitnami.com/bitnami"),
+                "redis bitnami/redis",
+            ),
+        ];
+
+        for (repository, expected_repo_add, expected_chart_args) in test_cases {
+            let bin_dir = fake_helm(true);
+            let kubeconfig = tempfile::NamedTempFile::new().expect("cannot create kubeconfig");
+            let path = format!("{}:{}", bin_dir.path().display(), std::env::var("PATH").unwrap_or_default());
+            let helm = Helm::new(kubeconfig.path(), &[("PATH", path.as_str())]).expect("cannot create helm");
+            let chart = ChartInfo {
+                name: "redis".to_string(),
+                repository: Some(repository),
+                ..Default::default()
+            };
+
+            // execute:
+            let results = [
+                helm.upgrade(&chart, &[], &CommandKiller::never()),
+                helm.upgrade(&chart, &[], &CommandKiller::never()),
+            ];
+
+            // verify: the repository is only added for the first upgrade
+            assert!(results.iter().all(|result| result.is_ok()), "{:?}", results);
+            let calls = helm_calls(&bin_dir);
+            let repo_adds: Vec<String> = calls
+                .iter()
+                .filter(|call| call.starts_with("repo add"))
+                .cloned()
+                .collect();
+            assert_eq!(
+                expected_repo_add
+                    .into_iter()
+                    .map(|repo_add| repo_add.to_string())
+                    .collect::<Vec<_>>(),
+                repo_adds
+            );
+            let upgrades: Vec<&String> = calls.iter().filter(|call| call.starts_with("upgrade")).collect();
+            assert_eq!(2, upgrades.len(), "{:?}", upgrades);
+            assert!(
+                upgrades.iter().all(|upgrade| upgrade.ends_with(expected_chart_args)),
+                "{:?}",
+                upgrades
+            );
+        }
+    }
+}
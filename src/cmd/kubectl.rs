@@ -2,9 +2,8 @@ use std::fmt::Debug;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::time::Duration;
 
-use retry::delay::Fibonacci;
-use retry::OperationResult;
 use serde::de::DeserializeOwned;
 
 use crate::cloud_provider::digitalocean::models::svc::DoLoadBalancer;
@@ -17,7 +16,8 @@ use crate::cmd::structs::{
     KubernetesVersion, MetricsServer, Namespace, Secrets, HPA, PDB, PVC, SVC,
 };
 use crate::constants::KUBECONFIG;
-use crate::errors::{CommandError, ErrorMessageVerbosity};
+use crate::errors::{CommandError, ErrorMessageVerbosity, Tool};
+use crate::poller::{poll_until, ExponentialBackoff, PollError, PollState};
 
 pub enum ScalingKind {
     Deployment,
@@ -54,7 +54,9 @@ where
             envs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
             None,
             None,
-        ));
+            err.exit_status(),
+        )
+        .with_tool(Tool::Kubectl));
     };
 
     Ok(())
@@ -124,6 +126,7 @@ where
             envs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
             Some(output_string.to_string()),
             Some(err_output_string),
+            None,
         )),
     }
 }
@@ -224,32 +227,23 @@ pub fn kubectl_exec_is_pod_ready_with_retry<P>(
 where
     P: AsRef<Path>,
 {
-    let result = retry::retry(Fibonacci::from_millis(3000).take(10), || {
-        let r = kubectl_exec_is_pod_ready(kubernetes_config.as_ref(), namespace, selector, envs.clone());
-
-        match r {
-            Ok(is_ready) => match is_ready {
-                Some(true) => OperationResult::Ok(true),
-                _ => {
-                    let t = format!("pod with selector: {} is not ready yet", selector);
-                    info!("{}", t.as_str());
-                    OperationResult::Retry(t)
-                }
-            },
-            Err(err) => OperationResult::Err(format!("command error: {:?}", err)),
-        }
-    });
+    let result = poll_until(
+        || match kubectl_exec_is_pod_ready(kubernetes_config.as_ref(), namespace, selector, envs.clone()) {
+            Ok(Some(true)) => Ok(PollState::Ready(())),
+            Ok(_) => {
+                info!("pod with selector: {} is not ready yet", selector);
+                Ok(PollState::Pending)
+            }
+            Err(err) => Err(err),
+        },
+        ExponentialBackoff::new(Duration::from_secs(3), Duration::from_secs(60)),
+        Duration::from_secs(7 * 60),
+    );
 
     match result {
-        Err(err) => match err {
-            retry::Error::Operation {
-                error: _,
-                total_delay: _,
-                tries: _,
-            } => Ok(Some(false)),
-            retry::Error::Internal(err) => Err(CommandError::new_from_safe_message(err)),
-        },
         Ok(_) => Ok(Some(true)),
+        Err(PollError::Timeout { .. }) => Ok(Some(false)),
+        Err(PollError::Operation(err)) => Err(CommandError::new_from_safe_message(format!("command error: {:?}", err))),
     }
 }
 
@@ -573,6 +567,29 @@ where
     })
 }
 
+pub fn kubectl_exec_rollout_restart_by_selector<P>(
+    kubernetes_config: P,
+    selector: &str,
+    namespace: &str,
+    is_statefulset: bool,
+    envs: &[(&str, &str)],
+) -> Result<(), CommandError>
+where
+    P: AsRef<Path>,
+{
+    let mut environment_variables: Vec<(&str, &str)> = envs.to_owned();
+    environment_variables.push(("KUBECONFIG", kubernetes_config.as_ref().to_str().unwrap()));
+    let kind = match is_statefulset {
+        true => "statefulset",
+        false => "deployment",
+    };
+    let args = vec!["-n", namespace, "rollout", "restart", kind, "-l", selector];
+
+    kubectl_exec_with_output(args, environment_variables, &mut |line| info!("{}", line), &mut |line| {
+        error!("{}", line)
+    })
+}
+
 pub fn kubectl_exec_get_node<P>(
     kubernetes_config: P,
     envs: Vec<(&str, &str)>,
@@ -1099,6 +1116,25 @@ where
     Ok(crash_looping_pods)
 }
 
+/// kubectl_exec_get_image_pull_error_pods: returns the pods having a container which cannot pull its image.
+pub fn kubectl_exec_get_image_pull_error_pods<P>(
+    kubernetes_config: P,
+    namespace: Option<&str>,
+    selector: Option<&str>,
+    envs: Vec<(&str, &str)>,
+) -> Result<Vec<KubernetesPod>, CommandError>
+where
+    P: AsRef<Path>,
+{
+    let pods = kubectl_exec_get_pods(kubernetes_config, namespace, selector, envs)?;
+
+    Ok(pods
+        .items
+        .into_iter()
+        .filter(|pod| pod.image_pull_error().is_some())
+        .collect())
+}
+
 /// kubectl_exec_delete_pod: allow to delete a k8s pod if exists.
 ///
 /// Arguments
@@ -1432,3 +1468,70 @@ where
     ];
     kubectl_exec_raw_output(cmd_args, kubernetes_config, envs, false)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::kubectl::kubectl_exec_rollout_restart_by_selector;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// Fake kubectl binary recording its arguments and exiting with `exit_code`.
+    fn fake_kubectl(exit_code: i32) -> TempDir {
+        let bin_dir = tempfile::tempdir().expect("cannot create fake kubectl directory");
+        let script = format!(
+            "#!/bin/sh\necho \"$@\" >> {}/calls\nexit {}\n",
+            bin_dir.path().display(),
+            exit_code
+        );
+        let kubectl = bin_dir.path().join("kubectl");
+        fs::write(&kubectl, script).expect("cannot write fake kubectl");
+        fs::set_permissions(&kubectl, fs::Permissions::from_mode(0o755)).expect("cannot set fake kubectl permissions");
+
+        bin_dir
+    }
+
+    #[test]
+    fn test_kubectl_exec_rollout_restart_by_selector() {
+        for (is_statefulset, kind) in [(true, "statefulset"), (false, "deployment")] {
+            // setup:
+            let bin_dir = fake_kubectl(0);
+            let path = format!("{}:{}", bin_dir.path().display(), std::env::var("PATH").unwrap_or_default());
+
+            // execute:
+            let ret = kubectl_exec_rollout_restart_by_selector(
+                "/tmp/kubeconfig",
+                "appId=z1234",
+                "z1234-env",
+                is_statefulset,
+                &[("PATH", path.as_str())],
+            );
+
+            // verify:
+            assert!(ret.is_ok());
+            assert_eq!(
+                format!("-n z1234-env rollout restart {} -l appId=z1234\n", kind),
+                fs::read_to_string(bin_dir.path().join("calls")).unwrap_or_default()
+            );
+        }
+    }
+
+    #[test]
+    fn test_kubectl_exec_rollout_restart_by_selector_failure() {
+        // setup:
+        let bin_dir = fake_kubectl(1);
+        let path = format!("{}:{}", bin_dir.path().display(), std::env::var("PATH").unwrap_or_default());
+
+        // execute:
+        let ret = kubectl_exec_rollout_restart_by_selector(
+            "/tmp/kubeconfig",
+            "appId=z1234",
+            "z1234-env",
+            false,
+            &[("PATH", path.as_str())],
+        );
+
+        // verify:
+        assert!(ret.is_err());
+    }
+}
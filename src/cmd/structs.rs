@@ -180,6 +180,23 @@ pub struct KubernetesPod {
     pub metadata: KubernetesPodMetadata,
 }
 
+impl KubernetesPod {
+    /// Returns the waiting state of the first container of the pod which cannot pull its image, if any.
+    pub fn image_pull_error(&self) -> Option<&ContainerStatusWaiting> {
+        self.status
+            .container_statuses
+            .iter()
+            .flatten()
+            .filter_map(|container_status| container_status.state.waiting.as_ref())
+            .find(|waiting| {
+                matches!(
+                    waiting.reason,
+                    KubernetesPodStatusReason::ImagePullBackOff | KubernetesPodStatusReason::ErrImagePull
+                )
+            })
+    }
+}
+
 #[derive(Deserialize, Clone, Eq, PartialEq, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct KubernetesPodMetadata {
@@ -210,6 +227,8 @@ pub enum KubernetesPodStatusReason {
     Preempting,
     CrashLoopBackOff,
     ExceededGracePeriod,
+    ImagePullBackOff,
+    ErrImagePull,
 }
 
 impl Default for KubernetesPodStatusReason {
@@ -228,6 +247,8 @@ impl From<String> for KubernetesPodStatusReason {
             "preempting" => KubernetesPodStatusReason::Preempting,
             "crashloopbackoff" => KubernetesPodStatusReason::CrashLoopBackOff,
             "exceededgraceperiod" => KubernetesPodStatusReason::ExceededGracePeriod,
+            "imagepullbackoff" => KubernetesPodStatusReason::ImagePullBackOff,
+            "errimagepull" => KubernetesPodStatusReason::ErrImagePull,
             _ => Unknown(match s.as_str() {
                 "" => None,
                 _ => Some(s),
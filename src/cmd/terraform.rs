@@ -8,6 +8,7 @@ use crate::constants::TF_PLUGIN_CACHE_DIR;
 use rand::Rng;
 use regex::Regex;
 use retry::Error::Operation;
+use serde::Deserialize;
 use std::fmt::{Display, Formatter};
 use std::{env, fs, thread, time};
 
@@ -37,6 +38,214 @@ pub enum QuotaExceededError {
     ScwNewAccountNeedsValidation,
 }
 
+/// Lock info printed by Terraform when the state is already locked, i.e: by another run.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StateLockInfo {
+    pub id: String,
+    pub who: Option<String>,
+    pub created: Option<String>,
+}
+
+impl StateLockInfo {
+    /// Parses the `Lock Info:` block of a Terraform error output, `None` if there is no lock ID.
+    pub fn from_terraform_output(raw_terraform_output: &str) -> Option<StateLockInfo> {
+        let lock_info = &raw_terraform_output[raw_terraform_output.find("Lock Info:")?..];
+        let field = |name: &str| {
+            Regex::new(&format!(r"(?m)^\s*{}:[ \t]*(?P<value>\S.*?)\s*$", name))
+                .ok()?
+                .captures(lock_info)?
+                .name("value")
+                .map(|value| value.as_str().to_string())
+        };
+
+        Some(StateLockInfo {
+            id: field("ID")?,
+            who: field("Who"),
+            created: field("Created"),
+        })
+    }
+}
+
+/// Diagnostic emitted by Terraform when run with `-json`, one JSON object per line:
+/// {"@level":"error","@message":"Error: ...","type":"diagnostic","diagnostic":{"severity":"error","summary":"...","detail":"...","range":{"filename":"main.tf","start":{"line":12}}}}
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TerraformDiagnostic {
+    pub severity: String,
+    pub summary: String,
+    pub detail: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct JsonDiagnosticLine {
+    #[serde(rename = "@level")]
+    level: String,
+    #[serde(rename = "type")]
+    kind: String,
+    diagnostic: Option<JsonDiagnostic>,
+}
+
+#[derive(Deserialize)]
+struct JsonDiagnostic {
+    severity: String,
+    summary: String,
+    #[serde(default)]
+    detail: String,
+    range: Option<JsonDiagnosticRange>,
+}
+
+#[derive(Deserialize)]
+struct JsonDiagnosticRange {
+    filename: String,
+    start: JsonDiagnosticPosition,
+}
+
+#[derive(Deserialize)]
+struct JsonDiagnosticPosition {
+    line: u32,
+}
+
+impl TerraformDiagnostic {
+    /// Parses a single `-json` output line, only error diagnostics are kept.
+    pub fn from_json_line(line: &str) -> Option<TerraformDiagnostic> {
+        let json_line: JsonDiagnosticLine = serde_json::from_str(line.trim()).ok()?;
+        if json_line.level != "error" || json_line.kind != "diagnostic" {
+            return None;
+        }
+
+        let diagnostic = json_line.diagnostic?;
+        Some(TerraformDiagnostic {
+            severity: diagnostic.severity,
+            summary: diagnostic.summary,
+            detail: diagnostic.detail,
+            file: diagnostic.range.as_ref().map(|r| r.filename.to_string()),
+            line: diagnostic.range.map(|r| r.start.line),
+        })
+    }
+
+    /// Parses all error diagnostics from a Terraform `-json` output, non JSON lines are ignored.
+    pub fn parse_all(output: &str) -> Vec<TerraformDiagnostic> {
+        output.lines().filter_map(TerraformDiagnostic::from_json_line).collect()
+    }
+}
+
+impl Display for TerraformDiagnostic {
+    /// Renders the diagnostic the same way Terraform does in its human readable output
+    /// so the raw error parsing applies to it as well.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error: {}", self.summary)?;
+        if let (Some(file), Some(line)) = (&self.file, self.line) {
+            write!(f, "\n\n  on {} line {}", file, line)?;
+        }
+        if !self.detail.is_empty() {
+            write!(f, "\n\n{}", self.detail)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Changes planned by Terraform, parsed from `terraform plan -json` output lines:
+/// {"@level":"info","type":"planned_change","change":{"resource":{"addr":"aws_vpc.eks"},"action":"delete"}}
+/// {"@level":"info","type":"change_summary","changes":{"add":1,"change":0,"remove":1,"operation":"plan"}}
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TerraformPlanSummary {
+    pub to_add: u32,
+    pub to_change: u32,
+    pub to_destroy: u32,
+    /// destroyed_resources: addresses of the resources to be destroyed, replaced ones included.
+    pub destroyed_resources: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonPlanLine {
+    #[serde(rename = "type")]
+    kind: String,
+    change: Option<JsonPlannedChange>,
+    changes: Option<JsonChangeSummary>,
+}
+
+#[derive(Deserialize)]
+struct JsonPlannedChange {
+    resource: JsonPlannedResource,
+    action: String,
+}
+
+#[derive(Deserialize)]
+struct JsonPlannedResource {
+    addr: String,
+}
+
+#[derive(Deserialize)]
+struct JsonChangeSummary {
+    add: u32,
+    change: u32,
+    remove: u32,
+}
+
+impl TerraformPlanSummary {
+    /// Parses a Terraform plan `-json` output, non JSON lines are ignored.
+    /// Counts are the ones of Terraform change summary, if any, computed from the planned changes otherwise.
+    pub fn from_json_output(output: &[String]) -> TerraformPlanSummary {
+        let mut summary = TerraformPlanSummary::default();
+        let mut change_summary: Option<JsonChangeSummary> = None;
+
+        for line in output
+            .iter()
+            .filter_map(|line| serde_json::from_str::<JsonPlanLine>(line.trim()).ok())
+        {
+            match (line.kind.as_str(), line.change, line.changes) {
+                ("planned_change", Some(change), _) => match change.action.as_str() {
+                    "create" => summary.to_add += 1,
+                    "update" => summary.to_change += 1,
+                    "delete" => {
+                        summary.to_destroy += 1;
+                        summary.destroyed_resources.push(change.resource.addr);
+                    }
+                    // a replaced resource is destroyed then created again
+                    "replace" => {
+                        summary.to_add += 1;
+                        summary.to_destroy += 1;
+                        summary.destroyed_resources.push(change.resource.addr);
+                    }
+                    _ => {}
+                },
+                ("change_summary", _, Some(changes)) => change_summary = Some(changes),
+                _ => {}
+            }
+        }
+
+        if let Some(changes) = change_summary {
+            summary.to_add = changes.add;
+            summary.to_change = changes.change;
+            summary.to_destroy = changes.remove;
+        }
+
+        summary
+    }
+
+    /// A destructive plan deletes existing resources, it may require a confirmation before being applied.
+    pub fn is_destructive(&self) -> bool {
+        self.to_destroy > 0
+    }
+}
+
+impl Display for TerraformPlanSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} to add, {} to change, {} to destroy",
+            self.to_add, self.to_change, self.to_destroy
+        )?;
+        if !self.destroyed_resources.is_empty() {
+            write!(f, " ({})", self.destroyed_resources.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum TerraformError {
     Unknown {
@@ -136,12 +345,26 @@ pub enum TerraformError {
         /// raw_message: raw Terraform error message with all details.
         raw_message: String,
     },
+    SubnetAddressPoolExhausted {
+        subnet_id: String,
+        /// Providers don't always give back free and requested IPs counts ... so might be None
+        available: Option<u32>,
+        requested: Option<u32>,
+        /// raw_message: raw Terraform error message with all details.
+        raw_message: String,
+    },
     MultipleInterruptsReceived {
         /// raw_message: raw Terraform error message with all details.
         raw_message: String,
     },
     StateLocked {
-        lock_id: String,
+        lock_info: StateLockInfo,
+        /// raw_message: raw Terraform error message with all details.
+        raw_message: String,
+    },
+    /// Structured error diagnostic which doesn't match any known issue.
+    Diagnostic {
+        diagnostic: TerraformDiagnostic,
         /// raw_message: raw Terraform error message with all details.
         raw_message: String,
     },
@@ -149,6 +372,47 @@ pub enum TerraformError {
 
 impl TerraformError {
     fn new(terraform_args: Vec<String>, raw_terraform_std_output: String, raw_terraform_error_output: String) -> Self {
+        // With `-json`, diagnostics are emitted on stdout
+        let diagnostics: Vec<TerraformDiagnostic> = TerraformDiagnostic::parse_all(&raw_terraform_std_output)
+            .into_iter()
+            .chain(TerraformDiagnostic::parse_all(&raw_terraform_error_output))
+            .collect();
+
+        if diagnostics.is_empty() {
+            return TerraformError::from_raw_output(
+                terraform_args,
+                raw_terraform_std_output,
+                raw_terraform_error_output,
+            );
+        }
+
+        // The most relevant diagnostic is the first one matching a known issue, otherwise the first one emitted
+        for diagnostic in &diagnostics {
+            match TerraformError::from_raw_output(
+                terraform_args.clone(),
+                raw_terraform_std_output.to_string(),
+                diagnostic.to_string(),
+            ) {
+                TerraformError::Unknown { .. } => continue,
+                terraform_error => return terraform_error,
+            }
+        }
+
+        TerraformError::Diagnostic {
+            raw_message: diagnostics
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>()
+                .join("\n\n"),
+            diagnostic: diagnostics[0].clone(),
+        }
+    }
+
+    fn from_raw_output(
+        terraform_args: Vec<String>,
+        raw_terraform_std_output: String,
+        raw_terraform_error_output: String,
+    ) -> Self {
         // TODO(benjaminch): this logic might probably not live here on the long run.
         // There is some cloud providers specific errors and it would make more sense to delegate logic
         // identifying those errors (trait implementation) on cloud provider side next to their kubernetes implementation.
@@ -306,10 +570,29 @@ impl TerraformError {
 
         // Dependencies issues
         // AWS
-        if let Ok(aws_state_expected_re) = Regex::new(
+        if let Ok(aws_dependency_violation_re) = Regex::new(
             r"Error deleting (?P<resource_kind>\w+): DependencyViolation: .+ '(?P<resource_name>.+?)' has dependencies and cannot be deleted",
         ) {
-            if let Some(cap) = aws_state_expected_re.captures(raw_terraform_error_output.as_str()) {
+            if let Some(cap) = aws_dependency_violation_re.captures(raw_terraform_error_output.as_str()) {
+                if let (Some(resource_kind), Some(resource_name)) = (
+                    cap.name("resource_kind").map(|e| e.as_str()),
+                    cap.name("resource_name").map(|e| e.as_str()),
+                ) {
+                    return TerraformError::ResourceDependencyViolation {
+                        resource_name: resource_name.to_string(),
+                        resource_kind: resource_kind.to_string(),
+                        raw_message: raw_terraform_error_output.to_string(),
+                    };
+                }
+            }
+        }
+        // Error: deleting EC2 Subnet (subnet-0a1b2c3d): DependencyViolation: The subnet 'subnet-0a1b2c3d' has dependencies and cannot be deleted.
+        if let Ok(aws_dependency_violation_with_resource_id_re) = Regex::new(
+            r"deleting (?:EC2 )?(?P<resource_kind>[\w\s]+?) \((?P<resource_name>.+?)\): DependencyViolation: .+ has dependencies and cannot be deleted",
+        ) {
+            if let Some(cap) =
+                aws_dependency_violation_with_resource_id_re.captures(raw_terraform_error_output.as_str())
+            {
                 if let (Some(resource_kind), Some(resource_name)) = (
                     cap.name("resource_kind").map(|e| e.as_str()),
                     cap.name("resource_name").map(|e| e.as_str()),
@@ -421,6 +704,32 @@ impl TerraformError {
                 }
             }
         }
+        // InsufficientFreeAddressesInSubnet: There are not enough free addresses in subnet 'subnet-0a1b2c3d' to satisfy the requested number of instances.
+        // InvalidSubnet: Not enough IP space available in subnet-0a1b2c3d. ELB requires at least 8 free IP addresses in each subnet.
+        if let (Ok(aws_subnet_re), Ok(aws_available_ips_re), Ok(aws_requested_ips_re)) = (
+            Regex::new(
+                r"(?:InsufficientFreeAddressesInSubnet|Not enough IP space available|free IP addresses).*?(?P<subnet_id>subnet-[0-9a-f]+)|(?P<subnet_id_first>subnet-[0-9a-f]+).*?free IP addresses",
+            ),
+            Regex::new(r"has (?:only )?(?P<available>\d+) (?:free|available) IP addresses"),
+            Regex::new(r"at least (?P<requested>\d+) (?:free )?IP addresses"),
+        ) {
+            for line in raw_terraform_error_output.lines() {
+                if let Some(cap) = aws_subnet_re.captures(line) {
+                    if let Some(subnet_id) = cap.name("subnet_id").or_else(|| cap.name("subnet_id_first")) {
+                        let count = |re: &Regex, name: &str| {
+                            re.captures(line)
+                                .and_then(|cap| cap.name(name).and_then(|e| e.as_str().parse::<u32>().ok()))
+                        };
+                        return TerraformError::SubnetAddressPoolExhausted {
+                            subnet_id: subnet_id.as_str().to_string(),
+                            available: count(&aws_available_ips_re, "available"),
+                            requested: count(&aws_requested_ips_re, "requested"),
+                            raw_message: raw_terraform_error_output.to_string(),
+                        };
+                    }
+                }
+            }
+        }
 
         // SCW
         if raw_terraform_error_output.contains("scaleway-sdk-go: waiting for")
@@ -466,20 +775,12 @@ impl TerraformError {
             };
         }
 
-        if raw_terraform_error_output.contains("Error acquiring the state lock")
-            && raw_terraform_error_output.contains("Lock Info:")
-        {
-            if let Ok(tf_state_lock) = Regex::new(
-                r"ID:\s+(?P<lock_id>\b(?:[0-9a-fA-F]{8}\-[0-9a-fA-F]{4}\-[0-9a-fA-F]{4}\-[0-9a-fA-F]{4}\-[0-9a-fA-F]{12}))",
-            ) {
-                if let Some(cap) = tf_state_lock.captures(raw_terraform_error_output.as_str()) {
-                    if let Some(lock_id) = cap.name("lock_id").map(|e| e.as_str()) {
-                        return TerraformError::StateLocked {
-                            lock_id: lock_id.to_string(),
-                            raw_message: raw_terraform_error_output,
-                        };
-                    }
-                }
+        if raw_terraform_error_output.contains("Error acquiring the state lock") {
+            if let Some(lock_info) = StateLockInfo::from_terraform_output(&raw_terraform_error_output) {
+                return TerraformError::StateLocked {
+                    lock_info,
+                    raw_message: raw_terraform_error_output,
+                };
             }
         }
 
@@ -590,9 +891,25 @@ impl TerraformError {
             TerraformError::InvalidCIDRBlock {cidr,..} => {
                 format!("Error, the CIDR block `{}` can't be used.", cidr)
             }
-            TerraformError::StateLocked { lock_id, .. } => {
-                format!("Error, terraform state is locked (lock_id: {})", lock_id)
+            TerraformError::SubnetAddressPoolExhausted { subnet_id, available, requested, .. } => {
+                format!("Error, subnet `{}` doesn't have enough free IP addresses{}.", subnet_id, match (available, requested) {
+                    (Some(available), Some(requested)) => format!(" ({} available, {} requested)", available, requested),
+                    (None, Some(requested)) => format!(" ({} requested)", requested),
+                    (Some(available), None) => format!(" ({} available)", available),
+                    (None, None) => "".to_string(),
+                })
             }
+            TerraformError::StateLocked { lock_info, .. } => match &lock_info.created {
+                Some(created) => format!(
+                    "Error, terraform state is locked (lock_id: {}, created: {})",
+                    lock_info.id, created
+                ),
+                None => format!("Error, terraform state is locked (lock_id: {})", lock_info.id),
+            },
+            TerraformError::Diagnostic { diagnostic, .. } => match (&diagnostic.file, diagnostic.line) {
+                (Some(file), Some(line)) => format!("Error, {} (`{}` line {}).", diagnostic.summary, file, line),
+                _ => format!("Error, {}.", diagnostic.summary),
+            },
         }
     }
 }
@@ -657,9 +974,15 @@ impl Display for TerraformError {
             TerraformError::InvalidCIDRBlock { raw_message, .. } => {
                 format!("{}\n{}", self.to_safe_message(), raw_message)
             }
+            TerraformError::SubnetAddressPoolExhausted { raw_message, .. } => {
+                format!("{}\n{}", self.to_safe_message(), raw_message)
+            }
             TerraformError::StateLocked { raw_message, .. } => {
                 format!("{}\n{}", self.to_safe_message(), raw_message)
             }
+            TerraformError::Diagnostic { raw_message, .. } => {
+                format!("{}\n{}", self.to_safe_message(), raw_message)
+            }
         };
 
         f.write_str(&message)
@@ -810,12 +1133,43 @@ pub fn terraform_state_list(root_dir: &str) -> Result<Vec<String>, TerraformErro
     }
 }
 
-pub fn terraform_plan(root_dir: &str) -> Result<Vec<String>, TerraformError> {
+/// Returns terraform args with `-parallelism=N` set right after the command when a parallelism is configured.
+/// When unset, Terraform default parallelism is used.
+fn terraform_args_with_parallelism(
+    terraform_args: &[&str],
+    terraform_parallelism: Option<u32>,
+) -> Result<Vec<String>, TerraformError> {
+    let mut args: Vec<String> = terraform_args.iter().map(|e| e.to_string()).collect();
+
+    if let Some(parallelism) = terraform_parallelism {
+        if parallelism < 1 {
+            return Err(TerraformError::ContextUnsupportedParameterValue {
+                service_type: "Terraform".to_string(),
+                parameter_name: "parallelism".to_string(),
+                parameter_value: parallelism.to_string(),
+                raw_message: "Terraform parallelism should be greater or equal to 1".to_string(),
+            });
+        }
+        args.insert(1.min(args.len()), format!("-parallelism={}", parallelism));
+    }
+
+    Ok(args)
+}
+
+pub fn terraform_plan(
+    root_dir: &str,
+    terraform_parallelism: Option<u32>,
+) -> Result<TerraformPlanSummary, TerraformError> {
     // plan
-    let terraform_args = vec!["plan", "-no-color", "-out", "tf_plan"];
+    let terraform_args =
+        terraform_args_with_parallelism(&["plan", "-no-color", "-json", "-out", "tf_plan"], terraform_parallelism)?;
     // Retry is not needed, fixing it to 1 only for the time being
     let result = retry::retry(Fixed::from_millis(3000).take(1), || {
-        match terraform_exec(root_dir, terraform_args.clone()) {
+        match terraform_exec_with_stdout_format(
+            root_dir,
+            terraform_args.iter().map(|e| e.as_str()).collect(),
+            &json_line_message,
+        ) {
             Ok(out) => OperationResult::Ok(out),
             Err(err) => {
                 let _ = manage_common_issues(root_dir, "", &err);
@@ -826,7 +1180,13 @@ pub fn terraform_plan(root_dir: &str) -> Result<Vec<String>, TerraformError> {
     });
 
     match result {
-        Ok(output) => Ok(output),
+        Ok(output) => {
+            // JSON output only tells which resources change, showing the plan logs what changes on them
+            if let Err(err) = terraform_exec(root_dir, vec!["show", "-no-color", "tf_plan"]) {
+                warn!("Cannot show Terraform plan: {}", err);
+            }
+            Ok(TerraformPlanSummary::from_json_output(&output))
+        }
         Err(Operation { error, .. }) => Err(error),
         Err(retry::Error::Internal(e)) => Err(TerraformError::new(
             terraform_args.iter().map(|e| e.to_string()).collect(),
@@ -836,16 +1196,27 @@ pub fn terraform_plan(root_dir: &str) -> Result<Vec<String>, TerraformError> {
     }
 }
 
-fn terraform_apply(root_dir: &str) -> Result<Vec<String>, TerraformError> {
-    let terraform_args = vec!["apply", "-no-color", "-auto-approve", "tf_plan"];
+/// Logs the changes planned before running `operation`, destructive plans being logged as warnings.
+fn log_plan_summary(operation: &str, summary: &TerraformPlanSummary) {
+    if summary.is_destructive() {
+        warn!("Terraform plan before {} destroys resources: {}", operation, summary);
+    } else {
+        info!("Terraform plan before {}: {}", operation, summary);
+    }
+}
+
+fn terraform_apply(root_dir: &str, terraform_parallelism: Option<u32>) -> Result<Vec<String>, TerraformError> {
+    let terraform_args =
+        terraform_args_with_parallelism(&["apply", "-no-color", "-auto-approve", "tf_plan"], terraform_parallelism)?;
     let result = retry::retry(Fixed::from_millis(3000).take(1), || {
         // ensure we do plan before apply otherwise apply could crash.
-        if let Err(e) = terraform_plan(root_dir) {
-            return OperationResult::Retry(e);
+        match terraform_plan(root_dir, terraform_parallelism) {
+            Ok(summary) => log_plan_summary("apply", &summary),
+            Err(e) => return OperationResult::Retry(e),
         };
 
         // terraform apply
-        match terraform_exec(root_dir, terraform_args.clone()) {
+        match terraform_exec(root_dir, terraform_args.iter().map(|e| e.as_str()).collect()) {
             Ok(out) => OperationResult::Ok(out),
             Err(err) => {
                 let _ = manage_common_issues(root_dir, "", &err);
@@ -869,16 +1240,19 @@ fn terraform_apply(root_dir: &str) -> Result<Vec<String>, TerraformError> {
 pub fn terraform_apply_with_tf_workers_resources(
     root_dir: &str,
     tf_workers_resources: Vec<String>,
+    terraform_parallelism: Option<u32>,
 ) -> Result<Vec<String>, TerraformError> {
-    let mut terraform_args_string = vec!["apply".to_string(), "-auto-approve".to_string()];
+    let mut terraform_args_string =
+        terraform_args_with_parallelism(&["apply", "-auto-approve"], terraform_parallelism)?;
     for x in tf_workers_resources {
         terraform_args_string.push(format!("-target={}", x));
     }
 
     let result = retry::retry(Fixed::from_millis(3000).take(1), || {
         // terraform plan first
-        if let Err(err) = terraform_plan(root_dir) {
-            return OperationResult::Retry(err);
+        match terraform_plan(root_dir, terraform_parallelism) {
+            Ok(summary) => log_plan_summary("apply", &summary),
+            Err(err) => return OperationResult::Retry(err),
         }
 
         // terraform apply
@@ -911,17 +1285,19 @@ pub fn terraform_state_rm_entry(root_dir: &str, entry: &str) -> Result<Vec<Strin
     }
 }
 
-pub fn terraform_destroy(root_dir: &str) -> Result<Vec<String>, TerraformError> {
+pub fn terraform_destroy(root_dir: &str, terraform_parallelism: Option<u32>) -> Result<Vec<String>, TerraformError> {
     // terraform destroy
-    let terraform_args = vec!["destroy", "-no-color", "-auto-approve"];
+    let terraform_args =
+        terraform_args_with_parallelism(&["destroy", "-no-color", "-auto-approve"], terraform_parallelism)?;
     let result = retry::retry(Fixed::from_millis(3000).take(1), || {
         // terraform plan first
-        if let Err(err) = terraform_plan(root_dir) {
-            return OperationResult::Retry(err);
+        match terraform_plan(root_dir, terraform_parallelism) {
+            Ok(summary) => log_plan_summary("destroy", &summary),
+            Err(err) => return OperationResult::Retry(err),
         }
 
         // terraform destroy
-        match terraform_exec(root_dir, terraform_args.clone()) {
+        match terraform_exec(root_dir, terraform_args.iter().map(|e| e.as_str()).collect()) {
             Ok(out) => OperationResult::Ok(out),
             Err(err) => {
                 // Error while trying to run terraform destroy on rendered templates, retrying...
@@ -1016,7 +1392,12 @@ pub fn terraform_remove_resource_from_tf_state(root_dir: &str, resource: &str) -
     }
 }
 
-fn terraform_run(actions: TerraformAction, root_dir: &str, dry_run: bool) -> Result<Vec<String>, TerraformError> {
+fn terraform_run(
+    actions: TerraformAction,
+    root_dir: &str,
+    dry_run: bool,
+    terraform_parallelism: Option<u32>,
+) -> Result<Vec<String>, TerraformError> {
     let mut output = vec![];
 
     if actions.contains(TerraformAction::INIT) {
@@ -1032,33 +1413,39 @@ fn terraform_run(actions: TerraformAction, root_dir: &str, dry_run: bool) -> Res
     }
 
     if actions.contains(TerraformAction::APPLY) && !dry_run {
-        output.extend(terraform_apply(root_dir)?);
+        output.extend(terraform_apply(root_dir, terraform_parallelism)?);
     }
 
     if actions.contains(TerraformAction::DESTROY) && !dry_run {
-        output.extend(terraform_destroy(root_dir)?);
+        output.extend(terraform_destroy(root_dir, terraform_parallelism)?);
     }
 
     Ok(output)
 }
 
-pub fn terraform_init_validate_plan_apply(root_dir: &str, dry_run: bool) -> Result<Vec<String>, TerraformError> {
+pub fn terraform_init_validate_plan_apply(
+    root_dir: &str,
+    dry_run: bool,
+    terraform_parallelism: Option<u32>,
+) -> Result<Vec<String>, TerraformError> {
     // Terraform init, validate, plan and apply
     terraform_run(
         TerraformAction::INIT | TerraformAction::VALIDATE | TerraformAction::APPLY,
         root_dir,
         dry_run,
+        terraform_parallelism,
     )
 }
 
 pub fn terraform_init_validate(root_dir: &str) -> Result<Vec<String>, TerraformError> {
     // Terraform init & validate
-    terraform_run(TerraformAction::INIT | TerraformAction::VALIDATE, root_dir, false)
+    terraform_run(TerraformAction::INIT | TerraformAction::VALIDATE, root_dir, false, None)
 }
 
 pub fn terraform_init_validate_destroy(
     root_dir: &str,
     run_apply_before_destroy: bool,
+    terraform_parallelism: Option<u32>,
 ) -> Result<Vec<String>, TerraformError> {
     let mut terraform_actions_to_be_performed = TerraformAction::INIT | TerraformAction::VALIDATE;
 
@@ -1067,7 +1454,12 @@ pub fn terraform_init_validate_destroy(
         terraform_actions_to_be_performed |= TerraformAction::APPLY;
     }
 
-    terraform_run(terraform_actions_to_be_performed | TerraformAction::DESTROY, root_dir, false)
+    terraform_run(
+        terraform_actions_to_be_performed | TerraformAction::DESTROY,
+        root_dir,
+        false,
+        terraform_parallelism,
+    )
 }
 
 pub fn terraform_init_validate_state_list(root_dir: &str) -> Result<Vec<String>, TerraformError> {
@@ -1076,17 +1468,22 @@ pub fn terraform_init_validate_state_list(root_dir: &str) -> Result<Vec<String>,
         TerraformAction::INIT | TerraformAction::VALIDATE | TerraformAction::STATE_LIST,
         root_dir,
         false,
+        None,
     )
 }
 
 /// This method should not be exposed to the outside world, it's internal magic.
-fn terraform_exec_from_command(cmd: &mut impl ExecutableCommand) -> Result<Vec<String>, TerraformError> {
+/// Stdout lines are logged once formatted, returned ones being left as is.
+fn terraform_exec_from_command(
+    cmd: &mut impl ExecutableCommand,
+    format_stdout_line: &dyn Fn(&str) -> String,
+) -> Result<Vec<String>, TerraformError> {
     let mut stdout = Vec::new();
     let mut stderr = Vec::new();
 
     let result = cmd.exec_with_output(
         &mut |line| {
-            info!("{}", line);
+            info!("{}", format_stdout_line(&line));
             stdout.push(line);
         },
         &mut |line| {
@@ -1101,8 +1498,29 @@ fn terraform_exec_from_command(cmd: &mut impl ExecutableCommand) -> Result<Vec<S
     }
 }
 
+/// Human readable message of a Terraform `-json` output line, the line itself if it isn't JSON.
+fn json_line_message(line: &str) -> String {
+    #[derive(Deserialize)]
+    struct JsonMessageLine {
+        #[serde(rename = "@message")]
+        message: String,
+    }
+
+    serde_json::from_str::<JsonMessageLine>(line.trim())
+        .map(|json_line| json_line.message)
+        .unwrap_or_else(|_| line.to_string())
+}
+
 /// This method should not be exposed to the outside world, it's internal magic.
 fn terraform_exec(root_dir: &str, args: Vec<&str>) -> Result<Vec<String>, TerraformError> {
+    terraform_exec_with_stdout_format(root_dir, args, &|line| line.to_string())
+}
+
+fn terraform_exec_with_stdout_format(
+    root_dir: &str,
+    args: Vec<&str>,
+    format_stdout_line: &dyn Fn(&str) -> String,
+) -> Result<Vec<String>, TerraformError> {
     // override if environment variable is set
     let tf_plugin_cache_dir_value = match env::var_os(TF_PLUGIN_CACHE_DIR) {
         Some(val) => format!("{:?}", val)
@@ -1119,15 +1537,16 @@ fn terraform_exec(root_dir: &str, args: Vec<&str>) -> Result<Vec<String>, Terraf
     let mut cmd = QoveryCommand::new("terraform", &args, envs);
     cmd.set_current_dir(root_dir);
 
-    terraform_exec_from_command(&mut cmd)
+    terraform_exec_from_command(&mut cmd, format_stdout_line)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::cmd::command::{CommandError, CommandKiller, ExecutableCommand};
     use crate::cmd::terraform::{
-        manage_common_issues, terraform_exec_from_command, terraform_init, terraform_init_validate, QuotaExceededError,
-        TerraformError,
+        json_line_message, manage_common_issues, terraform_args_with_parallelism, terraform_exec_from_command,
+        terraform_init, terraform_init_validate, QuotaExceededError, StateLockInfo, TerraformDiagnostic,
+        TerraformError, TerraformPlanSummary,
     };
     use std::fs;
     use std::process::Child;
@@ -1240,7 +1659,7 @@ in the dependency lock file
         };
 
         // execute:
-        let result = terraform_exec_from_command(qovery_cmd_mock);
+        let result = terraform_exec_from_command(qovery_cmd_mock, &|line| line.to_string());
 
         // verify:
         assert_eq!(
@@ -1529,6 +1948,40 @@ terraform {
         );
     }
 
+    #[test]
+    fn test_terraform_error_aws_dependency_violation_on_delete_issue() {
+        // setup:
+        let test_cases = vec![
+            (
+                r#"Error: deleting EC2 Subnet (subnet-0a1b2c3d4e5f67890): DependencyViolation: The subnet 'subnet-0a1b2c3d4e5f67890' has dependencies and cannot be deleted.
+            status code: 400, request id: 2be352ce-4b43-4243-ace7-0b9f2ba35734"#,
+                "Subnet",
+                "subnet-0a1b2c3d4e5f67890",
+            ),
+            (
+                r#"Error: deleting EC2 VPC (vpc-0330249c67533e3e7): DependencyViolation: The vpc 'vpc-0330249c67533e3e7' has dependencies and cannot be deleted.
+            status code: 400, request id: 2be352ce-4b43-4243-ace7-0b9f2ba35734"#,
+                "VPC",
+                "vpc-0330249c67533e3e7",
+            ),
+        ];
+
+        for (raw_message, resource_kind, resource_name) in test_cases {
+            // execute:
+            let result = TerraformError::new(vec!["destroy".to_string()], "".to_string(), raw_message.to_string());
+
+            // validate:
+            assert_eq!(
+                TerraformError::ResourceDependencyViolation {
+                    resource_name: resource_name.to_string(),
+                    resource_kind: resource_kind.to_string(),
+                    raw_message: raw_message.to_string(),
+                },
+                result
+            );
+        }
+    }
+
     #[test]
     fn test_terraform_error_aws_invalid_instance_type() {
         // setup:
@@ -1594,6 +2047,48 @@ terraform {
         );
     }
 
+    #[test]
+    fn test_terraform_error_aws_subnet_address_pool_exhausted() {
+        // setup:
+        let test_cases = vec![
+            (
+                "Error: creating EKS Node Group (qovery-z1a2b3c4:ng-1): InvalidRequestException: Subnet subnet-0c8f1e2d3a4b5c6d7 has 2 free IP addresses, at least 5 free IP addresses are required.",
+                "subnet-0c8f1e2d3a4b5c6d7",
+                Some(2),
+                Some(5),
+            ),
+            (
+                "Error: creating ELBv2 application Load Balancer (qovery-lb): InvalidSubnet: Not enough IP space available in subnet-0a1b2c3d4e5f67890. ELB requires at least 8 free IP addresses in each subnet.",
+                "subnet-0a1b2c3d4e5f67890",
+                None,
+                Some(8),
+            ),
+            (
+                "Error: creating EC2 Instance: InsufficientFreeAddressesInSubnet: There are not enough free addresses in subnet 'subnet-0123456789abcdef0' to satisfy the requested number of instances.",
+                "subnet-0123456789abcdef0",
+                None,
+                None,
+            ),
+        ];
+
+        for (raw_terraform_error_str, subnet_id, available, requested) in test_cases {
+            // execute:
+            let result =
+                TerraformError::new(vec!["apply".to_string()], "".to_string(), raw_terraform_error_str.to_string());
+
+            // validate:
+            assert_eq!(
+                TerraformError::SubnetAddressPoolExhausted {
+                    subnet_id: subnet_id.to_string(),
+                    available,
+                    requested,
+                    raw_message: raw_terraform_error_str.to_string(),
+                },
+                result
+            );
+        }
+    }
+
     #[test]
     fn test_terraform_error_multiple_interrupts_received() {
         // setup:
@@ -1656,10 +2151,244 @@ flag, but this is not recommended."#;
         // validate:
         assert_eq!(
             TerraformError::StateLocked {
-                lock_id: "ecd9f287-8d29-4331-1683-48028be7aaba".to_string(),
+                lock_info: StateLockInfo {
+                    id: "ecd9f287-8d29-4331-1683-48028be7aaba".to_string(),
+                    who: Some("likornus@likornus".to_string()),
+                    created: Some("2022-11-14 13:59:21.540636643 +0000 UTC".to_string()),
+                },
                 raw_message: raw_terraform_error_str.to_string(),
             },
             result
         );
     }
+
+    #[test]
+    fn test_state_lock_info_from_terraform_output() {
+        // setup:
+        let test_cases = vec![
+            (
+                "Error: Error acquiring the state lock\nLock Info:\n  ID:        7b6c0d3e-1d1f-4a9e-9b52-3f5b1c0c9a11\n  Operation: OperationTypeApply\n  Info:      \n",
+                Some(StateLockInfo {
+                    id: "7b6c0d3e-1d1f-4a9e-9b52-3f5b1c0c9a11".to_string(),
+                    who: None,
+                    created: None,
+                }),
+            ),
+            (
+                "Error: Error acquiring the state lock\nLock Info:\n  ID:\n  Who:       runner@engine\n",
+                None,
+            ),
+            ("Error: Error acquiring the state lock\n  ID:        7b6c0d3e\n", None),
+        ];
+
+        for (raw_output, expected) in test_cases {
+            // execute:
+            let lock_info = StateLockInfo::from_terraform_output(raw_output);
+
+            // verify:
+            assert_eq!(expected, lock_info, "output: {}", raw_output);
+        }
+    }
+
+    #[test]
+    fn test_terraform_args_with_parallelism() {
+        // setup:
+        struct TestCase<'a> {
+            args: Vec<&'a str>,
+            parallelism: Option<u32>,
+            expected: Result<Vec<&'a str>, ()>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                args: vec!["plan", "-no-color", "-out", "tf_plan"],
+                parallelism: None,
+                expected: Ok(vec!["plan", "-no-color", "-out", "tf_plan"]),
+            },
+            TestCase {
+                args: vec!["plan", "-no-color", "-out", "tf_plan"],
+                parallelism: Some(4),
+                expected: Ok(vec!["plan", "-parallelism=4", "-no-color", "-out", "tf_plan"]),
+            },
+            TestCase {
+                args: vec!["apply", "-no-color", "-auto-approve", "tf_plan"],
+                parallelism: Some(30),
+                expected: Ok(vec!["apply", "-parallelism=30", "-no-color", "-auto-approve", "tf_plan"]),
+            },
+            TestCase {
+                args: vec!["destroy", "-no-color", "-auto-approve"],
+                parallelism: Some(1),
+                expected: Ok(vec!["destroy", "-parallelism=1", "-no-color", "-auto-approve"]),
+            },
+            TestCase {
+                args: vec!["destroy", "-no-color", "-auto-approve"],
+                parallelism: Some(0),
+                expected: Err(()),
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = terraform_args_with_parallelism(&tc.args, tc.parallelism);
+
+            // validate:
+            match tc.expected {
+                Ok(expected) => assert_eq!(Ok(expected.iter().map(|e| e.to_string()).collect::<Vec<String>>()), result),
+                Err(_) => assert!(matches!(result, Err(TerraformError::ContextUnsupportedParameterValue { .. }))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_terraform_diagnostic_from_json_line() {
+        // setup:
+        let test_cases = vec![
+            (
+                r#"{"@level":"error","@message":"Error: Unsupported argument","@module":"terraform.ui","@timestamp":"2022-11-14T13:59:21.540636Z","diagnostic":{"severity":"error","summary":"Unsupported argument","detail":"An argument named \"foo\" is not expected here.","range":{"filename":"main.tf","start":{"line":12,"column":3,"byte":254},"end":{"line":12,"column":6,"byte":257}}},"type":"diagnostic"}"#,
+                Some(TerraformDiagnostic {
+                    severity: "error".to_string(),
+                    summary: "Unsupported argument".to_string(),
+                    detail: "An argument named \"foo\" is not expected here.".to_string(),
+                    file: Some("main.tf".to_string()),
+                    line: Some(12),
+                }),
+            ),
+            (
+                r#"{"@level":"error","@message":"Error: No valid credential sources found","diagnostic":{"severity":"error","summary":"No valid credential sources found"},"type":"diagnostic"}"#,
+                Some(TerraformDiagnostic {
+                    severity: "error".to_string(),
+                    summary: "No valid credential sources found".to_string(),
+                    detail: "".to_string(),
+                    file: None,
+                    line: None,
+                }),
+            ),
+            (
+                r#"{"@level":"warn","@message":"Warning: Deprecated attribute","diagnostic":{"severity":"warning","summary":"Deprecated attribute","detail":""},"type":"diagnostic"}"#,
+                None,
+            ),
+            (
+                r#"{"@level":"info","@message":"aws_vpc.eks: Refreshing state... [id=vpc-1234]","type":"refresh_start"}"#,
+                None,
+            ),
+            ("Error: Unsupported argument", None),
+        ];
+
+        for (line, expected) in test_cases {
+            // execute:
+            let result = TerraformDiagnostic::from_json_line(line);
+
+            // verify:
+            assert_eq!(expected, result, "line: {}", line);
+        }
+    }
+
+    #[test]
+    fn test_terraform_error_from_json_diagnostics() {
+        // setup:
+        let std_output = r#"{"@level":"info","@message":"Terraform 1.3.3","type":"version"}
+{"@level":"error","@message":"Error: Unsupported argument","diagnostic":{"severity":"error","summary":"Unsupported argument","detail":"An argument named \"foo\" is not expected here.","range":{"filename":"main.tf","start":{"line":12,"column":3,"byte":254},"end":{"line":12,"column":6,"byte":257}}},"type":"diagnostic"}
+{"@level":"error","@message":"Error: error deleting EC2 Subnet","diagnostic":{"severity":"error","summary":"Error deleting Subnet: DependencyViolation: The subnet 'subnet-0a1b2c3d' has dependencies and cannot be deleted.","detail":""},"type":"diagnostic"}"#;
+
+        // execute:
+        let result = TerraformError::new(vec!["destroy".to_string()], std_output.to_string(), "".to_string());
+
+        // verify:
+        // most relevant diagnostic is the known one, even if it comes last
+        assert_eq!(
+            TerraformError::ResourceDependencyViolation {
+                resource_kind: "Subnet".to_string(),
+                resource_name: "subnet-0a1b2c3d".to_string(),
+                raw_message: "Error: Error deleting Subnet: DependencyViolation: The subnet 'subnet-0a1b2c3d' has dependencies and cannot be deleted.".to_string(),
+            },
+            result
+        );
+    }
+
+    #[test]
+    fn test_terraform_error_from_unknown_json_diagnostic() {
+        // setup:
+        let std_output = r#"{"@level":"error","@message":"Error: Unsupported argument","diagnostic":{"severity":"error","summary":"Unsupported argument","detail":"An argument named \"foo\" is not expected here.","range":{"filename":"main.tf","start":{"line":12,"column":3,"byte":254},"end":{"line":12,"column":6,"byte":257}}},"type":"diagnostic"}"#;
+
+        // execute:
+        let result = TerraformError::new(vec!["plan".to_string()], std_output.to_string(), "".to_string());
+
+        // verify:
+        assert_eq!(
+            TerraformError::Diagnostic {
+                diagnostic: TerraformDiagnostic {
+                    severity: "error".to_string(),
+                    summary: "Unsupported argument".to_string(),
+                    detail: "An argument named \"foo\" is not expected here.".to_string(),
+                    file: Some("main.tf".to_string()),
+                    line: Some(12),
+                },
+                raw_message: "Error: Unsupported argument\n\n  on main.tf line 12\n\nAn argument named \"foo\" is not expected here.".to_string(),
+            },
+            result
+        );
+        assert_eq!("Error, Unsupported argument (`main.tf` line 12).", result.to_safe_message());
+    }
+
+    #[test]
+    fn test_terraform_plan_summary_from_json_output() {
+        // setup:
+        let output: Vec<String> = r#"{"@level":"info","@message":"Terraform 1.3.3","type":"version","terraform":"1.3.3","ui":"1.0"}
+{"@level":"info","@message":"aws_vpc.eks: Refreshing state... [id=vpc-1234]","type":"refresh_start","hook":{"resource":{"addr":"aws_vpc.eks"}}}
+{"@level":"info","@message":"aws_subnet.eks_zone_a: Plan to create","type":"planned_change","change":{"resource":{"addr":"aws_subnet.eks_zone_a","resource_type":"aws_subnet","resource_name":"eks_zone_a"},"action":"create"}}
+{"@level":"info","@message":"aws_eks_cluster.eks: Plan to update","type":"planned_change","change":{"resource":{"addr":"aws_eks_cluster.eks","resource_type":"aws_eks_cluster","resource_name":"eks"},"action":"update"}}
+{"@level":"info","@message":"aws_instance.bastion: Plan to replace","type":"planned_change","change":{"resource":{"addr":"aws_instance.bastion","resource_type":"aws_instance","resource_name":"bastion"},"action":"replace","reason":"cannot_update"}}
+{"@level":"info","@message":"aws_subnet.eks_zone_c: Plan to delete","type":"planned_change","change":{"resource":{"addr":"aws_subnet.eks_zone_c","resource_type":"aws_subnet","resource_name":"eks_zone_c"},"action":"delete"}}
+{"@level":"info","@message":"Plan: 2 to add, 1 to change, 2 to destroy.","type":"change_summary","changes":{"add":2,"change":1,"remove":2,"operation":"plan"}}
+not a json line"#
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+
+        // execute:
+        let summary = TerraformPlanSummary::from_json_output(&output);
+
+        // verify:
+        assert_eq!(
+            TerraformPlanSummary {
+                to_add: 2,
+                to_change: 1,
+                to_destroy: 2,
+                destroyed_resources: vec!["aws_instance.bastion".to_string(), "aws_subnet.eks_zone_c".to_string()],
+            },
+            summary
+        );
+        assert!(summary.is_destructive());
+        assert_eq!(
+            "2 to add, 1 to change, 2 to destroy (aws_instance.bastion, aws_subnet.eks_zone_c)",
+            summary.to_string()
+        );
+    }
+
+    #[test]
+    fn test_terraform_plan_summary_without_changes() {
+        // setup:
+        let output = vec![
+            r#"{"@level":"info","@message":"Plan: 0 to add, 0 to change, 0 to destroy.","type":"change_summary","changes":{"add":0,"change":0,"remove":0,"operation":"plan"}}"#.to_string(),
+        ];
+
+        // execute:
+        let summary = TerraformPlanSummary::from_json_output(&output);
+
+        // verify:
+        assert_eq!(TerraformPlanSummary::default(), summary);
+        assert!(!summary.is_destructive());
+    }
+
+    #[test]
+    fn test_json_line_message() {
+        assert_eq!(
+            "aws_subnet.eks_zone_a: Plan to create",
+            json_line_message(
+                r#"{"@level":"info","@message":"aws_subnet.eks_zone_a: Plan to create","type":"planned_change"}"#
+            )
+        );
+        assert_eq!("not a json line", json_line_message("not a json line"));
+        assert_eq!(r#"{"type":"version"}"#, json_line_message(r#"{"type":"version"}"#));
+    }
 }
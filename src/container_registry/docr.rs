@@ -6,15 +6,16 @@ use serde::{Deserialize, Serialize};
 use crate::build_platform::Image;
 use crate::cmd::command::{ExecutableCommand, QoveryCommand};
 use crate::container_registry::errors::ContainerRegistryError;
-use crate::container_registry::{ContainerRegistry, ContainerRegistryInfo, Kind};
+use crate::container_registry::{list_all_pages, ContainerRegistry, ContainerRegistryInfo, Kind, RetentionPolicy};
 use crate::io_models::context::Context;
 use crate::utilities;
+use chrono::{DateTime, Utc};
 use url::Url;
 use uuid::Uuid;
 
 const CR_API_PATH: &str = "https://api.digitalocean.com/v2/registry";
 const CR_CLUSTER_API_PATH: &str = "https://api.digitalocean.com/v2/kubernetes/registry";
-const CR_REGISTRY_DOMAIN: &str = "registry.digitalocean.com";
+pub const CR_REGISTRY_DOMAIN: &str = "registry.digitalocean.com";
 
 // TODO : use --output json
 // see https://www.digitalocean.com/community/tutorials/how-to-use-doctl-the-official-digitalocean-command-line-client
@@ -46,6 +47,7 @@ impl DOCR {
             endpoint: registry,
             registry_name: name.to_string(),
             registry_docker_json_config: None,
+            registry_client_certificate: None,
             get_image_name: Box::new(move |img_name| format!("{}/{}", registry_name, img_name)),
             get_repository_name: Box::new(move |_| registry_name2.to_string()),
         };
@@ -205,6 +207,71 @@ impl ContainerRegistry for DOCR {
         Ok(())
     }
 
+    fn set_retention_policy(
+        &self,
+        repository_name: &str,
+        policy: &RetentionPolicy,
+    ) -> Result<(), ContainerRegistryError> {
+        // DOCR has no lifecycle rules, expired tags are deleted right away and freed by the next garbage collection
+        if policy.is_empty() {
+            return Ok(());
+        }
+
+        let to_error = |raw_error_message: String| ContainerRegistryError::CannotSetRepositoryLifecyclePolicy {
+            registry_name: self.name.to_string(),
+            repository_name: repository_name.to_string(),
+            raw_error_message,
+        };
+        let tags_url = format!("{}/{}/repositories/{}/tags", CR_API_PATH, self.name, repository_name);
+
+        let tags = list_all_pages(|page| {
+            let res = reqwest::blocking::Client::new()
+                .get(format!("{}?page={}&per_page=200", tags_url, page).as_str())
+                .headers(utilities::get_header_with_bearer(self.api_key.as_str()))
+                .send()
+                .map_err(|e| to_error(format!("No response from the Digital Ocean API, error: {}", e)))?;
+            match res.status() {
+                StatusCode::OK => {
+                    let tags = res
+                        .json::<DescribeTagsForImage>()
+                        .map_err(|e| to_error(format!("Cannot deserialize repository tags, error: {}", e)))?;
+                    Ok((tags.tags, Some(tags.meta.total as usize)))
+                }
+                StatusCode::NOT_FOUND => Ok((vec![], None)),
+                status => Err(to_error(format!(
+                    "Bad status code: `{}` returned by the DO registry API for listing repository tags.",
+                    status
+                ))),
+            }
+        })?;
+
+        let pushed_tags = tags
+            .into_iter()
+            .filter_map(|tag| {
+                let updated_at = DateTime::parse_from_rfc3339(&tag.updated_at).ok()?;
+                Some((tag.tag, updated_at.with_timezone(&Utc)))
+            })
+            .collect::<Vec<_>>();
+        for expired_tag in policy.expired_tags(&pushed_tags, Utc::now()) {
+            let res = reqwest::blocking::Client::new()
+                .delete(format!("{}/{}", tags_url, expired_tag).as_str())
+                .headers(utilities::get_header_with_bearer(self.api_key.as_str()))
+                .send()
+                .map_err(|e| to_error(format!("No response from the Digital Ocean API, error: {}", e)))?;
+            match res.status() {
+                StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {}
+                status => {
+                    return Err(to_error(format!(
+                        "Bad status code: `{}` returned by the DO registry API for deleting tag `{}`.",
+                        status, expired_tag
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn delete_image(&self, _image: &Image) -> Result<(), ContainerRegistryError> {
         Ok(())
     }
@@ -2,28 +2,30 @@
 
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Mutex;
 
 use rusoto_core::{Client, HttpClient, Region, RusotoError};
 use rusoto_credential::StaticProvider;
 use rusoto_ecr::{
-    BatchDeleteImageRequest, CreateRepositoryRequest, DeleteRepositoryError, DeleteRepositoryRequest,
-    DescribeImagesRequest, DescribeRepositoriesError, DescribeRepositoriesRequest, Ecr, EcrClient,
-    GetAuthorizationTokenRequest, ImageDetail, ImageIdentifier, PutLifecyclePolicyRequest, Repository, Tag,
-    TagResourceRequest,
+    BatchDeleteImageRequest, CreateRepositoryRequest, DeleteLifecyclePolicyError, DeleteLifecyclePolicyRequest,
+    DeleteRepositoryError, DeleteRepositoryRequest, DescribeImagesRequest, DescribeRepositoriesError,
+    DescribeRepositoriesRequest, Ecr, EcrClient, GetAuthorizationTokenRequest, ImageDetail, ImageIdentifier,
+    PutLifecyclePolicyRequest, Repository, Tag, TagResourceRequest,
 };
 use rusoto_sts::{GetCallerIdentityRequest, Sts, StsClient};
 
 use crate::build_platform::Image;
 use crate::container_registry::errors::ContainerRegistryError;
-use crate::container_registry::{ContainerRegistry, ContainerRegistryInfo, Kind};
+use crate::container_registry::{ContainerRegistry, ContainerRegistryInfo, Kind, RetentionPolicy};
 use crate::events::{EngineEvent, EventMessage, InfrastructureStep, Stage};
 use crate::io_models::context::Context;
 use crate::logger::Logger;
 use crate::runtime::block_on;
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use retry::delay::Fixed;
 use retry::Error::Operation;
 use retry::{Error, OperationResult};
-use serde_json::json;
+use serde_json::{json, Value};
 use url::Url;
 use uuid::Uuid;
 
@@ -38,6 +40,15 @@ pub struct ECR {
     registry_info: Option<ContainerRegistryInfo>,
     logger: Box<dyn Logger>,
     tags: HashMap<String, String>,
+    token_expires_at: Mutex<Option<DateTime<Utc>>>,
+}
+
+// ECR tokens are valid for 12 hours, login again a bit before so a push doesn't start with a token about to expire
+fn token_needs_refresh(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    match expires_at {
+        Some(expires_at) => now + Duration::hours(1) >= expires_at,
+        None => false,
+    }
 }
 
 impl ECR {
@@ -63,23 +74,15 @@ impl ECR {
             registry_info: None,
             logger,
             tags,
+            token_expires_at: Mutex::new(None),
         };
 
-        let credentials = Self::get_credentials(&cr.ecr_client())?;
-        let mut registry_url = Url::parse(credentials.endpoint_url.as_str()).unwrap();
-        let _ = registry_url.set_username(&credentials.access_token);
-        let _ = registry_url.set_password(Some(&credentials.password));
-
-        cr.log_info(format!("🔓 Login to ECR registry {}", credentials.endpoint_url));
-        cr.context
-            .docker
-            .login(&registry_url)
-            .map_err(|_err| ContainerRegistryError::InvalidCredentials)?;
-
+        let registry_url = cr.login()?;
         let registry_info = ContainerRegistryInfo {
             endpoint: registry_url,
             registry_name: cr.name.to_string(),
             registry_docker_json_config: None,
+            registry_client_certificate: None,
             get_image_name: Box::new(|img_name| img_name.to_string()),
             get_repository_name: Box::new(|imag_name| imag_name.to_string()),
         };
@@ -89,6 +92,23 @@ impl ECR {
         Ok(cr)
     }
 
+    // Get a new token and login docker with it, returns the registry url with the token as credentials
+    fn login(&self) -> Result<Url, ContainerRegistryError> {
+        let credentials = Self::get_credentials(&self.ecr_client())?;
+        let mut registry_url = Url::parse(credentials.endpoint_url.as_str()).unwrap();
+        let _ = registry_url.set_username(&credentials.access_token);
+        let _ = registry_url.set_password(Some(&credentials.password));
+
+        self.log_info(format!("🔓 Login to ECR registry {}", credentials.endpoint_url));
+        self.context
+            .docker
+            .login(&registry_url)
+            .map_err(|_err| ContainerRegistryError::InvalidCredentials)?;
+
+        *self.token_expires_at.lock().unwrap() = credentials.expires_at;
+        Ok(registry_url)
+    }
+
     pub fn log_info(&self, msg: String) {
         self.logger.log(EngineEvent::Info(
             self.get_event_details(Stage::Infrastructure(InfrastructureStep::ValidateSystemRequirements)),
@@ -245,43 +265,12 @@ impl ECR {
             Ok(repos) => {
                 // apply retention policy
                 if let Some(repos) = repos {
-                    let retention_policy_in_days = match image_retention_time_in_seconds / 86400 {
-                        0..=1 => 1,
-                        _ => image_retention_time_in_seconds / 86400,
-                    };
-
-                    let lifecycle_policy_text = json!({
-                      "rules": [
-                        {
-                          "action": {
-                            "type": "expire"
-                          },
-                          "selection": {
-                            "countType": "sinceImagePushed",
-                            "countUnit": "days",
-                            "countNumber": retention_policy_in_days,
-                            "tagStatus": "any"
-                          },
-                          "description": "Images retention policy",
-                          "rulePriority": 1
-                        }
-                      ]
-                    });
-
-                    let plp = PutLifecyclePolicyRequest {
-                        repository_name: repository_name.to_string(),
-                        lifecycle_policy_text: lifecycle_policy_text.to_string(),
-                        ..Default::default()
-                    };
-
-                    match block_on(self.ecr_client().put_lifecycle_policy(plp)) {
-                        Err(err) => Err(ContainerRegistryError::CannotSetRepositoryLifecyclePolicy {
-                            registry_name: self.name.to_string(),
-                            repository_name: repository_name.to_string(),
-                            raw_error_message: err.to_string(),
-                        }),
-                        _ => Ok(self.get_repository(repository_name).expect("cannot get repository")),
-                    }?;
+                    self.set_retention_policy(
+                        repository_name,
+                        &RetentionPolicy::new_with_max_age(std::time::Duration::from_secs(
+                            image_retention_time_in_seconds as u64,
+                        )),
+                    )?;
 
                     if let Some(repository_arn) = &repos[0].repository_arn {
                         let mut ecr_tags: Vec<Tag> = vec![];
@@ -333,7 +322,7 @@ impl ECR {
     pub fn get_credentials(ecr_client: &EcrClient) -> Result<ECRCredentials, ContainerRegistryError> {
         let r = block_on(ecr_client.get_authorization_token(GetAuthorizationTokenRequest::default()));
 
-        let (access_token, password, endpoint_url) = match r {
+        let (access_token, password, endpoint_url, expires_at) = match r {
             Ok(t) => match t.authorization_data {
                 Some(authorization_data) => {
                     let ad = authorization_data.first().unwrap();
@@ -348,6 +337,8 @@ impl ECR {
                         s_token.first().unwrap().to_string(),
                         s_token.get(1).unwrap().to_string(),
                         ad.clone().proxy_endpoint.unwrap(),
+                        ad.expires_at
+                            .and_then(|secs| Utc.timestamp_opt(secs as i64, 0).single()),
                     )
                 }
                 None => {
@@ -359,7 +350,7 @@ impl ECR {
             }
         };
 
-        Ok(ECRCredentials::new(access_token, password, endpoint_url))
+        Ok(ECRCredentials::new(access_token, password, endpoint_url, expires_at))
     }
 
     fn is_credentials_valid(&self) -> Result<(), ContainerRegistryError> {
@@ -417,6 +408,39 @@ impl ContainerRegistry for ECR {
         self.delete_repository(repository_name)
     }
 
+    fn set_retention_policy(
+        &self,
+        repository_name: &str,
+        policy: &RetentionPolicy,
+    ) -> Result<(), ContainerRegistryError> {
+        let to_error = |raw_error_message: String| ContainerRegistryError::CannotSetRepositoryLifecyclePolicy {
+            registry_name: self.name.to_string(),
+            repository_name: repository_name.to_string(),
+            raw_error_message,
+        };
+
+        // ECR rejects lifecycle policies without rules
+        if policy.is_empty() {
+            let dlp = DeleteLifecyclePolicyRequest {
+                repository_name: repository_name.to_string(),
+                ..Default::default()
+            };
+            return match block_on(self.ecr_client().delete_lifecycle_policy(dlp)) {
+                Ok(_) | Err(RusotoError::Service(DeleteLifecyclePolicyError::LifecyclePolicyNotFound(_))) => Ok(()),
+                Err(err) => Err(to_error(err.to_string())),
+            };
+        }
+
+        let plp = PutLifecyclePolicyRequest {
+            repository_name: repository_name.to_string(),
+            lifecycle_policy_text: to_ecr_lifecycle_policy(policy).to_string(),
+            ..Default::default()
+        };
+        block_on(self.ecr_client().put_lifecycle_policy(plp)).map_err(|err| to_error(err.to_string()))?;
+
+        Ok(())
+    }
+
     fn delete_image(&self, image: &Image) -> Result<(), ContainerRegistryError> {
         self.delete_image(image)
     }
@@ -424,20 +448,184 @@ impl ContainerRegistry for ECR {
     fn does_image_exists(&self, image: &Image) -> bool {
         self.get_image(image).is_some()
     }
+
+    // Docker keeps the new token in its config, the one in the registry info endpoint is left as is
+    fn refresh_credentials(&self, force: bool) -> Result<(), ContainerRegistryError> {
+        let expires_at = *self.token_expires_at.lock().unwrap();
+        if !force && !token_needs_refresh(expires_at, Utc::now()) {
+            return Ok(());
+        }
+
+        let _ = self.login()?;
+        Ok(())
+    }
 }
 
 pub struct ECRCredentials {
     pub access_token: String,
     pub password: String,
     pub endpoint_url: String,
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl ECRCredentials {
-    fn new(access_token: String, password: String, endpoint_url: String) -> Self {
+    fn new(access_token: String, password: String, endpoint_url: String, expires_at: Option<DateTime<Utc>>) -> Self {
         ECRCredentials {
             access_token,
             password,
             endpoint_url,
+            expires_at,
+        }
+    }
+}
+
+/// Converts the retention policy into ECR lifecycle rules, one rule per limit.
+/// ECR only allows the last rule to select `any` image, previous ones select every tagged image instead.
+fn to_ecr_lifecycle_policy(policy: &RetentionPolicy) -> Value {
+    let mut counts = vec![];
+    if let Some(max_age) = policy.max_age {
+        let max_age_in_days = std::cmp::max(max_age.as_secs() / 86400, 1);
+        counts.push(("Images retention policy", "sinceImagePushed", Some("days"), max_age_in_days));
+    }
+    if let Some(max_images) = policy.max_images {
+        counts.push(("Images count retention policy", "imageCountMoreThan", None, max_images as u64));
+    }
+
+    let rules_count = counts.len();
+    let rules = counts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (description, count_type, count_unit, count_number))| {
+            let mut selection = match (policy.tag_patterns.is_empty(), idx + 1 == rules_count) {
+                (true, true) => json!({ "tagStatus": "any" }),
+                (true, false) => json!({ "tagStatus": "tagged", "tagPatternList": ["*"] }),
+                (false, _) => json!({ "tagStatus": "tagged", "tagPatternList": policy.tag_patterns }),
+            };
+            selection["countType"] = json!(count_type);
+            if let Some(count_unit) = count_unit {
+                selection["countUnit"] = json!(count_unit);
+            }
+            selection["countNumber"] = json!(count_number);
+
+            json!({
+                "action": {
+                    "type": "expire"
+                },
+                "selection": selection,
+                "description": description,
+                "rulePriority": idx + 1
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "rules": rules })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container_registry::ecr::{to_ecr_lifecycle_policy, token_needs_refresh};
+    use crate::container_registry::RetentionPolicy;
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn test_token_needs_refresh_before_expiration() {
+        // setup:
+        let now = Utc::now();
+
+        // execute & verify:
+        assert!(!token_needs_refresh(Some(now + Duration::hours(12)), now));
+        assert!(token_needs_refresh(Some(now + Duration::minutes(30)), now));
+        assert!(token_needs_refresh(Some(now - Duration::minutes(1)), now));
+        // without expiration, the token is only refreshed when the registry rejects it
+        assert!(!token_needs_refresh(None, now));
+    }
+
+    #[test]
+    fn test_retention_policy_to_ecr_lifecycle_policy() {
+        // setup:
+        let test_cases = vec![
+            (
+                RetentionPolicy::new_with_max_age(std::time::Duration::from_secs(3600)),
+                json!({
+                  "rules": [
+                    {
+                      "action": { "type": "expire" },
+                      "selection": {
+                        "tagStatus": "any",
+                        "countType": "sinceImagePushed",
+                        "countUnit": "days",
+                        "countNumber": 1
+                      },
+                      "description": "Images retention policy",
+                      "rulePriority": 1
+                    }
+                  ]
+                }),
+            ),
+            (
+                RetentionPolicy {
+                    max_images: Some(10),
+                    max_age: Some(std::time::Duration::from_secs(7 * 86400)),
+                    tag_patterns: vec![],
+                },
+                json!({
+                  "rules": [
+                    {
+                      "action": { "type": "expire" },
+                      "selection": {
+                        "tagStatus": "tagged",
+                        "tagPatternList": ["*"],
+                        "countType": "sinceImagePushed",
+                        "countUnit": "days",
+                        "countNumber": 7
+                      },
+                      "description": "Images retention policy",
+                      "rulePriority": 1
+                    },
+                    {
+                      "action": { "type": "expire" },
+                      "selection": {
+                        "tagStatus": "any",
+                        "countType": "imageCountMoreThan",
+                        "countNumber": 10
+                      },
+                      "description": "Images count retention policy",
+                      "rulePriority": 2
+                    }
+                  ]
+                }),
+            ),
+            (
+                RetentionPolicy {
+                    max_images: Some(5),
+                    max_age: None,
+                    tag_patterns: vec!["release-*".to_string()],
+                },
+                json!({
+                  "rules": [
+                    {
+                      "action": { "type": "expire" },
+                      "selection": {
+                        "tagStatus": "tagged",
+                        "tagPatternList": ["release-*"],
+                        "countType": "imageCountMoreThan",
+                        "countNumber": 5
+                      },
+                      "description": "Images count retention policy",
+                      "rulePriority": 1
+                    }
+                  ]
+                }),
+            ),
+        ];
+
+        for (policy, expected) in test_cases {
+            // execute:
+            let lifecycle_policy = to_ecr_lifecycle_policy(&policy);
+
+            // verify:
+            assert_eq!(expected, lifecycle_policy, "policy: {:?}", policy);
         }
     }
 }
@@ -1,11 +1,17 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
 use url::Url;
 use uuid::Uuid;
 
 use crate::build_platform::Image;
+use crate::cmd::docker::ContainerImage;
 use crate::container_registry::errors::ContainerRegistryError;
-use crate::errors::EngineError;
+use crate::errors::{CommandError, EngineError};
 use crate::events::{EventDetails, Stage, Transmitter};
+use crate::io_models::container::ClientCertificate;
 use crate::io_models::context::Context;
 use crate::io_models::QoveryIdentifier;
 
@@ -14,7 +20,7 @@ pub mod ecr;
 pub mod errors;
 pub mod scaleway_container_registry;
 
-pub trait ContainerRegistry {
+pub trait ContainerRegistry: Send + Sync {
     fn context(&self) -> &Context;
     fn kind(&self) -> Kind;
     fn id(&self) -> &str;
@@ -42,11 +48,25 @@ pub trait ContainerRegistry {
     ) -> Result<(), ContainerRegistryError>;
     fn delete_repository(&self, repository_name: &str) -> Result<(), ContainerRegistryError>;
 
+    // Set which images of the repository have to be removed, an empty policy keeps every image.
+    // Registries without lifecycle rules apply the policy right away, on the images already pushed
+    fn set_retention_policy(
+        &self,
+        repository_name: &str,
+        policy: &RetentionPolicy,
+    ) -> Result<(), ContainerRegistryError>;
+
     fn delete_image(&self, image_name: &Image) -> Result<(), ContainerRegistryError>;
 
     // Check on the registry if a specific image already exist
     fn does_image_exists(&self, image: &Image) -> bool;
 
+    // Registries issuing short-lived credentials (i.e: ECR tokens expire after 12 hours) must login again before
+    // they expire, or regardless of their expiration if `force` is set, i.e: after the registry returned a 401
+    fn refresh_credentials(&self, _force: bool) -> Result<(), ContainerRegistryError> {
+        Ok(())
+    }
+
     fn get_event_details(&self, stage: Stage) -> EventDetails {
         let context = self.context();
         let ev = EventDetails::new(
@@ -62,22 +82,165 @@ pub trait ContainerRegistry {
     }
 }
 
+/// Images retention policy of a repository.
+/// Images having a tag matching one of `tag_patterns` (any image if empty) expire once older than `max_age`
+/// or when they are not among the `max_images` most recently pushed ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub max_images: Option<u32>,
+    pub max_age: Option<Duration>,
+    /// Tag patterns, `*` matching any sequence of characters.
+    pub tag_patterns: Vec<String>,
+}
+
+impl RetentionPolicy {
+    pub fn new_with_max_age(max_age: Duration) -> Self {
+        RetentionPolicy {
+            max_age: Some(max_age),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_images.is_none() && self.max_age.is_none()
+    }
+
+    pub fn matches_tag(&self, tag: &str) -> bool {
+        self.tag_patterns.is_empty() || self.tag_patterns.iter().any(|pattern| wildcard_match(pattern, tag))
+    }
+
+    /// Returns tags of images expired by this policy, out of the tags of a repository along with their push date.
+    pub fn expired_tags(&self, tags: &[(String, DateTime<Utc>)], now: DateTime<Utc>) -> Vec<String> {
+        let mut matching_tags = tags.iter().filter(|(tag, _)| self.matches_tag(tag)).collect::<Vec<_>>();
+        matching_tags.sort_by(|(_, left), (_, right)| right.cmp(left));
+
+        let max_age = self
+            .max_age
+            .and_then(|max_age| chrono::Duration::from_std(max_age).ok());
+        matching_tags
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, (_, pushed_at))| {
+                let too_many = matches!(self.max_images, Some(max_images) if *idx >= max_images as usize);
+                let too_old = matches!(max_age, Some(max_age) if now - *pushed_at > max_age);
+                too_many || too_old
+            })
+            .map(|(_, (tag, _))| tag.to_string())
+            .collect()
+    }
+}
+
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match value.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+
+    let parts = parts.collect::<Vec<_>>();
+    let (last, middle) = match parts.split_last() {
+        Some((last, middle)) => (last, middle),
+        None => return rest.is_empty(),
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+/// Fetches every page of a paginated registry API listing, pages being numbered from 1.
+/// `fetch_page` returns the items of the requested page along with the total number of items when known,
+/// pages are fetched until this total is reached or an empty page is returned.
+pub(crate) fn list_all_pages<T, E>(
+    mut fetch_page: impl FnMut(u32) -> Result<(Vec<T>, Option<usize>), E>,
+) -> Result<Vec<T>, E> {
+    let mut items = vec![];
+    for page in 1.. {
+        let (page_items, total) = fetch_page(page)?;
+        if page_items.is_empty() {
+            break;
+        }
+
+        items.extend(page_items);
+        if matches!(total, Some(total) if items.len() >= total) {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
 pub fn to_engine_error(event_details: EventDetails, err: ContainerRegistryError) -> EngineError {
     EngineError::new_container_registry_error(event_details, err)
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum RefreshedCredentialsError<E> {
+    /// Credentials couldn't be refreshed, the operation has not been (re)tried.
+    Credentials(ContainerRegistryError),
+    /// Operation failed, after a retry if the registry rejected the credentials the first time.
+    Operation(E),
+}
+
+/// Runs an operation against the registry with up-to-date credentials:
+/// they are refreshed before the operation if about to expire, and if the registry rejects them anyway,
+/// they are refreshed again and the operation is retried once.
+pub fn with_refreshed_credentials<T, E>(
+    refresh_credentials: impl Fn(bool) -> Result<(), ContainerRegistryError>,
+    mut operation: impl FnMut() -> Result<T, E>,
+    is_unauthorized: impl Fn(&E) -> bool,
+) -> Result<T, RefreshedCredentialsError<E>> {
+    refresh_credentials(false).map_err(RefreshedCredentialsError::Credentials)?;
+
+    match operation() {
+        Err(err) if is_unauthorized(&err) => {
+            refresh_credentials(true).map_err(RefreshedCredentialsError::Credentials)?;
+            operation().map_err(RefreshedCredentialsError::Operation)
+        }
+        result => result.map_err(RefreshedCredentialsError::Operation),
+    }
+}
+
 pub struct ContainerRegistryInfo {
     pub endpoint: Url, // Contains username and password if necessary
     pub registry_name: String,
     pub registry_docker_json_config: Option<String>,
+    // client certificate to present to registries requiring mutual TLS
+    pub registry_client_certificate: Option<ClientCertificate>,
     // give it the name of your image, and it returns the full name with prefix if needed
     // i.e: for DigitalOcean => registry_name/image_name
     // i.e: fo scaleway => image_name/image_name
     // i.e: for AWS => image_name
-    pub get_image_name: Box<dyn Fn(&str) -> String>,
+    pub get_image_name: Box<dyn Fn(&str) -> String + Send + Sync>,
 
     // Give it the name of your image, and it return the name of the repository that will be used
-    pub get_repository_name: Box<dyn Fn(&str) -> String>,
+    pub get_repository_name: Box<dyn Fn(&str) -> String + Send + Sync>,
+}
+
+/// Credentials to pull images from a registry, i.e: to be set in an image pull secret.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RegistryCredential {
+    pub host: String,
+    pub username: String,
+    pub password: String,
+}
+
+/// Base64 encoded docker config json with one `auths` entry per registry, as expected by `kubernetes.io/dockerconfigjson` secrets.
+pub fn docker_json_config(credentials: &[RegistryCredential]) -> String {
+    let auths: serde_json::Map<String, serde_json::Value> = credentials
+        .iter()
+        .map(|credential| {
+            let auth = base64::encode(format!("{}:{}", credential.username, credential.password).as_bytes());
+            (credential.host.clone(), serde_json::json!({ "auth": auth }))
+        })
+        .collect();
+
+    base64::encode(serde_json::json!({ "auths": auths }).to_string().as_bytes())
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
@@ -87,3 +250,411 @@ pub enum Kind {
     Docr,
     ScalewayCr,
 }
+
+/// Fully qualified container image reference, i.e: `registry.digitalocean.com/qovery/zabcd1234:v1.0.0`.
+/// `repository` is the path between the registry and the image, it is empty when the image sits at the registry root.
+/// `tag` holds either a tag or a digest (i.e: `sha256:...`), digests being displayed after a `@` instead of a `:`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageReference {
+    pub registry: String,
+    pub repository: String,
+    pub image: String,
+    pub tag: String,
+}
+
+impl ImageReference {
+    pub fn is_digest(&self) -> bool {
+        // tags can't contain `:` while digests are `algorithm:hex`
+        self.tag.contains(':')
+    }
+}
+
+impl Display for ImageReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tag_separator = if self.is_digest() { '@' } else { ':' };
+        match self.repository.is_empty() {
+            true => write!(f, "{}/{}{}{}", self.registry, self.image, tag_separator, self.tag),
+            false => write!(
+                f,
+                "{}/{}/{}{}{}",
+                self.registry, self.repository, self.image, tag_separator, self.tag
+            ),
+        }
+    }
+}
+
+impl FromStr for ImageReference {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<ImageReference, CommandError> {
+        let invalid_reference = |reason: &str| {
+            CommandError::new_from_safe_message(format!("`{}` is not a valid image reference: {}", s, reason))
+        };
+
+        let (name, tag) = match s.split_once('@') {
+            Some((name, digest)) if digest.contains(':') => (name, digest),
+            Some(_) => return Err(invalid_reference("digest should be `algorithm:hex`")),
+            // the tag separator is the last `:` of the last path segment, others are registry ports
+            None => match s.rsplit_once(':') {
+                Some((name, tag)) if !tag.contains('/') => (name, tag),
+                _ => return Err(invalid_reference("tag or digest is missing")),
+            },
+        };
+
+        let (registry, path) = match name.split_once('/') {
+            Some((registry, path)) if registry.contains('.') || registry.contains(':') || registry == "localhost" => {
+                (registry, path)
+            }
+            _ => return Err(invalid_reference("registry is missing")),
+        };
+
+        if tag.is_empty() || path.split('/').any(|segment| segment.is_empty()) {
+            return Err(invalid_reference("empty path segment"));
+        }
+        let (repository, image) = path.rsplit_once('/').unwrap_or(("", path));
+
+        Ok(ImageReference {
+            registry: registry.to_string(),
+            repository: repository.to_string(),
+            image: image.to_string(),
+            tag: tag.to_string(),
+        })
+    }
+}
+
+// The first tag is the one referenced, as for `ContainerImage::image_name`
+impl From<&ContainerImage> for ImageReference {
+    fn from(image: &ContainerImage) -> Self {
+        let registry = match image.registry.port() {
+            Some(port) => format!("{}:{}", image.registry.host_str().unwrap_or_default(), port),
+            None => image.registry.host_str().unwrap_or_default().to_string(),
+        };
+        let (repository, name) = image.name.rsplit_once('/').unwrap_or(("", &image.name));
+
+        ImageReference {
+            registry,
+            repository: repository.to_string(),
+            image: name.to_string(),
+            tag: image.tags.first().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::docker::ContainerImage;
+    use crate::container_registry::errors::ContainerRegistryError;
+    use crate::container_registry::{
+        docker_json_config, list_all_pages, with_refreshed_credentials, ImageReference, RefreshedCredentialsError,
+        RegistryCredential, RetentionPolicy,
+    };
+    use chrono::{Duration, TimeZone, Utc};
+    use std::cell::RefCell;
+    use std::str::FromStr;
+    use url::Url;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum PushError {
+        Unauthorized,
+        Other,
+    }
+
+    #[test]
+    fn test_expired_token_is_refreshed_and_operation_retried() {
+        // setup: token expires while the engine is running, the registry rejects the first push with a 401
+        let refreshes = RefCell::new(vec![]);
+        let token_is_valid = RefCell::new(false);
+        let mut pushes = 0;
+
+        // execute:
+        let result = with_refreshed_credentials(
+            |force| {
+                refreshes.borrow_mut().push(force);
+                if force {
+                    *token_is_valid.borrow_mut() = true;
+                }
+                Ok(())
+            },
+            || {
+                pushes += 1;
+                match *token_is_valid.borrow() {
+                    true => Ok("pushed"),
+                    false => Err(PushError::Unauthorized),
+                }
+            },
+            |err| *err == PushError::Unauthorized,
+        );
+
+        // verify:
+        assert_eq!(Ok("pushed"), result);
+        assert_eq!(2, pushes);
+        assert_eq!(vec![false, true], *refreshes.borrow());
+    }
+
+    #[test]
+    fn test_operation_is_retried_only_once_and_not_on_other_errors() {
+        // setup:
+        let mut pushes = 0;
+
+        // execute:
+        let unauthorized: Result<(), _> = with_refreshed_credentials(
+            |_| Ok(()),
+            || {
+                pushes += 1;
+                Err(PushError::Unauthorized)
+            },
+            |err| *err == PushError::Unauthorized,
+        );
+        let other: Result<(), _> =
+            with_refreshed_credentials(|_| Ok(()), || Err(PushError::Other), |err| *err == PushError::Unauthorized);
+
+        // verify:
+        assert_eq!(Err(RefreshedCredentialsError::Operation(PushError::Unauthorized)), unauthorized);
+        assert_eq!(2, pushes);
+        assert_eq!(Err(RefreshedCredentialsError::Operation(PushError::Other)), other);
+    }
+
+    #[test]
+    fn test_operation_not_run_when_credentials_cannot_be_refreshed() {
+        // execute:
+        let result: Result<(), RefreshedCredentialsError<PushError>> = with_refreshed_credentials(
+            |_| Err(ContainerRegistryError::CannotGetCredentials),
+            || panic!("operation must not run without valid credentials"),
+            |err| *err == PushError::Unauthorized,
+        );
+
+        // verify:
+        assert_eq!(
+            Err(RefreshedCredentialsError::Credentials(
+                ContainerRegistryError::CannotGetCredentials
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn test_image_reference_parsing() {
+        // setup:
+        let reference = |registry: &str, repository: &str, image: &str, tag: &str| ImageReference {
+            registry: registry.to_string(),
+            repository: repository.to_string(),
+            image: image.to_string(),
+            tag: tag.to_string(),
+        };
+        let digest = "sha256:3c5a5e4fdc9c4d0b4a2a1b0e9f8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d";
+        let digest_reference = format!("rg.fr-par.scw.cloud/zabcd1234/app@{}", digest);
+        let test_cases = vec![
+            (
+                "registry.digitalocean.com/qovery/zabcd1234:v1.0.0",
+                Some(reference("registry.digitalocean.com", "qovery", "zabcd1234", "v1.0.0")),
+            ),
+            (
+                "123456789.dkr.ecr.eu-west-3.amazonaws.com/zabcd1234:latest",
+                Some(reference(
+                    "123456789.dkr.ecr.eu-west-3.amazonaws.com",
+                    "",
+                    "zabcd1234",
+                    "latest",
+                )),
+            ),
+            (
+                "localhost:5000/org/team/app:1.2",
+                Some(reference("localhost:5000", "org/team", "app", "1.2")),
+            ),
+            (
+                digest_reference.as_str(),
+                Some(reference("rg.fr-par.scw.cloud", "zabcd1234", "app", digest)),
+            ),
+            ("registry.digitalocean.com/qovery/zabcd1234", None),
+            ("localhost:5000/app", None),
+            ("qovery/zabcd1234:v1.0.0", None),
+            ("zabcd1234:v1.0.0", None),
+            ("registry.digitalocean.com/qovery//app:v1", None),
+            ("registry.digitalocean.com/app:", None),
+            ("registry.digitalocean.com/app@3c5a5e4f", None),
+        ];
+
+        for (input, expected) in test_cases {
+            // execute:
+            let result = ImageReference::from_str(input);
+
+            // verify:
+            assert_eq!(expected, result.clone().ok(), "input: {}", input);
+            if let Ok(image_reference) = result {
+                assert_eq!(input, image_reference.to_string(), "round trip of: {}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_image_reference_from_container_image() {
+        // setup:
+        let test_cases = vec![
+            (
+                ContainerImage::new(
+                    Url::parse("https://registry.digitalocean.com").unwrap(),
+                    "qovery/zabcd1234".to_string(),
+                    vec!["v1.0.0".to_string(), "latest".to_string()],
+                ),
+                "registry.digitalocean.com/qovery/zabcd1234:v1.0.0",
+            ),
+            (
+                ContainerImage::new(
+                    Url::parse("http://localhost:5000").unwrap(),
+                    "app".to_string(),
+                    vec!["1.2".to_string()],
+                ),
+                "localhost:5000/app:1.2",
+            ),
+        ];
+
+        for (container_image, expected) in test_cases {
+            // execute:
+            let image_reference = ImageReference::from(&container_image);
+
+            // verify:
+            assert_eq!(container_image.image_name(), image_reference.to_string());
+            assert_eq!(Ok(image_reference), ImageReference::from_str(expected));
+        }
+    }
+
+    #[test]
+    fn test_retention_policy_expired_tags() {
+        // setup:
+        let now = Utc.ymd(2022, 12, 1).and_hms(0, 0, 0);
+        let tags = vec![
+            ("v3".to_string(), now - Duration::days(1)),
+            ("v1".to_string(), now - Duration::days(30)),
+            ("v2".to_string(), now - Duration::days(10)),
+            ("cache-v2".to_string(), now - Duration::days(10)),
+            ("cache-v1".to_string(), now - Duration::days(30)),
+        ];
+        let test_cases = vec![
+            (RetentionPolicy::default(), vec![]),
+            (
+                RetentionPolicy::new_with_max_age(std::time::Duration::from_secs(15 * 86400)),
+                vec!["v1", "cache-v1"],
+            ),
+            (
+                RetentionPolicy {
+                    max_images: Some(3),
+                    ..Default::default()
+                },
+                vec!["v1", "cache-v1"],
+            ),
+            (
+                RetentionPolicy {
+                    max_images: Some(1),
+                    max_age: None,
+                    tag_patterns: vec!["v*".to_string()],
+                },
+                vec!["v2", "v1"],
+            ),
+            (
+                RetentionPolicy {
+                    max_images: Some(10),
+                    max_age: Some(std::time::Duration::from_secs(5 * 86400)),
+                    tag_patterns: vec!["cache-*".to_string()],
+                },
+                vec!["cache-v2", "cache-v1"],
+            ),
+        ];
+
+        for (policy, expected) in test_cases {
+            // execute:
+            let expired = policy.expired_tags(&tags, now);
+
+            // verify:
+            assert_eq!(expected, expired, "policy: {:?}", policy);
+        }
+    }
+
+    #[test]
+    fn test_list_all_pages() {
+        // setup:
+        let items = (0..450).collect::<Vec<u32>>();
+        let test_cases = vec![
+            ("total is known", Some(items.len()), 3),
+            // last page is only known once an empty page is returned
+            ("total is unknown", None, 4),
+        ];
+
+        for (description, total, expected_fetched_pages) in test_cases {
+            let mut fetched_pages = vec![];
+
+            // execute:
+            let result: Result<Vec<u32>, ()> = list_all_pages(|page| {
+                fetched_pages.push(page);
+                let page_items = items.iter().skip((page as usize - 1) * 200).take(200).cloned();
+                Ok((page_items.collect(), total))
+            });
+
+            // verify:
+            assert_eq!(Ok(items.clone()), result, "case: {}", description);
+            assert_eq!(
+                (1..=expected_fetched_pages).collect::<Vec<u32>>(),
+                fetched_pages,
+                "case: {}",
+                description
+            );
+        }
+
+        assert_eq!(Err("unavailable"), list_all_pages::<u32, _>(|_| Err("unavailable")));
+    }
+
+    #[test]
+    fn test_retention_policy_matches_tag() {
+        // setup:
+        let policy = RetentionPolicy {
+            tag_patterns: vec!["release-*".to_string(), "*-rc*".to_string(), "latest".to_string()],
+            ..Default::default()
+        };
+
+        // execute & verify:
+        assert!(policy.matches_tag("release-1.0"));
+        assert!(policy.matches_tag("v1-rc2"));
+        assert!(policy.matches_tag("latest"));
+        assert!(!policy.matches_tag("latest-1"));
+        assert!(!policy.matches_tag("v1.0"));
+        assert!(RetentionPolicy::default().matches_tag("any"));
+    }
+
+    #[test]
+    fn test_docker_json_config_with_multiple_registries() {
+        // setup:
+        let credentials = vec![
+            RegistryCredential {
+                host: "registry.digitalocean.com".to_string(),
+                username: "do_token".to_string(),
+                password: "do_token".to_string(),
+            },
+            RegistryCredential {
+                host: "mirror.mycompany.com:5000".to_string(),
+                username: "login".to_string(),
+                password: "p@ss\"word".to_string(),
+            },
+        ];
+
+        // execute:
+        let docker_json_config = docker_json_config(&credentials);
+
+        // verify:
+        let json = base64::decode(docker_json_config).expect("should be valid base64");
+        let json: serde_json::Value = serde_json::from_slice(&json).expect("should be valid json");
+        let auths = json["auths"].as_object().expect("auths should be an object");
+        assert_eq!(2, auths.len());
+        for credential in credentials {
+            let auth = auths[&credential.host]["auth"]
+                .as_str()
+                .expect("auth should be a string");
+            assert_eq!(
+                format!("{}:{}", credential.username, credential.password).into_bytes(),
+                base64::decode(auth).expect("auth should be valid base64")
+            );
+        }
+        assert_eq!(
+            r#"{"auths":{}}"#.as_bytes(),
+            base64::decode(super::docker_json_config(&[])).unwrap()
+        );
+    }
+}
@@ -4,10 +4,11 @@ use self::scaleway_api_rs::models::scaleway_registry_v1_namespace::Status;
 use crate::build_platform::Image;
 use crate::cmd::docker;
 use crate::container_registry::errors::ContainerRegistryError;
-use crate::container_registry::{ContainerRegistry, ContainerRegistryInfo, Kind};
+use crate::container_registry::{list_all_pages, ContainerRegistry, ContainerRegistryInfo, Kind, RetentionPolicy};
 use crate::io_models::context::Context;
 use crate::models::scaleway::ScwZone;
 use crate::runtime::block_on;
+use chrono::{DateTime, Utc};
 use url::Url;
 use uuid::Uuid;
 
@@ -52,6 +53,7 @@ impl ScalewayCR {
                 &secret_token,
                 zone.region().as_str(),
             )),
+            registry_client_certificate: None,
             get_image_name: Box::new(move |img_name| format!("{}/{}", img_name, img_name)),
             get_repository_name: Box::new(|img_name| img_name.to_string()),
         };
@@ -303,6 +305,89 @@ impl ContainerRegistry for ScalewayCR {
         Ok(())
     }
 
+    fn set_retention_policy(
+        &self,
+        repository_name: &str,
+        policy: &RetentionPolicy,
+    ) -> Result<(), ContainerRegistryError> {
+        // Scaleway registry has no lifecycle rules, expired tags are deleted right away
+        if policy.is_empty() {
+            return Ok(());
+        }
+
+        let to_error = |raw_error_message: String| ContainerRegistryError::CannotSetRepositoryLifecyclePolicy {
+            registry_name: self.name.to_string(),
+            repository_name: repository_name.to_string(),
+            raw_error_message,
+        };
+        let configuration = self.get_configuration();
+        let region = self.zone.region().to_string();
+
+        let namespace_id = self
+            .get_registry_namespace(repository_name)
+            .and_then(|namespace| namespace.id)
+            .ok_or_else(|| to_error("registry namespace doesn't exist".to_string()))?;
+        // https://developers.scaleway.com/en/products/registry/api/#get-a6f1bc
+        let images = list_all_pages(|page| {
+            let images = block_on(scaleway_api_rs::apis::images_api::list_images(
+                &configuration,
+                region.as_str(),
+                Some(page as f32),
+                Some(100.0),
+                None,
+                Some(namespace_id.as_str()),
+                None,
+                None,
+                None,
+            ))
+            .map_err(|err| to_error(err.to_string()))?;
+            Ok((
+                images.images.unwrap_or_default(),
+                images.total_count.map(|total| total as usize),
+            ))
+        })?;
+
+        for image_id in images.into_iter().filter_map(|image| image.id) {
+            let tags = list_all_pages(|page| {
+                let tags = block_on(scaleway_api_rs::apis::tags_api::list_tags(
+                    &configuration,
+                    region.as_str(),
+                    image_id.as_str(),
+                    Some(page as f32),
+                    Some(100.0),
+                    None,
+                    None,
+                ))
+                .map_err(|err| to_error(err.to_string()))?;
+                Ok((tags.tags.unwrap_or_default(), tags.total_count.map(|total| total as usize)))
+            })?;
+
+            let pushed_tags = tags
+                .iter()
+                .filter_map(|tag| {
+                    let created_at = DateTime::parse_from_rfc3339(tag.created_at.as_ref()?).ok()?;
+                    Some((tag.name.clone()?, created_at.with_timezone(&Utc)))
+                })
+                .collect::<Vec<_>>();
+            for expired_tag in policy.expired_tags(&pushed_tags, Utc::now()) {
+                let tag_id = match tags.iter().find(|tag| tag.name.as_ref() == Some(&expired_tag)) {
+                    Some(scaleway_api_rs::models::ScalewayRegistryV1Tag { id: Some(id), .. }) => id,
+                    _ => continue,
+                };
+
+                let _ = block_on(scaleway_api_rs::apis::tags_api::delete_tag(
+                    &configuration,
+                    region.as_str(),
+                    tag_id.as_str(),
+                    Some(true),
+                ))
+                .map_err(|err| to_error(err.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn delete_image(&self, image: &Image) -> Result<(), ContainerRegistryError> {
         match self.delete_image(image) {
             Ok(_) => Ok(()),
@@ -0,0 +1,448 @@
+// Blue/green deployment of applications:
+// 1. the new version is deployed in its own Deployment, labelled with the color not receiving the traffic
+// 2. once rolled out, every pod of the new color must be Ready according to kubernetes (readiness probe), then is
+//    health checked through the service health check endpoint when it has one, reached through the API server pod proxy,
+//    or by checking its port is listening otherwise
+// 3. if all pods are ready, the Service selector and the autoscaler are switched to the new color and the previous
+//    Deployment deleted, otherwise the new Deployment is deleted and the Service keeps pointing to the previous color
+//
+// An application previously deployed with the rolling strategy has a single Deployment named after the application.
+// It is kept by helm and labelled with the color opposite to the new one, so it keeps receiving the traffic until
+// the new color is healthy.
+use crate::cloud_provider::utilities::{check_tcp_port_is_open, TcpCheckSource};
+use crate::deployment_report::utils::QPodExt;
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
+use k8s_openapi::api::core::v1::{Pod, Service};
+use kube::api::{DeleteParams, ListParams, Patch, PatchParams};
+use kube::core::{ApiResource, DynamicObject, GroupVersionKind};
+use kube::Api;
+use serde_json::json;
+use std::net::{IpAddr, SocketAddr};
+
+pub const DEPLOYMENT_COLOR_LABEL: &str = "qovery.com/deployment-color";
+const HELM_RESOURCE_POLICY_ANNOTATION: &str = "helm.sh/resource-policy";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeploymentColor {
+    Blue,
+    Green,
+}
+
+impl DeploymentColor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeploymentColor::Blue => "blue",
+            DeploymentColor::Green => "green",
+        }
+    }
+
+    pub fn other(&self) -> DeploymentColor {
+        match self {
+            DeploymentColor::Blue => DeploymentColor::Green,
+            DeploymentColor::Green => DeploymentColor::Blue,
+        }
+    }
+
+    fn from_label(value: &str) -> Option<DeploymentColor> {
+        match value {
+            "blue" => Some(DeploymentColor::Blue),
+            "green" => Some(DeploymentColor::Green),
+            _ => None,
+        }
+    }
+}
+
+pub fn colored_deployment_name(sanitized_name: &str, color: DeploymentColor) -> String {
+    format!("{}-{}", sanitized_name, color.as_str())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PodHealth {
+    pub pod_name: String,
+    pub result: Result<(), String>,
+}
+
+/// A pod is healthy once kubernetes marks it as Ready, the readiness probe being run by the kubelet,
+/// and `health_check` succeeds for it.
+pub fn check_pods_health<F>(pods: &[Pod], mut health_check: F) -> Vec<PodHealth>
+where
+    F: FnMut(&Pod) -> Result<(), String>,
+{
+    pods.iter()
+        .map(|pod| {
+            let pod_name = pod.metadata.name.clone().unwrap_or_default();
+            let result = match pod.is_ready() {
+                true => health_check(pod),
+                false => Err(pod
+                    .is_failing()
+                    .map(|reason| reason.to_string())
+                    .or_else(|| not_ready_message(pod))
+                    .unwrap_or_else(|| "pod is not ready".to_string())),
+            };
+
+            PodHealth { pod_name, result }
+        })
+        .collect()
+}
+
+/// Health check of pods without health check endpoint, their `port` must be listening.
+pub fn check_pod_tcp_port(pod: &Pod, port: u16) -> Result<(), String> {
+    let pod_ip = pod
+        .status
+        .as_ref()
+        .and_then(|status| status.pod_ip.as_ref())
+        .and_then(|ip| ip.parse::<IpAddr>().ok())
+        .ok_or_else(|| "pod has no IP assigned".to_string())?;
+
+    check_tcp_port_is_open(&TcpCheckSource::SocketAddr(SocketAddr::new(pod_ip, port)), port)
+        .map_err(|err| format!("TCP check on {}:{} failed: {:?}", pod_ip, port, err))
+}
+
+fn not_ready_message(pod: &Pod) -> Option<String> {
+    pod.status
+        .as_ref()?
+        .conditions
+        .as_ref()?
+        .iter()
+        .find(|condition| condition.type_ == "Ready")
+        .and_then(|condition| condition.message.clone())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BlueGreenDecision {
+    /// New color is healthy: the Service is switched to it and the previous color deleted.
+    SwitchTraffic,
+    /// New color is unhealthy: it is deleted and the Service keeps pointing to the previous color.
+    Rollback { reason: String },
+}
+
+pub fn blue_green_decision(pods_health: &[PodHealth]) -> BlueGreenDecision {
+    if pods_health.is_empty() {
+        return BlueGreenDecision::Rollback {
+            reason: "no pod is running the new version".to_string(),
+        };
+    }
+
+    let failures: Vec<String> = pods_health
+        .iter()
+        .filter_map(|pod| match &pod.result {
+            Ok(_) => None,
+            Err(err) => Some(format!("{}: {}", pod.pod_name, err)),
+        })
+        .collect();
+
+    match failures.is_empty() {
+        true => BlueGreenDecision::SwitchTraffic,
+        false => BlueGreenDecision::Rollback {
+            reason: failures.join(", "),
+        },
+    }
+}
+
+/// Returns the color the Service is sending the traffic to, if any.
+pub async fn get_active_color(
+    client: &kube::Client,
+    namespace: &str,
+    service_name: &str,
+) -> Result<Option<DeploymentColor>, kube::Error> {
+    let api: Api<Service> = Api::namespaced(client.clone(), namespace);
+    let service = api.get_opt(service_name).await?;
+
+    Ok(service
+        .and_then(|s| s.spec)
+        .and_then(|spec| spec.selector)
+        .and_then(|selector| selector.get(DEPLOYMENT_COLOR_LABEL).cloned())
+        .and_then(|color| DeploymentColor::from_label(&color)))
+}
+
+pub async fn get_deployment(
+    client: &kube::Client,
+    namespace: &str,
+    name: &str,
+) -> Result<Option<Deployment>, kube::Error> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+    api.get_opt(name).await
+}
+
+/// Prevents helm from deleting the Deployment of the rolling strategy and labels it, along with its running pods,
+/// with `color`, so the Service can keep sending them the traffic while the new color is deployed.
+/// Its pod template is left as is, changing it would roll out the very pods being kept.
+pub async fn keep_legacy_deployment(
+    client: &kube::Client,
+    namespace: &str,
+    name: &str,
+    color: DeploymentColor,
+) -> Result<(), kube::Error> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+    let patch = json!({
+        "metadata": {
+            "annotations": { HELM_RESOURCE_POLICY_ANNOTATION: "keep" },
+            "labels": { DEPLOYMENT_COLOR_LABEL: color.as_str() }
+        }
+    });
+    let deployment = api.patch(name, &PatchParams::default(), &Patch::Merge(patch)).await?;
+
+    let selector = deployment
+        .spec
+        .and_then(|spec| spec.selector.match_labels)
+        .unwrap_or_default()
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<String>>()
+        .join(",");
+    if selector.is_empty() {
+        return Ok(());
+    }
+
+    let pods_api: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod_patch = Patch::Merge(json!({ "metadata": { "labels": { DEPLOYMENT_COLOR_LABEL: color.as_str() } } }));
+    for pod in pods_api.list(&ListParams::default().labels(&selector)).await?.items {
+        if let Some(pod_name) = pod.metadata.name {
+            pods_api.patch(&pod_name, &PatchParams::default(), &pod_patch).await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn get_colored_pods(
+    client: &kube::Client,
+    namespace: &str,
+    selector: &str,
+    color: DeploymentColor,
+) -> Result<Vec<Pod>, kube::Error> {
+    let api: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let list_params =
+        ListParams::default().labels(&format!("{},{}={}", selector, DEPLOYMENT_COLOR_LABEL, color.as_str()));
+
+    Ok(api.list(&list_params).await?.items)
+}
+
+pub async fn switch_service_color(
+    client: &kube::Client,
+    namespace: &str,
+    service_name: &str,
+    color: DeploymentColor,
+) -> Result<(), kube::Error> {
+    let api: Api<Service> = Api::namespaced(client.clone(), namespace);
+    let patch = json!({ "spec": { "selector": { DEPLOYMENT_COLOR_LABEL: color.as_str() } } });
+    api.patch(service_name, &PatchParams::default(), &Patch::Merge(patch))
+        .await?;
+
+    Ok(())
+}
+
+/// Points the HPA or KEDA ScaledObject of the application, when there is one, to `deployment_name`.
+pub async fn switch_autoscaler_target(
+    client: &kube::Client,
+    namespace: &str,
+    autoscaler_name: &str,
+    deployment_name: &str,
+) -> Result<(), kube::Error> {
+    let patch = Patch::Merge(json!({ "spec": { "scaleTargetRef": { "name": deployment_name } } }));
+
+    let hpa_api: Api<HorizontalPodAutoscaler> = Api::namespaced(client.clone(), namespace);
+    if hpa_api.get_opt(autoscaler_name).await?.is_some() {
+        hpa_api.patch(autoscaler_name, &PatchParams::default(), &patch).await?;
+    }
+
+    // KEDA manages its own HPA, the ScaledObject is the one to update
+    let scaled_object = ApiResource::from_gvk(&GroupVersionKind::gvk("keda.sh", "v1alpha1", "ScaledObject"));
+    let scaled_object_api: Api<DynamicObject> = Api::namespaced_with(client.clone(), namespace, &scaled_object);
+    if scaled_object_api.get_opt(autoscaler_name).await?.is_some() {
+        scaled_object_api
+            .patch(autoscaler_name, &PatchParams::default(), &patch)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_deployment(client: &kube::Client, namespace: &str, name: &str) -> Result<(), kube::Error> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+    if api.get_opt(name).await?.is_some() {
+        api.delete(name, &DeleteParams::background()).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_colored_deployment(
+    client: &kube::Client,
+    namespace: &str,
+    sanitized_name: &str,
+    color: DeploymentColor,
+) -> Result<(), kube::Error> {
+    delete_deployment(client, namespace, &colored_deployment_name(sanitized_name, color)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deployment_action::blue_green::{
+        blue_green_decision, check_pods_health, keep_legacy_deployment, BlueGreenDecision, DeploymentColor, PodHealth,
+        DEPLOYMENT_COLOR_LABEL,
+    };
+    use crate::runtime::block_on;
+    use crate::test_utils::start_fake_kube_api_server;
+    use k8s_openapi::api::core::v1::{Pod, PodCondition, PodStatus};
+    use kube::api::ObjectMeta;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn pod(name: &str, ready: bool, message: Option<&str>) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            status: Some(PodStatus {
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".to_string(),
+                    status: if ready { "True" } else { "False" }.to_string(),
+                    message: message.map(|message| message.to_string()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_deployment_color_other() {
+        assert_eq!(DeploymentColor::Green, DeploymentColor::Blue.other());
+        assert_eq!(DeploymentColor::Blue, DeploymentColor::Green.other());
+    }
+
+    #[test]
+    fn test_blue_green_switches_traffic_when_new_version_is_ready() {
+        // setup:
+        let pods = vec![pod("app-green-1", true, None), pod("app-green-2", true, None)];
+        let mut checked_pods = vec![];
+
+        // execute:
+        let decision = blue_green_decision(&check_pods_health(&pods, |pod| {
+            checked_pods.push(pod.metadata.name.clone().unwrap_or_default());
+            Ok(())
+        }));
+
+        // verify:
+        assert_eq!(BlueGreenDecision::SwitchTraffic, decision);
+        assert_eq!(vec!["app-green-1", "app-green-2"], checked_pods);
+    }
+
+    #[test]
+    fn test_blue_green_rollback_when_new_version_is_not_ready() {
+        // setup: the ready pod of the new version fails its health check
+        let pods = vec![
+            pod("app-green-0", true, None),
+            pod("app-green-1", false, Some("containers with unready status: [app]")),
+            pod("app-green-2", false, None),
+            Pod {
+                metadata: ObjectMeta {
+                    name: Some("app-green-3".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ];
+
+        // execute:
+        let pods_health = check_pods_health(&pods, |_| Err("HTTP check returned 503".to_string()));
+        let decision = blue_green_decision(&pods_health);
+
+        // verify:
+        assert!(pods_health.iter().all(|pod| pod.result.is_err()));
+        match decision {
+            BlueGreenDecision::Rollback { reason } => {
+                assert!(reason.contains("app-green-0: HTTP check returned 503"));
+                assert!(reason.contains("app-green-1: containers with unready status: [app]"));
+                assert!(reason.contains("app-green-2: pod is not ready"));
+                assert!(reason.contains("app-green-3: pod is not ready"));
+            }
+            BlueGreenDecision::SwitchTraffic => panic!("traffic must not be switched to a failing version"),
+        }
+    }
+
+    #[test]
+    fn test_blue_green_rollback_when_one_pod_is_failing_or_no_pod() {
+        // setup:
+        let pods_health = vec![
+            PodHealth {
+                pod_name: "app-blue-1".to_string(),
+                result: Ok(()),
+            },
+            PodHealth {
+                pod_name: "app-blue-2".to_string(),
+                result: Err("CrashLoopBackOff".to_string()),
+            },
+        ];
+
+        // execute & verify:
+        assert_eq!(
+            BlueGreenDecision::Rollback {
+                reason: "app-blue-2: CrashLoopBackOff".to_string()
+            },
+            blue_green_decision(&pods_health)
+        );
+        assert!(matches!(blue_green_decision(&[]), BlueGreenDecision::Rollback { .. }));
+    }
+
+    #[test]
+    fn test_keep_legacy_deployment_does_not_roll_out_its_pods() {
+        // setup:
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = requests.clone();
+        let kube = start_fake_kube_api_server(move |method, path, body| {
+            received
+                .lock()
+                .unwrap()
+                .push((method.to_string(), path.to_string(), body.to_string()));
+            match (method, path) {
+                ("PATCH", "/apis/apps/v1/namespaces/env/deployments/app") => (
+                    200,
+                    json!({
+                        "apiVersion": "apps/v1",
+                        "kind": "Deployment",
+                        "metadata": { "name": "app" },
+                        "spec": { "selector": { "matchLabels": { "appId": "app-id" } }, "template": {} }
+                    })
+                    .to_string(),
+                ),
+                ("GET", _) => (
+                    200,
+                    json!({
+                        "apiVersion": "v1",
+                        "kind": "PodList",
+                        "metadata": {},
+                        "items": [{ "metadata": { "name": "app-1" } }]
+                    })
+                    .to_string(),
+                ),
+                _ => (
+                    200,
+                    json!({ "apiVersion": "v1", "kind": "Pod", "metadata": { "name": "app-1" } }).to_string(),
+                ),
+            }
+        });
+
+        // execute:
+        block_on(keep_legacy_deployment(&kube, "env", "app", DeploymentColor::Green)).unwrap();
+
+        // verify:
+        let requests = requests.lock().unwrap();
+        let (_, _, deployment_patch) = &requests[0];
+        let deployment_patch: serde_json::Value = serde_json::from_str(deployment_patch).unwrap();
+        assert_eq!(None, deployment_patch.get("spec"));
+        assert_eq!("keep", deployment_patch["metadata"]["annotations"]["helm.sh/resource-policy"]);
+        assert_eq!("green", deployment_patch["metadata"]["labels"][DEPLOYMENT_COLOR_LABEL]);
+        let (_, pods_list, _) = &requests[1];
+        assert!(pods_list.contains("labelSelector=appId%3Dapp-id"), "{}", pods_list);
+        let (method, pod_path, pod_patch) = &requests[2];
+        assert_eq!("PATCH", method);
+        assert_eq!("/api/v1/namespaces/env/pods/app-1", pod_path.split('?').next().unwrap());
+        let pod_patch: serde_json::Value = serde_json::from_str(pod_patch).unwrap();
+        assert_eq!("green", pod_patch["metadata"]["labels"][DEPLOYMENT_COLOR_LABEL]);
+    }
+}
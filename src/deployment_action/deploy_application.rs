@@ -1,31 +1,43 @@
 use crate::cloud_provider::helm::{ChartInfo, HelmAction, HelmChartNamespaces};
 use crate::cloud_provider::service::{delete_pending_service, Action, Service};
 use crate::cloud_provider::DeploymentTarget;
+use crate::deployment_action::blue_green::{
+    blue_green_decision, check_pod_tcp_port, check_pods_health, colored_deployment_name, delete_colored_deployment,
+    delete_deployment, get_active_color, get_colored_pods, get_deployment, keep_legacy_deployment,
+    switch_autoscaler_target, switch_service_color, BlueGreenDecision, DeploymentColor,
+};
 use crate::deployment_action::deploy_helm::HelmDeployment;
 use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::utils::{
+    get_failing_pods_node_issue, get_image_pull_error, rollout_restart, to_underlying_error, validate_ports,
+};
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::application::reporter::ApplicationDeploymentReporter;
 use crate::deployment_report::execute_long_deployment;
-use crate::errors::{CommandError, EngineError};
-use crate::events::{EnvironmentStep, Stage};
+use crate::errors::{CommandError, EngineError, Tag};
+use crate::events::{EnvironmentStep, EventDetails, Stage};
 use crate::kubers_utils::kube_delete_all_from_selector;
 use crate::models::application::{Application, ApplicationService};
 use crate::models::types::{CloudProvider, ToTeraContext};
 use crate::runtime::block_on;
-use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, Pod};
 
 use crate::deployment_report::logger::EnvProgressLogger;
 use std::path::PathBuf;
 use std::time::Duration;
 use tera::Context;
+use uuid::Uuid;
 
 impl<T: CloudProvider> DeploymentAction for Application<T>
 where
     Application<T>: ToTeraContext,
 {
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
-        let long_task = |_logger: &EnvProgressLogger| -> Result<(), EngineError> {
+        let long_task = |logger: &EnvProgressLogger| -> Result<(), EngineError> {
             let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+            validate_ports(self.ports(), event_details.clone())?;
+
             // If the service have been paused, we must ensure we un-pause it first as hpa will not kick in
             let _ = PauseServiceAction::new(
                 self.selector(),
@@ -45,15 +57,145 @@ where
                 ..Default::default()
             };
 
+            let namespace = target.environment.namespace();
+            let active_color = block_on(get_active_color(&target.kube, namespace, &self.sanitized_name()))
+                .map_err(|err| to_k8s_service_issue(event_details.clone(), err))?;
+
+            let sanitized_name = self.sanitized_name();
+            let mut tera_context = self.to_tera_context(target)?;
+            let mut serving_deployment: Option<String> = None;
+            let new_color = match self.is_blue_green() {
+                true => {
+                    let new_color = active_color.map(|color| color.other()).unwrap_or(DeploymentColor::Blue);
+                    // Deployment of the rolling strategy, it keeps receiving the traffic until the new color is healthy
+                    let legacy_deployment = block_on(get_deployment(&target.kube, namespace, &sanitized_name))
+                        .map_err(|err| to_k8s_service_issue(event_details.clone(), err))?;
+                    let serving_color = match legacy_deployment {
+                        Some(_) => {
+                            let legacy_color = active_color.unwrap_or_else(|| new_color.other());
+                            if !target.is_dry_run_deploy {
+                                keep_legacy_deployment_serving(
+                                    target,
+                                    &sanitized_name,
+                                    legacy_color,
+                                    event_details.clone(),
+                                )?;
+                            }
+                            serving_deployment = Some(sanitized_name.clone());
+                            Some(legacy_color)
+                        }
+                        None => {
+                            serving_deployment =
+                                active_color.map(|color| colored_deployment_name(&sanitized_name, color));
+                            active_color
+                        }
+                    };
+
+                    logger.info(format!(
+                        "🔵🟢 Deploying new version as {}, traffic is switched to it once healthy",
+                        new_color.as_str()
+                    ));
+                    let new_deployment_name = colored_deployment_name(&sanitized_name, new_color);
+                    tera_context.insert("deployment_name", &new_deployment_name);
+                    tera_context.insert("blue_green_color", new_color.as_str());
+                    // Service and autoscaler are switched to the new color only once it is healthy
+                    tera_context.insert("blue_green_active_color", serving_color.unwrap_or(new_color).as_str());
+                    tera_context.insert(
+                        "autoscaler_target_name",
+                        serving_deployment.as_ref().unwrap_or(&new_deployment_name),
+                    );
+                    Some(new_color)
+                }
+                false => None,
+            };
+            if self.is_autoscaled() {
+                match hpa_setup(
+                    check_metrics_api(&target.kube),
+                    self.advanced_settings().hpa_required,
+                    self.min_instances(),
+                    event_details.clone(),
+                )? {
+                    HpaSetup::Autoscaling => {}
+                    HpaSetup::StaticReplicas { warning } => {
+                        logger.warning(warning);
+                        tera_context.insert("hpa_enabled", &false);
+                    }
+                }
+            }
+
             let helm = HelmDeployment::new(
                 event_details.clone(),
-                self.to_tera_context(target)?,
+                tera_context,
                 PathBuf::from(self.helm_chart_dir()),
                 None,
                 chart,
             );
 
-            helm.on_create(target)?;
+            if let Err(err) = helm.on_create(target) {
+                // pods not ready before the timeout: tell why they can't start instead of a generic startup failure,
+                // other errors (i.e: cancellation) are returned as is.
+                // Pods are inspected before the new color is deleted, as its pods go away with it
+                let err = if err.tag() != &Tag::HelmDeployTimeout {
+                    err
+                } else {
+                    match block_on(get_failing_pods_node_issue(target.kube.clone(), namespace, &self.selector())) {
+                        Ok(Some(issue)) => EngineError::new_k8s_node_not_ready_with_condition(
+                            event_details,
+                            issue.node_name,
+                            issue.condition,
+                            issue.message,
+                            Some(to_underlying_error(&err)),
+                        ),
+                        _ => get_image_pull_error(
+                            target,
+                            &self.selector(),
+                            event_details.clone(),
+                            self.long_id(),
+                            self.name(),
+                        )
+                        .unwrap_or_else(|| to_startup_error(err, event_details, self.long_id(), self.name())),
+                    }
+                };
+
+                if let Some(new_color) = new_color {
+                    let _ = block_on(delete_colored_deployment(
+                        &target.kube,
+                        namespace,
+                        &self.sanitized_name(),
+                        new_color,
+                    ));
+                }
+
+                return Err(err);
+            }
+
+            // the chart has only been diffed, there are no new pods to switch the traffic to nor to clean up
+            if target.is_dry_run_deploy {
+                return Ok(());
+            }
+
+            match new_color {
+                Some(new_color) => switch_or_rollback(
+                    self,
+                    target,
+                    logger,
+                    serving_deployment.as_deref(),
+                    new_color,
+                    event_details.clone(),
+                )?,
+                // application was previously deployed with the blue/green strategy, its last color has been kept by helm
+                None => {
+                    if let Some(active_color) = active_color {
+                        block_on(delete_colored_deployment(
+                            &target.kube,
+                            namespace,
+                            &self.sanitized_name(),
+                            active_color,
+                        ))
+                        .map_err(|err| to_k8s_service_issue(event_details.clone(), err))?;
+                    }
+                }
+            }
 
             delete_pending_service(
                 target.kubernetes.get_kubeconfig_file_path()?.as_str(),
@@ -66,7 +208,11 @@ where
             Ok(())
         };
 
-        execute_long_deployment(ApplicationDeploymentReporter::new(self, target, Action::Create), long_task)
+        execute_long_deployment(
+            ApplicationDeploymentReporter::new(self, target, Action::Create),
+            long_task,
+            &target.cancellation_token,
+        )
     }
 
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
@@ -81,6 +227,32 @@ where
                 );
                 pause_service.on_pause(target)
             },
+            &target.cancellation_token,
+        )
+    }
+
+    fn on_resume(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        PauseServiceAction::new(
+            self.selector(),
+            self.is_stateful(),
+            Duration::from_secs(5 * 60),
+            self.get_event_details(Stage::Environment(EnvironmentStep::Pause)),
+        )
+        .scale_up_if_paused(target, self.min_instances().max(1))
+    }
+
+    fn on_restart(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        execute_long_deployment(
+            ApplicationDeploymentReporter::new(self, target, Action::Restart),
+            |_logger: &EnvProgressLogger| -> Result<(), EngineError> {
+                rollout_restart(
+                    target,
+                    &self.selector(),
+                    self.is_stateful(),
+                    self.get_event_details(Stage::Environment(EnvironmentStep::Deploy)),
+                )
+            },
+            &target.cancellation_token,
         )
     }
 
@@ -107,6 +279,23 @@ where
 
                 helm.on_delete(target)?;
 
+                if target.is_dry_run_deploy {
+                    target.log_dry_run(
+                        &event_details,
+                        "deletion of deployments, volumes and container registry of the application".to_string(),
+                    );
+                    return Ok(());
+                }
+
+                // Deployments of the blue/green strategy are kept by helm, they must be deleted explicitly
+                if let Err(err) = block_on(kube_delete_all_from_selector::<Deployment>(
+                    &target.kube,
+                    &self.selector(),
+                    target.environment.namespace(),
+                )) {
+                    return Err(to_k8s_service_issue(event_details.clone(), err));
+                }
+
                 // Delete pvc of statefulset if needed
                 // FIXME: Remove this after kubernetes 1.23 is deployed, at it should be done by kubernetes
                 if self.is_stateful() {
@@ -141,6 +330,415 @@ where
 
                 Ok(())
             },
+            &target.cancellation_token,
         )
     }
 }
+
+// Helm waits for the pods to be ready for the whole startup timeout, which includes the startup grace period,
+// timing out means the application didn't manage to start.
+// The helm error, telling which resources were still not ready, is kept as underlying error.
+fn to_startup_error(
+    err: EngineError,
+    event_details: EventDetails,
+    service_id: &Uuid,
+    service_name: &str,
+) -> EngineError {
+    match err.tag() {
+        Tag::HelmDeployTimeout => EngineError::new_client_service_failed_to_start_error(
+            event_details,
+            service_id.to_string(),
+            service_name.to_string(),
+            Some(to_underlying_error(&err)),
+        ),
+        _ => err,
+    }
+}
+
+fn to_k8s_service_issue(event_details: EventDetails, err: kube::Error) -> EngineError {
+    EngineError::new_k8s_service_issue(event_details, CommandError::new_from_safe_message(err.to_string()))
+}
+
+// Labels the rolling strategy Deployment and its pods with `color`, so the Service can select them
+fn keep_legacy_deployment_serving(
+    target: &DeploymentTarget,
+    deployment_name: &str,
+    color: DeploymentColor,
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    let namespace = target.environment.namespace();
+    block_on(keep_legacy_deployment(&target.kube, namespace, deployment_name, color))
+        .map_err(|err| to_k8s_service_issue(event_details, err))
+}
+
+// Pods of the new color must be ready, and pass the service health check when it has one,
+// or have `tcp_port` listening otherwise. Without both, the readiness of the pods is the only check
+fn new_color_decision(
+    service: &dyn Service,
+    tcp_port: Option<u16>,
+    kube: &kube::Client,
+    namespace: &str,
+    pods: &[Pod],
+) -> BlueGreenDecision {
+    let health_check = service.health_check();
+    blue_green_decision(&check_pods_health(pods, |pod| match (&health_check, tcp_port) {
+        (Some(health_check), _) => {
+            block_on(health_check.run(kube, namespace, pod.metadata.name.as_deref().unwrap_or_default()))
+        }
+        (None, Some(port)) => check_pod_tcp_port(pod, port),
+        (None, None) => Ok(()),
+    }))
+}
+
+// Check the pods of the new color are ready, then either send the traffic to them or delete them
+fn switch_or_rollback<T: CloudProvider>(
+    app: &Application<T>,
+    target: &DeploymentTarget,
+    logger: &EnvProgressLogger,
+    serving_deployment: Option<&str>,
+    new_color: DeploymentColor,
+    event_details: EventDetails,
+) -> Result<(), EngineError>
+where
+    Application<T>: ToTeraContext,
+{
+    let namespace = target.environment.namespace();
+    let sanitized_name = app.sanitized_name();
+    let pods = block_on(get_colored_pods(&target.kube, namespace, &app.selector(), new_color))
+        .map_err(|err| to_k8s_service_issue(event_details.clone(), err))?;
+
+    match new_color_decision(app, app.default_port(), &target.kube, namespace, &pods) {
+        BlueGreenDecision::SwitchTraffic => {
+            logger.info(format!(
+                "🔀 New version is healthy, switching traffic to {}",
+                new_color.as_str()
+            ));
+            block_on(switch_service_color(&target.kube, namespace, &sanitized_name, new_color))
+                .map_err(|err| to_k8s_service_issue(event_details.clone(), err))?;
+            block_on(switch_autoscaler_target(
+                &target.kube,
+                namespace,
+                &sanitized_name,
+                &colored_deployment_name(&sanitized_name, new_color),
+            ))
+            .map_err(|err| to_k8s_service_issue(event_details.clone(), err))?;
+            if let Some(serving_deployment) = serving_deployment {
+                block_on(delete_deployment(&target.kube, namespace, serving_deployment))
+                    .map_err(|err| to_k8s_service_issue(event_details, err))?;
+            }
+            Ok(())
+        }
+        BlueGreenDecision::Rollback { reason } => {
+            logger.warning(format!(
+                "⏪ New version is not healthy, rolling back to the previous version: {}",
+                reason
+            ));
+            let _ = block_on(delete_colored_deployment(&target.kube, namespace, &sanitized_name, new_color));
+            Err(EngineError::new_k8s_blue_green_rollback(
+                event_details,
+                colored_deployment_name(&sanitized_name, new_color),
+                reason,
+            ))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum HpaSetup {
+    Autoscaling,
+    StaticReplicas { warning: String },
+}
+
+// The HPA relies on the metrics API (metrics-server) to get pods CPU usage
+fn check_metrics_api(kube: &kube::Client) -> Result<(), CommandError> {
+    match block_on(kube.list_api_group_resources("metrics.k8s.io/v1beta1")) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(CommandError::new(
+            "Kubernetes metrics API is not available".to_string(),
+            Some(err.to_string()),
+            None,
+        )),
+    }
+}
+
+// Without the metrics API, the HPA can't scale the application, so unless the user requires it,
+// the application is deployed with its min instances instead of failing the whole deployment
+fn hpa_setup(
+    metrics_api: Result<(), CommandError>,
+    hpa_required: bool,
+    min_instances: u32,
+    event_details: EventDetails,
+) -> Result<HpaSetup, EngineError> {
+    match metrics_api {
+        Ok(_) => Ok(HpaSetup::Autoscaling),
+        Err(err) if hpa_required => Err(EngineError::new_cannot_get_k8s_api_custom_metrics(event_details, err)),
+        Err(_) => Ok(HpaSetup::StaticReplicas {
+            warning: format!(
+                "⚠️ Kubernetes metrics API is not available, autoscaling is disabled and the application is deployed with {} instance(s). Set `hpa.required` to fail the deployment instead",
+                min_instances
+            ),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::service::{
+        Action, HealthCheck, HealthCheckProtocol, Service, ServiceType, HTTP_PROBE_SUCCESS_STATUSES,
+    };
+    use crate::cmd::helm::{HelmCommand, HelmError};
+    use crate::deployment_action::blue_green::BlueGreenDecision;
+    use crate::deployment_action::deploy_application::{hpa_setup, new_color_decision, to_startup_error, HpaSetup};
+    use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity, Tag};
+    use crate::events::{EventDetails, Stage};
+    use crate::test_utils::start_fake_kube_api_server;
+    use crate::test_utils::EventDetailsBuilder;
+    use k8s_openapi::api::core::v1::{Pod, PodCondition, PodStatus};
+    use kube::api::ObjectMeta;
+    use std::net::TcpListener;
+    use uuid::Uuid;
+
+    struct FakeService {
+        long_id: Uuid,
+        health_check: Option<HealthCheck>,
+    }
+
+    impl Service for FakeService {
+        fn service_type(&self) -> ServiceType {
+            ServiceType::Application
+        }
+
+        fn id(&self) -> &str {
+            "app"
+        }
+
+        fn long_id(&self) -> &Uuid {
+            &self.long_id
+        }
+
+        fn name(&self) -> &str {
+            "app"
+        }
+
+        fn sanitized_name(&self) -> String {
+            "app".to_string()
+        }
+
+        fn get_event_details(&self, _stage: Stage) -> EventDetails {
+            EventDetailsBuilder::new().build()
+        }
+
+        fn action(&self) -> &Action {
+            &Action::Create
+        }
+
+        fn selector(&self) -> Option<String> {
+            None
+        }
+
+        fn health_check(&self) -> Option<HealthCheck> {
+            self.health_check.clone()
+        }
+
+        fn as_service(&self) -> &dyn Service {
+            self
+        }
+    }
+
+    fn ready_pod(name: &str) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            status: Some(PodStatus {
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".to_string(),
+                    status: "True".to_string(),
+                    ..Default::default()
+                }]),
+                pod_ip: Some("127.0.0.1".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_new_color_decision_runs_the_service_health_check() {
+        // setup: pods are reached through the API server pod proxy, the TCP check would fail if it was run instead
+        let unused_port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("cannot bind a local port");
+            listener.local_addr().unwrap().port()
+        };
+        let service = FakeService {
+            long_id: Uuid::new_v4(),
+            health_check: Some(HealthCheck {
+                protocol: HealthCheckProtocol::Http,
+                path: "/health".to_string(),
+                port: 8080,
+                expected_status: HTTP_PROBE_SUCCESS_STATUSES,
+            }),
+        };
+        let kube = |status: u16| {
+            start_fake_kube_api_server(move |_, path, _| match path {
+                "/api/v1/namespaces/my-namespace/pods/http:app-green-1:8080/proxy/health" => (status, "".to_string()),
+                _ => (404, "".to_string()),
+            })
+        };
+        let pods = vec![ready_pod("app-green-1")];
+
+        // execute & verify:
+        assert_eq!(
+            BlueGreenDecision::SwitchTraffic,
+            new_color_decision(&service, Some(unused_port), &kube(200), "my-namespace", &pods)
+        );
+        match new_color_decision(&service, Some(unused_port), &kube(503), "my-namespace", &pods) {
+            BlueGreenDecision::Rollback { reason } => assert!(reason.contains("returned 503")),
+            BlueGreenDecision::SwitchTraffic => panic!("traffic must not be switched to a failing version"),
+        }
+    }
+
+    #[test]
+    fn test_new_color_decision_falls_back_to_tcp_check() {
+        // setup:
+        let kube = start_fake_kube_api_server(|_, _, _| (503, "".to_string()));
+        let listener = TcpListener::bind("127.0.0.1:0").expect("cannot bind a local port");
+        let listening_port = listener.local_addr().unwrap().port();
+        let unused_port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("cannot bind a local port");
+            listener.local_addr().unwrap().port()
+        };
+        let service = FakeService {
+            long_id: Uuid::new_v4(),
+            health_check: None,
+        };
+        let pods = vec![ready_pod("app-green-1")];
+
+        // execute & verify:
+        assert_eq!(
+            BlueGreenDecision::SwitchTraffic,
+            new_color_decision(&service, Some(listening_port), &kube, "my-namespace", &pods)
+        );
+        match new_color_decision(&service, Some(unused_port), &kube, "my-namespace", &pods) {
+            BlueGreenDecision::Rollback { reason } => assert!(reason.contains("TCP check")),
+            BlueGreenDecision::SwitchTraffic => panic!("traffic must not be switched to a failing version"),
+        }
+    }
+
+    #[test]
+    fn test_new_color_decision_without_health_check_nor_port_relies_on_pods_readiness() {
+        // setup: any request to the API server would fail the health check
+        let kube = start_fake_kube_api_server(|_, _, _| (503, "".to_string()));
+        let service = FakeService {
+            long_id: Uuid::new_v4(),
+            health_check: None,
+        };
+        let mut not_ready_pod = ready_pod("app-green-2");
+        not_ready_pod.status = None;
+
+        // execute & verify:
+        assert_eq!(
+            BlueGreenDecision::SwitchTraffic,
+            new_color_decision(&service, None, &kube, "my-namespace", &[ready_pod("app-green-1")])
+        );
+        match new_color_decision(
+            &service,
+            None,
+            &kube,
+            "my-namespace",
+            &[ready_pod("app-green-1"), not_ready_pod],
+        ) {
+            BlueGreenDecision::Rollback { reason } => assert_eq!("app-green-2: pod is not ready", reason),
+            BlueGreenDecision::SwitchTraffic => panic!("traffic must not be switched to a failing version"),
+        }
+    }
+
+    #[test]
+    fn test_hpa_setup_metrics_api_available() {
+        // execute:
+        let result = hpa_setup(Ok(()), true, 2, EventDetailsBuilder::new().build());
+
+        // verify:
+        assert_eq!(Some(HpaSetup::Autoscaling), result.ok());
+    }
+
+    #[test]
+    fn test_hpa_setup_metrics_api_unavailable_falls_back_to_min_instances() {
+        // setup:
+        let metrics_api = Err(CommandError::new_from_safe_message(
+            "Kubernetes metrics API is not available".to_string(),
+        ));
+
+        // execute:
+        let result = hpa_setup(metrics_api, false, 2, EventDetailsBuilder::new().build());
+
+        // verify:
+        match result {
+            Ok(HpaSetup::StaticReplicas { warning }) => assert!(warning.contains("deployed with 2 instance(s)")),
+            _ => panic!("expected the deployment to proceed without HPA"),
+        }
+    }
+
+    #[test]
+    fn test_hpa_setup_metrics_api_unavailable_and_hpa_required() {
+        // setup:
+        let metrics_api = Err(CommandError::new_from_safe_message(
+            "Kubernetes metrics API is not available".to_string(),
+        ));
+
+        // execute:
+        let result = hpa_setup(metrics_api, true, 2, EventDetailsBuilder::new().build());
+
+        // verify:
+        assert_eq!(
+            Some(Tag::CannotExecuteK8sApiCustomMetrics),
+            result.err().map(|e| e.tag().clone())
+        );
+    }
+
+    #[test]
+    fn test_to_startup_error() {
+        // setup:
+        let service_id = Uuid::new_v4();
+        let test_cases = vec![
+            (
+                HelmError::Timeout(
+                    "app".to_string(),
+                    HelmCommand::UPGRADE,
+                    "timed out".to_string(),
+                    vec!["Deployment/app".to_string()],
+                ),
+                Tag::ClientServiceFailedToStart,
+                "Resources still not ready: Deployment/app",
+            ),
+            (
+                HelmError::CmdError(
+                    "app".to_string(),
+                    HelmCommand::UPGRADE,
+                    CommandError::new_from_safe_message("error".to_string()),
+                ),
+                Tag::HelmChartsDeployError,
+                "error",
+            ),
+        ];
+
+        for (helm_error, expected_tag, expected_underlying_error) in test_cases {
+            // execute:
+            let err = to_startup_error(
+                EngineError::new_helm_error(EventDetailsBuilder::new().build(), helm_error),
+                EventDetailsBuilder::new().build(),
+                &service_id,
+                "app",
+            );
+
+            // verify:
+            assert_eq!(&expected_tag, err.tag());
+            assert!(err
+                .underlying_error()
+                .expect("helm error must be kept as underlying error")
+                .message(ErrorMessageVerbosity::FullDetails)
+                .contains(expected_underlying_error));
+        }
+    }
+}
@@ -6,7 +6,7 @@ use crate::deployment_action::pause_service::PauseServiceAction;
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::application::reporter::ApplicationDeploymentReporter;
 use crate::deployment_report::{execute_long_deployment, DeploymentTaskImpl};
-use crate::errors::{CommandError, EngineError};
+use crate::errors::{CommandError, EngineError, Tag};
 use crate::events::{EnvironmentStep, Stage};
 use crate::kubers_utils::kube_delete_all_from_selector;
 use crate::models::container::{Container, ContainerService};
@@ -14,7 +14,9 @@ use crate::models::types::{CloudProvider, ToTeraContext};
 use crate::runtime::block_on;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
 
-use crate::deployment_action::utils::{delete_cached_image, get_last_deployed_image, mirror_image, KubeObjectKind};
+use crate::deployment_action::utils::{
+    delete_cached_image, get_image_pull_error, get_last_deployed_image, mirror_image, validate_ports, KubeObjectKind,
+};
 use crate::deployment_report::logger::{EnvProgressLogger, EnvSuccessLogger};
 use std::path::PathBuf;
 use std::time::Duration;
@@ -31,6 +33,7 @@ where
 
         // We first mirror the image if needed
         let pre_task = |logger: &EnvProgressLogger| -> Result<TaskContext, EngineError> {
+            validate_ports(self.ports(), event_details.clone())?;
             mirror_image(
                 &self.registry,
                 &self.image,
@@ -85,7 +88,19 @@ where
                 chart,
             );
 
-            helm.on_create(target)?;
+            // a mirrored image which can't be pulled is reported as such instead of a helm timeout,
+            // other errors (i.e: cancellation) are returned as is
+            helm.on_create(target).map_err(|err| match err.tag() {
+                Tag::HelmDeployTimeout => {
+                    get_image_pull_error(target, &self.selector(), event_details.clone(), self.long_id(), self.name())
+                        .unwrap_or(err)
+                }
+                _ => err,
+            })?;
+
+            if target.is_dry_run_deploy {
+                return Ok(state);
+            }
 
             delete_pending_service(
                 target.kubernetes.get_kubeconfig_file_path()?.as_str(),
@@ -100,11 +115,18 @@ where
 
         let post_task = |logger: &EnvSuccessLogger, state: TaskContext| {
             // Delete previous image from cache to cleanup resources
-            let _ = delete_cached_image(self.tag_for_mirror(), state.last_deployed_image, false, target, logger)
-                .map_err(|err| {
-                    error!("Error while deleting cached image: {}", err);
-                    EngineError::new_container_registry_error(event_details.clone(), err)
-                });
+            let _ = delete_cached_image(
+                self.tag_for_mirror(),
+                state.last_deployed_image,
+                false,
+                target,
+                logger,
+                &event_details,
+            )
+            .map_err(|err| {
+                error!("Error while deleting cached image: {}", err);
+                EngineError::new_container_registry_error(event_details.clone(), err)
+            });
         };
 
         // At last we deploy our container
@@ -114,7 +136,9 @@ where
                 pre_run: &pre_task,
                 run: &long_task,
                 post_run_success: &post_task,
+                post_run_failure: None,
             },
+            &target.cancellation_token,
         )
     }
 
@@ -130,7 +154,18 @@ where
                 );
                 pause_service.on_pause(target)
             },
+            &target.cancellation_token,
+        )
+    }
+
+    fn on_resume(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        PauseServiceAction::new(
+            self.selector(),
+            self.is_stateful(),
+            Duration::from_secs(5 * 60),
+            self.get_event_details(Stage::Environment(EnvironmentStep::Pause)),
         )
+        .scale_up_if_paused(target, self.min_instances().max(1))
     }
 
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
@@ -176,6 +211,11 @@ where
 
             helm.on_delete(target)?;
 
+            if target.is_dry_run_deploy {
+                target.log_dry_run(&event_details, "deletion of volumes of the container".to_string());
+                return Ok(state);
+            }
+
             // Delete pvc of statefulset if needed
             // FIXME: Remove this after kubernetes 1.23 is deployed, at it should be done by kubernetes
             if self.is_stateful() {
@@ -206,10 +246,11 @@ where
             };
 
             let _ =
-                delete_cached_image(self.tag_for_mirror(), last_deployed_image, true, target, logger).map_err(|err| {
-                    error!("Error while deleting cached image: {}", err);
-                    EngineError::new_container_registry_error(event_details.clone(), err)
-                });
+                delete_cached_image(self.tag_for_mirror(), last_deployed_image, true, target, logger, &event_details)
+                    .map_err(|err| {
+                        error!("Error while deleting cached image: {}", err);
+                        EngineError::new_container_registry_error(event_details.clone(), err)
+                    });
         };
 
         // Trigger deployment
@@ -219,7 +260,9 @@ where
                 pre_run: &pre_task,
                 run: &long_task,
                 post_run_success: &post_task,
+                post_run_failure: None,
             },
+            &target.cancellation_token,
         )
     }
 }
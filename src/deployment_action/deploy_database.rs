@@ -1,5 +1,6 @@
 use crate::cloud_provider::helm::{ChartInfo, ChartSetValue, HelmAction, HelmChartNamespaces};
 use crate::cloud_provider::service::{delete_pending_service, get_database_terraform_config, Action, Service};
+use crate::cloud_provider::utilities::TCP_CHECK_DEFAULT_TIMEOUT;
 use crate::cloud_provider::Kind::Aws;
 use crate::cloud_provider::{service, DeploymentTarget};
 use crate::cmd;
@@ -9,25 +10,46 @@ use crate::deployment_action::check_dns::CheckDnsForDomains;
 use crate::deployment_action::deploy_helm::HelmDeployment;
 use crate::deployment_action::deploy_terraform::TerraformDeployment;
 use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::utils::rollout_restart;
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::database::reporter::DatabaseDeploymentReporter;
 use crate::deployment_report::{execute_long_deployment, DeploymentTaskImpl};
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
+use crate::io_models::database::DatabaseOptions;
 use crate::kubers_utils::kube_delete_all_from_selector;
-use crate::models::database::{Container, Database, DatabaseService, DatabaseType, Managed};
+use crate::models::database::{
+    managed_database_service_names, Container, Database, DatabaseService, DatabaseType, Managed,
+};
 use crate::models::types::{CloudProvider, ToTeraContext};
+use crate::poller::{poll_until, ExponentialBackoff, PollError, PollState};
 use crate::runtime::block_on;
-use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, Pod};
+use kube::api::ListParams;
+use kube::Api;
 use serde::Deserialize;
 
 use crate::deployment_report::logger::{EnvProgressLogger, EnvSuccessLogger};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 const DB_READY_STATE: &str = "available";
 const DB_STOPPED_STATE: &str = "stopped";
+const DB_CONNECTION_READY_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+// Postgres frontend/backend protocol version 3.0
+const POSTGRES_PROTOCOL_VERSION: i32 = 196608;
+const MYSQL_PROTOCOL_VERSION: u8 = 10;
+const MYSQL_ERROR_PACKET: u8 = 0xff;
+const MONGODB_OP_MSG: i32 = 2013;
 
 #[derive(Deserialize, Default)]
 struct CacheCluster {
@@ -136,6 +158,36 @@ fn get_managed_database_status(
     }
 }
 
+/// State of a managed database, as reported by the cloud provider.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DatabaseState {
+    Available,
+    Starting,
+    Stopping,
+    Stopped,
+    Other(String),
+}
+
+impl DatabaseState {
+    fn from_status(status: &str) -> DatabaseState {
+        match status {
+            DB_READY_STATE => DatabaseState::Available,
+            "starting" => DatabaseState::Starting,
+            "stopping" => DatabaseState::Stopping,
+            DB_STOPPED_STATE => DatabaseState::Stopped,
+            _ => DatabaseState::Other(status.to_string()),
+        }
+    }
+}
+
+fn current_state(
+    db_type: service::DatabaseType,
+    db_id: &str,
+    credentials: &[(&str, &str)],
+) -> Result<DatabaseState, (cmd::command::CommandError, String)> {
+    get_managed_database_status(db_type, db_id, credentials).map(|status| DatabaseState::from_status(&status))
+}
+
 /// We can have different cache_cluster_id patterns according to managed redis version:
 /// - v5: "z${db_id}"
 /// - v6 created before 2022-21-07: "z${db_id}"
@@ -233,21 +285,178 @@ fn await_db_state(
     state: &str,
 ) -> Result<(), Option<(cmd::command::CommandError, String)>> {
     // Wait for the database to be in given state
-    let now = Instant::now();
-    loop {
-        if now.elapsed() >= timeout {
-            break Err(None);
+    poll_until(
+        || match get_managed_database_status(db_type, db_id, credentials) {
+            Ok(status) if status == state => Ok(PollState::Ready(())),
+            Ok(_) => Ok(PollState::Pending),
+            Err(err) => Err(err),
+        },
+        ExponentialBackoff::new(Duration::from_secs(10), Duration::from_secs(60)),
+        timeout,
+    )
+    .map_err(|err| match err {
+        PollError::Timeout { .. } => None,
+        PollError::Operation(err) => Some(err),
+    })
+}
+
+/// Stops the managed database, unless it is already stopped, and waits for it to be stopped.
+/// Returns the state of the database before pausing it.
+fn pause_managed_database(
+    current_state: impl Fn() -> Result<DatabaseState, (cmd::command::CommandError, String)>,
+    stop: impl Fn() -> Result<(), (cmd::command::CommandError, String)>,
+    await_stopped: impl Fn() -> Result<(), Option<(cmd::command::CommandError, String)>>,
+    event_details: &EventDetails,
+) -> Result<DatabaseState, EngineError> {
+    // If the state can't be retrieved, we try to stop the database anyway
+    let state = current_state().ok();
+    match state {
+        Some(DatabaseState::Stopped) => return Ok(DatabaseState::Stopped),
+        // Stopping is already in progress, asking for it again would fail
+        Some(DatabaseState::Stopping) => {}
+        _ => stop().map_err(|(cmd_error, msg)| {
+            EngineError::new_cannot_pause_managed_database(
+                event_details.clone(),
+                CommandError::new_from_legacy_command_error(cmd_error, Some(msg)),
+            )
+        })?,
+    }
+
+    match await_stopped() {
+        Ok(_) => Ok(state.unwrap_or_else(|| DatabaseState::Other("unknown".to_string()))),
+        // timeout
+        Err(None) => Err(EngineError::new_cannot_pause_managed_database(
+            event_details.clone(),
+            CommandError::new_from_safe_message(format!(
+                "Timeout reached waiting for the database to be in {} state",
+                DB_STOPPED_STATE
+            )),
+        )),
+        // Error ;'(
+        Err(Some((cmd_err, msg))) => Err(EngineError::new_cannot_pause_managed_database(
+            event_details.clone(),
+            CommandError::new_from_legacy_command_error(cmd_err, Some(msg)),
+        )),
+    }
+}
+
+/// Starts the managed database, unless it is already available, and waits for it to be available.
+fn resume_managed_database(
+    current_state: impl Fn() -> Result<DatabaseState, (cmd::command::CommandError, String)>,
+    start: impl Fn() -> Result<(), (cmd::command::CommandError, String)>,
+    await_available: impl Fn() -> Result<(), Option<(cmd::command::CommandError, String)>>,
+    db_id: &str,
+    db_type: service::DatabaseType,
+    event_details: &EventDetails,
+) -> Result<(), EngineError> {
+    let to_engine_error = |command_error: CommandError| {
+        EngineError::new_database_failed_to_start_after_several_retries(
+            event_details.clone(),
+            db_id.to_string(),
+            db_type,
+            Some(command_error),
+        )
+    };
+
+    // If the state can't be retrieved, we try to start the database anyway
+    match current_state().ok() {
+        Some(DatabaseState::Available) => return Ok(()),
+        // Starting is already in progress, asking for it again would fail
+        Some(DatabaseState::Starting) => {}
+        _ => start().map_err(|(cmd_error, msg)| {
+            to_engine_error(CommandError::new_from_legacy_command_error(cmd_error, Some(msg)))
+        })?,
+    }
+
+    match await_available() {
+        Ok(_) => Ok(()),
+        // timeout
+        Err(None) => Err(to_engine_error(CommandError::new_from_safe_message(format!(
+            "Timeout reached waiting for the database to be in {} state",
+            DB_READY_STATE
+        )))),
+        // Error ;'(
+        Err(Some((cmd_err, msg))) => {
+            Err(to_engine_error(CommandError::new_from_legacy_command_error(cmd_err, Some(msg))))
         }
+    }
+}
+
+fn connection_pooler_chart<C: CloudProvider, T: DatabaseType<C, Managed, DatabaseOptions = DatabaseOptions>>(
+    db: &Database<C, Managed, T>,
+    event_details: EventDetails,
+    target: &DeploymentTarget,
+    values: Vec<ChartSetValue>,
+    values_files: Vec<String>,
+    action: HelmAction,
+) -> HelmDeployment {
+    let chart = ChartInfo {
+        name: format!("{}-pgbouncer", db.fqdn_id),
+        path: format!("{}/{}", db.workspace_directory(), "pgbouncer-chart"),
+        namespace: HelmChartNamespaces::Custom,
+        custom_namespace: Some(target.environment.namespace().to_string()),
+        action,
+        values,
+        values_files,
+        ..Default::default()
+    };
+
+    HelmDeployment::new(
+        event_details,
+        tera::Context::default(),
+        PathBuf::from(db.helm_chart_connection_pooler_dir()),
+        None,
+        chart,
+    )
+}
+
+/// Connection pooler credentials values file written in clear for helm, it is deleted once dropped.
+struct ConnectionPoolerCredentialsFile {
+    path: PathBuf,
+}
 
-        match get_managed_database_status(db_type, db_id, credentials) {
-            Ok(status) if status == state => break Ok(()),
-            Ok(_) => thread::sleep(Duration::from_secs(30)),
-            Err(err) => break Err(Some(err)),
+impl Drop for ConnectionPoolerCredentialsFile {
+    fn drop(&mut self) {
+        match fs::remove_file(&self.path) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => error!("Cannot delete connection pooler credentials file: {}", err),
         }
     }
 }
 
-fn on_create_managed_impl<C: CloudProvider, T: DatabaseType<C, Managed>>(
+/// Writes the connection pooler credentials into a values file readable only by us,
+/// as values given with `--set` end up in the helm command line.
+fn write_connection_pooler_credentials(
+    path: &Path,
+    username: &str,
+    password: &str,
+) -> Result<ConnectionPoolerCredentialsFile, CommandError> {
+    let to_command_error = |err: String| {
+        CommandError::new(
+            format!("Cannot write connection pooler credentials to {}", path.to_string_lossy()),
+            Some(err),
+            None,
+        )
+    };
+    let content = serde_yaml::to_string(&BTreeMap::from([("username", username), ("password", password)]))
+        .map_err(|err| to_command_error(err.to_string()))?;
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|err| to_command_error(err.to_string()))?;
+
+    Ok(ConnectionPoolerCredentialsFile {
+        path: path.to_path_buf(),
+    })
+}
+
+fn on_create_managed_impl<C: CloudProvider, T: DatabaseType<C, Managed, DatabaseOptions = DatabaseOptions>>(
     db: &Database<C, Managed, T>,
     event_details: EventDetails,
     target: &DeploymentTarget,
@@ -269,16 +478,32 @@ where
     );
     terraform_deploy.on_create(target)?;
 
+    // database has only been planned, its config file required by the next steps doesn't exist
+    if target.is_dry_run_deploy {
+        return Ok(());
+    }
+
     // Our terrraform give us back a file with all the info we need to deploy the remaining stuff
     let database_config =
         get_database_terraform_config(format!("{}/database-tf-config.json", &workspace_dir,).as_str())
             .map_err(|err| EngineError::new_terraform_error(event_details.clone(), err))?;
 
+    // When a connection pooler is enabled, it takes over the service name used by applications
+    let service_names =
+        managed_database_service_names(&database_config.target_fqdn_id, db.options.connection_pooler.is_some());
+
+    // Pooler may have been disabled since last deployment, release its service name before the external one takes it.
+    // Helm uninstall is a no-op if the pooler has never been deployed
+    if service_names.connection_pooler_service.is_none() {
+        connection_pooler_chart(db, event_details.clone(), target, vec![], vec![], HelmAction::Destroy)
+            .on_delete(target)?;
+    }
+
     // Deploy the external service name
     let values = vec![
         ChartSetValue {
             key: "target_hostname".to_string(),
-            value: database_config.target_hostname,
+            value: database_config.target_hostname.clone(),
         },
         ChartSetValue {
             key: "source_fqdn".to_string(),
@@ -306,7 +531,7 @@ where
         },
         ChartSetValue {
             key: "service_name".to_string(),
-            value: database_config.target_fqdn_id,
+            value: service_names.external_name_service,
         },
         ChartSetValue {
             key: "publicly_accessible".to_string(),
@@ -333,6 +558,74 @@ where
 
     helm.on_create(target)?;
 
+    if let (Some(pooler), Some(pooler_service_name)) =
+        (&db.options.connection_pooler, service_names.connection_pooler_service)
+    {
+        let values = vec![
+            ChartSetValue {
+                key: "service_name".to_string(),
+                value: pooler_service_name,
+            },
+            ChartSetValue {
+                key: "target_hostname".to_string(),
+                value: database_config.target_hostname,
+            },
+            ChartSetValue {
+                key: "target_port".to_string(),
+                value: db.options.port.to_string(),
+            },
+            ChartSetValue {
+                key: "pool_mode".to_string(),
+                value: pooler.pool_mode.clone(),
+            },
+            ChartSetValue {
+                key: "default_pool_size".to_string(),
+                value: pooler.default_pool_size.to_string(),
+            },
+            ChartSetValue {
+                key: "max_client_connections".to_string(),
+                value: pooler.max_client_connections.to_string(),
+            },
+            ChartSetValue {
+                key: "database_id".to_string(),
+                value: db.id().to_string(),
+            },
+            ChartSetValue {
+                key: "database_long_id".to_string(),
+                value: db.long_id().to_string(),
+            },
+            ChartSetValue {
+                key: "environment_id".to_string(),
+                value: target.environment.id.to_string(),
+            },
+            ChartSetValue {
+                key: "environment_long_id".to_string(),
+                value: target.environment.long_id.to_string(),
+            },
+            ChartSetValue {
+                key: "project_long_id".to_string(),
+                value: target.environment.project_long_id.to_string(),
+            },
+        ];
+        let credentials_values_file = format!("{}/pgbouncer-credentials.yaml", workspace_dir);
+        // deleted once the chart is deployed, whatever the outcome
+        let _credentials_file = write_connection_pooler_credentials(
+            Path::new(&credentials_values_file),
+            &db.options.login,
+            &db.options.password,
+        )
+        .map_err(|err| EngineError::new_cannot_create_file(event_details.clone(), err))?;
+        connection_pooler_chart(
+            db,
+            event_details.clone(),
+            target,
+            values,
+            vec![credentials_values_file],
+            HelmAction::Deploy,
+        )
+        .on_create(target)?;
+    }
+
     // We don't manage START/PAUSE for managed database elsewhere than for AWS
     if target.kubernetes.cloud_provider().kind() != Aws {
         return Ok(());
@@ -347,8 +640,8 @@ where
     };
 
     // If the database is not in the available state, try to start it
-    match get_managed_database_status(db.db_type(), &db.fqdn_id, &credentials) {
-        Ok(status) if status == DB_READY_STATE => {}
+    match current_state(db.db_type(), &db.fqdn_id, &credentials) {
+        Ok(DatabaseState::Available) => {}
         Ok(_) | Err(_) => {
             let _ = start_stop_managed_database(db.db_type(), &db.fqdn_id, &credentials, false);
         }
@@ -368,7 +661,7 @@ where
         Err(None) => Err(EngineError::new_database_failed_to_start_after_several_retries(
             event_details,
             db.id.to_string(),
-            db.db_type().to_string(),
+            db.db_type(),
             Some(CommandError::new_from_safe_message(format!(
                 "Timeout reached waiting for the database to be in {} state",
                 DB_READY_STATE
@@ -378,14 +671,296 @@ where
         Err(Some((cmd_err, msg))) => Err(EngineError::new_database_failed_to_start_after_several_retries(
             event_details,
             db.id.to_string(),
-            db.db_type().to_string(),
+            db.db_type(),
             Some(CommandError::new_from_legacy_command_error(cmd_err, Some(msg))),
         )),
     }
 }
 
+/// Credentials used to open a connection handshake against a database.
+pub struct DatabaseCredentials<'a> {
+    pub login: &'a str,
+    pub password: &'a str,
+    pub database: &'a str,
+}
+
+fn postgres_startup_handshake(stream: &mut TcpStream, credentials: &DatabaseCredentials) -> Result<(), String> {
+    let mut body = POSTGRES_PROTOCOL_VERSION.to_be_bytes().to_vec();
+    for (key, value) in [("user", credentials.login), ("database", credentials.database)] {
+        body.extend_from_slice(key.as_bytes());
+        body.push(0);
+        body.extend_from_slice(value.as_bytes());
+        body.push(0);
+    }
+    body.push(0);
+
+    let mut packet = ((body.len() + 4) as i32).to_be_bytes().to_vec();
+    packet.extend(body);
+    stream
+        .write_all(&packet)
+        .map_err(|err| format!("cannot send startup packet: {}", err))?;
+
+    // The server answers with an authentication request once it accepts connections,
+    // and with an error while it is still starting up
+    let mut message_type = [0u8; 1];
+    stream
+        .read_exact(&mut message_type)
+        .map_err(|err| format!("cannot read startup response: {}", err))?;
+    match message_type[0] {
+        b'R' => Ok(()),
+        b'E' => Err("server rejected the connection, it is probably still starting up".to_string()),
+        other => Err(format!("unexpected startup response message type `{}`", other as char)),
+    }
+}
+
+fn redis_ping(stream: &mut TcpStream, credentials: &DatabaseCredentials) -> Result<(), String> {
+    let mut reader = BufReader::new(
+        stream
+            .try_clone()
+            .map_err(|err| format!("cannot read from connection: {}", err))?,
+    );
+    let mut send = |command: &[&str], expected: &str| -> Result<(), String> {
+        let mut request = format!("*{}\r\n", command.len());
+        for arg in command {
+            request.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+        }
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|err| format!("cannot send {}: {}", command[0], err))?;
+
+        let mut reply = String::new();
+        reader
+            .read_line(&mut reply)
+            .map_err(|err| format!("cannot read {} reply: {}", command[0], err))?;
+        match reply.trim_end() {
+            reply if reply == expected => Ok(()),
+            reply => Err(format!("unexpected {} reply `{}`", command[0], reply)),
+        }
+    };
+
+    if !credentials.password.is_empty() {
+        send(&["AUTH", credentials.password], "+OK")?;
+    }
+    send(&["PING"], "+PONG")
+}
+
+fn mysql_greeting(stream: &mut TcpStream) -> Result<(), String> {
+    // The server speaks first, with a handshake packet once it accepts connections
+    // and with an error packet while it is still starting up
+    let mut header = [0u8; 4];
+    stream
+        .read_exact(&mut header)
+        .map_err(|err| format!("cannot read server greeting: {}", err))?;
+    let mut payload = vec![0u8; u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize];
+    stream
+        .read_exact(&mut payload)
+        .map_err(|err| format!("cannot read server greeting: {}", err))?;
+    match payload.first() {
+        Some(&MYSQL_PROTOCOL_VERSION) => Ok(()),
+        // error code on 2 bytes, then the error message
+        Some(&MYSQL_ERROR_PACKET) => Err(format!(
+            "server rejected the connection: {}",
+            String::from_utf8_lossy(payload.get(3..).unwrap_or_default())
+        )),
+        Some(other) => Err(format!("unexpected server greeting protocol version `{}`", other)),
+        None => Err("empty server greeting".to_string()),
+    }
+}
+
+fn mongodb_is_master(stream: &mut TcpStream) -> Result<(), String> {
+    // BSON document { isMaster: 1, $db: "admin" }, allowed without authentication
+    let mut document = vec![0x10];
+    document.extend_from_slice(b"isMaster\0");
+    document.extend_from_slice(&1i32.to_le_bytes());
+    document.push(0x02);
+    document.extend_from_slice(b"$db\0");
+    document.extend_from_slice(&6i32.to_le_bytes());
+    document.extend_from_slice(b"admin\0");
+    document.push(0);
+    let mut bson = ((document.len() + 4) as i32).to_le_bytes().to_vec();
+    bson.extend(document);
+
+    // OP_MSG: header, flag bits and a single body section
+    let request_id = 1i32;
+    let mut message = Vec::new();
+    message.extend_from_slice(&((16 + 4 + 1 + bson.len()) as i32).to_le_bytes());
+    message.extend_from_slice(&request_id.to_le_bytes());
+    message.extend_from_slice(&0i32.to_le_bytes());
+    message.extend_from_slice(&MONGODB_OP_MSG.to_le_bytes());
+    message.extend_from_slice(&0u32.to_le_bytes());
+    message.push(0);
+    message.extend(bson);
+    stream
+        .write_all(&message)
+        .map_err(|err| format!("cannot send isMaster: {}", err))?;
+
+    let mut header = [0u8; 16];
+    stream
+        .read_exact(&mut header)
+        .map_err(|err| format!("cannot read isMaster reply: {}", err))?;
+    let response_to = i32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+    let op_code = i32::from_le_bytes([header[12], header[13], header[14], header[15]]);
+    match (response_to, op_code) {
+        (id, MONGODB_OP_MSG) if id == request_id => Ok(()),
+        _ => Err(format!("unexpected isMaster reply with op code `{}`", op_code)),
+    }
+}
+
+fn check_database_connection(
+    db_type: service::DatabaseType,
+    host: &str,
+    port: u16,
+    credentials: &DatabaseCredentials,
+) -> Result<(), String> {
+    let address = (host, port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| format!("cannot resolve {}", host))?;
+    let mut stream = TcpStream::connect_timeout(&address, TCP_CHECK_DEFAULT_TIMEOUT)
+        .map_err(|err| format!("cannot connect to {}: {}", address, err))?;
+    stream
+        .set_read_timeout(Some(TCP_CHECK_DEFAULT_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(TCP_CHECK_DEFAULT_TIMEOUT)))
+        .map_err(|err| format!("cannot configure connection to {}: {}", address, err))?;
+
+    match db_type {
+        service::DatabaseType::PostgreSQL => postgres_startup_handshake(&mut stream, credentials),
+        service::DatabaseType::Redis => redis_ping(&mut stream, credentials),
+        service::DatabaseType::MySQL => mysql_greeting(&mut stream),
+        service::DatabaseType::MongoDB => mongodb_is_master(&mut stream),
+    }
+}
+
+/// Waits until the database accepts connections, checking the protocol handshake of each database:
+/// a Postgres startup packet, a Redis PING, the MySQL server greeting or a MongoDB isMaster command.
+pub fn wait_for_database_ready(
+    db_type: service::DatabaseType,
+    host: &str,
+    port: u16,
+    credentials: &DatabaseCredentials,
+    timeout: Duration,
+    service_id: &str,
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    let mut last_error = None;
+    let ret = poll_until(
+        || match check_database_connection(db_type, host, port, credentials) {
+            Ok(()) => Ok::<_, Infallible>(PollState::Ready(())),
+            Err(err) => {
+                last_error = Some(err);
+                Ok(PollState::Pending)
+            }
+        },
+        ExponentialBackoff::new(Duration::from_secs(1), Duration::from_secs(10)),
+        timeout,
+    );
+
+    match ret {
+        Ok(()) => Ok(()),
+        Err(PollError::Timeout { elapsed, attempts }) => {
+            Err(EngineError::new_database_failed_to_start_after_several_retries(
+                event_details,
+                service_id.to_string(),
+                db_type,
+                Some(CommandError::new(
+                    format!(
+                        "Database is still not accepting connections on {}:{} after {} attempts in {} seconds",
+                        host,
+                        port,
+                        attempts,
+                        elapsed.as_secs()
+                    ),
+                    last_error,
+                    None,
+                )),
+            ))
+        }
+        Err(PollError::Operation(never)) => match never {},
+    }
+}
+
+/// Local end of a port-forward to a database pod, letting the engine reach the database even when it does not
+/// run in the cluster. The forward is closed when dropped.
+struct DatabasePortForward {
+    local_port: u16,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for DatabasePortForward {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // unblock the listener waiting for a new connection
+        let _ = TcpStream::connect(("127.0.0.1", self.local_port));
+    }
+}
+
+/// Forwards a random local port to `port` of the first running pod matching the selector.
+/// Each local connection opens its own port-forward, so a restarted pod is picked up by the next attempt.
+fn forward_database_port(
+    kube: kube::Client,
+    namespace: &str,
+    selector: &str,
+    port: u16,
+) -> io::Result<DatabasePortForward> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let local_port = listener.local_addr()?.port();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let pods: Api<Pod> = Api::namespaced(kube, namespace);
+    let selector = selector.to_string();
+    let should_stop = stop.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if should_stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let pods = pods.clone();
+            let selector = selector.clone();
+            thread::spawn(move || {
+                // errors close the local connection, and are reported by the connection check
+                let _ = block_on(bridge_to_pod(stream, &pods, &selector, port));
+            });
+        }
+    });
+
+    Ok(DatabasePortForward { local_port, stop })
+}
+
+async fn bridge_to_pod(stream: TcpStream, pods: &Api<Pod>, selector: &str, port: u16) -> Result<(), String> {
+    let pod_name = pods
+        .list(&ListParams::default().labels(selector))
+        .await
+        .map_err(|err| err.to_string())?
+        .items
+        .into_iter()
+        .find(|pod| pod.status.as_ref().and_then(|s| s.phase.as_deref()) == Some("Running"))
+        .and_then(|pod| pod.metadata.name)
+        .ok_or_else(|| format!("no running pod matches `{}`", selector))?;
+
+    let mut forwarder = pods
+        .portforward(&pod_name, &[port])
+        .await
+        .map_err(|err| err.to_string())?;
+    let mut upstream = forwarder
+        .take_stream(port)
+        .ok_or_else(|| format!("cannot forward port {} of pod {}", port, pod_name))?;
+    stream.set_nonblocking(true).map_err(|err| err.to_string())?;
+    let mut local = tokio::net::TcpStream::from_std(stream).map_err(|err| err.to_string())?;
+
+    let ret = tokio::io::copy_bidirectional(&mut local, &mut upstream).await;
+    drop(upstream);
+    forwarder.abort();
+    ret.map(|_| ()).map_err(|err| err.to_string())
+}
+
 // For Managed database
-impl<C: CloudProvider, T: DatabaseType<C, Managed>> DeploymentAction for Database<C, Managed, T>
+impl<C: CloudProvider, T: DatabaseType<C, Managed, DatabaseOptions = DatabaseOptions>> DeploymentAction
+    for Database<C, Managed, T>
 where
     Database<C, Managed, T>: ToTeraContext,
 {
@@ -413,7 +988,9 @@ where
                 pre_run: &pre_run,
                 run: &run,
                 post_run_success: &post_run,
+                post_run_failure: None,
             },
+            &target.cancellation_token,
         )
     }
 
@@ -421,7 +998,7 @@ where
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Pause));
         execute_long_deployment(
             DatabaseDeploymentReporter::new(self, target, Action::Pause),
-            |_logger: &EnvProgressLogger| -> Result<(), EngineError> {
+            |logger: &EnvProgressLogger| -> Result<(), EngineError> {
                 // We don't manage PAUSE for managed database elsewhere than for AWS
                 if target.kubernetes.cloud_provider().kind() != Aws {
                     return Ok(());
@@ -432,6 +1009,11 @@ where
                     return Ok(());
                 }
 
+                if target.is_dry_run_deploy {
+                    target.log_dry_run(&event_details, format!("stop of managed database {}", self.fqdn_id));
+                    return Ok(());
+                }
+
                 // Terraform does not ensure that the database is correctly started
                 // So we must force it ourselves in case
                 let credentials = {
@@ -440,40 +1022,63 @@ where
                     credentials
                 };
                 // We use the fqdn_id as db identifier, why not id or name like everything else ¯\_(ツ)_/¯
-                start_stop_managed_database(self.db_type(), &self.fqdn_id, &credentials, true).map_err(
-                    |(cmd_error, msg)| {
-                        EngineError::new_cannot_pause_managed_database(
-                            event_details.clone(),
-                            CommandError::new_from_legacy_command_error(cmd_error, Some(msg)),
+                let previous_state = pause_managed_database(
+                    || current_state(self.db_type(), &self.fqdn_id, &credentials),
+                    || start_stop_managed_database(self.db_type(), &self.fqdn_id, &credentials, true),
+                    || {
+                        await_db_state(
+                            Duration::from_secs(60 * 30),
+                            self.db_type(),
+                            &self.fqdn_id,
+                            &credentials,
+                            DB_STOPPED_STATE,
                         )
                     },
+                    &event_details,
                 )?;
+                if previous_state == DatabaseState::Stopped {
+                    logger.info(format!("Database {} is already paused, nothing to do", self.name()));
+                }
+
+                Ok(())
+            },
+            &target.cancellation_token,
+        )
+    }
+
+    fn on_resume(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Pause));
+
+        // We don't manage PAUSE for managed database elsewhere than for AWS, and Elasticache can't be paused
+        if target.kubernetes.cloud_provider().kind() != Aws || self.db_type() == service::DatabaseType::Redis {
+            return Ok(());
+        }
+
+        if target.is_dry_run_deploy {
+            target.log_dry_run(&event_details, format!("start of managed database {}", self.fqdn_id));
+            return Ok(());
+        }
 
-                let ret = await_db_state(
+        let credentials = {
+            let mut credentials = target.kubernetes.cloud_provider().credentials_environment_variables();
+            credentials.push((AWS_DEFAULT_REGION, target.kubernetes.region()));
+            credentials
+        };
+        resume_managed_database(
+            || current_state(self.db_type(), &self.fqdn_id, &credentials),
+            || start_stop_managed_database(self.db_type(), &self.fqdn_id, &credentials, false),
+            || {
+                await_db_state(
                     Duration::from_secs(60 * 30),
                     self.db_type(),
                     &self.fqdn_id,
                     &credentials,
-                    DB_STOPPED_STATE,
-                );
-
-                match ret {
-                    Ok(_) => Ok(()),
-                    // timeout
-                    Err(None) => Err(EngineError::new_cannot_pause_managed_database(
-                        event_details.clone(),
-                        CommandError::new_from_safe_message(format!(
-                            "Timeout reached waiting for the database to be in {} state",
-                            DB_STOPPED_STATE
-                        )),
-                    )),
-                    // Error ;'(
-                    Err(Some((cmd_err, msg))) => Err(EngineError::new_cannot_pause_managed_database(
-                        event_details.clone(),
-                        CommandError::new_from_legacy_command_error(cmd_err, Some(msg)),
-                    )),
-                }
+                    DB_READY_STATE,
+                )
             },
+            &self.id,
+            self.db_type(),
+            &event_details,
         )
     }
 
@@ -499,6 +1104,10 @@ where
                 );
                 terraform_deploy.on_delete(target)?;
 
+                // Delete the connection pooler, if any
+                connection_pooler_chart(self, event_details.clone(), target, vec![], vec![], HelmAction::Destroy)
+                    .on_delete(target)?;
+
                 // Delete the service attached
                 let chart = ChartInfo {
                     name: format!("{}-externalname", self.fqdn_id), // here it is the fqdn id :O
@@ -518,19 +1127,21 @@ where
 
                 helm.on_delete(target)
             },
+            &target.cancellation_token,
         )
     }
 }
 
 // For Container database
-impl<C: CloudProvider, T: DatabaseType<C, Container>> DeploymentAction for Database<C, Container, T>
+impl<C: CloudProvider, T: DatabaseType<C, Container, DatabaseOptions = DatabaseOptions>> DeploymentAction
+    for Database<C, Container, T>
 where
     Database<C, Container, T>: ToTeraContext,
 {
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
         let pre_run = |_: &EnvProgressLogger| -> Result<(), EngineError> { Ok(()) };
-        let run = |_logger: &EnvProgressLogger, _: ()| -> Result<(), EngineError> {
+        let run = |logger: &EnvProgressLogger, _: ()| -> Result<(), EngineError> {
             let chart = ChartInfo {
                 name: self.helm_release_name(),
                 path: self.workspace_directory().to_string(),
@@ -550,6 +1161,10 @@ where
 
             helm.on_create(target)?;
 
+            if target.is_dry_run_deploy {
+                return Ok(());
+            }
+
             delete_pending_service(
                 target.kubernetes.get_kubeconfig_file_path()?.as_str(),
                 target.environment.namespace(),
@@ -558,6 +1173,46 @@ where
                 event_details.clone(),
             )?;
 
+            // the engine may not run in the cluster, so the service name cannot be resolved from here
+            let database_ready = forward_database_port(
+                target.kube.clone(),
+                target.environment.namespace(),
+                &self.selector(),
+                T::db_type().container_port(),
+            )
+            .map_err(|err| {
+                EngineError::new_database_failed_to_start_after_several_retries(
+                    event_details.clone(),
+                    self.id().to_string(),
+                    T::db_type(),
+                    Some(CommandError::new(
+                        "Cannot forward a local port to the database".to_string(),
+                        Some(err.to_string()),
+                        None,
+                    )),
+                )
+            })
+            .and_then(|forward| {
+                wait_for_database_ready(
+                    T::db_type(),
+                    "127.0.0.1",
+                    forward.local_port,
+                    &DatabaseCredentials {
+                        login: &self.options.login,
+                        password: &self.options.password,
+                        database: &self.name,
+                    },
+                    DB_CONNECTION_READY_TIMEOUT,
+                    self.id(),
+                    event_details.clone(),
+                )
+            });
+
+            // helm already waited for the database pods, not accepting connections yet doesn't fail the deployment
+            if let Err(err) = database_ready {
+                logger.warning(format!("Database is not accepting connections yet: {}", err.user_log_message()));
+            }
+
             Ok(())
         };
 
@@ -580,7 +1235,9 @@ where
                 pre_run: &pre_run,
                 run: &run,
                 post_run_success: &post_run,
+                post_run_failure: None,
             },
+            &target.cancellation_token,
         )
     }
 
@@ -596,6 +1253,32 @@ where
                 );
                 pause_service.on_pause(target)
             },
+            &target.cancellation_token,
+        )
+    }
+
+    fn on_resume(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        PauseServiceAction::new(
+            self.selector(),
+            true,
+            Duration::from_secs(5 * 60),
+            self.get_event_details(Stage::Environment(EnvironmentStep::Pause)),
+        )
+        .scale_up_if_paused(target, 1)
+    }
+
+    fn on_restart(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        execute_long_deployment(
+            DatabaseDeploymentReporter::new(self, target, Action::Restart),
+            |_logger: &EnvProgressLogger| -> Result<(), EngineError> {
+                rollout_restart(
+                    target,
+                    &self.selector(),
+                    true,
+                    self.get_event_details(Stage::Environment(EnvironmentStep::Deploy)),
+                )
+            },
+            &target.cancellation_token,
         )
     }
 
@@ -622,6 +1305,11 @@ where
 
                 helm.on_delete(target)?;
 
+                if target.is_dry_run_deploy {
+                    target.log_dry_run(&event_details, "deletion of volumes of the database".to_string());
+                    return Ok(());
+                }
+
                 // TODO: Remove once we migrate to kube 1.23, it will done automatically
                 logger.info("🪓 Terminating network volume of the database".to_string());
                 if let Err(err) = block_on(kube_delete_all_from_selector::<PersistentVolumeClaim>(
@@ -638,6 +1326,401 @@ where
 
                 Ok(())
             },
+            &target.cancellation_token,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::service::DatabaseType;
+    use crate::deployment_action::deploy_database::{
+        pause_managed_database, resume_managed_database, wait_for_database_ready, write_connection_pooler_credentials,
+        DatabaseCredentials, DatabaseState,
+    };
+    use crate::deployment_action::deploy_environment::pause_services_or_rollback;
+    use crate::deployment_action::test_utils::RecordingLogger;
+    use crate::errors::{EngineError, Tag};
+    use crate::events::{EnvironmentStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    const CREDENTIALS: DatabaseCredentials = DatabaseCredentials {
+        login: "superuser",
+        password: "secret",
+        database: "qovery",
+    };
+
+    /// Spawns a server answering every connection with `handler`, returns its port.
+    fn mock_server(handler: impl Fn(TcpStream) + Send + 'static) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("cannot bind mock server");
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handler(stream)
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_wait_for_postgresql_ready() {
+        // setup:
+        let port = mock_server(|mut stream| {
+            let mut length = [0u8; 4];
+            stream.read_exact(&mut length).unwrap();
+            let mut body = vec![0u8; i32::from_be_bytes(length) as usize - 4];
+            stream.read_exact(&mut body).unwrap();
+            assert_eq!(196608, i32::from_be_bytes([body[0], body[1], body[2], body[3]]));
+            assert_eq!(b"user\0superuser\0database\0qovery\0\0", &body[4..]);
+            // AuthenticationOk
+            stream.write_all(&[b'R', 0, 0, 0, 8, 0, 0, 0, 0]).unwrap();
+        });
+
+        // execute:
+        let ret = wait_for_database_ready(
+            DatabaseType::PostgreSQL,
+            "127.0.0.1",
+            port,
+            &CREDENTIALS,
+            Duration::from_secs(5),
+            "id",
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                .build(),
+        );
+
+        // verify:
+        assert!(ret.is_ok());
+    }
+
+    #[test]
+    fn test_wait_for_redis_ready() {
+        // setup:
+        let port = mock_server(|stream| {
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut lines = Vec::new();
+            for reply in ["+OK\r\n", "+PONG\r\n"] {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let args = line.trim_end()[1..].parse::<usize>().unwrap();
+                for _ in 0..args * 2 {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    lines.push(line.trim_end().to_string());
+                }
+                writer.write_all(reply.as_bytes()).unwrap();
+            }
+            assert_eq!(vec!["$4", "AUTH", "$6", "secret", "$4", "PING"], lines);
+        });
+
+        // execute:
+        let ret = wait_for_database_ready(
+            DatabaseType::Redis,
+            "127.0.0.1",
+            port,
+            &CREDENTIALS,
+            Duration::from_secs(5),
+            "id",
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                .build(),
+        );
+
+        // verify:
+        assert!(ret.is_ok());
+    }
+
+    #[test]
+    fn test_wait_for_mysql_ready() {
+        // setup:
+        let port = mock_server(|mut stream| {
+            // protocol version, then the beginning of the server version
+            stream
+                .write_all(&[7, 0, 0, 0, 10, b'8', b'.', b'0', b'.', b'3', 0])
+                .unwrap();
+        });
+
+        // execute:
+        let ret = wait_for_database_ready(
+            DatabaseType::MySQL,
+            "127.0.0.1",
+            port,
+            &CREDENTIALS,
+            Duration::from_secs(5),
+            "id",
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                .build(),
+        );
+
+        // verify:
+        assert!(ret.is_ok());
+    }
+
+    #[test]
+    fn test_wait_for_mysql_ready_times_out() {
+        // setup: the port-forward accepts the connection but closes it as nothing listens in the pod yet
+        let port = mock_server(drop);
+
+        // execute:
+        let ret = wait_for_database_ready(
+            DatabaseType::MySQL,
+            "127.0.0.1",
+            port,
+            &CREDENTIALS,
+            Duration::from_millis(500),
+            "id",
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                .build(),
+        );
+
+        // verify:
+        let err = ret.expect_err("database should not be ready");
+        assert!(err
+            .underlying_error()
+            .and_then(|err| err.message_raw())
+            .unwrap_or_default()
+            .contains("cannot read server greeting"));
+    }
+
+    #[test]
+    fn test_wait_for_mongodb_ready() {
+        // setup:
+        let port = mock_server(|mut stream| {
+            let mut length = [0u8; 4];
+            stream.read_exact(&mut length).unwrap();
+            let mut message = vec![0u8; i32::from_le_bytes(length) as usize - 4];
+            stream.read_exact(&mut message).unwrap();
+            let request_id = &message[0..4];
+            assert_eq!(2013, i32::from_le_bytes([message[8], message[9], message[10], message[11]]));
+            assert!(message.windows(9).any(|field| field == b"isMaster\0"));
+
+            let mut reply = 16i32.to_le_bytes().to_vec();
+            reply.extend_from_slice(&0i32.to_le_bytes());
+            reply.extend_from_slice(request_id);
+            reply.extend_from_slice(&2013i32.to_le_bytes());
+            stream.write_all(&reply).unwrap();
+        });
+
+        // execute:
+        let ret = wait_for_database_ready(
+            DatabaseType::MongoDB,
+            "127.0.0.1",
+            port,
+            &CREDENTIALS,
+            Duration::from_secs(5),
+            "id",
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                .build(),
+        );
+
+        // verify:
+        assert!(ret.is_ok());
+    }
+
+    #[test]
+    fn test_wait_for_database_ready_times_out() {
+        // setup: the server is still starting up and rejects the connection
+        let port = mock_server(|mut stream| {
+            let mut length = [0u8; 4];
+            stream.read_exact(&mut length).unwrap();
+            let mut body = vec![0u8; i32::from_be_bytes(length) as usize - 4];
+            stream.read_exact(&mut body).unwrap();
+            stream.write_all(&[b'E', 0, 0, 0, 4]).unwrap();
+        });
+
+        // execute:
+        let ret = wait_for_database_ready(
+            DatabaseType::PostgreSQL,
+            "127.0.0.1",
+            port,
+            &CREDENTIALS,
+            Duration::from_millis(500),
+            "id",
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                .build(),
+        );
+
+        // verify:
+        let err = ret.expect_err("database should not be ready");
+        assert_eq!(&Tag::DatabaseFailedToStartAfterSeveralRetries, err.tag());
+        assert!(err
+            .underlying_error()
+            .and_then(|err| err.message_raw())
+            .unwrap_or_default()
+            .contains("still starting up"));
+    }
+
+    #[test]
+    fn test_write_connection_pooler_credentials() {
+        // setup:
+        let workspace_dir = tempfile::tempdir().expect("cannot create temp dir");
+        let path = workspace_dir.path().join("pgbouncer-credentials.yaml");
+
+        // execute:
+        let credentials_file = write_connection_pooler_credentials(&path, "superuser", "p@ss: \"word\"");
+
+        // verify:
+        assert!(credentials_file.is_ok());
+        assert_eq!(0o600, fs::metadata(&path).unwrap().permissions().mode() & 0o777);
+        let values: BTreeMap<String, String> = serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            BTreeMap::from([
+                ("username".to_string(), "superuser".to_string()),
+                ("password".to_string(), "p@ss: \"word\"".to_string()),
+            ]),
+            values
+        );
+        drop(credentials_file);
+        assert!(!path.exists(), "credentials file should be deleted once dropped");
+    }
+
+    #[test]
+    fn test_pause_managed_database_is_idempotent() {
+        // setup:
+        let test_cases = vec![
+            (DatabaseState::Stopped, 0, 0),
+            (DatabaseState::Stopping, 0, 1),
+            (DatabaseState::Available, 1, 1),
+        ];
+
+        for (state, expected_stop_calls, expected_await_calls) in test_cases {
+            let stop_calls = AtomicU32::new(0);
+            let await_calls = AtomicU32::new(0);
+
+            // execute:
+            let ret = pause_managed_database(
+                || Ok(state.clone()),
+                || {
+                    stop_calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+                || {
+                    await_calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+                &EventDetailsBuilder::new()
+                    .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                    .build(),
+            );
+
+            // verify:
+            assert_eq!(Some(state.clone()), ret.ok(), "state: {:?}", state);
+            assert_eq!(expected_stop_calls, stop_calls.load(Ordering::SeqCst), "state: {:?}", state);
+            assert_eq!(expected_await_calls, await_calls.load(Ordering::SeqCst), "state: {:?}", state);
+        }
+    }
+
+    #[test]
+    fn test_pause_rollback_resumes_paused_managed_database() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Environment(EnvironmentStep::Pause))
+            .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+            .build();
+        let (database_id, app_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let database_state = Mutex::new(DatabaseState::Available);
+        let start_calls = AtomicU32::new(0);
+        let set_state = |state: DatabaseState| {
+            *database_state.lock().unwrap() = state;
+            Ok(())
+        };
+        let logger = RecordingLogger::default();
+
+        // execute: the managed database is stopped, then the application fails to pause
+        let paused_services = pause_services_or_rollback(
+            vec![(database_id, database_id), (app_id, app_id)].into_iter(),
+            &|id: &Uuid| {
+                if *id == app_id {
+                    return Err(EngineError::new_unknown(
+                        event_details.clone(),
+                        "cannot pause app".to_string(),
+                        None,
+                        None,
+                        None,
+                    ));
+                }
+                pause_managed_database(
+                    || Ok(database_state.lock().unwrap().clone()),
+                    || set_state(DatabaseState::Stopped),
+                    || Ok(()),
+                    &event_details,
+                )
+                .map(|_| ())
+            },
+            &|_: &Uuid| {
+                resume_managed_database(
+                    || Ok(database_state.lock().unwrap().clone()),
+                    || {
+                        start_calls.fetch_add(1, Ordering::SeqCst);
+                        set_state(DatabaseState::Available)
+                    },
+                    || Ok(()),
+                    &database_id.to_string(),
+                    DatabaseType::PostgreSQL,
+                    &event_details,
+                )
+            },
+            &logger,
+            &event_details,
+        );
+
+        // verify:
+        assert_eq!(Some(&Tag::Unknown), paused_services.result.as_ref().err().map(|err| err.tag()));
+        assert_eq!(1, start_calls.load(Ordering::SeqCst));
+        assert_eq!(DatabaseState::Available, *database_state.lock().unwrap());
+        assert!(logger.messages.lock().unwrap()[1].contains(&format!("Service {} has been resumed", database_id)));
+    }
+
+    #[test]
+    fn test_resume_managed_database_is_idempotent() {
+        // setup:
+        let test_cases = vec![
+            (DatabaseState::Available, 0, 0),
+            (DatabaseState::Starting, 0, 1),
+            (DatabaseState::Stopped, 1, 1),
+        ];
+
+        for (state, expected_start_calls, expected_await_calls) in test_cases {
+            let start_calls = AtomicU32::new(0);
+            let await_calls = AtomicU32::new(0);
+
+            // execute:
+            let ret = resume_managed_database(
+                || Ok(state.clone()),
+                || {
+                    start_calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+                || {
+                    await_calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+                "database",
+                DatabaseType::PostgreSQL,
+                &EventDetailsBuilder::new()
+                    .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                    .build(),
+            );
+
+            // verify:
+            assert!(ret.is_ok(), "state: {:?}", state);
+            assert_eq!(expected_start_calls, start_calls.load(Ordering::SeqCst), "state: {:?}", state);
+            assert_eq!(expected_await_calls, await_calls.load(Ordering::SeqCst), "state: {:?}", state);
+        }
+    }
+}
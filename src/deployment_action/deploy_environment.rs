@@ -1,17 +1,32 @@
 use crate::cloud_provider::aws::load_balancers::clean_up_deleted_k8s_nlb;
-use crate::cloud_provider::environment::Environment;
+use crate::cloud_provider::environment::{Environment, RequestedPods};
 use crate::cloud_provider::service::Action;
 use crate::cloud_provider::DeploymentTarget;
+use crate::cloud_provider::Kind::Aws;
 use crate::cmd::kubectl::kubectl_exec_is_namespace_present;
 use crate::deployment_action::deploy_namespace::NamespaceDeployment;
+use crate::deployment_action::preflight::{preflight, PreflightReport};
 use crate::deployment_action::DeploymentAction;
+use crate::deployment_report::CancellationToken;
 use crate::engine::InfrastructureContext;
-use crate::errors::EngineError;
-use crate::events::{EnvironmentStep, EventDetails};
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity, MultiEngineError};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage};
+use crate::logger::Logger;
+use crate::runtime::block_on;
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::ListParams;
+use kube::Api;
+use std::cmp::{max, min};
 use std::collections::HashSet;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Condvar, Mutex};
+use std::thread;
 use std::time::Duration;
 use uuid::Uuid;
 
+const ABORT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 pub struct EnvironmentDeployment<'a> {
     pub deployed_services: HashSet<Uuid>,
     deployment_target: DeploymentTarget<'a>,
@@ -21,7 +36,7 @@ impl<'a> EnvironmentDeployment<'a> {
     pub fn new(
         infra_ctx: &'a InfrastructureContext,
         environment: &'a Environment,
-        should_abort: &'a dyn Fn() -> bool,
+        should_abort: &'a (dyn Fn() -> bool + Sync),
     ) -> Result<EnvironmentDeployment<'a>, EngineError> {
         let deployment_target = DeploymentTarget::new(infra_ctx, environment, should_abort)?;
         Ok(EnvironmentDeployment {
@@ -66,12 +81,62 @@ impl<'a> EnvironmentDeployment<'a> {
             )
     }
 
+    /// Services along with the ones which must be deployed before them: databases first, as other services may
+    /// need them at startup, then jobs, then containers and applications, and routers after the services they route to.
+    fn services_to_deploy(environment: &Environment) -> Vec<ServiceToDeploy<(&dyn DeploymentAction, Action)>> {
+        let databases_ids: Vec<Uuid> = environment.databases.iter().map(|s| *s.long_id()).collect();
+        let jobs_ids: Vec<Uuid> = environment.jobs.iter().map(|s| *s.long_id()).collect();
+        let databases_and_jobs_ids: Vec<Uuid> = databases_ids.iter().chain(jobs_ids.iter()).copied().collect();
+        let routable_ids: HashSet<Uuid> = environment
+            .containers
+            .iter()
+            .map(|s| *s.long_id())
+            .chain(environment.applications.iter().map(|s| *s.long_id()))
+            .collect();
+
+        let mut services = Vec::with_capacity(Self::services_iter(environment).count());
+        services.extend(environment.databases.iter().map(|s| ServiceToDeploy {
+            id: *s.long_id(),
+            depends_on: vec![],
+            service: (s.as_deployment_action(), *s.action()),
+        }));
+        services.extend(environment.jobs.iter().map(|s| ServiceToDeploy {
+            id: *s.long_id(),
+            depends_on: databases_ids.clone(),
+            service: (s.as_deployment_action(), *s.action()),
+        }));
+        services.extend(environment.containers.iter().map(|s| ServiceToDeploy {
+            id: *s.long_id(),
+            depends_on: databases_and_jobs_ids.clone(),
+            service: (s.as_deployment_action(), *s.action()),
+        }));
+        services.extend(environment.applications.iter().map(|s| ServiceToDeploy {
+            id: *s.long_id(),
+            depends_on: databases_and_jobs_ids.clone(),
+            service: (s.as_deployment_action(), *s.action()),
+        }));
+        services.extend(environment.routers.iter().map(|s| {
+            ServiceToDeploy {
+                id: *s.long_id(),
+                depends_on: s
+                    .routed_services_ids()
+                    .into_iter()
+                    .filter(|id| routable_ids.contains(id))
+                    .collect(),
+                service: (s.as_deployment_action(), *s.action()),
+            }
+        }));
+
+        services
+    }
+
     fn should_abort_wrapper<'b>(
         target: &'b DeploymentTarget,
         event_details: &'b EventDetails,
     ) -> impl Fn() -> Result<(), EngineError> + 'b {
         move || {
             if (target.should_abort)() {
+                target.cancellation_token.cancel();
                 Err(EngineError::new_task_cancellation_requested(event_details.clone()))
             } else {
                 Ok(())
@@ -79,7 +144,24 @@ impl<'a> EnvironmentDeployment<'a> {
         }
     }
 
+    // Services only check `should_abort` before being deployed, long running ones rely on the cancellation token,
+    // which must be cancelled as soon as the abort is requested.
+    fn watching_abort(&mut self, action: impl FnOnce(&mut Self) -> Result<(), EngineError>) -> Result<(), EngineError> {
+        let should_abort = self.deployment_target.should_abort;
+        let cancellation_token = self.deployment_target.cancellation_token.clone();
+        run_with_abort_watcher(should_abort, &cancellation_token, ABORT_POLL_INTERVAL, || action(self))
+    }
+
+    /// Runs every check non-destructively, before any mutation is done.
+    pub fn preflight(&self) -> PreflightReport {
+        preflight(self.deployment_target.environment, &self.deployment_target)
+    }
+
     pub fn on_create(&mut self) -> Result<(), EngineError> {
+        self.watching_abort(|env| env.create())
+    }
+
+    fn create(&mut self) -> Result<(), EngineError> {
         let target = &self.deployment_target;
         let event_details = self
             .deployment_target
@@ -92,6 +174,25 @@ impl<'a> EnvironmentDeployment<'a> {
             .map(|ttl| Duration::from_secs(ttl as u64));
         let should_abort = Self::should_abort_wrapper(target, &event_details);
 
+        // fail early if some services would clash once their names are sanitized
+        check_sanitized_name_collisions(&event_details, &target.environment.sanitized_name_collisions())?;
+
+        // fail early if the cluster can't schedule the pods the environment adds to it
+        should_abort()?;
+        match cluster_pods_usage(target, &event_details) {
+            Ok(Some((free_pods, requested_pods))) => {
+                check_enough_free_pods(&event_details, &requested_pods, free_pods)?
+            }
+            Ok(None) => {}
+            Err(err) => target.kubernetes.logger().log(EngineEvent::Warning(
+                event_details.clone(),
+                EventMessage::new_from_safe(format!(
+                    "Cannot verify the cluster has enough free pods to deploy the environment: {}",
+                    err.user_log_message()
+                )),
+            )),
+        }
+
         // deploy namespace first
         should_abort()?;
         let ns = NamespaceDeployment {
@@ -100,20 +201,29 @@ impl<'a> EnvironmentDeployment<'a> {
         };
         ns.exec_action(target, target.environment.action)?;
 
-        let services = Self::services_iter(target.environment);
-        for (service_id, service, service_action) in services {
-            should_abort()?;
-            self.deployed_services.insert(service_id);
-            service.exec_action(target, service_action)?;
-        }
+        // independent services are deployed concurrently, a failing one stops the deployment of the next ones
+        let max_parallelism = target.kubernetes.advanced_settings().deployment_max_parallelism as usize;
+        let (deployed_services, errors) = deploy_services_concurrently(
+            Self::services_to_deploy(target.environment),
+            max_parallelism,
+            &|(service, service_action): &(&dyn DeploymentAction, Action)| {
+                should_abort()?;
+                service.exec_action(target, *service_action)
+            },
+        );
+        self.deployed_services.extend(deployed_services);
+        report_services_errors(&errors, target.kubernetes.logger(), &event_details)?;
 
-        // clean up nlb
-        clean_up_deleted_k8s_nlb(event_details.clone(), target)?;
+        clean_up_lingering_load_balancers(target, &event_details)?;
 
         Ok(())
     }
 
     pub fn on_pause(&mut self) -> Result<(), EngineError> {
+        self.watching_abort(|env| env.pause())
+    }
+
+    fn pause(&mut self) -> Result<(), EngineError> {
         let event_details = self
             .deployment_target
             .environment
@@ -121,13 +231,22 @@ impl<'a> EnvironmentDeployment<'a> {
         let target = &mut self.deployment_target;
         let should_abort = Self::should_abort_wrapper(target, &event_details);
 
-        // reverse order of the deployment
-        let services = Self::services_iter(target.environment).rev();
-        for (service_id, service, _) in services {
-            should_abort()?;
-            self.deployed_services.insert(service_id);
-            service.on_pause(target)?;
-        }
+        // reverse order of the deployment, services already paused are resumed if one fails to pause
+        let services = Self::services_iter(target.environment)
+            .rev()
+            .map(|(service_id, service, _)| (service_id, service));
+        let paused_services = pause_services_or_rollback(
+            services,
+            &|service: &&dyn DeploymentAction| {
+                should_abort()?;
+                service.on_pause(target)
+            },
+            &|service: &&dyn DeploymentAction| service.on_resume(target),
+            target.kubernetes.logger(),
+            &event_details,
+        );
+        self.deployed_services.extend(paused_services.started);
+        paused_services.result?;
 
         let ns = NamespaceDeployment {
             resource_expiration: target
@@ -142,7 +261,44 @@ impl<'a> EnvironmentDeployment<'a> {
         Ok(())
     }
 
+    pub fn on_restart(&mut self) -> Result<(), EngineError> {
+        self.watching_abort(|env| env.restart())
+    }
+
+    fn restart(&mut self) -> Result<(), EngineError> {
+        let target = &self.deployment_target;
+        let event_details = self
+            .deployment_target
+            .environment
+            .event_details_with_step(EnvironmentStep::Deploy);
+        let should_abort = Self::should_abort_wrapper(target, &event_details);
+
+        // services restart independently, a failing one must not prevent the other ones from being restarted
+        let mut errors = MultiEngineError::new();
+        let services = Self::services_iter(target.environment);
+        for (service_id, service, _) in services {
+            if let Err(err) = should_abort() {
+                errors.push(err);
+                break;
+            }
+            self.deployed_services.insert(service_id);
+            if let Err(err) = service.on_restart(target) {
+                let is_cancel = err.tag().is_cancel();
+                errors.push(err);
+                if is_cancel {
+                    break;
+                }
+            }
+        }
+
+        report_services_errors(&errors, target.kubernetes.logger(), &event_details)
+    }
+
     pub fn on_delete(&mut self) -> Result<(), EngineError> {
+        self.watching_abort(|env| env.delete())
+    }
+
+    fn delete(&mut self) -> Result<(), EngineError> {
         let target = &self.deployment_target;
         let environment = &target.environment;
         let event_details = self
@@ -183,6 +339,773 @@ impl<'a> EnvironmentDeployment<'a> {
         };
         ns.on_delete(target)?;
 
+        clean_up_lingering_load_balancers(target, &event_details)?;
+
         Ok(())
     }
 }
+
+// AWS network load balancers may linger once their services are gone
+fn clean_up_lingering_load_balancers(
+    target: &DeploymentTarget,
+    event_details: &EventDetails,
+) -> Result<(), EngineError> {
+    if target.kubernetes.cloud_provider().kind() != Aws {
+        return Ok(());
+    }
+
+    clean_up_deleted_k8s_nlb(event_details.clone(), target)
+}
+
+/// Returns the first cancellation or error of the services, when several services failed they are all
+/// reported to the user beforehand, not to hide the other failures behind the returned one.
+fn report_services_errors(
+    errors: &MultiEngineError,
+    logger: &dyn Logger,
+    event_details: &EventDetails,
+) -> Result<(), EngineError> {
+    let err = match errors.first_cancel_or_error() {
+        Some(err) => err.clone(),
+        None => return Ok(()),
+    };
+
+    if errors.errors().len() > 1 {
+        logger.log(EngineEvent::Warning(
+            event_details.clone(),
+            EventMessage::new(
+                errors.message(ErrorMessageVerbosity::SafeOnly),
+                Some(errors.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+            ),
+        ));
+    }
+
+    Err(err)
+}
+
+/// A service to deploy, along with the ids of the services which must be deployed before it.
+pub(super) struct ServiceToDeploy<T> {
+    pub id: Uuid,
+    pub depends_on: Vec<Uuid>,
+    pub service: T,
+}
+
+struct DeploymentSchedule<T> {
+    pending: Vec<ServiceToDeploy<T>>,
+    deployed: HashSet<Uuid>,
+    started: HashSet<Uuid>,
+    running: usize,
+    is_stopped: bool,
+    errors: MultiEngineError,
+}
+
+/// Deploys services on at most `max_parallelism` threads, a service being started once all its dependencies
+/// are deployed, in the given order otherwise. No new service is started once one failed, the ones already
+/// started are awaited and their errors collected too. Returns the ids of the started services along with the errors.
+pub(super) fn deploy_services_concurrently<T, F>(
+    services: Vec<ServiceToDeploy<T>>,
+    max_parallelism: usize,
+    deploy: &F,
+) -> (HashSet<Uuid>, MultiEngineError)
+where
+    T: Send,
+    F: Fn(&T) -> Result<(), EngineError> + Sync,
+{
+    let workers_count = min(max(max_parallelism, 1), services.len());
+    let schedule = Mutex::new(DeploymentSchedule {
+        pending: services,
+        deployed: HashSet::new(),
+        started: HashSet::new(),
+        running: 0,
+        is_stopped: false,
+        errors: MultiEngineError::new(),
+    });
+    let service_done = Condvar::new();
+
+    thread::scope(|s| {
+        for _ in 0..workers_count {
+            let current_span = tracing::Span::current();
+            let (schedule, service_done) = (&schedule, &service_done);
+            s.spawn(move || {
+                // making sure to pass the current span to the new thread not to lose any tracing info
+                let _enter = current_span.enter();
+                let mut state = schedule.lock().unwrap_or_else(|e| e.into_inner());
+                loop {
+                    if state.is_stopped || state.pending.is_empty() {
+                        return;
+                    }
+
+                    let next_service = state
+                        .pending
+                        .iter()
+                        .position(|service| service.depends_on.iter().all(|id| state.deployed.contains(id)));
+                    let service = match next_service {
+                        Some(index) => state.pending.remove(index),
+                        // nothing left able to run, the remaining services depend on a service which is not deployed
+                        None if state.running == 0 => return,
+                        None => {
+                            state = service_done.wait(state).unwrap_or_else(|e| e.into_inner());
+                            continue;
+                        }
+                    };
+                    state.started.insert(service.id);
+                    state.running += 1;
+                    drop(state);
+
+                    let ret = catch_unwind(AssertUnwindSafe(|| deploy(&service.service)));
+
+                    state = schedule.lock().unwrap_or_else(|e| e.into_inner());
+                    state.running -= 1;
+                    match ret {
+                        Ok(Ok(())) => {
+                            state.deployed.insert(service.id);
+                        }
+                        Ok(Err(err)) => {
+                            state.is_stopped = true;
+                            state.errors.push(err);
+                        }
+                        Err(panic) => {
+                            // wake up the other workers before unwinding, for them not to wait forever
+                            state.is_stopped = true;
+                            drop(state);
+                            service_done.notify_all();
+                            resume_unwind(panic);
+                        }
+                    }
+                    service_done.notify_all();
+                }
+            });
+        }
+    });
+
+    let state = schedule.into_inner().unwrap_or_else(|e| e.into_inner());
+    (state.started, state.errors)
+}
+
+/// Runs `task` while polling `should_abort` in the background, the cancellation token is cancelled
+/// as soon as an abort is requested. Polling stops once the task is done.
+pub(super) fn run_with_abort_watcher<R>(
+    should_abort: &(dyn Fn() -> bool + Sync),
+    cancellation_token: &CancellationToken,
+    poll_interval: Duration,
+    task: impl FnOnce() -> R,
+) -> R {
+    let (task_done_tx, task_done_rx) = mpsc::channel::<()>();
+    thread::scope(|scope| {
+        scope.spawn(move || loop {
+            match task_done_rx.recv_timeout(poll_interval) {
+                Err(RecvTimeoutError::Timeout) => {
+                    if should_abort() {
+                        cancellation_token.cancel();
+                        return;
+                    }
+                }
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => return,
+            }
+        });
+
+        let ret = task();
+        let _ = task_done_tx.send(());
+        ret
+    })
+}
+
+pub(super) struct PausedServices {
+    /// started: ids of the services whose pause was attempted, resumed ones included.
+    pub started: HashSet<Uuid>,
+    pub result: Result<(), EngineError>,
+}
+
+/// Pauses services one after the other. If one fails to pause, the services already paused are resumed,
+/// in reverse order, before returning the pause error, not to leave the environment half paused.
+/// Services are not resumed on cancellation, as it has been explicitly requested.
+pub(super) fn pause_services_or_rollback<T, P, R>(
+    services: impl Iterator<Item = (Uuid, T)>,
+    pause: &P,
+    resume: &R,
+    logger: &dyn Logger,
+    event_details: &EventDetails,
+) -> PausedServices
+where
+    P: Fn(&T) -> Result<(), EngineError>,
+    R: Fn(&T) -> Result<(), EngineError>,
+{
+    let mut started = HashSet::new();
+    let mut paused: Vec<(Uuid, T)> = vec![];
+    for (service_id, service) in services {
+        started.insert(service_id);
+        let err = match pause(&service) {
+            Ok(()) => {
+                paused.push((service_id, service));
+                continue;
+            }
+            Err(err) => err,
+        };
+
+        if !err.tag().is_cancel() {
+            logger.log(EngineEvent::Warning(
+                event_details.clone(),
+                EventMessage::new_from_safe(format!(
+                    "Cannot pause service {}, resuming the {} service(s) already paused",
+                    service_id,
+                    paused.len()
+                )),
+            ));
+            for (paused_service_id, paused_service) in paused.iter().rev() {
+                match resume(paused_service) {
+                    Ok(()) => logger.log(EngineEvent::Info(
+                        event_details.clone(),
+                        EventMessage::new_from_safe(format!("Service {} has been resumed", paused_service_id)),
+                    )),
+                    Err(resume_err) => logger.log(EngineEvent::Error(
+                        resume_err,
+                        Some(EventMessage::new_from_safe(format!(
+                            "Cannot resume service {}",
+                            paused_service_id
+                        ))),
+                    )),
+                }
+            }
+        }
+
+        return PausedServices {
+            started,
+            result: Err(err),
+        };
+    }
+
+    PausedServices {
+        started,
+        result: Ok(()),
+    }
+}
+
+pub(super) fn check_sanitized_name_collisions(
+    event_details: &EventDetails,
+    collisions: &[(String, Vec<String>)],
+) -> Result<(), EngineError> {
+    if collisions.is_empty() {
+        return Ok(());
+    }
+
+    let conflicting_services = collisions
+        .iter()
+        .map(|(sanitized_name, names)| {
+            format!(
+                "{} are all named `{}` once sanitized",
+                names
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                sanitized_name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    Err(EngineError::new_invalid_engine_payload(
+        event_details.clone(),
+        format!("services names are conflicting: {}", conflicting_services).as_str(),
+    ))
+}
+
+/// Free pods of the cluster, once the running ones are removed, and pods the environment deployment adds to it.
+/// `None` when the cluster has no running node to tell.
+pub(super) fn cluster_pods_usage(
+    target: &DeploymentTarget,
+    event_details: &EventDetails,
+) -> Result<Option<(u32, Vec<ServicePods>)>, EngineError> {
+    let resources = target.kubernetes.resources(target.environment)?;
+    if resources.running_nodes == 0 {
+        return Ok(None);
+    }
+
+    let running_pods = list_running_pods(&Api::all(target.kube.clone()), event_details)?;
+
+    let free_pods = resources.free_pods.saturating_sub(running_pods.len() as u32);
+    let environment_pods: Vec<Pod> = running_pods
+        .into_iter()
+        .filter(|pod| pod.metadata.namespace.as_deref() == Some(target.environment.namespace()))
+        .collect();
+
+    Ok(Some((
+        free_pods,
+        services_requested_pods(&target.environment.requested_pods(), &environment_pods),
+    )))
+}
+
+/// Pods the environment deployment adds to its namespace.
+pub(super) fn environment_requested_pods(
+    target: &DeploymentTarget,
+    event_details: &EventDetails,
+) -> Result<u32, EngineError> {
+    let running_pods = list_running_pods(
+        &Api::namespaced(target.kube.clone(), target.environment.namespace()),
+        event_details,
+    )?;
+
+    Ok(requested_pods_delta(&target.environment.requested_pods(), &running_pods))
+}
+
+fn list_running_pods(pods: &Api<Pod>, event_details: &EventDetails) -> Result<Vec<Pod>, EngineError> {
+    block_on(pods.list(&ListParams::default().fields("status.phase!=Succeeded,status.phase!=Failed")))
+        .map(|pods| pods.items)
+        .map_err(|err| {
+            EngineError::new_k8s_cannot_get_pods(
+                event_details.clone(),
+                CommandError::new("Cannot list running pods.".to_string(), Some(err.to_string()), None),
+            )
+        })
+}
+
+/// Pods added by the services to be created, services already running only add the pods they are scaled up by.
+pub(super) fn requested_pods_delta(requested_pods: &[RequestedPods], running_pods: &[Pod]) -> u32 {
+    services_requested_pods(requested_pods, running_pods)
+        .iter()
+        .map(|(_, pods)| pods)
+        .sum()
+}
+
+/// Service name and the pods it adds to the cluster.
+pub(super) type ServicePods = (String, u32);
+
+/// Pods added by each service, services not adding any are left out.
+pub(super) fn services_requested_pods(requested_pods: &[RequestedPods], running_pods: &[Pod]) -> Vec<ServicePods> {
+    requested_pods
+        .iter()
+        .map(|requested| {
+            let running = match &requested.selector {
+                Some(selector) => running_pods.iter().filter(|pod| has_labels(pod, selector)).count() as u32,
+                None => 0,
+            };
+            (requested.service_name.clone(), requested.pods.saturating_sub(running))
+        })
+        .filter(|(_, pods)| *pods > 0)
+        .collect()
+}
+
+// selector is a comma separated list of `key=value` labels, the pod must have all of them
+fn has_labels(pod: &Pod, selector: &str) -> bool {
+    let labels = pod.metadata.labels.as_ref();
+    selector.split(',').all(|label| match label.split_once('=') {
+        Some((key, value)) => {
+            labels.and_then(|labels| labels.get(key.trim())).map(String::as_str) == Some(value.trim())
+        }
+        None => false,
+    })
+}
+
+pub(super) fn check_enough_free_pods(
+    event_details: &EventDetails,
+    requested_pods: &[ServicePods],
+    free_pods: u32,
+) -> Result<(), EngineError> {
+    if requested_pods.iter().map(|(_, pods)| pods).sum::<u32>() <= free_pods {
+        return Ok(());
+    }
+
+    Err(EngineError::new_cannot_deploy_environment_exceeding_free_pods(
+        event_details.clone(),
+        free_pods,
+        requested_pods,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::environment::RequestedPods;
+    use crate::deployment_action::deploy_environment::{
+        check_enough_free_pods, check_sanitized_name_collisions, deploy_services_concurrently,
+        pause_services_or_rollback, report_services_errors, requested_pods_delta, run_with_abort_watcher,
+        services_requested_pods, ServiceToDeploy,
+    };
+    use crate::deployment_action::test_utils::RecordingLogger;
+    use crate::deployment_report::CancellationToken;
+    use crate::errors::{EngineError, MultiEngineError, Tag};
+    use crate::events::{EnvironmentStep, Stage, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use k8s_openapi::api::core::v1::Pod;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use std::collections::{BTreeMap, HashSet};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use uuid::Uuid;
+
+    fn service_to_deploy(id: Uuid, depends_on: Vec<Uuid>) -> ServiceToDeploy<Uuid> {
+        ServiceToDeploy {
+            id,
+            depends_on,
+            service: id,
+        }
+    }
+
+    #[test]
+    fn test_check_enough_free_pods() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+
+        let requested_pods = vec![
+            ("my-postgresql".to_string(), 1),
+            ("my-job".to_string(), 1),
+            ("my-container".to_string(), 3),
+            ("my-app".to_string(), 2),
+        ];
+
+        // execute & verify:
+        assert!(check_enough_free_pods(&event_details, &requested_pods, 7).is_ok());
+        assert!(check_enough_free_pods(&event_details, &[], 0).is_ok());
+
+        let err = check_enough_free_pods(&event_details, &requested_pods, 4).expect_err("should exceed free pods");
+        assert_eq!(&Tag::NotEnoughResourcesToDeployEnvironment, err.tag());
+        assert!(err.user_log_message().contains("free 4 VS 7 required"));
+        assert!(err.user_log_message().contains(
+            "`my-postgresql` (1 pod(s)), `my-job` (1 pod(s)), `my-container` (3 pod(s)), `my-app` (2 pod(s))"
+        ));
+    }
+
+    #[test]
+    fn test_requested_pods_delta_only_counts_pods_added_to_running_services() {
+        // setup:
+        let pod = |labels: &[(&str, &str)]| Pod {
+            metadata: ObjectMeta {
+                labels: Some(
+                    labels
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect::<BTreeMap<_, _>>(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let requested = |service_name: &str, selector: Option<&str>, pods: u32| RequestedPods {
+            service_name: service_name.to_string(),
+            selector: selector.map(str::to_string),
+            pods,
+        };
+        let running_pods = vec![
+            pod(&[("appId", "app"), ("envId", "env")]),
+            pod(&[("appId", "app"), ("envId", "env")]),
+            pod(&[("qovery.com/service-id", "container")]),
+            pod(&[("databaseId", "db")]),
+        ];
+
+        for (requested_pods, expected_delta) in [
+            // new service
+            (vec![requested("my-job", Some("qovery.com/service-id=job"), 1)], 1),
+            // redeployed services at the same scale
+            (
+                vec![
+                    requested("my-app", Some("appId=app"), 2),
+                    requested("my-postgresql", Some("databaseId=db"), 1),
+                ],
+                0,
+            ),
+            // redeployed service scaled up
+            (vec![requested("my-container", Some("qovery.com/service-id=container"), 3)], 2),
+            // redeployed service scaled down
+            (vec![requested("my-app", Some("appId=app,envId=env"), 1)], 0),
+            // pods must have every label of the selector
+            (vec![requested("my-app", Some("appId=app,envId=other"), 1)], 1),
+            // service without any selector
+            (vec![requested("my-app", None, 2)], 2),
+        ] {
+            // execute & verify:
+            assert_eq!(expected_delta, requested_pods_delta(&requested_pods, &running_pods));
+        }
+    }
+
+    #[test]
+    fn test_services_requested_pods_leaves_out_services_adding_no_pod() {
+        // setup:
+        let running_pods = vec![Pod {
+            metadata: ObjectMeta {
+                labels: Some(BTreeMap::from([("appId".to_string(), "app".to_string())])),
+                ..Default::default()
+            },
+            ..Default::default()
+        }];
+        let requested_pods = vec![
+            RequestedPods {
+                service_name: "my-app".to_string(),
+                selector: Some("appId=app".to_string()),
+                pods: 1,
+            },
+            RequestedPods {
+                service_name: "my-container".to_string(),
+                selector: Some("qovery.com/service-id=container".to_string()),
+                pods: 2,
+            },
+        ];
+
+        // execute:
+        let services_pods = services_requested_pods(&requested_pods, &running_pods);
+
+        // verify:
+        assert_eq!(vec![("my-container".to_string(), 2)], services_pods);
+    }
+
+    #[test]
+    fn test_check_sanitized_name_collisions() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let collisions = vec![("my-app".to_string(), vec!["my_app".to_string(), "my-app".to_string()])];
+
+        // execute & verify:
+        assert!(check_sanitized_name_collisions(&event_details, &[]).is_ok());
+
+        let err = check_sanitized_name_collisions(&event_details, &collisions).expect_err("should collide");
+        assert_eq!(&Tag::InvalidEnginePayload, err.tag());
+        assert!(err
+            .user_log_message()
+            .contains("`my_app`, `my-app` are all named `my-app` once sanitized"));
+    }
+
+    #[test]
+    fn test_deploy_services_concurrently_respects_dependencies() {
+        // setup:
+        let (app_1, app_2, router) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let services = vec![
+            service_to_deploy(app_1, vec![]),
+            service_to_deploy(app_2, vec![]),
+            service_to_deploy(router, vec![app_1]),
+        ];
+        let apps_in_progress = AtomicUsize::new(0);
+        let max_apps_in_progress = AtomicUsize::new(0);
+        let deployed = Mutex::new(vec![]);
+
+        // execute:
+        let (started, errors) = deploy_services_concurrently(services, 4, &|id: &Uuid| {
+            if *id != router {
+                // wait for the other app to start, which never happens if apps are deployed one after the other
+                let in_progress = apps_in_progress.fetch_add(1, Ordering::SeqCst) + 1;
+                max_apps_in_progress.fetch_max(in_progress, Ordering::SeqCst);
+                let deadline = Instant::now() + Duration::from_secs(5);
+                while apps_in_progress.load(Ordering::SeqCst) < 2 && Instant::now() < deadline {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                max_apps_in_progress.fetch_max(apps_in_progress.load(Ordering::SeqCst), Ordering::SeqCst);
+            }
+            deployed.lock().unwrap().push(*id);
+            Ok(())
+        });
+
+        // verify:
+        assert!(errors.is_empty());
+        assert_eq!(HashSet::from([app_1, app_2, router]), started);
+        assert_eq!(2, max_apps_in_progress.load(Ordering::SeqCst));
+        let deployed = deployed.into_inner().unwrap();
+        let position = |id: &Uuid| deployed.iter().position(|deployed_id| deployed_id == id).unwrap();
+        assert!(position(&app_1) < position(&router));
+    }
+
+    #[test]
+    fn test_deploy_services_concurrently_stops_on_error() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let (database, app, router) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let services = vec![
+            service_to_deploy(database, vec![]),
+            service_to_deploy(app, vec![database]),
+            service_to_deploy(router, vec![app]),
+        ];
+
+        // execute:
+        let (started, errors) = deploy_services_concurrently(services, 1, &|id: &Uuid| {
+            if *id == app {
+                return Err(EngineError::new_unknown(
+                    event_details.clone(),
+                    "cannot deploy app".to_string(),
+                    None,
+                    None,
+                    None,
+                ));
+            }
+            Ok(())
+        });
+
+        // verify:
+        assert_eq!(HashSet::from([database, app]), started);
+        assert_eq!(1, errors.errors().len());
+        assert_eq!(&Tag::Unknown, errors.first_cancel_or_error().unwrap().tag());
+    }
+
+    #[test]
+    fn test_run_with_abort_watcher_cancels_running_task() {
+        // setup:
+        let cancellation_token = CancellationToken::new();
+        let abort_requested_at = Instant::now() + Duration::from_millis(100);
+        let should_abort = move || Instant::now() >= abort_requested_at;
+
+        // execute: task only stops once cancelled
+        let is_cancelled =
+            run_with_abort_watcher(&should_abort, &cancellation_token, Duration::from_millis(10), || {
+                let deadline = Instant::now() + Duration::from_secs(10);
+                while !cancellation_token.is_cancelled() && Instant::now() < deadline {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                cancellation_token.is_cancelled()
+            });
+
+        // verify:
+        assert!(is_cancelled);
+    }
+
+    #[test]
+    fn test_run_with_abort_watcher_stops_with_the_task() {
+        // setup:
+        let cancellation_token = CancellationToken::new();
+        let polls = AtomicUsize::new(0);
+        let should_abort = || {
+            polls.fetch_add(1, Ordering::SeqCst);
+            false
+        };
+
+        // execute:
+        let started_at = Instant::now();
+        let ret = run_with_abort_watcher(&should_abort, &cancellation_token, Duration::from_secs(60), || 42);
+
+        // verify: watcher doesn't wait for its next poll to stop
+        assert_eq!(42, ret);
+        assert!(started_at.elapsed() < Duration::from_secs(10));
+        assert_eq!(0, polls.load(Ordering::SeqCst));
+        assert!(!cancellation_token.is_cancelled());
+    }
+
+    #[test]
+    fn test_pause_services_or_rollback_resumes_paused_services() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Environment(EnvironmentStep::Pause))
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let (app_1, app_2, app_3) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let paused = Mutex::new(vec![]);
+        let resumed = Mutex::new(vec![]);
+        let logger = RecordingLogger::default();
+
+        // execute:
+        let paused_services = pause_services_or_rollback(
+            vec![(app_1, app_1), (app_2, app_2), (app_3, app_3)].into_iter(),
+            &|id: &Uuid| {
+                if *id == app_2 {
+                    return Err(EngineError::new_unknown(
+                        event_details.clone(),
+                        "cannot pause app".to_string(),
+                        None,
+                        None,
+                        None,
+                    ));
+                }
+                paused.lock().unwrap().push(*id);
+                Ok(())
+            },
+            &|id: &Uuid| {
+                resumed.lock().unwrap().push(*id);
+                Ok(())
+            },
+            &logger,
+            &event_details,
+        );
+
+        // verify:
+        assert_eq!(Some(&Tag::Unknown), paused_services.result.as_ref().err().map(|err| err.tag()));
+        assert_eq!(HashSet::from([app_1, app_2]), paused_services.started);
+        assert_eq!(vec![app_1], paused.into_inner().unwrap());
+        assert_eq!(vec![app_1], resumed.into_inner().unwrap());
+        let messages = logger.messages.lock().unwrap();
+        assert_eq!(2, messages.len());
+        assert!(messages[0].contains(&format!(
+            "Cannot pause service {}, resuming the 1 service(s) already paused",
+            app_2
+        )));
+        assert!(messages[1].contains(&format!("Service {} has been resumed", app_1)));
+    }
+
+    #[test]
+    fn test_pause_services_or_rollback_does_not_resume_on_cancel() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .stage(Stage::Environment(EnvironmentStep::Pause))
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let (app_1, app_2) = (Uuid::new_v4(), Uuid::new_v4());
+        let resumed = Mutex::new(vec![]);
+        let logger = RecordingLogger::default();
+
+        // execute:
+        let paused_services = pause_services_or_rollback(
+            vec![(app_1, app_1), (app_2, app_2)].into_iter(),
+            &|id: &Uuid| match *id == app_2 {
+                true => Err(EngineError::new_task_cancellation_requested(event_details.clone())),
+                false => Ok(()),
+            },
+            &|id: &Uuid| {
+                resumed.lock().unwrap().push(*id);
+                Ok(())
+            },
+            &logger,
+            &event_details,
+        );
+
+        // verify:
+        assert!(paused_services
+            .result
+            .expect_err("pause should be cancelled")
+            .tag()
+            .is_cancel());
+        assert!(resumed.into_inner().unwrap().is_empty());
+        assert!(logger.messages.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_report_services_errors() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let app_error =
+            |message: &str| EngineError::new_unknown(event_details.clone(), message.to_string(), None, None, None);
+        let test_cases = vec![
+            (MultiEngineError::new(), None, 0),
+            (MultiEngineError::from(vec![app_error("app-1 failed")]), Some(Tag::Unknown), 0),
+            (
+                MultiEngineError::from(vec![
+                    app_error("app-1 failed"),
+                    EngineError::new_task_cancellation_requested(event_details.clone()),
+                    app_error("app-2 failed"),
+                ]),
+                Some(Tag::TaskCancellationRequested),
+                1,
+            ),
+        ];
+
+        for (errors, expected_tag, expected_messages) in test_cases {
+            let logger = RecordingLogger::default();
+
+            // execute:
+            let result = report_services_errors(&errors, &logger, &event_details);
+
+            // verify: a single failure is only returned, several ones are all reported
+            assert_eq!(expected_tag, result.err().map(|err| err.tag().clone()));
+            let messages = logger.messages.lock().unwrap();
+            assert_eq!(expected_messages, messages.len());
+            if let Some(message) = messages.first() {
+                assert!(message.contains("3 error(s) occurred"), "{}", message);
+                assert!(
+                    message.contains("app-1 failed") && message.contains("app-2 failed"),
+                    "{}",
+                    message
+                );
+            }
+        }
+    }
+}
@@ -1,18 +1,14 @@
 use crate::cloud_provider::helm::ChartInfo;
+use crate::cloud_provider::kubernetes::kube_wait_for_pods_deletion;
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::command::CommandKiller;
 use crate::deployment_action::DeploymentAction;
 use crate::errors::EngineError;
 use crate::events::EventDetails;
-use crate::runtime::block_on;
 use crate::template::generate_and_copy_all_files_into_dir;
-use k8s_openapi::api::core::v1::Pod;
-use kube::api::ListParams;
-use kube::Api;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tera::Context as TeraContext;
-use tokio::time::Instant;
 
 const DEFAULT_HELM_TIMEOUT: Duration = Duration::from_secs(10 * 60);
 /// Helm Deployment manages Helm + jinja support
@@ -86,6 +82,11 @@ impl DeploymentAction for HelmDeployment {
         // print diff in logs
         let _ = target.helm.upgrade_diff(&self.helm_chart, &[]);
 
+        if target.is_dry_run_deploy {
+            target.log_dry_run(&self.event_details, format!("helm upgrade of chart {}", self.helm_chart.name));
+            return Ok(());
+        }
+
         //upgrade
         target
             .helm
@@ -98,6 +99,11 @@ impl DeploymentAction for HelmDeployment {
     }
 
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        if target.is_dry_run_deploy {
+            target.log_dry_run(&self.event_details, format!("helm uninstall of chart {}", self.helm_chart.name));
+            return Ok(());
+        }
+
         target
             .helm
             .uninstall(&self.helm_chart, &[])
@@ -106,22 +112,12 @@ impl DeploymentAction for HelmDeployment {
         // helm does not wait for pod to terminate https://github.com/helm/helm/issues/10586
         // So wait for
         if let Some(pod_selector) = &self.helm_chart.k8s_selector {
-            block_on(async {
-                let started = Instant::now();
-
-                let pods: Api<Pod> = Api::namespaced(target.kube.clone(), target.environment.namespace());
-                while let Ok(pod) = pods.list(&ListParams::default().labels(pod_selector)).await {
-                    if pod.items.is_empty() {
-                        break;
-                    }
-
-                    if started.elapsed() > DEFAULT_HELM_TIMEOUT {
-                        break;
-                    }
-
-                    tokio::time::sleep(Duration::from_secs(10)).await;
-                }
-            });
+            let _ = kube_wait_for_pods_deletion(
+                &target.kube,
+                target.environment.namespace(),
+                pod_selector,
+                DEFAULT_HELM_TIMEOUT,
+            );
         }
 
         Ok(())
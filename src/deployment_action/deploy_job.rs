@@ -4,7 +4,9 @@ use crate::cloud_provider::service::{Action, Service};
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::kubectl::kubectl_get_job_pod_output;
 use crate::deployment_action::deploy_helm::HelmDeployment;
-use crate::deployment_action::utils::{get_last_deployed_image, mirror_image, KubeObjectKind};
+use crate::deployment_action::utils::{
+    block_on_unless_cancelled, get_last_deployed_image, mirror_image, KubeObjectKind,
+};
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::job::reporter::JobDeploymentReporter;
 use crate::deployment_report::logger::{EnvProgressLogger, EnvSuccessLogger};
@@ -49,15 +51,22 @@ where
                     pre_run: &pre_run,
                     run: &run,
                     post_run_success: &post_run,
+                    post_run_failure: None,
                 };
 
-                execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Create), task)
+                execute_long_deployment(
+                    JobDeploymentReporter::new(self, target, Action::Create),
+                    task,
+                    &target.cancellation_token,
+                )
             }
             JobSchedule::OnPause {} | JobSchedule::OnDelete {} => {
                 let job_reporter = JobDeploymentReporter::new(self, target, Action::Create);
-                execute_long_deployment(job_reporter, |_logger: &EnvProgressLogger| -> Result<(), EngineError> {
-                    Ok(())
-                })
+                execute_long_deployment(
+                    job_reporter,
+                    |_logger: &EnvProgressLogger| -> Result<(), EngineError> { Ok(()) },
+                    &target.cancellation_token,
+                )
             }
         }
     }
@@ -71,8 +80,13 @@ where
                     pre_run: &pre_run,
                     run: &run,
                     post_run_success: &post_run,
+                    post_run_failure: None,
                 };
-                execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Pause), task)
+                execute_long_deployment(
+                    JobDeploymentReporter::new(self, target, Action::Pause),
+                    task,
+                    &target.cancellation_token,
+                )
             }
             JobSchedule::OnPause {} => {
                 let (pre_run, run, post_run) = run_job(self, target, &event_details);
@@ -80,19 +94,35 @@ where
                     pre_run: &pre_run,
                     run: &run,
                     post_run_success: &post_run,
+                    post_run_failure: None,
                 };
 
-                execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Pause), task)
+                execute_long_deployment(
+                    JobDeploymentReporter::new(self, target, Action::Pause),
+                    task,
+                    &target.cancellation_token,
+                )
             }
             JobSchedule::OnStart {} | JobSchedule::OnDelete {} => {
                 let job_reporter = JobDeploymentReporter::new(self, target, Action::Pause);
-                execute_long_deployment(job_reporter, |_logger: &EnvProgressLogger| -> Result<(), EngineError> {
-                    Ok(())
-                })
+                execute_long_deployment(
+                    job_reporter,
+                    |_logger: &EnvProgressLogger| -> Result<(), EngineError> { Ok(()) },
+                    &target.cancellation_token,
+                )
             }
         }
     }
 
+    fn on_restart(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        // A restart is not a pause of the environment, OnPause jobs must not run
+        match self.schedule() {
+            JobSchedule::OnPause {} => {}
+            JobSchedule::Cron { .. } | JobSchedule::OnStart {} | JobSchedule::OnDelete {} => self.on_pause(target)?,
+        }
+        self.on_create(target)
+    }
+
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
         let event_details = self.get_event_details(Stage::Environment(self.action().to_environment_step()));
         match self.schedule() {
@@ -102,9 +132,14 @@ where
                     pre_run: &pre_run,
                     run: &run,
                     post_run_success: &post_run,
+                    post_run_failure: None,
                 };
 
-                execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Delete), task)
+                execute_long_deployment(
+                    JobDeploymentReporter::new(self, target, Action::Delete),
+                    task,
+                    &target.cancellation_token,
+                )
             }
             JobSchedule::Cron { .. } | JobSchedule::OnStart {} | JobSchedule::OnPause {} => Ok(()),
         }?;
@@ -114,8 +149,13 @@ where
             pre_run: &pre_run,
             run: &run,
             post_run_success: &post_run,
+            post_run_failure: None,
         };
-        execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Delete), task)
+        execute_long_deployment(
+            JobDeploymentReporter::new(self, target, Action::Delete),
+            task,
+            &target.cancellation_token,
+        )
     }
 }
 
@@ -187,6 +227,11 @@ where
         }
         helm.on_create(target)?;
 
+        // nothing has been started, so there is no job to wait for
+        if target.is_dry_run_deploy {
+            return Ok(state);
+        }
+
         // Wait for the job to terminate in order to have his status
         // For cronjob we dont care as we don't control when it is executed
         if !job.schedule().is_cronjob() {
@@ -218,14 +263,15 @@ where
                 "Waiting for the job container {} to terminate...",
                 job.kube_service_name()
             ));
-            let _ = block_on(async {
+            let _ = block_on_unless_cancelled(
                 await_condition(
                     kube_pod_api.clone(),
                     pod_name,
                     is_job_pod_container_terminated(job.kube_service_name().as_str()),
-                )
-                .await
-            });
+                ),
+                &target.cancellation_token,
+                event_details,
+            )?;
 
             // Get JSON output from shared volume
             let result_json_output = kubectl_get_job_pod_output(
@@ -278,7 +324,11 @@ where
 
             // wait for job to finish
             let jobs: Api<K8sJob> = Api::namespaced(target.kube.clone(), target.environment.namespace());
-            let ret = block_on(async { await_condition(jobs, &job.kube_service_name(), is_job_terminated()).await });
+            let ret = block_on_unless_cancelled(
+                await_condition(jobs, &job.kube_service_name(), is_job_terminated()),
+                &target.cancellation_token,
+                event_details,
+            )?;
             let ret = ret.unwrap();
             match job_status(&ret.as_ref()) {
                 JobStatus::Success => Ok(()),
@@ -295,7 +345,14 @@ where
 
     let post_run = move |logger: &EnvSuccessLogger, state: TaskContext| {
         // Delete previous image from cache to cleanup resources
-        if let Err(err) = delete_cached_image(job.tag_for_mirror(), state.last_deployed_image, false, target, logger) {
+        if let Err(err) = delete_cached_image(
+            job.tag_for_mirror(),
+            state.last_deployed_image,
+            false,
+            target,
+            logger,
+            event_details,
+        ) {
             error!("Failed to delete previous image from cache: {}", err);
         }
     };
@@ -358,7 +415,14 @@ where
 
     let post_run = move |logger: &EnvSuccessLogger, state: TaskContext| {
         // Delete previous image from cache to cleanup resources
-        if let Err(err) = delete_cached_image(job.tag_for_mirror(), state.last_deployed_image, false, target, logger) {
+        if let Err(err) = delete_cached_image(
+            job.tag_for_mirror(),
+            state.last_deployed_image,
+            false,
+            target,
+            logger,
+            event_details,
+        ) {
             error!("Failed to delete previous image from cache: {}", err);
         }
     };
@@ -1,5 +1,5 @@
 use crate::cloud_provider::kubernetes::{
-    kube_copy_secret_to_another_namespace, kube_create_namespace_if_not_exists, kube_does_secret_exists, Kind,
+    kube_copy_secret_to_another_namespace, kube_create_or_update_namespace, kube_does_secret_exists, Kind,
 };
 use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_action::DeploymentAction;
@@ -19,19 +19,35 @@ pub struct NamespaceDeployment {
 
 impl DeploymentAction for NamespaceDeployment {
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
-        let mut namespace_labels: Option<BTreeMap<String, String>> = None;
+        let advanced_settings = target.kubernetes.advanced_settings();
+        let mut namespace_labels: BTreeMap<String, String> = advanced_settings
+            .environment_namespace_labels
+            .clone()
+            .into_iter()
+            .collect();
         if let Some(resource_expiration) = &self.resource_expiration {
-            namespace_labels = Some(BTreeMap::from([(
-                "ttl".to_string(),
-                format!("{}", resource_expiration.as_secs()),
-            )]));
+            namespace_labels.insert("ttl".to_string(), format!("{}", resource_expiration.as_secs()));
         };
+        let namespace_annotations: BTreeMap<String, String> = advanced_settings
+            .environment_namespace_annotations
+            .clone()
+            .into_iter()
+            .collect();
 
-        // create a namespace with labels if it does not exist
-        block_on(kube_create_namespace_if_not_exists(
+        if target.is_dry_run_deploy {
+            target.log_dry_run(
+                &self.event_details,
+                format!("creation of namespace {}", target.environment.namespace()),
+            );
+            return Ok(());
+        }
+
+        // create the namespace, or reconcile its labels and annotations if it already exists
+        block_on(kube_create_or_update_namespace(
             &target.kube,
             target.environment.namespace(),
             namespace_labels,
+            namespace_annotations,
         ))
         .map_err(|e| {
             EngineError::new_k8s_create_namespace(
@@ -78,6 +94,14 @@ impl DeploymentAction for NamespaceDeployment {
     }
 
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        if target.is_dry_run_deploy {
+            target.log_dry_run(
+                &self.event_details,
+                format!("deletion of namespace {}", target.environment.namespace()),
+            );
+            return Ok(());
+        }
+
         block_on(async {
             let api: Api<Namespace> = Api::all(target.kube.clone());
             if api.get(target.environment.namespace()).await.is_ok() {
@@ -65,7 +65,9 @@ where
                 pre_run: &pre_run,
                 run: &run,
                 post_run_success: &post_run,
+                post_run_failure: None,
             },
+            &target.cancellation_token,
         )
     }
 
@@ -73,6 +75,7 @@ where
         execute_long_deployment(
             RouterDeploymentReporter::new(self, target, Action::Pause),
             |_logger: &EnvProgressLogger| -> Result<(), EngineError> { Ok(()) },
+            &target.cancellation_token,
         )
     }
 
@@ -98,6 +101,7 @@ where
                 helm.on_delete(target)
                 // FIXME: Delete also certificates
             },
+            &target.cancellation_token,
         )
     }
 }
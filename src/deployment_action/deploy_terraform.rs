@@ -92,18 +92,26 @@ impl TerraformDeployment {
 }
 
 impl DeploymentAction for TerraformDeployment {
-    fn on_create(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+    fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
         self.prepare_terraform_files()?;
         let ret = cmd::terraform::terraform_init_validate_plan_apply(
             &self.destination_folder.to_string_lossy(),
             self.is_dry_run,
+            target.kubernetes.advanced_settings().terraform_parallelism,
         );
 
         if let Err(err) = ret {
-            Err(EngineError::new_terraform_error(self.event_details.clone(), err))
-        } else {
-            Ok(())
+            return Err(EngineError::new_terraform_error(self.event_details.clone(), err));
         }
+
+        if self.is_dry_run {
+            target.log_dry_run(
+                &self.event_details,
+                format!("terraform apply of {}", self.destination_folder.to_string_lossy()),
+            );
+        }
+
+        Ok(())
     }
 
     fn on_pause(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
@@ -112,7 +120,18 @@ impl DeploymentAction for TerraformDeployment {
 
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
         self.prepare_terraform_files()?;
-        match cmd::terraform::terraform_init_validate_destroy(&self.destination_folder.to_string_lossy(), false) {
+        match cmd::terraform::terraform_init_validate_destroy(
+            &self.destination_folder.to_string_lossy(),
+            self.is_dry_run,
+            target.kubernetes.advanced_settings().terraform_parallelism,
+        ) {
+            Ok(_) if self.is_dry_run => {
+                target.log_dry_run(
+                    &self.event_details,
+                    format!("terraform destroy of {}", self.destination_folder.to_string_lossy()),
+                );
+                Ok(())
+            }
             Ok(_) => {
                 if let Err(err) = TerraformDeployment::delete_tfstate_secret(
                     target.kubernetes,
@@ -2,6 +2,7 @@ use crate::cloud_provider::service::Action;
 use crate::cloud_provider::DeploymentTarget;
 use crate::errors::EngineError;
 
+mod blue_green;
 mod check_dns;
 mod deploy_application;
 mod deploy_container;
@@ -13,19 +14,34 @@ pub mod deploy_namespace;
 mod deploy_router;
 mod deploy_terraform;
 mod pause_service;
+pub mod preflight;
 #[cfg(test)]
 mod test_utils;
 mod utils;
 
-pub trait DeploymentAction {
+// When `DeploymentTarget::is_dry_run_deploy` is set, implementations must not mutate anything,
+// and log what would have been done instead.
+pub trait DeploymentAction: Send + Sync {
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError>;
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), EngineError>;
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError>;
+    /// Scales back up the workloads scaled down by `on_pause`, without redeploying anything.
+    /// Services with nothing to scale back (i.e: jobs) keep this no-op, managed databases are started again.
+    fn on_resume(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+        Ok(())
+    }
+    /// Restarts the service without deleting it. Services able to restart in place should override it,
+    /// as well as services whose pause has side effects (i.e: jobs running on pause).
+    fn on_restart(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.on_pause(target)?;
+        self.on_create(target)
+    }
     fn exec_action(&self, deployment_target: &DeploymentTarget, action: Action) -> Result<(), EngineError> {
         match action {
             Action::Create => self.on_create(deployment_target),
             Action::Delete => self.on_delete(deployment_target),
             Action::Pause => self.on_pause(deployment_target),
+            Action::Restart => self.on_restart(deployment_target),
         }
     }
 }
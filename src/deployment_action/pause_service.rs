@@ -89,12 +89,15 @@ async fn unpause_service_if_needed(
     namespace: &str,
     selector: &str,
     is_statefulset: bool,
+    replicas: i32,
 ) -> Result<(), kube::Error> {
     let list_params = ListParams::default().labels(selector);
     let patch_params = PatchParams::default();
     let new_scale = Scale {
         metadata: Default::default(),
-        spec: Some(ScaleSpec { replicas: Some(1_i32) }),
+        spec: Some(ScaleSpec {
+            replicas: Some(replicas),
+        }),
         status: None,
     };
     let patch = Patch::Merge(new_scale);
@@ -145,11 +148,22 @@ impl PauseServiceAction {
     }
 
     pub fn unpause_if_needed(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.scale_up_if_paused(target, 1)
+    }
+
+    /// Scales the paused workloads back to `replicas`, workloads still running are left untouched.
+    pub fn scale_up_if_paused(&self, target: &DeploymentTarget, replicas: u32) -> Result<(), EngineError> {
+        if target.is_dry_run_deploy {
+            target.log_dry_run(&self.event_details, format!("un-pause of {}", self.selector));
+            return Ok(());
+        }
+
         let fut = unpause_service_if_needed(
             &target.kube,
             target.environment.namespace(),
             &self.selector,
             self.is_statefulset,
+            replicas as i32,
         );
 
         match block_on(async { tokio::time::timeout(self.timeout, fut).await }) {
@@ -193,6 +207,11 @@ impl DeploymentAction for PauseServiceAction {
     }
 
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        if target.is_dry_run_deploy {
+            target.log_dry_run(&self.event_details, format!("pause of {}", self.selector));
+            return Ok(());
+        }
+
         let fut = pause_service(
             &target.kube,
             target.environment.namespace(),
@@ -397,7 +416,11 @@ mod tests {
         )
         .await??;
 
-        tokio::time::timeout(timeout, unpause_service_if_needed(&kube_client, &namespace, &selector, false)).await??;
+        tokio::time::timeout(
+            timeout,
+            unpause_service_if_needed(&kube_client, &namespace, &selector, false, 1),
+        )
+        .await??;
         tokio::time::timeout(
             timeout,
             await_condition(deployments.clone(), &app_name, has_deployment_ready_replicas(1)),
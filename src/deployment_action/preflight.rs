@@ -0,0 +1,461 @@
+// Preflight report of an environment deployment: every check is run without mutating anything,
+// and all of them are run even if one fails, so the user gets every blocking issue at once.
+use crate::cloud_provider::environment::Environment;
+use crate::cloud_provider::service::Action;
+use crate::cloud_provider::DeploymentTarget;
+use crate::cmd::kubectl::kubectl_exec_version;
+use crate::cmd::structs::KubernetesVersion;
+use crate::deployment_action::deploy_environment::{
+    check_enough_free_pods, check_sanitized_name_collisions, cluster_pods_usage, environment_requested_pods,
+};
+use crate::errors::{CommandError, EngineError};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
+use crate::logger::Logger;
+use crate::models::database::DatabaseService;
+use crate::runtime::block_on;
+use k8s_openapi::api::core::v1::ResourceQuota;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::api::ListParams;
+use kube::Api;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreflightCheck {
+    CloudProviderCredentials,
+    DnsProviderCredentials,
+    KubernetesVersion,
+    ClusterCapacity,
+    NamespaceQuotas,
+    DatabaseVersion,
+    ServicesNaming,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Deployment can go on, but something may not behave as expected.
+    Warn(String),
+    /// Deployment would fail.
+    Fail(Box<EngineError>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    pub check: PreflightCheck,
+    pub status: CheckStatus,
+}
+
+impl CheckResult {
+    fn from_engine_result(check: PreflightCheck, result: Result<(), EngineError>) -> CheckResult {
+        CheckResult {
+            check,
+            status: match result {
+                Ok(_) => CheckStatus::Pass,
+                Err(err) => CheckStatus::Fail(Box::new(err)),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreflightReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl PreflightReport {
+    /// Deployment can start if no check failed, warnings don't block it.
+    pub fn is_go(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.status, CheckStatus::Fail(_)))
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.status, CheckStatus::Warn(_)))
+    }
+
+    /// Logs warnings and fails with the error of the first failing check, the other failing ones being logged.
+    pub fn check(&self, logger: &dyn Logger, event_details: &EventDetails) -> Result<(), EngineError> {
+        for check in &self.checks {
+            if let CheckStatus::Warn(message) = &check.status {
+                logger.log(EngineEvent::Warning(
+                    event_details.clone(),
+                    EventMessage::new_from_safe(format!("Preflight check {:?}: {}", check.check, message)),
+                ));
+            }
+        }
+
+        let mut errors = self.checks.iter().filter_map(|check| match &check.status {
+            CheckStatus::Fail(err) => Some(err.as_ref()),
+            _ => None,
+        });
+        let first_error = match errors.next() {
+            Some(err) => err,
+            None => return Ok(()),
+        };
+        for err in errors {
+            logger.log(EngineEvent::Error(err.clone(), None));
+        }
+
+        Err(first_error.clone())
+    }
+}
+
+pub fn preflight(environment: &Environment, target: &DeploymentTarget) -> PreflightReport {
+    let event_details = environment.event_details_with_step(EnvironmentStep::Deploy);
+    let kubernetes = target.kubernetes;
+
+    let server_version = kubernetes.get_kubeconfig_file_path().map(|kubeconfig| {
+        kubectl_exec_version(kubeconfig, kubernetes.cloud_provider().credentials_environment_variables())
+    });
+
+    let capacity = match cluster_pods_usage(target, &event_details) {
+        Ok(None) => CheckResult {
+            check: PreflightCheck::ClusterCapacity,
+            status: CheckStatus::Warn("no running node, cluster capacity cannot be verified".to_string()),
+        },
+        Ok(Some((free_pods, requested_pods))) => CheckResult::from_engine_result(
+            PreflightCheck::ClusterCapacity,
+            check_enough_free_pods(&event_details, &requested_pods, free_pods),
+        ),
+        Err(err) => CheckResult {
+            check: PreflightCheck::ClusterCapacity,
+            status: CheckStatus::Warn(format!("cluster capacity cannot be verified: {}", err.user_log_message())),
+        },
+    };
+
+    let namespace_quotas = match list_namespace_quotas(target)
+        .map_err(|err| err.to_string())
+        .and_then(|quotas| {
+            environment_requested_pods(target, &event_details)
+                .map(|requested_pods| (quotas, requested_pods))
+                .map_err(|err| err.user_log_message().to_string())
+        }) {
+        Ok((quotas, requested_pods)) => check_namespace_quotas(&event_details, &quotas, requested_pods),
+        Err(err) => CheckResult {
+            check: PreflightCheck::NamespaceQuotas,
+            status: CheckStatus::Warn(format!("namespace quotas cannot be verified: {}", err)),
+        },
+    };
+
+    let mut checks = vec![
+        CheckResult::from_engine_result(
+            PreflightCheck::CloudProviderCredentials,
+            kubernetes.cloud_provider().is_valid(),
+        ),
+        CheckResult::from_engine_result(
+            PreflightCheck::DnsProviderCredentials,
+            kubernetes
+                .dns_provider()
+                .is_valid()
+                .map_err(|err| err.to_engine_error(event_details.clone())),
+        ),
+        match server_version {
+            Ok(server_version) => check_kubernetes_version(&event_details, kubernetes.version(), server_version),
+            Err(err) => CheckResult::from_engine_result(PreflightCheck::KubernetesVersion, Err(err)),
+        },
+        capacity,
+        namespace_quotas,
+    ];
+    checks.extend(
+        environment
+            .databases
+            .iter()
+            .filter(|database| *database.action() == Action::Create)
+            .map(|database| check_database_version(database.as_ref())),
+    );
+    checks.push(CheckResult::from_engine_result(
+        PreflightCheck::ServicesNaming,
+        check_sanitized_name_collisions(&event_details, &environment.sanitized_name_collisions()),
+    ));
+
+    PreflightReport { checks }
+}
+
+fn list_namespace_quotas(target: &DeploymentTarget) -> Result<Vec<ResourceQuota>, kube::Error> {
+    let quotas: Api<ResourceQuota> = Api::namespaced(target.kube.clone(), target.environment.namespace());
+    block_on(quotas.list(&ListParams::default())).map(|quotas| quotas.items)
+}
+
+/// Pods quotas of the environment namespace must allow the pods the deployment adds to it.
+fn check_namespace_quotas(event_details: &EventDetails, quotas: &[ResourceQuota], requested_pods: u32) -> CheckResult {
+    let pods_quantity = |quantities: Option<&BTreeMap<String, Quantity>>| {
+        quantities
+            .and_then(|quantities| quantities.get("pods"))
+            .and_then(|quantity| quantity.0.parse::<u32>().ok())
+    };
+
+    let result = quotas.iter().try_for_each(|quota| {
+        let status = quota.status.as_ref();
+        let max_pods = match pods_quantity(status.and_then(|status| status.hard.as_ref())) {
+            Some(max_pods) => max_pods,
+            None => return Ok(()),
+        };
+        let used_pods = pods_quantity(status.and_then(|status| status.used.as_ref())).unwrap_or(0);
+
+        if used_pods + requested_pods > max_pods {
+            return Err(EngineError::new_cannot_deploy_namespace_pods_quota_exceeded(
+                event_details.clone(),
+                quota.metadata.name.as_deref().unwrap_or_default(),
+                requested_pods,
+                used_pods,
+                max_pods,
+            ));
+        }
+
+        Ok(())
+    });
+
+    CheckResult::from_engine_result(PreflightCheck::NamespaceQuotas, result)
+}
+
+fn check_database_version(database: &dyn DatabaseService) -> CheckResult {
+    let event_details = database.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+    let status = match database.check_version(event_details) {
+        Ok(version) => match version.message() {
+            Some(message) => CheckStatus::Warn(message),
+            None => CheckStatus::Pass,
+        },
+        Err(err) => CheckStatus::Fail(Box::new(err)),
+    };
+
+    CheckResult {
+        check: PreflightCheck::DatabaseVersion,
+        status,
+    }
+}
+
+fn check_kubernetes_version(
+    event_details: &EventDetails,
+    expected_version: &str,
+    server_version: Result<KubernetesVersion, CommandError>,
+) -> CheckResult {
+    let status = match server_version {
+        // some providers suffix the minor version, i.e: EKS returns `22+`
+        Ok(version) => match format!(
+            "{}.{}",
+            version.server_version.major,
+            version.server_version.minor.trim_end_matches('+')
+        ) {
+            server_version if expected_version.starts_with(&server_version) => CheckStatus::Pass,
+            server_version => CheckStatus::Warn(format!(
+                "cluster is running Kubernetes {} while {} is expected, it may be upgrading",
+                server_version, expected_version
+            )),
+        },
+        Err(err) => CheckStatus::Fail(Box::new(EngineError::new_cannot_execute_k8s_exec_version(
+            event_details.clone(),
+            err,
+        ))),
+    };
+
+    CheckResult {
+        check: PreflightCheck::KubernetesVersion,
+        status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::structs::{KubernetesVersion, ServerVersion};
+    use crate::deployment_action::deploy_environment::{check_enough_free_pods, check_sanitized_name_collisions};
+    use crate::deployment_action::preflight::{
+        check_kubernetes_version, check_namespace_quotas, CheckResult, CheckStatus, PreflightCheck, PreflightReport,
+    };
+    use crate::deployment_action::test_utils::RecordingLogger;
+    use crate::errors::{CommandError, EngineError, Tag};
+    use crate::events::{EventDetails, Transmitter};
+    use crate::test_utils::EventDetailsBuilder;
+    use k8s_openapi::api::core::v1::{ResourceQuota, ResourceQuotaStatus};
+    use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use std::collections::BTreeMap;
+    use uuid::Uuid;
+
+    fn server_version(major: &str, minor: &str) -> KubernetesVersion {
+        KubernetesVersion {
+            server_version: ServerVersion {
+                major: major.to_string(),
+                minor: minor.to_string(),
+                git_version: format!("v{}.{}", major, minor),
+            },
+        }
+    }
+
+    fn failing_report(event_details: &EventDetails) -> PreflightReport {
+        let collisions = vec![("my-app".to_string(), vec!["my_app".to_string(), "my-app".to_string()])];
+
+        PreflightReport {
+            checks: vec![
+                CheckResult::from_engine_result(
+                    PreflightCheck::CloudProviderCredentials,
+                    Err(EngineError::new_unknown(
+                        event_details.clone(),
+                        "invalid credentials".to_string(),
+                        None,
+                        None,
+                        None,
+                    )),
+                ),
+                CheckResult::from_engine_result(PreflightCheck::DnsProviderCredentials, Ok(())),
+                check_kubernetes_version(event_details, "1.23", Ok(server_version("1", "22"))),
+                CheckResult::from_engine_result(
+                    PreflightCheck::ClusterCapacity,
+                    check_enough_free_pods(event_details, &[("my-app".to_string(), 3)], 1),
+                ),
+                CheckResult::from_engine_result(
+                    PreflightCheck::ServicesNaming,
+                    check_sanitized_name_collisions(event_details, &collisions),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_check_kubernetes_version() {
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        assert_eq!(
+            CheckStatus::Pass,
+            check_kubernetes_version(&event_details, "1.22", Ok(server_version("1", "22+"))).status
+        );
+        assert!(matches!(
+            check_kubernetes_version(&event_details, "1.23", Ok(server_version("1", "22"))).status,
+            CheckStatus::Warn(_)
+        ));
+        assert!(matches!(
+            check_kubernetes_version(
+                &event_details,
+                "1.22",
+                Err(CommandError::new_from_safe_message("timeout".to_string()))
+            )
+            .status,
+            CheckStatus::Fail(err) if err.underlying_error().unwrap().message_safe().contains("timeout")
+        ));
+    }
+
+    #[test]
+    fn test_check_namespace_quotas() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let quota = |name: &str, hard: Option<&str>, used: Option<&str>| {
+            let quantities = |pods: Option<&str>| {
+                pods.map(|pods| BTreeMap::from([("pods".to_string(), Quantity(pods.to_string()))]))
+            };
+            ResourceQuota {
+                metadata: ObjectMeta {
+                    name: Some(name.to_string()),
+                    ..Default::default()
+                },
+                status: Some(ResourceQuotaStatus {
+                    hard: quantities(hard),
+                    used: quantities(used),
+                }),
+                ..Default::default()
+            }
+        };
+
+        for (quotas, requested_pods, is_exceeded) in [
+            (vec![], 10, false),
+            (vec![quota("pods", Some("10"), Some("4"))], 6, false),
+            (vec![quota("pods", Some("10"), Some("4"))], 7, true),
+            (vec![quota("pods", Some("10"), None)], 10, false),
+            // quotas not limiting pods
+            (vec![quota("cpu", None, None)], 100, false),
+            (vec![quota("cpu", None, None), quota("pods", Some("5"), Some("5"))], 1, true),
+        ] {
+            // execute:
+            let result = check_namespace_quotas(&event_details, &quotas, requested_pods);
+
+            // verify:
+            assert_eq!(PreflightCheck::NamespaceQuotas, result.check);
+            match result.status {
+                CheckStatus::Fail(err) => {
+                    assert!(is_exceeded, "{} pods shouldn't exceed quotas", requested_pods);
+                    assert_eq!(&Tag::NotEnoughResourcesToDeployEnvironment, err.tag());
+                    assert!(err.user_log_message().contains("`pods`"));
+                }
+                status => assert!(!is_exceeded, "{} pods should exceed quotas, got {:?}", requested_pods, status),
+            }
+        }
+    }
+
+    #[test]
+    fn test_preflight_report_surfaces_every_failing_check() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+
+        // execute:
+        let report = failing_report(&event_details);
+
+        // verify:
+        assert!(!report.is_go());
+        assert_eq!(
+            vec![
+                PreflightCheck::CloudProviderCredentials,
+                PreflightCheck::ClusterCapacity,
+                PreflightCheck::ServicesNaming
+            ],
+            report.failures().map(|check| check.check).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![PreflightCheck::KubernetesVersion],
+            report.warnings().map(|check| check.check).collect::<Vec<_>>()
+        );
+        assert_eq!(CheckStatus::Pass, report.checks[1].status);
+    }
+
+    #[test]
+    fn test_preflight_report_check_fails_with_first_failure_and_logs_every_issue() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let report = failing_report(&event_details);
+        let logger = RecordingLogger::default();
+
+        // execute:
+        let err = report
+            .check(&logger, &event_details)
+            .expect_err("report has failing checks");
+
+        // verify:
+        assert_eq!("invalid credentials", err.user_log_message());
+        let messages = logger.messages.lock().unwrap();
+        assert_eq!(3, messages.len());
+        assert!(messages[0].contains("KubernetesVersion"));
+        assert!(messages[1].contains("free 1 VS 3 required"));
+        assert!(messages[2].contains("my_app"));
+    }
+
+    #[test]
+    fn test_preflight_report_is_go_with_warnings_only() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let report = PreflightReport {
+            checks: vec![
+                CheckResult::from_engine_result(PreflightCheck::CloudProviderCredentials, Ok(())),
+                check_kubernetes_version(&event_details, "1.23", Ok(server_version("1", "22"))),
+            ],
+        };
+        let logger = RecordingLogger::default();
+
+        // execute & verify:
+        assert!(report.is_go());
+        assert!(report.check(&logger, &event_details).is_ok());
+        assert_eq!(1, logger.messages.lock().unwrap().len());
+    }
+}
@@ -1,10 +1,12 @@
+use crate::events::{EngineEvent, EventMessageVerbosity};
+use crate::logger::Logger;
 use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
 use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
 use k8s_openapi::api::core::v1::Namespace;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use kube::api::{DeleteParams, PostParams};
 use kube::Api;
-use std::sync::{Arc, Barrier};
+use std::sync::{Arc, Barrier, Mutex};
 use std::thread;
 
 pub fn get_simple_deployment() -> Deployment {
@@ -152,3 +154,22 @@ fn get_namespace(name: String) -> Namespace {
         ..Default::default()
     }
 }
+
+/// Logger keeping the safe message of every logged event.
+#[derive(Clone, Default)]
+pub struct RecordingLogger {
+    pub messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl Logger for RecordingLogger {
+    fn log(&self, event: EngineEvent) {
+        self.messages
+            .lock()
+            .unwrap()
+            .push(event.message(EventMessageVerbosity::SafeOnly));
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Logger> {
+        Box::new(self.clone())
+    }
+}
@@ -1,18 +1,31 @@
 use crate::build_platform::Image;
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::command::CommandKiller;
-use crate::cmd::docker::ContainerImage;
+use crate::cmd::docker::{is_tls_handshake_failure, is_unauthorized_failure, ContainerImage, DockerError};
+use crate::cmd::kubectl::{kubectl_exec_get_image_pull_error_pods, kubectl_exec_rollout_restart_by_selector};
+use crate::cmd::structs::KubernetesPod;
 use crate::container_registry::errors::ContainerRegistryError;
+use crate::container_registry::{with_refreshed_credentials, ImageReference, RefreshedCredentialsError};
 use crate::deployment_report::logger::{EnvProgressLogger, EnvSuccessLogger};
-use crate::errors::EngineError;
+use crate::deployment_report::CancellationToken;
+use crate::errors::{CommandError, EngineError};
 use crate::events::EventDetails;
+use crate::io_models::application::{Port, Protocol};
 use crate::io_models::container::Registry;
 use crate::models::container::QOVERY_MIRROR_REPOSITORY_NAME;
+use crate::runtime::block_on;
 use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
 use k8s_openapi::api::batch::v1::CronJob;
+use k8s_openapi::api::core::v1::{Node, Pod};
 use kube::api::ListParams;
 use kube::Api;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::future::Future;
 use std::time::Duration;
+use uuid::Uuid;
+
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 pub fn delete_cached_image(
     current_image_tag: String,
@@ -20,10 +33,16 @@ pub fn delete_cached_image(
     force_delete: bool,
     target: &DeploymentTarget,
     logger: &EnvSuccessLogger,
+    event_details: &EventDetails,
 ) -> Result<(), ContainerRegistryError> {
     // Delete previous image from cache to cleanup resources
     if let Some(last_image_tag) = last_image.and_then(|img| img.split(':').last().map(str::to_string)) {
         if force_delete || last_image_tag != current_image_tag {
+            if target.is_dry_run_deploy {
+                target.log_dry_run(event_details, format!("deletion of previous cached image {}", last_image_tag));
+                return Ok(());
+            }
+
             logger.send_success(format!("🪓 Deleting previous cached image {}", last_image_tag));
 
             let image = Image {
@@ -41,6 +60,35 @@ pub fn delete_cached_image(
     Ok(())
 }
 
+/// Restarts in place the pods of the service, instead of scaling them down and up again.
+pub fn rollout_restart(
+    target: &DeploymentTarget,
+    selector: &str,
+    is_statefulset: bool,
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    if target.is_dry_run_deploy {
+        target.log_dry_run(&event_details, format!("rollout restart of {}", selector));
+        return Ok(());
+    }
+
+    kubectl_exec_rollout_restart_by_selector(
+        target.kubernetes.get_kubeconfig_file_path()?,
+        selector,
+        target.environment.namespace(),
+        is_statefulset,
+        &target.kubernetes.cloud_provider().credentials_environment_variables(),
+    )
+    .map_err(|err| {
+        EngineError::new_k8s_cannot_rollout_restart(
+            event_details,
+            selector.to_string(),
+            target.environment.namespace().to_string(),
+            err,
+        )
+    })
+}
+
 pub fn mirror_image(
     registry: &Registry,
     image_name: &str,
@@ -50,8 +98,32 @@ pub fn mirror_image(
     logger: &EnvProgressLogger,
     event_details: EventDetails,
 ) -> Result<(), EngineError> {
+    if target.is_dry_run_deploy {
+        target.log_dry_run(
+            &event_details,
+            format!("mirroring of image {}:{} to the cluster registry", image_name, tag),
+        );
+        return Ok(());
+    }
+
     // We need to login to the registry to get access to the image
     let url = registry.get_url_with_credentials();
+
+    // Registries requiring mutual TLS need the client certificate to be presented by the docker daemon
+    // for the whole login/pull, it is removed once done
+    let _client_certificate_files = match registry.client_certificate() {
+        Some(client_certificate) => Some(
+            target
+                .docker
+                .install_registry_client_certificate(
+                    &url,
+                    &client_certificate.certificate,
+                    &client_certificate.private_key,
+                )
+                .map_err(|err| EngineError::new_docker_error(event_details.clone(), err))?,
+        ),
+        None => None,
+    };
     if url.password().is_some() {
         logger.info(format!(
             "🔓 Login to registry {} as user {}",
@@ -88,18 +160,94 @@ pub fn mirror_image(
         (registry_info.get_image_name)(QOVERY_MIRROR_REPOSITORY_NAME),
         vec![tag_for_mirror],
     );
-    if let Err(err) = target.docker.mirror(
-        &source_image,
-        &dest_image,
-        &mut |line| info!("{}", line),
-        &mut |line| warn!("{}", line),
-        &CommandKiller::from(Duration::from_secs(60 * 10), target.should_abort),
-    ) {
-        let err = EngineError::new_docker_error(event_details, err);
+    let mut tls_handshake_failed = false;
+    let mirror_result = with_refreshed_credentials(
+        |force| target.container_registry.refresh_credentials(force),
+        || {
+            let unauthorized = Cell::new(false);
+            let mut stderr = |line: String| {
+                tls_handshake_failed |= is_tls_handshake_failure(&line);
+                unauthorized.set(unauthorized.get() || is_unauthorized_failure(&line));
+                warn!("{}", line)
+            };
+            let cmd_killer = CommandKiller::from(Duration::from_secs(60 * 10), target.should_abort);
+
+            target
+                .docker
+                .pull(&source_image, &mut |line| info!("{}", line), &mut stderr, &cmd_killer)
+                .map_err(|err| (MirrorStep::Pull, err, unauthorized.get()))?;
+            target
+                .docker
+                .tag(
+                    &source_image,
+                    &dest_image,
+                    &mut |line| info!("{}", line),
+                    &mut stderr,
+                    &cmd_killer,
+                )
+                .and_then(|_| {
+                    target
+                        .docker
+                        .push(&dest_image, &mut |line| info!("{}", line), &mut stderr, &cmd_killer)
+                })
+                .map_err(|err| (MirrorStep::Push, err, unauthorized.get()))
+        },
+        |(_, _, unauthorized)| *unauthorized,
+    );
+
+    if let Err(err) = mirror_result {
+        let err = match err {
+            RefreshedCredentialsError::Credentials(err) => {
+                let err = EngineError::new_container_registry_error(event_details, err);
+                let user_err = EngineError::new_engine_error(
+                    err,
+                    format!(
+                        "❌ Failed to mirror image {}/{}: cannot refresh credentials of the cluster registry",
+                        image_name, tag
+                    ),
+                    None,
+                );
+
+                return Err(user_err);
+            }
+            RefreshedCredentialsError::Operation((step, err, _)) => (step, err),
+        };
+
+        if tls_handshake_failed {
+            let err = EngineError::new_container_registry_tls_handshake_failed(
+                event_details,
+                url.host_str().unwrap_or_default(),
+                CommandError::from(err.1),
+            );
+            let user_err = EngineError::new_engine_error(
+                err,
+                format!(
+                    "❌ Failed to mirror image {}/{}: TLS handshake with the registry failed, check its client certificate",
+                    image_name, tag
+                ),
+                None,
+            );
+
+            return Err(user_err);
+        }
+
+        let err = match err {
+            (_, err @ DockerError::PullRateLimited { .. }) => EngineError::new_docker_error(event_details, err),
+            (MirrorStep::Pull, err) => EngineError::new_docker_pull_image_error(
+                event_details,
+                &ImageReference::from(&source_image),
+                CommandError::from(err),
+            ),
+            (MirrorStep::Push, err) => EngineError::new_docker_push_image_error(
+                event_details,
+                &ImageReference::from(&dest_image),
+                CommandError::from(err),
+            ),
+        };
         let user_err = EngineError::new_engine_error(
             err.clone(),
             format!("❌ Failed to mirror image {}/{}: {}", image_name, tag, err),
-            None,
+            err.hint_message().clone(),
         );
 
         return Err(user_err);
@@ -107,6 +255,12 @@ pub fn mirror_image(
     Ok(())
 }
 
+// Step of the image mirroring which failed, to report a pull or a push error
+enum MirrorStep {
+    Pull,
+    Push,
+}
+
 pub enum KubeObjectKind {
     Deployment,
     Statefulset,
@@ -208,3 +362,469 @@ pub async fn get_last_deployed_image(
         }
     }
 }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeConditionIssue {
+    pub node_name: String,
+    pub condition: String,
+    pub message: String,
+}
+
+// Node conditions which prevent new pods from being scheduled, leaving them Pending
+pub fn node_scheduling_issues(nodes: &[Node]) -> Vec<NodeConditionIssue> {
+    let mut issues = vec![];
+    for node in nodes {
+        let node_name = node.metadata.name.clone().unwrap_or_default();
+        let conditions = node
+            .status
+            .as_ref()
+            .and_then(|status| status.conditions.as_ref())
+            .map(|conditions| conditions.as_slice())
+            .unwrap_or_default();
+
+        for condition in conditions {
+            let is_issue = match condition.type_.as_str() {
+                "DiskPressure" | "MemoryPressure" => condition.status == "True",
+                "Ready" => condition.status != "True",
+                _ => false,
+            };
+            if !is_issue {
+                continue;
+            }
+
+            issues.push(NodeConditionIssue {
+                node_name: node_name.clone(),
+                condition: match condition.type_.as_str() {
+                    "Ready" => "NotReady".to_string(),
+                    condition_type => condition_type.to_string(),
+                },
+                message: condition.message.clone().unwrap_or_default(),
+            });
+        }
+    }
+
+    issues
+}
+
+// Pods which can't pull their image never become ready, this is reported instead of a generic startup failure
+pub fn image_pull_error(
+    pods: &[KubernetesPod],
+    event_details: EventDetails,
+    service_id: &Uuid,
+    service_name: &str,
+) -> Option<EngineError> {
+    pods.iter().find_map(|pod| {
+        let waiting = pod.image_pull_error()?;
+        Some(EngineError::new_k8s_image_pull_error(
+            event_details.clone(),
+            service_id.to_string(),
+            service_name.to_string(),
+            pod.metadata.name.to_string(),
+            waiting.message.clone().unwrap_or_default(),
+        ))
+    })
+}
+
+pub fn get_image_pull_error(
+    target: &DeploymentTarget,
+    selector: &str,
+    event_details: EventDetails,
+    service_id: &Uuid,
+    service_name: &str,
+) -> Option<EngineError> {
+    let pods = kubectl_exec_get_image_pull_error_pods(
+        target.kubernetes.get_kubeconfig_file_path().ok()?,
+        Some(target.environment.namespace()),
+        Some(selector),
+        target.kubernetes.cloud_provider().credentials_environment_variables(),
+    )
+    .ok()?;
+
+    image_pull_error(&pods, event_details, service_id, service_name)
+}
+
+// Nodes the not ready pods are running on
+fn failing_pods_nodes(pods: &[Pod]) -> Vec<&str> {
+    pods.iter()
+        .filter(|pod| {
+            !pod.status
+                .as_ref()
+                .and_then(|status| status.conditions.as_ref())
+                .map(|conditions| {
+                    conditions
+                        .iter()
+                        .any(|condition| condition.type_ == "Ready" && condition.status == "True")
+                })
+                .unwrap_or(false)
+        })
+        .filter_map(|pod| pod.spec.as_ref().and_then(|spec| spec.node_name.as_deref()))
+        .collect()
+}
+
+// Node condition explaining why some of the given pods are not ready, only the nodes they run on are considered
+pub fn failing_pods_node_issue(pods: &[Pod], nodes: &[Node]) -> Option<NodeConditionIssue> {
+    let failing_nodes = failing_pods_nodes(pods);
+
+    node_scheduling_issues(nodes)
+        .into_iter()
+        .find(|issue| failing_nodes.contains(&issue.node_name.as_str()))
+}
+
+pub async fn get_failing_pods_node_issue(
+    client: kube::Client,
+    namespace: &str,
+    selector: &str,
+) -> Result<Option<NodeConditionIssue>, kube::Error> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pods = pods.list(&ListParams::default().labels(selector)).await?;
+    if failing_pods_nodes(&pods.items).is_empty() {
+        return Ok(None);
+    }
+
+    let nodes: Api<Node> = Api::all(client);
+    let nodes = nodes.list(&ListParams::default()).await?;
+
+    Ok(failing_pods_node_issue(&pods.items, &nodes.items))
+}
+
+// A deployment error reported as a more specific one is kept as its underlying error
+pub fn to_underlying_error(err: &EngineError) -> CommandError {
+    err.underlying_error()
+        .unwrap_or_else(|| CommandError::new_from_safe_message(err.user_log_message().to_string()))
+}
+
+/// Ensures ports can be exposed: public ports go through the HTTP router, which has no TCP/UDP (L4) routing,
+/// so only HTTP ports can be publicly accessible. A port number can't be declared twice.
+pub fn validate_ports(ports: &[Port], event_details: EventDetails) -> Result<(), EngineError> {
+    let mut seen_ports = HashSet::with_capacity(ports.len());
+    for port in ports {
+        if !seen_ports.insert(port.port) {
+            return Err(EngineError::new_invalid_engine_payload(
+                event_details,
+                format!("Port {} is declared more than once", port.port).as_str(),
+            ));
+        }
+
+        if port.publicly_accessible && port.protocol != Protocol::HTTP {
+            return Err(EngineError::new_invalid_engine_payload(
+                event_details,
+                format!(
+                    "Port {} uses {:?} protocol and cannot be publicly accessible, only HTTP ports can be exposed through the router",
+                    port.port, port.protocol
+                )
+                .as_str(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Waits for `future` to resolve, checking the cancellation token every second while waiting,
+/// so a long wait (i.e: a job to terminate) stops as soon as the deployment is cancelled.
+pub fn block_on_unless_cancelled<F: Future>(
+    future: F,
+    cancellation_token: &CancellationToken,
+    event_details: &EventDetails,
+) -> Result<F::Output, EngineError> {
+    block_on(async {
+        tokio::pin!(future);
+        loop {
+            if cancellation_token.is_cancelled() {
+                return Err(EngineError::new_task_cancellation_requested(event_details.clone()));
+            }
+
+            tokio::select! {
+                output = &mut future => return Ok(output),
+                _ = tokio::time::sleep(CANCELLATION_POLL_INTERVAL) => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::structs::{KubernetesList, KubernetesPod};
+    use crate::deployment_action::utils::{
+        block_on_unless_cancelled, failing_pods_node_issue, image_pull_error, node_scheduling_issues, validate_ports,
+        NodeConditionIssue,
+    };
+    use crate::deployment_report::CancellationToken;
+    use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity, Tag};
+    use crate::events::Transmitter;
+    use crate::io_models::application::{Port, Protocol};
+    use crate::test_utils::EventDetailsBuilder;
+    use k8s_openapi::api::core::v1::{Node, Pod};
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_node_scheduling_issues_disk_pressure() {
+        // setup:
+        let nodes: Vec<Node> = serde_json::from_str(
+            r#"[
+            {
+                "metadata": {"name": "ip-10-0-1-12.eu-west-3.compute.internal"},
+                "status": {
+                    "conditions": [
+                        {"type": "MemoryPressure", "status": "False", "reason": "KubeletHasSufficientMemory"},
+                        {"type": "DiskPressure", "status": "True", "reason": "KubeletHasDiskPressure", "message": "kubelet has disk pressure"},
+                        {"type": "PIDPressure", "status": "False", "reason": "KubeletHasSufficientPID"},
+                        {"type": "Ready", "status": "True", "reason": "KubeletReady"}
+                    ]
+                }
+            },
+            {
+                "metadata": {"name": "ip-10-0-2-34.eu-west-3.compute.internal"},
+                "status": {
+                    "conditions": [
+                        {"type": "DiskPressure", "status": "False", "reason": "KubeletHasNoDiskPressure"},
+                        {"type": "Ready", "status": "True", "reason": "KubeletReady"}
+                    ]
+                }
+            }
+        ]"#,
+        )
+        .expect("cannot parse nodes");
+
+        // execute:
+        let issues = node_scheduling_issues(&nodes);
+
+        // verify:
+        assert_eq!(
+            vec![NodeConditionIssue {
+                node_name: "ip-10-0-1-12.eu-west-3.compute.internal".to_string(),
+                condition: "DiskPressure".to_string(),
+                message: "kubelet has disk pressure".to_string(),
+            }],
+            issues
+        );
+
+        let issue = issues[0].clone();
+        let error = EngineError::new_k8s_node_not_ready_with_condition(
+            EventDetailsBuilder::new().build(),
+            issue.node_name,
+            issue.condition,
+            issue.message,
+            Some(CommandError::new_from_safe_message("helm timed out".to_string())),
+        );
+        assert_eq!(&Tag::K8sNodeIsNotReady, error.tag());
+        assert_eq!(
+            "Error, node `ip-10-0-1-12.eu-west-3.compute.internal` has condition `DiskPressure`, pods can't be scheduled on it.",
+            error.user_log_message()
+        );
+        assert!(error
+            .message(ErrorMessageVerbosity::FullDetails)
+            .contains("kubelet has disk pressure"));
+        assert!(error
+            .message(ErrorMessageVerbosity::FullDetails)
+            .contains("helm timed out"));
+    }
+
+    #[test]
+    fn test_node_scheduling_issues_not_ready() {
+        // setup:
+        let nodes: Vec<Node> = serde_json::from_str(
+            r#"[
+            {
+                "metadata": {"name": "node-1"},
+                "status": {"conditions": [{"type": "Ready", "status": "Unknown", "message": "Kubelet stopped posting node status."}]}
+            },
+            {"metadata": {"name": "node-2"}}
+        ]"#,
+        )
+        .expect("cannot parse nodes");
+
+        // execute:
+        let issues = node_scheduling_issues(&nodes);
+
+        // verify:
+        assert_eq!(1, issues.len());
+        assert_eq!("node-1", issues[0].node_name);
+        assert_eq!("NotReady", issues[0].condition);
+    }
+
+    #[test]
+    fn test_failing_pods_node_issue() {
+        // setup:
+        let nodes: Vec<Node> = serde_json::from_str(
+            r#"[{
+                "metadata": {"name": "node-1"},
+                "status": {"conditions": [{"type": "DiskPressure", "status": "True", "message": "kubelet has disk pressure"}]}
+            }, {
+                "metadata": {"name": "node-2"},
+                "status": {"conditions": [{"type": "MemoryPressure", "status": "True", "message": "kubelet has insufficient memory available"}]}
+            }]"#,
+        )
+        .expect("cannot parse nodes");
+        let ready_pod: Pod = serde_json::from_str(
+            r#"{
+                "metadata": {"name": "app-1"},
+                "spec": {"nodeName": "node-1", "containers": []},
+                "status": {"phase": "Running", "conditions": [{"type": "Ready", "status": "True"}]}
+            }"#,
+        )
+        .expect("cannot parse pod");
+        let not_ready_pod: Pod = serde_json::from_str(
+            r#"{
+                "metadata": {"name": "app-2"},
+                "spec": {"nodeName": "node-2", "containers": []},
+                "status": {"phase": "Running", "conditions": [{"type": "Ready", "status": "False"}]}
+            }"#,
+        )
+        .expect("cannot parse pod");
+        let pending_pod: Pod = serde_json::from_str(
+            r#"{
+                "metadata": {"name": "app-3"},
+                "spec": {"containers": []},
+                "status": {"phase": "Pending", "conditions": [{"type": "PodScheduled", "status": "False", "reason": "Unschedulable"}]}
+            }"#,
+        )
+        .expect("cannot parse pod");
+
+        // execute & verify: node-1 is under pressure but only runs a ready pod
+        assert_eq!(None, failing_pods_node_issue(&[ready_pod.clone()], &nodes));
+        assert_eq!(None, failing_pods_node_issue(&[pending_pod.clone()], &nodes));
+        assert_eq!(None, failing_pods_node_issue(&[not_ready_pod.clone()], &nodes[..1]));
+        assert_eq!(
+            Some(NodeConditionIssue {
+                node_name: "node-2".to_string(),
+                condition: "MemoryPressure".to_string(),
+                message: "kubelet has insufficient memory available".to_string(),
+            }),
+            failing_pods_node_issue(&[ready_pod, pending_pod, not_ready_pod], &nodes)
+        );
+    }
+
+    #[test]
+    fn test_image_pull_error() {
+        // setup:
+        let pods: KubernetesList<KubernetesPod> = serde_json::from_str(
+            r#"{
+            "apiVersion": "v1",
+            "kind": "List",
+            "items": [
+                {
+                    "metadata": {"name": "app-z85ba6759-7c8b6f9d4-h2x7q", "namespace": "z7e93ab68-z3a1e3d1b"},
+                    "status": {
+                        "phase": "Running",
+                        "containerStatuses": [
+                            {"ready": true, "restartCount": 0, "state": {"running": {"startedAt": "2022-11-14T13:59:21Z"}}}
+                        ]
+                    }
+                },
+                {
+                    "metadata": {"name": "app-z85ba6759-5d4f8c7b9-k9p2m", "namespace": "z7e93ab68-z3a1e3d1b"},
+                    "status": {
+                        "phase": "Pending",
+                        "containerStatuses": [
+                            {
+                                "ready": false,
+                                "restartCount": 0,
+                                "state": {
+                                    "waiting": {
+                                        "reason": "ImagePullBackOff",
+                                        "message": "Back-off pulling image \"registry.example.com/app:v2\""
+                                    }
+                                }
+                            }
+                        ]
+                    }
+                }
+            ]
+        }"#,
+        )
+        .expect("cannot parse pods");
+        let service_id = Uuid::new_v4();
+
+        // execute:
+        let error = image_pull_error(
+            &pods.items,
+            EventDetailsBuilder::new()
+                .transmitter(Transmitter::Application(service_id, "app".to_string()))
+                .build(),
+            &service_id,
+            "app",
+        )
+        .expect("image pull error should be detected");
+
+        // verify:
+        assert_eq!(&Tag::K8sImagePullError, error.tag());
+        assert!(error.user_log_message().contains("app-z85ba6759-5d4f8c7b9-k9p2m"));
+        assert!(error
+            .message(ErrorMessageVerbosity::FullDetails)
+            .contains("Back-off pulling image \"registry.example.com/app:v2\""));
+        assert!(error.hint_message().clone().unwrap_or_default().contains("credentials"));
+        assert_eq!(Some("hint.k8s_image_pull_error"), error.hint_key());
+        assert!(image_pull_error(&pods.items[..1], error.event_details().clone(), &service_id, "app").is_none());
+    }
+
+    fn port(port: u16, publicly_accessible: bool, protocol: Protocol) -> Port {
+        Port {
+            id: port.to_string(),
+            long_id: Uuid::new_v4(),
+            port,
+            is_default: false,
+            name: None,
+            publicly_accessible,
+            protocol,
+        }
+    }
+
+    #[test]
+    fn test_validate_ports() {
+        // setup:
+        let test_cases = vec![
+            (vec![port(80, true, Protocol::HTTP), port(5353, false, Protocol::UDP)], None),
+            (
+                vec![port(80, true, Protocol::HTTP), port(80, false, Protocol::TCP)],
+                Some("Port 80 is declared more than once"),
+            ),
+            (
+                vec![port(80, true, Protocol::HTTP), port(5353, true, Protocol::UDP)],
+                Some("Port 5353 uses UDP protocol and cannot be publicly accessible"),
+            ),
+        ];
+
+        for (ports, expected_error) in test_cases {
+            // execute:
+            let result = validate_ports(&ports, EventDetailsBuilder::new().build());
+
+            // verify:
+            match expected_error {
+                None => assert!(result.is_ok()),
+                Some(expected_error) => {
+                    let err = result.expect_err("ports should be rejected");
+                    assert_eq!(&Tag::InvalidEnginePayload, err.tag());
+                    assert!(err.user_log_message().contains(expected_error));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_on_unless_cancelled_stops_waiting_once_cancelled() {
+        // setup:
+        let event_details = EventDetailsBuilder::new().build();
+        let cancellation_token = CancellationToken::new();
+
+        // execute & verify:
+        assert_eq!(
+            42,
+            block_on_unless_cancelled(async { 42 }, &cancellation_token, &event_details).unwrap()
+        );
+
+        // token is flipped while waiting for a future which never resolves
+        let started_at = Instant::now();
+        let result = thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(300));
+                cancellation_token.cancel();
+            });
+            block_on_unless_cancelled(futures::future::pending::<()>(), &cancellation_token, &event_details)
+        });
+        assert_eq!(&Tag::TaskCancellationRequested, result.unwrap_err().tag());
+        assert!(started_at.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -2,9 +2,12 @@ use crate::cloud_provider::service::{Action, ServiceType};
 use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_report::application::renderer::render_app_deployment_report;
 use crate::deployment_report::logger::EnvLogger;
+use crate::deployment_report::utils::ready_pods_progress;
 use crate::deployment_report::DeploymentReporter;
 use crate::errors::EngineError;
 use crate::errors::Tag::HelmDeployTimeout;
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
 use crate::models::application::ApplicationService;
 use crate::models::container::ContainerService;
 use crate::runtime::block_on;
@@ -25,6 +28,7 @@ pub struct ApplicationDeploymentReporter<T> {
     namespace: String,
     kube_client: kube::Client,
     selector: String,
+    desired_replicas: u32,
     logger: EnvLogger,
     _tag: std::marker::PhantomData<T>,
 }
@@ -42,6 +46,7 @@ impl<T> ApplicationDeploymentReporter<T> {
             namespace: deployment_target.environment.namespace().to_string(),
             kube_client: deployment_target.kube.clone(),
             selector: app.selector().unwrap_or_default(),
+            desired_replicas: app.min_instances(),
             logger: deployment_target.env_logger(app, action.to_environment_step()),
             _tag: Default::default(),
         }
@@ -59,6 +64,7 @@ impl<T> ApplicationDeploymentReporter<T> {
             namespace: deployment_target.environment.namespace().to_string(),
             kube_client: deployment_target.kube.clone(),
             selector: container.selector().unwrap_or_default(),
+            desired_replicas: container.min_instances(),
             logger: deployment_target.env_logger(container, action.to_environment_step()),
             _tag: Default::default(),
         }
@@ -67,15 +73,21 @@ impl<T> ApplicationDeploymentReporter<T> {
 
 impl<T: Send + Sync> DeploymentReporter for ApplicationDeploymentReporter<T> {
     type DeploymentResult = T;
-    type DeploymentState = (String, Instant);
+    // last rendered report, when it has been sent, and ready pods progress
+    type DeploymentState = (String, Instant, Option<f32>);
     type Logger = EnvLogger;
 
     fn logger(&self) -> &Self::Logger {
         &self.logger
     }
 
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails {
+        self.logger.event_details().clone()
+    }
+
     fn new_state(&self) -> Self::DeploymentState {
-        ("".to_string(), Instant::now())
+        ("".to_string(), Instant::now(), None)
     }
 
     fn deployment_before_start(&self, _: &mut Self::DeploymentState) {
@@ -112,6 +124,7 @@ impl<T: Send + Sync> DeploymentReporter for ApplicationDeploymentReporter<T> {
                 return;
             }
         };
+        last_report.2 = ready_pods_progress(&report.pods, self.desired_replicas);
 
         // Format the deployment information and send to it to user
         let rendered_report = match render_app_deployment_report(self.service_type, &self.tag, &report) {
@@ -127,13 +140,19 @@ impl<T: Send + Sync> DeploymentReporter for ApplicationDeploymentReporter<T> {
         if rendered_report == last_report.0 && last_report.1.elapsed() < MAX_ELASPED_TIME_WITHOUT_REPORT {
             return;
         }
-        *last_report = (rendered_report, Instant::now());
+        last_report.0 = rendered_report;
+        last_report.1 = Instant::now();
 
         // Send it to user
         for line in last_report.0.trim_end().split('\n').map(str::to_string) {
             self.logger.send_progress(line);
         }
     }
+
+    fn progress_hint(&self, last_report: &Self::DeploymentState) -> Option<f32> {
+        last_report.2
+    }
+
     fn deployment_terminated(
         &self,
         result: &Result<Self::DeploymentResult, EngineError>,
@@ -2,8 +2,11 @@ use crate::cloud_provider::service::{Action, DatabaseType};
 use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_report::database::renderer::render_database_deployment_report;
 use crate::deployment_report::logger::EnvLogger;
+use crate::deployment_report::utils::ready_pods_progress;
 use crate::deployment_report::DeploymentReporter;
 use crate::errors::EngineError;
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
 use crate::models::database::DatabaseService;
 use crate::runtime::block_on;
 use crate::utilities::to_short_id;
@@ -114,15 +117,21 @@ impl DatabaseDeploymentReporter {
 
 impl DeploymentReporter for DatabaseDeploymentReporter {
     type DeploymentResult = ();
-    type DeploymentState = String;
+    // last rendered report and ready pods progress
+    type DeploymentState = (String, Option<f32>);
     type Logger = EnvLogger;
 
     fn logger(&self) -> &Self::Logger {
         &self.logger
     }
 
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails {
+        self.logger.event_details().clone()
+    }
+
     fn new_state(&self) -> Self::DeploymentState {
-        String::new()
+        (String::new(), None)
     }
 
     fn deployment_before_start(&self, _: &mut Self::DeploymentState) {
@@ -171,6 +180,10 @@ impl DeploymentReporter for DatabaseDeploymentReporter {
                 return;
             }
         };
+        // container databases run a single replica, managed ones have no pod to track
+        if !self.is_managed {
+            last_report.1 = ready_pods_progress(&report.pods, 1);
+        }
 
         // Format the deployment information and send to it to user
         let rendered_report = match render_database_deployment_report(&report) {
@@ -183,16 +196,21 @@ impl DeploymentReporter for DatabaseDeploymentReporter {
         };
 
         // Managed database don't make any progress, so display the message from time to time
-        if !self.is_managed && &rendered_report == last_report {
+        if !self.is_managed && rendered_report == last_report.0 {
             return;
         }
-        *last_report = rendered_report;
+        last_report.0 = rendered_report;
 
         // Send it to user
-        for line in last_report.trim_end().split('\n').map(str::to_string) {
+        for line in last_report.0.trim_end().split('\n').map(str::to_string) {
             self.logger.send_progress(line);
         }
     }
+
+    fn progress_hint(&self, last_report: &Self::DeploymentState) -> Option<f32> {
+        last_report.1
+    }
+
     fn deployment_terminated(
         &self,
         result: &Result<Self::DeploymentResult, EngineError>,
@@ -0,0 +1,138 @@
+// Failure injection is only meant to be used to test error handling paths of the deployment flow.
+// It allows to force a specific error Tag at a given stage of `execute_long_deployment`, either:
+// 1. programmatically, by calling `inject_failure` in the thread running the deployment
+// 2. via the QOVERY_ENGINE_FAILURE_INJECTION env var, formatted as <stage>:<Tag> i.e `run:HelmChartsDeployError`
+use crate::errors::Tag;
+use std::cell::RefCell;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub const FAILURE_INJECTION_ENV_VAR: &str = "QOVERY_ENGINE_FAILURE_INJECTION";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureInjectionStage {
+    PreRun,
+    Run,
+}
+
+impl FromStr for FailureInjectionStage {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pre_run" | "pre-run" => Ok(FailureInjectionStage::PreRun),
+            "run" => Ok(FailureInjectionStage::Run),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for FailureInjectionStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FailureInjectionStage::PreRun => "pre_run",
+            FailureInjectionStage::Run => "run",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InjectedFailure {
+    pub stage: FailureInjectionStage,
+    pub tag: Tag,
+}
+
+impl FromStr for InjectedFailure {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (stage, tag) = s.split_once(':').ok_or(())?;
+        Ok(InjectedFailure {
+            stage: stage.parse()?,
+            tag: tag.trim().parse().map_err(|_| ())?,
+        })
+    }
+}
+
+thread_local! {
+    static INJECTED_FAILURE: RefCell<Option<InjectedFailure>> = const { RefCell::new(None) };
+}
+
+/// Forces the next deployments executed by the current thread to fail at the given stage.
+pub fn inject_failure(failure: InjectedFailure) {
+    INJECTED_FAILURE.with(|f| *f.borrow_mut() = Some(failure));
+}
+
+pub fn clear_injected_failure() {
+    INJECTED_FAILURE.with(|f| *f.borrow_mut() = None);
+}
+
+/// Returns the Tag to fail with if a failure has been injected for this stage.
+/// Programmatic injection takes precedence over the env var.
+pub fn injected_failure(stage: FailureInjectionStage) -> Option<Tag> {
+    let failure = INJECTED_FAILURE.with(|f| f.borrow().clone()).or_else(|| {
+        env::var(FAILURE_INJECTION_ENV_VAR)
+            .ok()
+            .and_then(|value| value.parse::<InjectedFailure>().ok())
+    })?;
+
+    if failure.stage == stage {
+        Some(failure.tag)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deployment_report::failure_injection::{
+        clear_injected_failure, inject_failure, injected_failure, FailureInjectionStage, InjectedFailure,
+    };
+    use crate::errors::Tag;
+
+    #[test]
+    fn test_injected_failure_from_str() {
+        // setup:
+        let test_cases = vec![
+            (
+                "run:HelmChartsDeployError",
+                Ok(InjectedFailure {
+                    stage: FailureInjectionStage::Run,
+                    tag: Tag::HelmChartsDeployError,
+                }),
+            ),
+            (
+                " pre_run : K8sCannotGetPods",
+                Ok(InjectedFailure {
+                    stage: FailureInjectionStage::PreRun,
+                    tag: Tag::K8sCannotGetPods,
+                }),
+            ),
+            ("run", Err(())),
+            ("post_run:Unknown", Err(())),
+            ("run:NotATag", Err(())),
+        ];
+
+        for (input, expected) in test_cases {
+            // execute & verify:
+            assert_eq!(expected, input.parse::<InjectedFailure>(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_injected_failure_only_for_its_stage() {
+        // setup:
+        inject_failure(InjectedFailure {
+            stage: FailureInjectionStage::Run,
+            tag: Tag::Unknown,
+        });
+
+        // execute & verify:
+        assert_eq!(None, injected_failure(FailureInjectionStage::PreRun));
+        assert_eq!(Some(Tag::Unknown), injected_failure(FailureInjectionStage::Run));
+
+        clear_injected_failure();
+        assert_eq!(None, injected_failure(FailureInjectionStage::Run));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::deployment_report::{DeploymentReporter, ReportFrequency};
+use crate::errors::EngineError;
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
+use chrono::Utc;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::Mutex;
+
+const EVENT_BEFORE_START: &str = "before_start";
+const EVENT_IN_PROGRESS: &str = "in_progress";
+const EVENT_TERMINATED: &str = "terminated";
+const STATUS_SUCCESS: &str = "success";
+const STATUS_FAILURE: &str = "failure";
+
+/// One line of the NDJSON audit trail, `status` and the error fields are only set on termination.
+#[derive(Serialize)]
+struct DeploymentRecord<'a> {
+    timestamp: String,
+    event: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<u32>,
+}
+
+impl<'a> DeploymentRecord<'a> {
+    fn new(event: &'a str) -> Self {
+        DeploymentRecord {
+            timestamp: Utc::now().to_rfc3339(),
+            event,
+            status: None,
+            error_tag: None,
+            error_code: None,
+        }
+    }
+}
+
+/// Reporter writing every deployment lifecycle event as a newline-delimited JSON record to a sink,
+/// as a machine-readable audit trail. Everything else is delegated to the inner reporter.
+/// Failing to write a record is logged and never fails the deployment.
+pub struct FileDeploymentReporter<R, W> {
+    inner: R,
+    sink: Mutex<W>,
+}
+
+impl<R: DeploymentReporter, W: Write + Send> FileDeploymentReporter<R, W> {
+    pub fn new(inner: R, sink: W) -> Self {
+        FileDeploymentReporter {
+            inner,
+            sink: Mutex::new(sink),
+        }
+    }
+
+    /// Returns the sink, i.e: to flush or inspect it once the deployment is over.
+    pub fn into_sink(self) -> W {
+        self.sink.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write_record(&self, record: &DeploymentRecord) {
+        let mut line = match serde_json::to_vec(record) {
+            Ok(line) => line,
+            Err(err) => {
+                warn!("cannot serialize deployment record: {}", err);
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        let mut sink = self.sink.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(err) = sink.write_all(&line).and_then(|_| sink.flush()) {
+            warn!("cannot write deployment record: {}", err);
+        }
+    }
+}
+
+impl<R: DeploymentReporter, W: Write + Send> DeploymentReporter for FileDeploymentReporter<R, W> {
+    type DeploymentResult = R::DeploymentResult;
+    type DeploymentState = R::DeploymentState;
+    type Logger = R::Logger;
+
+    fn logger(&self) -> &Self::Logger {
+        self.inner.logger()
+    }
+
+    fn new_state(&self) -> Self::DeploymentState {
+        self.inner.new_state()
+    }
+
+    fn deployment_before_start(&self, state: &mut Self::DeploymentState) {
+        self.write_record(&DeploymentRecord::new(EVENT_BEFORE_START));
+        self.inner.deployment_before_start(state)
+    }
+
+    fn deployment_in_progress(&self, state: &mut Self::DeploymentState) {
+        self.write_record(&DeploymentRecord::new(EVENT_IN_PROGRESS));
+        self.inner.deployment_in_progress(state)
+    }
+
+    fn deployment_terminated(
+        &self,
+        result: &Result<Self::DeploymentResult, EngineError>,
+        state: &mut Self::DeploymentState,
+    ) {
+        let record = match result {
+            Ok(_) => DeploymentRecord {
+                status: Some(STATUS_SUCCESS),
+                ..DeploymentRecord::new(EVENT_TERMINATED)
+            },
+            Err(err) => DeploymentRecord {
+                status: Some(STATUS_FAILURE),
+                error_tag: Some(format!("{:?}", err.tag())),
+                error_code: Some(err.tag().error_code()),
+                ..DeploymentRecord::new(EVENT_TERMINATED)
+            },
+        };
+        self.write_record(&record);
+        self.inner.deployment_terminated(result, state)
+    }
+
+    fn report_frequency(&self) -> ReportFrequency {
+        self.inner.report_frequency()
+    }
+
+    fn progress_hint(&self, state: &Self::DeploymentState) -> Option<f32> {
+        self.inner.progress_hint(state)
+    }
+
+    fn deployment_progress(&self, progress: f32, state: &mut Self::DeploymentState) {
+        self.inner.deployment_progress(progress, state)
+    }
+
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails {
+        self.inner.failure_event_details()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deployment_report::file::FileDeploymentReporter;
+    use crate::deployment_report::test_utils::CountingReporter;
+    use crate::deployment_report::DeploymentReporter;
+    use crate::errors::EngineError;
+    use crate::test_utils::EventDetailsBuilder;
+    use std::sync::atomic::Ordering;
+
+    fn records(sink: Vec<u8>) -> Vec<serde_json::Value> {
+        String::from_utf8(sink)
+            .expect("records should be utf8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("record should be valid json"))
+            .collect()
+    }
+
+    #[test]
+    fn test_file_deployment_reporter_writes_ndjson_records() {
+        // setup:
+        let reporter = FileDeploymentReporter::new(CountingReporter::default(), Vec::new());
+
+        // execute:
+        let mut state = reporter.new_state();
+        reporter.deployment_before_start(&mut state);
+        reporter.deployment_in_progress(&mut state);
+        reporter.deployment_in_progress(&mut state);
+        reporter.deployment_terminated(&Ok(()), &mut state);
+
+        // verify:
+        assert_eq!(1, reporter.inner.terminated.load(Ordering::SeqCst));
+        let records = records(reporter.into_sink());
+        assert_eq!(
+            vec!["before_start", "in_progress", "in_progress", "terminated"],
+            records.iter().map(|r| r["event"].as_str().unwrap()).collect::<Vec<_>>()
+        );
+        assert!(records.iter().all(|r| r["timestamp"].is_string()));
+        assert_eq!("success", records[3]["status"]);
+        assert!(records[3].get("error_tag").is_none());
+        assert!(records[0].get("status").is_none());
+    }
+
+    #[test]
+    fn test_file_deployment_reporter_records_error_tag() {
+        // setup:
+        let reporter = FileDeploymentReporter::new(CountingReporter::default(), Vec::new());
+        let error = EngineError::new_unknown(
+            EventDetailsBuilder::new().build(),
+            "deployment failed".to_string(),
+            None,
+            None,
+            None,
+        );
+
+        // execute:
+        let mut state = reporter.new_state();
+        reporter.deployment_before_start(&mut state);
+        reporter.deployment_terminated(&Err(error), &mut state);
+
+        // verify:
+        let records = records(reporter.into_sink());
+        assert_eq!(2, records.len());
+        assert_eq!("terminated", records[1]["event"]);
+        assert_eq!("failure", records[1]["status"]);
+        assert_eq!("Unknown", records[1]["error_tag"]);
+        assert_eq!(1000, records[1]["error_code"]);
+    }
+}
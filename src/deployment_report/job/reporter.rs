@@ -3,6 +3,8 @@ use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_report::logger::EnvLogger;
 use crate::deployment_report::DeploymentReporter;
 use crate::errors::EngineError;
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
 use std::fmt::{Display, Formatter};
 use std::marker::PhantomData;
 
@@ -93,6 +95,11 @@ impl<T: Send + Sync> DeploymentReporter for JobDeploymentReporter<T> {
         &self.logger
     }
 
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails {
+        self.logger.event_details().clone()
+    }
+
     fn new_state(&self) -> Self::DeploymentState {
         ("".to_string(), Instant::now())
     }
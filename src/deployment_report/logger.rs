@@ -39,6 +39,10 @@ impl EnvLogger {
         }
     }
 
+    pub fn event_details(&self) -> &EventDetails {
+        &self.event_details_progress
+    }
+
     pub fn send_progress(&self, msg: String) {
         #[cfg(feature = "env-logger-check")]
         {
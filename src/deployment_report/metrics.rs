@@ -0,0 +1,196 @@
+use crate::deployment_report::{DeploymentReporter, ReportFrequency};
+use crate::errors::EngineError;
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+use std::time::{Duration, Instant};
+
+const RESULT_SUCCESS: &str = "success";
+const RESULT_FAILURE: &str = "failure";
+// Label value used for the `tag` of successful deployments
+const NO_TAG: &str = "none";
+
+/// Prometheus collectors updated at the end of every deployment.
+/// Cloning is cheap, all clones update the same underlying collectors.
+#[derive(Clone)]
+pub struct DeploymentMetrics {
+    deployments_total: IntCounterVec,
+    deployment_duration_seconds: HistogramVec,
+}
+
+impl DeploymentMetrics {
+    /// Creates the collectors and registers them in the given registry.
+    /// Fails if they are already registered, build them once and clone them instead.
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let deployments_total = IntCounterVec::new(
+            Opts::new("deployments_total", "Number of terminated deployments"),
+            &["result", "tag"],
+        )?;
+        let deployment_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("deployment_duration_seconds", "Duration of deployments in seconds")
+                .buckets(vec![5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0, 1800.0, 3600.0]),
+            &["result"],
+        )?;
+
+        registry.register(Box::new(deployments_total.clone()))?;
+        registry.register(Box::new(deployment_duration_seconds.clone()))?;
+
+        Ok(DeploymentMetrics {
+            deployments_total,
+            deployment_duration_seconds,
+        })
+    }
+
+    fn observe<T>(&self, result: &Result<T, EngineError>, duration: Duration) {
+        let tag = match result {
+            Ok(_) => NO_TAG.to_string(),
+            Err(err) => format!("{:?}", err.tag()),
+        };
+        let result = match result {
+            Ok(_) => RESULT_SUCCESS,
+            Err(_) => RESULT_FAILURE,
+        };
+
+        self.deployments_total.with_label_values(&[result, &tag]).inc();
+        self.deployment_duration_seconds
+            .with_label_values(&[result])
+            .observe(duration.as_secs_f64());
+    }
+}
+
+pub struct MetricsDeploymentState<S> {
+    started_at: Instant,
+    inner: S,
+}
+
+/// Reporter recording deployments result and duration, everything else is delegated to the inner reporter.
+pub struct MetricsDeploymentReporter<R> {
+    inner: R,
+    metrics: DeploymentMetrics,
+}
+
+impl<R: DeploymentReporter> MetricsDeploymentReporter<R> {
+    pub fn new(inner: R, metrics: DeploymentMetrics) -> Self {
+        MetricsDeploymentReporter { inner, metrics }
+    }
+}
+
+impl<R: DeploymentReporter> DeploymentReporter for MetricsDeploymentReporter<R> {
+    type DeploymentResult = R::DeploymentResult;
+    type DeploymentState = MetricsDeploymentState<R::DeploymentState>;
+    type Logger = R::Logger;
+
+    fn logger(&self) -> &Self::Logger {
+        self.inner.logger()
+    }
+
+    fn new_state(&self) -> Self::DeploymentState {
+        MetricsDeploymentState {
+            started_at: Instant::now(),
+            inner: self.inner.new_state(),
+        }
+    }
+
+    fn deployment_before_start(&self, state: &mut Self::DeploymentState) {
+        self.inner.deployment_before_start(&mut state.inner)
+    }
+
+    fn deployment_in_progress(&self, state: &mut Self::DeploymentState) {
+        self.inner.deployment_in_progress(&mut state.inner)
+    }
+
+    fn deployment_terminated(
+        &self,
+        result: &Result<Self::DeploymentResult, EngineError>,
+        state: &mut Self::DeploymentState,
+    ) {
+        self.metrics.observe(result, state.started_at.elapsed());
+        self.inner.deployment_terminated(result, &mut state.inner)
+    }
+
+    fn report_frequency(&self) -> ReportFrequency {
+        self.inner.report_frequency()
+    }
+
+    fn progress_hint(&self, state: &Self::DeploymentState) -> Option<f32> {
+        self.inner.progress_hint(&state.inner)
+    }
+
+    fn deployment_progress(&self, progress: f32, state: &mut Self::DeploymentState) {
+        self.inner.deployment_progress(progress, &mut state.inner)
+    }
+
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails {
+        self.inner.failure_event_details()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deployment_report::metrics::{DeploymentMetrics, MetricsDeploymentReporter};
+    use crate::deployment_report::test_utils::CountingReporter;
+    use crate::deployment_report::DeploymentReporter;
+    use crate::errors::EngineError;
+    use crate::test_utils::EventDetailsBuilder;
+    use prometheus::Registry;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_metrics_deployment_reporter_records_deployments() {
+        // setup:
+        let registry = Registry::new();
+        let metrics = DeploymentMetrics::new(&registry).expect("metrics should be registered");
+        let reporter = MetricsDeploymentReporter::new(CountingReporter::default(), metrics.clone());
+
+        // execute:
+        let mut state = reporter.new_state();
+        reporter.deployment_terminated(&Ok(()), &mut state);
+        let mut state = reporter.new_state();
+        reporter.deployment_terminated(
+            &Err(EngineError::new_unknown(
+                EventDetailsBuilder::new().build(),
+                "deployment failed".to_string(),
+                None,
+                None,
+                None,
+            )),
+            &mut state,
+        );
+
+        // verify:
+        assert_eq!(2, reporter.inner.terminated.load(Ordering::SeqCst));
+        assert_eq!(1, metrics.deployments_total.with_label_values(&["success", "none"]).get());
+        assert_eq!(
+            1,
+            metrics
+                .deployments_total
+                .with_label_values(&["failure", "Unknown"])
+                .get()
+        );
+        for result in ["success", "failure"] {
+            assert_eq!(
+                1,
+                metrics
+                    .deployment_duration_seconds
+                    .with_label_values(&[result])
+                    .get_sample_count()
+            );
+        }
+
+        let families = registry.gather();
+        let names: Vec<&str> = families.iter().map(|f| f.get_name()).collect();
+        assert!(names.contains(&"deployments_total"));
+        assert!(names.contains(&"deployment_duration_seconds"));
+    }
+
+    #[test]
+    fn test_deployment_metrics_cannot_be_registered_twice() {
+        // setup:
+        let registry = Registry::new();
+        let _metrics = DeploymentMetrics::new(&registry).expect("metrics should be registered");
+
+        // execute & verify:
+        assert!(DeploymentMetrics::new(&registry).is_err());
+    }
+}
@@ -1,5 +1,11 @@
 use crate::deployment_report::logger::{EnvLogger, EnvProgressLogger, EnvSuccessLogger};
-use crate::errors::EngineError;
+use crate::errors::{EngineError, Tag};
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
+use crate::poller::ExponentialBackoff;
+#[cfg(feature = "failure-injection")]
+use failure_injection::{injected_failure, FailureInjectionStage};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::mpsc::RecvTimeoutError;
 use std::sync::{mpsc, Arc, Barrier};
 use std::thread;
@@ -7,10 +13,64 @@ use std::time::Duration;
 
 pub mod application;
 pub mod database;
+#[cfg(feature = "failure-injection")]
+pub mod failure_injection;
+pub mod file;
 pub mod job;
 pub mod logger;
+pub mod metrics;
 pub mod router;
-mod utils;
+#[cfg(test)]
+mod test_utils;
+pub(crate) mod utils;
+
+/// Shared flag to ask a running deployment task to stop early.
+/// Cloning is cheap, all clones share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Interval between two progress reports of a deployment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReportFrequency {
+    Fixed(Duration),
+    /// Interval starts at `initial` and is multiplied by `factor` after each report, without exceeding `max`.
+    Backoff {
+        initial: Duration,
+        max: Duration,
+        factor: f32,
+    },
+}
+
+impl ReportFrequency {
+    pub fn intervals(&self) -> Box<dyn Iterator<Item = Duration>> {
+        match *self {
+            ReportFrequency::Fixed(interval) => Box::new(std::iter::repeat(interval)),
+            ReportFrequency::Backoff { initial, max, factor } => Box::new(
+                ExponentialBackoff {
+                    initial_delay: initial,
+                    max_delay: max,
+                    // a factor below 1 would make reports more and more frequent
+                    multiplier: factor.max(1.0),
+                }
+                .delays(),
+            ),
+        }
+    }
+}
 
 // Object responsible to log the progress of a deployment
 // This object is going to live in his own thread and is responsible to
@@ -34,8 +94,80 @@ pub trait DeploymentReporter: Send + Sync {
         result: &Result<Self::DeploymentResult, EngineError>,
         state: &mut Self::DeploymentState,
     );
-    fn report_frequency(&self) -> Duration {
-        Duration::from_secs(10)
+    fn report_frequency(&self) -> ReportFrequency {
+        ReportFrequency::Fixed(Duration::from_secs(10))
+    }
+    // Estimation of the deployment progress between 0.0 and 1.0, computed at each report tick
+    fn progress_hint(&self, _state: &Self::DeploymentState) -> Option<f32> {
+        None
+    }
+    // Receives the progress estimated by `progress_hint`, if any, at each report tick
+    fn deployment_progress(&self, _progress: f32, _state: &mut Self::DeploymentState) {}
+    // Event details attached to errors forced by the failure injection hook
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails;
+}
+
+/// Transitions of a deployment, as seen by the reporter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeploymentLifecycleEvent {
+    BeforeStart,
+    /// Sent at each report tick, along with the progress estimated by the reporter if any.
+    InProgress {
+        progress: Option<f32>,
+    },
+    /// Sent once per run, `error_tag` is set when the deployment failed.
+    Terminated {
+        error_tag: Option<Tag>,
+    },
+}
+
+// Receives deployment transitions alongside the reporter, i.e: to emit metrics or traces
+// without going through the human readable logs
+pub trait DeploymentObserver: Send + Sync {
+    fn on_event(&self, event: &DeploymentLifecycleEvent);
+}
+
+/// Observer counting the events it receives. Clones share the same counters.
+#[derive(Clone, Debug, Default)]
+pub struct CountingDeploymentObserver {
+    before_start: Arc<AtomicU32>,
+    in_progress: Arc<AtomicU32>,
+    terminated: Arc<AtomicU32>,
+}
+
+impl CountingDeploymentObserver {
+    pub fn new() -> Self {
+        CountingDeploymentObserver::default()
+    }
+
+    pub fn before_start_count(&self) -> u32 {
+        self.before_start.load(Ordering::SeqCst)
+    }
+
+    pub fn in_progress_count(&self) -> u32 {
+        self.in_progress.load(Ordering::SeqCst)
+    }
+
+    pub fn terminated_count(&self) -> u32 {
+        self.terminated.load(Ordering::SeqCst)
+    }
+}
+
+impl DeploymentObserver for CountingDeploymentObserver {
+    fn on_event(&self, event: &DeploymentLifecycleEvent) {
+        let counter = match event {
+            DeploymentLifecycleEvent::BeforeStart => &self.before_start,
+            DeploymentLifecycleEvent::InProgress { .. } => &self.in_progress,
+            DeploymentLifecycleEvent::Terminated { .. } => &self.terminated,
+        };
+        counter.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn notify_observers(observers: &[Box<dyn DeploymentObserver>], event: DeploymentLifecycleEvent) {
+    for observer in observers {
+        observer.on_event(&event);
     }
 }
 
@@ -47,10 +179,22 @@ pub trait DeploymentTask {
     type DeploymentResult;
 
     fn pre_run(&self, logger: &Self::Logger) -> Result<Self::DeploymentResult, EngineError>;
-    fn run(&self, logger: &Self::Logger, state: Self::DeploymentResult) -> Result<Self::DeploymentResult, EngineError>;
+    // Long running implementations should check the cancellation token and bail out
+    // with `EngineError::new_task_cancellation_requested` once it is cancelled
+    fn run(
+        &self,
+        logger: &Self::Logger,
+        state: Self::DeploymentResult,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Self::DeploymentResult, EngineError>;
     fn post_run_success(&self, logger: &Self::Logger, state: Self::DeploymentResult);
+    // Called once the run failed, to clean up what the run may have partially created
+    fn post_run_failure(&self, _logger: &Self::Logger, _err: &EngineError) {}
 }
 
+// Cleanup to run once the run failed
+pub type PostRunFailure<'a> = dyn Fn(&EnvProgressLogger, &EngineError) + 'a;
+
 pub struct DeploymentTaskImpl<'a, Pre, Run, Post, Ret>
 where
     Pre: Fn(&EnvProgressLogger) -> Result<Ret, EngineError>,
@@ -60,6 +204,7 @@ where
     pub pre_run: &'a Pre,
     pub run: &'a Run,
     pub post_run_success: &'a Post,
+    pub post_run_failure: Option<&'a PostRunFailure<'a>>,
 }
 
 impl<'a, Pre, Run, Post, Ret> DeploymentTask for DeploymentTaskImpl<'a, Pre, Run, Post, Ret>
@@ -76,7 +221,16 @@ where
         (self.pre_run)(&progress_logger)
     }
 
-    fn run(&self, logger: &Self::Logger, state: Self::DeploymentResult) -> Result<Self::DeploymentResult, EngineError> {
+    fn run(
+        &self,
+        logger: &Self::Logger,
+        state: Self::DeploymentResult,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Self::DeploymentResult, EngineError> {
+        if cancellation_token.is_cancelled() {
+            return Err(EngineError::new_task_cancellation_requested(logger.event_details().clone()));
+        }
+
         let progress_logger = EnvProgressLogger::new(logger);
         (self.run)(&progress_logger, state)
     }
@@ -85,6 +239,13 @@ where
         let success_logger = EnvSuccessLogger::new(logger);
         (self.post_run_success)(&success_logger, state)
     }
+
+    fn post_run_failure(&self, logger: &Self::Logger, err: &EngineError) {
+        if let Some(post_run_failure) = self.post_run_failure {
+            let progress_logger = EnvProgressLogger::new(logger);
+            post_run_failure(&progress_logger, err)
+        }
+    }
 }
 
 // Blanket impl helper to create a deployment task from a closure
@@ -99,7 +260,16 @@ where
         Ok(())
     }
 
-    fn run(&self, logger: &Self::Logger, state: Self::DeploymentResult) -> Result<Self::DeploymentResult, EngineError> {
+    fn run(
+        &self,
+        logger: &Self::Logger,
+        state: Self::DeploymentResult,
+        cancellation_token: &CancellationToken,
+    ) -> Result<Self::DeploymentResult, EngineError> {
+        if cancellation_token.is_cancelled() {
+            return Err(EngineError::new_task_cancellation_requested(logger.event_details().clone()));
+        }
+
         let progress_logger = EnvProgressLogger::new(logger);
         match self(&progress_logger) {
             Ok(_) => Ok(state),
@@ -112,11 +282,22 @@ where
 
 // Function that take a deployment reporter and a deployment task and execute/synchronize them together
 // The reporter is going to be executed in a separate thread and the task in the current thread.
-// Reporter will not be executed while the task is running the pre_run and post_run_success methods.
+// Reporter will not be executed while the task is running the pre_run and post_run_success/post_run_failure methods.
 // Only during the run method
 pub fn execute_long_deployment<Log, TaskRet>(
     deployment_reporter: impl DeploymentReporter<DeploymentResult = TaskRet, Logger = Log>,
     long_task: impl DeploymentTask<Logger = Log, DeploymentResult = TaskRet>,
+    cancellation_token: &CancellationToken,
+) -> Result<(), EngineError> {
+    execute_long_deployment_with_observers(deployment_reporter, long_task, cancellation_token, vec![])
+}
+
+// Same as `execute_long_deployment`, observers receiving the transitions sent to the reporter
+pub fn execute_long_deployment_with_observers<Log, TaskRet>(
+    deployment_reporter: impl DeploymentReporter<DeploymentResult = TaskRet, Logger = Log>,
+    long_task: impl DeploymentTask<Logger = Log, DeploymentResult = TaskRet>,
+    cancellation_token: &CancellationToken,
+    observers: Vec<Box<dyn DeploymentObserver>>,
 ) -> Result<(), EngineError> {
     // stop the thread when the blocking task is done
     let (tx, rx) = mpsc::channel();
@@ -124,6 +305,8 @@ pub fn execute_long_deployment<Log, TaskRet>(
     let mut state = deployment_reporter.new_state();
 
     let logger = deployment_reporter.logger();
+    #[cfg(feature = "failure-injection")]
+    inject_failure_at(&deployment_reporter, FailureInjectionStage::PreRun)?;
     let action_state = long_task.pre_run(logger)?;
 
     let deployment_result = thread::scope(|th_scope| {
@@ -136,25 +319,41 @@ pub fn execute_long_deployment<Log, TaskRet>(
                 let deployment_start = deployment_start.clone();
                 let deployment_reporter = &deployment_reporter; // to avoid moving the object into the thread
                 let state = &mut state;
+                let observers = &observers;
 
                 move || {
                     let _span = current_span.enter();
 
                     // Before the launch of the deployment
                     deployment_reporter.deployment_before_start(state);
+                    notify_observers(observers, DeploymentLifecycleEvent::BeforeStart);
 
                     // Wait the start of the deployment
                     deployment_start.wait();
 
-                    // Send deployment progress report every x secs
+                    // Send deployment progress report every x secs, x growing if a backoff is configured
                     let report_frequency = deployment_reporter.report_frequency();
-                    loop {
-                        match rx.recv_timeout(report_frequency) {
+                    let mut is_cancellation_logged = false;
+                    for report_interval in report_frequency.intervals() {
+                        match rx.recv_timeout(report_interval) {
                             // Deployment is terminated, we received the result of the task
                             Ok(_) => break,
 
                             // Deployment is still in progress
-                            Err(RecvTimeoutError::Timeout) => deployment_reporter.deployment_in_progress(state),
+                            Err(RecvTimeoutError::Timeout) => {
+                                if cancellation_token.is_cancelled() && !is_cancellation_logged {
+                                    info!("cancellation of the deployment has been requested, waiting for the task to stop");
+                                    is_cancellation_logged = true;
+                                }
+                                deployment_reporter.deployment_in_progress(state);
+                                let progress = deployment_reporter
+                                    .progress_hint(state)
+                                    .map(|progress| progress.clamp(0.0, 1.0));
+                                if let Some(progress) = progress {
+                                    deployment_reporter.deployment_progress(progress, state);
+                                }
+                                notify_observers(observers, DeploymentLifecycleEvent::InProgress { progress });
+                            }
 
                             // Other side died without passing us the result ! this is a logical bug !
                             Err(RecvTimeoutError::Disconnected) => {
@@ -169,7 +368,11 @@ pub fn execute_long_deployment<Log, TaskRet>(
 
         // Wait for our watcher thread to be ready before starting
         let _ = deployment_start.wait();
-        let deployment_result = long_task.run(deployment_reporter.logger(), action_state);
+        #[cfg(feature = "failure-injection")]
+        let deployment_result = inject_failure_at(&deployment_reporter, FailureInjectionStage::Run)
+            .and_then(|_| long_task.run(deployment_reporter.logger(), action_state, cancellation_token));
+        #[cfg(not(feature = "failure-injection"))]
+        let deployment_result = long_task.run(deployment_reporter.logger(), action_state, cancellation_token);
         let _ = tx.send(());
         let _ = th_handle.map(|th| th.join()); // wait for the thread to terminate
 
@@ -177,23 +380,54 @@ pub fn execute_long_deployment<Log, TaskRet>(
     });
 
     deployment_reporter.deployment_terminated(&deployment_result, &mut state);
+    notify_observers(
+        &observers,
+        DeploymentLifecycleEvent::Terminated {
+            error_tag: deployment_result.as_ref().err().map(|err| err.tag().clone()),
+        },
+    );
     match deployment_result {
         Ok(ret) => {
             long_task.post_run_success(deployment_reporter.logger(), ret);
             Ok(())
         }
-        Err(err) => Err(err),
+        Err(err) => {
+            long_task.post_run_failure(deployment_reporter.logger(), &err);
+            Err(err)
+        }
+    }
+}
+
+#[cfg(feature = "failure-injection")]
+fn inject_failure_at(
+    deployment_reporter: &impl DeploymentReporter,
+    stage: FailureInjectionStage,
+) -> Result<(), EngineError> {
+    match injected_failure(stage) {
+        Some(tag) => Err(EngineError::new_injected_failure(
+            deployment_reporter.failure_event_details(),
+            tag,
+            stage.to_string(),
+        )),
+        None => Ok(()),
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::deployment_report::{execute_long_deployment, DeploymentReporter, DeploymentTask};
+    use crate::deployment_report::{
+        execute_long_deployment, execute_long_deployment_with_observers, CancellationToken, CountingDeploymentObserver,
+        DeploymentLifecycleEvent, DeploymentObserver, DeploymentReporter, DeploymentTask, ReportFrequency,
+    };
     use crate::errors::EngineError;
-    use std::sync::atomic::{AtomicBool, Ordering};
-    use std::sync::Arc;
+    use crate::errors::Tag;
+    #[cfg(feature = "failure-injection")]
+    use crate::events::EventDetails;
+    use crate::test_utils::EventDetailsBuilder;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
     use std::thread;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     pub struct DeploymentReporterTest {
         pub before_deployment: Arc<AtomicBool>,
@@ -237,16 +471,22 @@ mod test {
             self.deployment_terminated.store(true, Ordering::SeqCst);
         }
 
-        fn report_frequency(&self) -> Duration {
-            Duration::from_secs(1)
+        fn report_frequency(&self) -> ReportFrequency {
+            ReportFrequency::Fixed(Duration::from_secs(1))
+        }
+
+        #[cfg(feature = "failure-injection")]
+        fn failure_event_details(&self) -> EventDetails {
+            EventDetailsBuilder::new().build()
         }
     }
 
     struct DeploymentAction {
-        pub run_fn: Box<dyn Fn()>,
+        pub run_fn: Box<dyn Fn(&CancellationToken) -> Result<(), EngineError>>,
         pub pre_run: Arc<AtomicBool>,
         pub run: Arc<AtomicBool>,
         pub post_run: Arc<AtomicBool>,
+        pub post_run_failure: Arc<AtomicU32>,
     }
 
     impl DeploymentTask for DeploymentAction {
@@ -262,9 +502,10 @@ mod test {
             &self,
             _logger: &Self::Logger,
             state: Self::DeploymentResult,
+            cancellation_token: &CancellationToken,
         ) -> Result<Self::DeploymentResult, EngineError> {
             self.run.store(true, Ordering::SeqCst);
-            (self.run_fn)();
+            (self.run_fn)(cancellation_token)?;
             assert_eq!(state, 1);
             Ok(2)
         }
@@ -273,6 +514,10 @@ mod test {
             self.post_run.store(true, Ordering::SeqCst);
             assert_eq!(state, 2);
         }
+
+        fn post_run_failure(&self, _logger: &Self::Logger, _err: &EngineError) {
+            self.post_run_failure.fetch_add(1, Ordering::SeqCst);
+        }
     }
 
     #[test]
@@ -292,19 +537,21 @@ mod test {
         let is_task_started = reporter.is_task_started.clone();
 
         let task = DeploymentAction {
-            run_fn: Box::new(move || {
+            run_fn: Box::new(move |_| {
                 is_task_started.store(true, Ordering::SeqCst);
                 thread::sleep(Duration::from_secs(2));
+                Ok(())
             }),
             pre_run: Default::default(),
             run: Default::default(),
             post_run: Default::default(),
+            post_run_failure: Default::default(),
         };
 
         let pre_run = task.pre_run.clone();
         let run = task.run.clone();
         let post_run = task.post_run.clone();
-        let _ = execute_long_deployment(reporter, task);
+        let _ = execute_long_deployment(reporter, task, &CancellationToken::new());
 
         // Check that our method have been called
         assert!(before_deployment.load(Ordering::SeqCst));
@@ -317,4 +564,335 @@ mod test {
         assert!(run.load(Ordering::SeqCst));
         assert!(post_run.load(Ordering::SeqCst));
     }
+
+    #[cfg(feature = "failure-injection")]
+    #[test]
+    fn test_execute_long_deployment_with_injected_failure() {
+        use crate::deployment_report::failure_injection::{
+            clear_injected_failure, inject_failure, FailureInjectionStage, InjectedFailure,
+        };
+        let reporter = DeploymentReporterTest {
+            before_deployment: Arc::new(AtomicBool::new(false)),
+            deployment_in_progress: Arc::new(AtomicBool::new(false)),
+            deployment_terminated: Arc::new(AtomicBool::new(false)),
+            thread_dead: Arc::new(AtomicBool::new(false)),
+            is_task_started: Arc::new(AtomicBool::new(false)),
+        };
+        let deployment_terminated = reporter.deployment_terminated.clone();
+
+        let task = DeploymentAction {
+            run_fn: Box::new(|_| Ok(())),
+            pre_run: Default::default(),
+            run: Default::default(),
+            post_run: Default::default(),
+            post_run_failure: Default::default(),
+        };
+        let pre_run = task.pre_run.clone();
+        let run = task.run.clone();
+        let post_run = task.post_run.clone();
+
+        inject_failure(InjectedFailure {
+            stage: FailureInjectionStage::Run,
+            tag: Tag::HelmChartsDeployError,
+        });
+        let result = execute_long_deployment(reporter, task, &CancellationToken::new());
+        clear_injected_failure();
+
+        // The tagged error is propagated and the real run is never executed
+        assert_eq!(result.unwrap_err().tag(), &Tag::HelmChartsDeployError);
+        assert!(pre_run.load(Ordering::SeqCst));
+        assert!(!run.load(Ordering::SeqCst));
+        assert!(!post_run.load(Ordering::SeqCst));
+        assert!(deployment_terminated.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_execute_long_deployment_calls_post_run_failure_only_on_error() {
+        // setup:
+        let task = |run_fn: Box<dyn Fn(&CancellationToken) -> Result<(), EngineError>>| DeploymentAction {
+            run_fn,
+            pre_run: Default::default(),
+            run: Default::default(),
+            post_run: Default::default(),
+            post_run_failure: Default::default(),
+        };
+        let succeeding_task = task(Box::new(|_| Ok(())));
+        let succeeding_post_run = succeeding_task.post_run.clone();
+        let succeeding_post_run_failure = succeeding_task.post_run_failure.clone();
+        let failing_task = task(Box::new(|_| {
+            Err(EngineError::new_unknown(
+                EventDetailsBuilder::new().build(),
+                "deployment failed".to_string(),
+                None,
+                None,
+                None,
+            ))
+        }));
+        let failing_post_run = failing_task.post_run.clone();
+        let failing_post_run_failure = failing_task.post_run_failure.clone();
+
+        // execute:
+        let result =
+            execute_long_deployment(ProgressReporterTest::default(), succeeding_task, &CancellationToken::new());
+        let failing_result =
+            execute_long_deployment(ProgressReporterTest::default(), failing_task, &CancellationToken::new());
+
+        // verify:
+        assert!(result.is_ok());
+        assert!(succeeding_post_run.load(Ordering::SeqCst));
+        assert_eq!(0, succeeding_post_run_failure.load(Ordering::SeqCst));
+
+        assert!(failing_result.is_err());
+        assert!(!failing_post_run.load(Ordering::SeqCst));
+        assert_eq!(1, failing_post_run_failure.load(Ordering::SeqCst));
+    }
+
+    // Counts report ticks in its state, and estimates the progress from it
+    #[derive(Default)]
+    struct ProgressReporterTest {
+        ticks: Arc<AtomicU32>,
+        progress_hint_calls: Arc<AtomicU32>,
+        reported_progress: Arc<Mutex<Vec<(u32, f32)>>>,
+    }
+
+    impl DeploymentReporter for ProgressReporterTest {
+        type DeploymentResult = u32;
+        type DeploymentState = u32;
+        type Logger = ();
+
+        fn logger(&self) -> &Self::Logger {
+            &()
+        }
+
+        fn new_state(&self) -> Self::DeploymentState {
+            0
+        }
+
+        fn deployment_before_start(&self, _: &mut Self::DeploymentState) {}
+
+        fn deployment_in_progress(&self, ticks: &mut Self::DeploymentState) {
+            *ticks += 1;
+        }
+
+        fn deployment_terminated(&self, _: &Result<Self::DeploymentResult, EngineError>, ticks: &mut u32) {
+            self.ticks.store(*ticks, Ordering::SeqCst);
+        }
+
+        fn report_frequency(&self) -> ReportFrequency {
+            ReportFrequency::Fixed(Duration::from_millis(100))
+        }
+
+        fn progress_hint(&self, ticks: &Self::DeploymentState) -> Option<f32> {
+            self.progress_hint_calls.fetch_add(1, Ordering::SeqCst);
+            Some(*ticks as f32 / 4.0)
+        }
+
+        fn deployment_progress(&self, progress: f32, ticks: &mut Self::DeploymentState) {
+            self.reported_progress.lock().unwrap().push((*ticks, progress));
+        }
+
+        #[cfg(feature = "failure-injection")]
+        fn failure_event_details(&self) -> EventDetails {
+            EventDetailsBuilder::new().build()
+        }
+    }
+
+    #[test]
+    fn test_execute_long_deployment_reports_progress_at_each_tick() {
+        // setup:
+        let reporter = ProgressReporterTest::default();
+        let ticks = reporter.ticks.clone();
+        let progress_hint_calls = reporter.progress_hint_calls.clone();
+        let reported_progress = reporter.reported_progress.clone();
+        let task = DeploymentAction {
+            run_fn: Box::new(|_| {
+                thread::sleep(Duration::from_millis(750));
+                Ok(())
+            }),
+            pre_run: Default::default(),
+            run: Default::default(),
+            post_run: Default::default(),
+            post_run_failure: Default::default(),
+        };
+
+        // execute:
+        let result = execute_long_deployment(reporter, task, &CancellationToken::new());
+
+        // verify:
+        assert!(result.is_ok());
+        let ticks = ticks.load(Ordering::SeqCst);
+        let reported_progress = reported_progress.lock().unwrap();
+        assert!(ticks >= 2);
+        assert_eq!(ticks, progress_hint_calls.load(Ordering::SeqCst));
+        assert_eq!(ticks as usize, reported_progress.len());
+        for (tick, progress) in reported_progress.iter() {
+            // hint is clamped to 1.0 once the estimation goes over
+            assert_eq!((*tick as f32 / 4.0).min(1.0), *progress);
+        }
+    }
+
+    // Records the events it receives
+    #[derive(Clone, Default)]
+    struct RecordingObserver {
+        events: Arc<Mutex<Vec<DeploymentLifecycleEvent>>>,
+    }
+
+    impl DeploymentObserver for RecordingObserver {
+        fn on_event(&self, event: &DeploymentLifecycleEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_execute_long_deployment_notifies_observers() {
+        // setup:
+        let counting_observer = CountingDeploymentObserver::new();
+        let recording_observer = RecordingObserver::default();
+        let failing_run_counting_observer = CountingDeploymentObserver::new();
+        let failing_run_recording_observer = RecordingObserver::default();
+        let task = |run_fn: Box<dyn Fn(&CancellationToken) -> Result<(), EngineError>>| DeploymentAction {
+            run_fn,
+            pre_run: Default::default(),
+            run: Default::default(),
+            post_run: Default::default(),
+            post_run_failure: Default::default(),
+        };
+
+        // execute:
+        let result = execute_long_deployment_with_observers(
+            ProgressReporterTest::default(),
+            task(Box::new(|_| {
+                thread::sleep(Duration::from_millis(350));
+                Ok(())
+            })),
+            &CancellationToken::new(),
+            vec![
+                Box::new(counting_observer.clone()),
+                Box::new(recording_observer.clone()),
+            ],
+        );
+        let failing_result = execute_long_deployment_with_observers(
+            ProgressReporterTest::default(),
+            task(Box::new(|_| {
+                Err(EngineError::new_task_cancellation_requested(EventDetailsBuilder::new().build()))
+            })),
+            &CancellationToken::new(),
+            vec![
+                Box::new(failing_run_counting_observer.clone()),
+                Box::new(failing_run_recording_observer.clone()),
+            ],
+        );
+
+        // verify:
+        assert!(result.is_ok());
+        assert_eq!(1, counting_observer.before_start_count());
+        assert!(counting_observer.in_progress_count() >= 2);
+        assert_eq!(1, counting_observer.terminated_count());
+        let events = recording_observer.events.lock().unwrap();
+        assert_eq!(Some(&DeploymentLifecycleEvent::BeforeStart), events.first());
+        assert_eq!(
+            Some(&DeploymentLifecycleEvent::InProgress { progress: Some(0.25) }),
+            events.get(1)
+        );
+        assert_eq!(Some(&DeploymentLifecycleEvent::Terminated { error_tag: None }), events.last());
+
+        assert!(failing_result.is_err());
+        assert_eq!(1, failing_run_counting_observer.before_start_count());
+        assert_eq!(1, failing_run_counting_observer.terminated_count());
+        assert_eq!(
+            Some(&DeploymentLifecycleEvent::Terminated {
+                error_tag: Some(Tag::TaskCancellationRequested)
+            }),
+            failing_run_recording_observer.events.lock().unwrap().last()
+        );
+    }
+
+    #[test]
+    fn test_report_frequency_backoff_intervals_grow_then_cap() {
+        // setup:
+        let backoff = |factor: f32| ReportFrequency::Backoff {
+            initial: Duration::from_secs(10),
+            max: Duration::from_secs(60),
+            factor,
+        };
+
+        // execute:
+        let intervals: Vec<Duration> = backoff(2.0).intervals().take(6).collect();
+
+        // verify:
+        assert_eq!(
+            vec![10, 20, 40, 60, 60, 60]
+                .into_iter()
+                .map(Duration::from_secs)
+                .collect::<Vec<Duration>>(),
+            intervals
+        );
+        assert_eq!(
+            vec![10_000, 15_000, 22_500, 33_750, 50_625, 60_000]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect::<Vec<Duration>>(),
+            backoff(1.5).intervals().take(6).collect::<Vec<Duration>>()
+        );
+        // a factor below 1 is clamped, intervals never shrink
+        assert_eq!(
+            vec![Duration::from_secs(10); 3],
+            backoff(0.5).intervals().take(3).collect::<Vec<Duration>>()
+        );
+        assert_eq!(
+            vec![Duration::from_secs(10); 3],
+            ReportFrequency::Fixed(Duration::from_secs(10))
+                .intervals()
+                .take(3)
+                .collect::<Vec<Duration>>()
+        );
+    }
+
+    #[test]
+    fn test_execute_long_deployment_cancelled_while_running() {
+        // setup:
+        let reporter = DeploymentReporterTest {
+            before_deployment: Arc::new(AtomicBool::new(false)),
+            deployment_in_progress: Arc::new(AtomicBool::new(false)),
+            deployment_terminated: Arc::new(AtomicBool::new(false)),
+            thread_dead: Arc::new(AtomicBool::new(false)),
+            is_task_started: Arc::new(AtomicBool::new(false)),
+        };
+        let deployment_terminated = reporter.deployment_terminated.clone();
+        let cancellation_token = CancellationToken::new();
+        let task = DeploymentAction {
+            // task runs until it's cancelled, or gives up after 10 seconds
+            run_fn: Box::new(|cancellation_token| {
+                let started_at = Instant::now();
+                while started_at.elapsed() < Duration::from_secs(10) {
+                    if cancellation_token.is_cancelled() {
+                        return Err(EngineError::new_task_cancellation_requested(EventDetailsBuilder::new().build()));
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Ok(())
+            }),
+            pre_run: Default::default(),
+            run: Default::default(),
+            post_run: Default::default(),
+            post_run_failure: Default::default(),
+        };
+        let post_run = task.post_run.clone();
+
+        // execute: token is flipped while the task is running
+        let started_at = Instant::now();
+        let result = thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(300));
+                cancellation_token.cancel();
+            });
+            execute_long_deployment(reporter, task, &cancellation_token)
+        });
+
+        // verify:
+        assert_eq!(result.unwrap_err().tag(), &Tag::TaskCancellationRequested);
+        assert!(started_at.elapsed() < Duration::from_secs(10));
+        assert!(deployment_terminated.load(Ordering::SeqCst));
+        assert!(!post_run.load(Ordering::SeqCst));
+    }
 }
@@ -3,6 +3,8 @@ use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_report::logger::EnvLogger;
 use crate::deployment_report::DeploymentReporter;
 use crate::errors::EngineError;
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
 use crate::models::router::RouterService;
 use crate::utilities::to_short_id;
 
@@ -31,6 +33,11 @@ impl DeploymentReporter for RouterDeploymentReporter {
         &self.logger
     }
 
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails {
+        self.logger.event_details().clone()
+    }
+
     fn new_state(&self) -> Self::DeploymentState {}
 
     fn deployment_before_start(&self, _: &mut Self::DeploymentState) {
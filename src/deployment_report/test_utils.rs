@@ -0,0 +1,38 @@
+use crate::deployment_report::DeploymentReporter;
+use crate::errors::EngineError;
+#[cfg(feature = "failure-injection")]
+use crate::events::EventDetails;
+#[cfg(feature = "failure-injection")]
+use crate::test_utils::EventDetailsBuilder;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Reporter doing nothing but counting the terminated deployments, to be wrapped by the reporters under test.
+#[derive(Default)]
+pub struct CountingReporter {
+    pub terminated: AtomicU32,
+}
+
+impl DeploymentReporter for CountingReporter {
+    type DeploymentResult = ();
+    type DeploymentState = ();
+    type Logger = ();
+
+    fn logger(&self) -> &Self::Logger {
+        &()
+    }
+
+    fn new_state(&self) -> Self::DeploymentState {}
+
+    fn deployment_before_start(&self, _: &mut Self::DeploymentState) {}
+
+    fn deployment_in_progress(&self, _: &mut Self::DeploymentState) {}
+
+    fn deployment_terminated(&self, _: &Result<Self::DeploymentResult, EngineError>, _: &mut Self::DeploymentState) {
+        self.terminated.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[cfg(feature = "failure-injection")]
+    fn failure_event_details(&self) -> EventDetails {
+        EventDetailsBuilder::new().build()
+    }
+}
@@ -347,9 +347,40 @@ pub fn to_pvc_render_context(pvcs: &[PersistentVolumeClaim], events: &[Event]) -
     pvcs_context
 }
 
+// Labels set by the deployment (ReplicaSet) and statefulset controllers on the pods of a given revision
+const POD_REVISION_LABELS: [&str; 2] = ["pod-template-hash", "controller-revision-hash"];
+
+fn pod_revision(pod: &Pod) -> Option<&str> {
+    let labels = pod.metadata.labels.as_ref()?;
+    POD_REVISION_LABELS
+        .iter()
+        .find_map(|label| labels.get(*label).map(|revision| revision.as_str()))
+}
+
+/// Fraction of the desired replicas having a ready pod of the current revision, `None` if no replica is expected.
+/// The current revision is the one of the most recently created pod, so ready pods of the previous
+/// revision still running during a rolling update are not counted.
+pub fn ready_pods_progress(pods: &[Pod], desired_replicas: u32) -> Option<f32> {
+    if desired_replicas == 0 {
+        return None;
+    }
+
+    let current_revision = pods
+        .iter()
+        .max_by_key(|pod| pod.metadata.creation_timestamp.as_ref().map(|ts| ts.0))
+        .and_then(pod_revision);
+    let ready_pods = pods
+        .iter()
+        .filter(|pod| current_revision.is_none() || pod_revision(pod) == current_revision)
+        .filter(|pod| pod.is_ready())
+        .count() as f32;
+    Some((ready_pods / desired_replicas as f32).min(1.0))
+}
+
 pub trait QPodExt {
     fn restart_count(&self) -> u32;
     fn is_starting(&self) -> bool;
+    fn is_ready(&self) -> bool;
     fn is_failing(&self) -> Option<&str>;
 }
 
@@ -386,6 +417,18 @@ impl QPodExt for Pod {
         false
     }
 
+    fn is_ready(&self) -> bool {
+        self.status
+            .as_ref()
+            .and_then(|status| status.conditions.as_ref())
+            .map(|conditions| {
+                conditions
+                    .iter()
+                    .any(|condition| condition.type_ == "Ready" && condition.status == "True")
+            })
+            .unwrap_or(false)
+    }
+
     fn is_failing<'a>(&'a self) -> Option<&'a str> {
         // https://stackoverflow.com/questions/57821723/list-of-all-reasons-for-container-states-in-kubernetes
         let is_error_reason = |reason: &str| {
@@ -456,3 +499,62 @@ pub fn get_last_events_for<'a>(
         .sorted_by(|evl, evr| evl.last_timestamp.cmp(&evr.last_timestamp).reverse())
         .take(max_events)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::deployment_report::utils::ready_pods_progress;
+    use chrono::{TimeZone, Utc};
+    use k8s_openapi::api::core::v1::{Pod, PodCondition, PodStatus};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
+    use std::collections::BTreeMap;
+
+    fn pod(revision: &str, created_at_secs: i64, ready: bool) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                labels: Some(BTreeMap::from([("pod-template-hash".to_string(), revision.to_string())])),
+                creation_timestamp: Utc.timestamp_opt(created_at_secs, 0).single().map(Time),
+                ..Default::default()
+            },
+            spec: None,
+            status: Some(PodStatus {
+                phase: Some("Running".to_string()),
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".to_string(),
+                    status: if ready { "True" } else { "False" }.to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_ready_pods_progress_only_counts_pods_of_the_current_revision() {
+        // setup:
+        let test_cases = vec![
+            ("no replica expected", vec![pod("old", 1, true)], 0, None),
+            ("no pod yet", vec![], 2, Some(0.0)),
+            (
+                "rolling update just started",
+                vec![pod("old", 1, true), pod("old", 2, true), pod("new", 3, false)],
+                2,
+                Some(0.0),
+            ),
+            (
+                "rolling update half done",
+                vec![pod("old", 1, true), pod("new", 3, true), pod("new", 4, false)],
+                2,
+                Some(0.5),
+            ),
+            ("rollout done", vec![pod("new", 3, true), pod("new", 4, true)], 2, Some(1.0)),
+        ];
+
+        for (description, pods, desired_replicas, expected) in test_cases {
+            // execute:
+            let progress = ready_pods_progress(&pods, desired_replicas);
+
+            // verify:
+            assert_eq!(progress, expected, "case: {}", description);
+        }
+    }
+}
@@ -5,9 +5,9 @@ use crate::cloud_provider::aws::regions::AwsRegion;
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::service;
 use crate::cloud_provider::service::Service;
-use crate::cmd::docker::Docker;
+use crate::cmd::docker::{Docker, DockerError};
 use crate::container_registry::errors::ContainerRegistryError;
-use crate::container_registry::to_engine_error;
+use crate::container_registry::{to_engine_error, with_refreshed_credentials, RefreshedCredentialsError};
 use crate::deployment_action::deploy_environment::EnvironmentDeployment;
 use crate::deployment_report::logger::EnvLogger;
 use crate::engine::InfrastructureContext;
@@ -145,12 +145,30 @@ impl EnvironmentTask {
                 .map_err(cr_to_engine_error)?;
 
             // Ok now everything is setup, we can try to build the app
-            let build_result = infra_ctx
-                .build_platform()
-                .build(app.get_build_mut(), &logger, should_abort);
+            // Image is pushed at the end of the build, be sure the registry token won't expire in the meantime
+            // and build again if the registry rejects it anyway
+            let build_result = with_refreshed_credentials(
+                |force| cr_registry.refresh_credentials(force),
+                || {
+                    infra_ctx
+                        .build_platform()
+                        .build(app.get_build_mut(), &logger, should_abort)
+                },
+                |err| {
+                    matches!(
+                        err,
+                        BuildError::DockerError {
+                            raw_error: DockerError::PushUnauthorized { .. },
+                            ..
+                        }
+                    )
+                },
+            );
 
             // logging
             let image_name = app.get_build().image.full_image_name_with_tag();
+            let sensitive_values = app.get_build().sensitive_values();
+            let sensitive_values = sensitive_values.iter().map(|value| value.as_str()).collect::<Vec<_>>();
 
             match build_result {
                 Ok(_) => {
@@ -158,17 +176,18 @@ impl EnvironmentTask {
                     logger.send_success(msg);
                     Ok(())
                 }
-                Err(err @ BuildError::Aborted { .. }) => {
+                Err(RefreshedCredentialsError::Credentials(err)) => Err(cr_to_engine_error(err)),
+                Err(RefreshedCredentialsError::Operation(err @ BuildError::Aborted { .. })) => {
                     let msg = format!("🚫 Container image {} build has been canceled", &image_name);
                     let event_details = app.get_event_details(Stage::Environment(EnvironmentStep::Cancelled));
-                    let build_result = build_platform::to_engine_error(event_details, err, msg);
+                    let build_result = build_platform::to_engine_error(event_details, err, msg, &sensitive_values);
                     logger.send_error(build_result.clone());
                     Err(build_result)
                 }
-                Err(err) => {
+                Err(RefreshedCredentialsError::Operation(err)) => {
                     let msg = format!("❌ Container image {} failed to be build: {}", &image_name, err);
                     let event_details = app.get_event_details(Stage::Environment(EnvironmentStep::BuiltError));
-                    let build_result = build_platform::to_engine_error(event_details, err, msg);
+                    let build_result = build_platform::to_engine_error(event_details, err, msg, &sensitive_values);
                     logger.send_error(build_result.clone());
                     Err(build_result)
                 }
@@ -181,7 +200,7 @@ impl EnvironmentTask {
     pub fn deploy_environment(
         mut environment: Environment,
         infra_ctx: &InfrastructureContext,
-        should_abort: &dyn Fn() -> bool,
+        should_abort: &(dyn Fn() -> bool + Sync),
     ) -> Result<(), EngineError> {
         let mut deployed_services: HashSet<Uuid> = HashSet::new();
         let event_details = environment.event_details().clone();
@@ -214,6 +233,7 @@ impl EnvironmentTask {
                 service::Action::Create => env_deployment.on_create(),
                 service::Action::Pause => env_deployment.on_pause(),
                 service::Action::Delete => env_deployment.on_delete(),
+                service::Action::Restart => env_deployment.on_restart(),
             };
             deployed_services = env_deployment.deployed_services;
 
@@ -232,7 +252,9 @@ impl EnvironmentTask {
             }
 
             match action {
-                service::Action::Create => Stage::Environment(EnvironmentStep::DeployedError),
+                service::Action::Create | service::Action::Restart => {
+                    Stage::Environment(EnvironmentStep::DeployedError)
+                }
                 service::Action::Pause => Stage::Environment(EnvironmentStep::PausedError),
                 service::Action::Delete => Stage::Environment(EnvironmentStep::DeletedError),
             }
@@ -302,10 +324,11 @@ impl Task for EnvironmentTask {
         ) {
             Ok(env) => env,
             Err(err) => {
-                self.logger.log(EngineEvent::Error(
-                    EngineError::new_invalid_engine_payload(event_details, err.to_string().as_str()),
-                    None,
-                ));
+                let engine_error = match err.engine_error() {
+                    Some(engine_error) => engine_error.clone(),
+                    None => EngineError::new_invalid_engine_payload(event_details, err.to_string().as_str()),
+                };
+                self.logger.log(EngineEvent::Error(engine_error, None));
                 return;
             }
         };
@@ -325,6 +348,10 @@ impl Task for EnvironmentTask {
                 self.get_event_details(EnvironmentStep::Deleted),
                 EventMessage::new("🗑️ Environment is deleted".to_string(), None),
             )),
+            (Action::Restart, Ok(())) => self.logger.log(EngineEvent::Info(
+                self.get_event_details(EnvironmentStep::Deployed),
+                EventMessage::new("🔄 Environment is restarted".to_string(), None),
+            )),
             (_, Err(err)) if err.tag().is_cancel() => self.logger.log(EngineEvent::Info(
                 self.get_event_details(EnvironmentStep::Cancelled),
                 EventMessage::new("🚫 Deployment has been canceled at user request 🚫".to_string(), None),
@@ -353,6 +380,13 @@ impl Task for EnvironmentTask {
                     EventMessage::new("💣 Environment failed to be deleted".to_string(), None),
                 ));
             }
+            (Action::Restart, Err(err)) => {
+                info!("{}", err);
+                self.logger.log(EngineEvent::Info(
+                    self.get_event_details(EnvironmentStep::DeployedError),
+                    EventMessage::new("💣 Environment failed to be restarted".to_string(), None),
+                ));
+            }
         };
 
         // Uploading to S3 can take a lot of time, and might hit the core timeout
@@ -398,7 +432,7 @@ impl Task for EnvironmentTask {
         true
     }
 
-    fn cancel_checker(&self) -> Box<dyn Fn() -> bool> {
+    fn cancel_checker(&self) -> Box<dyn Fn() -> bool + Send + Sync> {
         let cancel_requested = self.cancel_requested.clone();
         Box::new(move || cancel_requested.load(Ordering::Relaxed))
     }
@@ -78,7 +78,8 @@ impl InfrastructureTask {
         let kubernetes = &self.request.kubernetes;
         if let Some(engine_error) = option_engine_error {
             let infrastructure_step = match self.request.action {
-                Action::Create => InfrastructureStep::CreateError,
+                // a cluster has nothing to restart in place, restarting it redeploys it
+                Action::Create | Action::Restart => InfrastructureStep::CreateError,
                 Action::Pause => InfrastructureStep::PauseError,
                 Action::Delete => InfrastructureStep::DeleteError,
             };
@@ -93,7 +94,7 @@ impl InfrastructureTask {
             logger.log(engine_event);
         } else {
             let infrastructure_step = match self.request.action {
-                Action::Create => InfrastructureStep::Created,
+                Action::Create | Action::Restart => InfrastructureStep::Created,
                 Action::Pause => InfrastructureStep::Paused,
                 Action::Delete => InfrastructureStep::Deleted,
             };
@@ -161,7 +162,7 @@ impl Task for InfrastructureTask {
         };
 
         let _ = match self.request.action {
-            Action::Create => tx.create_kubernetes(),
+            Action::Create | Action::Restart => tx.create_kubernetes(),
             Action::Pause => tx.pause_kubernetes(),
             Action::Delete => tx.delete_kubernetes(),
         };
@@ -197,7 +198,7 @@ impl Task for InfrastructureTask {
         false
     }
 
-    fn cancel_checker(&self) -> Box<dyn Fn() -> bool> {
+    fn cancel_checker(&self) -> Box<dyn Fn() -> bool + Send + Sync> {
         Box::new(|| false)
     }
 }
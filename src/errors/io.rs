@@ -7,6 +7,8 @@ use serde_derive::{Deserialize, Serialize};
 pub struct CommandError {
     message: String,
     full_details: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool: Option<Tool>,
 }
 
 impl From<errors::CommandError> for CommandError {
@@ -14,6 +16,29 @@ impl From<errors::CommandError> for CommandError {
         CommandError {
             message: error.message_safe,
             full_details: error.full_details.unwrap_or_default(),
+            tool: error.tool.map(Tool::from),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Tool {
+    Helm,
+    Terraform,
+    Docker,
+    Kubectl,
+    CloudApi,
+}
+
+impl From<errors::Tool> for Tool {
+    fn from(tool: errors::Tool) -> Self {
+        match tool {
+            errors::Tool::Helm => Tool::Helm,
+            errors::Tool::Terraform => Tool::Terraform,
+            errors::Tool::Docker => Tool::Docker,
+            errors::Tool::Kubectl => Tool::Kubectl,
+            errors::Tool::CloudApi => Tool::CloudApi,
         }
     }
 }
@@ -47,8 +72,10 @@ pub enum Tag {
     K8sCannotGetCrashLoopingPods,
     K8sCannotDeleteCompletedJobs,
     K8sCannotGetPods,
+    K8sCannotCordonNode,
     K8sUpgradeDeployedVsRequestedVersionsInconsistency,
     K8sScaleReplicas,
+    K8sCannotRolloutRestart,
     K8sLoadBalancerConfigurationIssue,
     K8sServiceError,
     K8sGetLogs,
@@ -62,12 +89,15 @@ pub enum Tag {
     K8sErrorCopySecret,
     UnsupportedRegion,
     UnsupportedZone,
+    UnsupportedDatabaseDiskType,
     CannotFindRequiredBinary,
     SubnetsCountShouldBeEven,
+    InvalidCIDRBlock,
     CannotGetOrCreateIamRole,
     CannotCopyFilesFromDirectoryToDirectory,
     CannotPauseClusterTasksAreRunning,
     CannotPauseManagedDatabase,
+    CannotTriggerDatabaseBackup,
     TerraformCannotRemoveEntryOut,
     TerraformErrorWhileExecutingPipeline,
     TerraformErrorWhileExecutingDestroyPipeline,
@@ -88,6 +118,7 @@ pub enum Tag {
     CloudProviderApiMissingInfo,
     K8sValidateRequiredCPUandBurstableError,
     ClientServiceFailedToStart,
+    K8sImagePullError,
     ClientServiceFailedToDeployBeforeStart,
     DatabaseFailedToStartAfterSeveralRetries,
     RouterFailedToDeploy,
@@ -107,6 +138,7 @@ pub enum Tag {
     DockerError,
     DockerPushImageError,
     DockerPullImageError,
+    DockerPullImageRateLimited,
     BuilderDockerCannotListImages,
     ContainerRegistryCannotCreateRepository,
     ContainerRegistryCannotSetRepositoryLifecycleError,
@@ -178,6 +210,8 @@ pub enum Tag {
     TerraformInstanceVolumeCannotBeReduced,
     TerraformInvalidCIDRBlock,
     TerraformStateLocked,
+    TerraformDiagnosticError,
+    TerraformSubnetAddressPoolExhausted,
     CloudProviderGetLoadBalancer,
     CloudProviderGetLoadBalancerTags,
     CloudProviderDeleteLoadBalancer,
@@ -215,10 +249,12 @@ impl From<errors::Tag> for Tag {
             errors::Tag::K8sCannotGetCrashLoopingPods => Tag::K8sCannotGetCrashLoopingPods,
             errors::Tag::K8sCannotDeleteCompletedJobs => Tag::K8sCannotDeleteCompletedJobs,
             errors::Tag::K8sCannotGetPods => Tag::K8sCannotGetPods,
+            errors::Tag::K8sCannotCordonNode => Tag::K8sCannotCordonNode,
             errors::Tag::K8sUpgradeDeployedVsRequestedVersionsInconsistency => {
                 Tag::K8sUpgradeDeployedVsRequestedVersionsInconsistency
             }
             errors::Tag::K8sScaleReplicas => Tag::K8sScaleReplicas,
+            errors::Tag::K8sCannotRolloutRestart => Tag::K8sCannotRolloutRestart,
             errors::Tag::K8sLoadBalancerConfigurationIssue => Tag::K8sLoadBalancerConfigurationIssue,
             errors::Tag::K8sServiceError => Tag::K8sServiceError,
             errors::Tag::K8sGetLogs => Tag::K8sGetLogs,
@@ -229,6 +265,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::K8sPodIsNotReady => Tag::K8sPodIsNotReady,
             errors::Tag::CannotFindRequiredBinary => Tag::CannotFindRequiredBinary,
             errors::Tag::SubnetsCountShouldBeEven => Tag::SubnetsCountShouldBeEven,
+            errors::Tag::InvalidCIDRBlock => Tag::InvalidCIDRBlock,
             errors::Tag::CannotGetOrCreateIamRole => Tag::CannotGetOrCreateIamRole,
             errors::Tag::CannotCopyFilesFromDirectoryToDirectory => Tag::CannotCopyFilesFromDirectoryToDirectory,
             errors::Tag::CannotPauseClusterTasksAreRunning => Tag::CannotPauseClusterTasksAreRunning,
@@ -251,6 +288,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::ObjectStorageCannotPutFileIntoBucket => Tag::ObjectStorageCannotPutFileIntoBucket,
             errors::Tag::UnsupportedRegion => Tag::UnsupportedRegion,
             errors::Tag::UnsupportedZone => Tag::UnsupportedZone,
+            errors::Tag::UnsupportedDatabaseDiskType => Tag::UnsupportedDatabaseDiskType,
             errors::Tag::K8sNodeIsNotReadyWithTheRequestedVersion => Tag::K8sNodeIsNotReadyWithTheRequestedVersion,
             errors::Tag::K8sNodeIsNotReady => Tag::K8sNodeIsNotReady,
             errors::Tag::NoClusterFound => Tag::NoClusterFound,
@@ -259,6 +297,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::K8sValidateRequiredCPUandBurstableError => Tag::K8sValidateRequiredCPUandBurstableError,
             errors::Tag::TerraformContextUnsupportedParameterValue => Tag::TerraformContextUnsupportedParameterValue,
             errors::Tag::ClientServiceFailedToStart => Tag::ClientServiceFailedToStart,
+            errors::Tag::K8sImagePullError => Tag::K8sImagePullError,
             errors::Tag::ClientServiceFailedToDeployBeforeStart => Tag::ClientServiceFailedToDeployBeforeStart,
             errors::Tag::DatabaseFailedToStartAfterSeveralRetries => Tag::DatabaseFailedToStartAfterSeveralRetries,
             errors::Tag::RouterFailedToDeploy => Tag::RouterFailedToDeploy,
@@ -278,6 +317,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::BuilderCloningRepositoryError => Tag::BuilderCloningRepositoryError,
             errors::Tag::DockerPushImageError => Tag::DockerPushImageError,
             errors::Tag::DockerPullImageError => Tag::DockerPullImageError,
+            errors::Tag::DockerPullImageRateLimited => Tag::DockerPullImageRateLimited,
             errors::Tag::ContainerRegistryCannotCreateRepository => Tag::ContainerRegistryCannotCreateRepository,
             errors::Tag::ContainerRegistryCannotSetRepositoryLifecycle => {
                 Tag::ContainerRegistryCannotSetRepositoryLifecycleError
@@ -357,6 +397,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::TerraformInstanceVolumeCannotBeReduced => Tag::TerraformInstanceVolumeCannotBeReduced,
             errors::Tag::HelmDeployTimeout => Tag::HelmDeployTimeout,
             errors::Tag::CannotPauseManagedDatabase => Tag::CannotPauseManagedDatabase,
+            errors::Tag::CannotTriggerDatabaseBackup => Tag::CannotTriggerDatabaseBackup,
             errors::Tag::ObjectStorageCannotDeleteBucket => Tag::ObjectStorageCannotDeleteBucket,
             errors::Tag::ObjectStorageQuotaExceeded => Tag::ObjectStorageQuotaExceeded,
             errors::Tag::ObjectStorageCannotGetObjectFile => Tag::ObjectStorageCannotGetObjectFile,
@@ -369,6 +410,8 @@ impl From<errors::Tag> for Tag {
             errors::Tag::TerraformInvalidCIDRBlock => Tag::TerraformInvalidCIDRBlock,
             errors::Tag::DoNotRespectCloudProviderBestPractices => Tag::DoNotRespectCloudProviderBestPractices,
             errors::Tag::TerraformStateLocked => Tag::TerraformStateLocked,
+            errors::Tag::TerraformDiagnosticError => Tag::TerraformDiagnosticError,
+            errors::Tag::TerraformSubnetAddressPoolExhausted => Tag::TerraformSubnetAddressPoolExhausted,
         }
     }
 }
@@ -381,12 +424,15 @@ pub struct EngineError {
     underlying_error: Option<CommandError>,
     link: Option<String>,
     hint_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint_key: Option<String>,
 }
 
 impl EngineError {
     pub fn from(error: errors::EngineError) -> (Self, EventDetails) {
         (
             EngineError {
+                hint_key: error.hint_key().map(|key| key.to_string()),
                 tag: Tag::from(error.tag),
                 user_log_message: error.user_log_message,
                 underlying_error: error.underlying_error.map(CommandError::from),
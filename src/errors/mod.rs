@@ -4,31 +4,107 @@ extern crate derivative;
 extern crate url;
 
 use crate::build_platform::BuildError;
+use crate::cloud_provider::service::DatabaseType;
 use crate::cloud_provider::Kind;
 use crate::cmd;
 use crate::cmd::docker::DockerError;
 use crate::cmd::helm::HelmError;
 use crate::cmd::terraform::{QuotaExceededError, TerraformError};
 use crate::container_registry::errors::ContainerRegistryError;
+use crate::container_registry::ImageReference;
 
-use crate::cmd::command;
+use crate::cmd::command::{self, exit_status_code};
 use crate::events::{EventDetails, Stage};
 use crate::models::types::VersionsNumber;
 use crate::object_storage::errors::ObjectStorageError;
 use derivative::Derivative;
 use kube::error::Error as KubeError;
+use serde_derive::Serialize;
 use std::fmt::{Display, Formatter};
 use std::io::Error;
+use std::time::Duration;
+use strum_macros::{EnumIter, EnumString};
 use thiserror::Error;
 use url::Url;
 
 const DEFAULT_HINT_MESSAGE: &str = "Need Help ? Please consult our FAQ to troubleshoot your deployment https://hub.qovery.com/docs/using-qovery/troubleshoot/ and visit the forum https://discuss.qovery.com/";
 
 /// ErrorMessageVerbosity: represents command error message's verbosity from minimal to full verbosity.
+#[derive(Clone, Copy)]
 pub enum ErrorMessageVerbosity {
     SafeOnly,
     FullDetailsWithoutEnvVars,
     FullDetails,
+    /// Structured: JSON of `CommandErrorParts`, env vars are never part of it.
+    Structured,
+}
+
+/// CommandErrorParts: command error messages kept apart, for consumers to pick field by field what to display.
+#[derive(Derivative, Clone, PartialEq, Eq, Serialize)]
+#[derivative(Debug)]
+pub struct CommandErrorParts {
+    /// safe: error message omitting displaying any protected data such as passwords and tokens.
+    pub safe: String,
+    /// full: full error message, can contains unsafe text such as passwords and tokens.
+    pub full: Option<String>,
+    /// env_vars: environments variables including touchy data such as secret keys.
+    /// env_vars field is never serialized nor Debug printed, it has to be read explicitly.
+    #[derivative(Debug = "ignore")]
+    #[serde(skip)]
+    pub env_vars: Option<Vec<(String, String)>>,
+}
+
+impl CommandErrorParts {
+    /// Returns the parts as JSON, without env vars.
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| self.safe.to_string())
+    }
+}
+
+/// Tool: third party tool a command error originates from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tool {
+    Helm,
+    Terraform,
+    Docker,
+    Kubectl,
+    /// CloudApi: cloud provider API, i.e: object storage or container registry.
+    CloudApi,
+}
+
+/// RedactedEnvVars: command error env vars, `Display` and `Debug` only show their names, values being `<redacted>`.
+/// Values carry touchy data such as secret keys and have to be read explicitly with `into_raw`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RedactedEnvVars(Vec<(String, String)>);
+
+impl RedactedEnvVars {
+    /// Returns env vars names.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(key, _)| key.as_str())
+    }
+
+    /// Returns env vars with their values, including secrets: never log them.
+    pub fn into_raw(self) -> Vec<(String, String)> {
+        self.0
+    }
+}
+
+impl Display for RedactedEnvVars {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            &self
+                .keys()
+                .map(|key| format!("{}={}", key, REDACTED_SECRET))
+                .collect::<Vec<String>>()
+                .join(" "),
+        )
+    }
+}
+
+impl std::fmt::Debug for RedactedEnvVars {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RedactedEnvVars({})", self)
+    }
 }
 
 /// CommandError: command error, mostly returned by third party tools.
@@ -43,11 +119,52 @@ pub struct CommandError {
     /// env_vars field is ignored from any wild Debug printing because of it touchy data it carries.
     #[derivative(Debug = "ignore")]
     env_vars: Option<Vec<(String, String)>>,
+    /// exit_status: exit code of the underlying tool, if known, see `cmd::command::exit_status_code`.
+    exit_status: Option<i32>,
+    /// tool: third party tool the error originates from, if known.
+    tool: Option<Tool>,
+}
+
+/// Hint for a tool killed with `OOM_KILLED_EXIT_STATUS`, most likely because it ran out of memory.
+fn out_of_memory_hint(command_error: &CommandError) -> Option<Hint> {
+    match command_error.exit_status() {
+        Some(OOM_KILLED_EXIT_STATUS) => Some(Hint::new(
+            "hint.process_out_of_memory",
+            format!(
+                "The process has been killed (exit code {}), most likely because it ran out of memory. Try to reduce its memory usage or to increase the resources allocated to it.",
+                OOM_KILLED_EXIT_STATUS
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// Replaces all occurrences of given sensitive values by `<redacted>`.
+pub fn redact_values(message: &str, sensitive_values: &[&str]) -> String {
+    sensitive_values
+        .iter()
+        .filter(|value| !value.is_empty())
+        .fold(message.to_string(), |message, value| message.replace(value, REDACTED_SECRET))
 }
 
+/// Tells whether an env var may hold a secret, judging by its name, i.e: `GIT_TOKEN`, `DB_PASSWORD`.
+pub fn is_secret_env_var(key: &str) -> bool {
+    let key = key.to_uppercase();
+    ["TOKEN", "SECRET", "PASSWORD", "PASSWD", "KEY", "CREDENTIAL", "AUTH"]
+        .iter()
+        .any(|pattern| key.contains(pattern))
+}
+
+/// Exit status of a process killed by SIGKILL, i.e: by the OOM killer.
+pub const OOM_KILLED_EXIT_STATUS: i32 = 137;
+const MERGED_COMMAND_ERRORS_SEPARATOR: &str = " | ";
+/// Placeholder of secrets removed from command errors.
+pub const REDACTED_SECRET: &str = "<redacted>";
+
 impl From<command::CommandError> for CommandError {
     fn from(err: command::CommandError) -> Self {
-        CommandError::new(err.to_string(), None, None)
+        let exit_status = err.exit_status();
+        CommandError::new(err.to_string(), None, None).with_exit_status(exit_status)
     }
 }
 
@@ -62,15 +179,54 @@ impl CommandError {
         self.message_safe.to_string()
     }
 
-    /// Returns CommandError env_vars.
-    pub fn env_vars(&self) -> Option<Vec<(String, String)>> {
-        self.env_vars.clone()
+    /// Returns CommandError env_vars, their values being hidden when displayed, see `RedactedEnvVars`.
+    pub fn env_vars(&self) -> Option<RedactedEnvVars> {
+        self.env_vars.clone().map(RedactedEnvVars)
+    }
+
+    /// Returns CommandError env_vars with their values, including touchy data such as secret keys.
+    /// To be used only to build another error from this one, never to log them.
+    pub fn into_raw_env_vars(self) -> Option<Vec<(String, String)>> {
+        self.env_vars
+    }
+
+    /// Returns exit code of the underlying tool, if known.
+    pub fn exit_status(&self) -> Option<i32> {
+        self.exit_status
+    }
+
+    /// Returns CommandError carrying the exit code of the underlying tool.
+    pub fn with_exit_status(self, exit_status: Option<i32>) -> Self {
+        CommandError { exit_status, ..self }
+    }
+
+    /// Returns the third party tool the error originates from, if known.
+    pub fn tool(&self) -> Option<Tool> {
+        self.tool
+    }
+
+    /// Returns CommandError attributed to the given third party tool.
+    pub fn with_tool(self, tool: Tool) -> Self {
+        CommandError {
+            tool: Some(tool),
+            ..self
+        }
+    }
+
+    /// Returns error messages as separated parts, see `CommandErrorParts`.
+    pub fn structured(&self) -> CommandErrorParts {
+        CommandErrorParts {
+            safe: self.message_safe.to_string(),
+            full: self.full_details.clone(),
+            env_vars: self.env_vars.clone(),
+        }
     }
 
     /// Returns error message based on verbosity.
     pub fn message(&self, message_verbosity: ErrorMessageVerbosity) -> String {
         match message_verbosity {
             ErrorMessageVerbosity::SafeOnly => self.message_safe.to_string(),
+            ErrorMessageVerbosity::Structured => self.structured().to_json(),
             ErrorMessageVerbosity::FullDetailsWithoutEnvVars => match &self.full_details {
                 None => self.message(ErrorMessageVerbosity::SafeOnly),
                 Some(full_details) => format!("{} / Full details: {}", self.message_safe, full_details),
@@ -96,6 +252,62 @@ impl CommandError {
         }
     }
 
+    /// Returns CommandError having every occurrence of given secrets replaced by `<redacted>`,
+    /// in its messages as well as in its env vars values.
+    pub fn with_redacted_secrets(self, secrets: &[String]) -> CommandError {
+        let secrets: Vec<&str> = secrets.iter().map(|secret| secret.as_str()).collect();
+        let redact = |text: &str| redact_values(text, &secrets);
+
+        CommandError {
+            full_details: self.full_details.as_deref().map(redact),
+            message_safe: redact(&self.message_safe),
+            env_vars: self
+                .env_vars
+                .map(|env_vars| env_vars.into_iter().map(|(k, v)| (k, redact(&v))).collect()),
+            exit_status: self.exit_status,
+            tool: self.tool,
+        }
+    }
+
+    /// Merges two command errors, i.e: failures of several steps of a same pipeline, into a single one.
+    /// Messages are concatenated and env vars unioned, `other` value winning when a key is set by both.
+    /// Exit status and tool are the first ones known.
+    pub fn merge(self, other: CommandError) -> CommandError {
+        let full_details = match (self.full_details, other.full_details) {
+            (Some(full_details), Some(other_full_details)) => Some(format!(
+                "{}{}{}",
+                full_details, MERGED_COMMAND_ERRORS_SEPARATOR, other_full_details
+            )),
+            (full_details, other_full_details) => full_details.or(other_full_details),
+        };
+
+        let env_vars = match (self.env_vars, other.env_vars) {
+            (None, None) => None,
+            (env_vars, other_env_vars) => {
+                let mut merged_env_vars: Vec<(String, String)> = vec![];
+                for (key, value) in env_vars
+                    .into_iter()
+                    .flatten()
+                    .chain(other_env_vars.into_iter().flatten())
+                {
+                    match merged_env_vars.iter_mut().find(|(merged_key, _)| *merged_key == key) {
+                        Some(env_var) => env_var.1 = value,
+                        None => merged_env_vars.push((key, value)),
+                    }
+                }
+                Some(merged_env_vars)
+            }
+        };
+
+        CommandError {
+            full_details,
+            message_safe: format!("{}{}{}", self.message_safe, MERGED_COMMAND_ERRORS_SEPARATOR, other.message_safe),
+            env_vars,
+            exit_status: self.exit_status.or(other.exit_status),
+            tool: self.tool.or(other.tool),
+        }
+    }
+
     /// Creates a new CommandError from safe message. To be used when message is safe.
     pub fn new_from_safe_message(message: String) -> Self {
         CommandError::new(message, None, None)
@@ -107,6 +319,8 @@ impl CommandError {
             full_details: message_raw,
             message_safe,
             env_vars,
+            exit_status: None,
+            tool: None,
         }
     }
 
@@ -116,9 +330,11 @@ impl CommandError {
         safe_message: Option<String>,
     ) -> Self {
         CommandError {
+            exit_status: legacy_command_error.exit_status(),
             full_details: Some(legacy_command_error.to_string()),
             message_safe: safe_message.unwrap_or_else(|| "No message".to_string()),
             env_vars: None,
+            tool: None,
         }
     }
 
@@ -130,6 +346,7 @@ impl CommandError {
         envs: Vec<(String, String)>,
         stdout: Option<String>,
         stderr: Option<String>,
+        exit_status: Option<i32>,
     ) -> Self {
         let mut unsafe_message = format!("{}\ncommand: {} {}", message, bin, cmd_args.join(" "),);
 
@@ -140,7 +357,16 @@ impl CommandError {
             unsafe_message = format!("{}\nSTDERR {}", unsafe_message, txt);
         }
 
+        // secret env vars may be echoed by the command, i.e: a token in an URL
+        let secrets: Vec<String> = envs
+            .iter()
+            .filter(|(key, _)| is_secret_env_var(key))
+            .map(|(_, value)| value.to_string())
+            .collect();
+
         CommandError::new(message, Some(unsafe_message), Some(envs))
+            .with_redacted_secrets(&secrets)
+            .with_exit_status(exit_status)
     }
 }
 
@@ -150,6 +376,8 @@ impl Default for CommandError {
             full_details: None,
             message_safe: "Unknown command error".to_string(),
             env_vars: None,
+            exit_status: None,
+            tool: None,
         }
     }
 }
@@ -179,7 +407,7 @@ impl From<Error> for CommandError {
 impl From<ObjectStorageError> for CommandError {
     fn from(object_storage_error: ObjectStorageError) -> Self {
         // Note: safe message to be manually computed here because we are not 100% sure error won't leak some data
-        match object_storage_error {
+        let command_error = match object_storage_error {
             ObjectStorageError::InvalidBucketName {
                 bucket_name,
                 raw_error_message,
@@ -272,14 +500,16 @@ impl From<ObjectStorageError> for CommandError {
                 Some(raw_error_message),
                 None,
             ),
-        }
+        };
+
+        command_error.with_tool(Tool::CloudApi)
     }
 }
 
 impl From<ContainerRegistryError> for CommandError {
     fn from(container_registry_error: ContainerRegistryError) -> Self {
         // Note: safe message to be manually computed here because we are not 100% sure error won't leak some data
-        match container_registry_error {
+        let command_error = match container_registry_error {
             ContainerRegistryError::InvalidCredentials => {
                 CommandError::new_from_safe_message("Container registry error, invalid credentials".to_string())
             }
@@ -392,7 +622,7 @@ impl From<ContainerRegistryError> for CommandError {
                 raw_error_message,
             } => CommandError::new(
                 format!(
-                    "Container registry error, cannot set tags for for repository `{}` in registry: `{}`",
+                    "Container registry error, cannot set tags for repository `{}` in registry: `{}`",
                     repository_name, registry_name
                 ),
                 Some(raw_error_message),
@@ -401,7 +631,9 @@ impl From<ContainerRegistryError> for CommandError {
             ContainerRegistryError::Unknown { raw_error_message } => {
                 CommandError::new("Container registry unknown error.".to_string(), Some(raw_error_message), None)
             }
-        }
+        };
+
+        command_error.with_tool(Tool::CloudApi)
     }
 }
 
@@ -441,11 +673,19 @@ impl From<BuildError> for CommandError {
                 Some(raw_error.to_string()),
                 None,
             ),
-            BuildError::DockerError { application, raw_error } => CommandError::new(
-                format!("Build error, cannot build application `{}` due to a Docker error", application),
-                Some(raw_error.to_string()),
-                None,
-            ),
+            BuildError::DockerError { application, raw_error } => {
+                let exit_status = match &raw_error {
+                    DockerError::ExitStatusError { exit_status } => exit_status_code(exit_status),
+                    _ => None,
+                };
+                CommandError::new(
+                    format!("Build error, cannot build application `{}` due to a Docker error", application),
+                    Some(raw_error.to_string()),
+                    None,
+                )
+                .with_exit_status(exit_status)
+                .with_tool(Tool::Docker)
+            }
             BuildError::BuildpackError { application, raw_error } => CommandError::new(
                 format!(
                     "Build error, cannot build application `{}` due to a Buildpack error",
@@ -461,7 +701,7 @@ impl From<BuildError> for CommandError {
 impl From<DockerError> for CommandError {
     fn from(docker_error: DockerError) -> Self {
         // Note: safe message to be manually computed here because we are not 100% sure error won't leak some data
-        match docker_error {
+        let command_error = match docker_error {
             DockerError::InvalidConfig { raw_error_message } => {
                 CommandError::new("Docker error, invalid configuration".to_string(), Some(raw_error_message), None)
             }
@@ -473,7 +713,8 @@ impl From<DockerError> for CommandError {
             DockerError::ExitStatusError { exit_status } => CommandError::new_from_safe_message(format!(
                 "Docker error, docker terminated with a non success exit status: `{}`",
                 exit_status
-            )),
+            ))
+            .with_exit_status(exit_status_code(&exit_status)),
             DockerError::Aborted { raw_error_message } => CommandError::new(
                 "Docker error, aborted due to user cancel request".to_string(),
                 Some(raw_error_message),
@@ -484,7 +725,19 @@ impl From<DockerError> for CommandError {
                 Some(raw_error_message),
                 None,
             ),
-        }
+            DockerError::PullRateLimited { raw_error_message } => CommandError::new(
+                "Docker error, image pull rejected by the registry rate limit".to_string(),
+                Some(raw_error_message),
+                None,
+            ),
+            DockerError::PushUnauthorized { raw_error_message } => CommandError::new(
+                "Docker error, image push rejected by the registry, credentials are invalid or expired".to_string(),
+                Some(raw_error_message),
+                None,
+            ),
+        };
+
+        command_error.with_tool(Tool::Docker)
     }
 }
 
@@ -493,11 +746,11 @@ impl From<TerraformError> for CommandError {
         // Terraform errors are 99% safe and carry useful data to be sent to end-users.
         // Hence, for the time being, everything is sent back to end-users.
         // TODO(benjaminch): Terraform errors should be parsed properly extracting useful data from it.
-        CommandError::new(terraform_error.to_string(), None, None)
+        CommandError::new(terraform_error.to_string(), None, None).with_tool(Tool::Terraform)
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, EnumString, EnumIter)]
 /// Tag: unique identifier for an error.
 pub enum Tag {
     /// Unknown: unknown error.
@@ -512,7 +765,7 @@ pub enum Tag {
     NoClusterFound,
     /// ClusterHasNoWorkerNodes: represents an error where the current cluster doesn't have any worker nodes.
     ClusterHasNoWorkerNodes,
-    /// ClusterHasNoWorkerNodes: represents an error where the current cluster doesn't have any worker nodes.
+    /// ClusterWorkerNodeNotFound: represents an error where a given worker node cannot be found in the current cluster.
     ClusterWorkerNodeNotFound,
     /// CannotGetWorkspaceDirectory: represents an error while trying to get workspace directory.
     CannotGetWorkspaceDirectory,
@@ -526,6 +779,8 @@ pub enum Tag {
     UnsupportedRegion,
     /// UnsupportedZone: represents an unsupported zone in region for the given cloud provider.
     UnsupportedZone,
+    /// UnsupportedDatabaseDiskType: represents an unsupported database disk type for the given cloud provider and instance type.
+    UnsupportedDatabaseDiskType,
     /// CannotRetrieveKubernetesConfigFile: represents an error while trying to retrieve Kubernetes config file.
     CannotRetrieveClusterConfigFile,
     /// CannotCreateFile: represents an error while trying to create a file.
@@ -554,8 +809,10 @@ pub enum Tag {
     NumberOfRequestedMaxNodesIsBelowThanCurrentUsage,
     /// CannotDetermineK8sKubeProxyVersion: represents an error when trying to determine kube proxy version which cannot be retrieved.
     CannotDetermineK8sKubeProxyVersion,
-    /// CannotPauseManagedDatabase: as the title says
+    /// CannotPauseManagedDatabase: represents an error while trying to pause a managed database on the cloud provider.
     CannotPauseManagedDatabase,
+    /// CannotTriggerDatabaseBackup: represents an error when the cloud provider fails to start an on-demand backup of a managed database.
+    CannotTriggerDatabaseBackup,
     /// CannotConnectK8sCluster: represents an error when trying to connect to the kubernetes cluster
     CannotConnectK8sCluster,
     /// CannotExecuteK8sApiCustomMetrics: represents an error when trying to get K8s API custom metrics.
@@ -566,7 +823,7 @@ pub enum Tag {
     CloudProviderGetLoadBalancerTags,
     /// CloudProviderDeleteLoadBalancer: represents an issue while trying to delete load balancer from the cloud provider API
     CloudProviderDeleteLoadBalancer,
-    // DoNotRespectCloudProviderBestPractices: represents an error, the user is trying to do something that is not recommended by the cloud provider
+    /// DoNotRespectCloudProviderBestPractices: represents an error, the user is trying to do something that is not recommended by the cloud provider
     DoNotRespectCloudProviderBestPractices,
     /// K8sCannotConnectToApi: represents an error when trying to contact K8s API.
     K8sCannotReachToApi,
@@ -576,6 +833,7 @@ pub enum Tag {
     K8sPodsDisruptionBudgetCannotBeRetrieved,
     /// K8sCannotDeletePod: represents an error where we are not able to delete a pod.
     K8sCannotDeletePod,
+    /// K8sCannotDeletePvc: represents an error where we are not able to delete a persistent volume claim.
     K8sCannotDeletePvc,
     /// K8sCannotGetCrashLoopingPods: represents an error where we are not able to get crash looping pods.
     K8sCannotGetCrashLoopingPods,
@@ -583,10 +841,14 @@ pub enum Tag {
     K8sCannotDeleteCompletedJobs,
     /// K8sCannotGetPods: represents an error where we are not able to get pods.
     K8sCannotGetPods,
+    /// K8sCannotCordonNode: represents an error where a node cannot be cordoned.
+    K8sCannotCordonNode,
     /// K8sUpgradeDeployedVsRequestedVersionsInconsistency: represents an error where there is a K8s versions inconsistency between deployed and requested.
     K8sUpgradeDeployedVsRequestedVersionsInconsistency,
     /// K8sScaleReplicas: represents an error while trying to scale replicas.
     K8sScaleReplicas,
+    /// K8sCannotRolloutRestart: represents an error while trying to restart a service in place.
+    K8sCannotRolloutRestart,
     /// K8sLoadBalancerConfigurationIssue: represents an error where loadbalancer has a configuration issue.
     K8sLoadBalancerConfigurationIssue,
     /// K8sServiceError: represents an error on a k8s service.
@@ -615,6 +877,8 @@ pub enum Tag {
     CannotFindRequiredBinary,
     /// SubnetsCountShouldBeEven: represents an error where subnets count should be even to have as many public than private subnets.
     SubnetsCountShouldBeEven,
+    /// InvalidCIDRBlock: represents an error where a CIDR block is malformed or cannot be split into the requested subnets.
+    InvalidCIDRBlock,
     /// CannotGetOrCreateIamRole: represents an error where we cannot get or create the given IAM role.
     CannotGetOrCreateIamRole,
     /// CannotCopyFilesFromDirectoryToDirectory: represents an error where we cannot copy files from one directory to another.
@@ -678,6 +942,10 @@ pub enum Tag {
     TerraformInvalidCIDRBlock,
     /// TerraformStateLocked: represents an error due to Terraform state lock.
     TerraformStateLocked,
+    /// TerraformDiagnosticError: represents an error reported by a Terraform diagnostic not matching any known issue.
+    TerraformDiagnosticError,
+    /// TerraformSubnetAddressPoolExhausted: represents an error due to a subnet not having enough free IP addresses.
+    TerraformSubnetAddressPoolExhausted,
     /// HelmChartsSetupError: represents an error while trying to setup helm charts.
     HelmChartsSetupError,
     /// HelmChartsDeployError: represents an error while trying to deploy helm charts.
@@ -702,6 +970,8 @@ pub enum Tag {
     OnlyOneClusterExpected,
     /// ClientServiceFailedToStart: represent an error while trying to start a client's service.
     ClientServiceFailedToStart,
+    /// K8sImagePullError: represents an error where a client's service cannot start because its image cannot be pulled.
+    K8sImagePullError,
     /// ClientServiceFailedToDeployBeforeStart: represents an error while trying to deploy a client's service before start.
     ClientServiceFailedToDeployBeforeStart,
     /// DatabaseFailedToStartAfterSeveralRetries: represents an error while trying to start a database after several retries.
@@ -746,6 +1016,8 @@ pub enum Tag {
     DockerPushImageError,
     /// DockerPullImageError: represents an error when trying to pull a docker image.
     DockerPullImageError,
+    /// DockerPullImageRateLimited: represents an error when the registry rejects a docker image pull due to its rate limit.
+    DockerPullImageRateLimited,
     /// ContainerRegistryCannotCreateRepository: represents an error when trying to create a repository.
     ContainerRegistryCannotCreateRepository,
     /// ContainerRegistryCannotSetRepositoryLifecycle: represents an error when trying to set repository lifecycle policy.
@@ -826,10 +1098,675 @@ pub enum Tag {
     JobFailure,
 }
 
+/// Every tag, in declaration order.
+const ALL_TAGS: &[Tag] = &[
+    Tag::Unknown,
+    Tag::InvalidEnginePayload,
+    Tag::InvalidEngineApiInputCannotBeDeserialized,
+    Tag::MissingRequiredEnvVariable,
+    Tag::NoClusterFound,
+    Tag::ClusterHasNoWorkerNodes,
+    Tag::ClusterWorkerNodeNotFound,
+    Tag::CannotGetWorkspaceDirectory,
+    Tag::UnsupportedInstanceType,
+    Tag::NotAllowedInstanceType,
+    Tag::UnsupportedClusterKind,
+    Tag::UnsupportedRegion,
+    Tag::UnsupportedZone,
+    Tag::UnsupportedDatabaseDiskType,
+    Tag::CannotRetrieveClusterConfigFile,
+    Tag::CannotCreateFile,
+    Tag::CannotGetClusterNodes,
+    Tag::NotEnoughNodesAvailableToDeployEnvironment,
+    Tag::NotEnoughResourcesToDeployEnvironment,
+    Tag::CannotUninstallHelmChart,
+    Tag::CannotExecuteK8sVersion,
+    Tag::CannotDetermineK8sMasterVersion,
+    Tag::CannotDetermineK8sRequestedUpgradeVersion,
+    Tag::CannotDetermineK8sKubeletWorkerVersion,
+    Tag::CannotGetNodeGroupList,
+    Tag::CannotGetNodeGroupInfo,
+    Tag::NumberOfRequestedMaxNodesIsBelowThanCurrentUsage,
+    Tag::CannotDetermineK8sKubeProxyVersion,
+    Tag::CannotPauseManagedDatabase,
+    Tag::CannotTriggerDatabaseBackup,
+    Tag::CannotConnectK8sCluster,
+    Tag::CannotExecuteK8sApiCustomMetrics,
+    Tag::CloudProviderGetLoadBalancer,
+    Tag::CloudProviderGetLoadBalancerTags,
+    Tag::CloudProviderDeleteLoadBalancer,
+    Tag::DoNotRespectCloudProviderBestPractices,
+    Tag::K8sCannotReachToApi,
+    Tag::K8sPodDisruptionBudgetInInvalidState,
+    Tag::K8sPodsDisruptionBudgetCannotBeRetrieved,
+    Tag::K8sCannotDeletePod,
+    Tag::K8sCannotDeletePvc,
+    Tag::K8sCannotGetCrashLoopingPods,
+    Tag::K8sCannotDeleteCompletedJobs,
+    Tag::K8sCannotGetPods,
+    Tag::K8sCannotCordonNode,
+    Tag::K8sUpgradeDeployedVsRequestedVersionsInconsistency,
+    Tag::K8sScaleReplicas,
+    Tag::K8sCannotRolloutRestart,
+    Tag::K8sLoadBalancerConfigurationIssue,
+    Tag::K8sServiceError,
+    Tag::K8sGetLogs,
+    Tag::K8sGetEvents,
+    Tag::K8sDescribe,
+    Tag::K8sHistory,
+    Tag::K8sCannotCreateNamespace,
+    Tag::K8sPodIsNotReady,
+    Tag::K8sNodeIsNotReadyWithTheRequestedVersion,
+    Tag::K8sNodeIsNotReady,
+    Tag::K8sValidateRequiredCPUandBurstableError,
+    Tag::K8sErrorCopySecret,
+    Tag::CannotFindRequiredBinary,
+    Tag::SubnetsCountShouldBeEven,
+    Tag::InvalidCIDRBlock,
+    Tag::CannotGetOrCreateIamRole,
+    Tag::CannotCopyFilesFromDirectoryToDirectory,
+    Tag::CannotPauseClusterTasksAreRunning,
+    Tag::TerraformUnknownError,
+    Tag::TerraformInvalidCredentials,
+    Tag::TerraformAccountBlockedByProvider,
+    Tag::TerraformMultipleInterruptsReceived,
+    Tag::TerraformNotEnoughPermissions,
+    Tag::TerraformWrongState,
+    Tag::TerraformResourceDependencyViolation,
+    Tag::TerraformInstanceTypeDoesntExist,
+    Tag::TerraformInstanceVolumeCannotBeReduced,
+    Tag::TerraformConfigFileNotFound,
+    Tag::TerraformConfigFileInvalidContent,
+    Tag::TerraformCannotDeleteLockFile,
+    Tag::TerraformInitError,
+    Tag::TerraformValidateError,
+    Tag::TerraformPlanError,
+    Tag::TerraformApplyError,
+    Tag::TerraformDestroyError,
+    Tag::TerraformCannotRemoveEntryOut,
+    Tag::TerraformErrorWhileExecutingPipeline,
+    Tag::TerraformErrorWhileExecutingDestroyPipeline,
+    Tag::TerraformContextUnsupportedParameterValue,
+    Tag::TerraformCloudProviderQuotasReached,
+    Tag::TerraformCloudProviderActivationRequired,
+    Tag::TerraformServiceNotActivatedOptInRequired,
+    Tag::TerraformWaitingTimeoutResource,
+    Tag::TerraformAlreadyExistingResource,
+    Tag::TerraformInvalidCIDRBlock,
+    Tag::TerraformStateLocked,
+    Tag::TerraformDiagnosticError,
+    Tag::TerraformSubnetAddressPoolExhausted,
+    Tag::HelmChartsSetupError,
+    Tag::HelmChartsDeployError,
+    Tag::HelmChartsUpgradeError,
+    Tag::HelmChartUninstallError,
+    Tag::HelmHistoryError,
+    Tag::HelmDeployTimeout,
+    Tag::CannotGetAnyAvailableVPC,
+    Tag::UnsupportedVersion,
+    Tag::CannotGetSupportedVersions,
+    Tag::CannotGetCluster,
+    Tag::OnlyOneClusterExpected,
+    Tag::ClientServiceFailedToStart,
+    Tag::K8sImagePullError,
+    Tag::ClientServiceFailedToDeployBeforeStart,
+    Tag::DatabaseFailedToStartAfterSeveralRetries,
+    Tag::RouterFailedToDeploy,
+    Tag::CloudProviderInformationError,
+    Tag::CloudProviderClientInvalidCredentials,
+    Tag::CloudProviderApiMissingInfo,
+    Tag::VersionNumberParsingError,
+    Tag::NotImplementedError,
+    Tag::TaskCancellationRequested,
+    Tag::BuilderError,
+    Tag::BuilderDockerCannotFindAnyDockerfile,
+    Tag::BuilderDockerCannotReadDockerfile,
+    Tag::BuilderDockerCannotExtractEnvVarsFromDockerfile,
+    Tag::BuilderDockerCannotBuildContainerImage,
+    Tag::BuilderDockerCannotListImages,
+    Tag::BuilderBuildpackInvalidLanguageFormat,
+    Tag::BuilderBuildpackCannotBuildContainerImage,
+    Tag::BuilderGetBuildError,
+    Tag::BuilderCloningRepositoryError,
+    Tag::DockerError,
+    Tag::DockerPushImageError,
+    Tag::DockerPullImageError,
+    Tag::DockerPullImageRateLimited,
+    Tag::ContainerRegistryCannotCreateRepository,
+    Tag::ContainerRegistryCannotSetRepositoryLifecycle,
+    Tag::ContainerRegistryCannotGetCredentials,
+    Tag::ContainerRegistryCannotDeleteImage,
+    Tag::ContainerRegistryImageDoesntExist,
+    Tag::ContainerRegistryImageUnreachableAfterPush,
+    Tag::ContainerRegistryRepositoryDoesntExistInRegistry,
+    Tag::ContainerRegistryRegistryDoesntExist,
+    Tag::ContainerRegistryCannotDeleteRepository,
+    Tag::ContainerRegistryInvalidInformation,
+    Tag::ContainerRegistryInvalidCredentials,
+    Tag::ContainerRegistryCannotLinkRegistryToCluster,
+    Tag::ContainerRegistryCannotCreateRegistry,
+    Tag::ContainerRegistryCannotDeleteRegistry,
+    Tag::ContainerRegistryCannotSetRepositoryTags,
+    Tag::ContainerRegistryUnknownError,
+    Tag::KubeconfigFileDoNotPermitToConnectToK8sCluster,
+    Tag::KubeconfigSecurityCheckError,
+    Tag::DeleteLocalKubeconfigFileError,
+    Tag::VaultConnectionError,
+    Tag::VaultSecretCouldNotBeRetrieved,
+    Tag::VaultSecretCouldNotBeCreatedOrUpdated,
+    Tag::VaultSecretCouldNotBeDeleted,
+    Tag::JsonDeserializationError,
+    Tag::ClusterSecretsManipulationError,
+    Tag::DnsProviderInformationError,
+    Tag::DnsProviderInvalidCredentials,
+    Tag::DnsProviderInvalidApiUrl,
+    Tag::ObjectStorageCannotCreateBucket,
+    Tag::ObjectStorageCannotPutFileIntoBucket,
+    Tag::ObjectStorageCannotDeleteFileIntoBucket,
+    Tag::ObjectStorageCannotDeleteBucket,
+    Tag::ObjectStorageCannotActivateBucketVersioning,
+    Tag::ObjectStorageQuotaExceeded,
+    Tag::ObjectStorageInvalidBucketName,
+    Tag::ObjectStorageCannotEmptyBucket,
+    Tag::ObjectStorageCannotTagBucket,
+    Tag::ObjectStorageCannotGetObjectFile,
+    Tag::JobFailure,
+];
+
+/// Error codes of removed tags, they must not be given to any other tag.
+pub const RETIRED_ERROR_CODES: &[u32] = &[];
+
+/// Stable translation keys of the hints set by error constructors, so clients can localize them.
+/// Keys must never be changed: a constructor giving a hint has to register its key here.
+pub const HINT_KEYS: &[&str] = &[
+    "hint.process_out_of_memory",
+    "hint.invalid_engine_api_input_cannot_be_deserialized",
+    "hint.invalid_engine_payload",
+    "hint.cluster_has_no_worker_nodes",
+    "hint.cluster_worker_node_not_found",
+    "hint.cloud_provider_api_missing_info",
+    "hint.not_allowed_instance_type",
+    "hint.unsupported_instance_type",
+    "hint.unsupported_cluster_kind",
+    "hint.unsupported_region",
+    "hint.unsupported_zone",
+    "hint.unsupported_database_disk_type",
+    "hint.not_enough_nodes_available_to_deploy_environment",
+    "hint.number_of_requested_max_nodes_is_below_than_current_usage",
+    "hint.not_enough_resources_to_deploy_environment",
+    "hint.kubeconfig_file_do_not_permit_to_connect_to_k8s_cluster",
+    "hint.k8s_cannot_reach_to_api",
+    "hint.k8s_blue_green_rollback",
+    "hint.k8s_node_is_not_ready.disk_pressure",
+    "hint.k8s_node_is_not_ready.memory_pressure",
+    "hint.k8s_node_is_not_ready",
+    "hint.k8s_validate_required_cpu_and_burstable_error",
+    "hint.cannot_get_or_create_iam_role.naming_conflict",
+    "hint.cannot_get_or_create_iam_role.permission_denied",
+    "hint.terraform_unknown_error",
+    "hint.terraform_multiple_interrupts_received",
+    "hint.terraform_invalid_credentials",
+    "hint.terraform_account_blocked_by_provider.aws",
+    "hint.terraform_account_blocked_by_provider",
+    "hint.terraform_config_file_not_found",
+    "hint.terraform_config_file_invalid_content",
+    "hint.terraform_cloud_provider_quotas_reached.aws",
+    "hint.terraform_cloud_provider_quotas_reached",
+    "hint.terraform_cloud_provider_activation_required",
+    "hint.terraform_not_enough_permissions",
+    "hint.terraform_wrong_state",
+    "hint.terraform_resource_dependency_violation",
+    "hint.terraform_instance_type_doesnt_exist",
+    "hint.terraform_instance_volume_cannot_be_reduced",
+    "hint.terraform_invalid_cidr_block",
+    "hint.terraform_subnet_address_pool_exhausted",
+    "hint.terraform_state_locked",
+    "hint.terraform_diagnostic_error",
+    "hint.container_registry_invalid_credentials",
+    "hint.container_registry_tls_handshake_failed",
+    "hint.helm_deploy_timeout",
+    "hint.cannot_get_cluster",
+    "hint.client_service_failed_to_start",
+    "hint.k8s_image_pull_error",
+    "hint.database_failed_to_start_after_several_retries",
+    "hint.cloud_provider_client_invalid_credentials",
+    "hint.only_one_cluster_expected",
+    "hint.builder_docker_cannot_find_any_dockerfile",
+    "hint.builder_buildpack_invalid_language_format",
+    "hint.builder_buildpack_cannot_build_container_image",
+    "hint.docker_pull_image_rate_limited",
+    "hint.builder_docker_cannot_build_container_image",
+    "hint.object_storage_quota_exceeded.scaleway",
+    "hint.object_storage_quota_exceeded",
+    "hint.object_storage_invalid_bucket_name",
+    "hint.cloud_provider_information_error",
+    "hint.container_registry_invalid_information",
+    "hint.dns_provider_information_error",
+    "hint.dns_provider_invalid_credentials",
+    "hint.dns_provider_invalid_api_url",
+    "hint.cloud_provider_get_load_balancer",
+    "hint.cloud_provider_get_load_balancer_tags",
+];
+
 impl Tag {
     pub fn is_cancel(&self) -> bool {
         matches!(self, Tag::TaskCancellationRequested)
     }
+
+    /// Returns true if the error is transient and the failing operation can be retried as is,
+    /// false if it is deterministic and retrying would fail the same way.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Tag::K8sCannotReachToApi
+                | Tag::CannotConnectK8sCluster
+                | Tag::TerraformStateLocked
+                | Tag::CannotGetCluster
+                | Tag::HelmDeployTimeout
+        )
+    }
+
+    /// Delay to wait before retrying a retryable error, if the error calls for one.
+    /// For example a terraform state lock is usually held by another run for a while.
+    pub fn suggested_retry_delay(&self) -> Option<Duration> {
+        match self {
+            Tag::TerraformStateLocked => Some(Duration::from_secs(60)),
+            Tag::HelmDeployTimeout => Some(Duration::from_secs(30)),
+            Tag::K8sCannotReachToApi | Tag::CannotConnectK8sCluster | Tag::CannotGetCluster => {
+                Some(Duration::from_secs(10))
+            }
+            _ => None,
+        }
+    }
+
+    /// Stable numeric code of the tag, to be shared with support and searched in the knowledge base.
+    ///
+    /// Codes are grouped by ranges:
+    ///
+    /// * 1000-1999: engine, payload and generic errors
+    /// * 2000-2999: kubernetes clusters and cloud providers infrastructure
+    /// * 3000-3999: helm
+    /// * 4000-4999: terraform
+    /// * 5000-5999: container registries and docker
+    /// * 6000-6999: object storage
+    /// * 7000-7999: builder
+    /// * 8000-8999: secrets, DNS providers and kubeconfig
+    /// * 9000-9999: services deployment (applications, containers, databases, routers, jobs)
+    ///
+    /// A code must never be changed nor reused: when a tag is removed, its code goes to `RETIRED_ERROR_CODES`.
+    /// New tags take the next free code in their range.
+    pub fn error_code(&self) -> u32 {
+        match self {
+            // engine, payload and generic errors
+            Tag::Unknown => 1000,
+            Tag::InvalidEnginePayload => 1001,
+            Tag::InvalidEngineApiInputCannotBeDeserialized => 1002,
+            Tag::MissingRequiredEnvVariable => 1003,
+            Tag::CannotGetWorkspaceDirectory => 1004,
+            Tag::CannotCreateFile => 1005,
+            Tag::CannotFindRequiredBinary => 1006,
+            Tag::CannotCopyFilesFromDirectoryToDirectory => 1007,
+            Tag::UnsupportedVersion => 1008,
+            Tag::CannotGetSupportedVersions => 1009,
+            Tag::VersionNumberParsingError => 1010,
+            Tag::NotImplementedError => 1011,
+            Tag::TaskCancellationRequested => 1012,
+            Tag::JsonDeserializationError => 1013,
+            // kubernetes clusters and cloud providers infrastructure
+            Tag::NoClusterFound => 2000,
+            Tag::ClusterHasNoWorkerNodes => 2001,
+            Tag::ClusterWorkerNodeNotFound => 2002,
+            Tag::UnsupportedInstanceType => 2003,
+            Tag::NotAllowedInstanceType => 2004,
+            Tag::UnsupportedClusterKind => 2005,
+            Tag::UnsupportedRegion => 2006,
+            Tag::UnsupportedZone => 2007,
+            Tag::CannotRetrieveClusterConfigFile => 2008,
+            Tag::CannotGetClusterNodes => 2009,
+            Tag::NotEnoughNodesAvailableToDeployEnvironment => 2010,
+            Tag::NotEnoughResourcesToDeployEnvironment => 2011,
+            Tag::CannotExecuteK8sVersion => 2012,
+            Tag::CannotDetermineK8sMasterVersion => 2013,
+            Tag::CannotDetermineK8sRequestedUpgradeVersion => 2014,
+            Tag::CannotDetermineK8sKubeletWorkerVersion => 2015,
+            Tag::CannotGetNodeGroupList => 2016,
+            Tag::CannotGetNodeGroupInfo => 2017,
+            Tag::NumberOfRequestedMaxNodesIsBelowThanCurrentUsage => 2018,
+            Tag::CannotDetermineK8sKubeProxyVersion => 2019,
+            Tag::CannotConnectK8sCluster => 2020,
+            Tag::CannotExecuteK8sApiCustomMetrics => 2021,
+            Tag::CloudProviderGetLoadBalancer => 2022,
+            Tag::CloudProviderGetLoadBalancerTags => 2023,
+            Tag::CloudProviderDeleteLoadBalancer => 2024,
+            Tag::DoNotRespectCloudProviderBestPractices => 2025,
+            Tag::K8sCannotReachToApi => 2026,
+            Tag::K8sPodDisruptionBudgetInInvalidState => 2027,
+            Tag::K8sPodsDisruptionBudgetCannotBeRetrieved => 2028,
+            Tag::K8sCannotDeletePod => 2029,
+            Tag::K8sCannotDeletePvc => 2030,
+            Tag::K8sCannotGetCrashLoopingPods => 2031,
+            Tag::K8sCannotDeleteCompletedJobs => 2032,
+            Tag::K8sCannotGetPods => 2033,
+            Tag::K8sCannotCordonNode => 2059,
+            Tag::K8sUpgradeDeployedVsRequestedVersionsInconsistency => 2034,
+            Tag::K8sScaleReplicas => 2035,
+            Tag::K8sLoadBalancerConfigurationIssue => 2036,
+            Tag::K8sServiceError => 2037,
+            Tag::K8sGetLogs => 2038,
+            Tag::K8sGetEvents => 2039,
+            Tag::K8sDescribe => 2040,
+            Tag::K8sHistory => 2041,
+            Tag::K8sCannotCreateNamespace => 2042,
+            Tag::K8sPodIsNotReady => 2043,
+            Tag::K8sNodeIsNotReadyWithTheRequestedVersion => 2044,
+            Tag::K8sNodeIsNotReady => 2045,
+            Tag::K8sValidateRequiredCPUandBurstableError => 2046,
+            Tag::K8sErrorCopySecret => 2047,
+            Tag::SubnetsCountShouldBeEven => 2048,
+            Tag::CannotGetOrCreateIamRole => 2049,
+            Tag::CannotPauseClusterTasksAreRunning => 2050,
+            Tag::CannotGetAnyAvailableVPC => 2051,
+            Tag::CannotGetCluster => 2052,
+            Tag::OnlyOneClusterExpected => 2053,
+            Tag::CloudProviderInformationError => 2054,
+            Tag::CloudProviderClientInvalidCredentials => 2055,
+            Tag::CloudProviderApiMissingInfo => 2056,
+            Tag::K8sCannotRolloutRestart => 2057,
+            Tag::InvalidCIDRBlock => 2058,
+            // helm
+            Tag::CannotUninstallHelmChart => 3000,
+            Tag::HelmChartsSetupError => 3001,
+            Tag::HelmChartsDeployError => 3002,
+            Tag::HelmChartsUpgradeError => 3003,
+            Tag::HelmChartUninstallError => 3004,
+            Tag::HelmHistoryError => 3005,
+            Tag::HelmDeployTimeout => 3006,
+            // terraform
+            Tag::TerraformUnknownError => 4000,
+            Tag::TerraformInvalidCredentials => 4001,
+            Tag::TerraformAccountBlockedByProvider => 4002,
+            Tag::TerraformMultipleInterruptsReceived => 4003,
+            Tag::TerraformNotEnoughPermissions => 4004,
+            Tag::TerraformWrongState => 4005,
+            Tag::TerraformResourceDependencyViolation => 4006,
+            Tag::TerraformInstanceTypeDoesntExist => 4007,
+            Tag::TerraformInstanceVolumeCannotBeReduced => 4008,
+            Tag::TerraformConfigFileNotFound => 4009,
+            Tag::TerraformConfigFileInvalidContent => 4010,
+            Tag::TerraformCannotDeleteLockFile => 4011,
+            Tag::TerraformInitError => 4012,
+            Tag::TerraformValidateError => 4013,
+            Tag::TerraformPlanError => 4014,
+            Tag::TerraformApplyError => 4015,
+            Tag::TerraformDestroyError => 4016,
+            Tag::TerraformCannotRemoveEntryOut => 4017,
+            Tag::TerraformErrorWhileExecutingPipeline => 4018,
+            Tag::TerraformErrorWhileExecutingDestroyPipeline => 4019,
+            Tag::TerraformContextUnsupportedParameterValue => 4020,
+            Tag::TerraformCloudProviderQuotasReached => 4021,
+            Tag::TerraformCloudProviderActivationRequired => 4022,
+            Tag::TerraformServiceNotActivatedOptInRequired => 4023,
+            Tag::TerraformWaitingTimeoutResource => 4024,
+            Tag::TerraformAlreadyExistingResource => 4025,
+            Tag::TerraformInvalidCIDRBlock => 4026,
+            Tag::TerraformStateLocked => 4027,
+            Tag::TerraformDiagnosticError => 4028,
+            Tag::TerraformSubnetAddressPoolExhausted => 4029,
+            // container registries and docker
+            Tag::DockerError => 5000,
+            Tag::DockerPushImageError => 5001,
+            Tag::DockerPullImageError => 5002,
+            Tag::ContainerRegistryCannotCreateRepository => 5003,
+            Tag::ContainerRegistryCannotSetRepositoryLifecycle => 5004,
+            Tag::ContainerRegistryCannotGetCredentials => 5005,
+            Tag::ContainerRegistryCannotDeleteImage => 5006,
+            Tag::ContainerRegistryImageDoesntExist => 5007,
+            Tag::ContainerRegistryImageUnreachableAfterPush => 5008,
+            Tag::ContainerRegistryRepositoryDoesntExistInRegistry => 5009,
+            Tag::ContainerRegistryRegistryDoesntExist => 5010,
+            Tag::ContainerRegistryCannotDeleteRepository => 5011,
+            Tag::ContainerRegistryInvalidInformation => 5012,
+            Tag::ContainerRegistryInvalidCredentials => 5013,
+            Tag::ContainerRegistryCannotLinkRegistryToCluster => 5014,
+            Tag::ContainerRegistryCannotCreateRegistry => 5015,
+            Tag::ContainerRegistryCannotDeleteRegistry => 5016,
+            Tag::ContainerRegistryCannotSetRepositoryTags => 5017,
+            Tag::ContainerRegistryUnknownError => 5018,
+            Tag::DockerPullImageRateLimited => 5019,
+            // object storage
+            Tag::ObjectStorageCannotCreateBucket => 6000,
+            Tag::ObjectStorageCannotPutFileIntoBucket => 6001,
+            Tag::ObjectStorageCannotDeleteFileIntoBucket => 6002,
+            Tag::ObjectStorageCannotDeleteBucket => 6003,
+            Tag::ObjectStorageCannotActivateBucketVersioning => 6004,
+            Tag::ObjectStorageQuotaExceeded => 6005,
+            Tag::ObjectStorageInvalidBucketName => 6006,
+            Tag::ObjectStorageCannotEmptyBucket => 6007,
+            Tag::ObjectStorageCannotTagBucket => 6008,
+            Tag::ObjectStorageCannotGetObjectFile => 6009,
+            // builder
+            Tag::BuilderError => 7000,
+            Tag::BuilderDockerCannotFindAnyDockerfile => 7001,
+            Tag::BuilderDockerCannotReadDockerfile => 7002,
+            Tag::BuilderDockerCannotExtractEnvVarsFromDockerfile => 7003,
+            Tag::BuilderDockerCannotBuildContainerImage => 7004,
+            Tag::BuilderDockerCannotListImages => 7005,
+            Tag::BuilderBuildpackInvalidLanguageFormat => 7006,
+            Tag::BuilderBuildpackCannotBuildContainerImage => 7007,
+            Tag::BuilderGetBuildError => 7008,
+            Tag::BuilderCloningRepositoryError => 7009,
+            // secrets, DNS providers and kubeconfig
+            Tag::KubeconfigFileDoNotPermitToConnectToK8sCluster => 8000,
+            Tag::KubeconfigSecurityCheckError => 8001,
+            Tag::DeleteLocalKubeconfigFileError => 8002,
+            Tag::VaultConnectionError => 8003,
+            Tag::VaultSecretCouldNotBeRetrieved => 8004,
+            Tag::VaultSecretCouldNotBeCreatedOrUpdated => 8005,
+            Tag::VaultSecretCouldNotBeDeleted => 8006,
+            Tag::ClusterSecretsManipulationError => 8007,
+            Tag::DnsProviderInformationError => 8008,
+            Tag::DnsProviderInvalidCredentials => 8009,
+            Tag::DnsProviderInvalidApiUrl => 8010,
+            // services deployment (applications, containers, databases, routers, jobs)
+            Tag::CannotPauseManagedDatabase => 9000,
+            Tag::ClientServiceFailedToStart => 9001,
+            Tag::ClientServiceFailedToDeployBeforeStart => 9002,
+            Tag::DatabaseFailedToStartAfterSeveralRetries => 9003,
+            Tag::RouterFailedToDeploy => 9004,
+            Tag::JobFailure => 9005,
+            Tag::UnsupportedDatabaseDiskType => 9006,
+            Tag::CannotTriggerDatabaseBackup => 9007,
+            Tag::K8sImagePullError => 9008,
+        }
+    }
+
+    /// Returns every tag, i.e: to build the errors documentation.
+    pub fn all() -> &'static [Tag] {
+        ALL_TAGS
+    }
+
+    /// Short user-facing description of the tag.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Tag::Unknown => "Unknown error.",
+            Tag::InvalidEnginePayload => "Represents an error when the received payload contains invalid informations.",
+            Tag::InvalidEngineApiInputCannotBeDeserialized => "Represents an error where Engine's API input is not valid and cannot be deserialized.",
+            Tag::MissingRequiredEnvVariable => "Represents an error where a required env variable is not set.",
+            Tag::NoClusterFound => "Represents an error where no cluster was found.",
+            Tag::ClusterHasNoWorkerNodes => "Represents an error where the current cluster doesn't have any worker nodes.",
+            Tag::ClusterWorkerNodeNotFound => "Represents an error where a given worker node cannot be found in the current cluster.",
+            Tag::CannotGetWorkspaceDirectory => "Represents an error while trying to get workspace directory.",
+            Tag::UnsupportedInstanceType => "Represents an unsupported instance type for the given cloud provider.",
+            Tag::NotAllowedInstanceType => "Represents not allowed instance type for a specific kind of cluster.",
+            Tag::UnsupportedClusterKind => "Represents an unsupported cluster kind by Qovery.",
+            Tag::UnsupportedRegion => "Represents an unsupported region for the given cloud provider.",
+            Tag::UnsupportedZone => "Represents an unsupported zone in region for the given cloud provider.",
+            Tag::UnsupportedDatabaseDiskType => {
+                "Represents an unsupported database disk type for the given cloud provider and instance type."
+            }
+            Tag::CannotRetrieveClusterConfigFile => "Represents an error while trying to retrieve Kubernetes config file.",
+            Tag::CannotCreateFile => "Represents an error while trying to create a file.",
+            Tag::CannotGetClusterNodes => "Represents an error while trying to get cluster's nodes.",
+            Tag::NotEnoughNodesAvailableToDeployEnvironment => "Represents an error when trying to deploy an environment but there the desired number of nodes exceeds the maximum value.",
+            Tag::NotEnoughResourcesToDeployEnvironment => "Represents an error when trying to deploy an environment but there are not enough resources available on the cluster.",
+            Tag::CannotUninstallHelmChart => "Represents an error when trying to uninstall an helm chart on the cluster, uninstallation couldn't be proceeded.",
+            Tag::CannotExecuteK8sVersion => "Represents an error when trying to execute kubernetes version command.",
+            Tag::CannotDetermineK8sMasterVersion => "Represents an error when trying to determine kubernetes master version which cannot be retrieved.",
+            Tag::CannotDetermineK8sRequestedUpgradeVersion => "Represents an error when trying to determines upgrade requested kubernetes version.",
+            Tag::CannotDetermineK8sKubeletWorkerVersion => "Represents an error when trying to determine kubelet worker version which cannot be retrieved.",
+            Tag::CannotGetNodeGroupList => "Represents an error while getting node group list from the cloud provider.",
+            Tag::CannotGetNodeGroupInfo => "Represent and error caused by the cloud provider because no Nodegroup information has been returned.",
+            Tag::NumberOfRequestedMaxNodesIsBelowThanCurrentUsage => "Represents an error explaining to the user the requested maximum of nodes is below the current usage.",
+            Tag::CannotDetermineK8sKubeProxyVersion => "Represents an error when trying to determine kube proxy version which cannot be retrieved.",
+            Tag::CannotPauseManagedDatabase => "Represents an error while trying to pause a managed database on the cloud provider.",
+            Tag::CannotTriggerDatabaseBackup => "Represents an error when the cloud provider fails to start an on-demand backup of a managed database.",
+            Tag::CannotConnectK8sCluster => "Represents an error when trying to connect to the kubernetes cluster.",
+            Tag::CannotExecuteK8sApiCustomMetrics => "Represents an error when trying to get K8s API custom metrics.",
+            Tag::CloudProviderGetLoadBalancer => "Represents an issue while trying to get load balancers from the cloud provider API.",
+            Tag::CloudProviderGetLoadBalancerTags => "Represents an issue while trying to get load balancer tags from the cloud provider API.",
+            Tag::CloudProviderDeleteLoadBalancer => "Represents an issue while trying to delete load balancer from the cloud provider API.",
+            Tag::DoNotRespectCloudProviderBestPractices => "Represents an error, the user is trying to do something that is not recommended by the cloud provider.",
+            Tag::K8sCannotReachToApi => "Represents an error when trying to contact K8s API.",
+            Tag::K8sPodDisruptionBudgetInInvalidState => "Represents an error where pod disruption budget is in an invalid state.",
+            Tag::K8sPodsDisruptionBudgetCannotBeRetrieved => "Represents an error where pod disruption budget cannot be retrieved.",
+            Tag::K8sCannotDeletePod => "Represents an error where we are not able to delete a pod.",
+            Tag::K8sCannotDeletePvc => "Represents an error where we are not able to delete a persistent volume claim.",
+            Tag::K8sCannotGetCrashLoopingPods => "Represents an error where we are not able to get crash looping pods.",
+            Tag::K8sCannotDeleteCompletedJobs => "Represents an error where we are not able to delete completed jobs.",
+            Tag::K8sCannotGetPods => "Represents an error where we are not able to get pods.",
+            Tag::K8sCannotCordonNode => "Represents an error where a node cannot be cordoned.",
+            Tag::K8sUpgradeDeployedVsRequestedVersionsInconsistency => "Represents an error where there is a K8s versions inconsistency between deployed and requested.",
+            Tag::K8sScaleReplicas => "Represents an error while trying to scale replicas.",
+            Tag::K8sCannotRolloutRestart => "Represents an error while trying to restart a service in place.",
+            Tag::K8sLoadBalancerConfigurationIssue => "Represents an error where loadbalancer has a configuration issue.",
+            Tag::K8sServiceError => "Represents an error on a k8s service.",
+            Tag::K8sGetLogs => "Represents an error during a k8s logs command.",
+            Tag::K8sGetEvents => "Represents an error during a k8s get events command.",
+            Tag::K8sDescribe => "Represents an error during a k8s describe command.",
+            Tag::K8sHistory => "Represents an error during a k8s history command.",
+            Tag::K8sCannotCreateNamespace => "Represents an error while trying to create a k8s namespace.",
+            Tag::K8sPodIsNotReady => "Represents an error where the given pod is not ready.",
+            Tag::K8sNodeIsNotReadyWithTheRequestedVersion => "Represents an error where the given node is not ready in the given version.",
+            Tag::K8sNodeIsNotReady => "Represents an error where the given node is not ready.",
+            Tag::K8sValidateRequiredCPUandBurstableError => "Represents an error validating required CPU and burstable.",
+            Tag::K8sErrorCopySecret => "Represents an error while copying secret from one namespace to another.",
+            Tag::CannotFindRequiredBinary => "Represents an error where a required binary is not found on the system.",
+            Tag::SubnetsCountShouldBeEven => "Represents an error where subnets count should be even to have as many public than private subnets.",
+            Tag::InvalidCIDRBlock => "Represents an error where a CIDR block is malformed or cannot be split into the requested subnets.",
+            Tag::CannotGetOrCreateIamRole => "Represents an error where we cannot get or create the given IAM role.",
+            Tag::CannotCopyFilesFromDirectoryToDirectory => "Represents an error where we cannot copy files from one directory to another.",
+            Tag::CannotPauseClusterTasksAreRunning => "Represents an error where we cannot pause the cluster because some tasks are still running in the engine.",
+            Tag::TerraformUnknownError => "Terraform unknown error.",
+            Tag::TerraformInvalidCredentials => "Terraform invalid cloud provider credentials.",
+            Tag::TerraformAccountBlockedByProvider => "Terraform cannot perform action because account has been blocked by cloud provider.",
+            Tag::TerraformMultipleInterruptsReceived => "Terraform received multiple interrupts.",
+            Tag::TerraformNotEnoughPermissions => "Terraform issue due to user not having enough permissions to perform action on the resource.",
+            Tag::TerraformWrongState => "Terraform issue due to wrong state of the resource.",
+            Tag::TerraformResourceDependencyViolation => "Terraform issue due to resource dependency violation.",
+            Tag::TerraformInstanceTypeDoesntExist => "Terraform issue due to instance type doesn't exist in the current region.",
+            Tag::TerraformInstanceVolumeCannotBeReduced => "Terraform issue due to instance volume cannot be downsized.",
+            Tag::TerraformConfigFileNotFound => "Terraform config file cannot be found.",
+            Tag::TerraformConfigFileInvalidContent => "Terraform config file has invalid content.",
+            Tag::TerraformCannotDeleteLockFile => "Terraform cannot delete Lock file.",
+            Tag::TerraformInitError => "Terraform error while applying init command.",
+            Tag::TerraformValidateError => "Terraform error while applying validate command.",
+            Tag::TerraformPlanError => "Terraform error while applying plan command.",
+            Tag::TerraformApplyError => "Terraform error while applying apply command.",
+            Tag::TerraformDestroyError => "Terraform error while applying apply destroy command.",
+            Tag::TerraformCannotRemoveEntryOut => "Represents an error where we cannot remove an entry out of Terraform.",
+            Tag::TerraformErrorWhileExecutingPipeline => "Represents an error while executing Terraform pipeline.",
+            Tag::TerraformErrorWhileExecutingDestroyPipeline => "Represents an error while executing Terraform destroying pipeline.",
+            Tag::TerraformContextUnsupportedParameterValue => "Represents an error while trying to render terraform context because of unsupported parameter value.",
+            Tag::TerraformCloudProviderQuotasReached => "Represents an error due to cloud provider quotas exceeded.",
+            Tag::TerraformCloudProviderActivationRequired => "Represents an error due to cloud provider requiring account to be validated first.",
+            Tag::TerraformServiceNotActivatedOptInRequired => "Represents an error due to service not being activated on cloud account.",
+            Tag::TerraformWaitingTimeoutResource => "Represents an error due to resource being in flaky state in tf state.",
+            Tag::TerraformAlreadyExistingResource => "Represents an error due to resource already present in tf state while trying to create it.",
+            Tag::TerraformInvalidCIDRBlock => "Represents an error due to an unusable CIDR block already used in the target VPC.",
+            Tag::TerraformStateLocked => "Represents an error due to Terraform state lock.",
+            Tag::TerraformSubnetAddressPoolExhausted => {
+                "Represents an error due to a subnet not having enough free IP addresses."
+            }
+            Tag::TerraformDiagnosticError => "Represents an error reported by a Terraform diagnostic not matching any known issue.",
+            Tag::HelmChartsSetupError => "Represents an error while trying to setup helm charts.",
+            Tag::HelmChartsDeployError => "Represents an error while trying to deploy helm charts.",
+            Tag::HelmChartsUpgradeError => "Represents an error while trying to upgrade helm charts.",
+            Tag::HelmChartUninstallError => "Represents an error while trying to uninstall an helm chart.",
+            Tag::HelmHistoryError => "Represents an error while trying to execute helm history on a helm chart.",
+            Tag::HelmDeployTimeout => "Represent a failure to run the helm command in the given time frame.",
+            Tag::CannotGetAnyAvailableVPC => "Represents an error while trying to get any available VPC.",
+            Tag::UnsupportedVersion => "Represents an error where product doesn't support the given version.",
+            Tag::CannotGetSupportedVersions => "Represents an error while trying to get supported versions.",
+            Tag::CannotGetCluster => "Represents an error where we cannot get cluster.",
+            Tag::OnlyOneClusterExpected => "Represents an error where only one cluster was expected but several where found.",
+            Tag::ClientServiceFailedToStart => "Represent an error while trying to start a client's service.",
+            Tag::K8sImagePullError => "Represents an error where a client's service cannot start because its image cannot be pulled.",
+            Tag::ClientServiceFailedToDeployBeforeStart => "Represents an error while trying to deploy a client's service before start.",
+            Tag::DatabaseFailedToStartAfterSeveralRetries => "Represents an error while trying to start a database after several retries.",
+            Tag::RouterFailedToDeploy => "Represents an error while trying to deploy a router.",
+            Tag::CloudProviderInformationError => "Represents an error when checking cloud provider information provided.",
+            Tag::CloudProviderClientInvalidCredentials => "Represents an error where client credentials for a cloud providers appear to be invalid.",
+            Tag::CloudProviderApiMissingInfo => "Represents an error while expecting mandatory info.",
+            Tag::VersionNumberParsingError => "Represents an error where the version number is not valid.",
+            Tag::NotImplementedError => "Represents an error where feature / code has not been implemented yet.",
+            Tag::TaskCancellationRequested => "Represents an error where current task cancellation has been requested.",
+            Tag::BuilderError => "Represents an error when trying to build an application.",
+            Tag::BuilderDockerCannotFindAnyDockerfile => "Represents an error when trying to get a Dockerfile.",
+            Tag::BuilderDockerCannotReadDockerfile => "Represents an error while trying to read Dockerfile.",
+            Tag::BuilderDockerCannotExtractEnvVarsFromDockerfile => "Represents an error while trying to extract ENV vars from Dockerfile.",
+            Tag::BuilderDockerCannotBuildContainerImage => "Represents an error while trying to build Docker container image.",
+            Tag::BuilderDockerCannotListImages => "Represents an error while trying to list docker images.",
+            Tag::BuilderBuildpackInvalidLanguageFormat => "Represents an error where buildback requested language has wrong format.",
+            Tag::BuilderBuildpackCannotBuildContainerImage => "Represents an error while trying to build container image with Buildpack.",
+            Tag::BuilderGetBuildError => "Represents an error when builder is trying to get parent build.",
+            Tag::BuilderCloningRepositoryError => "Represents an error when builder is trying to clone a git repository.",
+            Tag::DockerError => "Represents an error when trying to use docker cli.",
+            Tag::DockerPushImageError => "Represents an error when trying to push a docker image.",
+            Tag::DockerPullImageError => "Represents an error when trying to pull a docker image.",
+            Tag::DockerPullImageRateLimited => {
+                "Represents an error when the registry rejects a docker image pull due to its rate limit."
+            }
+            Tag::ContainerRegistryCannotCreateRepository => "Represents an error when trying to create a repository.",
+            Tag::ContainerRegistryCannotSetRepositoryLifecycle => "Represents an error when trying to set repository lifecycle policy.",
+            Tag::ContainerRegistryCannotGetCredentials => "Represents an error when trying to get container registry credentials.",
+            Tag::ContainerRegistryCannotDeleteImage => "Represents an error while trying to delete an image.",
+            Tag::ContainerRegistryImageDoesntExist => "Represents an error, image doesn't exist in the registry.",
+            Tag::ContainerRegistryImageUnreachableAfterPush => "Represents an error when image has been pushed but is unreachable.",
+            Tag::ContainerRegistryRepositoryDoesntExistInRegistry => "Represents an error, repository doesn't exist in registry.",
+            Tag::ContainerRegistryRegistryDoesntExist => "Represents an error, registry doesn't exist.",
+            Tag::ContainerRegistryCannotDeleteRepository => "Represents an error while trying to delete a repository.",
+            Tag::ContainerRegistryInvalidInformation => "Represents an error on container registry information provided.",
+            Tag::ContainerRegistryInvalidCredentials => "Represents an error on container registry, credentials are not valid.",
+            Tag::ContainerRegistryCannotLinkRegistryToCluster => "Represents an error on container registry where it cannot be linked to cluster.",
+            Tag::ContainerRegistryCannotCreateRegistry => "Represents an error on container registry where it cannot create a registry.",
+            Tag::ContainerRegistryCannotDeleteRegistry => "Represents an error on container registry where it cannot delete a registry.",
+            Tag::ContainerRegistryCannotSetRepositoryTags => "Represents an error on container registry where it cannot cannot set tags.",
+            Tag::ContainerRegistryUnknownError => "Represents an unknown error on container registry.",
+            Tag::KubeconfigFileDoNotPermitToConnectToK8sCluster => "Represent a kubeconfig mismatch, not permitting to connect to k8s cluster.",
+            Tag::KubeconfigSecurityCheckError => "Represent an error because of a security concern/doubt on the kubeconfig file.",
+            Tag::DeleteLocalKubeconfigFileError => "Represent an error when trying to delete Kubeconfig.",
+            Tag::VaultConnectionError => "Represents an error while trying to connect ot Vault service.",
+            Tag::VaultSecretCouldNotBeRetrieved => "Represents an error to get the desired secret.",
+            Tag::VaultSecretCouldNotBeCreatedOrUpdated => "Represent a vault secret creation or update error.",
+            Tag::VaultSecretCouldNotBeDeleted => "VaultSecretCouldNotBeDeleted, represent a vault secret deletion error.",
+            Tag::JsonDeserializationError => "Represent a deserialization issue.",
+            Tag::ClusterSecretsManipulationError => "Represent an error while trying to manipulate ClusterSecrets.",
+            Tag::DnsProviderInformationError => "Represent an error on DNS provider information provided.",
+            Tag::DnsProviderInvalidCredentials => "Represent an error on invalid DNS provider credentials.",
+            Tag::DnsProviderInvalidApiUrl => "Represent an error on invalid DNS provider api url.",
+            Tag::ObjectStorageCannotCreateBucket => "Represents an error while trying to create a new object storage bucket.",
+            Tag::ObjectStorageCannotPutFileIntoBucket => "Represents an error while trying to put a file into an object storage bucket.",
+            Tag::ObjectStorageCannotDeleteFileIntoBucket => "Represents an error while trying to delete a file into an object storage bucket.",
+            Tag::ObjectStorageCannotDeleteBucket => "Represents an error while trying to delete a bucket.",
+            Tag::ObjectStorageCannotActivateBucketVersioning => "Represents an error while trying to activate bucket versioning for bucket.",
+            Tag::ObjectStorageQuotaExceeded => "Represents an error, quotas has been exceeded.",
+            Tag::ObjectStorageInvalidBucketName => "Represents an error, bucket name is not valid.",
+            Tag::ObjectStorageCannotEmptyBucket => "Represents an error while trying to empty an object storage bucket.",
+            Tag::ObjectStorageCannotTagBucket => "Represents an error while trying to tag an object storage bucket.",
+            Tag::ObjectStorageCannotGetObjectFile => "Represents an error while trying to get a file from object storage bucket.",
+            Tag::JobFailure => "Represents an error while indicating that the job failed to terminate properly.",
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -847,6 +1784,102 @@ pub struct EngineError {
     link: Option<Url>,
     /// hint_message: an hint message aiming to give an hint to the user. For example: "Happens when application port has been changed but application hasn't been restarted.".
     hint_message: Option<String>,
+    /// hint_key: stable translation key of the hint message, None for free text hints.
+    hint_key: Option<&'static str>,
+}
+
+/// Hint: message aiming to give an hint to the user along with its stable translation key, see `HINT_KEYS`.
+/// The message is the English fallback of the key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hint {
+    key: &'static str,
+    message: String,
+}
+
+impl Hint {
+    pub fn new<S: Into<String>>(key: &'static str, message: S) -> Self {
+        debug_assert!(HINT_KEYS.contains(&key), "hint key `{}` is not registered in HINT_KEYS", key);
+        Hint {
+            key,
+            message: message.into(),
+        }
+    }
+}
+
+/// EngineErrorBuilder: fluent alternative to the positional `EngineError::new`.
+///
+/// Fields left unset default to no underlying error, no link and no hint, the tag defaults to `Tag::Unknown`.
+#[derive(Clone, Debug)]
+pub struct EngineErrorBuilder {
+    tag: Tag,
+    user_log_message: String,
+    underlying_error: Option<CommandError>,
+    link: Option<Url>,
+    hint: Option<Hint>,
+}
+
+impl Default for EngineErrorBuilder {
+    fn default() -> Self {
+        EngineErrorBuilder {
+            tag: Tag::Unknown,
+            user_log_message: String::new(),
+            underlying_error: None,
+            link: None,
+            hint: None,
+        }
+    }
+}
+
+impl EngineErrorBuilder {
+    pub fn new() -> Self {
+        EngineErrorBuilder::default()
+    }
+
+    /// Sets error's unique identifier.
+    pub fn tag(mut self, tag: Tag) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Sets error log message targeting Qovery user.
+    pub fn user_message<S: Into<String>>(mut self, user_log_message: S) -> Self {
+        self.user_log_message = user_log_message.into();
+        self
+    }
+
+    /// Sets raw error such as command input / output.
+    pub fn underlying(mut self, underlying_error: CommandError) -> Self {
+        self.underlying_error = Some(underlying_error);
+        self
+    }
+
+    /// Sets link documenting the error.
+    pub fn link(mut self, link: Url) -> Self {
+        self.link = Some(link);
+        self
+    }
+
+    /// Sets hint message aiming to give an hint to the user, along with its translation key (see `HINT_KEYS`).
+    pub fn hint<S: Into<String>>(mut self, key: &'static str, hint_message: S) -> Self {
+        self.hint = Some(Hint::new(key, hint_message));
+        self
+    }
+
+    /// Creates the EngineError, moving event details to cancel or error stage depending on the tag.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    pub fn build(self, event_details: EventDetails) -> EngineError {
+        EngineError::new(
+            event_details,
+            self.tag,
+            self.user_log_message,
+            self.underlying_error,
+            self.link,
+            self.hint,
+        )
+    }
 }
 
 impl EngineError {
@@ -855,6 +1888,191 @@ impl EngineError {
         &self.tag
     }
 
+    /// Suggested HTTP status for APIs returning this error, derived from its tag.
+    /// The match is exhaustive on purpose: new tags have to be mapped explicitly.
+    pub fn suggested_http_status(&self) -> u16 {
+        match self.tag {
+            // invalid input or configuration
+            Tag::InvalidEnginePayload
+            | Tag::InvalidEngineApiInputCannotBeDeserialized
+            | Tag::MissingRequiredEnvVariable
+            | Tag::UnsupportedInstanceType
+            | Tag::NotAllowedInstanceType
+            | Tag::UnsupportedClusterKind
+            | Tag::UnsupportedRegion
+            | Tag::UnsupportedZone
+            | Tag::UnsupportedDatabaseDiskType
+            | Tag::NumberOfRequestedMaxNodesIsBelowThanCurrentUsage
+            | Tag::DoNotRespectCloudProviderBestPractices
+            | Tag::K8sValidateRequiredCPUandBurstableError
+            | Tag::SubnetsCountShouldBeEven
+            | Tag::InvalidCIDRBlock
+            | Tag::TerraformInstanceTypeDoesntExist
+            | Tag::TerraformInstanceVolumeCannotBeReduced
+            | Tag::TerraformContextUnsupportedParameterValue
+            | Tag::TerraformInvalidCIDRBlock
+            | Tag::UnsupportedVersion
+            | Tag::VersionNumberParsingError
+            | Tag::BuilderDockerCannotFindAnyDockerfile
+            | Tag::BuilderDockerCannotReadDockerfile
+            | Tag::BuilderDockerCannotExtractEnvVarsFromDockerfile
+            | Tag::BuilderBuildpackInvalidLanguageFormat
+            | Tag::ContainerRegistryInvalidInformation
+            | Tag::DnsProviderInvalidApiUrl
+            | Tag::ObjectStorageInvalidBucketName => 400,
+            // credentials or permissions
+            Tag::TerraformInvalidCredentials
+            | Tag::TerraformAccountBlockedByProvider
+            | Tag::TerraformNotEnoughPermissions
+            | Tag::TerraformCloudProviderActivationRequired
+            | Tag::TerraformServiceNotActivatedOptInRequired
+            | Tag::CloudProviderClientInvalidCredentials
+            | Tag::ContainerRegistryInvalidCredentials
+            | Tag::KubeconfigFileDoNotPermitToConnectToK8sCluster
+            | Tag::DnsProviderInvalidCredentials => 403,
+            // resource not found
+            Tag::NoClusterFound
+            | Tag::ClusterWorkerNodeNotFound
+            | Tag::ContainerRegistryImageDoesntExist
+            | Tag::ContainerRegistryRepositoryDoesntExistInRegistry
+            | Tag::ContainerRegistryRegistryDoesntExist => 404,
+            // conflict with the current state of the resource
+            Tag::K8sUpgradeDeployedVsRequestedVersionsInconsistency
+            | Tag::CannotPauseClusterTasksAreRunning
+            | Tag::TerraformResourceDependencyViolation
+            | Tag::TerraformAlreadyExistingResource
+            | Tag::TerraformStateLocked
+            | Tag::TerraformSubnetAddressPoolExhausted
+            | Tag::OnlyOneClusterExpected
+            | Tag::TaskCancellationRequested => 409,
+            // user's code or workload failed
+            Tag::NotEnoughNodesAvailableToDeployEnvironment
+            | Tag::NotEnoughResourcesToDeployEnvironment
+            | Tag::ClientServiceFailedToStart
+            | Tag::K8sImagePullError
+            | Tag::ClientServiceFailedToDeployBeforeStart
+            | Tag::BuilderDockerCannotBuildContainerImage
+            | Tag::BuilderBuildpackCannotBuildContainerImage
+            | Tag::BuilderCloningRepositoryError
+            | Tag::JobFailure => 422,
+            // quotas
+            Tag::TerraformCloudProviderQuotasReached
+            | Tag::ObjectStorageQuotaExceeded
+            | Tag::DockerPullImageRateLimited => 429,
+            Tag::NotImplementedError => 501,
+            // upstream provider or service error
+            Tag::CloudProviderGetLoadBalancer
+            | Tag::CannotTriggerDatabaseBackup
+            | Tag::CloudProviderGetLoadBalancerTags
+            | Tag::CloudProviderDeleteLoadBalancer
+            | Tag::CannotGetAnyAvailableVPC
+            | Tag::CannotGetSupportedVersions
+            | Tag::CloudProviderInformationError
+            | Tag::CloudProviderApiMissingInfo
+            | Tag::DockerPushImageError
+            | Tag::DockerPullImageError
+            | Tag::ContainerRegistryCannotCreateRepository
+            | Tag::ContainerRegistryCannotSetRepositoryLifecycle
+            | Tag::ContainerRegistryCannotGetCredentials
+            | Tag::ContainerRegistryCannotDeleteImage
+            | Tag::ContainerRegistryCannotDeleteRepository
+            | Tag::ContainerRegistryCannotLinkRegistryToCluster
+            | Tag::ContainerRegistryCannotCreateRegistry
+            | Tag::ContainerRegistryCannotDeleteRegistry
+            | Tag::ContainerRegistryCannotSetRepositoryTags
+            | Tag::ContainerRegistryUnknownError
+            | Tag::VaultSecretCouldNotBeRetrieved
+            | Tag::VaultSecretCouldNotBeCreatedOrUpdated
+            | Tag::VaultSecretCouldNotBeDeleted
+            | Tag::DnsProviderInformationError
+            | Tag::ObjectStorageCannotCreateBucket
+            | Tag::ObjectStorageCannotPutFileIntoBucket
+            | Tag::ObjectStorageCannotDeleteFileIntoBucket
+            | Tag::ObjectStorageCannotDeleteBucket
+            | Tag::ObjectStorageCannotActivateBucketVersioning
+            | Tag::ObjectStorageCannotEmptyBucket
+            | Tag::ObjectStorageCannotTagBucket
+            | Tag::ObjectStorageCannotGetObjectFile => 502,
+            // cluster or service unreachable
+            Tag::CannotConnectK8sCluster | Tag::K8sCannotReachToApi | Tag::VaultConnectionError => 503,
+            // upstream timeout
+            Tag::TerraformWaitingTimeoutResource | Tag::HelmDeployTimeout => 504,
+            // engine internal errors
+            Tag::Unknown
+            | Tag::ClusterHasNoWorkerNodes
+            | Tag::CannotGetWorkspaceDirectory
+            | Tag::CannotRetrieveClusterConfigFile
+            | Tag::CannotCreateFile
+            | Tag::CannotGetClusterNodes
+            | Tag::CannotUninstallHelmChart
+            | Tag::CannotExecuteK8sVersion
+            | Tag::CannotDetermineK8sMasterVersion
+            | Tag::CannotDetermineK8sRequestedUpgradeVersion
+            | Tag::CannotDetermineK8sKubeletWorkerVersion
+            | Tag::CannotGetNodeGroupList
+            | Tag::CannotGetNodeGroupInfo
+            | Tag::CannotDetermineK8sKubeProxyVersion
+            | Tag::CannotPauseManagedDatabase
+            | Tag::CannotExecuteK8sApiCustomMetrics
+            | Tag::K8sPodDisruptionBudgetInInvalidState
+            | Tag::K8sPodsDisruptionBudgetCannotBeRetrieved
+            | Tag::K8sCannotDeletePod
+            | Tag::K8sCannotDeletePvc
+            | Tag::K8sCannotGetCrashLoopingPods
+            | Tag::K8sCannotDeleteCompletedJobs
+            | Tag::K8sCannotGetPods
+            | Tag::K8sCannotCordonNode
+            | Tag::K8sScaleReplicas
+            | Tag::K8sCannotRolloutRestart
+            | Tag::K8sLoadBalancerConfigurationIssue
+            | Tag::K8sServiceError
+            | Tag::K8sGetLogs
+            | Tag::K8sGetEvents
+            | Tag::K8sDescribe
+            | Tag::K8sHistory
+            | Tag::K8sCannotCreateNamespace
+            | Tag::K8sPodIsNotReady
+            | Tag::K8sNodeIsNotReadyWithTheRequestedVersion
+            | Tag::K8sNodeIsNotReady
+            | Tag::K8sErrorCopySecret
+            | Tag::CannotFindRequiredBinary
+            | Tag::CannotGetOrCreateIamRole
+            | Tag::CannotCopyFilesFromDirectoryToDirectory
+            | Tag::TerraformUnknownError
+            | Tag::TerraformMultipleInterruptsReceived
+            | Tag::TerraformWrongState
+            | Tag::TerraformConfigFileNotFound
+            | Tag::TerraformConfigFileInvalidContent
+            | Tag::TerraformCannotDeleteLockFile
+            | Tag::TerraformInitError
+            | Tag::TerraformValidateError
+            | Tag::TerraformPlanError
+            | Tag::TerraformApplyError
+            | Tag::TerraformDestroyError
+            | Tag::TerraformCannotRemoveEntryOut
+            | Tag::TerraformErrorWhileExecutingPipeline
+            | Tag::TerraformErrorWhileExecutingDestroyPipeline
+            | Tag::TerraformDiagnosticError
+            | Tag::HelmChartsSetupError
+            | Tag::HelmChartsDeployError
+            | Tag::HelmChartsUpgradeError
+            | Tag::HelmChartUninstallError
+            | Tag::HelmHistoryError
+            | Tag::CannotGetCluster
+            | Tag::DatabaseFailedToStartAfterSeveralRetries
+            | Tag::RouterFailedToDeploy
+            | Tag::BuilderError
+            | Tag::BuilderDockerCannotListImages
+            | Tag::BuilderGetBuildError
+            | Tag::DockerError
+            | Tag::ContainerRegistryImageUnreachableAfterPush
+            | Tag::KubeconfigSecurityCheckError
+            | Tag::DeleteLocalKubeconfigFileError
+            | Tag::JsonDeserializationError
+            | Tag::ClusterSecretsManipulationError => 500,
+        }
+    }
+
     /// Returns error's event details.
     pub fn event_details(&self) -> &EventDetails {
         &self.event_details
@@ -867,9 +2085,21 @@ impl EngineError {
 
     /// Returns proper error message.
     pub fn message(&self, message_verbosity: ErrorMessageVerbosity) -> String {
-        match &self.underlying_error {
-            Some(msg) => msg.message(message_verbosity),
-            None => self.user_log_message.to_string(),
+        let with_error_code = matches!(message_verbosity, ErrorMessageVerbosity::FullDetails);
+        let message = match (&self.underlying_error, &message_verbosity) {
+            (Some(msg), _) => msg.message(message_verbosity),
+            (None, ErrorMessageVerbosity::Structured) => CommandErrorParts {
+                safe: self.user_log_message.to_string(),
+                full: None,
+                env_vars: None,
+            }
+            .to_json(),
+            (None, _) => self.user_log_message.to_string(),
+        };
+
+        match with_error_code {
+            true => format!("{} (error code: {})", message, self.tag.error_code()),
+            false => message,
         }
     }
 
@@ -888,6 +2118,12 @@ impl EngineError {
         &self.hint_message
     }
 
+    /// Returns the translation key of error's hint, see `HINT_KEYS`, the hint message being the English fallback.
+    /// None if the error has no hint or if its hint is a free text.
+    pub fn hint_key(&self) -> Option<&'static str> {
+        self.hint_key
+    }
+
     /// Creates new EngineError.
     ///
     /// Arguments:
@@ -897,14 +2133,14 @@ impl EngineError {
     /// * `user_log_message`: Error log message targeting Qovery user, avoiding any extending pointless details.
     /// * `underlying_error`: raw error message such as command input / output.
     /// * `link`: Link documenting the given error.
-    /// * `hint_message`: hint message aiming to give an hint to the user. For example: "Happens when application port has been changed but application hasn't been restarted.".
+    /// * `hint`: hint aiming to give an hint to the user and its translation key. For example: "Happens when application port has been changed but application hasn't been restarted.".
     fn new(
         mut event_details: EventDetails,
         tag: Tag,
         user_log_message: String,
         underlying_error: Option<CommandError>,
         link: Option<Url>,
-        hint_message: Option<String>,
+        hint: Option<Hint>,
     ) -> Self {
         if tag.is_cancel() {
             event_details.mut_to_cancel_stage()
@@ -912,6 +2148,11 @@ impl EngineError {
             event_details.mut_to_error_stage()
         }
 
+        let (hint_message, hint_key) = match hint {
+            Some(hint) => (Some(hint.message), Some(hint.key)),
+            None => (None, None),
+        };
+
         EngineError {
             event_details,
             tag,
@@ -919,6 +2160,7 @@ impl EngineError {
             underlying_error,
             link,
             hint_message,
+            hint_key,
         }
     }
     /// Clone an existing engine error to specify a stage
@@ -941,6 +2183,28 @@ impl EngineError {
             underlying_error: self.underlying_error.as_ref().cloned(),
             link: self.link.as_ref().cloned(),
             hint_message: self.hint_message.as_ref().cloned(),
+            hint_key: self.hint_key,
+        }
+    }
+
+    /// Clone an existing engine error to re-classify it with another tag, i.e: when a higher layer has a better context.
+    /// Event stage is moved to cancel or error stage according to the new tag.
+    ///
+    /// Arguments:
+    ///
+    /// * `tag`: tag that replaces the current tag of the engine error
+    pub fn clone_with_tag(&self, tag: Tag) -> Self {
+        EngineError {
+            hint_message: self.hint_message.as_ref().cloned(),
+            hint_key: self.hint_key,
+            ..EngineError::new(
+                self.event_details.clone(),
+                tag,
+                self.user_log_message.clone(),
+                self.underlying_error.as_ref().cloned(),
+                self.link.as_ref().cloned(),
+                None,
+            )
         }
     }
 
@@ -962,14 +2226,11 @@ impl EngineError {
         link: Option<Url>,
         hint_message: Option<String>,
     ) -> EngineError {
-        EngineError::new(
-            event_details,
-            Tag::Unknown,
-            user_log_message,
-            underlying_error,
-            link,
+        // free text hint, it has no translation key
+        EngineError {
             hint_message,
-        )
+            ..EngineError::new(event_details, Tag::Unknown, user_log_message, underlying_error, link, None)
+        }
     }
 
     /// Creates new from an engine error. Only change the use log message and hint
@@ -985,6 +2246,10 @@ impl EngineError {
         hint_message: Option<String>,
     ) -> EngineError {
         engine_error.user_log_message = user_log_message;
+        // a replaced hint is a free text, it has no translation key anymore
+        if engine_error.hint_message != hint_message {
+            engine_error.hint_key = None;
+        }
         engine_error.hint_message = hint_message;
         engine_error.underlying_error = None;
         engine_error.link = None;
@@ -1010,7 +2275,10 @@ impl EngineError {
             "Input is invalid and cannot be deserialized.".to_string(),
             Some(raw_error.into()),
             None,
-            Some("This is a Qovery issue, please contact our support team".to_string()),
+            Some(Hint::new(
+                "hint.invalid_engine_api_input_cannot_be_deserialized",
+                "This is a Qovery issue, please contact our support team".to_string(),
+            )),
         )
     }
 
@@ -1023,18 +2291,21 @@ impl EngineError {
     /// * `event_details`: Error linked event details.
     /// * `message`: Raw error message.
     pub fn new_invalid_engine_payload(event_details: EventDetails, message: &str) -> EngineError {
-        EngineError::new(
-            event_details,
-            Tag::InvalidEnginePayload,
-            format!("Input is invalid and cannot be executed by the engine: {}", message),
-            None,
-            None,
-            Some("This is a Qovery issue, please contact our support team".to_string()),
-        )
+        EngineErrorBuilder::new()
+            .tag(Tag::InvalidEnginePayload)
+            .user_message(format!("Input is invalid and cannot be executed by the engine: {}", message))
+            .hint(
+                "hint.invalid_engine_payload",
+                "This is a Qovery issue, please contact our support team",
+            )
+            .build(event_details)
     }
 
     pub fn new_job_error(event_details: EventDetails, message: String) -> EngineError {
-        EngineError::new(event_details, Tag::JobFailure, message, None, None, None)
+        EngineErrorBuilder::new()
+            .tag(Tag::JobFailure)
+            .user_message(message)
+            .build(event_details)
     }
 
     /// Creates new error for missing required env variable.
@@ -1046,8 +2317,10 @@ impl EngineError {
     /// * `event_details`: Error linked event details.
     /// * `variable_name`: Variable name which is not set.
     pub fn new_missing_required_env_variable(event_details: EventDetails, variable_name: String) -> EngineError {
-        let message = format!("`{}` environment variable wasn't found.", variable_name);
-        EngineError::new(event_details, Tag::MissingRequiredEnvVariable, message, None, None, None)
+        EngineErrorBuilder::new()
+            .tag(Tag::MissingRequiredEnvVariable)
+            .user_message(format!("`{}` environment variable wasn't found.", variable_name))
+            .build(event_details)
     }
 
     /// Creates new error for cluster has no worker nodes.
@@ -1069,10 +2342,11 @@ impl EngineError {
             message.to_string(),
             raw_error,
             None,
-            Some(
+            Some(Hint::new(
+                "hint.cluster_has_no_worker_nodes",
                 "This can happen if there where a manual operations on the workers or the infrastructure is paused."
                     .to_string(),
-            ),
+            )),
         )
     }
 
@@ -1117,10 +2391,11 @@ impl EngineError {
             message.to_string(),
             raw_error,
             None,
-            Some(
+            Some(Hint::new(
+                "hint.cluster_worker_node_not_found",
                 "This can happen if there where a manual operations on the workers or the infrastructure is paused."
                     .to_string(),
-            ),
+            )),
         )
     }
 
@@ -1141,9 +2416,10 @@ impl EngineError {
             message.to_string(),
             raw_error,
             None,
-            Some(
+            Some(Hint::new(
+                "hint.cloud_provider_api_missing_info",
                 "This can happen if the cloud provider is encountering issues. You should try again later".to_string(),
-            ),
+            )),
         )
     }
 
@@ -1166,7 +2442,10 @@ impl EngineError {
             message,
             None,
             None, // TODO(documentation): Create a page entry to details this error
-            Some("Selected instance type is not allowed, please check Qovery's documentation.".to_string()),
+            Some(Hint::new(
+                "hint.not_allowed_instance_type",
+                "Selected instance type is not allowed, please check Qovery's documentation.".to_string(),
+            )),
         )
     }
 
@@ -1191,7 +2470,10 @@ impl EngineError {
             message,
             Some(error_message),
             None, // TODO(documentation): Create a page entry to details this error
-            Some("Selected instance type is not supported, please check provider's documentation.".to_string()),
+            Some(Hint::new(
+                "hint.unsupported_instance_type",
+                "Selected instance type is not supported, please check provider's documentation.".to_string(),
+            )),
         )
     }
 
@@ -1216,7 +2498,10 @@ impl EngineError {
             message,
             Some(error_message),
             None, // TODO(documentation): Create a page entry to details this error
-            Some("Selected cluster kind is not supported, please check Qovery's documentation.".to_string()),
+            Some(Hint::new(
+                "hint.unsupported_cluster_kind",
+                "Selected cluster kind is not supported, please check Qovery's documentation.".to_string(),
+            )),
         )
     }
 
@@ -1241,7 +2526,10 @@ impl EngineError {
             message,
             Some(error_message),
             None, // TODO(documentation): Create a page entry to details this error
-            Some("Selected region is not supported, please check provider's documentation.".to_string()),
+            Some(Hint::new(
+                "hint.unsupported_region",
+                "Selected region is not supported, please check provider's documentation.".to_string(),
+            )),
         )
     }
 
@@ -1268,7 +2556,38 @@ impl EngineError {
             message,
             Some(error_message),
             None, // TODO(documentation): Create a page entry to details this error
-            Some("Selected zone is not supported in the region, please check provider's documentation.".to_string()),
+            Some(Hint::new(
+                "hint.unsupported_zone",
+                "Selected zone is not supported in the region, please check provider's documentation.".to_string(),
+            )),
+        )
+    }
+
+    /// Creates new error for unsupported database disk type.
+    ///
+    /// Cloud provider doesn't support the requested disk type, or not for the requested instance type.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `requested_disk_type`: Raw requested disk type string.
+    /// * `error_message`: Raw error message.
+    pub fn new_unsupported_database_disk_type(
+        event_details: EventDetails,
+        requested_disk_type: &str,
+        error_message: CommandError,
+    ) -> EngineError {
+        let message = format!("`{}` database disk type is not supported", requested_disk_type);
+        EngineError::new(
+            event_details,
+            Tag::UnsupportedDatabaseDiskType,
+            message,
+            Some(error_message),
+            None, // TODO(documentation): Create a page entry to details this error
+            Some(Hint::new(
+                "hint.unsupported_database_disk_type",
+                "Selected database disk type is not supported, please check provider's documentation.".to_string(),
+            )),
         )
     }
 
@@ -1372,7 +2691,10 @@ impl EngineError {
             message,
             None,
             None,
-            Some("Consider to upgrade your nodes configuration.".to_string()),
+            Some(Hint::new(
+                "hint.not_enough_nodes_available_to_deploy_environment",
+                "Consider to upgrade your nodes configuration.".to_string(),
+            )),
         )
     }
 
@@ -1397,7 +2719,10 @@ impl EngineError {
             ),
             None,
             None,
-            Some("Reduce your resources usage or set it to a higher value".to_string()),
+            Some(Hint::new(
+                "hint.number_of_requested_max_nodes_is_below_than_current_usage",
+                "Reduce your resources usage or set it to a higher value".to_string(),
+            )),
         )
     }
 
@@ -1438,7 +2763,7 @@ impl EngineError {
             message,
             None,
             None,
-            Some("Consider to add one more node or upgrade your nodes configuration. If not possible, pause or delete unused environments.".to_string()),
+            Some(Hint::new("hint.not_enough_resources_to_deploy_environment", "Consider to add one more node or upgrade your nodes configuration. If not possible, pause or delete unused environments.".to_string())),
         )
     }
 
@@ -1465,70 +2790,134 @@ impl EngineError {
             message,
             None,
             None,
-            Some("Consider to add one more node or upgrade your nodes configuration. If not possible, pause or delete unused environments.".to_string()),
+            Some(Hint::new("hint.not_enough_resources_to_deploy_environment", "Consider to add one more node or upgrade your nodes configuration. If not possible, pause or delete unused environments.".to_string())),
         )
     }
 
-    /// Creates new error for cannot uninstall an helm chart.
+    /// Creates new error for cannot deploy an environment because its services request more pods than free on the cluster.
     ///
     /// Arguments:
     ///
     /// * `event_details`: Error linked event details.
-    /// * `helm_chart_name`: Helm chart name.
-    /// * `errored_object_kind`: Errored kubernetes object name.
-    /// * `raw_error`: Raw error message.
-    pub fn new_cannot_uninstall_helm_chart(
+    /// * `free_pods`: How many pods are free.
+    /// * `requested_pods`: Services (name, pods) adding pods to the cluster.
+    pub fn new_cannot_deploy_environment_exceeding_free_pods(
         event_details: EventDetails,
-        helm_chart_name: String,
-        errored_object_kind: String,
-        raw_error: CommandError,
+        free_pods: u32,
+        requested_pods: &[(String, u32)],
     ) -> EngineError {
         let message = format!(
-            "Wasn't able to delete all objects type {}, it's a blocker to then delete cert-manager namespace. {}",
-            helm_chart_name, errored_object_kind,
+            "There is not enough free Pods (free {} VS {} required) on the cluster to deploy the environment. Requested pods: {}.",
+            free_pods,
+            requested_pods.iter().map(|(_, pods)| pods).sum::<u32>(),
+            requested_pods
+                .iter()
+                .map(|(name, pods)| format!("`{}` ({} pod(s))", name, pods))
+                .collect::<Vec<String>>()
+                .join(", "),
         );
 
         EngineError::new(
             event_details,
-            Tag::CannotUninstallHelmChart,
+            Tag::NotEnoughResourcesToDeployEnvironment,
             message,
-            Some(raw_error),
             None,
             None,
+            Some(Hint::new("hint.not_enough_resources_to_deploy_environment", "Consider to add one more node or upgrade your nodes configuration. If not possible, pause or delete unused environments.".to_string())),
         )
     }
 
-    /// Creates new error for cannot exec K8s version.
+    /// Creates new error for cannot deploy because the environment namespace pods quota would be exceeded.
     ///
     /// Arguments:
     ///
     /// * `event_details`: Error linked event details.
-    /// * `error_message`: Raw error message.
-    pub fn new_cannot_execute_k8s_exec_version(
+    /// * `quota_name`: Name of the namespace resource quota.
+    /// * `requested_pods`: How many pods are requested on top of the used ones.
+    /// * `used_pods`: How many pods are already counted by the quota.
+    /// * `max_pods`: How many pods the quota allows.
+    pub fn new_cannot_deploy_namespace_pods_quota_exceeded(
         event_details: EventDetails,
-        error_message: CommandError,
+        quota_name: &str,
+        requested_pods: u32,
+        used_pods: u32,
+        max_pods: u32,
     ) -> EngineError {
-        let message = "Unable to execute Kubernetes exec version: `{}`";
+        let message = format!(
+            "Namespace resource quota `{}` doesn't allow enough Pods (used {} + {} required VS {} allowed).",
+            quota_name, used_pods, requested_pods, max_pods,
+        );
 
         EngineError::new(
             event_details,
-            Tag::CannotExecuteK8sVersion,
-            message.to_string(),
-            Some(error_message),
+            Tag::NotEnoughResourcesToDeployEnvironment,
+            message,
             None,
             None,
+            Some(Hint::new("hint.not_enough_resources_to_deploy_environment", "Consider to raise the namespace resource quota. If not possible, pause or delete unused services or reduce services minimum instances.".to_string())),
         )
     }
 
-    /// Creates new error for cannot determine kubernetes master version.
+    /// Creates new error for cannot uninstall an helm chart.
     ///
     /// Arguments:
     ///
     /// * `event_details`: Error linked event details.
-    /// * `kubernetes_raw_version`: Kubernetes master raw version string we tried to get version from.
-    pub fn new_cannot_determine_k8s_master_version(
+    /// * `helm_chart_name`: Helm chart name.
+    /// * `errored_object_kind`: Errored kubernetes object name.
+    /// * `raw_error`: Raw error message.
+    pub fn new_cannot_uninstall_helm_chart(
         event_details: EventDetails,
-        kubernetes_raw_version: String,
+        helm_chart_name: String,
+        errored_object_kind: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!(
+            "Wasn't able to delete all objects type {}, it's a blocker to then delete cert-manager namespace. {}",
+            helm_chart_name, errored_object_kind,
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::CannotUninstallHelmChart,
+            message,
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
+    /// Creates new error for cannot exec K8s version.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `error_message`: Raw error message.
+    pub fn new_cannot_execute_k8s_exec_version(
+        event_details: EventDetails,
+        error_message: CommandError,
+    ) -> EngineError {
+        let message = "Unable to execute Kubernetes exec version: `{}`";
+
+        EngineError::new(
+            event_details,
+            Tag::CannotExecuteK8sVersion,
+            message.to_string(),
+            Some(error_message),
+            None,
+            None,
+        )
+    }
+
+    /// Creates new error for cannot determine kubernetes master version.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `kubernetes_raw_version`: Kubernetes master raw version string we tried to get version from.
+    pub fn new_cannot_determine_k8s_master_version(
+        event_details: EventDetails,
+        kubernetes_raw_version: String,
     ) -> EngineError {
         let message = format!("Unable to determine Kubernetes master version: `{}`", kubernetes_raw_version,);
 
@@ -1619,6 +3008,30 @@ impl EngineError {
         )
     }
 
+    /// Creates new error when an on-demand backup of a managed database cannot be triggered.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `database_identifier`: Managed database identifier at the cloud provider.
+    /// * `raw_error`: Raw error message.
+    pub fn new_cannot_trigger_database_backup(
+        event_details: EventDetails,
+        database_identifier: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Unable to trigger a backup of managed database `{}`.", database_identifier);
+
+        EngineError::new(
+            event_details,
+            Tag::CannotTriggerDatabaseBackup,
+            message,
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
     pub fn new_cannot_connect_to_k8s_cluster(event_details: EventDetails, kube_error: kube::Error) -> EngineError {
         let message = format!("Unable to connect to target k8s cluster: `{}`", kube_error);
 
@@ -1667,23 +3080,56 @@ impl EngineError {
         )
     }
 
+    /// Creates new error when the kubeconfig file targets another cluster than the requested one
+    ///
+    /// Same case as `new_kubeconfig_file_do_not_match_the_current_cluster`, with both cluster ids in the message
+    /// so the drift is visible right away. Cluster ids are not secrets and are part of the safe message.
+    ///
+    /// Arguments:
+    /// * `event_details`: Error linked event details.
+    /// * `expected_cluster_id`: Id of the cluster the engine is asked to work on.
+    /// * `found_cluster_id`: Id of the cluster the kubeconfig file gives access to.
+    pub fn new_kubeconfig_cluster_id_mismatch(
+        event_details: EventDetails,
+        expected_cluster_id: String,
+        found_cluster_id: String,
+    ) -> EngineError {
+        let message = format!(
+            "The kubeconfig file targets cluster `{}` while cluster `{}` is expected",
+            found_cluster_id, expected_cluster_id
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::KubeconfigFileDoNotPermitToConnectToK8sCluster,
+            message.clone(),
+            Some(CommandError::new_from_safe_message(message)),
+            None,
+            Some(Hint::new(
+                "hint.kubeconfig_file_do_not_permit_to_connect_to_k8s_cluster",
+                "The stored kubeconfig file is outdated or belongs to another cluster, it has to be regenerated."
+                    .to_string(),
+            )),
+        )
+    }
+
     /// Creates new error to catch kubeconfig security issues
     ///
     /// Ensure kubeconfig is not corrupted
     ///
     /// Arguments:
     /// * `event_details`: Error linked event details.
-    /// * `current_size`: Current size of the kubeconfig file
-    /// * `max_size`: Maximum authorized size of the kubeconfig file
+    /// * `current_size`: Current size of the kubeconfig file, in bytes
+    /// * `max_size`: Maximum authorized size of the kubeconfig file, in bytes
     pub fn new_kubeconfig_size_security_check_error(
         event_details: EventDetails,
         current_size: u64,
         max_size: u64,
     ) -> EngineError {
         let message = format!(
-            "The kubeconfig stored in the S3 bucket did not pass our security check. Kubeconfig file size ({}k), exceed authorized kubeconfig size (< {}k)",
-            current_size,
-            max_size
+            "The kubeconfig stored in the S3 bucket did not pass our security check. Kubeconfig file size ({:.1} KiB), exceed authorized kubeconfig size (<= {:.1} KiB)",
+            current_size as f64 / 1024.0,
+            max_size as f64 / 1024.0
         );
 
         EngineError::new(event_details, Tag::KubeconfigSecurityCheckError, message, None, None, None)
@@ -1723,7 +3169,10 @@ impl EngineError {
             "Kubernetes API cannot be reached.".to_string(),
             None,
             None,
-            Some("Did you manually performed changes AWS side?".to_string()),
+            Some(Hint::new(
+                "hint.k8s_cannot_reach_to_api",
+                "Did you manually performed changes AWS side?".to_string(),
+            )),
         )
     }
 
@@ -1864,6 +3313,30 @@ impl EngineError {
         )
     }
 
+    /// Creates new error for kubernetes not being able to cordon a node.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `node_name`: Node name.
+    /// * `raw_k8s_error`: Raw error message.
+    pub fn new_k8s_cannot_cordon_node(
+        event_details: EventDetails,
+        node_name: String,
+        raw_k8s_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Unable to cordon Kubernetes node `{}`.", node_name);
+
+        EngineError::new(
+            event_details,
+            Tag::K8sCannotCordonNode,
+            message,
+            Some(raw_k8s_error),
+            None,
+            None,
+        )
+    }
+
     /// Creates new error for kubernetes upgrade version inconsistency.
     ///
     /// Arguments:
@@ -1917,6 +3390,32 @@ impl EngineError {
         EngineError::new(event_details, Tag::K8sScaleReplicas, message, Some(raw_error), None, None)
     }
 
+    /// Creates new error for kubernetes rollout restart.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `selector`: K8s selector.
+    /// * `namespace`: K8s namespace.
+    /// * `raw_error`: Raw error message.
+    pub fn new_k8s_cannot_rollout_restart(
+        event_details: EventDetails,
+        selector: String,
+        namespace: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Unable to restart Kubernetes `{}` in namespace `{}`.", selector, namespace,);
+
+        EngineError::new(
+            event_details,
+            Tag::K8sCannotRolloutRestart,
+            message,
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
     /// Creates new error for kubernetes load balancer configuration issue.
     ///
     /// Arguments:
@@ -1997,6 +3496,25 @@ impl EngineError {
         EngineError::new(event_details, Tag::K8sGetLogs, message, Some(raw_error), None, None)
     }
 
+    /// Creates new error for kubernetes get events.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_k8s_get_events(event_details: EventDetails, raw_error: CommandError) -> EngineError {
+        let message = "Error, unable to retrieve Kubernetes events.";
+
+        EngineError::new(
+            event_details,
+            Tag::K8sGetEvents,
+            message.to_string(),
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
     /// Creates new error for kubernetes describe.
     ///
     /// Arguments:
@@ -2078,6 +3596,33 @@ impl EngineError {
         EngineError::new(event_details, Tag::K8sPodIsNotReady, message, Some(raw_error), None, None)
     }
 
+    /// Creates new error for a blue/green deployment rolled back because its new version is not healthy.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `deployment_name`: Name of the deployment running the new version.
+    /// * `reason`: Why the new version is considered unhealthy.
+    pub fn new_k8s_blue_green_rollback(
+        event_details: EventDetails,
+        deployment_name: String,
+        reason: String,
+    ) -> EngineError {
+        let message = format!(
+            "Error, new version `{}` is not healthy, it has been rolled back and the previous version keeps receiving the traffic.",
+            deployment_name
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::K8sPodIsNotReady,
+            message.to_string(),
+            Some(CommandError::new(message, Some(reason), None)),
+            None,
+            Some(Hint::new("hint.k8s_blue_green_rollback", "Check your application logs and its readiness probe settings (`readiness_probe.type`, `readiness_probe.http_get.path`).".to_string())),
+        )
+    }
+
     /// Creates new error for kubernetes node not being ready with the requested version.
     ///
     /// Arguments:
@@ -2121,6 +3666,54 @@ impl EngineError {
         )
     }
 
+    /// Creates new error for kubernetes node having a condition preventing pods to be scheduled on it.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `node_name`: Node name.
+    /// * `condition`: Node condition (DiskPressure, MemoryPressure, NotReady).
+    /// * `condition_message`: Message reported by the kubelet for this condition.
+    /// * `raw_error`: Raw error message of the failed operation, i.e: a deployment timing out.
+    pub fn new_k8s_node_not_ready_with_condition(
+        event_details: EventDetails,
+        node_name: String,
+        condition: String,
+        condition_message: String,
+        raw_error: Option<CommandError>,
+    ) -> EngineError {
+        let message = format!(
+            "Error, node `{}` has condition `{}`, pods can't be scheduled on it.",
+            node_name, condition
+        );
+        let hint = match condition.as_str() {
+            "DiskPressure" => Hint::new(
+                "hint.k8s_node_is_not_ready.disk_pressure",
+                "Node is running out of disk space, free some space (i.e: unused images) or use bigger disks.",
+            ),
+            "MemoryPressure" => Hint::new(
+                "hint.k8s_node_is_not_ready.memory_pressure",
+                "Node is running out of memory, reduce memory consumption or use bigger instances.",
+            ),
+            _ => Hint::new(
+                "hint.k8s_node_is_not_ready",
+                "Node is not ready, it may be starting, overloaded or unreachable. Check its status in your cloud provider console.",
+            ),
+        };
+
+        EngineError::new(
+            event_details,
+            Tag::K8sNodeIsNotReady,
+            message.to_string(),
+            Some(match raw_error {
+                Some(raw_error) => CommandError::new(message, Some(condition_message), None).merge(raw_error),
+                None => CommandError::new(message, Some(condition_message), None),
+            }),
+            None,
+            Some(hint),
+        )
+    }
+
     /// Creates new error for kubernetes validate required CPU and burstable.
     ///
     /// Arguments:
@@ -2146,7 +3739,10 @@ impl EngineError {
             message,
             Some(raw_error),
             None,
-            Some("Please ensure your configuration is valid.".to_string()),
+            Some(Hint::new(
+                "hint.k8s_validate_required_cpu_and_burstable_error",
+                "Please ensure your configuration is valid.".to_string(),
+            )),
         )
     }
 
@@ -2182,19 +3778,36 @@ impl EngineError {
         EngineError::new(event_details, Tag::SubnetsCountShouldBeEven, message, None, None, None)
     }
 
+    /// Creates new error for a CIDR block which is malformed or cannot be split into the requested subnets.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `cidr_block`: CIDR block.
+    /// * `reason`: Why the CIDR block cannot be used.
+    pub fn new_invalid_cidr_block(event_details: EventDetails, cidr_block: String, reason: String) -> EngineError {
+        let message = format!("CIDR block `{}` is not valid: {}.", cidr_block, reason);
+
+        EngineError::new(event_details, Tag::InvalidCIDRBlock, message, None, None, None)
+    }
+
     /// Creates new error for IAM role which cannot be retrieved or created.
     ///
     /// Arguments:
     ///
     /// * `event_details`: Error linked event details.
-    /// * `role_name`: IAM role name which failed to be created.
+    /// * `role_name`: IAM role name which failed to be retrieved or created.
+    /// * `reason`: Why the role failed to be retrieved or created.
     /// * `raw_error`: Raw error message.
+    /// * `hint`: Hint depending on the cloud provider error, if any.
     pub fn new_cannot_get_or_create_iam_role(
         event_details: EventDetails,
         role_name: String,
+        reason: String,
         raw_error: CommandError,
+        hint: Option<Hint>,
     ) -> EngineError {
-        let message = format!("Error while getting or creating the role {}.", role_name,);
+        let message = format!("Error while getting or creating the IAM role `{}`: {}.", role_name, reason);
 
         EngineError::new(
             event_details,
@@ -2202,7 +3815,7 @@ impl EngineError {
             message,
             Some(raw_error),
             None,
-            None,
+            hint,
         )
     }
 
@@ -2270,7 +3883,7 @@ impl EngineError {
                 terraform_error.to_string(), // Note: end-game goal is to have 0 Unknown Terraform issues. Showing everything in this case is just more convenient for both user and Qovery team.
                 Some(terraform_error.into()), // Note: Terraform error message are supposed to be safe
                 None,
-                Some(DEFAULT_HINT_MESSAGE.to_string()),
+                Some(Hint::new("hint.terraform_unknown_error", DEFAULT_HINT_MESSAGE.to_string())),
             ),
             TerraformError::MultipleInterruptsReceived { .. } => EngineError::new(
                 event_details,
@@ -2278,7 +3891,7 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()), // Note: Terraform error message are supposed to be safe
                 None,
-                Some(DEFAULT_HINT_MESSAGE.to_string()),
+                Some(Hint::new("hint.terraform_multiple_interrupts_received", DEFAULT_HINT_MESSAGE.to_string())),
             ),
             TerraformError::InvalidCredentials { .. } => EngineError::new(
                 event_details,
@@ -2286,12 +3899,12 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()), // Note: Terraform error message are supposed to be safe
                 None,
-                Some(DEFAULT_HINT_MESSAGE.to_string()),
+                Some(Hint::new("hint.terraform_invalid_credentials", DEFAULT_HINT_MESSAGE.to_string())),
             ),
             TerraformError::AccountBlockedByProvider { .. } => {
                 let hint_message = match event_details.provider_kind() {
-                   Some(Kind::Aws) => Some("This AWS account is currently blocked and not recognized as a valid account. Please contact aws-verification@amazon.com directly to get more details. Maybe you are not allowed to use your free tier in this region? Maybe you need to provide billing info? ".to_string()),
-                    _ => Some("This account is currently blocked by your cloud provider, please contact them directly.".to_string()),
+                   Some(Kind::Aws) => Some(Hint::new("hint.terraform_account_blocked_by_provider.aws", "This AWS account is currently blocked and not recognized as a valid account. Please contact aws-verification@amazon.com directly to get more details. Maybe you are not allowed to use your free tier in this region? Maybe you need to provide billing info? ")),
+                    _ => Some(Hint::new("hint.terraform_account_blocked_by_provider", "This account is currently blocked by your cloud provider, please contact them directly.")),
                 };
 
                 EngineError::new(
@@ -2309,7 +3922,7 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()), // Note: Terraform error message are supposed to be safe
                 None,
-                Some("This is normal if it's a newly created cluster".to_string()),
+                Some(Hint::new("hint.terraform_config_file_not_found", "This is normal if it's a newly created cluster".to_string())),
             ),
             TerraformError::ConfigFileInvalidContent { .. } => EngineError::new(
                 event_details,
@@ -2317,7 +3930,7 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()), // Note: Terraform error message are supposed to be safe
                 None,
-                Some("Did you manually performed changes AWS side?".to_string()),
+                Some(Hint::new("hint.terraform_config_file_invalid_content", "Did you manually performed changes AWS side?".to_string())),
             ),
             TerraformError::CannotDeleteLockFile { .. } => EngineError::new(
                 event_details,
@@ -2357,10 +3970,10 @@ impl EngineError {
                                 terraform_error_string,
                                 Some(terraform_error.into()), // Note: Terraform error message are supposed to be safe
                                 Some(Url::parse("https://hub.qovery.com/docs/using-qovery/troubleshoot/").expect("Error while trying to parse error link helper for `QuotaExceededError::ResourceLimitExceeded`, URL is not valid.")),
-                                Some(format!("Request AWS to increase your `{}` limit{} via this page http://aws.amazon.com/contact-us/ec2-request.", resource_type, match max_resource_count {
+                                Some(Hint::new("hint.terraform_cloud_provider_quotas_reached.aws", format!("Request AWS to increase your `{}` limit{} via this page http://aws.amazon.com/contact-us/ec2-request.", resource_type, match max_resource_count {
                                     None => "".to_string(),
                                     Some(count) => format!(" (current max = {}", count),
-                                })),
+                                }))),
                             );
                         }
 
@@ -2371,10 +3984,10 @@ impl EngineError {
                             terraform_error_string, // Note: Terraform error message are supposed to be safe
                             Some(terraform_error.into()),
                             Some(Url::parse("https://hub.qovery.com/docs/using-qovery/troubleshoot/").expect("Error while trying to parse error link helper for `QuotaExceededError::ResourceLimitExceeded`, URL is not valid.")),
-                            Some(format!("Request your cloud provider to increase your `{}` limit{}", resource_type, match max_resource_count {
+                            Some(Hint::new("hint.terraform_cloud_provider_quotas_reached", format!("Request your cloud provider to increase your `{}` limit{}", resource_type, match max_resource_count {
                                 None => "".to_string(),
                                 Some(count) => format!("(current max = {}", count),
-                            })),
+                            }))),
                         )
                     },
 
@@ -2385,7 +3998,7 @@ impl EngineError {
                         terraform_error_string, // Note: Terraform error message are supposed to be safe
                         Some(terraform_error.into()),
                         Some(Url::parse("https://hub.qovery.com/docs/using-qovery/configuration/cloud-service-provider/scaleway/#connect-your-scaleway-account").expect("Error while trying to parse error link helper for `QuotaExceededError::ScwNewAccountNeedsValidation`, URL is not valid.")),
-                        Some("If you have a new Scaleway account, your quota must be unlocked by the Scaleway support teams. To do this, open a ticket with their support with the following message: 'Hello, I would like to deploy my applications on Scaleway with Qovery. Can you increase my quota for the current Kubernetes node type to 10 please? '".to_string()),
+                        Some(Hint::new("hint.terraform_cloud_provider_activation_required", "If you have a new Scaleway account, your quota must be unlocked by the Scaleway support teams. To do this, open a ticket with their support with the following message: 'Hello, I would like to deploy my applications on Scaleway with Qovery. Can you increase my quota for the current Kubernetes node type to 10 please? '".to_string())),
                     ),
                 }
             }
@@ -2419,7 +4032,7 @@ impl EngineError {
                 terraform_error.to_safe_message(), // Note: Terraform error message are supposed to be safe
                 Some(terraform_error.into()),
                 Some(Url::parse("https://hub.qovery.com/docs/getting-started/install-qovery/").expect("Error while trying to parse error link helper for `TerraformError::NotEnoughPermissions`, URL is not valid.")),
-                Some("Make sure you provide proper credentials for your cloud account.".to_string()),
+                Some(Hint::new("hint.terraform_not_enough_permissions", "Make sure you provide proper credentials for your cloud account.".to_string())),
             ),
             TerraformError::WrongExpectedState { .. } => EngineError::new(
                 event_details,
@@ -2427,15 +4040,15 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()),
                 None,
-                Some("Try to set the resource in the desired state from your Cloud provider web console or API".to_string()),
+                Some(Hint::new("hint.terraform_wrong_state", "Try to set the resource in the desired state from your Cloud provider web console or API".to_string())),
             ),
             TerraformError::ResourceDependencyViolation { .. } => EngineError::new(
                 event_details,
-                Tag::TerraformWrongState,
+                Tag::TerraformResourceDependencyViolation,
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()),
                 None,
-                None,
+                Some(Hint::new("hint.terraform_resource_dependency_violation", "Some resources still depend on this one, usually lingering network interfaces (ENIs), load balancers, security groups or NAT gateways created outside of Terraform. Delete them first from your cloud provider console or API, then relaunch the deletion.".to_string())),
             ),
             TerraformError::InstanceTypeDoesntExist { .. } => EngineError::new(
                 event_details,
@@ -2443,7 +4056,7 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()),
                 None,
-                Some("Select a different instance type in your cluster settings and re-launch the installation process".to_string()),
+                Some(Hint::new("hint.terraform_instance_type_doesnt_exist", "Select a different instance type in your cluster settings and re-launch the installation process".to_string())),
             ),
             TerraformError::InstanceVolumeCannotBeDownSized { .. } => EngineError::new(
                 event_details,
@@ -2451,7 +4064,7 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()),
                 None,
-                Some("An existing instance volume cannot be downsized, you can only increase its volume.".to_string()),
+                Some(Hint::new("hint.terraform_instance_volume_cannot_be_reduced", "An existing instance volume cannot be downsized, you can only increase its volume.".to_string())),
             ),
             TerraformError::InvalidCIDRBlock { .. } => EngineError::new(
                 event_details,
@@ -2459,16 +4072,50 @@ impl EngineError {
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()),
                 None,
-                Some("The CIDR block is equal to or more specific than one of this VPC's CIDR blocks.".to_string()),
+                Some(Hint::new("hint.terraform_invalid_cidr_block", "The CIDR block is equal to or more specific than one of this VPC's CIDR blocks.".to_string())),
             ),
-            TerraformError::StateLocked { .. } => EngineError::new(
+            TerraformError::SubnetAddressPoolExhausted { .. } => EngineError::new(
                 event_details,
-                Tag::TerraformStateLocked,
+                Tag::TerraformSubnetAddressPoolExhausted,
                 terraform_error.to_safe_message(),
                 Some(terraform_error.into()),
                 None,
-                Some("Your deployment failed because Terraform faced a state lock. Please contact Qovery team to get unlocked.".to_string()),
+                Some(Hint::new("hint.terraform_subnet_address_pool_exhausted", "The subnet ran out of free IP addresses. Release unused IPs (network interfaces, load balancers) or resize your VPC / subnets with larger CIDR blocks, then relaunch the deployment.".to_string())),
             ),
+            TerraformError::StateLocked { ref lock_info, .. } => {
+                let locked_by = match &lock_info.who {
+                    Some(who) => format!(" by `{}`", who),
+                    None => "".to_string(),
+                };
+                let hint = format!(
+                    "Your deployment failed because Terraform state is locked{} (lock ID `{}`). The lock is usually released once the other deployment is over, please retry later. If no other deployment is running, the lock can be released with `terraform force-unlock {}`, otherwise please contact Qovery team to get unlocked.",
+                    locked_by, lock_info.id, lock_info.id
+                );
+                EngineError::new(
+                    event_details,
+                    Tag::TerraformStateLocked,
+                    terraform_error.to_safe_message(),
+                    Some(terraform_error.into()),
+                    None,
+                    Some(Hint::new("hint.terraform_state_locked", hint)),
+                )
+            }
+            TerraformError::Diagnostic { ref diagnostic, .. } => {
+                let hint_message = if diagnostic.detail.is_empty() {
+                    DEFAULT_HINT_MESSAGE.to_string()
+                } else {
+                    diagnostic.detail.to_string()
+                };
+
+                EngineError::new(
+                    event_details,
+                    Tag::TerraformDiagnosticError,
+                    terraform_error.to_safe_message(),
+                    Some(terraform_error.into()), // Note: Terraform error message are supposed to be safe
+                    None,
+                    Some(Hint::new("hint.terraform_diagnostic_error", hint_message)),
+                )
+            }
         }
     }
 
@@ -2543,7 +4190,7 @@ impl EngineError {
                 "Container registry: credentials are not valid.".to_string(),
                 Some(error.into()),
                 Some(Url::parse("https://hub.qovery.com/docs/getting-started/install-qovery/").expect("Error while trying to parse error link helper for `ContainerRegistryError::InvalidCredentials`, URL is not valid.")),
-                Some("Make sure you provide proper credentials for your cloud account.".to_string()),
+                Some(Hint::new("hint.container_registry_invalid_credentials", "Make sure you provide proper credentials for your cloud account.".to_string())),
             ),
             ContainerRegistryError::CannotGetCredentials => EngineError::new(
                 event_details,
@@ -2650,10 +4297,20 @@ impl EngineError {
     ///
     /// * `event_details`: Error linked event details.
     /// * `error`: Raw error message.
-    pub fn new_build_error(event_details: EventDetails, error: BuildError, user_message: String) -> EngineError {
-        let command_error = CommandError::from(error);
+    /// * `user_message`: User log message.
+    /// * `sensitive_values`: Values (such as sensitive build args) to be redacted from the error.
+    pub fn new_build_error(
+        event_details: EventDetails,
+        error: BuildError,
+        user_message: String,
+        sensitive_values: &[&str],
+    ) -> EngineError {
+        let user_message = redact_values(&user_message, sensitive_values);
+        let sensitive_values: Vec<String> = sensitive_values.iter().map(|value| value.to_string()).collect();
+        let command_error = CommandError::from(error).with_redacted_secrets(&sensitive_values);
+        let hint = out_of_memory_hint(&command_error);
 
-        EngineError::new(event_details, Tag::BuilderError, user_message, Some(command_error), None, None)
+        EngineError::new(event_details, Tag::BuilderError, user_message, Some(command_error), None, hint)
     }
 
     /// Creates new error from an Container Registry error
@@ -2665,16 +4322,33 @@ impl EngineError {
     pub fn new_helm_error(event_details: EventDetails, error: HelmError) -> EngineError {
         let cmd_error = match &error {
             HelmError::Killed(_, _) => return EngineError::new_task_cancellation_requested(event_details),
-            HelmError::CmdError(_, _, cmd_error) => Some(cmd_error.clone()),
+            HelmError::CmdError(_, _, cmd_error) => Some(cmd_error.clone().with_tool(Tool::Helm)),
             _ => None,
         };
 
         let tag = match &error {
-            HelmError::Timeout(_, _, _) => Tag::HelmDeployTimeout,
+            HelmError::Timeout(_, _, _, _) => Tag::HelmDeployTimeout,
+            HelmError::InvalidPostRenderer(_, _) => Tag::HelmChartsSetupError,
+            HelmError::InvalidJsonValue(_, _, _) => Tag::HelmChartsSetupError,
             _ => Tag::HelmChartsDeployError,
         };
 
-        EngineError::new(event_details, tag, error.to_string(), cmd_error, None, None)
+        // tell which resources helm was still waiting for, it is where the user should look at
+        let (message, hint) = match &error {
+            HelmError::Timeout(_, _, _, not_ready_resources) if !not_ready_resources.is_empty() => (
+                format!("{}. Resources still not ready: {}.", error, not_ready_resources.join(", ")),
+                Some(Hint::new(
+                    "hint.helm_deploy_timeout",
+                    format!(
+                        "Check the events and logs of the resources still not ready: {}.",
+                        not_ready_resources.join(", ")
+                    ),
+                )),
+            ),
+            _ => (error.to_string(), None),
+        };
+
+        EngineError::new(event_details, tag, message, cmd_error, None, hint)
     }
 
     /// Creates new error while uninstalling Helm chart.
@@ -2798,7 +4472,10 @@ impl EngineError {
             message.to_string(),
             Some(raw_error),
             None,
-            Some("Maybe there is a lag and cluster is not yet reported, please retry later.".to_string()),
+            Some(Hint::new(
+                "hint.cannot_get_cluster",
+                "Maybe there is a lag and cluster is not yet reported, please retry later.".to_string(),
+            )),
         )
     }
 
@@ -2809,10 +4486,12 @@ impl EngineError {
     /// * `event_details`: Error linked event details.
     /// * `service_id`: Client service ID.
     /// * `service_name`: Client service name.
+    /// * `raw_error`: Raw error message, i.e: the deployment error telling which resources are not ready.
     pub fn new_client_service_failed_to_start_error(
         event_details: EventDetails,
         service_id: String,
         service_name: String,
+        raw_error: Option<CommandError>,
     ) -> EngineError {
         // TODO(benjaminch): Service should probably passed otherwise, either inside event_details or via a new dedicated struct.
         let message = format!("Service `{}` (id `{}`) failed to start. ⤬", service_name, service_id);
@@ -2821,10 +4500,41 @@ impl EngineError {
             event_details,
             Tag::ClientServiceFailedToStart,
             message,
+            raw_error,
             None,
+            Some(Hint::new("hint.client_service_failed_to_start", "Ensure you can run it without issues with `qovery run` and check its logs from the web interface or the CLI with `qovery log`. \
+                This issue often occurs due to ports misconfiguration. Make sure you exposed the correct port (using EXPOSE statement in Dockerfile or via Qovery configuration).".to_string())),
+        )
+    }
+
+    /// Creates new error for a client service which cannot start because its image cannot be pulled.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_id`: Client service ID.
+    /// * `service_name`: Client service name.
+    /// * `pod_name`: Name of the pod which cannot pull its image.
+    /// * `pull_error_message`: Message reported by the kubelet, i.e: `Back-off pulling image "..."`.
+    pub fn new_k8s_image_pull_error(
+        event_details: EventDetails,
+        service_id: String,
+        service_name: String,
+        pod_name: String,
+        pull_error_message: String,
+    ) -> EngineError {
+        let message = format!(
+            "Service `{}` (id `{}`) failed to start, image of pod `{}` cannot be pulled.",
+            service_name, service_id, pod_name
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::K8sImagePullError,
+            message.to_string(),
+            Some(CommandError::new(message, Some(pull_error_message), None)),
             None,
-            Some("Ensure you can run it without issues with `qovery run` and check its logs from the web interface or the CLI with `qovery log`. \
-                This issue often occurs due to ports misconfiguration. Make sure you exposed the correct port (using EXPOSE statement in Dockerfile or via Qovery configuration).".to_string()),
+            Some(Hint::new("hint.k8s_image_pull_error", "Ensure the image and its tag exist in the container registry, and that the registry credentials are valid and allowed to pull it.".to_string())),
         )
     }
 
@@ -2862,22 +4572,23 @@ impl EngineError {
     ///
     /// * `event_details`: Error linked event details.
     /// * `service_id`: Client service ID.
-    /// * `service_type`: Client service type.
+    /// * `database_type`: Client database type.
     /// * `raw_error`: Raw error message.
     pub fn new_database_failed_to_start_after_several_retries(
         event_details: EventDetails,
         service_id: String,
-        service_type: String,
+        database_type: DatabaseType,
         raw_error: Option<CommandError>,
     ) -> EngineError {
         let message = format!(
             "Database `{}` (id `{}`) failed to start after several retries.",
-            service_type, service_id
+            database_type.to_string(),
+            service_id
         );
 
-        let hint = match service_type.eq("Redis") {
-            true => Some("If you are redeploying a managed Redis v6 created before 2022-21-07, it means you're using a deprecated version. The database is running but we recommend to create a fresh new one to replace the actual.".to_string()),
-            false => None
+        let hint = match database_type {
+            DatabaseType::Redis => Some(Hint::new("hint.database_failed_to_start_after_several_retries", "If you are redeploying a managed Redis v6 created before 2022-21-07, it means you're using a deprecated version. The database is running but we recommend to create a fresh new one to replace the actual.")),
+            DatabaseType::PostgreSQL | DatabaseType::MySQL | DatabaseType::MongoDB => None,
         };
 
         EngineError::new(
@@ -2915,7 +4626,38 @@ impl EngineError {
             message.to_string(),
             None,
             None,
-            Some("Please contact your Organization administrator to fix or change the Credentials.".to_string()),
+            Some(Hint::new(
+                "hint.cloud_provider_client_invalid_credentials",
+                "Please contact your Organization administrator to fix or change the Credentials.".to_string(),
+            )),
+        )
+    }
+
+    /// Creates new error when cloud provider credentials are not allowed to perform operations the engine needs.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `user`: Cloud provider user or role the credentials belong to.
+    /// * `denied_actions`: Provider actions the credentials are not allowed to perform.
+    pub fn new_cloud_provider_missing_permissions(
+        event_details: EventDetails,
+        user: String,
+        denied_actions: Vec<String>,
+    ) -> EngineError {
+        let message = format!(
+            "Error, user `{}` is not allowed to perform: {}.",
+            user,
+            denied_actions.join(", ")
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::TerraformNotEnoughPermissions,
+            message,
+            None,
+            Some(Url::parse("https://hub.qovery.com/docs/getting-started/install-qovery/").expect("Error while trying to parse error link helper for `Tag::TerraformNotEnoughPermissions`, URL is not valid.")),
+            Some(Hint::new("hint.terraform_not_enough_permissions", "Make sure you provide proper credentials for your cloud account.".to_string())),
         )
     }
 
@@ -2958,7 +4700,10 @@ impl EngineError {
             message.to_string(),
             Some(raw_error),
             None,
-            Some("Maybe there is a lag and cluster is not yet reported, please retry later.".to_string()),
+            Some(Hint::new(
+                "hint.cannot_get_cluster",
+                "Maybe there is a lag and cluster is not yet reported, please retry later.".to_string(),
+            )),
         )
     }
 
@@ -3015,7 +4760,10 @@ impl EngineError {
             message.to_string(),
             Some(raw_error),
             None,
-            Some("Maybe there is a lag and cluster is not yet reported, please retry later.".to_string()),
+            Some(Hint::new(
+                "hint.cannot_get_cluster",
+                "Maybe there is a lag and cluster is not yet reported, please retry later.".to_string(),
+            )),
         )
     }
 
@@ -3037,7 +4785,10 @@ impl EngineError {
             message.to_string(),
             Some(raw_error),
             None,
-            Some("Please contact Qovery support for investigation.".to_string()),
+            Some(Hint::new(
+                "hint.only_one_cluster_expected",
+                "Please contact Qovery support for investigation.".to_string(),
+            )),
         )
     }
 
@@ -3074,7 +4825,10 @@ impl EngineError {
             message,
             None,
             None,
-            Some("Your Dockerfile is not present at the specified location, check your settings.".to_string()),
+            Some(Hint::new(
+                "hint.builder_docker_cannot_find_any_dockerfile",
+                "Your Dockerfile is not present at the specified location, check your settings.".to_string(),
+            )),
         )
     }
 
@@ -3096,7 +4850,10 @@ impl EngineError {
             message,
             None,
             None,
-            Some("Expected format `builder[@version]`.".to_string()),
+            Some(Hint::new(
+                "hint.builder_buildpack_invalid_language_format",
+                "Expected format `builder[@version]`.".to_string(),
+            )),
         )
     }
 
@@ -3122,11 +4879,11 @@ impl EngineError {
             message.to_string(),
             Some(raw_error),
             None,
-            Some(format!(
+            Some(Hint::new("hint.builder_buildpack_cannot_build_container_image", format!(
                 "Qovery can't build your container image {} with one of the following builders: {}. Please do provide a valid Dockerfile to build your application or contact the support.",
                 container_image_name,
                 builders.join(", ")
-            ),),
+            ),)),
         )
     }
 
@@ -3189,15 +4946,20 @@ impl EngineError {
     /// * `event_details`: Error linked event details.
     /// * `error`: Raw error message.
     pub fn new_docker_error(event_details: EventDetails, error: DockerError) -> EngineError {
+        if let DockerError::PullRateLimited { .. } = error {
+            return EngineError::new_docker_pull_image_rate_limited(event_details, CommandError::from(error));
+        }
+
         // build command error from underlying error in order to have proper safe message.
         let command_error = CommandError::from(error);
+        let hint = out_of_memory_hint(&command_error);
         EngineError::new(
             event_details,
             Tag::DockerError,
             command_error.message_safe(),
             Some(command_error),
             None,
-            None,
+            hint,
         )
     }
 
@@ -3206,18 +4968,16 @@ impl EngineError {
     /// Arguments:
     ///
     /// * `event_details`: Error linked event details.
-    /// * `image_name`: Docker image name.
-    /// * `repository_url`: Repository URL.
+    /// * `image`: Docker image reference.
     /// * `raw_error`: Raw error message.
     pub fn new_docker_push_image_error(
         event_details: EventDetails,
-        image_name: String,
-        repository_url: String,
+        image: &ImageReference,
         raw_error: CommandError,
     ) -> EngineError {
         let message = format!(
-            "Error, trying to push Docker image `{}` to repository `{}`.",
-            image_name, repository_url
+            "Error, trying to push Docker image `{}` to registry `{}`.",
+            image, image.registry
         );
 
         EngineError::new(event_details, Tag::DockerPushImageError, message, Some(raw_error), None, None)
@@ -3228,23 +4988,65 @@ impl EngineError {
     /// Arguments:
     ///
     /// * `event_details`: Error linked event details.
-    /// * `image_name`: Docker image name.
-    /// * `repository_url`: Repository URL.
+    /// * `image`: Docker image reference.
     /// * `raw_error`: Raw error message.
     pub fn new_docker_pull_image_error(
         event_details: EventDetails,
-        image_name: String,
-        repository_url: String,
+        image: &ImageReference,
         raw_error: CommandError,
     ) -> EngineError {
         let message = format!(
-            "Error, trying to pull Docker image `{}` from repository `{}`.",
-            image_name, repository_url
+            "Error, trying to pull Docker image `{}` from registry `{}`.",
+            image, image.registry
         );
 
         EngineError::new(event_details, Tag::DockerPullImageError, message, Some(raw_error), None, None)
     }
 
+    /// Creates new error when the registry rejects a Docker image pull due to its rate limit.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_docker_pull_image_rate_limited(event_details: EventDetails, raw_error: CommandError) -> EngineError {
+        let message =
+            "Error, the registry rejected the Docker image pull because its pull rate limit has been reached.";
+
+        EngineError::new(
+            event_details,
+            Tag::DockerPullImageRateLimited,
+            message.to_string(),
+            Some(raw_error),
+            None,
+            Some(Hint::new("hint.docker_pull_image_rate_limited", "Wait for the rate limit to reset, authenticate to the registry to get a higher limit, or configure a registry mirror (i.e: a pull through cache) to pull the image from.".to_string())),
+        )
+    }
+
+    /// Creates new error when the TLS handshake with a container registry failed.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `registry_host`: Host of the registry.
+    /// * `raw_error`: Raw error message.
+    pub fn new_container_registry_tls_handshake_failed(
+        event_details: EventDetails,
+        registry_host: &str,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Container registry: TLS handshake with registry `{}` failed.", registry_host);
+
+        EngineError::new(
+            event_details,
+            Tag::ContainerRegistryInvalidCredentials,
+            message,
+            Some(raw_error),
+            None,
+            Some(Hint::new("hint.container_registry_tls_handshake_failed", "Make sure the client certificate and private key of the registry are valid, not expired and trusted by the registry.".to_string())),
+        )
+    }
+
     /// Creates new error when trying to read Dockerfile content.
     ///
     /// Arguments:
@@ -3313,7 +5115,7 @@ impl EngineError {
             message,
             Some(raw_error),
             None,
-            Some("It looks like there is something wrong in your Dockerfile. Try building the application locally with `docker build --no-cache`.".to_string()),
+            Some(Hint::new("hint.builder_docker_cannot_build_container_image", "It looks like there is something wrong in your Dockerfile. Try building the application locally with `docker build --no-cache`.".to_string())),
         )
     }
 
@@ -3356,7 +5158,7 @@ impl EngineError {
                         "Error: quotas exceeded while trying to perform operation on object storage.".to_string(),
                         Some(object_storage_error.into()),
                         Some(Url::parse("https://hub.qovery.com/docs/using-qovery/configuration/cloud-service-provider/scaleway/#connect-your-scaleway-account").expect("Error while trying to parse error link helper for SCW `ObjectStorageError::QuotasExceeded`, URL is not valid.")),
-                        Some("If you have a new Scaleway account, your quota must be unlocked by the Scaleway support teams. To do this, open a ticket with their support with the following message: 'Hello, I would like to deploy my applications on Scaleway with Qovery. Can you increase my quota for the current Kubernetes node type to 10 please?'".to_string()),
+                        Some(Hint::new("hint.object_storage_quota_exceeded.scaleway", "If you have a new Scaleway account, your quota must be unlocked by the Scaleway support teams. To do this, open a ticket with their support with the following message: 'Hello, I would like to deploy my applications on Scaleway with Qovery. Can you increase my quota for the current Kubernetes node type to 10 please?'".to_string())),
                     );
                 }
 
@@ -3367,7 +5169,10 @@ impl EngineError {
                     "Error: quotas exceeded while trying to perform operation on object storage.".to_string(),
                     Some(object_storage_error.into()),
                     None,
-                    Some("Contact your cloud provider support to increase your quotas.".to_string()),
+                    Some(Hint::new(
+                        "hint.object_storage_quota_exceeded",
+                        "Contact your cloud provider support to increase your quotas.".to_string(),
+                    )),
                 )
             }
             ObjectStorageError::InvalidBucketName { ref bucket_name, .. } => EngineError::new(
@@ -3376,7 +5181,10 @@ impl EngineError {
                 format!("Error: bucket name `{}` is not valid.", bucket_name),
                 Some(object_storage_error.into()),
                 None,
-                Some("Check your cloud provider documentation to know bucket naming rules.".to_string()),
+                Some(Hint::new(
+                    "hint.object_storage_invalid_bucket_name",
+                    "Check your cloud provider documentation to know bucket naming rules.".to_string(),
+                )),
             ),
             ObjectStorageError::CannotCreateBucket { ref bucket_name, .. } => EngineError::new(
                 event_details,
@@ -3588,7 +5396,10 @@ impl EngineError {
             message_safe,
             Some(raw_error),
             None,
-            Some("Check your cloud provider information".to_string()),
+            Some(Hint::new(
+                "hint.cloud_provider_information_error",
+                "Check your cloud provider information".to_string(),
+            )),
         )
     }
 
@@ -3610,7 +5421,10 @@ impl EngineError {
             message_safe,
             Some(raw_error),
             None,
-            Some("Check your container registry information".to_string()),
+            Some(Hint::new(
+                "hint.container_registry_invalid_information",
+                "Check your container registry information".to_string(),
+            )),
         )
     }
 
@@ -3629,7 +5443,10 @@ impl EngineError {
             message_safe,
             Some(raw_error),
             None,
-            Some("Check your DNS provider information".to_string()),
+            Some(Hint::new(
+                "hint.dns_provider_information_error",
+                "Check your DNS provider information".to_string(),
+            )),
         )
     }
 
@@ -3647,7 +5464,10 @@ impl EngineError {
             message_safe,
             None,
             None,
-            Some("Check your DNS provider credentials".to_string()),
+            Some(Hint::new(
+                "hint.dns_provider_invalid_credentials",
+                "Check your DNS provider credentials".to_string(),
+            )),
         )
     }
 
@@ -3665,7 +5485,10 @@ impl EngineError {
             message_safe,
             None,
             None,
-            Some("Check your DNS provider api url".to_string()),
+            Some(Hint::new(
+                "hint.dns_provider_invalid_api_url",
+                "Check your DNS provider api url".to_string(),
+            )),
         )
     }
 
@@ -3706,7 +5529,10 @@ impl EngineError {
             message_safe,
             Some(cloud_provider_error_message),
             None,
-            Some("Please ensure Qovery has correct permissions or try again later".to_string()),
+            Some(Hint::new(
+                "hint.cloud_provider_get_load_balancer",
+                "Please ensure Qovery has correct permissions or try again later".to_string(),
+            )),
         )
     }
 
@@ -3727,7 +5553,10 @@ impl EngineError {
             message_safe,
             Some(cloud_provider_error_message),
             None,
-            Some("Please ensure Qovery has correct permissions or try again later".to_string()),
+            Some(Hint::new(
+                "hint.cloud_provider_get_load_balancer_tags",
+                "Please ensure Qovery has correct permissions or try again later".to_string(),
+            )),
         )
     }
 
@@ -3746,6 +5575,20 @@ impl EngineError {
             None,
         )
     }
+
+    /// Creates new error forced by the failure injection hook, only used to test error handling.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `tag`: Injected error unique identifier.
+    /// * `stage`: Deployment stage at which the failure has been injected.
+    #[cfg(feature = "failure-injection")]
+    pub fn new_injected_failure(event_details: EventDetails, tag: Tag, stage: String) -> EngineError {
+        let message_safe = format!("Injected failure `{:?}` at stage `{}`", tag, stage);
+
+        EngineError::new(event_details, tag, message_safe, None, None, None)
+    }
 }
 impl Display for EngineError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -3754,13 +5597,249 @@ impl Display for EngineError {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// MultiEngineError: aggregates errors raised by independent tasks, i.e: services of a same environment,
+/// so a failure doesn't hide the other ones. Errors are kept in the order they were raised.
+pub struct MultiEngineError {
+    errors: Vec<EngineError>,
+}
+
+impl MultiEngineError {
+    pub fn new() -> Self {
+        MultiEngineError::default()
+    }
+
+    pub fn push(&mut self, error: EngineError) {
+        self.errors.push(error)
+    }
+
+    pub fn errors(&self) -> &[EngineError] {
+        &self.errors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the first cancellation if any, as it has to be honored over any other error, the first error otherwise.
+    pub fn first_cancel_or_error(&self) -> Option<&EngineError> {
+        self.errors
+            .iter()
+            .find(|error| error.tag().is_cancel())
+            .or_else(|| self.errors.first())
+    }
+
+    /// Returns a message listing every underlying error along with its tag.
+    pub fn message(&self, message_verbosity: ErrorMessageVerbosity) -> String {
+        let errors = self
+            .errors
+            .iter()
+            .map(|error| format!("- {:?}: {}", error.tag(), error.message(message_verbosity)))
+            .collect::<Vec<_>>();
+
+        format!("{} error(s) occurred:\n{}", self.errors.len(), errors.join("\n"))
+    }
+}
+
+impl From<Vec<EngineError>> for MultiEngineError {
+    fn from(errors: Vec<EngineError>) -> Self {
+        MultiEngineError { errors }
+    }
+}
+
+impl Display for MultiEngineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars).as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::cloud_provider::service::DatabaseType;
     use crate::cloud_provider::Kind;
-    use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
-    use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
+    use crate::cmd::docker::DockerError;
+    use crate::cmd::helm::{HelmCommand, HelmError};
+    use crate::cmd::terraform::{StateLockInfo, TerraformError};
+    use crate::errors::{
+        CommandError, EngineError, EngineErrorBuilder, ErrorMessageVerbosity, Hint, MultiEngineError, Tag, Tool,
+        HINT_KEYS, REDACTED_SECRET, RETIRED_ERROR_CODES,
+    };
+    use crate::events::{EnvironmentStep, EventDetails, InfrastructureStep, Stage, Transmitter};
     use crate::io_models::QoveryIdentifier;
+    use crate::test_utils::EventDetailsBuilder;
+    use regex::Regex;
+    use std::collections::HashSet;
+    use std::fs;
+    use std::time::Duration;
+    use strum::IntoEnumIterator;
     use uuid::Uuid;
+    use walkdir::WalkDir;
+
+    #[test]
+    fn test_terraform_state_locked_error_gives_lock_id() {
+        // setup:
+        let terraform_error = TerraformError::StateLocked {
+            lock_info: StateLockInfo {
+                id: "ecd9f287-8d29-4331-1683-48028be7aaba".to_string(),
+                who: Some("runner@engine-7d9f".to_string()),
+                created: Some("2022-11-14 13:59:21.540636643 +0000 UTC".to_string()),
+            },
+            raw_message: "Error: Error acquiring the state lock".to_string(),
+        };
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let engine_err = EngineError::new_terraform_error(event_details, terraform_error);
+
+        // verify:
+        assert_eq!(&Tag::TerraformStateLocked, engine_err.tag());
+        assert!(engine_err
+            .user_log_message()
+            .contains("lock_id: ecd9f287-8d29-4331-1683-48028be7aaba"));
+        let hint = engine_err.hint_message().clone().unwrap_or_default();
+        assert!(hint.contains("by `runner@engine-7d9f` (lock ID `ecd9f287-8d29-4331-1683-48028be7aaba`)"));
+        assert!(hint.contains("terraform force-unlock ecd9f287-8d29-4331-1683-48028be7aaba"));
+    }
+
+    #[test]
+    fn test_terraform_dependency_violation_error_tag() {
+        // setup:
+        let terraform_error = TerraformError::ResourceDependencyViolation {
+            resource_kind: "Subnet".to_string(),
+            resource_name: "subnet-0a1b2c3d4e5f67890".to_string(),
+            raw_message: "DependencyViolation".to_string(),
+        };
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Delete))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let engine_err = EngineError::new_terraform_error(event_details, terraform_error);
+
+        // verify:
+        assert_eq!(&Tag::TerraformResourceDependencyViolation, engine_err.tag());
+        assert!(engine_err
+            .hint_message()
+            .as_ref()
+            .map(|hint| hint.contains("network interfaces (ENIs), load balancers"))
+            .unwrap_or(false));
+    }
+
+    #[test]
+    fn test_tag_error_codes_are_unique_and_never_reused() {
+        // setup:
+        let mut codes = HashSet::new();
+
+        // execute & verify:
+        for code in RETIRED_ERROR_CODES {
+            assert!(codes.insert(*code), "retired error code {} is listed twice", code);
+        }
+        for tag in Tag::iter() {
+            let code = tag.error_code();
+            assert!((1000..10000).contains(&code), "{:?} error code {} is out of range", tag, code);
+            assert!(codes.insert(code), "{:?} error code {} is already used", tag, code);
+        }
+        assert_eq!(4000, Tag::TerraformUnknownError.error_code() / 1000 * 1000);
+        assert_eq!(5000, Tag::ContainerRegistryUnknownError.error_code() / 1000 * 1000);
+        assert_eq!(6000, Tag::ObjectStorageCannotCreateBucket.error_code() / 1000 * 1000);
+    }
+
+    #[test]
+    fn test_engine_error_message_contains_error_code_only_with_full_details() {
+        // setup:
+        let engine_err = EngineError::new_unknown(
+            EventDetailsBuilder::new()
+                .provider_kind(Kind::Aws)
+                .stage(Stage::Infrastructure(InfrastructureStep::Create))
+                .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+                .build(),
+            "user message".to_string(),
+            None,
+            None,
+            None,
+        );
+
+        // execute & verify:
+        assert_eq!(
+            "user message (error code: 1000)",
+            engine_err.message(ErrorMessageVerbosity::FullDetails)
+        );
+        assert_eq!("user message", engine_err.message(ErrorMessageVerbosity::SafeOnly));
+        assert_eq!(
+            "user message",
+            engine_err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)
+        );
+    }
+
+    #[test]
+    fn test_command_error_from_command_line_redacts_secrets_from_every_verbosity() {
+        // setup:
+        let token = "ghp_s3cr3tT0k3n";
+
+        // execute:
+        let command_err = CommandError::new_from_command_line(
+            "git clone failed".to_string(),
+            "git".to_string(),
+            vec![
+                "clone".to_string(),
+                format!("https://x-access-token:{}@github.com/org/repo.git", token),
+            ],
+            vec![
+                ("GIT_TOKEN".to_string(), token.to_string()),
+                ("AWS_REGION".to_string(), "eu-west-3".to_string()),
+            ],
+            Some(format!("Cloning into 'repo' with token {}...", token)),
+            Some("fatal: repository not found in eu-west-3 with token ghp_s3cr3tT0k3n".to_string()),
+            Some(128),
+        );
+
+        // verify:
+        assert_eq!(Some(128), command_err.exit_status());
+        for verbosity in [
+            ErrorMessageVerbosity::SafeOnly,
+            ErrorMessageVerbosity::FullDetailsWithoutEnvVars,
+            ErrorMessageVerbosity::FullDetails,
+            ErrorMessageVerbosity::Structured,
+        ] {
+            let message = command_err.message(verbosity);
+            assert!(!message.contains(token), "token is leaking in: {}", message);
+        }
+        let full_message = command_err.message(ErrorMessageVerbosity::FullDetails);
+        assert!(full_message.contains("with token <redacted>..."));
+        assert!(full_message.contains("repository not found in eu-west-3 with token <redacted>"));
+        assert!(full_message.contains("GIT_TOKEN=<redacted>"));
+        let env_vars = command_err.env_vars().expect("env vars should be kept");
+        assert_eq!(
+            vec![
+                ("GIT_TOKEN".to_string(), REDACTED_SECRET.to_string()),
+                ("AWS_REGION".to_string(), "eu-west-3".to_string()),
+            ],
+            env_vars.into_raw()
+        );
+    }
+
+    #[test]
+    fn test_command_error_with_redacted_secrets() {
+        // setup:
+        let command_err = CommandError::new(
+            "cannot login with my-password".to_string(),
+            Some("STDOUT login my-user:my-password".to_string()),
+            None,
+        );
+
+        // execute:
+        let command_err = command_err.with_redacted_secrets(&["my-password".to_string(), "".to_string()]);
+
+        // verify:
+        assert_eq!("cannot login with <redacted>", command_err.message_safe());
+        assert_eq!(Some("STDOUT login my-user:<redacted>".to_string()), command_err.message_raw());
+    }
 
     #[test]
     fn test_command_error_test_hidding_env_vars_in_message_safe_only() {
@@ -3796,6 +5875,36 @@ mod tests {
         assert!(!res.contains("my_secret_value"));
     }
 
+    #[test]
+    fn test_command_error_structured_hides_env_vars_unless_read_explicitly() {
+        // setup:
+        let command_err = CommandError::new(
+            "my safe message".to_string(),
+            Some("my raw message".to_string()),
+            Some(vec![("my_secret".to_string(), "my_secret_value".to_string())]),
+        );
+
+        // execute:
+        let parts = command_err.structured();
+        let res = command_err.message(ErrorMessageVerbosity::Structured);
+
+        // verify:
+        assert_eq!("my safe message", parts.safe);
+        assert_eq!(Some("my raw message".to_string()), parts.full);
+        assert_eq!(
+            Some(vec![("my_secret".to_string(), "my_secret_value".to_string())]),
+            parts.env_vars
+        );
+        assert!(!format!("{:?}", parts).contains("my_secret"));
+        assert!(!res.contains("my_secret"));
+        assert_eq!(
+            serde_json::json!({"safe": "my safe message", "full": "my raw message"}),
+            serde_json::from_str::<serde_json::Value>(&res).unwrap()
+        );
+        // default display stays safe only
+        assert_eq!("my safe message", command_err.to_string());
+    }
+
     #[test]
     fn test_engine_error_test_hidding_env_vars_in_message_safe_only() {
         // setup:
@@ -3871,10 +5980,13 @@ mod tests {
 
         // execute:
         let res = format!("{}", command_err);
+        let env_vars = command_err.env_vars().expect("env vars should be kept");
 
         // verify:
         assert!(!res.contains("my_secret"));
         assert!(!res.contains("my_secret_value"));
+        assert_eq!("my_secret=<redacted>", env_vars.to_string());
+        assert!(!format!("{:?}", env_vars).contains("my_secret_value"));
     }
 
     #[test]
@@ -3943,4 +6055,646 @@ mod tests {
             &Stage::Infrastructure(InfrastructureStep::CreateError)
         );
     }
+
+    #[test]
+    fn test_clone_engine_error_with_tag() {
+        // setup:
+        let engine_err = EngineError::new_unknown(
+            EventDetailsBuilder::new().provider_kind(Kind::Scw).build(),
+            "user_log_message".to_string(),
+            Some(CommandError::new_from_safe_message("my safe message".to_string())),
+            None,
+            Some("hint".to_string()),
+        );
+        assert_eq!(
+            engine_err.event_details.stage(),
+            &Stage::Environment(EnvironmentStep::DeployedError)
+        );
+
+        // execute:
+        let cancel_err = engine_err.clone_with_tag(Tag::TaskCancellationRequested);
+
+        // verify:
+        assert_eq!(cancel_err.tag(), &Tag::TaskCancellationRequested);
+        assert_eq!(
+            cancel_err.event_details.stage(),
+            &Stage::Environment(EnvironmentStep::Cancelled)
+        );
+        assert_eq!(cancel_err.user_log_message, engine_err.user_log_message);
+        assert_eq!(cancel_err.underlying_error, engine_err.underlying_error);
+        assert_eq!(cancel_err.hint_message, engine_err.hint_message);
+        assert_eq!(cancel_err.event_details.execution_id(), engine_err.event_details.execution_id());
+    }
+
+    #[test]
+    fn test_engine_error_suggested_http_status() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+        let test_cases = vec![
+            (Tag::InvalidEnginePayload, 400),
+            (Tag::UnsupportedVersion, 400),
+            (Tag::CloudProviderClientInvalidCredentials, 403),
+            (Tag::TerraformNotEnoughPermissions, 403),
+            (Tag::ContainerRegistryImageDoesntExist, 404),
+            (Tag::TerraformStateLocked, 409),
+            (Tag::TerraformSubnetAddressPoolExhausted, 409),
+            (Tag::ClientServiceFailedToStart, 422),
+            (Tag::TerraformCloudProviderQuotasReached, 429),
+            (Tag::ObjectStorageQuotaExceeded, 429),
+            (Tag::DockerPullImageRateLimited, 429),
+            (Tag::Unknown, 500),
+            (Tag::HelmChartsDeployError, 500),
+            (Tag::NotImplementedError, 501),
+            (Tag::VaultSecretCouldNotBeRetrieved, 502),
+            (Tag::CannotTriggerDatabaseBackup, 502),
+            (Tag::K8sCannotReachToApi, 503),
+            (Tag::HelmDeployTimeout, 504),
+        ];
+
+        for (tag, expected_status) in test_cases {
+            // execute:
+            let error = EngineError::new(event_details.clone(), tag.clone(), "error".to_string(), None, None, None);
+
+            // verify:
+            assert_eq!(expected_status, error.suggested_http_status(), "tag: {:?}", tag);
+        }
+    }
+
+    #[test]
+    fn test_engine_error_suggested_http_status_covers_all_tags() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute & verify:
+        // exhaustiveness is enforced by the compiler, this makes sure every status is a valid error status
+        for tag in Tag::iter() {
+            let error = EngineError::new(event_details.clone(), tag.clone(), "error".to_string(), None, None, None);
+            let status = error.suggested_http_status();
+            assert!((400..600).contains(&status), "tag: {:?}, status: {}", tag, status);
+        }
+    }
+
+    #[test]
+    fn test_tag_retryability() {
+        // setup:
+        let test_cases = vec![
+            (Tag::K8sCannotReachToApi, true, Some(Duration::from_secs(10))),
+            (Tag::CannotConnectK8sCluster, true, Some(Duration::from_secs(10))),
+            (Tag::CannotGetCluster, true, Some(Duration::from_secs(10))),
+            (Tag::TerraformStateLocked, true, Some(Duration::from_secs(60))),
+            (Tag::HelmDeployTimeout, true, Some(Duration::from_secs(30))),
+            (Tag::UnsupportedInstanceType, false, None),
+            (Tag::InvalidEnginePayload, false, None),
+            (Tag::NotAllowedInstanceType, false, None),
+        ];
+
+        for (tag, expected_retryable, expected_delay) in test_cases {
+            // execute & verify:
+            assert_eq!(expected_retryable, tag.is_retryable(), "tag: {:?}", tag);
+            assert_eq!(expected_delay, tag.suggested_retry_delay(), "tag: {:?}", tag);
+        }
+    }
+
+    #[test]
+    fn test_tag_retry_delay_only_for_retryable_tags() {
+        // execute & verify:
+        for tag in Tag::iter() {
+            if tag.suggested_retry_delay().is_some() {
+                assert!(tag.is_retryable(), "tag: {:?}", tag);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tag_all_lists_every_tag_with_a_description() {
+        // execute:
+        let tags = Tag::all();
+
+        // verify:
+        // `description` exhaustive match forces every new tag to get one, this makes sure `all` doesn't miss it
+        assert_eq!(Tag::iter().count(), tags.len());
+        for (tag, expected) in tags.iter().zip(Tag::iter()) {
+            assert_eq!(&expected, tag);
+            assert!(!tag.description().is_empty(), "tag: {:?}", tag);
+        }
+    }
+
+    #[test]
+    fn test_engine_error_builder_matches_new() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+        let link = url::Url::parse("https://hub.qovery.com/docs/").expect("valid url");
+        let underlying_error = CommandError::new_from_safe_message("raw error".to_string());
+
+        // execute:
+        let built = EngineErrorBuilder::new()
+            .tag(Tag::HelmDeployTimeout)
+            .user_message("user message")
+            .underlying(underlying_error.clone())
+            .link(link.clone())
+            .hint("hint.helm_deploy_timeout", "hint")
+            .build(event_details.clone());
+        let expected = EngineError::new(
+            event_details,
+            Tag::HelmDeployTimeout,
+            "user message".to_string(),
+            Some(underlying_error),
+            Some(link),
+            Some(Hint::new("hint.helm_deploy_timeout", "hint".to_string())),
+        );
+
+        // verify:
+        assert_eq!(expected, built);
+        assert_eq!(
+            Stage::Infrastructure(InfrastructureStep::CreateError),
+            built.event_details().stage().clone()
+        );
+    }
+
+    #[test]
+    fn test_engine_error_builder_mutates_stage_for_cancel_tag() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let error = EngineErrorBuilder::new()
+            .tag(Tag::TaskCancellationRequested)
+            .user_message("cancelled")
+            .build(event_details);
+
+        // verify:
+        assert!(error.tag().is_cancel());
+        assert_eq!(
+            Stage::Environment(EnvironmentStep::Cancelled),
+            error.event_details().stage().clone()
+        );
+    }
+
+    #[test]
+    fn test_engine_error_helpers_on_builder_are_unchanged() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let error = EngineError::new_invalid_engine_payload(event_details.clone(), "missing field");
+
+        // verify:
+        assert_eq!(
+            EngineError::new(
+                event_details,
+                Tag::InvalidEnginePayload,
+                "Input is invalid and cannot be executed by the engine: missing field".to_string(),
+                None,
+                None,
+                Some(Hint::new(
+                    "hint.invalid_engine_payload",
+                    "This is a Qovery issue, please contact our support team".to_string()
+                )),
+            ),
+            error
+        );
+    }
+
+    #[test]
+    fn test_command_error_merge() {
+        // setup:
+        let init_error = CommandError::new(
+            "terraform init failed".to_string(),
+            Some("init raw error".to_string()),
+            Some(vec![
+                ("AWS_REGION".to_string(), "eu-west-3".to_string()),
+                ("AWS_SECRET_ACCESS_KEY".to_string(), "old-secret".to_string()),
+            ]),
+        );
+        let apply_error = CommandError::new(
+            "terraform apply failed".to_string(),
+            Some("apply raw error".to_string()),
+            Some(vec![
+                ("AWS_SECRET_ACCESS_KEY".to_string(), "new-secret".to_string()),
+                ("TF_LOG".to_string(), "debug".to_string()),
+            ]),
+        );
+
+        // execute:
+        let merged = init_error.merge(apply_error);
+
+        // verify:
+        assert_eq!("terraform init failed | terraform apply failed", merged.message_safe());
+        assert_eq!(Some("init raw error | apply raw error".to_string()), merged.message_raw());
+        assert_eq!(
+            Some(vec![
+                ("AWS_REGION".to_string(), "eu-west-3".to_string()),
+                ("AWS_SECRET_ACCESS_KEY".to_string(), "new-secret".to_string()),
+                ("TF_LOG".to_string(), "debug".to_string()),
+            ]),
+            merged.clone().into_raw_env_vars()
+        );
+        let safe_message = merged.message(ErrorMessageVerbosity::SafeOnly);
+        assert_eq!("terraform init failed | terraform apply failed", safe_message);
+        for hidden in ["secret", "eu-west-3", "raw error"] {
+            assert!(!safe_message.contains(hidden), "`{}` leaked in: {}", hidden, safe_message);
+        }
+        assert!(!merged
+            .message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)
+            .contains("secret"));
+    }
+
+    #[test]
+    fn test_command_error_merge_without_details() {
+        // execute:
+        let merged = CommandError::new_from_safe_message("first".to_string()).merge(CommandError::new(
+            "second".to_string(),
+            Some("details".to_string()),
+            None,
+        ));
+
+        // verify:
+        assert_eq!("first | second", merged.message_safe());
+        assert_eq!(Some("details".to_string()), merged.message_raw());
+        assert_eq!(None, merged.env_vars());
+    }
+
+    #[test]
+    fn test_kubeconfig_cluster_id_mismatch_error() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::LoadConfiguration))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let error = EngineError::new_kubeconfig_cluster_id_mismatch(
+            event_details,
+            "z1a2b3c4d".to_string(),
+            "zf9e8d7c6".to_string(),
+        );
+
+        // verify:
+        assert_eq!(&Tag::KubeconfigFileDoNotPermitToConnectToK8sCluster, error.tag());
+        for message in [
+            error.user_log_message().to_string(),
+            error.message(ErrorMessageVerbosity::SafeOnly),
+            error.message(ErrorMessageVerbosity::FullDetails),
+        ] {
+            assert!(message.contains("z1a2b3c4d"), "expected cluster id missing from: {}", message);
+            assert!(message.contains("zf9e8d7c6"), "found cluster id missing from: {}", message);
+            assert!(!message.contains(REDACTED_SECRET));
+        }
+        assert_eq!(None, error.underlying_error().and_then(|err| err.env_vars()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_oom_killed_exit_status_hint() {
+        use crate::build_platform::BuildError;
+        use crate::cmd::docker::DockerError;
+        use crate::errors::OOM_KILLED_EXIT_STATUS;
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Environment(EnvironmentStep::Build))
+            .build();
+        // raw wait statuses: exit code is in the second byte, killing signal in the first one
+        let exit_status_error = |wait_status: i32| DockerError::ExitStatusError {
+            exit_status: ExitStatus::from_raw(wait_status),
+        };
+        let test_cases: Vec<(Box<dyn Fn() -> DockerError>, Option<i32>, bool)> = vec![
+            (
+                Box::new(move || exit_status_error(137 << 8)),
+                Some(OOM_KILLED_EXIT_STATUS),
+                true,
+            ),
+            (Box::new(move || exit_status_error(9)), Some(OOM_KILLED_EXIT_STATUS), true),
+            (Box::new(move || exit_status_error(1 << 8)), Some(1), false),
+            (
+                Box::new(|| DockerError::Timeout {
+                    raw_error_message: "timeout".to_string(),
+                }),
+                None,
+                false,
+            ),
+        ];
+
+        for (docker_error, expected_exit_status, expect_oom_hint) in test_cases {
+            // execute:
+            let command_error = CommandError::from(docker_error());
+            let docker_engine_error = EngineError::new_docker_error(event_details.clone(), docker_error());
+            let build_engine_error = EngineError::new_build_error(
+                event_details.clone(),
+                BuildError::DockerError {
+                    application: "app".to_string(),
+                    raw_error: docker_error(),
+                },
+                "build failed".to_string(),
+                &[],
+            );
+
+            // verify:
+            assert_eq!(expected_exit_status, command_error.exit_status());
+            for engine_error in [docker_engine_error, build_engine_error] {
+                assert_eq!(
+                    expected_exit_status,
+                    engine_error.underlying_error().and_then(|err| err.exit_status())
+                );
+                let hint = engine_error.hint_message().clone().unwrap_or_default();
+                assert_eq!(expect_oom_hint, hint.contains("exit code 137"), "wrong hint: {}", hint);
+                assert_eq!(expect_oom_hint, hint.contains("ran out of memory"), "wrong hint: {}", hint);
+            }
+        }
+    }
+
+    #[test]
+    fn test_docker_pull_image_rate_limited_error() {
+        use crate::cmd::docker::{is_rate_limited_failure, DockerError};
+
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .transmitter(Transmitter::Container(Uuid::new_v4(), "container".to_string()))
+            .build();
+        let raw_error_message = "Error response from daemon: toomanyrequests: You have reached your pull rate limit. You may increase the limit by authenticating and upgrading: https://www.docker.com/increase-rate-limit";
+
+        // execute:
+        let error = EngineError::new_docker_error(
+            event_details,
+            DockerError::PullRateLimited {
+                raw_error_message: raw_error_message.to_string(),
+            },
+        );
+
+        // verify:
+        assert!(is_rate_limited_failure(raw_error_message));
+        assert_eq!(&Tag::DockerPullImageRateLimited, error.tag());
+        assert_eq!(429, error.suggested_http_status());
+        assert!(error
+            .hint_message()
+            .clone()
+            .unwrap_or_default()
+            .contains("configure a registry mirror"));
+        assert!(error
+            .message(ErrorMessageVerbosity::FullDetails)
+            .contains("You have reached your pull rate limit"));
+    }
+
+    #[test]
+    fn test_database_failed_to_start_hint() {
+        // setup:
+        let test_cases = vec![
+            (DatabaseType::Redis, true),
+            (DatabaseType::PostgreSQL, false),
+            (DatabaseType::MySQL, false),
+            (DatabaseType::MongoDB, false),
+        ];
+
+        for (database_type, expect_hint) in test_cases {
+            // execute:
+            let error = EngineError::new_database_failed_to_start_after_several_retries(
+                EventDetailsBuilder::new()
+                    .provider_kind(Kind::Aws)
+                    .transmitter(Transmitter::Database(Uuid::new_v4(), "database".to_string()))
+                    .build(),
+                "database_id".to_string(),
+                database_type,
+                None,
+            );
+
+            // verify:
+            assert!(error
+                .user_log_message()
+                .contains(&format!("Database `{}`", database_type.to_string())));
+            assert_eq!(
+                expect_hint,
+                error.hint_message().is_some(),
+                "wrong hint for {}",
+                database_type.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_multi_engine_error_keeps_errors_order() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+        let mut errors = MultiEngineError::new();
+
+        // execute:
+        errors.push(EngineError::new_invalid_engine_payload(event_details.clone(), "app1"));
+        errors.push(EngineError::new_job_error(event_details.clone(), "job1".to_string()));
+        errors.push(EngineError::new_unknown(event_details, "db1".to_string(), None, None, None));
+
+        // verify:
+        assert_eq!(
+            vec![&Tag::InvalidEnginePayload, &Tag::JobFailure, &Tag::Unknown],
+            errors.errors().iter().map(|e| e.tag()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&Tag::InvalidEnginePayload),
+            errors.first_cancel_or_error().map(|e| e.tag())
+        );
+        let message = errors.message(ErrorMessageVerbosity::SafeOnly);
+        assert!(message.starts_with("3 error(s) occurred:"));
+        let lines = message.lines().skip(1).collect::<Vec<_>>();
+        assert!(lines[0].starts_with("- InvalidEnginePayload: "));
+        assert_eq!("- JobFailure: job1", lines[1]);
+        assert_eq!("- Unknown: db1", lines[2]);
+    }
+
+    #[test]
+    fn test_multi_engine_error_prioritizes_cancellation() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .transmitter(Transmitter::Environment(Uuid::new_v4(), "env".to_string()))
+            .build();
+
+        // execute:
+        let errors = MultiEngineError::from(vec![
+            EngineError::new_job_error(event_details.clone(), "job1".to_string()),
+            EngineError::new_task_cancellation_requested(event_details),
+        ]);
+
+        // verify:
+        assert_eq!(
+            Some(&Tag::TaskCancellationRequested),
+            errors.first_cancel_or_error().map(|e| e.tag())
+        );
+        assert_eq!(None, MultiEngineError::new().first_cancel_or_error());
+    }
+
+    #[test]
+    fn test_command_error_from_docker_and_terraform_errors_set_tool() {
+        // setup:
+        let docker_error = DockerError::Timeout {
+            raw_error_message: "timeout".to_string(),
+        };
+        let terraform_error = TerraformError::Unknown {
+            terraform_args: vec!["apply".to_string()],
+            raw_message: "error".to_string(),
+        };
+
+        // execute:
+        let docker_command_error = CommandError::from(docker_error);
+        let terraform_command_error = CommandError::from(terraform_error);
+
+        // verify:
+        assert_eq!(Some(Tool::Docker), docker_command_error.tool());
+        assert_eq!(Some(Tool::Terraform), terraform_command_error.tool());
+        assert_eq!(None, CommandError::new_from_safe_message("error".to_string()).tool());
+        assert_eq!(
+            Some(Tool::Docker),
+            CommandError::new_from_safe_message("error".to_string())
+                .merge(docker_command_error)
+                .tool()
+        );
+    }
+
+    #[test]
+    fn test_hint_keys_are_unique_and_not_empty() {
+        // setup:
+        let mut keys = HashSet::new();
+
+        for key in HINT_KEYS {
+            // verify:
+            assert!(key.starts_with("hint."), "hint key {} is not prefixed by `hint.`", key);
+            assert!(keys.insert(key), "hint key {} registered twice", key);
+        }
+    }
+
+    #[test]
+    fn test_hint_keys_given_in_sources_are_registered() {
+        // setup:
+        let hint_key_literal = Regex::new(r#""(hint\.[a-z0-9_.]+)""#).unwrap();
+        let registry = Regex::new(r"(?s)pub const HINT_KEYS: &\[&str\] = &\[.*?\];").unwrap();
+        let mut used_keys = HashSet::new();
+
+        // execute:
+        for entry in WalkDir::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().map(|ext| ext == "rs").unwrap_or(false))
+        {
+            let source = fs::read_to_string(entry.path()).expect("source file should be readable");
+            let source = registry.replace(&source, "");
+            for key in hint_key_literal.captures_iter(&source) {
+                used_keys.insert(key[1].to_string());
+            }
+        }
+
+        // verify:
+        for key in &used_keys {
+            assert!(
+                HINT_KEYS.contains(&key.as_str()),
+                "hint key {} is not registered in HINT_KEYS",
+                key
+            );
+        }
+        for key in HINT_KEYS {
+            assert!(used_keys.contains(*key), "hint key {} is registered but never given", key);
+        }
+    }
+
+    #[test]
+    fn test_errors_with_hint_expose_hint_key() {
+        // setup:
+        let event_details = EventDetailsBuilder::new()
+            .provider_kind(Kind::Aws)
+            .stage(Stage::Infrastructure(InfrastructureStep::Create))
+            .transmitter(Transmitter::Kubernetes(Uuid::new_v4(), "cluster".to_string()))
+            .build();
+
+        // execute:
+        let errors_with_hint = vec![
+            EngineError::new_invalid_engine_payload(event_details.clone(), "invalid payload"),
+            EngineError::new_cluster_has_no_worker_nodes(event_details.clone(), None),
+            EngineError::new_k8s_cannot_reach_api(event_details.clone()),
+            EngineError::new_cannot_get_cluster_error(event_details.clone(), CommandError::default()),
+            EngineError::new_docker_pull_image_rate_limited(event_details.clone(), CommandError::default()),
+            EngineError::new_container_registry_tls_handshake_failed(
+                event_details.clone(),
+                "registry.mycompany.com",
+                CommandError::default(),
+            ),
+            EngineError::new_terraform_error(
+                event_details.clone(),
+                TerraformError::ResourceDependencyViolation {
+                    resource_kind: "Subnet".to_string(),
+                    resource_name: "subnet-0a1b2c3d4e5f67890".to_string(),
+                    raw_message: "DependencyViolation".to_string(),
+                },
+            ),
+            EngineError::new_database_failed_to_start_after_several_retries(
+                event_details.clone(),
+                "db".to_string(),
+                DatabaseType::Redis,
+                None,
+            ),
+            EngineError::new_k8s_blue_green_rollback(
+                event_details.clone(),
+                "app-green".to_string(),
+                "readiness probe failed".to_string(),
+            ),
+            EngineError::new_helm_error(
+                event_details.clone(),
+                HelmError::Timeout(
+                    "app".to_string(),
+                    HelmCommand::UPGRADE,
+                    "timed out".to_string(),
+                    vec!["Deployment/app".to_string()],
+                ),
+            ),
+        ];
+        let node_conditions_errors = ["DiskPressure", "MemoryPressure", "NotReady"].map(|condition| {
+            EngineError::new_k8s_node_not_ready_with_condition(
+                event_details.clone(),
+                "node".to_string(),
+                condition.to_string(),
+                "kubelet message".to_string(),
+                None,
+            )
+        });
+        let error_without_hint = EngineError::new_k8s_get_events(event_details.clone(), CommandError::default());
+        let error_with_free_text_hint = EngineError::new_engine_error(
+            EngineError::new_k8s_cannot_reach_api(event_details),
+            "user message".to_string(),
+            Some("free text".to_string()),
+        );
+
+        // verify:
+        for error in errors_with_hint.iter().chain(node_conditions_errors.iter()) {
+            assert!(error.hint_message().is_some(), "{:?} has no hint", error.tag());
+            match error.hint_key() {
+                Some(key) => assert!(HINT_KEYS.contains(&key), "{:?} hint key {} is not registered", error.tag(), key),
+                None => panic!("{:?} has a hint but no hint key", error.tag()),
+            }
+        }
+        // same tag, different hints
+        let node_conditions_keys: HashSet<_> = node_conditions_errors.iter().map(|e| e.hint_key()).collect();
+        assert_eq!(3, node_conditions_keys.len());
+        assert_eq!(None, error_without_hint.hint_key());
+        assert_eq!(None, error_with_free_text_hint.hint_key());
+    }
 }
@@ -190,7 +190,7 @@ impl EventMessage {
 
 impl From<CommandError> for EventMessage {
     fn from(e: CommandError) -> Self {
-        EventMessage::new_with_env_vars(e.message_safe(), e.message_raw(), e.env_vars())
+        EventMessage::new_with_env_vars(e.message_safe(), e.message_raw(), e.into_raw_env_vars())
     }
 }
 
@@ -469,6 +469,49 @@ impl Display for Transmitter {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// CorrelationId: identifier shared by the logs, cloud provider resources tags and kubernetes labels of an execution,
+/// allowing to correlate them across systems. It is derived from the execution identifier, sanitized to be a valid
+/// kubernetes label value.
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// Creates a new CorrelationId from a Qovery execution identifier.
+    ///
+    /// Arguments
+    ///
+    /// * `execution_id`: Qovery's execution identifier.
+    pub fn from_execution_id(execution_id: &str) -> Self {
+        // kubernetes label values are limited to 63 alphanumeric, '-', '_' or '.' characters,
+        // starting and ending with an alphanumeric one
+        let sanitized: String = execution_id
+            .to_lowercase()
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | '0'..='9' | '-' | '_' | '.' => c,
+                _ => '-',
+            })
+            .take(63)
+            .collect();
+        let sanitized = sanitized.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+
+        match sanitized.is_empty() {
+            true => CorrelationId("unknown".to_string()),
+            false => CorrelationId(sanitized.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// EventDetails: represents an event details, carrying all useful data such as Qovery identifiers, transmitter, stage etc.
 pub struct EventDetails {
@@ -584,6 +627,11 @@ impl EventDetails {
         &self.execution_id
     }
 
+    /// Returns event's correlation identifier, derived from its execution identifier.
+    pub fn correlation_id(&self) -> CorrelationId {
+        CorrelationId::from_execution_id(&self.execution_id)
+    }
+
     /// Returns event's stage in which the event has been triggered.
     pub fn stage(&self) -> &Stage {
         &self.stage
@@ -597,7 +645,9 @@ impl EventDetails {
 
 #[cfg(test)]
 mod tests {
-    use crate::events::{EnvironmentStep, EventMessage, EventMessageVerbosity, InfrastructureStep, Stage};
+    use crate::events::{
+        CorrelationId, EnvironmentStep, EventMessage, EventMessageVerbosity, InfrastructureStep, Stage,
+    };
 
     #[test]
     fn test_event_message() {
@@ -754,4 +804,26 @@ mod tests {
         assert!(!res.contains("my_secret"));
         assert!(!res.contains("my_secret_value"));
     }
+
+    #[test]
+    fn test_correlation_id_from_execution_id() {
+        // setup:
+        let test_cases = vec![
+            (
+                "7b5a2e1c-0b1f-4b3e-9a8c-2d1e0f9c8b7a-3-1672531200",
+                "7b5a2e1c-0b1f-4b3e-9a8c-2d1e0f9c8b7a-3-1672531200",
+            ),
+            ("Execution_ID:42", "execution_id-42"),
+            ("-_exec/", "exec"),
+            ("", "unknown"),
+        ];
+
+        for (execution_id, expected) in test_cases {
+            // execute & verify:
+            assert_eq!(expected, CorrelationId::from_execution_id(execution_id).as_str());
+        }
+
+        // label values are limited to 63 characters
+        assert_eq!(63, CorrelationId::from_execution_id(&"a".repeat(100)).as_str().len());
+    }
 }
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::cmd::command::{ExecutableCommand, QoveryCommand};
 use git2::build::{CheckoutBuilder, RepoBuilder};
 use git2::ErrorCode::Auth;
 use git2::ResetType::Hard;
@@ -110,6 +111,123 @@ where
     // position the repo at the correct commit
     let _ = checkout(&repo, commit_id)?;
 
+    update_submodules(&repo, get_credentials)?;
+
+    Ok(repo)
+}
+
+/// Options of a shallow clone, the number of commits to fetch from the tip of the remote branches
+/// and whether only the default branch should be fetched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShallowCloneOptions<'a> {
+    pub depth: u32,
+    pub single_branch: bool,
+    // login and password used for http basic auth
+    pub basic_auth: Option<(&'a str, &'a str)>,
+}
+
+fn shallow_clone_args(repository_url: &Url, into_dir: &Path, options: &ShallowCloneOptions) -> Vec<String> {
+    vec![
+        "clone".to_string(),
+        "--depth".to_string(),
+        options.depth.to_string(),
+        if options.single_branch {
+            "--single-branch".to_string()
+        } else {
+            "--no-single-branch".to_string()
+        },
+        repository_url.to_string(),
+        into_dir.to_string_lossy().to_string(),
+    ]
+}
+
+// Credentials are passed as env vars and not as args, as command args are logged while env vars values are redacted
+fn shallow_clone_envs(options: &ShallowCloneOptions) -> Vec<(String, String)> {
+    let mut envs = vec![("GIT_TERMINAL_PROMPT".to_string(), "0".to_string())];
+    if let Some((login, password)) = options.basic_auth {
+        let token = base64::encode(format!("{}:{}", login, password));
+        envs.push(("GIT_CONFIG_COUNT".to_string(), "1".to_string()));
+        envs.push(("GIT_CONFIG_KEY_0".to_string(), "http.extraHeader".to_string()));
+        envs.push(("GIT_CONFIG_VALUE_0".to_string(), format!("Authorization: Basic {}", token)));
+    }
+
+    envs
+}
+
+// git2 does not support shallow clones, so we rely on the git binary for it
+fn shallow_clone(repository_url: &Url, into_dir: &Path, options: &ShallowCloneOptions) -> Result<Repository, Error> {
+    if repository_url.scheme() != "https" {
+        return Err(Error::from_str("Repository URL have to start with https://"));
+    }
+
+    if into_dir.exists() {
+        let _ = std::fs::remove_dir_all(into_dir);
+    }
+
+    let args = shallow_clone_args(repository_url, into_dir, options);
+    let envs = shallow_clone_envs(options);
+    let mut cmd = QoveryCommand::new(
+        "git",
+        &args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>(),
+        &envs.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect::<Vec<_>>(),
+    );
+    cmd.exec()
+        .map_err(|err| Error::from_str(&format!("Unable to shallow clone repository {}: {}", repository_url, err)))?;
+
+    Repository::open(into_dir)
+}
+
+// Try to position the shallow clone at the requested commit, if the commit is not part of the fetched history
+// (or the shallow clone failed) fallback to a full clone
+fn clone_at_commit_with_fallback(
+    commit_id: &str,
+    shallow_clone: impl FnOnce() -> Result<Repository, Error>,
+    full_clone: impl FnOnce() -> Result<Repository, Error>,
+) -> Result<Repository, Error> {
+    let shallow_repo = shallow_clone().and_then(|repo| {
+        let _ = checkout(&repo, commit_id)?;
+        Ok(repo)
+    });
+
+    match shallow_repo {
+        Ok(repo) => Ok(repo),
+        Err(err) => {
+            info!(
+                "shallow clone cannot resolve commit {}, falling back to a full clone: {}",
+                commit_id, err
+            );
+            let repo = full_clone()?;
+            let _ = checkout(&repo, commit_id)?;
+            Ok(repo)
+        }
+    }
+}
+
+pub fn shallow_clone_at_commit<P>(
+    repository_url: &Url,
+    commit_id: &str,
+    into_dir: P,
+    get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
+    options: &ShallowCloneOptions,
+) -> Result<Repository, Error>
+where
+    P: AsRef<Path>,
+{
+    let repo = clone_at_commit_with_fallback(
+        commit_id,
+        || shallow_clone(repository_url, into_dir.as_ref(), options),
+        || clone(repository_url, into_dir.as_ref(), get_credentials),
+    )?;
+
+    update_submodules(&repo, get_credentials)?;
+
+    Ok(repo)
+}
+
+fn update_submodules(
+    repo: &Repository,
+    get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
+) -> Result<(), Error> {
     // check submodules if needed
     {
         let submodules = repo.submodules()?;
@@ -130,7 +248,7 @@ where
         }
     }
 
-    Ok(repo)
+    Ok(())
 }
 
 pub fn get_parent_commit_id<P>(
@@ -156,8 +274,13 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::git::{checkout, clone, clone_at_commit, get_parent_commit_id};
-    use git2::{Cred, CredentialType};
+    use crate::git::{
+        checkout, clone, clone_at_commit, clone_at_commit_with_fallback, get_parent_commit_id, shallow_clone_args,
+        shallow_clone_envs, ShallowCloneOptions,
+    };
+    use git2::{Cred, CredentialType, Repository, Signature};
+    use std::cell::Cell;
+    use std::path::Path;
     use url::Url;
     use uuid::Uuid;
 
@@ -343,4 +466,91 @@ mod tests {
         );
         assert!(matches!(repo, Ok(_)));
     }
+
+    #[test]
+    fn test_shallow_clone_args() {
+        // setup:
+        let url = Url::parse("https://github.com/Qovery/engine-testing.git").unwrap();
+        let into_dir = Path::new("/tmp/engine_test_shallow");
+        let test_cases = vec![
+            (
+                ShallowCloneOptions {
+                    depth: 1,
+                    single_branch: true,
+                    basic_auth: None,
+                },
+                vec!["clone", "--depth", "1", "--single-branch"],
+            ),
+            (
+                ShallowCloneOptions {
+                    depth: 50,
+                    single_branch: false,
+                    basic_auth: Some(("login", "password")),
+                },
+                vec!["clone", "--depth", "50", "--no-single-branch"],
+            ),
+        ];
+
+        for (options, expected_flags) in test_cases {
+            // execute:
+            let args = shallow_clone_args(&url, into_dir, &options);
+            let envs = shallow_clone_envs(&options);
+
+            // verify:
+            let mut expected_args: Vec<String> = expected_flags.iter().map(|arg| arg.to_string()).collect();
+            expected_args.push("https://github.com/Qovery/engine-testing.git".to_string());
+            expected_args.push("/tmp/engine_test_shallow".to_string());
+            assert_eq!(expected_args, args);
+            assert!(!args.iter().any(|arg| arg.contains("password")));
+            assert_eq!(
+                options.basic_auth.is_some(),
+                envs.contains(&(
+                    "GIT_CONFIG_VALUE_0".to_string(),
+                    format!("Authorization: Basic {}", base64::encode("login:password"))
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_shallow_clone_falls_back_to_full_clone() {
+        // setup:
+        let full_repo_dir = DirectoryForTests::new_with_random_suffix("/tmp/engine_test_full_repo".to_string());
+        let shallow_repo_dir = DirectoryForTests::new_with_random_suffix("/tmp/engine_test_shallow_repo".to_string());
+        let commit_id = {
+            let repo = Repository::init(full_repo_dir.path()).unwrap();
+            let signature = Signature::now("qovery", "test@qovery.com").unwrap();
+            let tree_id = repo.index().unwrap().write_tree().unwrap();
+            let tree = repo.find_tree(tree_id).unwrap();
+            repo.commit(Some("HEAD"), &signature, &signature, "initial commit", &tree, &[])
+                .unwrap()
+                .to_string()
+        };
+        // shallow clone does not contain the requested commit
+        let _ = Repository::init(shallow_repo_dir.path()).unwrap();
+
+        let test_cases = vec![
+            (shallow_repo_dir.path().to_string(), true),
+            (full_repo_dir.path().to_string(), false),
+        ];
+
+        for (shallow_repo_path, expect_full_clone) in test_cases {
+            let full_clone_called = Cell::new(false);
+
+            // execute:
+            let repo = clone_at_commit_with_fallback(
+                &commit_id,
+                || Repository::open(&shallow_repo_path),
+                || {
+                    full_clone_called.set(true);
+                    Repository::open(full_repo_dir.path())
+                },
+            )
+            .unwrap();
+
+            // verify:
+            assert_eq!(expect_full_clone, full_clone_called.get());
+            assert_eq!(commit_id, repo.head().unwrap().target().unwrap().to_string());
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::build_platform::{Build, Credentials, GitRepository, Image, SshKey};
+use crate::build_platform::{Build, BuildSecret, Credentials, GitRepository, Image, SshKey};
 use crate::cloud_provider::kubernetes::Kind as KubernetesKind;
 use crate::cloud_provider::models::EnvironmentVariable;
 use crate::cloud_provider::{CloudProvider, Kind as CPKind};
@@ -47,6 +47,13 @@ pub enum AdvancedSettingsProbeType {
     Http,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AdvancedSettingsDeploymentStrategy {
+    RollingUpdate,
+    BlueGreen,
+}
+
 pub fn to_environment_variable(env_vars: BTreeMap<String, String>) -> Vec<EnvironmentVariable> {
     env_vars
         .into_iter()
@@ -71,8 +78,24 @@ pub struct ApplicationAdvancedSettings {
     pub deployment_delay_start_time_sec: u32,
     #[serde(alias = "deployment.custom_domain_check_enabled")]
     pub deployment_custom_domain_check_enabled: bool,
+    #[serde(alias = "deployment.strategy")]
+    pub deployment_strategy: AdvancedSettingsDeploymentStrategy,
+    // minimum time given to the application to start before it is considered as failed
+    #[serde(alias = "deployment.startup_grace_period_sec")]
+    pub deployment_startup_grace_period_sec: u32,
     #[serde(alias = "build.timeout_max_sec")]
     pub build_timeout_max_sec: u32,
+    #[serde(alias = "build.arguments_file")]
+    pub build_arguments_file: Option<String>,
+    #[serde(alias = "build.sensitive_arguments")]
+    pub build_sensitive_arguments: Vec<String>,
+    // environment variables mounted as BuildKit secrets, with their name as id, instead of being build args
+    #[serde(alias = "build.secrets")]
+    pub build_secrets: Vec<String>,
+    #[serde(alias = "build.git_clone_depth")]
+    pub build_git_clone_depth: Option<u32>,
+    #[serde(alias = "build.git_single_branch")]
+    pub build_git_single_branch: bool,
     #[serde(alias = "network.ingress.proxy_body_size_mb")]
     pub network_ingress_proxy_body_size_mb: u32,
     #[serde(alias = "network.ingress.cors_enable")]
@@ -129,6 +152,13 @@ pub struct ApplicationAdvancedSettings {
     pub liveness_probe_failure_threshold: u32,
     #[serde(alias = "hpa.cpu.average_utilization_percent")]
     pub hpa_cpu_average_utilization_percent: i8,
+    #[serde(alias = "hpa.scale_to_zero.enabled")]
+    pub hpa_scale_to_zero_enabled: bool,
+    #[serde(alias = "hpa.scale_to_zero.idle_period_seconds")]
+    pub hpa_scale_to_zero_idle_period_seconds: u32,
+    // fail the deployment instead of running min_instances replicas when the HPA metrics API is unavailable
+    #[serde(alias = "hpa.required")]
+    pub hpa_required: bool,
 }
 
 impl Default for ApplicationAdvancedSettings {
@@ -136,7 +166,14 @@ impl Default for ApplicationAdvancedSettings {
         ApplicationAdvancedSettings {
             deployment_delay_start_time_sec: 30,
             build_timeout_max_sec: 30 * 60, // 30min
+            build_arguments_file: None,
+            build_sensitive_arguments: vec![],
+            build_secrets: vec![],
+            build_git_clone_depth: None,
+            build_git_single_branch: false,
             deployment_custom_domain_check_enabled: true,
+            deployment_strategy: AdvancedSettingsDeploymentStrategy::RollingUpdate,
+            deployment_startup_grace_period_sec: 60 * 10, // 10min
             network_ingress_proxy_body_size_mb: 100,
             network_ingress_cors_enable: false,
             network_ingress_cors_allow_origin: "*".to_string(),
@@ -165,6 +202,9 @@ impl Default for ApplicationAdvancedSettings {
             liveness_probe_success_threshold: 1,
             liveness_probe_failure_threshold: 9,
             hpa_cpu_average_utilization_percent: 60,
+            hpa_scale_to_zero_enabled: false,
+            hpa_scale_to_zero_idle_period_seconds: 15 * 60, // 15min
+            hpa_required: false,
         }
     }
 }
@@ -304,11 +344,17 @@ impl Application {
             registry_name: cr_info.registry_name.clone(),
             registry_url: cr_info.endpoint.clone(),
             registry_docker_json_config: cr_info.registry_docker_json_config.clone(),
+            registry_client_certificate: cr_info.registry_client_certificate.clone(),
             repository_name: (cr_info.get_repository_name)(&self.name),
         }
     }
 
-    pub fn to_build(&self, registry_url: &ContainerRegistryInfo) -> Build {
+    /// Build secrets files are written into `build_secrets_dir` for the duration of the docker build only.
+    pub fn to_build(
+        &self,
+        registry_url: &ContainerRegistryInfo,
+        build_secrets_dir: &Path,
+    ) -> Result<Build, ApplicationError> {
         // Retrieve ssh keys from env variables
         const ENV_GIT_PREFIX: &str = "GIT_SSH_KEY";
         let env_ssh_keys: Vec<(String, String)> = self
@@ -368,6 +414,14 @@ impl Application {
             }
         });
 
+        let build_args_file = self.advanced_settings.build_arguments_file.as_ref().map(|path| {
+            if Path::new(&path).is_absolute() {
+                root_path.join(path.trim_start_matches('/'))
+            } else {
+                root_path.join(path)
+            }
+        });
+
         //FIXME: Return a result the function
         let url = Url::parse(&self.git_url).unwrap_or_else(|_| Url::parse("https://invalid-git-url.com").unwrap());
 
@@ -384,6 +438,8 @@ impl Application {
                 dockerfile_path,
                 root_path,
                 buildpack_language: self.buildpack_language.clone(),
+                clone_depth: self.advanced_settings.build_git_clone_depth,
+                single_branch: self.advanced_settings.build_git_single_branch,
             },
             image: self.to_image(registry_url),
             environment_variables: self
@@ -396,19 +452,76 @@ impl Application {
                         disable_build_cache = true;
                         return None;
                     }
+                    // mounted as a secret, it must not end up in the image layers
+                    if self.advanced_settings.build_secrets.contains(k) {
+                        return None;
+                    }
 
                     Some((k.clone(), v))
                 })
                 .collect::<BTreeMap<_, _>>(),
             disable_cache: disable_build_cache,
             timeout: Duration::from_secs(self.advanced_settings.build_timeout_max_sec as u64),
+            build_args_file,
+            sensitive_build_args: self
+                .advanced_settings
+                .build_sensitive_arguments
+                .iter()
+                .cloned()
+                .collect(),
+            build_secrets: self.build_secrets(build_secrets_dir)?,
         };
 
         build.compute_image_tag();
-        build
+        Ok(build)
+    }
+}
+
+impl Application {
+    fn build_secrets(&self, build_secrets_dir: &Path) -> Result<Vec<BuildSecret>, ApplicationError> {
+        let mut build_secrets = Vec::with_capacity(self.advanced_settings.build_secrets.len());
+        for name in &self.advanced_settings.build_secrets {
+            // the name is used as file name, it must not be able to escape the build secrets directory
+            if !is_valid_build_secret_name(name) {
+                return Err(ApplicationError::InvalidConfig(format!(
+                    "build secret `{}` is not a valid environment variable name",
+                    name
+                )));
+            }
+
+            let value = match self.environment_vars.get(name).map(base64::decode) {
+                Some(Ok(value)) => value,
+                Some(Err(_)) => {
+                    return Err(ApplicationError::InvalidConfig(format!(
+                        "build secret `{}` is not a valid base64 environment variable",
+                        name
+                    )))
+                }
+                None => {
+                    return Err(ApplicationError::InvalidConfig(format!(
+                        "build secret `{}` is not an environment variable of the application",
+                        name
+                    )))
+                }
+            };
+
+            build_secrets.push(BuildSecret {
+                id: name.clone(),
+                value,
+                path: build_secrets_dir.join(name),
+            });
+        }
+
+        Ok(build_secrets)
     }
 }
 
+fn is_valid_build_secret_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub struct Storage {
     pub id: String,
@@ -488,3 +601,122 @@ impl Storage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::container_registry::ContainerRegistryInfo;
+    use crate::io_models::application::{Application, ApplicationAdvancedSettings};
+    use crate::io_models::Action;
+    use crate::models::application::ApplicationError;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::tempdir;
+    use url::Url;
+    use uuid::Uuid;
+
+    fn application(environment_vars: BTreeMap<String, String>, build_secrets: Vec<&str>) -> Application {
+        Application {
+            long_id: Uuid::new_v4(),
+            name: "app".to_string(),
+            action: Action::Create,
+            git_url: "https://github.com/Qovery/engine.git".to_string(),
+            git_credentials: None,
+            branch: "main".to_string(),
+            commit_id: "3fdaf6b".to_string(),
+            dockerfile_path: Some("Dockerfile".to_string()),
+            buildpack_language: None,
+            root_path: "/".to_string(),
+            ports: vec![],
+            total_cpus: "1".to_string(),
+            cpu_burst: "1".to_string(),
+            total_ram_in_mib: 256,
+            min_instances: 1,
+            max_instances: 1,
+            storage: vec![],
+            environment_vars,
+            advanced_settings: ApplicationAdvancedSettings {
+                build_secrets: build_secrets.into_iter().map(str::to_string).collect(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn registry_info() -> ContainerRegistryInfo {
+        ContainerRegistryInfo {
+            endpoint: Url::parse("https://registry.mycompany.com").unwrap(),
+            registry_name: "registry".to_string(),
+            registry_docker_json_config: None,
+            registry_client_certificate: None,
+            get_image_name: Box::new(|name| name.to_string()),
+            get_repository_name: Box::new(|name| name.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_to_build_maps_build_secrets() {
+        // setup:
+        let build_secrets_dir = tempdir().expect("cannot create temp dir");
+        let application = application(
+            BTreeMap::from([
+                ("NPM_TOKEN".to_string(), base64::encode("npm-s3cr3t")),
+                ("NODE_ENV".to_string(), base64::encode("production")),
+            ]),
+            vec!["NPM_TOKEN"],
+        );
+
+        // execute:
+        let build = application
+            .to_build(&registry_info(), build_secrets_dir.path())
+            .expect("build secrets should be valid");
+
+        // verify:
+        assert_eq!(1, build.build_secrets.len());
+        assert_eq!("NPM_TOKEN", build.build_secrets[0].id);
+        assert_eq!(b"npm-s3cr3t".to_vec(), build.build_secrets[0].value);
+        assert_eq!(build_secrets_dir.path().join("NPM_TOKEN"), build.build_secrets[0].path);
+        // secrets are only written to disk right before the docker build
+        assert_eq!(0, fs::read_dir(build_secrets_dir.path()).unwrap().count());
+        // secrets are not passed as build args
+        assert_eq!(
+            vec!["NODE_ENV"],
+            build
+                .environment_variables
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_to_build_rejects_invalid_build_secrets() {
+        // setup:
+        let build_secrets_dir = tempdir().expect("cannot create temp dir");
+        let environment_vars = BTreeMap::from([
+            ("INVALID".to_string(), "not base64 !".to_string()),
+            ("../../escape".to_string(), base64::encode("s3cr3t")),
+        ]);
+
+        for (build_secret, expected_error) in [
+            ("INVALID", "build secret `INVALID` is not a valid base64 environment variable"),
+            (
+                "MISSING",
+                "build secret `MISSING` is not an environment variable of the application",
+            ),
+            (
+                "../../escape",
+                "build secret `../../escape` is not a valid environment variable name",
+            ),
+        ] {
+            // execute:
+            let result = application(environment_vars.clone(), vec![build_secret])
+                .to_build(&registry_info(), build_secrets_dir.path());
+
+            // verify:
+            match result {
+                Err(ApplicationError::InvalidConfig(err)) => assert_eq!(expected_error, err),
+                _ => panic!("build secret {} must be rejected", build_secret),
+            }
+        }
+        assert_eq!(0, fs::read_dir(build_secrets_dir.path()).unwrap().count());
+    }
+}
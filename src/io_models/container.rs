@@ -2,6 +2,7 @@ use crate::cloud_provider::kubernetes::Kind as KubernetesKind;
 use crate::cloud_provider::{CloudProvider, Kind as CPKind};
 use crate::container_registry::ecr::ECR;
 use crate::container_registry::ContainerRegistry;
+use crate::errors::REDACTED_SECRET;
 use crate::io_models::application::{to_environment_variable, AdvancedSettingsProbeType, Port, Storage};
 use crate::io_models::context::Context;
 use crate::io_models::Action;
@@ -17,6 +18,7 @@ use rusoto_credential::StaticProvider;
 use rusoto_ecr::EcrClient;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
 use url::Url;
 use uuid::Uuid;
@@ -27,6 +29,22 @@ pub struct Credentials {
     pub password: String,
 }
 
+/// PEM encoded client certificate and key, for registries requiring mutual TLS
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct ClientCertificate {
+    pub certificate: String,
+    pub private_key: String,
+}
+
+impl Debug for ClientCertificate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientCertificate")
+            .field("certificate", &REDACTED_SECRET)
+            .field("private_key", &REDACTED_SECRET)
+            .finish()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub enum Registry {
     DockerHub {
@@ -62,6 +80,15 @@ pub enum Registry {
         long_id: Uuid,
         url: Url,
     },
+
+    // Self hosted registry
+    GenericCr {
+        long_id: Uuid,
+        url: Url,
+        credentials: Option<Credentials>,
+        #[serde(default)]
+        client_certificate: Option<ClientCertificate>,
+    },
 }
 
 impl Registry {
@@ -72,6 +99,7 @@ impl Registry {
             Registry::ScalewayCr { url, .. } => url,
             Registry::PrivateEcr { url, .. } => url,
             Registry::PublicEcr { url, .. } => url,
+            Registry::GenericCr { url, .. } => url,
         }
     }
 
@@ -85,6 +113,7 @@ impl Registry {
             Registry::ScalewayCr { ref mut url, .. } => *url = new_url,
             Registry::PrivateEcr { ref mut url, .. } => *url = new_url,
             Registry::PublicEcr { ref mut url, .. } => *url = new_url,
+            Registry::GenericCr { ref mut url, .. } => *url = new_url,
         }
     }
 
@@ -95,13 +124,21 @@ impl Registry {
             Registry::ScalewayCr { long_id, .. } => long_id,
             Registry::PrivateEcr { long_id, .. } => long_id,
             Registry::PublicEcr { long_id, .. } => long_id,
+            Registry::GenericCr { long_id, .. } => long_id,
+        }
+    }
+
+    pub fn client_certificate(&self) -> Option<&ClientCertificate> {
+        match self {
+            Registry::GenericCr { client_certificate, .. } => client_certificate.as_ref(),
+            _ => None,
         }
     }
 
     // Does some network calls for AWS/ECR
     pub fn get_url_with_credentials(&self) -> Url {
         let url = match self {
-            Registry::DockerHub { url, credentials, .. } => {
+            Registry::DockerHub { url, credentials, .. } | Registry::GenericCr { url, credentials, .. } => {
                 let mut url = url.clone();
                 if let Some(credentials) = credentials {
                     let _ = url.set_username(&credentials.login);
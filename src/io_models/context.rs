@@ -4,6 +4,7 @@ use crate::utilities::to_short_id;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::env;
 use url::Url;
 use uuid::Uuid;
 
@@ -140,6 +141,20 @@ impl Context {
         false
     }
 
+    /// Feature flags from the request, overridden by the ones set in the environment (if any).
+    pub fn feature_flags(&self) -> FeatureFlags {
+        let request_flags = FeatureFlags {
+            log_history_enabled: self.is_feature_enabled(&Features::LogsHistory),
+            metrics_history_enabled: self.is_feature_enabled(&Features::MetricsHistory),
+            disable_pleco: self.disable_pleco(),
+        };
+
+        FeatureFlags::from_env(request_flags).unwrap_or_else(|err| {
+            warn!("Ignoring feature flags set in environment: {}", err);
+            request_flags
+        })
+    }
+
     pub fn get_event_details(&self, transmitter: Transmitter) -> EventDetails {
         EventDetails::clone_changing_transmitter(self.event_details.clone(), transmitter)
     }
@@ -180,6 +195,64 @@ pub enum Features {
     MetricsHistory,
 }
 
+pub const FF_LOG_HISTORY_ENABLED_ENV_VAR: &str = "QOVERY_FF_LOG_HISTORY_ENABLED";
+pub const FF_METRICS_HISTORY_ENABLED_ENV_VAR: &str = "QOVERY_FF_METRICS_HISTORY_ENABLED";
+pub const FF_DISABLE_PLECO_ENV_VAR: &str = "QOVERY_DISABLE_PLECO";
+
+/// Feature flags driving which infrastructure charts are deployed on a cluster.
+/// They are resolved once, either from the request context or from the environment, then passed around as is.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FeatureFlags {
+    pub log_history_enabled: bool,
+    pub metrics_history_enabled: bool,
+    pub disable_pleco: bool,
+}
+
+impl FeatureFlags {
+    /// Reads flags from QOVERY_FF_LOG_HISTORY_ENABLED, QOVERY_FF_METRICS_HISTORY_ENABLED and QOVERY_DISABLE_PLECO,
+    /// unset variables keep their value from `default`.
+    pub fn from_env(default: FeatureFlags) -> Result<Self, String> {
+        Self::from_vars(default, |name| env::var(name).ok())
+    }
+
+    fn from_vars(default: FeatureFlags, var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        Ok(FeatureFlags {
+            log_history_enabled: parse_flag(
+                FF_LOG_HISTORY_ENABLED_ENV_VAR,
+                var(FF_LOG_HISTORY_ENABLED_ENV_VAR),
+                default.log_history_enabled,
+            )?,
+            metrics_history_enabled: parse_flag(
+                FF_METRICS_HISTORY_ENABLED_ENV_VAR,
+                var(FF_METRICS_HISTORY_ENABLED_ENV_VAR),
+                default.metrics_history_enabled,
+            )?,
+            disable_pleco: parse_flag(FF_DISABLE_PLECO_ENV_VAR, var(FF_DISABLE_PLECO_ENV_VAR), default.disable_pleco)?,
+        })
+    }
+
+    /// Grafana is shared by logs and metrics history, it is deployed as soon as one of them is enabled.
+    pub fn observability_enabled(&self) -> bool {
+        self.log_history_enabled || self.metrics_history_enabled
+    }
+}
+
+fn parse_flag(name: &str, value: Option<String>, default: bool) -> Result<bool, String> {
+    let value = match value {
+        Some(v) if !v.trim().is_empty() => v,
+        _ => return Ok(default),
+    };
+
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(format!(
+            "Invalid value `{}` for feature flag `{}`, expected one of: true, false, 1, 0",
+            value, name
+        )),
+    }
+}
+
 // trait used to reimplement clone without same fields
 // this trait is used for Context struct
 pub trait CloneForTest {
@@ -203,7 +276,87 @@ impl CloneForTest for Context {
 
 #[cfg(test)]
 mod tests {
-    use crate::io_models::context::Metadata;
+    use crate::io_models::context::{
+        FeatureFlags, Metadata, FF_DISABLE_PLECO_ENV_VAR, FF_LOG_HISTORY_ENABLED_ENV_VAR,
+        FF_METRICS_HISTORY_ENABLED_ENV_VAR,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_feature_flags_from_vars() {
+        // setup:
+        struct TestCase<'a> {
+            default: FeatureFlags,
+            vars: Vec<(&'a str, &'a str)>,
+            expected: Result<FeatureFlags, ()>,
+        }
+
+        let request_flags = FeatureFlags {
+            log_history_enabled: true,
+            metrics_history_enabled: true,
+            disable_pleco: true,
+        };
+        let test_cases = vec![
+            TestCase {
+                default: FeatureFlags::default(),
+                vars: vec![],
+                expected: Ok(FeatureFlags::default()),
+            },
+            TestCase {
+                default: request_flags,
+                vars: vec![],
+                expected: Ok(request_flags),
+            },
+            TestCase {
+                default: request_flags,
+                vars: vec![(FF_LOG_HISTORY_ENABLED_ENV_VAR, "false")],
+                expected: Ok(FeatureFlags {
+                    log_history_enabled: false,
+                    ..request_flags
+                }),
+            },
+            TestCase {
+                default: FeatureFlags::default(),
+                vars: vec![
+                    (FF_LOG_HISTORY_ENABLED_ENV_VAR, "true"),
+                    (FF_METRICS_HISTORY_ENABLED_ENV_VAR, "0"),
+                    (FF_DISABLE_PLECO_ENV_VAR, " FALSE "),
+                ],
+                expected: Ok(FeatureFlags {
+                    log_history_enabled: true,
+                    metrics_history_enabled: false,
+                    disable_pleco: false,
+                }),
+            },
+            TestCase {
+                default: FeatureFlags::default(),
+                vars: vec![
+                    (FF_METRICS_HISTORY_ENABLED_ENV_VAR, "1"),
+                    (FF_DISABLE_PLECO_ENV_VAR, ""),
+                ],
+                expected: Ok(FeatureFlags {
+                    log_history_enabled: false,
+                    metrics_history_enabled: true,
+                    disable_pleco: false,
+                }),
+            },
+            TestCase {
+                default: FeatureFlags::default(),
+                vars: vec![(FF_LOG_HISTORY_ENABLED_ENV_VAR, "yes")],
+                expected: Err(()),
+            },
+        ];
+
+        for tc in test_cases {
+            let vars: HashMap<&str, &str> = tc.vars.into_iter().collect();
+
+            // execute:
+            let result = FeatureFlags::from_vars(tc.default, |name| vars.get(name).map(|v| v.to_string()));
+
+            // verify:
+            assert_eq!(tc.expected, result.map_err(|_| ()), "vars: {:?}", vars);
+        }
+    }
 
     #[test]
     /// Preventing empty / partially empty metadata input from triggering a deserialization error
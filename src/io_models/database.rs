@@ -4,12 +4,18 @@ use crate::io_models::context::Context;
 use crate::io_models::Action;
 use crate::models;
 use crate::models::database::{Container, DatabaseError, DatabaseService, Managed, MongoDB, MySQL, PostgresSQL, Redis};
+use crate::models::database_utils::{
+    get_supported_version_to_use, self_hosted_mongodb_versions, self_hosted_mysql_versions,
+    self_hosted_postgres_versions, self_hosted_redis_versions, supported_versions_summary,
+};
 use crate::models::types::CloudProvider as CloudProviderTrait;
 use crate::models::types::{AWSEc2, VersionsNumber, AWS, DO, SCW};
+use crate::models::{aws, scaleway};
 use chrono::{DateTime, Utc};
 use core::result::Result;
 use core::result::Result::{Err, Ok};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::str::FromStr;
 use uuid::Uuid;
 
@@ -44,14 +50,44 @@ pub struct Database {
     pub activate_backups: bool,
     pub publicly_accessible: bool,
     pub mode: DatabaseMode,
+    #[serde(default)] // => no pooler if not present in input
+    pub connection_pooler: Option<ConnectionPooler>,
 }
 
 impl Database {
+    /// Connection pooler (PgBouncer) can only be put in front of managed PostgreSQL databases.
+    fn validate_connection_pooler(&self) -> Result<(), DatabaseError> {
+        match (&self.connection_pooler, &self.kind, &self.mode) {
+            (None, _, _) => Ok(()),
+            (Some(pooler), DatabaseKind::Postgresql, DatabaseMode::MANAGED) => {
+                match CONNECTION_POOLER_POOL_MODES.contains(&pooler.pool_mode.as_str()) {
+                    true => Ok(()),
+                    false => Err(DatabaseError::InvalidConfig(format!(
+                        "Invalid connection pooler pool mode `{}`, expected one of: {}",
+                        pooler.pool_mode,
+                        CONNECTION_POOLER_POOL_MODES.join(", ")
+                    ))),
+                }
+            }
+            (Some(_), _, _) => Err(DatabaseError::InvalidConfig(format!(
+                "Connection pooler is only available for managed PostgreSQL databases, not for {} {}",
+                match self.mode {
+                    DatabaseMode::MANAGED => "managed",
+                    DatabaseMode::CONTAINER => "container",
+                },
+                self.kind.name(),
+            ))),
+        }
+    }
+
     pub fn to_database_domain(
         &self,
         context: &Context,
         cloud_provider: &dyn CloudProvider,
     ) -> Result<Box<dyn DatabaseService>, DatabaseError> {
+        self.validate_connection_pooler()?;
+        validate_database_version(&cloud_provider.kind(), &self.kind, &self.mode, &self.version)?;
+
         let database_options = DatabaseOptions {
             mode: self.mode.clone(),
             login: self.username.clone(),
@@ -64,6 +100,7 @@ impl Database {
             activate_high_availability: self.activate_high_availability,
             activate_backups: self.activate_backups,
             publicly_accessible: self.publicly_accessible,
+            connection_pooler: self.connection_pooler.clone(),
         };
 
         let version = VersionsNumber::from_str(self.version.as_str())
@@ -666,6 +703,49 @@ impl DatabaseKind {
     }
 }
 
+/// Checks upfront that the requested version can be deployed, instead of failing in the middle of the deployment.
+/// Managed databases are checked against the versions offered by the cloud provider,
+/// container ones against the versions supported by the bundled charts.
+pub fn validate_database_version(
+    provider: &CPKind,
+    kind: &DatabaseKind,
+    mode: &DatabaseMode,
+    version: &str,
+) -> Result<(), DatabaseError> {
+    let supported_versions: HashMap<String, String> = match (mode, provider, kind) {
+        (DatabaseMode::CONTAINER, _, DatabaseKind::Postgresql) => self_hosted_postgres_versions(),
+        (DatabaseMode::CONTAINER, _, DatabaseKind::Mysql) => self_hosted_mysql_versions(),
+        (DatabaseMode::CONTAINER, _, DatabaseKind::Mongodb) => self_hosted_mongodb_versions(),
+        (DatabaseMode::CONTAINER, _, DatabaseKind::Redis) => self_hosted_redis_versions(),
+        (DatabaseMode::MANAGED, CPKind::Aws, DatabaseKind::Postgresql) => {
+            aws::database_utils::managed_postgres_versions()
+        }
+        (DatabaseMode::MANAGED, CPKind::Aws, DatabaseKind::Mysql) => aws::database_utils::managed_mysql_versions(),
+        (DatabaseMode::MANAGED, CPKind::Aws, DatabaseKind::Mongodb) => aws::database_utils::managed_mongodb_versions(),
+        (DatabaseMode::MANAGED, CPKind::Aws, DatabaseKind::Redis) => aws::database_utils::managed_redis_versions(),
+        (DatabaseMode::MANAGED, CPKind::Scw, DatabaseKind::Postgresql) => {
+            scaleway::database_utils::managed_postgres_versions()
+        }
+        (DatabaseMode::MANAGED, CPKind::Scw, DatabaseKind::Mysql) => scaleway::database_utils::managed_mysql_versions(),
+        // provider doesn't offer this managed database at all, this is reported when building the database
+        (DatabaseMode::MANAGED, _, _) => return Ok(()),
+    };
+
+    let database_name = match mode {
+        DatabaseMode::MANAGED => format!("Managed {} on {}", kind.name(), provider),
+        DatabaseMode::CONTAINER => format!("Container {}", kind.name()),
+    };
+
+    match get_supported_version_to_use(&database_name, supported_versions.clone(), version.to_string()) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(DatabaseError::UnsupportedVersion(
+            database_name,
+            version.to_string(),
+            supported_versions_summary(&supported_versions),
+        )),
+    }
+}
+
 #[derive(Eq, PartialEq)]
 pub struct DatabaseOptions {
     pub login: String,
@@ -679,4 +759,180 @@ pub struct DatabaseOptions {
     pub activate_high_availability: bool,
     pub activate_backups: bool,
     pub publicly_accessible: bool,
+    pub connection_pooler: Option<ConnectionPooler>,
+}
+
+/// Pool modes supported by PgBouncer
+pub const CONNECTION_POOLER_POOL_MODES: [&str; 3] = ["session", "transaction", "statement"];
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+#[serde(default)]
+pub struct ConnectionPooler {
+    pub pool_mode: String,
+    pub default_pool_size: u32,
+    pub max_client_connections: u32,
+}
+
+impl Default for ConnectionPooler {
+    fn default() -> Self {
+        ConnectionPooler {
+            pool_mode: "transaction".to_string(),
+            default_pool_size: 20,
+            max_client_connections: 1000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::Kind as CPKind;
+    use crate::io_models::database::{
+        validate_database_version, ConnectionPooler, Database, DatabaseKind, DatabaseMode,
+    };
+    use crate::io_models::Action;
+    use crate::models::database::DatabaseError;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn database(kind: DatabaseKind, mode: DatabaseMode, connection_pooler: Option<ConnectionPooler>) -> Database {
+        Database {
+            kind,
+            action: Action::Create,
+            long_id: Uuid::new_v4(),
+            name: "my-db".to_string(),
+            version: "13".to_string(),
+            created_at: Utc::now(),
+            fqdn_id: "zabcd1234".to_string(),
+            fqdn: "zabcd1234.internal".to_string(),
+            port: 5432,
+            username: "superuser".to_string(),
+            password: "password".to_string(),
+            total_cpus: "1".to_string(),
+            total_ram_in_mib: 512,
+            disk_size_in_gib: 10,
+            database_instance_type: "db.t3.micro".to_string(),
+            database_disk_type: "gp2".to_string(),
+            encrypt_disk: true,
+            activate_high_availability: false,
+            activate_backups: false,
+            publicly_accessible: false,
+            mode,
+            connection_pooler,
+        }
+    }
+
+    #[test]
+    fn test_connection_pooler_is_only_allowed_for_managed_postgresql() {
+        // setup:
+        let pooler = Some(ConnectionPooler::default());
+        let test_cases = vec![
+            (database(DatabaseKind::Postgresql, DatabaseMode::MANAGED, pooler.clone()), true),
+            (database(DatabaseKind::Postgresql, DatabaseMode::MANAGED, None), true),
+            (database(DatabaseKind::Mysql, DatabaseMode::MANAGED, None), true),
+            (
+                database(DatabaseKind::Postgresql, DatabaseMode::CONTAINER, pooler.clone()),
+                false,
+            ),
+            (database(DatabaseKind::Mysql, DatabaseMode::MANAGED, pooler.clone()), false),
+            (database(DatabaseKind::Redis, DatabaseMode::MANAGED, pooler.clone()), false),
+            (database(DatabaseKind::Mongodb, DatabaseMode::CONTAINER, pooler), false),
+        ];
+
+        for (db, expected_valid) in test_cases {
+            // execute:
+            let result = db.validate_connection_pooler();
+
+            // verify:
+            assert_eq!(expected_valid, result.is_ok(), "kind: {}", db.kind.name());
+        }
+    }
+
+    #[test]
+    fn test_connection_pooler_pool_mode_validation() {
+        // setup:
+        let test_cases = vec![
+            ("session", true),
+            ("transaction", true),
+            ("statement", true),
+            ("Transaction", false),
+            ("", false),
+            ("transaction\nauth_type = trust", false),
+        ];
+
+        for (pool_mode, expected_valid) in test_cases {
+            let pooler = ConnectionPooler {
+                pool_mode: pool_mode.to_string(),
+                ..Default::default()
+            };
+            let db = database(DatabaseKind::Postgresql, DatabaseMode::MANAGED, Some(pooler));
+
+            // execute:
+            let result = db.validate_connection_pooler();
+
+            // verify:
+            assert_eq!(expected_valid, result.is_ok(), "pool_mode: {}", pool_mode);
+        }
+    }
+
+    #[test]
+    fn test_connection_pooler_defaults() {
+        // execute:
+        let pooler: ConnectionPooler = serde_json::from_str(r#"{"pool_mode": "session"}"#).unwrap();
+
+        // verify:
+        assert_eq!(
+            ConnectionPooler {
+                pool_mode: "session".to_string(),
+                default_pool_size: 20,
+                max_client_connections: 1000,
+            },
+            pooler
+        );
+    }
+
+    #[test]
+    fn test_validate_database_version() {
+        // setup:
+        let test_cases = vec![
+            (CPKind::Aws, DatabaseKind::Postgresql, DatabaseMode::MANAGED, "13.5", true),
+            (CPKind::Aws, DatabaseKind::Postgresql, DatabaseMode::MANAGED, "9.6", false),
+            (CPKind::Scw, DatabaseKind::Mysql, DatabaseMode::MANAGED, "8", true),
+            (CPKind::Scw, DatabaseKind::Mysql, DatabaseMode::MANAGED, "5.7", false),
+            (CPKind::Do, DatabaseKind::Redis, DatabaseMode::CONTAINER, "6", true),
+            (CPKind::Do, DatabaseKind::Redis, DatabaseMode::CONTAINER, "4", false),
+            (CPKind::Scw, DatabaseKind::Mongodb, DatabaseMode::CONTAINER, "4.4", true),
+            (CPKind::Scw, DatabaseKind::Mongodb, DatabaseMode::CONTAINER, "5.0", false),
+        ];
+
+        for (provider, kind, mode, version, expected_valid) in test_cases {
+            // execute:
+            let result = validate_database_version(&provider, &kind, &mode, version);
+
+            // verify:
+            assert_eq!(
+                expected_valid,
+                result.is_ok(),
+                "{} {} {}: {:?}",
+                provider,
+                kind.name(),
+                version,
+                result.err().map(|e| e.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_database_version_lists_supported_versions() {
+        // execute:
+        let result = validate_database_version(&CPKind::Scw, &DatabaseKind::Mysql, &DatabaseMode::MANAGED, "5.7");
+
+        // verify:
+        match result {
+            Err(DatabaseError::UnsupportedVersion(_, version, supported_versions)) => {
+                assert_eq!("5.7", version);
+                assert_eq!("8, 8.0", supported_versions);
+            }
+            _ => panic!("expected an unsupported version error"),
+        }
+    }
 }
@@ -66,19 +66,12 @@ impl<T> EngineRequest<T> {
         logger: Box<dyn Logger>,
     ) -> Result<InfrastructureContext, IoEngineError> {
         let build_platform = self.build_platform.to_engine_build_platform(context);
-        let cloud_provider = self
-            .cloud_provider
-            .to_engine_cloud_provider(context.clone(), &self.kubernetes.region, self.kubernetes.kind.clone())
-            .ok_or_else(|| {
-                IoEngineError::new_error_on_cloud_provider_information(
-                    event_details.clone(),
-                    CommandError::new(
-                        "Invalid cloud provider information".to_string(),
-                        Some(format!("Invalid cloud provider information: {:?}", self.cloud_provider)),
-                        None,
-                    ),
-                )
-            })?;
+        let cloud_provider = self.cloud_provider.to_engine_cloud_provider(
+            context.clone(),
+            &self.kubernetes.region,
+            self.kubernetes.kind.clone(),
+            event_details.clone(),
+        )?;
         let cloud_provider = Arc::new(cloud_provider);
 
         let mut tags = self
@@ -158,7 +151,7 @@ impl InfrastructureEngineRequest {
     pub fn event_details(&self) -> EventDetails {
         let kubernetes = &self.kubernetes;
         let stage = match self.action {
-            Action::Create => Stage::Infrastructure(InfrastructureStep::Create),
+            Action::Create | Action::Restart => Stage::Infrastructure(InfrastructureStep::Create),
             Action::Pause => Stage::Infrastructure(InfrastructureStep::Pause),
             Action::Delete => Stage::Infrastructure(InfrastructureStep::Delete),
         };
@@ -226,7 +219,18 @@ impl CloudProvider {
         context: Context,
         region: &str,
         cluster_kind: cloud_provider::kubernetes::Kind,
-    ) -> Option<Box<dyn cloud_provider::CloudProvider>> {
+        event_details: EventDetails,
+    ) -> Result<Box<dyn cloud_provider::CloudProvider>, EngineError> {
+        let invalid_information = || {
+            EngineError::new_error_on_cloud_provider_information(
+                event_details.clone(),
+                CommandError::new(
+                    "Invalid cloud provider information".to_string(),
+                    Some(format!("Invalid cloud provider information: {:?}", self)),
+                    None,
+                ),
+            )
+        };
         let terraform_state_credentials = cloud_provider::TerraformStateCredentials {
             access_key_id: self.terraform_state_credentials.access_key_id.clone(),
             secret_access_key: self.terraform_state_credentials.secret_access_key.clone(),
@@ -234,34 +238,62 @@ impl CloudProvider {
         };
 
         match self.kind {
-            cloud_provider::Kind::Aws => Some(Box::new(AWS::new(
+            cloud_provider::Kind::Aws => Ok(Box::new(AWS::new(
                 context,
                 self.long_id,
                 self.name.as_str(),
-                self.options.access_key_id.as_ref()?.as_str(),
-                self.options.secret_access_key.as_ref()?.as_str(),
+                self.options
+                    .access_key_id
+                    .as_ref()
+                    .ok_or_else(invalid_information)?
+                    .as_str(),
+                self.options
+                    .secret_access_key
+                    .as_ref()
+                    .ok_or_else(invalid_information)?
+                    .as_str(),
                 region,
                 self.zones.clone(),
                 cluster_kind,
                 terraform_state_credentials,
             ))),
-            cloud_provider::Kind::Do => Some(Box::new(DO::new(
+            cloud_provider::Kind::Do => Ok(Box::new(DO::new(
                 context,
                 self.long_id,
-                self.options.token.as_ref()?.as_str(),
-                self.options.spaces_access_id.as_ref()?.as_str(),
-                self.options.spaces_secret_key.as_ref()?.as_str(),
+                self.options.token.as_ref().ok_or_else(invalid_information)?.as_str(),
+                self.options
+                    .spaces_access_id
+                    .as_ref()
+                    .ok_or_else(invalid_information)?
+                    .as_str(),
+                self.options
+                    .spaces_secret_key
+                    .as_ref()
+                    .ok_or_else(invalid_information)?
+                    .as_str(),
                 region,
                 self.name.as_str(),
                 terraform_state_credentials,
-            ))),
-            cloud_provider::Kind::Scw => Some(Box::new(Scaleway::new(
+            )?)),
+            cloud_provider::Kind::Scw => Ok(Box::new(Scaleway::new(
                 context,
                 self.long_id,
                 self.name.as_str(),
-                self.options.scaleway_access_key.as_ref()?.as_str(),
-                self.options.scaleway_secret_key.as_ref()?.as_str(),
-                self.options.scaleway_project_id.as_ref()?.as_str(),
+                self.options
+                    .scaleway_access_key
+                    .as_ref()
+                    .ok_or_else(invalid_information)?
+                    .as_str(),
+                self.options
+                    .scaleway_secret_key
+                    .as_ref()
+                    .ok_or_else(invalid_information)?
+                    .as_str(),
+                self.options
+                    .scaleway_project_id
+                    .as_ref()
+                    .ok_or_else(invalid_information)?
+                    .as_str(),
                 region,
                 terraform_state_credentials,
             ))),
@@ -297,6 +329,8 @@ impl Kubernetes {
         dns_provider: Arc<Box<dyn dns_provider::DnsProvider>>,
         logger: Box<dyn Logger>,
     ) -> Result<Box<dyn cloud_provider::kubernetes::Kubernetes + 'a>, EngineError> {
+        self.check_region_and_zones(&**cloud_provider)?;
+
         match self.kind {
             cloud_provider::kubernetes::Kind::Eks => match EKS::new(
                 context.clone(),
@@ -388,6 +422,33 @@ impl Kubernetes {
             }
         }
     }
+
+    /// Rejects regions and zones the cloud provider cannot deploy clusters in before building the cluster.
+    fn check_region_and_zones(&self, cloud_provider: &dyn cloud_provider::CloudProvider) -> Result<(), EngineError> {
+        let event_details =
+            cloud_provider.get_event_details(Stage::Infrastructure(InfrastructureStep::LoadConfiguration));
+
+        match self.kind {
+            cloud_provider::kubernetes::Kind::Eks | cloud_provider::kubernetes::Kind::Ec2 => {
+                cloud_provider.check_region_and_zones(&self.region, cloud_provider.zones(), event_details)?;
+            }
+            cloud_provider::kubernetes::Kind::Doks => {
+                cloud_provider.check_region_and_zones(&self.region, &[], event_details)?;
+            }
+            // Kapsule clusters are requested with a zone, i.e: `fr-par-1`, its region has to be looked up
+            cloud_provider::kubernetes::Kind::ScwKapsule => {
+                let region = cloud_provider
+                    .supported_regions()
+                    .iter()
+                    .find(|region| region.has_zone(&self.region))
+                    .map(|region| region.code.as_str())
+                    .unwrap_or(self.region.as_str());
+                cloud_provider.check_region_and_zones(region, std::slice::from_ref(&self.region), event_details)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -1,6 +1,8 @@
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::CloudProvider;
 use crate::container_registry::ContainerRegistry;
+use crate::errors::EngineError;
+use crate::fs::workspace_directory;
 use crate::io_models::application::Application;
 use crate::io_models::container::Container;
 use crate::io_models::context::Context;
@@ -14,6 +16,7 @@ use crate::models::database::DatabaseError;
 use crate::models::job::JobError;
 use crate::models::router::RouterError;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
@@ -45,6 +48,20 @@ pub enum DomainError {
     JobError(JobError),
 }
 
+impl DomainError {
+    /// Engine error behind the domain error, when the domain could not be built because of the engine itself.
+    pub fn engine_error(&self) -> Option<&EngineError> {
+        match self {
+            DomainError::ApplicationError(ApplicationError::CannotGetWorkspaceDirectory(err))
+            | DomainError::ContainerError(ContainerError::CannotGetWorkspaceDirectory(err))
+            | DomainError::RouterError(RouterError::CannotGetWorkspaceDirectory(err))
+            | DomainError::DatabaseError(DatabaseError::CannotGetWorkspaceDirectory(err))
+            | DomainError::JobError(JobError::CannotGetWorkspaceDirectory(err)) => Some(err),
+            _ => None,
+        }
+    }
+}
+
 impl EnvironmentRequest {
     pub fn to_environment_domain(
         &self,
@@ -54,11 +71,21 @@ impl EnvironmentRequest {
     ) -> Result<Environment, DomainError> {
         let mut applications = Vec::with_capacity(self.applications.len());
         for app in &self.applications {
-            match app.clone().to_application_domain(
-                context,
-                app.to_build(container_registry.registry_info()),
-                cloud_provider,
-            ) {
+            let build_secrets_dir = workspace_directory(
+                context.workspace_root_dir(),
+                context.execution_id(),
+                format!("build-secrets/{}", app.long_id),
+            )
+            .map_err(|err| {
+                DomainError::ApplicationError(ApplicationError::InvalidConfig(format!(
+                    "cannot create build secrets directory: {}",
+                    err
+                )))
+            })?;
+            let build = app
+                .to_build(container_registry.registry_info(), Path::new(&build_secrets_dir))
+                .map_err(DomainError::ApplicationError)?;
+            match app.clone().to_application_domain(context, build, cloud_provider) {
                 Ok(app) => applications.push(app),
                 Err(err) => {
                     return Err(DomainError::ApplicationError(err));
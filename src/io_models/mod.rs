@@ -55,6 +55,7 @@ pub enum Action {
     Create,
     Pause,
     Delete,
+    Restart,
 }
 
 impl Action {
@@ -63,6 +64,32 @@ impl Action {
             Action::Create => service::Action::Create,
             Action::Pause => service::Action::Pause,
             Action::Delete => service::Action::Delete,
+            Action::Restart => service::Action::Restart,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::service;
+    use crate::io_models::Action;
+
+    #[test]
+    fn test_action_to_service_action() {
+        // setup:
+        let test_cases = vec![
+            ("\"CREATE\"", service::Action::Create),
+            ("\"PAUSE\"", service::Action::Pause),
+            ("\"DELETE\"", service::Action::Delete),
+            ("\"RESTART\"", service::Action::Restart),
+        ];
+
+        for (payload, expected) in test_cases {
+            // execute:
+            let action: Action = serde_json::from_str(payload).expect("cannot deserialize action");
+
+            // verify:
+            assert_eq!(expected, action.to_service_action());
         }
     }
 }
@@ -8,6 +8,7 @@ extern crate core;
 extern crate trust_dns_resolver;
 
 pub mod build_platform;
+pub mod clock;
 pub mod cloud_provider;
 pub mod cmd;
 pub mod constants;
@@ -27,10 +28,13 @@ mod kubers_utils;
 pub mod logger;
 pub mod models;
 pub mod object_storage;
+pub mod poller;
 pub mod runtime;
 mod secret_manager;
 mod string;
 mod template;
+#[cfg(test)]
+mod test_utils;
 pub mod transaction;
 mod unit_conversion;
 pub mod utilities;
@@ -33,6 +33,7 @@ impl Logger for StdIoLogger {
         let event_details = event.get_details();
         let stage = event_details.stage();
         let execution_id = event_details.execution_id().to_string();
+        let correlation_id = event_details.correlation_id();
 
         tracing::span!(
             tracing::Level::INFO,
@@ -40,6 +41,7 @@ impl Logger for StdIoLogger {
             organization_id = event_details.organisation_id().short(),
             cluster_id = event_details.cluster_id().short(),
             execution_id = execution_id.as_str(),
+            correlation_id = correlation_id.as_str(),
             provider = match event_details.provider_kind() {
                 Some(kind) => kind.to_string(),
                 None => "".to_string(),
@@ -171,6 +173,7 @@ mod tests {
         for tc in test_cases {
             // execute:
             logger.log(tc.event.clone());
+            let details = tc.event.get_details();
 
             // validate:
             assert!(
@@ -199,8 +202,12 @@ mod tests {
                 "{}",
                 tc.description
             );
+            assert!(
+                logs_contain(format!("correlation_id=\"{}\"", details.correlation_id()).as_str()),
+                "{}",
+                tc.description
+            );
 
-            let details = tc.event.get_details();
             assert!(
                 logs_contain(
                     format!(
@@ -1,12 +1,14 @@
 use crate::build_platform::Build;
-use crate::cloud_provider::environment::Environment;
-use crate::cloud_provider::kubernetes::Kubernetes;
 use crate::cloud_provider::models::{EnvironmentVariable, EnvironmentVariableDataTemplate, Storage};
-use crate::cloud_provider::service::{Action, Service, ServiceType};
+use crate::cloud_provider::service::{Action, HealthCheck, Service, ServiceType};
 use crate::cloud_provider::utilities::sanitize_name;
+use crate::cloud_provider::{service, DeploymentTarget};
 use crate::deployment_action::DeploymentAction;
+use crate::errors::EngineError;
 use crate::events::{EventDetails, Stage, Transmitter};
-use crate::io_models::application::{AdvancedSettingsProbeType, ApplicationAdvancedSettings, Port};
+use crate::io_models::application::{
+    AdvancedSettingsDeploymentStrategy, AdvancedSettingsProbeType, ApplicationAdvancedSettings, Port,
+};
 use crate::io_models::context::Context;
 
 use crate::models::types::{CloudProvider, ToTeraContext};
@@ -20,11 +22,58 @@ use uuid::Uuid;
 pub enum ApplicationError {
     #[error("Application invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("Cannot create workspace directory: {0}")]
+    CannotGetWorkspaceDirectory(Box<EngineError>),
+}
+
+// Prometheus deployed by Qovery on every cluster, scraping nginx ingress metrics
+const SCALE_TO_ZERO_PROMETHEUS_URL: &str = "http://prometheus-operated.prometheus.svc:9090";
+
+/// Scaling to zero replicas is driven by the requests the application receives, so it
+/// cannot be combined with a minimum number of instances nor with a storage (statefulset).
+fn validate_scale_to_zero(
+    min_instances: u32,
+    has_storage: bool,
+    advanced_settings: &ApplicationAdvancedSettings,
+) -> Result<(), ApplicationError> {
+    if !advanced_settings.hpa_scale_to_zero_enabled {
+        return Ok(());
+    }
+
+    if min_instances > 0 {
+        return Err(ApplicationError::InvalidConfig(format!(
+            "hpa.scale_to_zero.enabled requires min_instances to be 0, got {}",
+            min_instances
+        )));
+    }
+
+    if has_storage {
+        return Err(ApplicationError::InvalidConfig(
+            "hpa.scale_to_zero.enabled is not available for applications with storage".to_string(),
+        ));
+    }
+
+    if advanced_settings.hpa_scale_to_zero_idle_period_seconds == 0 {
+        return Err(ApplicationError::InvalidConfig(
+            "hpa.scale_to_zero.idle_period_seconds must be greater than 0".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Requests rate received by the application through the ingress over the idle period.
+fn scale_to_zero_prometheus_query(namespace: &str, service_name: &str, idle_period_seconds: u32) -> String {
+    format!(
+        r#"sum(rate(nginx_ingress_controller_requests{{exported_namespace="{}",exported_service="{}"}}[{}s]))"#,
+        namespace, service_name, idle_period_seconds
+    )
 }
 
 pub struct Application<T: CloudProvider> {
     _marker: PhantomData<T>,
-    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails>,
+    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails + Send + Sync>,
     pub(super) id: String,
     pub(super) long_id: Uuid,
     pub(super) action: Action,
@@ -65,15 +114,12 @@ impl<T: CloudProvider> Application<T> {
         mk_event_details: impl Fn(Transmitter) -> EventDetails,
     ) -> Result<Self, ApplicationError> {
         // TODO: Check that the information provided are coherent
-
-        let workspace_directory = crate::fs::workspace_directory(
-            context.workspace_root_dir(),
-            context.execution_id(),
-            format!("applications/{}", long_id),
-        )
-        .map_err(|_| ApplicationError::InvalidConfig("Can't create workspace directory".to_string()))?;
+        validate_scale_to_zero(min_instances, !storage.is_empty(), &advanced_settings)?;
 
         let event_details = mk_event_details(Transmitter::Application(long_id, name.to_string()));
+        let workspace_directory =
+            service::workspace_directory(context, &ServiceType::Application, &long_id, event_details.clone())
+                .map_err(|err| ApplicationError::CannotGetWorkspaceDirectory(Box::new(err)))?;
         let mk_event_details = move |stage: Stage| EventDetails::clone_changing_stage(event_details.clone(), stage);
         Ok(Self {
             _marker: PhantomData,
@@ -110,11 +156,17 @@ impl<T: CloudProvider> Application<T> {
         format!("{}/{}/charts/q-application", self.lib_root_directory, T::lib_directory_name(),)
     }
 
+    pub fn ports(&self) -> &[Port] {
+        &self.ports
+    }
+
     fn public_ports(&self) -> impl Iterator<Item = &Port> + '_ {
         self.ports.iter().filter(|port| port.publicly_accessible)
     }
 
-    pub(super) fn default_tera_context(&self, kubernetes: &dyn Kubernetes, environment: &Environment) -> TeraContext {
+    pub(super) fn default_tera_context(&self, target: &DeploymentTarget) -> TeraContext {
+        let kubernetes = target.kubernetes;
+        let environment = target.environment;
         let mut context = TeraContext::new();
         context.insert("id", self.id());
         context.insert("long_id", &self.long_id);
@@ -129,7 +181,14 @@ impl<T: CloudProvider> Application<T> {
         context.insert("zone", kubernetes.zone());
         context.insert("name", self.name());
         context.insert("sanitized_name", &self.sanitized_name());
+        // overridden when deploying with the blue/green strategy
+        context.insert("deployment_name", &self.sanitized_name());
+        // deployment scaled by the HPA/ScaledObject, the one receiving the traffic
+        context.insert("autoscaler_target_name", &self.sanitized_name());
+        context.insert("blue_green_color", "");
+        context.insert("blue_green_active_color", "");
         context.insert("namespace", environment.namespace());
+        context.insert("correlation_id", target.correlation_id.as_str());
         context.insert("cluster_name", kubernetes.name());
         context.insert("total_cpus", &self.total_cpus());
         context.insert("total_ram_in_mib", &self.total_ram_in_mib());
@@ -139,6 +198,21 @@ impl<T: CloudProvider> Application<T> {
             "hpa_cpu_average_utilization_percent",
             &self.advanced_settings.hpa_cpu_average_utilization_percent,
         );
+        context.insert("hpa_enabled", &true);
+        context.insert("scale_to_zero_enabled", &self.advanced_settings.hpa_scale_to_zero_enabled);
+        context.insert(
+            "scale_to_zero_idle_period_seconds",
+            &self.advanced_settings.hpa_scale_to_zero_idle_period_seconds,
+        );
+        context.insert("scale_to_zero_prometheus_url", SCALE_TO_ZERO_PROMETHEUS_URL);
+        context.insert(
+            "scale_to_zero_prometheus_query",
+            &scale_to_zero_prometheus_query(
+                environment.namespace(),
+                &self.sanitized_name(),
+                self.advanced_settings.hpa_scale_to_zero_idle_period_seconds,
+            ),
+        );
 
         if let Some(default_port) = self.ports.iter().find(|p| p.is_default) {
             context.insert("is_private_port", &true);
@@ -272,6 +346,27 @@ impl<T: CloudProvider> Application<T> {
         !self.storage.is_empty()
     }
 
+    // Same condition as in horizontal_autoscaler.j2.yaml
+    pub fn is_autoscaled(&self) -> bool {
+        !self.is_stateful()
+            && !self.advanced_settings.hpa_scale_to_zero_enabled
+            && self.min_instances != self.max_instances
+            && self.advanced_settings.hpa_cpu_average_utilization_percent >= 0
+    }
+
+    // A statefulset can't run two versions side by side, and without ports there is no Service to switch,
+    // in both cases the application is rolled out
+    pub fn is_blue_green(&self) -> bool {
+        !self.is_stateful()
+            && !self.ports.is_empty()
+            && self.advanced_settings.deployment_strategy == AdvancedSettingsDeploymentStrategy::BlueGreen
+    }
+
+    // Port used by the probes, same as `private_port` in the chart
+    pub fn default_port(&self) -> Option<u16> {
+        self.ports.iter().find(|p| p.is_default).map(|p| p.port)
+    }
+
     pub fn service_type(&self) -> ServiceType {
         ServiceType::Application
     }
@@ -370,6 +465,14 @@ impl<T: CloudProvider> Service for Application<T> {
         Some(self.selector())
     }
 
+    fn health_check(&self) -> Option<HealthCheck> {
+        HealthCheck::from_readiness_probe(
+            &self.advanced_settings.readiness_probe_type,
+            self.default_port(),
+            &self.advanced_settings.readiness_probe_http_get_path,
+        )
+    }
+
     fn as_service(&self) -> &dyn Service {
         self
     }
@@ -380,17 +483,12 @@ pub trait ApplicationService: Service + DeploymentAction + ToTeraContext {
     fn get_build_mut(&mut self) -> &mut Build;
     fn public_ports(&self) -> Vec<&Port>;
     fn advanced_settings(&self) -> &ApplicationAdvancedSettings;
+    fn min_instances(&self) -> u32;
+    fn startup_grace_period(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.advanced_settings().deployment_startup_grace_period_sec as u64)
+    }
     fn startup_timeout(&self) -> std::time::Duration {
-        let settings = self.advanced_settings();
-        let readiness_probe_timeout = settings.readiness_probe_initial_delay_seconds
-            + ((settings.readiness_probe_timeout_seconds + settings.readiness_probe_period_seconds)
-                * settings.readiness_probe_failure_threshold);
-        let liveness_probe_timeout = settings.liveness_probe_initial_delay_seconds
-            + ((settings.liveness_probe_timeout_seconds + settings.liveness_probe_period_seconds)
-                * settings.liveness_probe_failure_threshold);
-        let probe_timeout = std::cmp::max(readiness_probe_timeout, liveness_probe_timeout);
-        let startup_timeout = std::cmp::max(probe_timeout /* * 10 rolling restart percent */, 60 * 10);
-        std::time::Duration::from_secs(startup_timeout as u64)
+        startup_timeout(self.advanced_settings())
     }
 
     fn as_deployment_action(&self) -> &dyn DeploymentAction;
@@ -416,7 +514,367 @@ where
         &self.advanced_settings
     }
 
+    fn min_instances(&self) -> u32 {
+        self.min_instances
+    }
+
     fn as_deployment_action(&self) -> &dyn DeploymentAction {
         self
     }
 }
+
+// Time given to the application to get ready, probes can't fail before it, and slow-booting apps can get more
+// of it through the startup grace period
+fn startup_timeout(settings: &ApplicationAdvancedSettings) -> std::time::Duration {
+    let readiness_probe_timeout = settings.readiness_probe_initial_delay_seconds
+        + ((settings.readiness_probe_timeout_seconds + settings.readiness_probe_period_seconds)
+            * settings.readiness_probe_failure_threshold);
+    let liveness_probe_timeout = settings.liveness_probe_initial_delay_seconds
+        + ((settings.liveness_probe_timeout_seconds + settings.liveness_probe_period_seconds)
+            * settings.liveness_probe_failure_threshold);
+    let probe_timeout = std::cmp::max(readiness_probe_timeout, liveness_probe_timeout);
+    let startup_timeout = std::cmp::max(
+        probe_timeout, /* * 10 rolling restart percent */
+        settings.deployment_startup_grace_period_sec,
+    );
+    std::time::Duration::from_secs(startup_timeout as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::events::CorrelationId;
+    use crate::io_models::application::ApplicationAdvancedSettings;
+    use crate::io_models::job::JobAdvancedSettings;
+    use crate::models::application::{scale_to_zero_prometheus_query, startup_timeout, validate_scale_to_zero};
+    use crate::models::job::{ClusterTeraContext, JobTeraContext, ServiceTeraContext};
+    use serde_json::json;
+    use std::time::Duration;
+    use tera::{Context as TeraContext, Tera};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_validate_scale_to_zero() {
+        // setup:
+        let enabled = ApplicationAdvancedSettings {
+            hpa_scale_to_zero_enabled: true,
+            ..Default::default()
+        };
+        let test_cases = vec![
+            (0, false, ApplicationAdvancedSettings::default(), true),
+            (2, true, ApplicationAdvancedSettings::default(), true),
+            (0, false, enabled.clone(), true),
+            (1, false, enabled.clone(), false),
+            (0, true, enabled.clone(), false),
+            (
+                0,
+                false,
+                ApplicationAdvancedSettings {
+                    hpa_scale_to_zero_idle_period_seconds: 0,
+                    ..enabled
+                },
+                false,
+            ),
+        ];
+
+        for (min_instances, has_storage, advanced_settings, expected_valid) in test_cases {
+            // execute:
+            let result = validate_scale_to_zero(min_instances, has_storage, &advanced_settings);
+
+            // verify:
+            assert_eq!(
+                expected_valid,
+                result.is_ok(),
+                "min_instances: {}, has_storage: {}",
+                min_instances,
+                has_storage
+            );
+        }
+    }
+
+    #[test]
+    fn test_scaled_object_rendering() {
+        // setup:
+        let template = std::fs::read_to_string(format!(
+            "{}/lib/aws/charts/q-application/templates/scaled_object.j2.yaml",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .expect("cannot read scaled object template");
+        let mut context = TeraContext::new();
+        context.insert("is_storage", &false);
+        context.insert("sanitized_name", "app-zabcd1234");
+        context.insert("autoscaler_target_name", "app-zabcd1234");
+        context.insert("namespace", "z1234-zabcd");
+        context.insert("environment_id", "zabcd");
+        context.insert("id", "zabcd1234");
+        context.insert("long_id", "00000000-0000-0000-0000-000000000000");
+        context.insert("max_instances", &3);
+        context.insert("hpa_cpu_average_utilization_percent", &60);
+        context.insert("scale_to_zero_idle_period_seconds", &600);
+        context.insert("scale_to_zero_prometheus_url", "http://prometheus-operated.prometheus.svc:9090");
+        context.insert(
+            "scale_to_zero_prometheus_query",
+            &scale_to_zero_prometheus_query("z1234-zabcd", "app-zabcd1234", 600),
+        );
+
+        // execute:
+        context.insert("scale_to_zero_enabled", &false);
+        let disabled = Tera::one_off(&template, &context, false).expect("cannot render template");
+        context.insert("scale_to_zero_enabled", &true);
+        let enabled = Tera::one_off(&template, &context, false).expect("cannot render template");
+
+        // verify:
+        assert!(disabled.trim().is_empty());
+        assert!(enabled.contains("kind: ScaledObject"));
+        assert!(enabled.contains("  minReplicaCount: 0\n  maxReplicaCount: 3\n  cooldownPeriod: 600\n"));
+        assert!(enabled.contains(r#"query: 'sum(rate(nginx_ingress_controller_requests{exported_namespace="z1234-zabcd",exported_service="app-zabcd1234"}[600s]))'"#));
+        // helm directives are kept for helm to fail when KEDA is not installed, an HPA would scale the application
+        // back to 1 instance
+        assert!(enabled.contains(r#"{{- if .Capabilities.APIVersions.Has "keda.sh/v1alpha1" }}"#));
+        assert!(enabled.contains(r#"{{- fail "hpa.scale_to_zero.enabled requires KEDA"#));
+        assert!(!enabled.contains("HorizontalPodAutoscaler"));
+    }
+
+    #[test]
+    fn test_deployment_replicas_rendered_without_autoscaler() {
+        // setup:
+        let template = std::fs::read_to_string(format!(
+            "{}/lib/aws/charts/q-application/templates/deployment.j2.yaml",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .expect("cannot read deployment template");
+        let mut context = TeraContext::new();
+        for key in [
+            "correlation_id",
+            "deployment_name",
+            "sanitized_name",
+            "namespace",
+            "id",
+            "long_id",
+            "owner_id",
+            "environment_id",
+            "environment_long_id",
+            "project_long_id",
+            "image_name_with_tag",
+            "version",
+            "cpu_burst",
+            "total_cpus",
+            "total_ram_in_mib",
+        ] {
+            context.insert(key, "value");
+        }
+        for key in [
+            "blue_green_color",
+            "is_storage",
+            "is_registry_secret",
+            "liveness_probe_enabled",
+            "readiness_probe_enabled",
+        ] {
+            context.insert(key, &false);
+        }
+        context.insert("private_port", &8080);
+        context.insert("ports", &json!([]));
+        context.insert("environment_variables", &json!([]));
+        context.insert("hpa_cpu_average_utilization_percent", &60);
+        let test_cases = vec![
+            // (hpa_enabled, scale_to_zero_enabled, min_instances, max_instances, replicas rendered)
+            (true, false, 2, 3, false),
+            (true, true, 2, 3, false),
+            (false, false, 2, 3, true),
+            (true, false, 2, 2, true),
+        ];
+
+        for (hpa_enabled, scale_to_zero_enabled, min_instances, max_instances, expected_replicas) in test_cases {
+            context.insert("hpa_enabled", &hpa_enabled);
+            context.insert("scale_to_zero_enabled", &scale_to_zero_enabled);
+            context.insert("min_instances", &min_instances);
+            context.insert("max_instances", &max_instances);
+
+            // execute:
+            let deployment = Tera::one_off(&template, &context, false).expect("cannot render template");
+
+            // verify:
+            assert_eq!(
+                expected_replicas,
+                deployment.contains("\n  replicas: 2\n"),
+                "hpa: {}, scale to zero: {}, instances: {}-{}",
+                hpa_enabled,
+                scale_to_zero_enabled,
+                min_instances,
+                max_instances
+            );
+        }
+    }
+
+    #[test]
+    fn test_startup_timeout_with_grace_period() {
+        // setup:
+        // with the default probes, a failing application is detected after 165s at most
+        let test_cases = vec![
+            // default grace period, keeping the former 10min floor
+            (ApplicationAdvancedSettings::default(), Duration::from_secs(10 * 60)),
+            // short grace period, probes still get the time to fail
+            (
+                ApplicationAdvancedSettings {
+                    deployment_startup_grace_period_sec: 60,
+                    ..Default::default()
+                },
+                Duration::from_secs(165),
+            ),
+            // long grace period for a slow-booting application
+            (
+                ApplicationAdvancedSettings {
+                    deployment_startup_grace_period_sec: 20 * 60,
+                    ..Default::default()
+                },
+                Duration::from_secs(20 * 60),
+            ),
+            // probes allowing more than the grace period: 900s + (1s + 10s) * 9
+            (
+                ApplicationAdvancedSettings {
+                    readiness_probe_initial_delay_seconds: 900,
+                    ..Default::default()
+                },
+                Duration::from_secs(999),
+            ),
+        ];
+
+        for (advanced_settings, expected_timeout) in test_cases {
+            // execute:
+            let timeout = startup_timeout(&advanced_settings);
+
+            // verify:
+            assert_eq!(
+                expected_timeout, timeout,
+                "grace period: {}s",
+                advanced_settings.deployment_startup_grace_period_sec
+            );
+        }
+    }
+
+    #[test]
+    fn test_correlation_id_in_resource_tags_and_service_charts_labels() {
+        // setup:
+        let correlation_id = CorrelationId::from_execution_id("Execution_ID:42");
+        let render = |path: &str, context: &TeraContext| {
+            let template = std::fs::read_to_string(format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path))
+                .unwrap_or_else(|_| panic!("cannot read template {}", path));
+            Tera::one_off(&template, context, false).unwrap_or_else(|err| panic!("cannot render {}: {:?}", path, err))
+        };
+
+        let mut context = TeraContext::new();
+        context.insert("correlation_id", correlation_id.as_str());
+        context.insert("cluster_name", "qovery-zabcd");
+        context.insert("kubernetes_cluster_id", "zabcd");
+        context.insert("kubernetes_cluster_az_list", &vec!["\"eu-west-3a\""]);
+        context.insert("region", "eu-west-3");
+        context.insert("owner_id", "zowner");
+        context.insert("project_id", "zproject");
+        context.insert("project_long_id", "00000000-0000-0000-0000-000000000001");
+        context.insert("environment_id", "zenv");
+        context.insert("environment_short_id", "zenv");
+        context.insert("environment_long_id", "00000000-0000-0000-0000-000000000002");
+        context.insert("resource_expiration_in_seconds", &-1);
+        context.insert("skip_final_snapshot", &false);
+        context.insert("final_snapshot_name", "qovery-zdb-final-snap");
+        context.insert("delete_automated_backups", &false);
+        context.insert("publicly_accessible", &false);
+        context.insert("sanitized_name", "app-zabcd1234");
+        context.insert("namespace", "zproject-zenv");
+        context.insert("id", "zabcd1234");
+        context.insert("long_id", "00000000-0000-0000-0000-000000000000");
+        context.insert("ports", &json!([{ "port": 8080 }]));
+        context.insert("blue_green_active_color", "");
+        // container
+        context.insert(
+            "service",
+            &json!({
+                "name": "container-zabcd1234",
+                "long_id": "00000000-0000-0000-0000-000000000000",
+                "default_port": 8080,
+                "ports": [{ "port": 8080 }],
+            }),
+        );
+        // router
+        context.insert(
+            "hosts",
+            &json!([{ "domain_name": "p8080.example.com", "service_name": "app-zabcd1234", "service_port": 8080 }]),
+        );
+        context.insert("custom_domains", &json!([]));
+        context.insert("advanced_settings", &ApplicationAdvancedSettings::default());
+        context.insert("router_should_declare_domain_to_external_dns", &false);
+        context.insert("sticky_sessions_enabled", &false);
+        context.insert("whitelist_source_range_enabled", &false);
+        context.insert("metadata_annotations_cert_manager_cluster_issuer", "letsencrypt-qovery");
+        // database
+        context.insert("fqdn", "zabcd1234.zenv.example.com");
+        context.insert("service_name", "zabcd1234");
+        context.insert("version", "8.0.30");
+        context.insert("database_login", "superuser");
+        context.insert("database_password", "password");
+        context.insert("database_disk_size_in_gib", &10);
+        context.insert("database_ram_size_in_mib", &512);
+        context.insert("database_total_cpus", "500m");
+
+        let job_context = TeraContext::from_serialize(JobTeraContext {
+            organization_long_id: Uuid::nil(),
+            project_long_id: Uuid::nil(),
+            environment_short_id: "zenv".to_string(),
+            environment_long_id: Uuid::nil(),
+            cluster: ClusterTeraContext {
+                long_id: Uuid::nil(),
+                name: "qovery-zabcd".to_string(),
+                region: "eu-west-3".to_string(),
+                zone: "".to_string(),
+            },
+            namespace: "zproject-zenv".to_string(),
+            correlation_id: correlation_id.as_str().to_string(),
+            service: ServiceTeraContext {
+                short_id: "zabcd1234".to_string(),
+                long_id: Uuid::nil(),
+                name: "job-zabcd1234".to_string(),
+                user_unsafe_name: "job".to_string(),
+                image_full: "registry/job:latest".to_string(),
+                image_tag: "latest".to_string(),
+                command_args: vec![],
+                entrypoint: None,
+                cpu_request_in_milli: "500m".to_string(),
+                cpu_limit_in_milli: "500m".to_string(),
+                ram_request_in_mib: "512Mi".to_string(),
+                ram_limit_in_mib: "512Mi".to_string(),
+                default_port: None,
+                max_nb_restart: 0,
+                max_duration_in_sec: 300,
+                cronjob_schedule: None,
+                advanced_settings: JobAdvancedSettings::default(),
+            },
+            registry: None,
+            environment_variables: vec![],
+            resource_expiration_in_seconds: None,
+        })
+        .expect("cannot serialize job context");
+
+        // execute:
+        let database_tags = render("lib/aws/services/common/common-variables.j2.tf", &context);
+        let service_charts = vec![
+            render("lib/aws/charts/q-application/templates/service.j2.yaml", &context),
+            render("lib/common/charts/q-container/templates/service.j2.yaml", &context),
+            render("lib/common/charts/q-ingress-tls/templates/ingress-qovery.j2.yaml", &context),
+            render("lib/aws/chart_values/mysql/qovery-values.j2.yaml", &context),
+            render("lib/common/charts/q-job/templates/job.j2.yaml", &job_context),
+        ];
+
+        // verify:
+        assert_eq!("execution_id-42", correlation_id.as_str());
+        assert!(database_tags
+            .lines()
+            .any(|line| line.contains("\"q_correlation_id\"") && line.ends_with("= \"execution_id-42\"")));
+        for chart in service_charts {
+            assert!(
+                chart.contains("qovery.com/correlation-id: execution_id-42\n")
+                    || chart.contains("qovery.com/correlation-id: \"execution_id-42\"\n"),
+                "correlation id label is missing from: {}",
+                chart
+            );
+        }
+    }
+}
@@ -11,7 +11,7 @@ use tera::Context as TeraContext;
 impl ToTeraContext for Application<AWS> {
     fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
         let event_details = (self.mk_event_details)(Stage::Environment(EnvironmentStep::LoadConfiguration));
-        let mut context = self.default_tera_context(target.kubernetes, target.environment);
+        let mut context = self.default_tera_context(target);
 
         let cpu_limits = match validate_k8s_required_cpu_and_burstable(self.total_cpus(), self.cpu_burst()) {
             Ok(l) => l,
@@ -182,6 +182,10 @@ impl DatabaseType<AWS, Managed> for PostgresSQL {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::PostgreSQL
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_postgres_version(requested_version)
+    }
 }
 
 impl DatabaseType<AWS, Managed> for MySQL {
@@ -197,6 +201,10 @@ impl DatabaseType<AWS, Managed> for MySQL {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::MySQL
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_mysql_version(requested_version)
+    }
 }
 
 impl DatabaseType<AWS, Managed> for Redis {
@@ -212,6 +220,10 @@ impl DatabaseType<AWS, Managed> for Redis {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::Redis
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_redis_version(requested_version)
+    }
 }
 
 impl DatabaseType<AWS, Managed> for MongoDB {
@@ -228,6 +240,10 @@ impl DatabaseType<AWS, Managed> for MongoDB {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::MongoDB
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_mongodb_version(requested_version)
+    }
 }
 
 impl<T: DatabaseType<AWS, Managed>> Database<AWS, Managed, T>
@@ -235,14 +251,7 @@ where
     Database<AWS, Managed, T>: Service,
 {
     fn get_version_aws_managed(&self, event_details: EventDetails) -> Result<ServiceVersionCheckResult, EngineError> {
-        let fn_version = match T::db_type() {
-            service::DatabaseType::PostgreSQL => get_managed_postgres_version,
-            service::DatabaseType::MongoDB => get_managed_mongodb_version,
-            service::DatabaseType::MySQL => get_managed_mysql_version,
-            service::DatabaseType::Redis => get_managed_redis_version,
-        };
-
-        check_service_version(fn_version(self.version.to_string()), self, event_details)
+        check_service_version(T::supported_version(self.version.to_string()), self, event_details)
     }
 
     fn to_tera_context_for_aws_managed(
@@ -254,6 +263,7 @@ where
         let kubernetes = target.kubernetes;
         let environment = target.environment;
         let mut context = default_tera_context(self, kubernetes, environment);
+        context.insert("correlation_id", target.correlation_id.as_str());
 
         // we need the kubernetes config file to store tfstates file in kube secrets
         let kube_config_file_path = kubernetes.get_kubeconfig_file_path()?;
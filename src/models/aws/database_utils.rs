@@ -2,7 +2,7 @@ use crate::errors::CommandError;
 use crate::models::database_utils::{generate_supported_version, get_supported_version_to_use};
 use std::collections::HashMap;
 
-pub(super) fn get_managed_mysql_version(requested_version: String) -> Result<String, CommandError> {
+pub(crate) fn managed_mysql_versions() -> HashMap<String, String> {
     let mut supported_mysql_versions = HashMap::new();
     // https://docs.aws.amazon.com/AmazonRDS/latest/UserGuide/CHAP_MySQL.html#MySQL.Concepts.VersionMgmt
 
@@ -17,10 +17,14 @@ pub(super) fn get_managed_mysql_version(requested_version: String) -> Result<Str
     v8.remove("8.0.24");
     supported_mysql_versions.extend(v8);
 
-    get_supported_version_to_use("RDS MySQL", supported_mysql_versions, requested_version)
+    supported_mysql_versions
 }
 
-pub(super) fn get_managed_mongodb_version(requested_version: String) -> Result<String, CommandError> {
+pub(super) fn get_managed_mysql_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("RDS MySQL", managed_mysql_versions(), requested_version)
+}
+
+pub(crate) fn managed_mongodb_versions() -> HashMap<String, String> {
     let mut supported_mongodb_versions = HashMap::new();
 
     // v3.6.0
@@ -31,10 +35,14 @@ pub(super) fn get_managed_mongodb_version(requested_version: String) -> Result<S
     let mongo_version = generate_supported_version(4, 0, 0, Some(0), Some(0), None);
     supported_mongodb_versions.extend(mongo_version);
 
-    get_supported_version_to_use("DocumentDB", supported_mongodb_versions, requested_version)
+    supported_mongodb_versions
 }
 
-pub(super) fn get_managed_postgres_version(requested_version: String) -> Result<String, CommandError> {
+pub(super) fn get_managed_mongodb_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("DocumentDB", managed_mongodb_versions(), requested_version)
+}
+
+pub(crate) fn managed_postgres_versions() -> HashMap<String, String> {
     let mut supported_postgres_versions = HashMap::new();
 
     // https://docs.aws.amazon.com/AmazonRDS/latest/UserGuide/CHAP_PostgreSQL.html#PostgreSQL.Concepts
@@ -58,10 +66,14 @@ pub(super) fn get_managed_postgres_version(requested_version: String) -> Result<
     let v14 = generate_supported_version(14, 1, 3, None, None, None);
     supported_postgres_versions.extend(v14);
 
-    get_supported_version_to_use("Postgresql", supported_postgres_versions, requested_version)
+    supported_postgres_versions
 }
 
-pub(super) fn get_managed_redis_version(requested_version: String) -> Result<String, CommandError> {
+pub(super) fn get_managed_postgres_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("Postgresql", managed_postgres_versions(), requested_version)
+}
+
+pub(crate) fn managed_redis_versions() -> HashMap<String, String> {
     let mut supported_redis_versions = HashMap::with_capacity(2);
     // https://docs.aws.amazon.com/AmazonElastiCache/latest/red-ug/supported-engine-versions.html
 
@@ -69,7 +81,11 @@ pub(super) fn get_managed_redis_version(requested_version: String) -> Result<Str
     supported_redis_versions.insert("6".to_string(), "6.x".to_string());
     supported_redis_versions.insert("5".to_string(), "5.0.6".to_string());
 
-    get_supported_version_to_use("Elasticache", supported_redis_versions, requested_version)
+    supported_redis_versions
+}
+
+pub(super) fn get_managed_redis_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("Elasticache", managed_redis_versions(), requested_version)
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 mod application;
 mod container;
 mod database;
-mod database_utils;
+pub(crate) mod database_utils;
 mod router;
 
 use crate::models::types::CloudProvider;
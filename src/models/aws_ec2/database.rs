@@ -3,7 +3,7 @@ use crate::cloud_provider::service::{
     ServiceVersionCheckResult,
 };
 use crate::cloud_provider::{service, DeploymentTarget};
-use crate::errors::EngineError;
+use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
 use crate::models::aws_ec2::database_utils::{
     get_managed_mongodb_version, get_managed_mysql_version, get_managed_postgres_version, get_managed_redis_version,
@@ -180,6 +180,10 @@ impl DatabaseType<AWSEc2, Managed> for PostgresSQL {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::PostgreSQL
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_postgres_version(requested_version)
+    }
 }
 
 impl DatabaseType<AWSEc2, Managed> for MySQL {
@@ -194,6 +198,10 @@ impl DatabaseType<AWSEc2, Managed> for MySQL {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::MySQL
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_mysql_version(requested_version)
+    }
 }
 
 impl DatabaseType<AWSEc2, Managed> for Redis {
@@ -208,6 +216,10 @@ impl DatabaseType<AWSEc2, Managed> for Redis {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::Redis
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_redis_version(requested_version)
+    }
 }
 
 impl DatabaseType<AWSEc2, Managed> for MongoDB {
@@ -224,6 +236,10 @@ impl DatabaseType<AWSEc2, Managed> for MongoDB {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::MongoDB
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        get_managed_mongodb_version(requested_version)
+    }
 }
 
 impl<T: DatabaseType<AWSEc2, Managed>> Database<AWSEc2, Managed, T>
@@ -231,14 +247,7 @@ where
     Database<AWSEc2, Managed, T>: Service,
 {
     fn get_version_aws_managed(&self, event_details: EventDetails) -> Result<ServiceVersionCheckResult, EngineError> {
-        let fn_version = match T::db_type() {
-            service::DatabaseType::PostgreSQL => get_managed_postgres_version,
-            service::DatabaseType::MongoDB => get_managed_mongodb_version,
-            service::DatabaseType::MySQL => get_managed_mysql_version,
-            service::DatabaseType::Redis => get_managed_redis_version,
-        };
-
-        check_service_version(fn_version(self.version.to_string()), self, event_details)
+        check_service_version(T::supported_version(self.version.to_string()), self, event_details)
     }
 
     fn to_tera_context_for_aws_managed(
@@ -250,6 +259,7 @@ where
         let kubernetes = target.kubernetes;
         let environment = target.environment;
         let mut context = default_tera_context(self, kubernetes, environment);
+        context.insert("correlation_id", target.correlation_id.as_str());
 
         // we need the kubernetes config file to store tfstates file in kube secrets
         let kube_config_file_path = kubernetes.get_kubeconfig_file_path()?;
@@ -1,7 +1,8 @@
 use crate::cloud_provider::models::{EnvironmentVariable, Storage, StorageDataTemplate};
-use crate::cloud_provider::service::{Action, Service, ServiceType};
-use crate::cloud_provider::DeploymentTarget;
+use crate::cloud_provider::service::{Action, HealthCheck, Service, ServiceType};
+use crate::cloud_provider::{service, DeploymentTarget};
 use crate::deployment_action::DeploymentAction;
+use crate::errors::EngineError;
 use crate::events::{EventDetails, Stage, Transmitter};
 use crate::io_models::application::Port;
 use crate::io_models::container::{ContainerAdvancedSettings, Registry};
@@ -18,11 +19,14 @@ use uuid::Uuid;
 pub enum ContainerError {
     #[error("Container invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("Cannot create workspace directory: {0}")]
+    CannotGetWorkspaceDirectory(Box<EngineError>),
 }
 
 pub struct Container<T: CloudProvider> {
     _marker: PhantomData<T>,
-    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails>,
+    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails + Send + Sync>,
     pub(super) id: String,
     pub(super) long_id: Uuid,
     pub(super) name: String,
@@ -110,14 +114,10 @@ impl<T: CloudProvider> Container<T> {
             ));
         }
 
-        let workspace_directory = crate::fs::workspace_directory(
-            context.workspace_root_dir(),
-            context.execution_id(),
-            format!("containers/{}", long_id),
-        )
-        .map_err(|_| ContainerError::InvalidConfig("Can't create workspace directory".to_string()))?;
-
         let event_details = mk_event_details(Transmitter::Container(long_id, name.to_string()));
+        let workspace_directory =
+            service::workspace_directory(context, &ServiceType::Container, &long_id, event_details.clone())
+                .map_err(|err| ContainerError::CannotGetWorkspaceDirectory(Box::new(err)))?;
         let mk_event_details = move |stage: Stage| EventDetails::clone_changing_stage(event_details.clone(), stage);
         Ok(Self {
             _marker: PhantomData,
@@ -167,6 +167,10 @@ impl<T: CloudProvider> Container<T> {
         &self.registry
     }
 
+    pub fn ports(&self) -> &[Port] {
+        &self.ports
+    }
+
     fn public_ports(&self) -> impl Iterator<Item = &Port> + '_ {
         self.ports.iter().filter(|port| port.publicly_accessible)
     }
@@ -188,6 +192,7 @@ impl<T: CloudProvider> Container<T> {
                 zone: kubernetes.zone().to_string(),
             },
             namespace: environment.namespace().to_string(),
+            correlation_id: target.correlation_id.as_str().to_string(),
             service: ServiceTeraContext {
                 short_id: to_short_id(&self.long_id),
                 long_id: self.long_id,
@@ -304,6 +309,14 @@ impl<T: CloudProvider> Service for Container<T> {
         Some(self.selector())
     }
 
+    fn health_check(&self) -> Option<HealthCheck> {
+        HealthCheck::from_readiness_probe(
+            &self.advanced_settings.readiness_probe_type,
+            self.ports.iter().find_or_first(|p| p.is_default).map(|p| p.port),
+            &self.advanced_settings.readiness_probe_http_get_path,
+        )
+    }
+
     fn as_service(&self) -> &dyn Service {
         self
     }
@@ -312,6 +325,7 @@ impl<T: CloudProvider> Service for Container<T> {
 pub trait ContainerService: Service + DeploymentAction + ToTeraContext {
     fn public_ports(&self) -> Vec<&Port>;
     fn advanced_settings(&self) -> &ContainerAdvancedSettings;
+    fn min_instances(&self) -> u32;
     fn image_full(&self) -> String;
     fn kube_service_name(&self) -> String;
     fn startup_timeout(&self) -> std::time::Duration {
@@ -342,6 +356,10 @@ where
         &self.advanced_settings
     }
 
+    fn min_instances(&self) -> u32 {
+        self.min_instances
+    }
+
     fn image_full(&self) -> String {
         format!(
             "{}{}:{}",
@@ -404,6 +422,7 @@ pub(super) struct ContainerTeraContext {
     pub(super) environment_long_id: Uuid,
     pub(super) cluster: ClusterTeraContext,
     pub(super) namespace: String,
+    pub(super) correlation_id: String,
     pub(super) service: ServiceTeraContext,
     pub(super) registry: Option<RegistryTeraContext>,
     pub(super) environment_variables: Vec<EnvironmentVariable>,
@@ -1,10 +1,11 @@
+use crate::cloud_provider::database_backup::{trigger_database_backup, BackupHandle};
 use crate::cloud_provider::service::{
     check_service_version, default_tera_context, Action, Service, ServiceType, ServiceVersionCheckResult,
 };
 use crate::cloud_provider::utilities::managed_db_name_sanitizer;
 use crate::cloud_provider::{service, DeploymentTarget};
 use crate::deployment_action::DeploymentAction;
-use crate::errors::EngineError;
+use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
 use crate::io_models::context::Context;
 use crate::io_models::database::DatabaseOptions;
@@ -49,12 +50,21 @@ pub struct MySQL {}
 pub struct MongoDB {}
 pub struct Redis {}
 
-pub trait DatabaseType<T: CloudProvider, M: DatabaseMode> {
+pub trait DatabaseType<T: CloudProvider, M: DatabaseMode>: Send + Sync {
     type DatabaseOptions;
 
     fn short_name() -> &'static str;
     fn lib_directory_name() -> &'static str;
     fn db_type() -> service::DatabaseType;
+    /// Version to deploy for the requested one, fails if the requested version is not supported.
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        match Self::db_type() {
+            service::DatabaseType::PostgreSQL => get_self_hosted_postgres_version(requested_version),
+            service::DatabaseType::MongoDB => get_self_hosted_mongodb_version(requested_version),
+            service::DatabaseType::MySQL => get_self_hosted_mysql_version(requested_version),
+            service::DatabaseType::Redis => get_self_hosted_redis_version(requested_version),
+        }
+    }
     // autocorrect resources if needed
     fn cpu_validate(desired_cpu: String) -> String {
         desired_cpu
@@ -72,13 +82,19 @@ pub enum DatabaseError {
     #[error("Database invalid configuration: {0}")]
     InvalidConfig(String),
 
+    #[error("Cannot create workspace directory: {0}")]
+    CannotGetWorkspaceDirectory(Box<EngineError>),
+
     #[error("Managed database for {0:?} is not supported (yet) by provider {1}")]
     UnsupportedManagedMode(service::DatabaseType, String),
+
+    #[error("{0} version {1} is not supported, supported versions are: {2}")]
+    UnsupportedVersion(String, String, String),
 }
 
 pub struct Database<C: CloudProvider, M: DatabaseMode, T: DatabaseType<C, M>> {
     _marker: PhantomData<(C, M, T)>,
-    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails>,
+    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails + Send + Sync>,
     pub(crate) id: String,
     pub(crate) long_id: Uuid,
     pub(crate) action: Action,
@@ -117,14 +133,14 @@ impl<C: CloudProvider, M: DatabaseMode, T: DatabaseType<C, M>> Database<C, M, T>
     ) -> Result<Self, DatabaseError> {
         // TODO: Implement domain constraint logic
 
-        let workspace_directory = crate::fs::workspace_directory(
-            context.workspace_root_dir(),
-            context.execution_id(),
-            format!("databases/{}", long_id),
-        )
-        .map_err(|_| DatabaseError::InvalidConfig("Can't create workspace directory".to_string()))?;
-
         let event_details = mk_event_details(Transmitter::Database(long_id, name.to_string()));
+        let workspace_directory = service::workspace_directory(
+            context,
+            &ServiceType::Database(T::db_type()),
+            &long_id,
+            event_details.clone(),
+        )
+        .map_err(|err| DatabaseError::CannotGetWorkspaceDirectory(Box::new(err)))?;
         let mk_event_details = move |stage: Stage| EventDetails::clone_changing_stage(event_details.clone(), stage);
         Ok(Self {
             _marker: PhantomData,
@@ -159,12 +175,24 @@ impl<C: CloudProvider, M: DatabaseMode, T: DatabaseType<C, M>> Database<C, M, T>
     pub(super) fn fqdn(&self, target: &DeploymentTarget, fqdn: &str) -> String {
         match &self.publicly_accessible {
             true => fqdn.to_string(),
-            false => match M::is_managed() {
-                true => format!("{}-dns.{}.svc.cluster.local", self.id(), target.environment.namespace()),
-                false => format!("{}.{}.svc.cluster.local", self.sanitized_name(), target.environment.namespace()),
-            },
+            false => self
+                .internal_fqdns(target)
+                .into_iter()
+                .next()
+                .map(|(_, fqdn)| fqdn)
+                .unwrap_or_default(),
         }
     }
+
+    pub(crate) fn internal_fqdns(&self, target: &DeploymentTarget) -> Vec<(u16, String)> {
+        database_internal_fqdns(
+            M::is_managed(),
+            self.id(),
+            &self.sanitized_name(),
+            target.environment.namespace(),
+            &[self.private_port],
+        )
+    }
 }
 
 impl<C: CloudProvider, M: DatabaseMode, T: DatabaseType<C, M>> Service for Database<C, M, T> {
@@ -240,6 +268,7 @@ impl<C: CloudProvider, T: DatabaseType<C, Container>> Database<C, Container, T>
         let kubernetes = target.kubernetes;
         let environment = target.environment;
         let mut context = default_tera_context(self, kubernetes, environment);
+        context.insert("correlation_id", target.correlation_id.as_str());
 
         // we need the kubernetes config file to store tfstates file in kube secrets
         let kube_config_file_path = kubernetes.get_kubeconfig_file_path()?;
@@ -282,14 +311,7 @@ impl<C: CloudProvider, T: DatabaseType<C, Container>> Database<C, Container, T>
     }
 
     fn get_version(&self, event_details: EventDetails) -> Result<ServiceVersionCheckResult, EngineError> {
-        let fn_version = match T::db_type() {
-            service::DatabaseType::PostgreSQL => get_self_hosted_postgres_version,
-            service::DatabaseType::MongoDB => get_self_hosted_mongodb_version,
-            service::DatabaseType::MySQL => get_self_hosted_mysql_version,
-            service::DatabaseType::Redis => get_self_hosted_redis_version,
-        };
-
-        check_service_version(fn_version(self.version.to_string()), self, event_details)
+        check_service_version(T::supported_version(self.version.to_string()), self, event_details)
     }
 }
 
@@ -299,6 +321,10 @@ impl<C: CloudProvider, T: DatabaseType<C, Managed>> Database<C, Managed, T> {
         format!("{}/common/charts/external-name-svc", self.lib_root_directory)
     }
 
+    pub fn helm_chart_connection_pooler_dir(&self) -> String {
+        format!("{}/common/charts/pgbouncer", self.lib_root_directory)
+    }
+
     pub fn terraform_common_resource_dir_path(&self) -> String {
         format!("{}/{}/services/common", self.lib_root_directory, C::lib_directory_name())
     }
@@ -313,6 +339,51 @@ impl<C: CloudProvider, T: DatabaseType<C, Managed>> Database<C, Managed, T> {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagedDatabaseServiceNames {
+    /// ExternalName service targeting the database hosted by the cloud provider
+    pub external_name_service: String,
+    /// Connection pooler service, if enabled, sitting between applications and the database
+    pub connection_pooler_service: Option<String>,
+}
+
+/// Applications always reach a managed database through the `service_name` host.
+/// When a connection pooler is enabled, its service takes over this name and the ExternalName service
+/// is renamed so the pooler can still reach the database directly.
+pub fn managed_database_service_names(
+    service_name: &str,
+    connection_pooler_enabled: bool,
+) -> ManagedDatabaseServiceNames {
+    if connection_pooler_enabled {
+        ManagedDatabaseServiceNames {
+            external_name_service: format!("{}-direct", service_name),
+            connection_pooler_service: Some(service_name.to_string()),
+        }
+    } else {
+        ManagedDatabaseServiceNames {
+            external_name_service: service_name.to_string(),
+            connection_pooler_service: None,
+        }
+    }
+}
+
+/// Cluster local names of a database, one per exposed port so other services can target a specific one.
+/// Managed databases are reached through their `-dns` ExternalName service, self-hosted ones through their own service.
+pub fn database_internal_fqdns(
+    is_managed: bool,
+    id: &str,
+    sanitized_name: &str,
+    namespace: &str,
+    ports: &[u16],
+) -> Vec<(u16, String)> {
+    let fqdn = match is_managed {
+        true => format!("{}-dns.{}.svc.cluster.local", id, namespace),
+        false => format!("{}.{}.svc.cluster.local", sanitized_name, namespace),
+    };
+
+    ports.iter().map(|port| (*port, fqdn.clone())).collect()
+}
+
 pub trait DatabaseService: Service + DeploymentAction + ToTeraContext {
     fn is_managed_service(&self) -> bool;
 
@@ -320,7 +391,14 @@ pub trait DatabaseService: Service + DeploymentAction + ToTeraContext {
 
     fn version(&self) -> String;
 
+    /// Checks the requested version can be deployed, the version check result telling which one will be.
+    fn check_version(&self, event_details: EventDetails) -> Result<ServiceVersionCheckResult, EngineError>;
+
     fn as_deployment_action(&self) -> &dyn DeploymentAction;
+
+    /// Starts an on-demand backup of a managed database through the cloud provider backup API.
+    /// Self-hosted databases are not supported.
+    fn trigger_backup(&self, target: &DeploymentTarget) -> Result<BackupHandle, EngineError>;
 }
 
 impl<C: CloudProvider, M: DatabaseMode, T: DatabaseType<C, M>> DatabaseService for Database<C, M, T>
@@ -339,7 +417,110 @@ where
         self.version.to_string()
     }
 
+    fn check_version(&self, event_details: EventDetails) -> Result<ServiceVersionCheckResult, EngineError> {
+        check_service_version(T::supported_version(self.version.to_string()), self, event_details)
+    }
+
     fn as_deployment_action(&self) -> &dyn DeploymentAction {
         self
     }
+
+    fn trigger_backup(&self, target: &DeploymentTarget) -> Result<BackupHandle, EngineError> {
+        let client = target.cloud_provider.database_backup_client();
+        trigger_database_backup(
+            M::is_managed(),
+            T::db_type(),
+            self.long_id,
+            &self.fqdn_id,
+            client.as_deref(),
+            self.get_event_details(Stage::Environment(EnvironmentStep::Deploy)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::database::{
+        database_internal_fqdns, managed_database_service_names, Container, DatabaseType, Managed,
+        ManagedDatabaseServiceNames, MongoDB,
+    };
+    use crate::models::types::AWS;
+
+    #[test]
+    fn test_supported_version_depends_on_database_mode() {
+        // execute & verify: DocumentDB doesn't run MongoDB 4.4 while it can be self-hosted
+        assert_eq!(
+            "4.4.15",
+            <MongoDB as DatabaseType<AWS, Container>>::supported_version("4.4".to_string()).unwrap()
+        );
+        assert!(<MongoDB as DatabaseType<AWS, Managed>>::supported_version("4.4".to_string()).is_err());
+        assert!(<MongoDB as DatabaseType<AWS, Managed>>::supported_version("4.0".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_managed_database_service_names() {
+        // setup:
+        let test_cases = vec![
+            (
+                false,
+                ManagedDatabaseServiceNames {
+                    external_name_service: "zabcd1234".to_string(),
+                    connection_pooler_service: None,
+                },
+            ),
+            (
+                true,
+                ManagedDatabaseServiceNames {
+                    external_name_service: "zabcd1234-direct".to_string(),
+                    connection_pooler_service: Some("zabcd1234".to_string()),
+                },
+            ),
+        ];
+
+        for (connection_pooler_enabled, expected) in test_cases {
+            // execute:
+            let result = managed_database_service_names("zabcd1234", connection_pooler_enabled);
+
+            // verify:
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn test_database_internal_fqdns() {
+        // setup:
+        let test_cases = vec![
+            (
+                true,
+                vec![
+                    "zabcd1234-dns.z1234-env.svc.cluster.local:6432",
+                    "zabcd1234-dns.z1234-env.svc.cluster.local:5432",
+                ],
+            ),
+            (
+                false,
+                vec![
+                    "postgresql-zabcd1234.z1234-env.svc.cluster.local:6432",
+                    "postgresql-zabcd1234.z1234-env.svc.cluster.local:5432",
+                ],
+            ),
+        ];
+
+        for (is_managed, expected) in test_cases {
+            // execute:
+            let result =
+                database_internal_fqdns(is_managed, "zabcd1234", "postgresql-zabcd1234", "z1234-env", &[6432, 5432]);
+
+            // verify: every declared port gets its own name, in the declared order
+            assert_eq!(
+                expected,
+                result
+                    .iter()
+                    .map(|(port, fqdn)| format!("{}:{}", fqdn, port))
+                    .collect::<Vec<String>>(),
+                "managed: {}",
+                is_managed
+            );
+        }
+    }
 }
@@ -3,7 +3,7 @@ use crate::models::types::VersionsNumber;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-pub fn get_self_hosted_postgres_version(requested_version: String) -> Result<String, CommandError> {
+pub(crate) fn self_hosted_postgres_versions() -> HashMap<String, String> {
     let mut supported_postgres_versions = HashMap::new();
 
     // https://hub.docker.com/r/bitnami/postgresql/tags?page=1&ordering=last_updated
@@ -28,10 +28,14 @@ pub fn get_self_hosted_postgres_version(requested_version: String) -> Result<Str
     let v14 = generate_supported_version(14, 4, 4, Some(0), Some(0), None);
     supported_postgres_versions.extend(v14);
 
-    get_supported_version_to_use("Postgresql", supported_postgres_versions, requested_version)
+    supported_postgres_versions
 }
 
-pub fn get_self_hosted_mysql_version(requested_version: String) -> Result<String, CommandError> {
+pub fn get_self_hosted_postgres_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("Postgresql", self_hosted_postgres_versions(), requested_version)
+}
+
+pub(crate) fn self_hosted_mysql_versions() -> HashMap<String, String> {
     let mut supported_mysql_versions = HashMap::new();
     // https://hub.docker.com/r/bitnami/mysql/tags?page=1&ordering=last_updated
 
@@ -43,10 +47,14 @@ pub fn get_self_hosted_mysql_version(requested_version: String) -> Result<String
     let v8 = generate_supported_version(8, 0, 0, Some(11), Some(30), None);
     supported_mysql_versions.extend(v8);
 
-    get_supported_version_to_use("MySQL", supported_mysql_versions, requested_version)
+    supported_mysql_versions
 }
 
-pub fn get_self_hosted_mongodb_version(requested_version: String) -> Result<String, CommandError> {
+pub fn get_self_hosted_mysql_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("MySQL", self_hosted_mysql_versions(), requested_version)
+}
+
+pub(crate) fn self_hosted_mongodb_versions() -> HashMap<String, String> {
     let mut supported_mongodb_versions = HashMap::new();
 
     // https://hub.docker.com/r/bitnami/mongodb/tags?page=1&ordering=last_updated
@@ -67,10 +75,14 @@ pub fn get_self_hosted_mongodb_version(requested_version: String) -> Result<Stri
     let mongo_version = generate_supported_version(4, 4, 4, Some(0), Some(15), None);
     supported_mongodb_versions.extend(mongo_version);
 
-    get_supported_version_to_use("MongoDB", supported_mongodb_versions, requested_version)
+    supported_mongodb_versions
 }
 
-pub fn get_self_hosted_redis_version(requested_version: String) -> Result<String, CommandError> {
+pub fn get_self_hosted_mongodb_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("MongoDB", self_hosted_mongodb_versions(), requested_version)
+}
+
+pub(crate) fn self_hosted_redis_versions() -> HashMap<String, String> {
     let mut supported_redis_versions = HashMap::with_capacity(6);
     // https://hub.docker.com/r/bitnami/redis/tags?page=1&ordering=last_updated
 
@@ -82,7 +94,11 @@ pub fn get_self_hosted_redis_version(requested_version: String) -> Result<String
     supported_redis_versions.insert("5".to_string(), "5.0.14".to_string());
     supported_redis_versions.insert("5.0".to_string(), "5.0.14".to_string());
 
-    get_supported_version_to_use("Redis", supported_redis_versions, requested_version)
+    supported_redis_versions
+}
+
+pub fn get_self_hosted_redis_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("Redis", self_hosted_redis_versions(), requested_version)
 }
 
 pub fn get_supported_version_to_use(
@@ -133,6 +149,23 @@ pub fn get_supported_version_to_use(
     }
 }
 
+/// Human readable list of the major and major.minor versions a user can ask for, sorted by version number.
+/// Full patch versions are left out to keep the list short.
+pub fn supported_versions_summary(all_supported_versions: &HashMap<String, String>) -> String {
+    let mut versions: Vec<&String> = all_supported_versions
+        .keys()
+        .filter(|version| version.split('.').count() <= 2)
+        .collect();
+    versions.sort_by_key(|version| {
+        version
+            .split('.')
+            .map(|part| part.parse::<u32>().unwrap_or(0))
+            .collect::<Vec<u32>>()
+    });
+
+    versions.iter().map(|v| v.as_str()).collect::<Vec<&str>>().join(", ")
+}
+
 // Ease the support of multiple versions by range
 pub fn generate_supported_version(
     major: i32,
@@ -8,6 +8,7 @@ use crate::models::types::DO;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use strum_macros::EnumIter;
 
 pub struct DoAppExtraSettings {}
 pub struct DoDbExtraSettings {}
@@ -45,7 +46,7 @@ pub enum DoStorageType {
     Standard,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, EnumIter)]
 pub enum DoRegion {
     NewYorkCity1,
     NewYorkCity2,
@@ -80,6 +81,24 @@ impl DoRegion {
             DoRegion::Bangalore => "blr1",
         }
     }
+
+    pub fn human_name(&self) -> &'static str {
+        match self {
+            DoRegion::NewYorkCity1 => "New York 1",
+            DoRegion::NewYorkCity2 => "New York 2",
+            DoRegion::NewYorkCity3 => "New York 3",
+            DoRegion::Amsterdam2 => "Amsterdam 2",
+            DoRegion::Amsterdam3 => "Amsterdam 3",
+            DoRegion::SanFrancisco1 => "San Francisco 1",
+            DoRegion::SanFrancisco2 => "San Francisco 2",
+            DoRegion::SanFrancisco3 => "San Francisco 3",
+            DoRegion::Singapore => "Singapore",
+            DoRegion::London => "London",
+            DoRegion::Frankfurt => "Frankfurt",
+            DoRegion::Toronto => "Toronto",
+            DoRegion::Bangalore => "Bangalore",
+        }
+    }
 }
 
 impl Display for DoRegion {
@@ -1,6 +1,6 @@
 use crate::cloud_provider::models::EnvironmentVariable;
 use crate::cloud_provider::service::{Action, Service, ServiceType};
-use crate::cloud_provider::DeploymentTarget;
+use crate::cloud_provider::{service, DeploymentTarget};
 use crate::deployment_action::DeploymentAction;
 use crate::errors::EngineError;
 use crate::events::{EventDetails, Stage, Transmitter};
@@ -22,11 +22,14 @@ use uuid::Uuid;
 pub enum JobError {
     #[error("Job invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("Cannot create workspace directory: {0}")]
+    CannotGetWorkspaceDirectory(Box<EngineError>),
 }
 
 pub struct Job<T: CloudProvider> {
     _marker: PhantomData<T>,
-    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails>,
+    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails + Send + Sync>,
     pub(super) id: String,
     pub(super) long_id: Uuid,
     pub(super) name: String,
@@ -100,14 +103,10 @@ impl<T: CloudProvider> Job<T> {
             return Err(JobError::InvalidConfig("ram_request_in_mib must be greater than 0".to_string()));
         }
 
-        let workspace_directory = crate::fs::workspace_directory(
-            context.workspace_root_dir(),
-            context.execution_id(),
-            format!("jobs/{}", long_id),
-        )
-        .map_err(|err| JobError::InvalidConfig(format!("Can't create workspace directory: {}", err)))?;
-
         let event_details = mk_event_details(Transmitter::Job(long_id, name.to_string()));
+        let workspace_directory =
+            service::workspace_directory(context, &ServiceType::Job, &long_id, event_details.clone())
+                .map_err(|err| JobError::CannotGetWorkspaceDirectory(Box::new(err)))?;
         let mk_event_details = move |stage: Stage| EventDetails::clone_changing_stage(event_details.clone(), stage);
         Ok(Self {
             _marker: PhantomData,
@@ -183,6 +182,7 @@ impl<T: CloudProvider> Job<T> {
                 zone: kubernetes.zone().to_string(),
             },
             namespace: environment.namespace().to_string(),
+            correlation_id: target.correlation_id.as_str().to_string(),
             service: ServiceTeraContext {
                 short_id: to_short_id(&self.long_id),
                 long_id: self.long_id,
@@ -406,6 +406,7 @@ pub(super) struct JobTeraContext {
     pub(super) environment_long_id: Uuid,
     pub(super) cluster: ClusterTeraContext,
     pub(super) namespace: String,
+    pub(super) correlation_id: String,
     pub(super) service: ServiceTeraContext,
     pub(super) registry: Option<RegistryTeraContext>,
     pub(super) environment_variables: Vec<EnvironmentVariable>,
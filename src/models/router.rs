@@ -1,7 +1,7 @@
 use crate::cloud_provider::models::{CustomDomain, CustomDomainDataTemplate, HostDataTemplate, Route};
 use crate::cloud_provider::service::{default_tera_context, Action, Service, ServiceType};
 use crate::cloud_provider::utilities::sanitize_name;
-use crate::cloud_provider::DeploymentTarget;
+use crate::cloud_provider::{service, DeploymentTarget};
 use crate::deployment_action::DeploymentAction;
 use crate::errors::EngineError;
 use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
@@ -17,6 +17,9 @@ use uuid::Uuid;
 pub enum RouterError {
     #[error("Router invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("Cannot create workspace directory: {0}")]
+    CannotGetWorkspaceDirectory(Box<EngineError>),
 }
 
 pub struct RouterAdvancedSettings {
@@ -26,7 +29,7 @@ pub struct RouterAdvancedSettings {
 
 pub struct Router<T: CloudProvider> {
     _marker: PhantomData<T>,
-    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails>,
+    pub(super) mk_event_details: Box<dyn Fn(Stage) -> EventDetails + Send + Sync>,
     pub(crate) id: String,
     pub(crate) long_id: Uuid,
     pub(crate) action: Action,
@@ -55,14 +58,10 @@ impl<T: CloudProvider> Router<T> {
         advanced_settings: RouterAdvancedSettings,
         mk_event_details: impl Fn(Transmitter) -> EventDetails,
     ) -> Result<Self, RouterError> {
-        let workspace_directory = crate::fs::workspace_directory(
-            context.workspace_root_dir(),
-            context.execution_id(),
-            format!("databases/{}", long_id),
-        )
-        .map_err(|_| RouterError::InvalidConfig("Can't create workspace directory".to_string()))?;
-
         let event_details = mk_event_details(Transmitter::Router(long_id, name.to_string()));
+        let workspace_directory =
+            service::workspace_directory(context, &ServiceType::Router, &long_id, event_details.clone())
+                .map_err(|err| RouterError::CannotGetWorkspaceDirectory(Box::new(err)))?;
         let mk_event_details = move |stage: Stage| EventDetails::clone_changing_stage(event_details.clone(), stage);
         Ok(Self {
             _marker: PhantomData,
@@ -97,6 +96,7 @@ impl<T: CloudProvider> Router<T> {
         let kubernetes = target.kubernetes;
         let environment = target.environment;
         let mut context = default_tera_context(self, kubernetes, environment);
+        context.insert("correlation_id", target.correlation_id.as_str());
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
 
         let custom_domain_data_templates = self
@@ -285,6 +285,9 @@ pub trait RouterService: Service + DeploymentAction + ToTeraContext {
     /// all domains (auto-generated by Qovery and user custom domains) associated to the router
     fn has_custom_domains(&self) -> bool;
 
+    /// ids of the applications/containers the router sends traffic to
+    fn routed_services_ids(&self) -> Vec<Uuid>;
+
     fn as_deployment_action(&self) -> &dyn DeploymentAction;
 }
 
@@ -296,6 +299,10 @@ where
         !self.custom_domains.is_empty()
     }
 
+    fn routed_services_ids(&self) -> Vec<Uuid> {
+        self.routes.iter().map(|route| route.service_long_id).collect()
+    }
+
     fn as_deployment_action(&self) -> &dyn DeploymentAction {
         self
     }
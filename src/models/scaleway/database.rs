@@ -3,7 +3,7 @@ use crate::cloud_provider::service::{
     ServiceVersionCheckResult,
 };
 use crate::cloud_provider::{service, DeploymentTarget};
-use crate::errors::EngineError;
+use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
 use crate::io_models::database::DatabaseOptions;
 use crate::models::database::{
@@ -14,7 +14,9 @@ use crate::models::database_utils::{
     get_self_hosted_redis_version,
 };
 use crate::models::scaleway::database_utils::{pick_managed_mysql_version, pick_managed_postgres_version};
+use crate::models::scaleway::ScwDatabaseDiskType;
 use crate::models::types::{ToTeraContext, SCW};
+use std::str::FromStr;
 use tera::Context as TeraContext;
 
 /////////////////////////////////////////////////////////////////
@@ -93,6 +95,10 @@ impl DatabaseType<SCW, Managed> for PostgresSQL {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::PostgreSQL
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        pick_managed_postgres_version(requested_version)
+    }
 }
 
 impl DatabaseType<SCW, Managed> for MySQL {
@@ -108,11 +114,28 @@ impl DatabaseType<SCW, Managed> for MySQL {
     fn db_type() -> service::DatabaseType {
         service::DatabaseType::MySQL
     }
+
+    fn supported_version(requested_version: String) -> Result<String, CommandError> {
+        pick_managed_mysql_version(requested_version)
+    }
 }
 
 // Redis and MongoDB are not supported managed db yet
 
 impl<M: DatabaseMode, T: DatabaseType<SCW, M>> Database<SCW, M, T> {
+    /// Rejects the disk type before reaching terraform or helm when it doesn't exist or cannot be used by this database.
+    fn check_database_disk_type(&self, options: &DatabaseOptions) -> Result<(), EngineError> {
+        ScwDatabaseDiskType::from_str(&options.database_disk_type)
+            .and_then(|disk_type| disk_type.check_allowed(&options.mode, &self.database_instance_type))
+            .map_err(|err| {
+                EngineError::new_unsupported_database_disk_type(
+                    self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration)),
+                    &options.database_disk_type,
+                    err,
+                )
+            })
+    }
+
     fn to_tera_context_for_scaleway_managed(
         &self,
         target: &DeploymentTarget,
@@ -122,11 +145,13 @@ impl<M: DatabaseMode, T: DatabaseType<SCW, M>> Database<SCW, M, T> {
     where
         Database<SCW, M, T>: Service,
     {
+        self.check_database_disk_type(options)?;
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
         let kubernetes = target.kubernetes;
         let environment = target.environment;
 
         let mut context = default_tera_context(self, kubernetes, environment);
+        context.insert("correlation_id", target.correlation_id.as_str());
 
         // we need the kubernetes config file to store tfstates file in kube secrets
         let kube_config_file_path = kubernetes.get_kubeconfig_file_path()?;
@@ -197,6 +222,7 @@ where
         let _check_version = |event_details| {
             check_service_version(get_self_hosted_postgres_version(self.version.to_string()), self, event_details)
         };
+        self.check_database_disk_type(&self.options)?;
         self.to_tera_context_for_container(target, &self.options)
     }
 }
@@ -223,6 +249,7 @@ where
         let _check_version = |event_details| {
             check_service_version(get_self_hosted_mysql_version(self.version.to_string()), self, event_details)
         };
+        self.check_database_disk_type(&self.options)?;
         self.to_tera_context_for_container(target, &self.options)
     }
 }
@@ -238,6 +265,7 @@ where
             check_service_version(get_self_hosted_mongodb_version(self.version.to_string()), self, event_details)
         };
 
+        self.check_database_disk_type(&self.options)?;
         self.to_tera_context_for_container(target, &self.options)
     }
 }
@@ -252,6 +280,7 @@ where
         let _check_version = |event_details| {
             check_service_version(get_self_hosted_redis_version(self.version.to_string()), self, event_details)
         };
+        self.check_database_disk_type(&self.options)?;
         self.to_tera_context_for_container(target, &self.options)
     }
 }
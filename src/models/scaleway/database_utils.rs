@@ -2,7 +2,7 @@ use crate::errors::CommandError;
 use crate::models::database_utils::get_supported_version_to_use;
 use std::collections::HashMap;
 
-pub(super) fn pick_managed_postgres_version(requested_version: String) -> Result<String, CommandError> {
+pub(crate) fn managed_postgres_versions() -> HashMap<String, String> {
     // Scaleway supported postgres versions
     // https://api.scaleway.com/rdb/v1/regions/fr-par/database-engines
     let mut supported_postgres_versions = HashMap::new();
@@ -20,10 +20,14 @@ pub(super) fn pick_managed_postgres_version(requested_version: String) -> Result
     supported_postgres_versions.insert("13".to_string(), "13".to_string());
     supported_postgres_versions.insert("13.0".to_string(), "13.0".to_string());
 
-    get_supported_version_to_use("RDB postgres", supported_postgres_versions, requested_version)
+    supported_postgres_versions
 }
 
-pub(super) fn pick_managed_mysql_version(requested_version: String) -> Result<String, CommandError> {
+pub(super) fn pick_managed_postgres_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("RDB postgres", managed_postgres_versions(), requested_version)
+}
+
+pub(crate) fn managed_mysql_versions() -> HashMap<String, String> {
     // Scaleway supported MySQL versions
     // https://api.scaleway.com/rdb/v1/regions/fr-par/database-engines
     let mut supported_mysql_versions = HashMap::new();
@@ -32,5 +36,9 @@ pub(super) fn pick_managed_mysql_version(requested_version: String) -> Result<St
     supported_mysql_versions.insert("8".to_string(), "8".to_string());
     supported_mysql_versions.insert("8.0".to_string(), "8.0".to_string());
 
-    get_supported_version_to_use("RDB MySQL", supported_mysql_versions, requested_version)
+    supported_mysql_versions
+}
+
+pub(super) fn pick_managed_mysql_version(requested_version: String) -> Result<String, CommandError> {
+    get_supported_version_to_use("RDB MySQL", managed_mysql_versions(), requested_version)
 }
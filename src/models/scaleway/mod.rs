@@ -1,14 +1,16 @@
 mod application;
 mod container;
 mod database;
-mod database_utils;
+pub(crate) mod database_utils;
 mod router;
 
 use crate::errors::CommandError;
+use crate::io_models::database::DatabaseMode;
 use crate::models::types::CloudProvider;
 use crate::models::types::SCW;
 use std::fmt;
 use std::str::FromStr;
+use strum_macros::EnumIter;
 
 pub struct ScwAppExtraSettings {}
 pub struct ScwDbExtraSettings {}
@@ -49,7 +51,7 @@ pub enum ScwStorageType {
     LocalSsd,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, EnumIter)]
 pub enum ScwRegion {
     Paris,
     Amsterdam,
@@ -65,6 +67,14 @@ impl ScwRegion {
             ScwRegion::Warsaw => "pl-waw",
         }
     }
+
+    pub fn human_name(&self) -> &'static str {
+        match self {
+            ScwRegion::Paris => "Paris",
+            ScwRegion::Amsterdam => "Amsterdam",
+            ScwRegion::Warsaw => "Warsaw",
+        }
+    }
 }
 
 impl fmt::Display for ScwRegion {
@@ -90,7 +100,7 @@ impl FromStr for ScwRegion {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, EnumIter)]
 pub enum ScwZone {
     Paris1,
     Paris2,
@@ -160,10 +170,78 @@ impl FromStr for ScwZone {
     }
 }
 
+/// Database disk types: volume types for managed (RDB) instances, storage class for container databases.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, EnumIter)]
+pub enum ScwDatabaseDiskType {
+    LocalSsd,
+    BlockSsd,
+    BlockVolumeSsd,
+}
+
+impl ScwDatabaseDiskType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ScwDatabaseDiskType::LocalSsd => "lssd",
+            ScwDatabaseDiskType::BlockSsd => "bssd",
+            ScwDatabaseDiskType::BlockVolumeSsd => "scw-sbv-ssd-0",
+        }
+    }
+
+    /// Checks the disk type can be used by a database of this mode.
+    /// Local SSD is only available on legacy DEV and GP managed node types.
+    pub fn check_allowed(&self, mode: &DatabaseMode, instance_type: &str) -> Result<(), CommandError> {
+        let instance_type = instance_type.to_lowercase();
+        match (self, mode) {
+            (ScwDatabaseDiskType::BlockSsd, DatabaseMode::MANAGED)
+            | (ScwDatabaseDiskType::BlockVolumeSsd, DatabaseMode::CONTAINER) => Ok(()),
+            (ScwDatabaseDiskType::LocalSsd, DatabaseMode::MANAGED)
+                if instance_type.starts_with("db-dev-") || instance_type.starts_with("db-gp-") =>
+            {
+                Ok(())
+            }
+            (ScwDatabaseDiskType::LocalSsd, DatabaseMode::MANAGED) => Err(CommandError::new_from_safe_message(
+                format!("`{}` disk type is not available for `{}` instance type", self, instance_type),
+            )),
+            (_, DatabaseMode::MANAGED) => Err(CommandError::new_from_safe_message(format!(
+                "`{}` disk type is not available for managed databases",
+                self
+            ))),
+            (_, DatabaseMode::CONTAINER) => Err(CommandError::new_from_safe_message(format!(
+                "`{}` disk type is not available for container databases",
+                self
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ScwDatabaseDiskType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ScwDatabaseDiskType {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<ScwDatabaseDiskType, CommandError> {
+        match s {
+            "lssd" => Ok(ScwDatabaseDiskType::LocalSsd),
+            "bssd" => Ok(ScwDatabaseDiskType::BlockSsd),
+            "scw-sbv-ssd-0" => Ok(ScwDatabaseDiskType::BlockVolumeSsd),
+            _ => Err(CommandError::new_from_safe_message(format!(
+                "`{}` database disk type is not supported",
+                s
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ScwRegion, ScwZone};
+    use super::{ScwDatabaseDiskType, ScwRegion, ScwZone};
+    use crate::io_models::database::DatabaseMode;
     use std::str::FromStr;
+    use strum::IntoEnumIterator;
 
     #[test]
     fn test_region_to_str() {
@@ -205,4 +283,37 @@ mod tests {
         assert_eq!(ScwZone::Amsterdam1.region(), ScwRegion::Amsterdam);
         assert_eq!(ScwZone::Warsaw1.region(), ScwRegion::Warsaw);
     }
+
+    #[test]
+    fn test_database_disk_type_from_str() {
+        for disk_type in ScwDatabaseDiskType::iter() {
+            assert_eq!(ScwDatabaseDiskType::from_str(disk_type.as_str()), Ok(disk_type));
+        }
+        assert!(ScwDatabaseDiskType::from_str("unknown").is_err());
+        assert!(ScwDatabaseDiskType::from_str("").is_err());
+    }
+
+    #[test]
+    fn test_database_disk_type_check_allowed() {
+        // setup:
+        let test_cases = vec![
+            (ScwDatabaseDiskType::BlockSsd, DatabaseMode::MANAGED, "db-dev-s", true),
+            (ScwDatabaseDiskType::BlockSsd, DatabaseMode::MANAGED, "db-pro2-xxs", true),
+            (ScwDatabaseDiskType::LocalSsd, DatabaseMode::MANAGED, "db-dev-s", true),
+            (ScwDatabaseDiskType::LocalSsd, DatabaseMode::MANAGED, "DB-GP-XS", true),
+            (ScwDatabaseDiskType::LocalSsd, DatabaseMode::MANAGED, "db-pro2-xxs", false),
+            (ScwDatabaseDiskType::BlockVolumeSsd, DatabaseMode::MANAGED, "db-dev-s", false),
+            (ScwDatabaseDiskType::BlockVolumeSsd, DatabaseMode::CONTAINER, "", true),
+            (ScwDatabaseDiskType::BlockSsd, DatabaseMode::CONTAINER, "", false),
+            (ScwDatabaseDiskType::LocalSsd, DatabaseMode::CONTAINER, "", false),
+        ];
+
+        for (disk_type, mode, instance_type, expected) in test_cases {
+            // execute:
+            let result = disk_type.check_allowed(&mode, instance_type);
+
+            // verify:
+            assert_eq!(expected, result.is_ok(), "{} on `{}`", disk_type, instance_type);
+        }
+    }
 }
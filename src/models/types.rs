@@ -15,11 +15,11 @@ pub struct SCW {}
 
 // CloudProvider trait allows to derive all the custom type we need per provider,
 // with our marker type defined above to be able to select the correct one
-pub trait CloudProvider {
-    type AppExtraSettings;
-    type DbExtraSettings;
-    type RouterExtraSettings;
-    type StorageTypes;
+pub trait CloudProvider: Send + Sync {
+    type AppExtraSettings: Send + Sync;
+    type DbExtraSettings: Send + Sync;
+    type RouterExtraSettings: Send + Sync;
+    type StorageTypes: Send + Sync;
 
     fn short_name() -> &'static str;
     fn full_name() -> &'static str;
@@ -65,6 +65,25 @@ impl VersionsNumber {
 
         test
     }
+
+    /// Whether moving from the `current` version to this one is an upgrade Kubernetes allows:
+    /// same major version and exactly one minor version ahead. Downgrades, skipped minor versions
+    /// and versions without a numeric minor are rejected.
+    pub fn is_valid_upgrade_from(&self, current: &VersionsNumber) -> bool {
+        let parse_minor = |version: &VersionsNumber| {
+            version
+                .minor
+                .as_ref()
+                .and_then(|minor| minor.trim_end_matches('+').parse::<u32>().ok())
+        };
+
+        match (parse_minor(current), parse_minor(self)) {
+            (Some(current_minor), Some(requested_minor)) => {
+                self.major == current.major && requested_minor == current_minor + 1
+            }
+            _ => false,
+        }
+    }
 }
 
 impl FromStr for VersionsNumber {
@@ -128,3 +147,72 @@ impl fmt::Display for VersionsNumber {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::models::types::VersionsNumber;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_versions_number_is_valid_upgrade_from() {
+        // setup:
+        struct TestCase {
+            current: &'static str,
+            requested: &'static str,
+            expected: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                current: "1.22",
+                requested: "1.23",
+                expected: true,
+            },
+            TestCase {
+                current: "1.22.15",
+                requested: "1.23",
+                expected: true,
+            },
+            TestCase {
+                current: "1.9",
+                requested: "1.10",
+                expected: true,
+            },
+            TestCase {
+                current: "1.22",
+                requested: "1.24",
+                expected: false,
+            },
+            TestCase {
+                current: "1.23",
+                requested: "1.22",
+                expected: false,
+            },
+            TestCase {
+                current: "1.23",
+                requested: "1.23",
+                expected: false,
+            },
+            TestCase {
+                current: "1.23",
+                requested: "2.24",
+                expected: false,
+            },
+            TestCase {
+                current: "1",
+                requested: "1.23",
+                expected: false,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let current = VersionsNumber::from_str(tc.current).unwrap();
+            let requested = VersionsNumber::from_str(tc.requested).unwrap();
+            let result = requested.is_valid_upgrade_from(&current);
+
+            // verify:
+            assert_eq!(tc.expected, result, "{} -> {}", tc.current, tc.requested);
+        }
+    }
+}
@@ -6,6 +6,7 @@ use crate::object_storage::errors::ObjectStorageError;
 use std::fs::File;
 
 pub mod errors;
+pub mod multipart;
 pub mod s3;
 pub mod scaleway_object_storage;
 pub mod spaces;
@@ -39,6 +40,14 @@ pub trait ObjectStorage {
         use_cache: bool,
     ) -> Result<(StringPath, File), ObjectStorageError>;
     fn put(&self, bucket_name: &str, object_key: &str, file_path: &str) -> Result<(), ObjectStorageError>;
+    /// Upload a file, using multipart upload with parts of `part_size_bytes` when it's bigger than 100MiB.
+    fn upload_large_file(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        file_path: &str,
+        part_size_bytes: u64,
+    ) -> Result<(), ObjectStorageError>;
     fn ensure_file_is_absent(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError>;
 }
 
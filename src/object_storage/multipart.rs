@@ -0,0 +1,504 @@
+// Multipart upload of large files, supported by every S3 compatible API (AWS S3, DO Spaces, Scaleway Object Storage).
+// Each part is retried on its own, and if one of them can't be uploaded, the whole upload is aborted
+// so the provider drops already uploaded parts instead of keeping (and billing) them.
+use retry::delay::Fixed;
+use rusoto_s3::{
+    AbortMultipartUploadRequest, CompleteMultipartUploadRequest, CompletedMultipartUpload, CompletedPart,
+    CreateMultipartUploadRequest, PutObjectRequest, S3Client, StreamingBody, UploadPartRequest, S3,
+};
+use std::fs::File;
+use std::io::Read;
+
+use crate::object_storage::errors::ObjectStorageError;
+use crate::runtime::block_on;
+
+/// Files bigger than this are uploaded using multipart upload.
+pub const MULTIPART_UPLOAD_THRESHOLD_BYTES: u64 = 100 * 1024 * 1024;
+/// Must be at least 5MiB (S3 limit for every part but the last one).
+pub const MULTIPART_UPLOAD_PART_SIZE_BYTES: u64 = 50 * 1024 * 1024;
+pub const MULTIPART_UPLOAD_MIN_PART_SIZE_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultipartUploadOptions {
+    pub threshold_bytes: u64,
+    pub part_size_bytes: u64,
+    pub part_max_attempts: usize,
+    pub part_retry_delay_millis: u64,
+}
+
+impl Default for MultipartUploadOptions {
+    fn default() -> Self {
+        MultipartUploadOptions {
+            threshold_bytes: MULTIPART_UPLOAD_THRESHOLD_BYTES,
+            part_size_bytes: MULTIPART_UPLOAD_PART_SIZE_BYTES,
+            part_max_attempts: 3,
+            part_retry_delay_millis: 2000,
+        }
+    }
+}
+
+/// Upload operations of a single object, errors are returned as raw messages.
+pub trait ObjectUploader {
+    fn put_object(&self, body: Vec<u8>) -> Result<(), String>;
+    /// Returns the upload id.
+    fn create_multipart_upload(&self) -> Result<String, String>;
+    /// Returns the ETag of the uploaded part.
+    fn upload_part(&self, upload_id: &str, part_number: i64, body: Vec<u8>) -> Result<String, String>;
+    fn complete_multipart_upload(&self, upload_id: &str, parts: Vec<CompletedPart>) -> Result<(), String>;
+    fn abort_multipart_upload(&self, upload_id: &str) -> Result<(), String>;
+}
+
+/// Uploads `file_path`, using multipart upload if the file is bigger than the threshold.
+pub fn upload_file(
+    uploader: &dyn ObjectUploader,
+    file_path: &str,
+    options: &MultipartUploadOptions,
+) -> Result<(), String> {
+    let mut file = File::open(file_path).map_err(|e| e.to_string())?;
+    let file_size = file.metadata().map_err(|e| e.to_string())?.len();
+
+    if file_size <= options.threshold_bytes {
+        let mut body = Vec::with_capacity(file_size as usize);
+        file.read_to_end(&mut body).map_err(|e| e.to_string())?;
+        return uploader.put_object(body);
+    }
+
+    let upload_id = uploader.create_multipart_upload()?;
+    match upload_parts(uploader, &upload_id, &mut file, options)
+        .and_then(|parts| uploader.complete_multipart_upload(&upload_id, parts))
+    {
+        Ok(_) => Ok(()),
+        Err(err) => match uploader.abort_multipart_upload(&upload_id) {
+            Ok(_) => Err(format!("multipart upload {} aborted: {}", upload_id, err)),
+            Err(abort_err) => Err(format!(
+                "multipart upload {} failed: {}, and cannot be aborted: {}",
+                upload_id, err, abort_err
+            )),
+        },
+    }
+}
+
+fn upload_parts(
+    uploader: &dyn ObjectUploader,
+    upload_id: &str,
+    file: &mut File,
+    options: &MultipartUploadOptions,
+) -> Result<Vec<CompletedPart>, String> {
+    let mut parts = vec![];
+
+    for part_number in 1.. {
+        let mut body = Vec::with_capacity(options.part_size_bytes as usize);
+        file.by_ref()
+            .take(options.part_size_bytes)
+            .read_to_end(&mut body)
+            .map_err(|e| e.to_string())?;
+        if body.is_empty() {
+            break;
+        }
+
+        let e_tag = retry::retry(
+            Fixed::from_millis(options.part_retry_delay_millis).take(options.part_max_attempts.saturating_sub(1)),
+            || uploader.upload_part(upload_id, part_number, body.clone()),
+        )
+        .map_err(|err| match err {
+            retry::Error::Operation { error, tries, .. } => {
+                format!("part {} failed after {} attempts: {}", part_number, tries, error)
+            }
+            retry::Error::Internal(error) => format!("part {} failed: {}", part_number, error),
+        })?;
+
+        parts.push(CompletedPart {
+            e_tag: Some(e_tag),
+            part_number: Some(part_number),
+        });
+    }
+
+    Ok(parts)
+}
+
+/// Uploads `file_path` with parts of `part_size_bytes`, shared by every S3 compatible object storage.
+pub fn upload_large_file(
+    client: &S3Client,
+    bucket_name: &str,
+    object_key: &str,
+    file_path: &str,
+    part_size_bytes: u64,
+) -> Result<(), ObjectStorageError> {
+    let to_error = |raw_error_message: String| ObjectStorageError::CannotUploadFile {
+        bucket_name: bucket_name.to_string(),
+        file_name: object_key.to_string(),
+        raw_error_message,
+    };
+
+    if part_size_bytes < MULTIPART_UPLOAD_MIN_PART_SIZE_BYTES {
+        return Err(to_error(format!(
+            "part size {} bytes is below the {} bytes minimum",
+            part_size_bytes, MULTIPART_UPLOAD_MIN_PART_SIZE_BYTES
+        )));
+    }
+
+    upload_file(
+        &S3ObjectUploader::new(client, bucket_name, object_key),
+        file_path,
+        &MultipartUploadOptions {
+            part_size_bytes,
+            ..Default::default()
+        },
+    )
+    .map_err(to_error)
+}
+
+/// S3 API implementation, shared by every S3 compatible object storage.
+pub struct S3ObjectUploader<'a> {
+    client: &'a S3Client,
+    bucket_name: &'a str,
+    object_key: &'a str,
+}
+
+impl<'a> S3ObjectUploader<'a> {
+    pub fn new(client: &'a S3Client, bucket_name: &'a str, object_key: &'a str) -> Self {
+        S3ObjectUploader {
+            client,
+            bucket_name,
+            object_key,
+        }
+    }
+}
+
+impl ObjectUploader for S3ObjectUploader<'_> {
+    fn put_object(&self, body: Vec<u8>) -> Result<(), String> {
+        block_on(self.client.put_object(PutObjectRequest {
+            bucket: self.bucket_name.to_string(),
+            key: self.object_key.to_string(),
+            body: Some(StreamingBody::from(body)),
+            ..Default::default()
+        }))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+
+    fn create_multipart_upload(&self) -> Result<String, String> {
+        let output = block_on(self.client.create_multipart_upload(CreateMultipartUploadRequest {
+            bucket: self.bucket_name.to_string(),
+            key: self.object_key.to_string(),
+            ..Default::default()
+        }))
+        .map_err(|e| e.to_string())?;
+
+        output
+            .upload_id
+            .ok_or_else(|| "no upload id returned for multipart upload".to_string())
+    }
+
+    fn upload_part(&self, upload_id: &str, part_number: i64, body: Vec<u8>) -> Result<String, String> {
+        let output = block_on(self.client.upload_part(UploadPartRequest {
+            bucket: self.bucket_name.to_string(),
+            key: self.object_key.to_string(),
+            upload_id: upload_id.to_string(),
+            part_number,
+            content_length: Some(body.len() as i64),
+            body: Some(StreamingBody::from(body)),
+            ..Default::default()
+        }))
+        .map_err(|e| e.to_string())?;
+
+        output
+            .e_tag
+            .ok_or_else(|| format!("no ETag returned for part {}", part_number))
+    }
+
+    fn complete_multipart_upload(&self, upload_id: &str, parts: Vec<CompletedPart>) -> Result<(), String> {
+        block_on(self.client.complete_multipart_upload(CompleteMultipartUploadRequest {
+            bucket: self.bucket_name.to_string(),
+            key: self.object_key.to_string(),
+            upload_id: upload_id.to_string(),
+            multipart_upload: Some(CompletedMultipartUpload { parts: Some(parts) }),
+            ..Default::default()
+        }))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+
+    fn abort_multipart_upload(&self, upload_id: &str) -> Result<(), String> {
+        block_on(self.client.abort_multipart_upload(AbortMultipartUploadRequest {
+            bucket: self.bucket_name.to_string(),
+            key: self.object_key.to_string(),
+            upload_id: upload_id.to_string(),
+            ..Default::default()
+        }))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::object_storage::errors::ObjectStorageError;
+    use crate::object_storage::multipart::{
+        upload_file, upload_large_file, MultipartUploadOptions, ObjectUploader, S3ObjectUploader,
+    };
+    use rusoto_core::{HttpClient, Region};
+    use rusoto_credential::StaticProvider;
+    use rusoto_s3::{CompletedPart, S3Client};
+    use std::collections::BTreeMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use tempfile::NamedTempFile;
+
+    #[derive(Default)]
+    struct FakeUploader {
+        // part number that always fails
+        failing_part: Option<i64>,
+        calls: Mutex<Vec<String>>,
+        // parts currently stored by the provider for the pending upload
+        stored_parts: Mutex<BTreeMap<i64, Vec<u8>>>,
+        object: Mutex<Option<Vec<u8>>>,
+    }
+
+    impl ObjectUploader for FakeUploader {
+        fn put_object(&self, body: Vec<u8>) -> Result<(), String> {
+            self.calls.lock().unwrap().push("put_object".to_string());
+            *self.object.lock().unwrap() = Some(body);
+            Ok(())
+        }
+
+        fn create_multipart_upload(&self) -> Result<String, String> {
+            self.calls.lock().unwrap().push("create".to_string());
+            Ok("upload-id".to_string())
+        }
+
+        fn upload_part(&self, _upload_id: &str, part_number: i64, body: Vec<u8>) -> Result<String, String> {
+            self.calls.lock().unwrap().push(format!("part_{}", part_number));
+            if self.failing_part == Some(part_number) {
+                return Err("connection reset".to_string());
+            }
+            self.stored_parts.lock().unwrap().insert(part_number, body);
+            Ok(format!("etag-{}", part_number))
+        }
+
+        fn complete_multipart_upload(&self, _upload_id: &str, parts: Vec<CompletedPart>) -> Result<(), String> {
+            self.calls.lock().unwrap().push("complete".to_string());
+            let mut stored_parts = self.stored_parts.lock().unwrap();
+            let object = parts
+                .iter()
+                .flat_map(|part| stored_parts[&part.part_number.unwrap()].clone())
+                .collect();
+            stored_parts.clear();
+            *self.object.lock().unwrap() = Some(object);
+            Ok(())
+        }
+
+        fn abort_multipart_upload(&self, _upload_id: &str) -> Result<(), String> {
+            self.calls.lock().unwrap().push("abort".to_string());
+            self.stored_parts.lock().unwrap().clear();
+            Ok(())
+        }
+    }
+
+    fn options() -> MultipartUploadOptions {
+        MultipartUploadOptions {
+            threshold_bytes: 10,
+            part_size_bytes: 10,
+            part_max_attempts: 3,
+            part_retry_delay_millis: 1,
+        }
+    }
+
+    fn file_with_content(content: &[u8]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().expect("cannot create temp file");
+        file.write_all(content).expect("cannot write temp file");
+        file
+    }
+
+    #[test]
+    fn test_small_file_is_uploaded_in_a_single_request() {
+        // setup:
+        let file = file_with_content(b"0123456789");
+        let uploader = FakeUploader::default();
+
+        // execute:
+        let result = upload_file(&uploader, file.path().to_str().unwrap(), &options());
+
+        // verify:
+        assert!(result.is_ok());
+        assert_eq!(vec!["put_object".to_string()], *uploader.calls.lock().unwrap());
+        assert_eq!(Some(b"0123456789".to_vec()), *uploader.object.lock().unwrap());
+    }
+
+    #[test]
+    fn test_file_above_threshold_is_uploaded_using_multipart() {
+        // setup:
+        let content = b"0123456789abcdefghijABCDE";
+        let file = file_with_content(content);
+        let uploader = FakeUploader::default();
+
+        // execute:
+        let result = upload_file(&uploader, file.path().to_str().unwrap(), &options());
+
+        // verify:
+        assert!(result.is_ok());
+        assert_eq!(
+            vec!["create", "part_1", "part_2", "part_3", "complete"],
+            *uploader.calls.lock().unwrap()
+        );
+        assert_eq!(Some(content.to_vec()), *uploader.object.lock().unwrap());
+        assert!(uploader.stored_parts.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failing_part_is_retried_then_upload_is_aborted() {
+        // setup:
+        let file = file_with_content(b"0123456789abcdefghijABCDE");
+        let uploader = FakeUploader {
+            failing_part: Some(2),
+            ..Default::default()
+        };
+
+        // execute:
+        let result = upload_file(&uploader, file.path().to_str().unwrap(), &options());
+
+        // verify:
+        let err = result.expect_err("upload must fail");
+        assert!(err.contains("part 2 failed after 3 attempts: connection reset"));
+        assert_eq!(
+            vec!["create", "part_1", "part_2", "part_2", "part_2", "abort"],
+            *uploader.calls.lock().unwrap()
+        );
+        // no orphan part is kept by the provider, and nothing has been written
+        assert!(uploader.stored_parts.lock().unwrap().is_empty());
+        assert_eq!(None, *uploader.object.lock().unwrap());
+    }
+
+    /// Minimal fake S3 endpoint handling multipart uploads, every received request is recorded as (method, path, body).
+    fn start_fake_s3_endpoint() -> (S3Client, Arc<Mutex<Vec<(String, String, String)>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded_requests = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                let _ = reader.read_line(&mut request_line);
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    if reader.read_line(&mut header).unwrap_or(0) == 0 || header == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = header.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+                let mut body = vec![0; content_length];
+                let _ = reader.read_exact(&mut body);
+
+                let mut request = request_line.split_whitespace();
+                let method = request.next().unwrap_or_default().to_string();
+                let path = request.next().unwrap_or_default().to_string();
+                let (headers, response) = if method == "POST" && path.ends_with("?uploads=") {
+                    (
+                        "".to_string(),
+                        "<InitiateMultipartUploadResult><Bucket>bucket</Bucket><Key>key</Key><UploadId>upload-id</UploadId></InitiateMultipartUploadResult>".to_string(),
+                    )
+                } else if method == "PUT" && path.contains("partNumber=") {
+                    let part_number = path.split("partNumber=").nth(1).unwrap_or_default().split('&').next();
+                    (
+                        format!("ETag: \"etag-{}\"\r\n", part_number.unwrap_or_default()),
+                        "".to_string(),
+                    )
+                } else if method == "POST" && path.contains("uploadId=upload-id") {
+                    (
+                        "".to_string(),
+                        "<CompleteMultipartUploadResult><Bucket>bucket</Bucket><Key>key</Key><ETag>\"etag\"</ETag></CompleteMultipartUploadResult>".to_string(),
+                    )
+                } else {
+                    ("".to_string(), "".to_string())
+                };
+                recorded_requests
+                    .lock()
+                    .unwrap()
+                    .push((method, path, String::from_utf8_lossy(&body).to_string()));
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    headers,
+                    response.len(),
+                    response
+                );
+            }
+        });
+
+        let client = S3Client::new_with(
+            HttpClient::new().unwrap(),
+            StaticProvider::new_minimal("access_key".to_string(), "secret_key".to_string()),
+            Region::Custom {
+                name: "fake".to_string(),
+                endpoint,
+            },
+        );
+
+        (client, requests)
+    }
+
+    #[test]
+    fn test_multipart_upload_against_s3_endpoint() {
+        // setup:
+        let content = b"0123456789abcdefghijABCDE";
+        let file = file_with_content(content);
+        let (client, requests) = start_fake_s3_endpoint();
+
+        // execute:
+        let result = upload_file(
+            &S3ObjectUploader::new(&client, "bucket", "key"),
+            file.path().to_str().unwrap(),
+            &options(),
+        );
+
+        // verify:
+        assert!(result.is_ok(), "{:?}", result);
+        let requests = requests.lock().unwrap();
+        let calls: Vec<(&str, &str)> = requests
+            .iter()
+            .map(|(method, path, _)| (method.as_str(), path.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("POST", "/bucket/key?uploads="),
+                ("PUT", "/bucket/key?partNumber=1&uploadId=upload-id"),
+                ("PUT", "/bucket/key?partNumber=2&uploadId=upload-id"),
+                ("PUT", "/bucket/key?partNumber=3&uploadId=upload-id"),
+                ("POST", "/bucket/key?uploadId=upload-id"),
+            ],
+            calls
+        );
+        let uploaded_parts: Vec<&str> = requests[1..4].iter().map(|(_, _, body)| body.as_str()).collect();
+        assert_eq!(vec!["0123456789", "abcdefghij", "ABCDE"], uploaded_parts);
+        // every uploaded part is referenced when completing the upload
+        let complete_body = &requests[4].2;
+        for part_number in 1..=3 {
+            assert!(complete_body.contains(&format!("<PartNumber>{}</PartNumber>", part_number)));
+            assert!(complete_body.contains(&format!("etag-{}", part_number)));
+        }
+    }
+
+    #[test]
+    fn test_upload_large_file_rejects_too_small_parts() {
+        // setup:
+        let file = file_with_content(b"0123456789");
+        let (client, requests) = start_fake_s3_endpoint();
+
+        // execute:
+        let result = upload_large_file(&client, "bucket", "key", file.path().to_str().unwrap(), 1024);
+
+        // verify:
+        assert!(matches!(result, Err(ObjectStorageError::CannotUploadFile { .. })));
+        assert!(requests.lock().unwrap().is_empty());
+    }
+}
@@ -10,14 +10,14 @@ use rusoto_core::{Client, HttpClient, Region as RusotoRegion};
 use rusoto_s3::{
     CreateBucketConfiguration, CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectRequest,
     DeleteObjectsRequest, GetObjectRequest, HeadBucketRequest, ListObjectsRequest, ObjectIdentifier,
-    PutBucketTaggingRequest, PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging,
-    S3 as RusotoS3,
+    PutBucketTaggingRequest, PutBucketVersioningRequest, S3Client, Tag, Tagging, S3 as RusotoS3,
 };
 use tokio::io;
 
 use crate::io_models::context::Context;
 use crate::io_models::domain::StringPath;
 use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::multipart::{upload_large_file, MULTIPART_UPLOAD_PART_SIZE_BYTES};
 use crate::object_storage::{Kind, ObjectStorage};
 use crate::runtime::block_on;
 
@@ -320,33 +320,19 @@ impl ObjectStorage for S3 {
     }
 
     fn put(&self, bucket_name: &str, object_key: &str, file_path: &str) -> Result<(), ObjectStorageError> {
-        S3::is_bucket_name_valid(bucket_name)?;
+        self.upload_large_file(bucket_name, object_key, file_path, MULTIPART_UPLOAD_PART_SIZE_BYTES)
+    }
 
-        let s3_client = self.get_s3_client();
+    fn upload_large_file(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        file_path: &str,
+        part_size_bytes: u64,
+    ) -> Result<(), ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
 
-        match block_on(s3_client.put_object(PutObjectRequest {
-            bucket: bucket_name.to_string(),
-            key: object_key.to_string(),
-            body: Some(StreamingBody::from(match std::fs::read(file_path) {
-                Ok(x) => x,
-                Err(e) => {
-                    return Err(ObjectStorageError::CannotUploadFile {
-                        bucket_name: bucket_name.to_string(),
-                        file_name: object_key.to_string(),
-                        raw_error_message: e.to_string(),
-                    })
-                }
-            })),
-            expected_bucket_owner: None,
-            ..Default::default()
-        })) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(ObjectStorageError::CannotUploadFile {
-                bucket_name: bucket_name.to_string(),
-                file_name: object_key.to_string(),
-                raw_error_message: e.to_string(),
-            }),
-        }
+        upload_large_file(&self.get_s3_client(), bucket_name, object_key, file_path, part_size_bytes)
     }
 
     fn ensure_file_is_absent(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
@@ -7,14 +7,16 @@ use rusoto_core::{Client, HttpClient, Region};
 use rusoto_credential::StaticProvider;
 use rusoto_s3::{
     CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectRequest, DeleteObjectsRequest, GetObjectRequest,
-    HeadBucketRequest, ListObjectsRequest, ObjectIdentifier, PutObjectRequest, S3Client, StreamingBody, S3,
+    HeadBucketRequest, ListObjectsRequest, ObjectIdentifier, S3Client, S3,
 };
 use tokio::io;
 
+use crate::cloud_provider::digitalocean::spaces_endpoint;
 use crate::io_models::context::Context;
 use crate::io_models::domain::StringPath;
 use crate::models::digital_ocean::DoRegion;
 use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::multipart::{upload_large_file, MULTIPART_UPLOAD_PART_SIZE_BYTES};
 use crate::object_storage::{Kind, ObjectStorage};
 use crate::runtime::block_on;
 
@@ -55,7 +57,7 @@ impl Spaces {
     }
 
     fn get_endpoint_url_for_region(&self) -> String {
-        format!("https://{}.digitaloceanspaces.com", self.region)
+        spaces_endpoint(&self.region.to_string())
     }
 
     fn get_credentials(&self) -> StaticProvider {
@@ -160,7 +162,7 @@ impl Spaces {
     {
         let region = Region::Custom {
             name: self.region.to_string(),
-            endpoint: format!("https://{}.digitaloceanspaces.com", self.region),
+            endpoint: spaces_endpoint(&self.region.to_string()),
         };
 
         let credentials = StaticProvider::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None);
@@ -348,32 +350,19 @@ impl ObjectStorage for Spaces {
 
     fn put(&self, bucket_name: &str, object_key: &str, file_path: &str) -> Result<(), ObjectStorageError> {
         // TODO(benjamin): switch to `digitalocean-api-rs` once we'll made the auo-generated lib
-        Spaces::is_bucket_name_valid(bucket_name)?;
+        self.upload_large_file(bucket_name, object_key, file_path, MULTIPART_UPLOAD_PART_SIZE_BYTES)
+    }
 
-        let s3_client = self.get_s3_client();
+    fn upload_large_file(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        file_path: &str,
+        part_size_bytes: u64,
+    ) -> Result<(), ObjectStorageError> {
+        Spaces::is_bucket_name_valid(bucket_name)?;
 
-        match block_on(s3_client.put_object(PutObjectRequest {
-            bucket: bucket_name.to_string(),
-            key: object_key.to_string(),
-            body: Some(StreamingBody::from(match std::fs::read(file_path) {
-                Ok(x) => x,
-                Err(e) => {
-                    return Err(ObjectStorageError::CannotUploadFile {
-                        bucket_name: bucket_name.to_string(),
-                        file_name: object_key.to_string(),
-                        raw_error_message: e.to_string(),
-                    })
-                }
-            })),
-            ..Default::default()
-        })) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(ObjectStorageError::CannotUploadFile {
-                bucket_name: bucket_name.to_string(),
-                file_name: object_key.to_string(),
-                raw_error_message: e.to_string(),
-            }),
-        }
+        upload_large_file(&self.get_s3_client(), bucket_name, object_key, file_path, part_size_bytes)
     }
 
     fn ensure_file_is_absent(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
@@ -0,0 +1,230 @@
+use crate::clock::{Clock, SystemClock};
+use std::cmp::min;
+use std::time::Duration;
+
+/// Result of a single poll attempt.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PollState<T> {
+    Ready(T),
+    Pending,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PollError<E> {
+    /// Deadline has been reached before the operation resolved.
+    Timeout { elapsed: Duration, attempts: u32 },
+    /// Operation returned a terminal error, polling has been aborted.
+    Operation(E),
+}
+
+/// Delay between two poll attempts, starting at `initial_delay` and multiplied by `multiplier`
+/// after each attempt, without exceeding `max_delay`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExponentialBackoff {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f32,
+}
+
+impl ExponentialBackoff {
+    pub fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        ExponentialBackoff {
+            initial_delay,
+            max_delay,
+            multiplier: 2.0,
+        }
+    }
+
+    pub fn delays(&self) -> impl Iterator<Item = Duration> {
+        let (max_delay, multiplier) = (self.max_delay, self.multiplier);
+        std::iter::successors(Some(min(self.initial_delay, max_delay)), move |delay| {
+            // capped in floating point, so a large multiplier can't overflow the duration
+            let next_delay = (delay.as_secs_f64() * f64::from(multiplier)).max(0.0);
+            Some(Duration::from_secs_f64(next_delay.min(max_delay.as_secs_f64())))
+        })
+    }
+}
+
+/// Calls `op` until it resolves, returns a terminal error or `timeout` is reached.
+/// The operation is always attempted at least once, and waits between attempts never go past the deadline.
+pub fn poll_until<T, E>(
+    op: impl FnMut() -> Result<PollState<T>, E>,
+    backoff: ExponentialBackoff,
+    timeout: Duration,
+) -> Result<T, PollError<E>> {
+    poll_until_with_clock(op, backoff, timeout, &SystemClock)
+}
+
+/// Same as `poll_until`, reading and waiting for time through the given clock.
+pub fn poll_until_with_clock<T, E>(
+    mut op: impl FnMut() -> Result<PollState<T>, E>,
+    backoff: ExponentialBackoff,
+    timeout: Duration,
+    clock: &dyn Clock,
+) -> Result<T, PollError<E>> {
+    let started_at = clock.now();
+    let deadline = started_at + timeout;
+    let mut attempts: u32 = 0;
+
+    for delay in backoff.delays() {
+        attempts += 1;
+        match op() {
+            Ok(PollState::Ready(value)) => return Ok(value),
+            Ok(PollState::Pending) => {}
+            Err(err) => return Err(PollError::Operation(err)),
+        }
+
+        let now = clock.now();
+        if now >= deadline {
+            break;
+        }
+
+        clock.sleep(min(delay, deadline - now));
+    }
+
+    Err(PollError::Timeout {
+        elapsed: clock.now() - started_at,
+        attempts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::{Clock, MockClock};
+    use crate::poller::{poll_until, poll_until_with_clock, ExponentialBackoff, PollError, PollState};
+    use chrono::Utc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_exponential_backoff_delays() {
+        // setup:
+        let backoff = ExponentialBackoff::new(Duration::from_secs(1), Duration::from_secs(10));
+
+        // execute:
+        let delays: Vec<Duration> = backoff.delays().take(6).collect();
+
+        // verify:
+        assert_eq!(
+            vec![1, 2, 4, 8, 10, 10]
+                .into_iter()
+                .map(Duration::from_secs)
+                .collect::<Vec<Duration>>(),
+            delays
+        );
+    }
+
+    #[test]
+    fn test_poll_until_resolves_before_deadline() {
+        // setup:
+        let mut attempts = 0;
+
+        // execute:
+        let result: Result<&str, PollError<()>> = poll_until(
+            || {
+                attempts += 1;
+                match attempts {
+                    3 => Ok(PollState::Ready("ready")),
+                    _ => Ok(PollState::Pending),
+                }
+            },
+            ExponentialBackoff::new(Duration::from_millis(1), Duration::from_millis(5)),
+            Duration::from_secs(10),
+        );
+
+        // verify:
+        assert_eq!(Ok("ready"), result);
+        assert_eq!(3, attempts);
+    }
+
+    #[test]
+    fn test_poll_until_timeout() {
+        // execute:
+        let result: Result<(), PollError<()>> = poll_until(
+            || Ok(PollState::Pending),
+            ExponentialBackoff::new(Duration::from_millis(5), Duration::from_millis(20)),
+            Duration::from_millis(100),
+        );
+
+        // verify:
+        match result {
+            Err(PollError::Timeout { elapsed, attempts }) => {
+                assert!(elapsed >= Duration::from_millis(100));
+                assert!(attempts > 1);
+            }
+            _ => panic!("expected a timeout, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_poll_until_terminal_error_aborts() {
+        // setup:
+        let mut attempts = 0;
+
+        // execute:
+        let result: Result<(), PollError<&str>> = poll_until(
+            || {
+                attempts += 1;
+                match attempts {
+                    2 => Err("boom"),
+                    _ => Ok(PollState::Pending),
+                }
+            },
+            ExponentialBackoff::new(Duration::from_millis(1), Duration::from_millis(5)),
+            Duration::from_secs(10),
+        );
+
+        // verify:
+        assert_eq!(Err(PollError::Operation("boom")), result);
+        assert_eq!(2, attempts);
+    }
+
+    #[test]
+    fn test_poll_until_with_clock_deterministic_timeout() {
+        // setup:
+        let clock = MockClock::new(Utc::now());
+        let mut attempts_at = vec![];
+
+        // execute:
+        let result: Result<(), PollError<()>> = poll_until_with_clock(
+            || {
+                attempts_at.push(clock.elapsed().as_secs());
+                Ok(PollState::Pending)
+            },
+            ExponentialBackoff::new(Duration::from_secs(1), Duration::from_secs(10)),
+            Duration::from_secs(30),
+            &clock,
+        );
+
+        // verify: backoff is 1, 2, 4, 8, 10 then shortened to not go past the deadline
+        assert_eq!(vec![0, 1, 3, 7, 15, 25, 30], attempts_at);
+        assert_eq!(
+            Err(PollError::Timeout {
+                elapsed: Duration::from_secs(30),
+                attempts: 7
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn test_poll_until_with_clock_resolves_before_deadline() {
+        // setup:
+        let clock = MockClock::new(Utc::now());
+        let started_at = clock.now();
+
+        // execute: operation is ready once 10 seconds have elapsed
+        let result: Result<u64, PollError<()>> = poll_until_with_clock(
+            || match clock.elapsed() >= Duration::from_secs(10) {
+                true => Ok(PollState::Ready(clock.elapsed().as_secs())),
+                false => Ok(PollState::Pending),
+            },
+            ExponentialBackoff::new(Duration::from_secs(2), Duration::from_secs(60)),
+            Duration::from_secs(60),
+            &clock,
+        );
+
+        // verify: attempts at 0, 2, 6 then 14
+        assert_eq!(Ok(14), result);
+        assert_eq!(Duration::from_secs(14), clock.now() - started_at);
+    }
+}
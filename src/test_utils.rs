@@ -0,0 +1,169 @@
+use crate::cloud_provider::Kind;
+use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
+use crate::io_models::QoveryIdentifier;
+use crate::runtime::block_on;
+use std::convert::TryFrom;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use uuid::Uuid;
+
+/// Request received by the fake HTTP server.
+pub struct FakeHttpRequest {
+    pub method: String,
+    /// Path with its query string.
+    pub path: String,
+    pub body: String,
+}
+
+/// Response sent back by the fake HTTP server.
+pub struct FakeHttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl FakeHttpResponse {
+    pub fn new<S: Into<String>>(status: u16, body: S) -> Self {
+        FakeHttpResponse {
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    pub fn header<S: Into<String>>(mut self, name: &str, value: S) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+}
+
+/// Minimal HTTP/1.1 server listening on a random local port, answering every request with the response returned
+/// by the handler. Returns the server base url, e.g `http://127.0.0.1:12345`.
+pub fn start_fake_http_server<F>(handler: F) -> String
+where
+    F: Fn(&FakeHttpRequest) -> FakeHttpResponse + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let request = match read_request(&mut BufReader::new(stream.try_clone().unwrap())) {
+                Some(request) => request,
+                None => continue,
+            };
+
+            let response = handler(&request);
+            let headers: String = response
+                .headers
+                .iter()
+                .map(|(name, value)| format!("{}: {}\r\n", name, value))
+                .collect();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} Status\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.status,
+                headers,
+                response.body.len(),
+                response.body
+            );
+        }
+    });
+
+    format!("http://{}", address)
+}
+
+/// Minimal fake kubernetes API server, answering every request with the (status, body) returned by the handler
+/// from the request method, path and body.
+pub fn start_fake_kube_api_server<F>(handler: F) -> kube::Client
+where
+    F: Fn(&str, &str, &str) -> (u16, String) + Send + 'static,
+{
+    let url = start_fake_http_server(move |request| {
+        let (status, body) = handler(&request.method, &request.path, &request.body);
+        FakeHttpResponse::new(status, body).header("Content-Type", "application/json")
+    });
+
+    let config = kube::Config::new(url.parse().unwrap());
+    // kube client must be created within the tokio runtime
+    block_on(async { kube::Client::try_from(config) }).unwrap()
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Option<FakeHttpRequest> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).unwrap_or(0) == 0 || header == "\r\n" {
+            break;
+        }
+        if let Some(value) = header.to_lowercase().strip_prefix("content-length:") {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+
+    let mut request = request_line.split_whitespace();
+    Some(FakeHttpRequest {
+        method: request.next().unwrap_or_default().to_string(),
+        path: request.next().unwrap_or_default().to_string(),
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+/// Builds `EventDetails` for tests, with random organization, cluster and execution ids.
+///
+/// Defaults to an application deployment event, without cloud provider.
+pub struct EventDetailsBuilder {
+    provider_kind: Option<Kind>,
+    stage: Stage,
+    transmitter: Transmitter,
+}
+
+impl Default for EventDetailsBuilder {
+    fn default() -> Self {
+        EventDetailsBuilder {
+            provider_kind: None,
+            stage: Stage::Environment(EnvironmentStep::Deploy),
+            transmitter: Transmitter::Application(Uuid::new_v4(), "app".to_string()),
+        }
+    }
+}
+
+impl EventDetailsBuilder {
+    pub fn new() -> Self {
+        EventDetailsBuilder::default()
+    }
+
+    pub fn provider_kind(mut self, provider_kind: Kind) -> Self {
+        self.provider_kind = Some(provider_kind);
+        self
+    }
+
+    pub fn stage(mut self, stage: Stage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    pub fn transmitter(mut self, transmitter: Transmitter) -> Self {
+        self.transmitter = transmitter;
+        self
+    }
+
+    pub fn build(self) -> EventDetails {
+        EventDetails::new(
+            self.provider_kind,
+            QoveryIdentifier::new_random(),
+            QoveryIdentifier::new_random(),
+            Uuid::new_v4().to_string(),
+            self.stage,
+            self.transmitter,
+        )
+    }
+}
@@ -269,6 +269,7 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
             encrypt_disk: false,
             activate_high_availability: false,
             activate_backups: false,
+            connection_pooler: None,
             publicly_accessible: false,
             mode: CONTAINER,
         }];
@@ -2,14 +2,17 @@ use crate::helm::{
     application_context, chart_path, container_context, container_database_context, job_context, kubeconfig_path,
     lib_dir, managed_database_context,
 };
+use k8s_openapi::api::core::v1::ConfigMap;
 use kube::core::DynamicObject;
 use qovery_engine::cloud_provider::helm::CommonChart;
 use qovery_engine::cloud_provider::helm::{ChartInfo, HelmAction, HelmChartNamespaces};
+use qovery_engine::cloud_provider::helm_charts::coredns_config_chart::CoreDNSConfigChart;
 use qovery_engine::cmd::helm::Helm;
 use qovery_engine::deployment_action::deploy_helm::HelmDeployment;
 use qovery_engine::events::EventDetails;
 use std::collections::HashMap;
 use std::fs::{read_dir, File};
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::{env, fs};
 use tera::Context;
@@ -102,12 +105,16 @@ fn q_ingress_test() {
             wait: false,
             values: vec![],
             values_string: vec![],
+            values_json: vec![],
             values_files: vec![],
             yaml_files_content: vec![],
             parse_stderr_for_error: false,
             k8s_selector: None,
             backup_resources: None,
             crds_update: None,
+            post_renderer: None,
+            depends_on: vec![],
+            repository: None,
         },
         chart_installation_checker: None,
     };
@@ -146,12 +153,16 @@ fn q_container_test() {
             wait: false,
             values: vec![],
             values_string: vec![],
+            values_json: vec![],
             values_files: vec![],
             yaml_files_content: vec![],
             parse_stderr_for_error: false,
             k8s_selector: None,
             backup_resources: None,
             crds_update: None,
+            post_renderer: None,
+            depends_on: vec![],
+            repository: None,
         },
         chart_installation_checker: None,
     };
@@ -186,12 +197,16 @@ fn q_application_test() {
             wait: false,
             values: vec![],
             values_string: vec![],
+            values_json: vec![],
             values_files: vec![],
             yaml_files_content: vec![],
             parse_stderr_for_error: false,
             k8s_selector: None,
             backup_resources: None,
             crds_update: None,
+            post_renderer: None,
+            depends_on: vec![],
+            repository: None,
         },
         chart_installation_checker: None,
     };
@@ -226,12 +241,16 @@ fn q_container_psql_test() {
             wait: false,
             values: vec![],
             values_string: vec![],
+            values_json: vec![],
             values_files: vec![],
             yaml_files_content: vec![],
             parse_stderr_for_error: false,
             k8s_selector: None,
             backup_resources: None,
             crds_update: None,
+            post_renderer: None,
+            depends_on: vec![],
+            repository: None,
         },
         chart_installation_checker: None,
     };
@@ -266,12 +285,16 @@ fn q_managed_psql_test() {
             wait: false,
             values: vec![],
             values_string: vec![],
+            values_json: vec![],
             values_files: vec![],
             yaml_files_content: vec![],
             parse_stderr_for_error: false,
             k8s_selector: None,
             backup_resources: None,
             crds_update: None,
+            post_renderer: None,
+            depends_on: vec![],
+            repository: None,
         },
         chart_installation_checker: None,
     };
@@ -306,12 +329,16 @@ fn q_job_test() {
             wait: false,
             values: vec![],
             values_string: vec![],
+            values_json: vec![],
             values_files: vec![],
             yaml_files_content: vec![],
             parse_stderr_for_error: false,
             k8s_selector: None,
             backup_resources: None,
             crds_update: None,
+            post_renderer: None,
+            depends_on: vec![],
+            repository: None,
         },
         chart_installation_checker: None,
     };
@@ -324,3 +351,38 @@ fn q_job_test() {
     );
     assert!(!resources.is_empty());
 }
+
+#[cfg(feature = "test-local-kube")]
+#[test]
+fn coredns_config_custom_dns_resolvers_test() {
+    for provider in ["aws", "aws-ec2", "digitalocean", "scaleway"] {
+        // setup:
+        let chart = CoreDNSConfigChart::new(
+            Some(format!("{}/{}/bootstrap", lib_dir(), provider).as_str()),
+            false,
+            "{qovery.io}".to_string(),
+            "{1.1.1.1,8.8.8.8}".to_string(),
+            vec![IpAddr::from([10, 0, 0, 2]), IpAddr::from([10, 0, 0, 1])],
+        );
+
+        // execute:
+        let template_dir = generate_template(&chart.chart_info);
+
+        // verify:
+        let configmap_path = format!("{}/coredns-config/templates/configmap.yml", template_dir);
+        let file = File::open(&configmap_path).unwrap_or_else(|_| panic!("Unable to open file {}", &configmap_path));
+        let configmap: ConfigMap =
+            serde_yaml::from_reader(file).unwrap_or_else(|_| panic!("Unable to parse file {}", &configmap_path));
+        let corefile = configmap
+            .data
+            .unwrap_or_default()
+            .remove("Corefile")
+            .unwrap_or_default();
+        let (cluster_block, managed_dns_block) = corefile
+            .split_once("qovery.io:53")
+            .unwrap_or_else(|| panic!("No managed DNS block in {} Corefile: {}", provider, corefile));
+        assert!(cluster_block.contains("forward . 10.0.0.2 10.0.0.1\n"), "{}", corefile);
+        assert!(!cluster_block.contains("/etc/resolv.conf"), "{}", corefile);
+        assert!(managed_dns_block.contains("forward . 1.1.1.1 8.8.8.8\n"), "{}", corefile);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::helm::{deployment_target, infra_ctx_with_logger, test_environment, test_kubernetes};
+use k8s_openapi::api::core::v1::Namespace;
+use kube::Api;
+use qovery_engine::cloud_provider::service::Action;
+use qovery_engine::deployment_action::deploy_namespace::NamespaceDeployment;
+use qovery_engine::deployment_action::DeploymentAction;
+use qovery_engine::events::{EngineEvent, EnvironmentStep, EventMessageVerbosity};
+use qovery_engine::logger::Logger;
+use qovery_engine::runtime::block_on;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct RecordingLogger {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl Logger for RecordingLogger {
+    fn log(&self, event: EngineEvent) {
+        self.messages
+            .lock()
+            .unwrap()
+            .push(event.message(EventMessageVerbosity::SafeOnly));
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Logger> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_namespace_is_not_created_on_dry_run() {
+    // setup:
+    let test_kube = test_kubernetes();
+    let test_env = test_environment(test_kube.as_ref());
+    let logger = RecordingLogger::default();
+    let infra_ctx = infra_ctx_with_logger(test_kube.as_ref(), Box::new(logger.clone()));
+    let mut target = deployment_target(test_kube.as_ref(), &test_env, &infra_ctx);
+    target.is_dry_run_deploy = true;
+    let namespace_deployment = NamespaceDeployment {
+        resource_expiration: None,
+        event_details: test_env.event_details_with_step(EnvironmentStep::Deploy),
+    };
+
+    // execute:
+    let result = namespace_deployment.exec_action(&target, Action::Create);
+
+    // verify:
+    assert!(result.is_ok());
+    let namespaces: Api<Namespace> = Api::all(target.kube.clone());
+    assert!(block_on(namespaces.get_opt(test_env.namespace()))
+        .expect("cannot get namespaces")
+        .is_none());
+    assert!(
+        logger
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|message| message
+                .contains(&format!("Dry run, skipping: creation of namespace {}", test_env.namespace())))
+    );
+}
@@ -17,10 +17,13 @@ use qovery_engine::cloud_provider::service::Action;
 use qovery_engine::cloud_provider::DeploymentTarget;
 use qovery_engine::engine::InfrastructureContext;
 use qovery_engine::events::{EnvironmentStep, EventDetails, Stage};
-use qovery_engine::io_models::application::{AdvancedSettingsProbeType, ApplicationAdvancedSettings, Port, Protocol};
+use qovery_engine::io_models::application::{
+    AdvancedSettingsDeploymentStrategy, AdvancedSettingsProbeType, ApplicationAdvancedSettings, Port, Protocol,
+};
 use qovery_engine::io_models::container::{ContainerAdvancedSettings, Registry};
 use qovery_engine::io_models::database::{DatabaseMode, DatabaseOptions};
 use qovery_engine::io_models::job::{JobAdvancedSettings, JobSchedule};
+use qovery_engine::logger::Logger;
 use qovery_engine::models::application::Application;
 use qovery_engine::models::aws::{AwsAppExtraSettings, AwsRouterExtraSettings, AwsStorageType};
 use qovery_engine::models::container::Container;
@@ -29,7 +32,7 @@ use qovery_engine::models::job::Job;
 use qovery_engine::models::router::{Router, RouterAdvancedSettings};
 use qovery_engine::models::types::{VersionsNumber, AWS as AWSType};
 use qovery_engine::utilities::to_short_id;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::string::ToString;
@@ -42,6 +45,10 @@ use uuid::Uuid;
 
 mod cert_manager;
 mod chart_testing;
+#[cfg(feature = "test-local-kube")]
+mod dry_run;
+#[cfg(feature = "test-local-kube")]
+mod restart;
 
 fn lib_dir() -> String {
     env::var("LIB_ROOT_DIR").expect("Missing environment variable LIB_ROOT_DIR")
@@ -109,8 +116,23 @@ fn test_kubernetes() -> Box<dyn Kubernetes> {
                 registry_image_retention_time_sec: 1,
                 pleco_resources_ttl: 2,
                 loki_log_retention_in_week: 3,
+                loki_s3_use_path_style: None,
+                loki_s3_insecure: false,
+                loki_s3_sse_type: None,
+                loki_s3_sse_kms_key_id: None,
+                prometheus_remote_write_url: None,
+                prometheus_remote_write_auth_type: None,
+                prometheus_remote_write_auth_secret_name: None,
                 aws_iam_user_mapper_group_name: "my_aws_iam_user_mapper_group_name".to_string(),
                 cloud_provider_container_registry_tags: HashMap::new(),
+                terraform_parallelism: None,
+                kubeconfig_max_size_bytes: 16 * 1024,
+                dns_custom_resolvers: vec![],
+                helm_charts_timeout_override_sec: HashMap::new(),
+                helm_charts_max_parallelism: 3,
+                deployment_max_parallelism: 1,
+                environment_namespace_labels: HashMap::new(),
+                environment_namespace_annotations: HashMap::new(),
             },
         )
         .unwrap(),
@@ -229,6 +251,8 @@ pub fn test_application(test_kube: &dyn Kubernetes) -> Application<AWSType> {
                 dockerfile_path: Some(PathBuf::from("my_dockerfile_path")),
                 root_path: PathBuf::from("my_root_path"),
                 buildpack_language: Some("my_language".to_string()),
+                clone_depth: None,
+                single_branch: false,
             },
             image: Image {
                 application_id: "my_application_id".to_string(),
@@ -239,19 +263,30 @@ pub fn test_application(test_kube: &dyn Kubernetes) -> Application<AWSType> {
                 commit_id: "my_image_commit".to_string(),
                 registry_name: "my_image_registry_name".to_string(),
                 registry_docker_json_config: Some("my_image_registry_docker_json_config".to_string()),
+                registry_client_certificate: None,
                 registry_url: Url::parse("https://my_image_registry_url.com").unwrap(),
                 repository_name: "my_image_repository_name".to_string(),
             },
             environment_variables: BTreeMap::new(),
             disable_cache: false,
             timeout: Duration::from_secs(42),
+            build_args_file: None,
+            sensitive_build_args: BTreeSet::new(),
+            build_secrets: vec![],
         },
         vec![test_storage()],
         vec![test_env_var()],
         ApplicationAdvancedSettings {
             deployment_delay_start_time_sec: 0,
             deployment_custom_domain_check_enabled: true,
+            deployment_strategy: AdvancedSettingsDeploymentStrategy::RollingUpdate,
+            deployment_startup_grace_period_sec: 600,
             build_timeout_max_sec: 2,
+            build_arguments_file: None,
+            build_sensitive_arguments: vec![],
+            build_secrets: vec![],
+            build_git_clone_depth: None,
+            build_git_single_branch: false,
             network_ingress_proxy_body_size_mb: 3,
             network_ingress_cors_enable: true,
             network_ingress_cors_allow_origin: "my_network_ingress_cors_allow_origin".to_string(),
@@ -280,6 +315,9 @@ pub fn test_application(test_kube: &dyn Kubernetes) -> Application<AWSType> {
             liveness_probe_success_threshold: 24,
             liveness_probe_failure_threshold: 25,
             hpa_cpu_average_utilization_percent: 31,
+            hpa_scale_to_zero_enabled: false,
+            hpa_scale_to_zero_idle_period_seconds: 900,
+            hpa_required: false,
         },
         AwsAppExtraSettings {},
         |transmitter| test_kube.context().get_event_details(transmitter),
@@ -374,6 +412,7 @@ pub fn test_managed_database(test_kube: &dyn Kubernetes) -> Database<AWSType, Ma
             encrypt_disk: true,
             activate_high_availability: true,
             activate_backups: true,
+            connection_pooler: None,
             publicly_accessible: true,
         },
         |transmitter| test_kube.context().get_event_details(transmitter),
@@ -407,6 +446,7 @@ pub fn test_container_database(test_kube: &dyn Kubernetes) -> Database<AWSType,
             encrypt_disk: true,
             activate_high_availability: true,
             activate_backups: true,
+            connection_pooler: None,
             publicly_accessible: true,
         },
         |transmitter| test_kube.context().get_event_details(transmitter),
@@ -488,9 +528,13 @@ fn test_job(test_kube: &dyn Kubernetes) -> Job<AWSType> {
 }
 
 fn infra_ctx(test_kube: &dyn Kubernetes) -> InfrastructureContext {
+    infra_ctx_with_logger(test_kube, logger())
+}
+
+fn infra_ctx_with_logger(test_kube: &dyn Kubernetes, logger: Box<dyn Logger>) -> InfrastructureContext {
     AWS::docker_cr_engine(
         test_kube.context(),
-        logger(),
+        logger,
         test_kube.region(),
         test_kube.kind(),
         test_kube.version().to_string(),
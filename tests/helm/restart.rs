@@ -0,0 +1,89 @@
+use crate::helm::{deployment_target, infra_ctx, test_environment, test_kubernetes};
+use qovery_engine::cloud_provider::service::Action;
+use qovery_engine::cloud_provider::DeploymentTarget;
+use qovery_engine::deployment_action::DeploymentAction;
+use qovery_engine::errors::EngineError;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct RecordingAction {
+    calls: Mutex<Vec<&'static str>>,
+}
+
+impl RecordingAction {
+    fn record(&self, call: &'static str) -> Result<(), EngineError> {
+        self.calls.lock().unwrap().push(call);
+        Ok(())
+    }
+}
+
+impl DeploymentAction for RecordingAction {
+    fn on_create(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.record("create")
+    }
+
+    fn on_pause(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.record("pause")
+    }
+
+    fn on_delete(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.record("delete")
+    }
+}
+
+#[derive(Default)]
+struct InPlaceRestartAction {
+    inner: RecordingAction,
+}
+
+impl DeploymentAction for InPlaceRestartAction {
+    fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.inner.on_create(target)
+    }
+
+    fn on_pause(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.inner.on_pause(target)
+    }
+
+    fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.inner.on_delete(target)
+    }
+
+    fn on_restart(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+        self.inner.record("restart")
+    }
+}
+
+#[test]
+fn test_restart_defaults_to_pause_then_create() {
+    // setup:
+    let test_kube = test_kubernetes();
+    let test_env = test_environment(test_kube.as_ref());
+    let infra_ctx = infra_ctx(test_kube.as_ref());
+    let target = deployment_target(test_kube.as_ref(), &test_env, &infra_ctx);
+    let action = RecordingAction::default();
+
+    // execute:
+    let result = action.exec_action(&target, Action::Restart);
+
+    // verify:
+    assert!(result.is_ok());
+    assert_eq!(vec!["pause", "create"], *action.calls.lock().unwrap());
+}
+
+#[test]
+fn test_restart_is_routed_to_overridden_hook() {
+    // setup:
+    let test_kube = test_kubernetes();
+    let test_env = test_environment(test_kube.as_ref());
+    let infra_ctx = infra_ctx(test_kube.as_ref());
+    let target = deployment_target(test_kube.as_ref(), &test_env, &infra_ctx);
+    let action = InPlaceRestartAction::default();
+
+    // execute:
+    let result = action.exec_action(&target, Action::Restart);
+
+    // verify:
+    assert!(result.is_ok());
+    assert_eq!(vec!["restart"], *action.inner.calls.lock().unwrap());
+}
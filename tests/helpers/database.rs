@@ -320,6 +320,7 @@ pub fn environment_3_apps_3_databases(
                 encrypt_disk: true,
                 activate_high_availability: false,
                 activate_backups: false,
+                connection_pooler: None,
                 publicly_accessible: false,
                 mode: CONTAINER,
             },
@@ -343,6 +344,7 @@ pub fn environment_3_apps_3_databases(
                 encrypt_disk: true,
                 activate_high_availability: false,
                 activate_backups: false,
+                connection_pooler: None,
                 publicly_accessible: false,
                 mode: CONTAINER,
             },
@@ -366,6 +368,7 @@ pub fn environment_3_apps_3_databases(
                 encrypt_disk: true,
                 activate_high_availability: false,
                 activate_backups: false,
+                connection_pooler: None,
                 publicly_accessible: false,
                 mode: CONTAINER,
             },
@@ -551,6 +554,7 @@ pub fn test_db(
         encrypt_disk: true,
         activate_high_availability: false,
         activate_backups: false,
+        connection_pooler: None,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
     };
@@ -830,6 +834,7 @@ pub fn test_pause_managed_db(
         encrypt_disk: true,
         activate_high_availability: false,
         activate_backups: false,
+        connection_pooler: None,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
     };
@@ -1073,6 +1078,7 @@ pub fn test_db_on_upgrade(
         encrypt_disk: true,
         activate_high_availability: false,
         activate_backups: false,
+        connection_pooler: None,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
     };
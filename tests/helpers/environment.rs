@@ -141,6 +141,7 @@ pub fn environment_2_app_2_routers_1_psql(
             encrypt_disk: true,
             activate_high_availability: false,
             activate_backups: false,
+            connection_pooler: None,
             publicly_accessible: false,
             mode: CONTAINER,
         }],
@@ -262,11 +262,12 @@ pub fn clean_environments(
 
     // delete images created in registry
     let registry_url = container_registry_client.registry_info();
+    let build_secrets_dir = tempfile::tempdir().expect("cannot create temp dir");
     for env in environments.iter() {
         for build in env
             .applications
             .iter()
-            .map(|a| a.to_build(registry_url))
+            .filter_map(|a| a.to_build(registry_url, build_secrets_dir.path()).ok())
             .collect::<Vec<Build>>()
         {
             let _ = container_registry_client.delete_image(&build.image);
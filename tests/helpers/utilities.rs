@@ -28,7 +28,11 @@ use crate::helpers::scaleway::{
     SCW_SELF_HOSTED_DATABASE_INSTANCE_TYPE,
 };
 use hashicorp_vault;
+use k8s_openapi::api::core::v1::Namespace;
+use kube::api::DeleteParams;
+use kube::Api;
 use qovery_engine::build_platform::local_docker::LocalDocker;
+use qovery_engine::cloud_provider::kubernetes::kube_create_or_update_namespace;
 use qovery_engine::cloud_provider::Kind;
 use qovery_engine::cmd;
 use qovery_engine::constants::{
@@ -53,7 +57,7 @@ use qovery_engine::io_models::QoveryIdentifier;
 use qovery_engine::logger::{Logger, StdIoLogger};
 use qovery_engine::models::scaleway::ScwZone;
 use qovery_engine::runtime::block_on;
-use qovery_engine::utilities::to_short_id;
+use qovery_engine::utilities::{create_kube_client, to_short_id};
 use time::Instant;
 use tracing_subscriber::EnvFilter;
 use url::Url;
@@ -860,6 +864,76 @@ pub fn get_pods(
     )
 }
 
+/// Namespace living as long as the guard: created on construction and deleted on drop, so it is also
+/// cleaned up when a test panics. Drop never panics (it would abort an already unwinding test),
+/// deletion errors are only logged.
+pub struct ScopedNamespace {
+    name: String,
+    kube: kube::Client,
+}
+
+impl ScopedNamespace {
+    pub fn new(
+        context: Context,
+        provider_kind: Kind,
+        namespace_name: &str,
+        secrets: FuncTestsSecrets,
+    ) -> Result<ScopedNamespace, CommandError> {
+        // same ttl label the engine sets, so pleco still collects the namespace if the test process is killed
+        let labels = context
+            .resource_expiration_in_seconds()
+            .map(|ttl| BTreeMap::from([("ttl".to_string(), ttl.to_string())]))
+            .unwrap_or_default();
+        let kubernetes_config = kubernetes_config_path(context, provider_kind.clone(), "/tmp", secrets.clone())?;
+        let envs: Vec<(String, String)> = get_cloud_provider_credentials(provider_kind, &secrets)
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let kube = block_on(create_kube_client(kubernetes_config, &envs))
+            .map_err(|e| CommandError::new("Cannot create kube client".to_string(), Some(e.to_string()), None))?;
+
+        block_on(kube_create_or_update_namespace(&kube, namespace_name, labels, BTreeMap::new())).map_err(|e| {
+            CommandError::new(format!("Cannot create namespace {}", namespace_name), Some(e.to_string()), None)
+        })?;
+
+        Ok(ScopedNamespace {
+            name: namespace_name.to_string(),
+            kube,
+        })
+    }
+
+    /// Guard on the namespace the engine deploys the given environment in.
+    pub fn for_environment(
+        context: Context,
+        provider_kind: Kind,
+        environment: &EnvironmentRequest,
+        secrets: FuncTestsSecrets,
+    ) -> Result<ScopedNamespace, CommandError> {
+        let namespace_name = format!(
+            "{}-{}",
+            to_short_id(&environment.project_long_id),
+            to_short_id(&environment.long_id),
+        );
+        ScopedNamespace::new(context, provider_kind, &namespace_name, secrets)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for ScopedNamespace {
+    fn drop(&mut self) {
+        let namespaces: Api<Namespace> = Api::all(self.kube.clone());
+        match block_on(namespaces.delete(&self.name, &DeleteParams::default())) {
+            Ok(_) => info!("namespace {} deleted", self.name),
+            // already deleted along with the environment
+            Err(kube::Error::Api(api_err)) if api_err.code == 404 => {}
+            Err(e) => warn!("cannot delete namespace {}, error: {}", self.name, e),
+        }
+    }
+}
+
 pub fn execution_id() -> String {
     Utc::now()
         .to_rfc3339()
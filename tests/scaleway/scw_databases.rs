@@ -1,6 +1,6 @@
 use crate::helpers::utilities::{
     context_for_resource, engine_run_test, generate_id, generate_password, get_pods, get_svc_name, init,
-    is_pod_restarted_env, logger, FuncTestsSecrets,
+    is_pod_restarted_env, logger, FuncTestsSecrets, ScopedNamespace,
 };
 use ::function_name::named;
 use qovery_engine::cloud_provider::{Kind as ProviderKind, Kind};
@@ -119,6 +119,10 @@ fn deploy_an_environment_with_db_and_pause_it() {
             SCW_SELF_HOSTED_DATABASE_DISK_TYPE,
             Kind::Scw,
         );
+        // namespace is deleted even if an assertion below fails
+        let _namespace =
+            ScopedNamespace::for_environment(context.clone(), ProviderKind::Scw, &environment, secrets.clone())
+                .expect("cannot create test namespace");
 
         let mut environment_delete = environment.clone();
         environment_delete.action = Action::Delete;
@@ -297,6 +301,7 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
             encrypt_disk: false,
             activate_high_availability: false,
             activate_backups: false,
+            connection_pooler: None,
             publicly_accessible: false,
         }];
         environment.applications = environment